@@ -0,0 +1,525 @@
+//! `odin-cli governance report`: a governance posture snapshot for ops reviews, rendered as
+//! JSON or Markdown. Each section is collected only when its source is given; the others are
+//! reported as not collected rather than empty.
+
+use std::cmp::Reverse;
+use std::fs;
+use std::path::Path;
+
+use odin_audit::chain::ChainedRecord;
+use odin_audit::taxonomy::{GOVERNANCE_MANIFEST_DENIED, SECRET_DENIED};
+use odin_audit::{AuditError, AuditRecord};
+use odin_governance::plugins::PluginPermissionRegistry;
+use odin_plugin_protocol::{PluginManifest, PluginPermissionEnvelope, SkillRecord, TrustLevel};
+use odin_policy_engine::elevation::Elevation;
+use odin_policy_engine::file::PolicyDocument;
+use serde_json::{json, Value};
+
+use crate::trust_level_as_str;
+
+const TRUST_LEVELS: [TrustLevel; 3] = [
+    TrustLevel::Trusted,
+    TrustLevel::Caution,
+    TrustLevel::Untrusted,
+];
+
+struct EnvelopeRow {
+    plugin: String,
+    project: Option<String>,
+    trust_level: &'static str,
+    permissions: Vec<String>,
+}
+
+struct Denial {
+    ts_unix: u64,
+    event_type: String,
+    plugin: String,
+    capability: String,
+    project: Option<String>,
+    reason_code: Option<String>,
+}
+
+struct ExpiringGrant {
+    source: &'static str,
+    plugin: String,
+    capabilities: Vec<String>,
+    projects: Vec<String>,
+    expires_at_unix: u64,
+}
+
+struct UnsignedPlugin {
+    name: String,
+    signing_required: bool,
+}
+
+pub struct GovernanceReport {
+    generated_at_unix: u64,
+    since_unix: Option<u64>,
+    denial_limit: usize,
+    expiring_within_secs: u64,
+    skills: Option<Vec<(String, &'static str)>>,
+    envelopes: Option<Vec<EnvelopeRow>>,
+    denials: Option<Vec<Denial>>,
+    grants: Option<Vec<ExpiringGrant>>,
+    unsigned_plugins: Option<Vec<UnsignedPlugin>>,
+}
+
+impl GovernanceReport {
+    pub fn new(generated_at_unix: u64) -> Self {
+        Self {
+            generated_at_unix,
+            since_unix: None,
+            denial_limit: 20,
+            expiring_within_secs: 7 * 86_400,
+            skills: None,
+            envelopes: None,
+            denials: None,
+            grants: None,
+            unsigned_plugins: None,
+        }
+    }
+
+    /// Ignores denials recorded before `since_unix`.
+    pub fn with_since(mut self, since_unix: u64) -> Self {
+        self.since_unix = Some(since_unix);
+        self
+    }
+
+    /// Keeps only the newest `limit` denials (default 20).
+    pub fn with_denial_limit(mut self, limit: usize) -> Self {
+        self.denial_limit = limit;
+        self
+    }
+
+    /// Lists time-boxed grants lapsing within `days` of the report time (default 7).
+    pub fn with_expiring_within_days(mut self, days: u64) -> Self {
+        self.expiring_within_secs = days.saturating_mul(86_400);
+        self
+    }
+
+    pub fn with_skills(mut self, skills: &[SkillRecord]) -> Self {
+        self.skills = Some(
+            skills
+                .iter()
+                .map(|skill| (skill.name.clone(), trust_level_as_str(&skill.trust_level)))
+                .collect(),
+        );
+        self
+    }
+
+    pub fn with_envelopes(mut self, registry: &PluginPermissionRegistry) -> Self {
+        let row = |project: Option<&str>, envelope: &PluginPermissionEnvelope| EnvelopeRow {
+            plugin: envelope.plugin.clone(),
+            project: project.map(str::to_string),
+            trust_level: trust_level_as_str(&envelope.trust_level),
+            permissions: envelope
+                .permissions
+                .iter()
+                .map(|permission| match permission.scope.as_slice() {
+                    [] => permission.id.clone(),
+                    scope => format!("{} ({})", permission.id, scope.join(", ")),
+                })
+                .collect(),
+        };
+        let mut rows: Vec<EnvelopeRow> = registry.envelopes().map(|e| row(None, e)).collect();
+        rows.extend(
+            registry
+                .project_envelopes()
+                .map(|(project, envelope)| row(Some(project), envelope)),
+        );
+        self.envelopes = Some(rows);
+        self
+    }
+
+    /// Collects denials from a `FileAuditSink` log and its rotated files: denied policy
+    /// decisions, manifest denials and secret denials. Unparseable lines are skipped.
+    pub fn with_audit_log(mut self, path: &Path) -> Result<Self, AuditError> {
+        let mut denials = Vec::new();
+        for file in odin_audit::file::log_files(path)? {
+            let contents = fs::read_to_string(&file).map_err(|e| {
+                AuditError::Write(format!("failed reading {}: {e}", file.display()))
+            })?;
+            for line in contents.lines() {
+                if let Ok(entry) = serde_json::from_str::<ChainedRecord>(line) {
+                    denials.extend(self.denial(entry.record));
+                }
+            }
+        }
+        self.denials = Some(denials);
+        Ok(self)
+    }
+
+    /// Time-boxed grants from a policy file.
+    pub fn with_policy(mut self, policy: &PolicyDocument) -> Self {
+        let grants = self.grants.get_or_insert_with(Vec::new);
+        for grant in &policy.grants {
+            if let Some(expires_at_unix) = grant.expires_at_unix {
+                grants.push(ExpiringGrant {
+                    source: "policy",
+                    plugin: grant.plugin.clone(),
+                    capabilities: grant.capabilities.clone(),
+                    projects: grant.projects.clone(),
+                    expires_at_unix,
+                });
+            }
+        }
+        self
+    }
+
+    /// Temporary elevations from the elevation overlay.
+    pub fn with_elevations(mut self, elevations: &[Elevation]) -> Self {
+        let grants = self.grants.get_or_insert_with(Vec::new);
+        grants.extend(elevations.iter().map(|elevation| ExpiringGrant {
+            source: "elevation",
+            plugin: elevation.plugin.clone(),
+            capabilities: vec![elevation.capability.clone()],
+            projects: vec![elevation.project.clone()],
+            expires_at_unix: elevation.expires_at_unix,
+        }));
+        self
+    }
+
+    /// Installed plugins whose manifest carries no signature.
+    pub fn with_plugins(mut self, manifests: &[PluginManifest]) -> Self {
+        let mut unsigned: Vec<UnsignedPlugin> = manifests
+            .iter()
+            .filter(|manifest| {
+                manifest
+                    .signing
+                    .as_ref()
+                    .and_then(|signing| signing.signature.as_deref())
+                    .is_none_or(|signature| signature.trim().is_empty())
+            })
+            .map(|manifest| UnsignedPlugin {
+                name: manifest.plugin.name.clone(),
+                signing_required: manifest
+                    .signing
+                    .as_ref()
+                    .and_then(|signing| signing.required)
+                    .unwrap_or(false),
+            })
+            .collect();
+        unsigned.sort_by(|a, b| a.name.cmp(&b.name));
+        self.unsigned_plugins = Some(unsigned);
+        self
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "generated_at_unix": self.generated_at_unix,
+            "expiring_within_days": self.expiring_within_secs / 86_400,
+            "skills": self.skills_by_trust().map(|levels| {
+                levels
+                    .into_iter()
+                    .map(|(level, names)| (level.to_string(), json!(names)))
+                    .collect::<serde_json::Map<_, _>>()
+            }),
+            "envelopes": self.envelopes.as_ref().map(|rows| {
+                rows.iter()
+                    .map(|row| json!({
+                        "plugin": row.plugin,
+                        "project": row.project,
+                        "trust_level": row.trust_level,
+                        "permissions": row.permissions,
+                    }))
+                    .collect::<Vec<_>>()
+            }),
+            "recent_denials": self.recent_denials().map(|denials| {
+                denials
+                    .into_iter()
+                    .map(|denial| json!({
+                        "ts_unix": denial.ts_unix,
+                        "event_type": denial.event_type,
+                        "plugin": denial.plugin,
+                        "capability": denial.capability,
+                        "project": denial.project,
+                        "reason_code": denial.reason_code,
+                    }))
+                    .collect::<Vec<_>>()
+            }),
+            "expiring_grants": self.expiring_grants().map(|grants| {
+                grants
+                    .into_iter()
+                    .map(|grant| json!({
+                        "source": grant.source,
+                        "plugin": grant.plugin,
+                        "capabilities": grant.capabilities,
+                        "projects": grant.projects,
+                        "expires_at_unix": grant.expires_at_unix,
+                    }))
+                    .collect::<Vec<_>>()
+            }),
+            "unsigned_plugins": self.unsigned_plugins.as_ref().map(|plugins| {
+                plugins
+                    .iter()
+                    .map(|plugin| json!({
+                        "plugin": plugin.name,
+                        "signing_required": plugin.signing_required,
+                    }))
+                    .collect::<Vec<_>>()
+            }),
+        })
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!(
+            "# Governance report\n\nGenerated at {} (unix).\n",
+            self.generated_at_unix
+        );
+
+        section(
+            &mut out,
+            "Skills by trust level",
+            "--registry",
+            self.skills_by_trust().map(|levels| {
+                let rows = levels
+                    .into_iter()
+                    .map(|(level, names)| vec![level.to_string(), names.join(", ")])
+                    .collect();
+                (vec!["Trust level", "Skills"], rows)
+            }),
+        );
+        section(
+            &mut out,
+            "Plugin permission envelopes",
+            "--permissions",
+            self.envelopes.as_ref().map(|envelopes| {
+                let rows = envelopes
+                    .iter()
+                    .map(|row| {
+                        vec![
+                            row.plugin.clone(),
+                            row.project.clone().unwrap_or_else(|| "*".to_string()),
+                            row.trust_level.to_string(),
+                            row.permissions.join("; "),
+                        ]
+                    })
+                    .collect();
+                (
+                    vec!["Plugin", "Project", "Trust level", "Permissions"],
+                    rows,
+                )
+            }),
+        );
+        section(
+            &mut out,
+            "Recent denials",
+            "--audit-log",
+            self.recent_denials().map(|denials| {
+                let rows = denials
+                    .into_iter()
+                    .map(|denial| {
+                        vec![
+                            denial.ts_unix.to_string(),
+                            denial.event_type.clone(),
+                            denial.plugin.clone(),
+                            denial.capability.clone(),
+                            denial.project.clone().unwrap_or_default(),
+                            denial.reason_code.clone().unwrap_or_default(),
+                        ]
+                    })
+                    .collect();
+                let header = vec![
+                    "Time (unix)",
+                    "Event",
+                    "Plugin",
+                    "Capability",
+                    "Project",
+                    "Reason",
+                ];
+                (header, rows)
+            }),
+        );
+        section(
+            &mut out,
+            &format!(
+                "Grants expiring within {} days",
+                self.expiring_within_secs / 86_400
+            ),
+            "--policy-file or --elevations",
+            self.expiring_grants().map(|grants| {
+                let rows = grants
+                    .into_iter()
+                    .map(|grant| {
+                        vec![
+                            grant.source.to_string(),
+                            grant.plugin.clone(),
+                            grant.capabilities.join(", "),
+                            match grant.projects.as_slice() {
+                                [] => "*".to_string(),
+                                projects => projects.join(", "),
+                            },
+                            grant.expires_at_unix.to_string(),
+                        ]
+                    })
+                    .collect();
+                let header = vec![
+                    "Source",
+                    "Plugin",
+                    "Capabilities",
+                    "Projects",
+                    "Expires (unix)",
+                ];
+                (header, rows)
+            }),
+        );
+        section(
+            &mut out,
+            "Unsigned plugins",
+            "--plugins-dir",
+            self.unsigned_plugins.as_ref().map(|plugins| {
+                let rows = plugins
+                    .iter()
+                    .map(|plugin| {
+                        let required = if plugin.signing_required { "yes" } else { "no" };
+                        vec![plugin.name.clone(), required.to_string()]
+                    })
+                    .collect();
+                (vec!["Plugin", "Signing required"], rows)
+            }),
+        );
+        out
+    }
+
+    fn denial(&self, record: AuditRecord) -> Option<Denial> {
+        let field = |name: &str| record.metadata.get(name).and_then(Value::as_str);
+        let denied = match record.event_type.as_str() {
+            "policy.decision" => field("decision") == Some("deny"),
+            GOVERNANCE_MANIFEST_DENIED | SECRET_DENIED => true,
+            _ => false,
+        };
+        if !denied || self.since_unix.is_some_and(|since| record.ts_unix < since) {
+            return None;
+        }
+        Some(Denial {
+            ts_unix: record.ts_unix,
+            plugin: field("plugin").unwrap_or_default().to_string(),
+            capability: field("capability").unwrap_or_default().to_string(),
+            reason_code: field("reason_code").map(str::to_string),
+            event_type: record.event_type,
+            project: record.project,
+        })
+    }
+
+    fn skills_by_trust(&self) -> Option<Vec<(&'static str, Vec<&str>)>> {
+        let skills = self.skills.as_ref()?;
+        Some(
+            TRUST_LEVELS
+                .iter()
+                .map(|level| {
+                    let level = trust_level_as_str(level);
+                    let mut names: Vec<&str> = skills
+                        .iter()
+                        .filter(|(_, trust)| *trust == level)
+                        .map(|(name, _)| name.as_str())
+                        .collect();
+                    names.sort_unstable();
+                    (level, names)
+                })
+                .collect(),
+        )
+    }
+
+    /// Newest first, at most `denial_limit`.
+    fn recent_denials(&self) -> Option<Vec<&Denial>> {
+        let mut denials: Vec<&Denial> = self.denials.as_ref()?.iter().collect();
+        denials.sort_by_key(|denial| Reverse(denial.ts_unix));
+        denials.truncate(self.denial_limit);
+        Some(denials)
+    }
+
+    /// Grants still in force that lapse within the window, soonest first.
+    fn expiring_grants(&self) -> Option<Vec<&ExpiringGrant>> {
+        let deadline = self
+            .generated_at_unix
+            .saturating_add(self.expiring_within_secs);
+        let mut grants: Vec<&ExpiringGrant> = self
+            .grants
+            .as_ref()?
+            .iter()
+            .filter(|grant| {
+                grant.expires_at_unix > self.generated_at_unix && grant.expires_at_unix <= deadline
+            })
+            .collect();
+        grants.sort_by_key(|grant| grant.expires_at_unix);
+        Some(grants)
+    }
+}
+
+type Table = (Vec<&'static str>, Vec<Vec<String>>);
+
+fn section(out: &mut String, title: &str, source: &str, table: Option<Table>) {
+    out.push_str(&format!("\n## {title}\n\n"));
+    let Some((header, rows)) = table else {
+        out.push_str(&format!("_Not collected; pass {source}._\n"));
+        return;
+    };
+    if rows.is_empty() {
+        out.push_str("_None._\n");
+        return;
+    }
+    out.push_str(&format!("| {} |\n", header.join(" | ")));
+    out.push_str(&format!("|{}\n", "---|".repeat(header.len())));
+    for row in rows {
+        let cells: Vec<String> = row.iter().map(|cell| cell.replace('|', "\\|")).collect();
+        out.push_str(&format!("| {} |\n", cells.join(" | ")));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(ts_unix: u64, event_type: &str, metadata: Value) -> AuditRecord {
+        AuditRecord {
+            ts_unix,
+            event_type: event_type.to_string(),
+            severity: Default::default(),
+            request_id: None,
+            task_id: None,
+            project: Some("demo".to_string()),
+            trace_id: None,
+            metadata,
+        }
+    }
+
+    #[test]
+    fn keeps_only_denials_after_since() {
+        let report = GovernanceReport::new(1_000).with_since(100);
+        let deny =
+            json!({ "plugin": "huginn", "capability": "browser.observe", "decision": "deny" });
+        let allow =
+            json!({ "plugin": "huginn", "capability": "browser.observe", "decision": "allow" });
+        assert!(report
+            .denial(record(150, "policy.decision", deny.clone()))
+            .is_some());
+        assert!(report.denial(record(50, "policy.decision", deny)).is_none());
+        assert!(report
+            .denial(record(150, "policy.decision", allow))
+            .is_none());
+        let denial = report
+            .denial(record(
+                150,
+                GOVERNANCE_MANIFEST_DENIED,
+                json!({ "plugin": "huginn", "capability": "repo.push", "reason_code": "manifest_capability_undeclared" }),
+            ))
+            .expect("manifest denial");
+        assert_eq!(
+            denial.reason_code.as_deref(),
+            Some("manifest_capability_undeclared")
+        );
+    }
+
+    #[test]
+    fn markdown_marks_uncollected_sections_and_escapes_cells() {
+        let markdown = GovernanceReport::new(0)
+            .with_skills(&[SkillRecord {
+                trust_level: TrustLevel::Caution,
+                ..SkillRecord::default_for("a|b")
+            }])
+            .to_markdown();
+        assert!(markdown.contains("| caution | a\\|b |\n"));
+        assert!(markdown.contains("| trusted |  |\n"));
+        assert!(markdown.contains("_Not collected; pass --permissions._"));
+    }
+}
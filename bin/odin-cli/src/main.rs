@@ -1,33 +1,92 @@
+mod governance_report;
+mod policy_diff;
+mod policy_init;
+
 use std::collections::HashSet;
 use std::env;
 use std::ffi::OsString;
 use std::fs;
+use std::io;
 use std::path::{Path, PathBuf};
 use std::process;
-use std::thread;
+use std::sync::Arc;
 use std::time::Duration;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use anyhow::{anyhow, Context};
 use clap::{Parser, Subcommand, ValueEnum};
-use odin_audit::NoopAuditSink;
+use odin_audit::analytics::CapabilityUsageReport;
+use odin_audit::chain::{verify_log, VerifyRange};
+use odin_audit::file::FileAuditSink;
+use odin_audit::redact::Redactor;
+use odin_audit::{taxonomy, AuditRecord, AuditSink, EnrichedAuditSink, NoopAuditSink, Severity};
 use odin_compat_bash::{
     BashBackendStateAdapter, BashFailoverAdapter, BashTaskIngressAdapter, LegacyScriptPaths,
 };
+use odin_core_runtime::approvals::{ApprovalStore, FileApprovalStore};
+use odin_core_runtime::cancel::CancellationToken;
+use odin_core_runtime::checkpoint::{FileCheckpointStore, ReconcilePolicy};
+use odin_core_runtime::dlq::FileDeadLetterQueue;
+use odin_core_runtime::health::{check_plugin, PluginHealth};
+use odin_core_runtime::http::{HttpActionExecutor, HttpCapability};
+use odin_core_runtime::metrics::{InMemoryRuntimeMetrics, RuntimeMetrics};
+use odin_core_runtime::retention::{run_task_retention, TaskRetentionConfig};
+use odin_core_runtime::revocation::{CapabilityRevocation, RevocationList, REVOCATIONS_FILE};
+use odin_core_runtime::router::{PluginTaskHandler, TaskRouter};
+use odin_core_runtime::secrets::PolicyGatedSecretStore;
+use odin_core_runtime::selfcheck::{run_selfcheck, SelfCheckConfig};
+use odin_core_runtime::snapshot::{CopySnapshotter, Snapshotter};
 use odin_core_runtime::{
-    BackendState, DryRunExecutor, ExternalProcessPluginRunner, OrchestratorRuntime, TaskIngress,
+    aggregate_warnings, ActionExecutor, BackendState, DryRunExecutor, EntrypointPolicy,
+    ExternalProcessPluginRunner, OrchestratorRuntime, RuntimeError, TaskBatchResult, TaskIngress,
+    DEFAULT_TASK_BATCH_CONCURRENCY,
+};
+use odin_governance::ack_ledger::{AckLedger, AckLedgerEntry};
+use odin_governance::acks::{
+    skill_ack_digest, verify_ack_file, AckKind, AckTrustStore, VerifiedAck,
+};
+use odin_governance::diff::{diff_skill_registries, GovernanceChange, RiskDelta};
+use odin_governance::duties::SeparationOfDuties;
+use odin_governance::egress::EgressPolicy;
+use odin_governance::envelopes::{
+    load_permission_registry, revoke_capability, save_permission_registry, EnvelopeFileError,
+    PERMISSIONS_FILE,
+};
+use odin_governance::expiry::{demote_expired, overdue_skills};
+use odin_governance::import::{
+    Ack, ImportGateError, InstallGate, InstallGateStatus, SkillImportCandidate,
 };
-use odin_governance::import::{evaluate_install, Ack, InstallGateStatus, SkillImportCandidate};
+use odin_governance::pins::{PinCheck, PinStatus, PinVerifier};
 use odin_governance::plugins::{
-    huginn_policy_from_envelope, Action as HuginnAction, PermissionDecision as HuginnDecision,
+    command_scope_probe, huginn_policy_from_envelope, Action as HuginnAction,
+    PermissionDecision as HuginnDecision, PluginPermissionRegistry,
+};
+use odin_governance::promotion::{
+    change_trust, trust_rank, PromotionError, PromotionEvidence, PromotionPolicy,
+    PromotionRequirement,
+};
+use odin_governance::recommend::{LeastPrivilegeAnalyzer, ObservedUse};
+use odin_governance::risk_scan::{RiskFinding, RiskScanner};
+use odin_governance::scopes::{load_scope_templates, SCOPE_TEMPLATES_FILE};
+use odin_governance::skill_dir::scan_skill_dir_with;
+use odin_governance::skill_signature::SkillSignerTrustStore;
+use odin_governance::skills::{
+    add_skill, load_global_registry, load_project_registry, load_user_registry,
+    SkillRegistryWriteError,
 };
-use odin_governance::risk_scan::{RiskCategory, RiskFinding};
-use odin_governance::skills::{load_global_registry, load_project_registry, load_user_registry};
 use odin_plugin_protocol::{
-    ActionRequest, CapabilityRequest, DelegationCapability, PluginPermissionEnvelope, RiskTier,
-    SkillRecord, SkillScope, TrustLevel,
+    ActionRequest, CapabilityRequest, DelegationCapability, PluginClass, PluginPermissionEnvelope,
+    RiskTier, SkillRecord, SkillScope, TrustLevel,
 };
-use odin_policy_engine::StaticPolicyEngine;
+use odin_policy_engine::bundle::BundleVerifier;
+use odin_policy_engine::elevation::{ElevationGrant, ElevationOverlay};
+use odin_policy_engine::file::{FilePolicyEngine, PolicyDocument};
+use odin_policy_engine::simulate::simulate;
+use odin_policy_engine::{describe_decision, PolicyEngine, StaticPolicyEngine};
+use odin_secrets::cache::{CachedSecretStore, SecretCacheConfig};
+use odin_secrets::config::SecretBackendConfig;
+use odin_secrets::rotation::RotationPolicy;
+use odin_secrets::{SecretHandle, SecretStore};
 use serde_json::{json, Value};
 
 #[derive(Clone, Debug)]
@@ -37,7 +96,17 @@ struct CliConfig {
     legacy_odin_dir: PathBuf,
     plugins_root: PathBuf,
     task_file: Option<PathBuf>,
+    task_dir: Option<PathBuf>,
     run_once: bool,
+    task_queue_dir: Option<PathBuf>,
+    task_retention_days: u64,
+    task_retention_max_bytes: Option<u64>,
+    task_archive_daily: bool,
+    reconcile_policy: ReconcilePolicy,
+    policy_file: Option<PathBuf>,
+    policy_bundle: Option<PathBuf>,
+    policy_bundle_key: Option<PathBuf>,
+    audit_log: Option<PathBuf>,
 }
 
 impl Default for CliConfig {
@@ -48,7 +117,17 @@ impl Default for CliConfig {
             legacy_odin_dir: PathBuf::from("/var/odin"),
             plugins_root: PathBuf::from("examples/private-plugins"),
             task_file: None,
+            task_dir: None,
             run_once: false,
+            task_queue_dir: None,
+            task_retention_days: 7,
+            task_retention_max_bytes: None,
+            task_archive_daily: false,
+            reconcile_policy: ReconcilePolicy::Requeue,
+            policy_file: None,
+            policy_bundle: None,
+            policy_bundle_key: None,
+            audit_log: None,
         }
     }
 }
@@ -67,8 +146,45 @@ struct Cli {
     plugins_root: PathBuf,
     #[arg(long, global = true)]
     task_file: Option<PathBuf>,
+    /// Inbox directory whose `*.json` tasks are processed as one batch, then moved to
+    /// `done/` or `failed/` beneath it.
+    #[arg(long, global = true)]
+    task_dir: Option<PathBuf>,
     #[arg(long, global = true)]
     run_once: bool,
+    /// Queue directory whose `done/` and `failed/` task archives are pruned while running.
+    #[arg(long, global = true)]
+    task_queue_dir: Option<PathBuf>,
+    #[arg(long, default_value_t = 7, global = true)]
+    task_retention_days: u64,
+    #[arg(long, global = true)]
+    task_retention_max_bytes: Option<u64>,
+    /// Roll aged task files into daily tarballs instead of deleting them.
+    #[arg(long, global = true)]
+    task_archive_daily: bool,
+    /// What to do at startup with tasks interrupted by the last shutdown or crash:
+    /// `resume`, `requeue` or `quarantine`.
+    #[arg(long = "reconcile", default_value = "requeue", value_parser = parse_reconcile_policy, global = true)]
+    reconcile_policy: ReconcilePolicy,
+    /// YAML or JSON policy document replacing the built-in grants; reloaded when it changes.
+    #[arg(long, global = true)]
+    policy_file: Option<PathBuf>,
+    /// Signed policy bundle directory used instead of --policy-file; unsigned or rolled-back
+    /// bundles are refused.
+    #[arg(
+        long,
+        global = true,
+        conflicts_with = "policy_file",
+        requires = "policy_bundle_key"
+    )]
+    policy_bundle: Option<PathBuf>,
+    /// Minisign public key (`.pub` file) trusted to sign policy bundles.
+    #[arg(long, global = true)]
+    policy_bundle_key: Option<PathBuf>,
+    /// JSON-lines audit log, rotated at 64 MiB and scrubbed of secrets; audit records are
+    /// discarded when unset.
+    #[arg(long, global = true)]
+    audit_log: Option<PathBuf>,
     #[command(subcommand)]
     command: Option<CliCommand>,
 }
@@ -112,11 +228,61 @@ enum CliCommand {
         #[command(subcommand)]
         command: SkillCommand,
     },
+    /// Ping installed plugins (or one with --plugin) and report their health
+    Doctor {
+        #[arg(long)]
+        plugin: Option<String>,
+    },
+    /// Validate and repair on-disk runtime state
+    Selfcheck {
+        #[arg(long, default_value = "/var/odin")]
+        odin_dir: PathBuf,
+        #[arg(long)]
+        approvals_dir: Option<PathBuf>,
+    },
+    /// Restore a workspace from a pre-execution copy snapshot
+    RestoreSnapshot {
+        #[arg(long, default_value = "/var/odin/snapshots")]
+        snapshot_root: PathBuf,
+        id: String,
+    },
+    /// Policy authoring tools
+    Policy {
+        #[command(subcommand)]
+        command: PolicySubcommand,
+    },
+    /// Audit log maintenance
+    Audit {
+        #[command(subcommand)]
+        command: AuditSubcommand,
+    },
+    /// Secret rotation tools
+    Secrets {
+        #[command(subcommand)]
+        command: SecretsSubcommand,
+    },
     /// Orchestrator-to-core migration tools
     Migrate {
         #[command(subcommand)]
         command: MigrateSubcommand,
     },
+    /// Resilience scenarios (requires the `chaos` feature)
+    #[cfg(feature = "chaos")]
+    Scenario {
+        #[command(subcommand)]
+        command: ScenarioSubcommand,
+    },
+}
+
+#[cfg(feature = "chaos")]
+#[derive(Clone, Debug, Subcommand)]
+enum ScenarioSubcommand {
+    /// Inject plugin spawn, audit write, ingress timeout and clock skew faults and check
+    /// the runtime degrades as designed
+    Chaos {
+        #[arg(long, default_value_t = 0x0d1e)]
+        seed: u64,
+    },
 }
 
 #[derive(Clone, Debug, Subcommand)]
@@ -152,6 +318,111 @@ enum SkillCommand {
     Mermaid { file: PathBuf },
 }
 
+#[derive(Clone, Debug, Subcommand)]
+enum PolicySubcommand {
+    /// Interview the operator and write a starting policy plus permission envelopes
+    Init {
+        #[arg(long, default_value = ".")]
+        out_dir: PathBuf,
+        #[arg(long)]
+        force: bool,
+    },
+    /// Semantic diff of two policy.yaml or plugin-permissions.yaml files
+    Diff {
+        old: PathBuf,
+        new: PathBuf,
+        #[arg(long)]
+        json: bool,
+    },
+    /// Grant a temporary capability elevation that expires after --ttl-secs
+    Elevate {
+        #[arg(long, default_value = DEFAULT_ELEVATION_OVERLAY)]
+        overlay: PathBuf,
+        #[arg(long)]
+        plugin: String,
+        #[arg(long)]
+        project: String,
+        #[arg(long)]
+        capability: String,
+        /// Allowed scope; repeat for several, omit for any
+        #[arg(long)]
+        scope: Vec<String>,
+        #[arg(long)]
+        ttl_secs: u64,
+        #[arg(long)]
+        reason: String,
+    },
+    /// Show how the active policy decides a request and which rules did not match
+    Explain {
+        #[arg(long)]
+        plugin: String,
+        #[arg(long)]
+        project: String,
+        #[arg(long)]
+        capability: String,
+        /// `safe`, `sensitive` or `destructive`
+        #[arg(long, default_value = "safe", value_parser = parse_risk_tier)]
+        risk_tier: RiskTier,
+        #[arg(long)]
+        scope: Vec<String>,
+        /// Request input as JSON, for rules that match on it
+        #[arg(long)]
+        input: Option<String>,
+        #[arg(long)]
+        json: bool,
+    },
+    /// Replay requests against a candidate policy file and report decisions that change
+    Simulate {
+        #[arg(long)]
+        candidate: PathBuf,
+        /// `ActionRequest`s as a JSON array or one per line
+        #[arg(long)]
+        requests: PathBuf,
+        #[arg(long)]
+        json: bool,
+        /// Exit non-zero when any decision changes
+        #[arg(long)]
+        fail_on_change: bool,
+    },
+}
+
+const DEFAULT_ELEVATION_OVERLAY: &str = "/var/odin/policy-elevations.json";
+
+#[derive(Clone, Debug, Subcommand)]
+enum AuditSubcommand {
+    /// Remove records older than --older-than-days, keeping daily rollups of them
+    Prune {
+        /// Defaults to --audit-log
+        #[arg(long)]
+        log: Option<PathBuf>,
+        #[arg(long)]
+        older_than_days: u64,
+    },
+    /// Recompute the audit hash chain and report the first record that does not match
+    Verify {
+        /// Defaults to --audit-log
+        #[arg(long)]
+        log: Option<PathBuf>,
+        /// Only check records at or after this unix timestamp
+        #[arg(long)]
+        from: Option<u64>,
+        /// Only check records at or before this unix timestamp
+        #[arg(long)]
+        to: Option<u64>,
+    },
+}
+
+#[derive(Clone, Debug, Subcommand)]
+enum SecretsSubcommand {
+    /// Report each stored secret's rotation deadline; exits 1 when any is past it
+    Status,
+    /// Replace a secret with a value from the configured rotation hook
+    Rotate {
+        #[arg(long)]
+        handle: String,
+    },
+}
+
 #[derive(Clone, Debug, Subcommand)]
 enum MigrateSubcommand {
     /// Export a migration bundle from the orchestrator
@@ -162,6 +433,9 @@ enum MigrateSubcommand {
         odin_dir: PathBuf,
         #[arg(long, default_value = "migration-bundle")]
         out_dir: PathBuf,
+        /// Carry the --audit-log under events/audit/
+        #[arg(long)]
+        include_audit: bool,
     },
     /// Validate a migration bundle
     Validate {
@@ -169,7 +443,17 @@ enum MigrateSubcommand {
         bundle: Option<PathBuf>,
     },
     /// Import a migration bundle into odin-core
-    Import,
+    Import {
+        #[arg(long)]
+        bundle: Option<PathBuf>,
+        #[arg(long)]
+        source_root: Option<PathBuf>,
+        #[arg(long, default_value = "/var/odin")]
+        odin_dir: PathBuf,
+        /// Verify the bundle and report what would be applied without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
     #[command(external_subcommand)]
     Unknown(Vec<OsString>),
 }
@@ -231,7 +515,9 @@ fn sample_action_request() -> ActionRequest {
             scope: vec!["project".to_string()],
             reason: "bootstrap health check".to_string(),
         },
+        trace_id: None,
         input: serde_json::json!({"probe": true}),
+        requested_by: None,
     }
 }
 
@@ -245,6 +531,79 @@ impl TaskIngress for StdoutTaskIngress {
     }
 }
 
+/// Grants used when no `--policy-file` is given.
+fn builtin_policy() -> StaticPolicyEngine {
+    let mut policy = StaticPolicyEngine::default();
+    policy.set_require_approval_for_destructive(true);
+    policy.allow_capability("example.safe-github", "*", "repo.read");
+    policy.allow_capability("private.ops-watchdog", "*", "monitoring.sentry.read");
+    policy.allow_capability("private.ops-watchdog", "*", "vcs.pr.read");
+    policy.allow_capability("private.ops-watchdog", "*", "task.enqueue");
+    policy
+}
+
+fn load_simulation_requests(path: &Path) -> anyhow::Result<Vec<ActionRequest>> {
+    let raw = fs::read_to_string(path)
+        .with_context(|| format!("failed to read requests {}", path.display()))?;
+    if raw.trim_start().starts_with('[') {
+        return serde_json::from_str(&raw)
+            .with_context(|| format!("invalid requests in {}", path.display()));
+    }
+    raw.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| {
+            serde_json::from_str(line)
+                .with_context(|| format!("invalid request at {}:{}", path.display(), idx + 1))
+        })
+        .collect()
+}
+
+/// `--policy-bundle` or `--policy-file` when given, otherwise the built-in grants. The highest
+/// activated bundle version is kept under the legacy odin dir.
+fn load_policy(cfg: &CliConfig) -> anyhow::Result<Box<dyn PolicyEngine>> {
+    if let Some(dir) = &cfg.policy_bundle {
+        let key_path = cfg
+            .policy_bundle_key
+            .as_ref()
+            .context("--policy-bundle requires --policy-bundle-key")?;
+        let key = fs::read_to_string(key_path)
+            .with_context(|| format!("failed to read {}", key_path.display()))?;
+        let verifier = BundleVerifier::new()
+            .with_public_key(&key)?
+            .with_version_state(cfg.legacy_odin_dir.join("policy-bundle-state.json"));
+        let policy =
+            FilePolicyEngine::load_bundle(dir, verifier).context("failed to load policy bundle")?;
+        return Ok(Box::new(policy));
+    }
+    Ok(match &cfg.policy_file {
+        Some(path) => Box::new(FilePolicyEngine::load(path).context("failed to load policy file")?),
+        None => Box::new(builtin_policy()),
+    })
+}
+
+fn parse_risk_tier(value: &str) -> Result<RiskTier, String> {
+    match value {
+        "safe" => Ok(RiskTier::Safe),
+        "sensitive" => Ok(RiskTier::Sensitive),
+        "destructive" => Ok(RiskTier::Destructive),
+        other => Err(format!(
+            "unknown risk tier {other:?}; expected safe, sensitive or destructive"
+        )),
+    }
+}
+
+fn parse_reconcile_policy(value: &str) -> Result<ReconcilePolicy, String> {
+    match value {
+        "resume" => Ok(ReconcilePolicy::Resume),
+        "requeue" => Ok(ReconcilePolicy::Requeue),
+        "quarantine" => Ok(ReconcilePolicy::Quarantine),
+        other => Err(format!(
+            "unknown reconcile policy {other} (expected resume, requeue or quarantine)"
+        )),
+    }
+}
+
 fn parse_legacy_cli_config(raw_args: &[String]) -> CliConfig {
     let mut cfg = CliConfig::default();
     let mut idx = 0usize;
@@ -287,11 +646,82 @@ fn parse_legacy_cli_config(raw_args: &[String]) -> CliConfig {
                     continue;
                 }
             }
+            "--task-dir" => {
+                if let Some(path) = raw_args.get(idx + 1) {
+                    cfg.task_dir = Some(PathBuf::from(path));
+                    idx += 2;
+                    continue;
+                }
+            }
             "--run-once" => {
                 cfg.run_once = true;
                 idx += 1;
                 continue;
             }
+            "--task-queue-dir" => {
+                if let Some(path) = raw_args.get(idx + 1) {
+                    cfg.task_queue_dir = Some(PathBuf::from(path));
+                    idx += 2;
+                    continue;
+                }
+            }
+            "--task-retention-days" => {
+                if let Some(days) = raw_args.get(idx + 1).and_then(|v| v.parse().ok()) {
+                    cfg.task_retention_days = days;
+                    idx += 2;
+                    continue;
+                }
+            }
+            "--task-retention-max-bytes" => {
+                if let Some(bytes) = raw_args.get(idx + 1).and_then(|v| v.parse().ok()) {
+                    cfg.task_retention_max_bytes = Some(bytes);
+                    idx += 2;
+                    continue;
+                }
+            }
+            "--task-archive-daily" => {
+                cfg.task_archive_daily = true;
+                idx += 1;
+                continue;
+            }
+            "--reconcile" => {
+                if let Some(policy) = raw_args
+                    .get(idx + 1)
+                    .and_then(|v| parse_reconcile_policy(v).ok())
+                {
+                    cfg.reconcile_policy = policy;
+                    idx += 2;
+                    continue;
+                }
+            }
+            "--policy-file" => {
+                if let Some(path) = raw_args.get(idx + 1) {
+                    cfg.policy_file = Some(PathBuf::from(path));
+                    idx += 2;
+                    continue;
+                }
+            }
+            "--policy-bundle" => {
+                if let Some(path) = raw_args.get(idx + 1) {
+                    cfg.policy_bundle = Some(PathBuf::from(path));
+                    idx += 2;
+                    continue;
+                }
+            }
+            "--policy-bundle-key" => {
+                if let Some(path) = raw_args.get(idx + 1) {
+                    cfg.policy_bundle_key = Some(PathBuf::from(path));
+                    idx += 2;
+                    continue;
+                }
+            }
+            "--audit-log" => {
+                if let Some(path) = raw_args.get(idx + 1) {
+                    cfg.audit_log = Some(PathBuf::from(path));
+                    idx += 2;
+                    continue;
+                }
+            }
             _ => {}
         }
 
@@ -315,6 +745,30 @@ fn parse_legacy_cli_config(raw_args: &[String]) -> CliConfig {
             if !path.is_empty() {
                 cfg.task_file = Some(PathBuf::from(path));
             }
+        } else if let Some(path) = arg.strip_prefix("--task-dir=") {
+            if !path.is_empty() {
+                cfg.task_dir = Some(PathBuf::from(path));
+            }
+        } else if let Some(path) = arg.strip_prefix("--task-queue-dir=") {
+            if !path.is_empty() {
+                cfg.task_queue_dir = Some(PathBuf::from(path));
+            }
+        } else if let Some(path) = arg.strip_prefix("--policy-file=") {
+            if !path.is_empty() {
+                cfg.policy_file = Some(PathBuf::from(path));
+            }
+        } else if let Some(path) = arg.strip_prefix("--policy-bundle=") {
+            if !path.is_empty() {
+                cfg.policy_bundle = Some(PathBuf::from(path));
+            }
+        } else if let Some(path) = arg.strip_prefix("--policy-bundle-key=") {
+            if !path.is_empty() {
+                cfg.policy_bundle_key = Some(PathBuf::from(path));
+            }
+        } else if let Some(path) = arg.strip_prefix("--audit-log=") {
+            if !path.is_empty() {
+                cfg.audit_log = Some(PathBuf::from(path));
+            }
         }
 
         idx += 1;
@@ -328,12 +782,24 @@ fn parse_error_targets_native_contract(raw_args: &[String]) -> bool {
     while idx < raw_args.len() {
         let arg = raw_args[idx].as_str();
         match arg {
-            "--config" | "--legacy-root" | "--legacy-odin-dir" | "--plugins-root"
-            | "--task-file" => {
+            "--config"
+            | "--legacy-root"
+            | "--legacy-odin-dir"
+            | "--plugins-root"
+            | "--task-file"
+            | "--task-dir"
+            | "--task-queue-dir"
+            | "--task-retention-days"
+            | "--task-retention-max-bytes"
+            | "--reconcile"
+            | "--policy-file"
+            | "--policy-bundle"
+            | "--policy-bundle-key"
+            | "--audit-log" => {
                 idx += 2;
                 continue;
             }
-            "--run-once" | "--help" | "-h" => {
+            "--run-once" | "--task-archive-daily" | "--help" | "-h" => {
                 idx += 1;
                 continue;
             }
@@ -349,6 +815,9 @@ fn parse_error_targets_native_contract(raw_args: &[String]) -> bool {
             || arg.starts_with("--legacy-odin-dir=")
             || arg.starts_with("--plugins-root=")
             || arg.starts_with("--task-file=")
+            || arg.starts_with("--task-dir=")
+            || arg.starts_with("--task-queue-dir=")
+            || arg.starts_with("--policy-file=")
         {
             idx += 1;
             continue;
@@ -368,7 +837,13 @@ fn parse_error_targets_native_contract(raw_args: &[String]) -> bool {
                 | "gateway"
                 | "verify"
                 | "skill"
+                | "policy"
+                | "selfcheck"
+                | "restore-snapshot"
+                | "audit"
+                | "secrets"
                 | "migrate"
+                | "scenario"
                 | "governance"
         );
     }
@@ -383,7 +858,13 @@ fn parse_error_targets_native_contract(raw_args: &[String]) -> bool {
                 | "gateway"
                 | "verify"
                 | "skill"
+                | "policy"
+                | "selfcheck"
+                | "restore-snapshot"
+                | "audit"
+                | "secrets"
                 | "migrate"
+                | "scenario"
                 | "governance"
         );
     }
@@ -435,21 +916,121 @@ Print registered skill candidates for the selected scope.
 "
         .to_string(),
         Some("install") => "\
-Usage: odin-cli governance install --name <skill> --trust-level <trusted|caution|untrusted> [--ack]
-
-Evaluate install gates for a skill candidate and report required acknowledgements.
+Usage: odin-cli governance install --name <skill> --trust-level <trusted|caution|untrusted>
+                                 [--ack | --ack-file <path> --trust-store <path>]
+                                 [--skill-dir <path>] [--risk-config <path>]
+                                 [--registry <path>] [--ack-ledger <path> [--operator <id>]]
+                                 [--requested-by <id> [--separation-of-duties]]
+                                 [--signature <path> --signer <id> --signer-trust-store <path>]
+
+Evaluate install gates for a skill candidate and report required acknowledgements. With
+--skill-dir, every file under the skill directory is scanned and findings carry file and line.
+A signed ack file (operator key, skill sha256, expiry) verified against the trust store
+satisfies the gate like --ack; blocked results report the ack_sha256 to sign. With --registry,
+an allowed skill is added to that project registry. A --risk-config file adds regex patterns,
+per-category severities and a block_at severity that blocks even acknowledged installs.
+With --ack-ledger (conventionally config/acks.ledger.jsonl), acks are appended with the operator
+and findings_sha256, and a later install without --ack reuses one only while its findings match.
+--requested-by records who asked for the install; with --separation-of-duties (which requires
+--requested-by) the install is blocked when that identity is also the one acknowledging it.
+Trusted skills need --skill-dir and a detached signature over its content_sha256 (the text
+sha256:<hex>, excluding the signature file) by a signer listed in the --signer-trust-store file;
+the verified signature is stored in the registry. An invalid signature blocks at any trust level.
 "
         .to_string(),
         Some("verify") => "\
-Usage: odin-cli governance verify --scope <global|project|user> [--registry <path>]
+Usage: odin-cli governance verify --scope <global|project|user> [--registry <path>] [--plugins-dir <path>]
+                                [--check-pins [--skills-root <dir>]]
+
+Run governance verification checks for a skill registry. With --plugins-dir, also check that
+observe_only plugins declare only read capabilities. With --check-pins, each pinned_version is
+compared with its source (a git commit, or sha256:<hex> of a local skill directory); scoped
+and relative sources resolve under --skills-root (default .). Drifted or unresolvable pins fail.
+"
+        .to_string(),
+        Some("diff") => "\
+Usage: odin-cli governance diff --scope <global|project|user> --old <path> --new <path>
+
+Compare two skill registries and report added or removed skills, trust changes and capability
+scope changes. Risk-increasing changes are flagged with \"risk\": \"increase\".
+"
+        .to_string(),
+        Some("usage") => "\
+Usage: odin-cli governance usage --audit-log <path> [--plugin <name>] [--since <unix-ts>]
+
+Count governance.capability.used events in the audit log per plugin and capability, with first
+and last use and the projects involved.
+"
+        .to_string(),
+        Some("recommend") => "\
+Usage: odin-cli governance recommend --permissions <path> --audit-log <path>
+                                  [--plugin <name>] [--since <unix-ts>]
+
+Compare the grants in plugin-permissions.yaml with governance.capability.used events in the
+audit log. Grants never used are reported as revoke_grant; scope entries of used grants that no
+use requested are reported as narrow_scope. Project-level envelopes are judged by the uses in
+their projects. Plugin-wide recommendations can be applied with governance revoke.
+"
+        .to_string(),
+        Some("promote") => "\
+Usage: odin-cli governance promote --skill <name> --to <caution|trusted> --registry <path>
+                                 [--ack-file <path> --trust-store <path>] [--skill-dir <path>]
+                                 [--risk-config <path>] [--audit-log <path>]
+                                 [--min-age-days <n>] [--min-uses <n>]
+
+Raise the trust level of a skill in a project registry. The ack file must sign the registered
+record's sha256, the skill directory must scan clean, and the audit log must show at least
+--min-uses governance.capability.used events (default 1), the first at least --min-age-days ago
+(default 7). A governance.trust.promoted record is appended to the audit log.
+"
+        .to_string(),
+        Some("demote") => "\
+Usage: odin-cli governance demote --skill <name> [--to <caution|untrusted>] --registry <path>
+                                [--audit-log <path>]
+
+Lower the trust level of a skill in a project registry (default untrusted). No evidence is
+required. A governance.trust.demoted record is appended to the audit log.
+"
+        .to_string(),
+        Some("review") => "\
+Usage: odin-cli governance review --scope <global|project|user> [--registry <path>]
+                               [--demote-expired [--audit-log <path>]]
 
-Run governance verification checks for a skill registry.
+Flag caution and untrusted skills past their review_by_unix or expires_at_unix. With
+--demote-expired, expired caution skills are demoted to untrusted and governance.trust.demoted
+is appended to the audit log. Exits non-zero while any skill remains flagged.
+"
+        .to_string(),
+        Some("revoke") => "\
+Usage: odin-cli governance revoke --plugin <name> --capability <id> [--scope <scope>]
+                               --permissions <path> [--approvals-dir <dir>] [--audit-log <path>]
+
+Remove a capability grant (or one scope of it) from the plugin's envelopes in
+plugin-permissions.yaml and record it in capability-revocations.json beside that file, which a
+running daemon re-reads before every decision. Pending approvals in --approvals-dir that relied
+on the grant are dropped, and a governance.capability.revoked record is appended to the audit log.
+"
+        .to_string(),
+        Some("report") => "\
+Usage: odin-cli governance report [--registry <path>] [--permissions <path>]
+                               [--audit-log <path> [--since <unix-ts>] [--denials <n>]]
+                               [--policy-file <path>] [--elevations <path>]
+                               [--expiring-within-days <n>] [--plugins-dir <path>]
+                               [--format <json|markdown>]
+
+Snapshot the governance posture for ops reviews: project registry skills by trust level,
+plugin permission envelopes, the newest --denials denials in the audit log (default 20),
+policy grants and elevations lapsing within --expiring-within-days (default 7), and installed
+plugins without a signature. Sections whose source is not given are reported as not collected.
 "
         .to_string(),
         Some("enable-plugin") => "\
 Usage: odin-cli governance enable-plugin --plugin huginn [--domains <csv>] [--workspaces <csv>] [--commands <csv>]
+                                      [--permissions <path>]
 
-Evaluate Huginn plugin policy requirements before enabling browser access.
+Evaluate Huginn plugin policy requirements before enabling browser access. With --permissions,
+an envelope that passes every check replaces the plugin's envelope in that
+plugin-permissions.yaml, which the runtime loads from beside its --config file.
 "
         .to_string(),
         _ => "\
@@ -459,6 +1040,14 @@ Commands:
   discover       List registered skill candidates for a governance scope
   install        Evaluate install gates for a skill candidate
   verify         Run governance verification checks
+  diff           Compare two skill registries semantically
+  usage          Report capability usage from the audit log
+  recommend      Recommend revoking unused grants and scopes from audit usage
+  promote        Raise a registered skill's trust level once evidence is present
+  demote         Lower a registered skill's trust level immediately
+  review         Flag or demote skills past their review or expiry date
+  revoke         Remove a capability grant from a plugin's permission envelope
+  report         Snapshot the governance posture as JSON or Markdown
   enable-plugin  Evaluate Huginn plugin policy inputs
 "
         .to_string(),
@@ -551,9 +1140,25 @@ fn parse_trust_level(command: &str, value: &str) -> Result<TrustLevel, Governanc
     }
 }
 
+/// Splits on commas outside braces, so `git:{status,log}` stays one command scope entry.
 fn parse_csv_values(value: &str) -> Vec<String> {
-    value
-        .split(',')
+    let mut items = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (idx, ch) in value.char_indices() {
+        match ch {
+            '{' => depth += 1,
+            '}' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                items.push(&value[start..idx]);
+                start = idx + 1;
+            }
+            _ => {}
+        }
+    }
+    items.push(&value[start..]);
+    items
+        .into_iter()
         .map(|item| item.trim().to_string())
         .filter(|item| !item.is_empty())
         .collect()
@@ -587,20 +1192,17 @@ fn trust_level_as_str(level: &TrustLevel) -> &'static str {
     }
 }
 
-fn risk_category_as_str(category: &RiskCategory) -> &'static str {
-    match category {
-        RiskCategory::Shell => "shell",
-        RiskCategory::Network => "network",
-        RiskCategory::Secret => "secret",
-        RiskCategory::Delete => "delete",
-    }
-}
-
 fn risk_finding_json(finding: &RiskFinding) -> Value {
-    json!({
-        "category": risk_category_as_str(&finding.category),
+    let mut body = json!({
+        "category": finding.category.as_str(),
         "pattern": finding.pattern,
-    })
+        "severity": finding.severity.as_str(),
+    });
+    if let Some(location) = &finding.location {
+        body["file"] = json!(location.file);
+        body["line"] = json!(location.line);
+    }
+    body
 }
 
 fn skill_record_json(record: &SkillRecord) -> Value {
@@ -616,12 +1218,15 @@ fn decision_to_str(decision: &HuginnDecision) -> &'static str {
     match decision {
         HuginnDecision::Allow { .. } => "allow",
         HuginnDecision::Deny { .. } => "deny",
+        HuginnDecision::RequireApproval { .. } => "require_approval",
     }
 }
 
 fn decision_reason(decision: &HuginnDecision) -> &str {
     match decision {
-        HuginnDecision::Allow { reason_code } | HuginnDecision::Deny { reason_code } => reason_code,
+        HuginnDecision::Allow { reason_code }
+        | HuginnDecision::Deny { reason_code }
+        | HuginnDecision::RequireApproval { reason_code } => reason_code,
     }
 }
 
@@ -734,11 +1339,21 @@ fn handle_governance_discover(tokens: &[String]) -> GovernanceOutcome {
     }
 }
 
-fn handle_governance_install(tokens: &[String]) -> GovernanceOutcome {
-    let command = "install";
-    let mut name: Option<String> = None;
-    let mut trust_level: Option<TrustLevel> = None;
-    let mut ack = false;
+fn governance_diff_report(changes: &[GovernanceChange]) -> Value {
+    json!({
+        "changes": changes,
+        "risk_increasing": changes
+            .iter()
+            .filter(|change| change.risk == RiskDelta::Increase)
+            .count(),
+    })
+}
+
+fn handle_governance_diff(tokens: &[String]) -> GovernanceOutcome {
+    let command = "diff";
+    let mut scope: Option<SkillScope> = None;
+    let mut old: Option<PathBuf> = None;
+    let mut new: Option<PathBuf> = None;
     let mut idx = 0usize;
 
     if tokens
@@ -757,85 +1372,1333 @@ fn handle_governance_install(tokens: &[String]) -> GovernanceOutcome {
         }
 
         let token = tokens[idx].as_str();
-        match token {
-            "--name" => match command_value(tokens, &mut idx, command, "--name") {
-                Ok(value) => name = Some(value),
-                Err(outcome) => return outcome,
-            },
-            "--trust-level" => match command_value(tokens, &mut idx, command, "--trust-level") {
-                Ok(value) => match parse_trust_level(command, &value) {
-                    Ok(parsed) => trust_level = Some(parsed),
-                    Err(outcome) => return outcome,
-                },
-                Err(outcome) => return outcome,
-            },
-            "--ack" => {
-                ack = true;
-                idx += 1;
-            }
-            _ if token.starts_with("--name=") => {
-                name = Some(token.trim_start_matches("--name=").to_string());
-                idx += 1;
-            }
-            _ if token.starts_with("--trust-level=") => {
-                match parse_trust_level(command, token.trim_start_matches("--trust-level=")) {
-                    Ok(parsed) => trust_level = Some(parsed),
+        let option = token.split('=').next().unwrap_or(token);
+        let value = match option {
+            "--scope" | "--old" | "--new" => {
+                match command_value_or_inline(tokens, &mut idx, command, option) {
+                    Ok(value) => value,
                     Err(outcome) => return outcome,
                 }
-                idx += 1;
             }
             _ => return governance_error(command, "unknown_argument", token),
+        };
+        match option {
+            "--scope" => match parse_governance_scope(command, &value) {
+                Ok(parsed) => scope = Some(parsed),
+                Err(outcome) => return outcome,
+            },
+            "--old" => old = Some(PathBuf::from(value)),
+            _ => new = Some(PathBuf::from(value)),
         }
     }
 
-    let Some(name) = name else {
-        return missing_required_value(command, "--name");
+    let Some(scope) = scope else {
+        return missing_required_value(command, "--scope");
     };
-    let Some(trust_level) = trust_level else {
-        return missing_required_value(command, "--trust-level");
+    let Some(old) = old else {
+        return missing_required_value(command, "--old");
     };
-
-    let candidate = SkillImportCandidate {
-        record: SkillRecord {
-            trust_level,
-            source: format!("project:/skills/{name}"),
-            ..SkillRecord::default_for(name)
-        },
-        scripts: Vec::new(),
-        readme: None,
+    let Some(new) = new else {
+        return missing_required_value(command, "--new");
     };
-
-    match evaluate_install(&candidate, if ack { Ack::Accepted } else { Ack::None }) {
-        Ok(plan) => {
-            let findings = plan
-                .findings
+    let registries = load_registry(&scope, &old).and_then(|old_registry| {
+        load_registry(&scope, &new).map(|new_registry| (old_registry, new_registry))
+    });
+    match registries {
+        Ok((old_registry, new_registry)) => {
+            let changes = diff_skill_registries(&old_registry, &new_registry);
+            let mut body = governance_diff_report(&changes);
+            body["command"] = json!(command);
+            body["status"] = json!("ok");
+            body["scope"] = json!(governance_scope_as_str(&scope));
+            body["old"] = json!(old.display().to_string());
+            body["new"] = json!(new.display().to_string());
+            GovernanceOutcome {
+                exit_code: 0,
+                body: GovernanceBody::Json(body),
+            }
+        }
+        Err(detail) => GovernanceOutcome {
+            exit_code: 1,
+            body: GovernanceBody::Json(json!({
+                "command": command,
+                "status": "failed",
+                "error_code": "registry_load_failed",
+                "detail": detail,
+            })),
+        },
+    }
+}
+
+fn handle_governance_usage(tokens: &[String]) -> GovernanceOutcome {
+    let command = "usage";
+    let mut audit_log: Option<PathBuf> = None;
+    let mut plugin: Option<String> = None;
+    let mut since: Option<u64> = None;
+    let mut idx = 0usize;
+
+    if tokens
+        .iter()
+        .any(|token| token == "--help" || token == "-h")
+    {
+        return GovernanceOutcome {
+            exit_code: 0,
+            body: GovernanceBody::Text(governance_help_text(Some(command))),
+        };
+    }
+
+    while idx < tokens.len() {
+        if skip_global_option(tokens, &mut idx) {
+            continue;
+        }
+
+        let token = tokens[idx].as_str();
+        let option = token.split('=').next().unwrap_or(token);
+        let value = match option {
+            "--audit-log" | "--plugin" | "--since" => {
+                match command_value_or_inline(tokens, &mut idx, command, option) {
+                    Ok(value) => value,
+                    Err(outcome) => return outcome,
+                }
+            }
+            _ => return governance_error(command, "unknown_argument", token),
+        };
+        match option {
+            "--audit-log" => audit_log = Some(PathBuf::from(value)),
+            "--plugin" => plugin = Some(value),
+            _ => match value.parse() {
+                Ok(parsed) => since = Some(parsed),
+                Err(_) => return governance_error(command, "invalid_since", &value),
+            },
+        }
+    }
+
+    let Some(audit_log) = audit_log else {
+        return missing_required_value(command, "--audit-log");
+    };
+    let mut report = CapabilityUsageReport::new();
+    if let Some(since) = since {
+        report = report.with_since(since);
+    }
+    match report.with_log(&audit_log) {
+        Ok(report) => GovernanceOutcome {
+            exit_code: 0,
+            body: GovernanceBody::Json(json!({
+                "command": command,
+                "status": "ok",
+                "audit_log": audit_log.display().to_string(),
+                "capabilities": report
+                    .entries()
+                    .filter(|usage| plugin.as_ref().is_none_or(|plugin| usage.plugin == *plugin))
+                    .collect::<Vec<_>>(),
+            })),
+        },
+        Err(err) => GovernanceOutcome {
+            exit_code: 1,
+            body: GovernanceBody::Json(json!({
+                "command": command,
+                "status": "failed",
+                "error_code": "audit_log_read_failed",
+                "detail": err.to_string(),
+            })),
+        },
+    }
+}
+
+fn handle_governance_recommend(tokens: &[String]) -> GovernanceOutcome {
+    let command = "recommend";
+    let mut permissions: Option<PathBuf> = None;
+    let mut audit_log: Option<PathBuf> = None;
+    let mut plugin: Option<String> = None;
+    let mut since: Option<u64> = None;
+    let mut idx = 0usize;
+
+    if tokens
+        .iter()
+        .any(|token| token == "--help" || token == "-h")
+    {
+        return GovernanceOutcome {
+            exit_code: 0,
+            body: GovernanceBody::Text(governance_help_text(Some(command))),
+        };
+    }
+
+    while idx < tokens.len() {
+        if skip_global_option(tokens, &mut idx) {
+            continue;
+        }
+
+        let token = tokens[idx].as_str();
+        let option = token.split('=').next().unwrap_or(token);
+        let value = match option {
+            "--permissions" | "--audit-log" | "--plugin" | "--since" => {
+                match command_value_or_inline(tokens, &mut idx, command, option) {
+                    Ok(value) => value,
+                    Err(outcome) => return outcome,
+                }
+            }
+            _ => return governance_error(command, "unknown_argument", token),
+        };
+        match option {
+            "--permissions" => permissions = Some(PathBuf::from(value)),
+            "--audit-log" => audit_log = Some(PathBuf::from(value)),
+            "--plugin" => plugin = Some(value),
+            _ => match value.parse() {
+                Ok(parsed) => since = Some(parsed),
+                Err(_) => return governance_error(command, "invalid_since", &value),
+            },
+        }
+    }
+
+    let Some(permissions) = permissions else {
+        return missing_required_value(command, "--permissions");
+    };
+    let Some(audit_log) = audit_log else {
+        return missing_required_value(command, "--audit-log");
+    };
+    let registry = match load_permission_registry(&permissions) {
+        Ok(registry) => registry,
+        Err(err) => return governance_error(command, "permissions_unreadable", &err.to_string()),
+    };
+    let mut report = CapabilityUsageReport::new();
+    if let Some(since) = since {
+        report = report.with_since(since);
+    }
+    let report = match report.with_log(&audit_log) {
+        Ok(report) => report,
+        Err(err) => return governance_error(command, "audit_log_read_failed", &err.to_string()),
+    };
+
+    // Usage is aggregated per plugin and capability, so each project it touched is credited
+    // with all of it: recommendations err toward keeping a grant.
+    let mut analyzer = LeastPrivilegeAnalyzer::new(&registry);
+    for usage in report.entries() {
+        let observed = ObservedUse {
+            plugin: usage.plugin.clone(),
+            capability: usage.capability.clone(),
+            project: None,
+            uses: usage.uses,
+            last_used_unix: usage.last_used_unix,
+            scopes: usage.scopes.clone(),
+        };
+        if usage.projects.is_empty() {
+            analyzer.add(&observed);
+        }
+        for project in &usage.projects {
+            analyzer.add(&ObservedUse {
+                project: Some(project.clone()),
+                ..observed.clone()
+            });
+        }
+    }
+    let recommendations: Vec<_> = analyzer
+        .recommendations()
+        .into_iter()
+        .filter(|recommendation| {
+            plugin
+                .as_ref()
+                .is_none_or(|plugin| recommendation.plugin == *plugin)
+        })
+        .collect();
+
+    GovernanceOutcome {
+        exit_code: 0,
+        body: GovernanceBody::Json(json!({
+            "command": command,
+            "status": "ok",
+            "permissions": permissions.display().to_string(),
+            "audit_log": audit_log.display().to_string(),
+            "recommendations": recommendations,
+        })),
+    }
+}
+
+fn handle_governance_report(tokens: &[String]) -> GovernanceOutcome {
+    let command = "report";
+    let mut registry: Option<PathBuf> = None;
+    let mut permissions: Option<PathBuf> = None;
+    let mut audit_log: Option<PathBuf> = None;
+    let mut policy_file: Option<PathBuf> = None;
+    let mut elevations: Option<PathBuf> = None;
+    let mut plugins_dir: Option<PathBuf> = None;
+    let mut markdown = false;
+    let now = now_unix_timestamp();
+    let mut report = governance_report::GovernanceReport::new(now);
+    let mut idx = 0usize;
+
+    if tokens
+        .iter()
+        .any(|token| token == "--help" || token == "-h")
+    {
+        return GovernanceOutcome {
+            exit_code: 0,
+            body: GovernanceBody::Text(governance_help_text(Some(command))),
+        };
+    }
+
+    while idx < tokens.len() {
+        if skip_global_option(tokens, &mut idx) {
+            continue;
+        }
+
+        let token = tokens[idx].as_str();
+        let option = token.split('=').next().unwrap_or(token);
+        let value = match option {
+            "--registry"
+            | "--permissions"
+            | "--audit-log"
+            | "--since"
+            | "--denials"
+            | "--policy-file"
+            | "--elevations"
+            | "--expiring-within-days"
+            | "--plugins-dir"
+            | "--format" => match command_value_or_inline(tokens, &mut idx, command, option) {
+                Ok(value) => value,
+                Err(outcome) => return outcome,
+            },
+            _ => return governance_error(command, "unknown_argument", token),
+        };
+        match option {
+            "--registry" => registry = Some(PathBuf::from(value)),
+            "--permissions" => permissions = Some(PathBuf::from(value)),
+            "--audit-log" => audit_log = Some(PathBuf::from(value)),
+            "--policy-file" => policy_file = Some(PathBuf::from(value)),
+            "--elevations" => elevations = Some(PathBuf::from(value)),
+            "--plugins-dir" => plugins_dir = Some(PathBuf::from(value)),
+            "--format" => match value.as_str() {
+                "json" => markdown = false,
+                "markdown" => markdown = true,
+                _ => return governance_error(command, "invalid_format", &value),
+            },
+            _ => {
+                let Ok(parsed) = value.parse::<u64>() else {
+                    return governance_error(command, "invalid_number", &value);
+                };
+                report = match option {
+                    "--since" => report.with_since(parsed),
+                    "--denials" => report.with_denial_limit(parsed as usize),
+                    _ => report.with_expiring_within_days(parsed),
+                };
+            }
+        }
+    }
+
+    if let Some(path) = &registry {
+        match load_project_registry(path) {
+            Ok(registry) => report = report.with_skills(&registry.skills),
+            Err(err) => return governance_error(command, "registry_unreadable", &err.to_string()),
+        }
+    }
+    if let Some(path) = &permissions {
+        match load_permission_registry(path) {
+            Ok(registry) => report = report.with_envelopes(&registry),
+            Err(err) => {
+                return governance_error(command, "permissions_unreadable", &err.to_string())
+            }
+        }
+    }
+    if let Some(path) = &audit_log {
+        report = match report.with_audit_log(path) {
+            Ok(report) => report,
+            Err(err) => {
+                return governance_error(command, "audit_log_read_failed", &err.to_string())
+            }
+        };
+    }
+    if let Some(path) = &policy_file {
+        match PolicyDocument::load(path) {
+            Ok(policy) => report = report.with_policy(&policy),
+            Err(err) => return governance_error(command, "policy_unreadable", &err.to_string()),
+        }
+    }
+    if let Some(path) = &elevations {
+        match ElevationOverlay::file(path).active(now) {
+            Ok(active) => report = report.with_elevations(&active),
+            Err(err) => {
+                return governance_error(command, "elevations_unreadable", &err.to_string())
+            }
+        }
+    }
+    if let Some(path) = &plugins_dir {
+        match ExternalProcessPluginRunner::new(path).installed_manifests() {
+            Ok(manifests) => report = report.with_plugins(&manifests),
+            Err(err) => return governance_error(command, "plugins_unreadable", &err.to_string()),
+        }
+    }
+
+    GovernanceOutcome {
+        exit_code: 0,
+        body: if markdown {
+            GovernanceBody::Text(report.to_markdown())
+        } else {
+            let mut body = json!({ "command": command, "status": "ok" });
+            if let (Some(body), Value::Object(sections)) = (body.as_object_mut(), report.to_json())
+            {
+                body.extend(sections);
+            }
+            GovernanceBody::Json(body)
+        },
+    }
+}
+
+fn handle_governance_install(tokens: &[String]) -> GovernanceOutcome {
+    let command = "install";
+    let mut name: Option<String> = None;
+    let mut trust_level: Option<TrustLevel> = None;
+    let mut ack = false;
+    let mut ack_file: Option<PathBuf> = None;
+    let mut trust_store: Option<PathBuf> = None;
+    let mut registry: Option<PathBuf> = None;
+    let mut skill_dir: Option<PathBuf> = None;
+    let mut risk_config: Option<PathBuf> = None;
+    let mut ack_ledger: Option<PathBuf> = None;
+    let mut operator: Option<String> = None;
+    let mut requested_by: Option<String> = None;
+    let mut separation_of_duties = false;
+    let mut signature: Option<PathBuf> = None;
+    let mut signer: Option<String> = None;
+    let mut signer_trust_store: Option<PathBuf> = None;
+    let mut idx = 0usize;
+
+    if tokens
+        .iter()
+        .any(|token| token == "--help" || token == "-h")
+    {
+        return GovernanceOutcome {
+            exit_code: 0,
+            body: GovernanceBody::Text(governance_help_text(Some(command))),
+        };
+    }
+
+    while idx < tokens.len() {
+        if skip_global_option(tokens, &mut idx) {
+            continue;
+        }
+
+        let token = tokens[idx].as_str();
+        match token {
+            "--name" => match command_value(tokens, &mut idx, command, "--name") {
+                Ok(value) => name = Some(value),
+                Err(outcome) => return outcome,
+            },
+            "--trust-level" => match command_value(tokens, &mut idx, command, "--trust-level") {
+                Ok(value) => match parse_trust_level(command, &value) {
+                    Ok(parsed) => trust_level = Some(parsed),
+                    Err(outcome) => return outcome,
+                },
+                Err(outcome) => return outcome,
+            },
+            "--ack" => {
+                ack = true;
+                idx += 1;
+            }
+            "--ack-file" => match command_value(tokens, &mut idx, command, "--ack-file") {
+                Ok(value) => ack_file = Some(PathBuf::from(value)),
+                Err(outcome) => return outcome,
+            },
+            "--trust-store" => match command_value(tokens, &mut idx, command, "--trust-store") {
+                Ok(value) => trust_store = Some(PathBuf::from(value)),
+                Err(outcome) => return outcome,
+            },
+            "--registry" => match command_value(tokens, &mut idx, command, "--registry") {
+                Ok(value) => registry = Some(PathBuf::from(value)),
+                Err(outcome) => return outcome,
+            },
+            "--skill-dir" => match command_value(tokens, &mut idx, command, "--skill-dir") {
+                Ok(value) => skill_dir = Some(PathBuf::from(value)),
+                Err(outcome) => return outcome,
+            },
+            "--risk-config" => match command_value(tokens, &mut idx, command, "--risk-config") {
+                Ok(value) => risk_config = Some(PathBuf::from(value)),
+                Err(outcome) => return outcome,
+            },
+            "--ack-ledger" => match command_value(tokens, &mut idx, command, "--ack-ledger") {
+                Ok(value) => ack_ledger = Some(PathBuf::from(value)),
+                Err(outcome) => return outcome,
+            },
+            "--operator" => match command_value(tokens, &mut idx, command, "--operator") {
+                Ok(value) => operator = Some(value),
+                Err(outcome) => return outcome,
+            },
+            "--requested-by" => match command_value(tokens, &mut idx, command, "--requested-by") {
+                Ok(value) => requested_by = Some(value),
+                Err(outcome) => return outcome,
+            },
+            "--separation-of-duties" => {
+                separation_of_duties = true;
+                idx += 1;
+            }
+            "--signature" => match command_value(tokens, &mut idx, command, "--signature") {
+                Ok(value) => signature = Some(PathBuf::from(value)),
+                Err(outcome) => return outcome,
+            },
+            "--signer" => match command_value(tokens, &mut idx, command, "--signer") {
+                Ok(value) => signer = Some(value),
+                Err(outcome) => return outcome,
+            },
+            "--signer-trust-store" => {
+                match command_value(tokens, &mut idx, command, "--signer-trust-store") {
+                    Ok(value) => signer_trust_store = Some(PathBuf::from(value)),
+                    Err(outcome) => return outcome,
+                }
+            }
+            _ if token.starts_with("--name=") => {
+                name = Some(token.trim_start_matches("--name=").to_string());
+                idx += 1;
+            }
+            _ if token.starts_with("--trust-level=") => {
+                match parse_trust_level(command, token.trim_start_matches("--trust-level=")) {
+                    Ok(parsed) => trust_level = Some(parsed),
+                    Err(outcome) => return outcome,
+                }
+                idx += 1;
+            }
+            _ => return governance_error(command, "unknown_argument", token),
+        }
+    }
+
+    let Some(name) = name else {
+        return missing_required_value(command, "--name");
+    };
+    let Some(trust_level) = trust_level else {
+        return missing_required_value(command, "--trust-level");
+    };
+    if separation_of_duties && requested_by.is_none() {
+        return missing_required_value(command, "--requested-by");
+    }
+    let signature = match signature {
+        Some(path) => {
+            let Some(signer) = signer else {
+                return missing_required_value(command, "--signer");
+            };
+            let Some(store_path) = signer_trust_store else {
+                return missing_required_value(command, "--signer-trust-store");
+            };
+            if skill_dir.is_none() {
+                return missing_required_value(command, "--skill-dir");
+            }
+            let store = match SkillSignerTrustStore::load(&store_path) {
+                Ok(store) => store,
+                Err(err) => {
+                    return governance_error(
+                        command,
+                        "signer_trust_store_load_failed",
+                        &err.to_string(),
+                    )
+                }
+            };
+            match store.signature(&signer, path) {
+                Ok(signature) => Some(signature),
+                Err(err) => return governance_error(command, "signer_untrusted", &err.to_string()),
+            }
+        }
+        None => None,
+    };
+
+    let candidate = SkillImportCandidate {
+        record: SkillRecord {
+            trust_level,
+            source: format!("project:/skills/{name}"),
+            ..SkillRecord::default_for(name)
+        },
+        scripts: Vec::new(),
+        readme: None,
+    };
+    let digest = skill_ack_digest(&candidate.record);
+
+    let verified = match ack_file {
+        Some(ack_file) => {
+            let Some(trust_store) = trust_store else {
+                return missing_required_value(command, "--trust-store");
+            };
+            match verify_skill_ack_file(
+                command,
+                &ack_file,
+                &trust_store,
+                &candidate.record.name,
+                &digest,
+            ) {
+                Ok(verified) => Some(verified),
+                Err(outcome) => return outcome,
+            }
+        }
+        None => None,
+    };
+    let explicit_ack = ack || verified.is_some();
+    let ack = if explicit_ack {
+        Ack::Accepted
+    } else {
+        Ack::None
+    };
+
+    let gate = match load_risk_scanner(command, risk_config.as_deref()) {
+        Ok(scanner) => InstallGate::new(scanner),
+        Err(outcome) => return outcome,
+    };
+    let ledger = ack_ledger.map(AckLedger::new);
+    let gate = match &ledger {
+        Some(ledger) => gate.with_ack_ledger(ledger.clone()),
+        None => gate,
+    };
+    let gate = match signature {
+        Some(signature) => gate.with_signature(signature),
+        None => gate,
+    };
+    let plan = match &skill_dir {
+        Some(dir) => gate.evaluate_dir(&candidate.record, dir, ack),
+        None => gate.evaluate(&candidate, ack),
+    };
+    match plan {
+        Ok(plan) => {
+            let findings = plan
+                .findings
+                .iter()
+                .map(risk_finding_json)
+                .collect::<Vec<_>>();
+
+            match plan.status {
+                InstallGateStatus::Allowed => {
+                    // The approver is whoever acknowledged the findings: the signer of a
+                    // verified ack, the named operator, or the operator of a reused ledger ack.
+                    let approver = verified
+                        .as_ref()
+                        .map(|verified| verified.ack.operator.clone())
+                        .or(operator)
+                        .or_else(|| {
+                            plan.ledger_ack
+                                .as_ref()
+                                .filter(|_| !explicit_ack)
+                                .map(|entry| entry.operator.clone())
+                        })
+                        .or_else(|| env::var("USER").ok())
+                        .unwrap_or_else(|| "unknown".to_string());
+                    let acknowledged = explicit_ack || plan.ledger_ack.is_some();
+                    if separation_of_duties
+                        && acknowledged
+                        && SeparationOfDuties::everywhere().violated(
+                            "*",
+                            requested_by.as_deref(),
+                            &approver,
+                        )
+                    {
+                        return GovernanceOutcome {
+                            exit_code: 1,
+                            body: GovernanceBody::Json(json!({
+                                "command": command,
+                                "status": "blocked",
+                                "error_code": "separation_of_duties_violation",
+                                "requested_by": requested_by,
+                                "approver": approver,
+                                "reasons": ["the requester may not acknowledge their own install"],
+                            })),
+                        };
+                    }
+                    let mut recorded = None;
+                    if let Some(ledger) = ledger.as_ref().filter(|_| explicit_ack) {
+                        let entry = AckLedgerEntry {
+                            operator: approver,
+                            skill: candidate.record.name.clone(),
+                            findings_sha256: plan.findings_sha256.clone(),
+                            acked_at_unix: now_unix_timestamp(),
+                            requested_by,
+                        };
+                        if let Err(err) = ledger.append(&entry) {
+                            return governance_error(
+                                command,
+                                "ack_ledger_write_failed",
+                                &err.to_string(),
+                            );
+                        }
+                        recorded = Some(entry);
+                    }
+                    if let Some(registry) = &registry {
+                        let record = SkillRecord {
+                            signature: plan.signature.clone(),
+                            ..candidate.record.clone()
+                        };
+                        if let Err(err) = add_skill(registry, SkillScope::Project, record) {
+                            let code = match err {
+                                SkillRegistryWriteError::Duplicate(_) => "skill_already_registered",
+                                _ => "registry_write_failed",
+                            };
+                            return governance_error(
+                                command,
+                                code,
+                                &format!("{}: {err}", registry.display()),
+                            );
+                        }
+                    }
+                    let mut body = json!({
+                        "command": command,
+                        "status": "ok",
+                        "reasons": plan.reasons,
+                        "findings": findings,
+                        "findings_sha256": plan.findings_sha256,
+                    });
+                    if let Some(entry) = plan.ledger_ack.or(recorded) {
+                        body["ack_ledger"] = json!(entry);
+                    }
+                    if let Some(registry) = registry {
+                        body["registry"] = json!(registry);
+                    }
+                    if let Some(content_sha256) = plan.content_sha256 {
+                        body["content_sha256"] = json!(content_sha256);
+                    }
+                    if let Some(signature) = plan.signature {
+                        body["signature"] = json!(signature);
+                    }
+                    if let Some(verified) = verified {
+                        body["audit"] = json!(ack_audit_record(&verified));
+                    }
+                    GovernanceOutcome {
+                        exit_code: 0,
+                        body: GovernanceBody::Json(body),
+                    }
+                }
+                InstallGateStatus::BlockedAckRequired => GovernanceOutcome {
+                    exit_code: 1,
+                    body: GovernanceBody::Json(json!({
+                        "command": command,
+                        "status": "blocked",
+                        "error_code": "ack_required",
+                        "reasons": plan.reasons,
+                        "findings": findings,
+                        "findings_sha256": plan.findings_sha256,
+                        "ack_sha256": digest,
+                    })),
+                },
+                InstallGateStatus::Blocked => {
+                    let error_code = ["blocking_finding", "signature_invalid"]
+                        .into_iter()
+                        .find(|code| plan.reasons.iter().any(|reason| reason == code))
+                        .unwrap_or("signature_required");
+                    let mut body = json!({
+                        "command": command,
+                        "status": "blocked",
+                        "error_code": error_code,
+                        "reasons": plan.reasons,
+                        "findings": findings,
+                    });
+                    if let Some(content_sha256) = plan.content_sha256 {
+                        body["content_sha256"] = json!(content_sha256);
+                    }
+                    if let Some(rejection) = plan.signature_rejection {
+                        body["signature_rejection"] = json!(rejection);
+                    }
+                    GovernanceOutcome {
+                        exit_code: 1,
+                        body: GovernanceBody::Json(body),
+                    }
+                }
+            }
+        }
+        Err(err @ ImportGateError::Scan(_)) => {
+            governance_error(command, "skill_dir_unreadable", &err.to_string())
+        }
+        Err(err @ ImportGateError::Ledger(_)) => {
+            governance_error(command, "ack_ledger_unreadable", &err.to_string())
+        }
+        Err(err @ ImportGateError::Signature(_)) => {
+            governance_error(command, "skill_dir_unreadable", &err.to_string())
+        }
+        Err(err) => governance_error(command, "invalid_name", &err.to_string()),
+    }
+}
+
+/// `governance promote` and `governance demote`: both rewrite the registry record through
+/// `promotion::change_trust`, which only asks for evidence when trust goes up.
+fn handle_governance_trust_change(tokens: &[String], command: &str) -> GovernanceOutcome {
+    let promote = command == "promote";
+    let mut skill: Option<String> = None;
+    let mut to: Option<TrustLevel> = None;
+    let mut registry: Option<PathBuf> = None;
+    let mut ack_file: Option<PathBuf> = None;
+    let mut trust_store: Option<PathBuf> = None;
+    let mut skill_dir: Option<PathBuf> = None;
+    let mut risk_config: Option<PathBuf> = None;
+    let mut audit_log: Option<PathBuf> = None;
+    let mut policy = PromotionPolicy::default();
+    let mut idx = 0usize;
+
+    if tokens
+        .iter()
+        .any(|token| token == "--help" || token == "-h")
+    {
+        return GovernanceOutcome {
+            exit_code: 0,
+            body: GovernanceBody::Text(governance_help_text(Some(command))),
+        };
+    }
+
+    while idx < tokens.len() {
+        if skip_global_option(tokens, &mut idx) {
+            continue;
+        }
+
+        let token = tokens[idx].as_str();
+        let option = token.split('=').next().unwrap_or(token);
+        let promote_only = matches!(
+            option,
+            "--ack-file"
+                | "--trust-store"
+                | "--skill-dir"
+                | "--risk-config"
+                | "--min-age-days"
+                | "--min-uses"
+        );
+        let shared = matches!(option, "--skill" | "--to" | "--registry" | "--audit-log");
+        if !(shared || promote && promote_only) {
+            return governance_error(command, "unknown_argument", token);
+        }
+        let value = match command_value_or_inline(tokens, &mut idx, command, option) {
+            Ok(value) => value,
+            Err(outcome) => return outcome,
+        };
+        match option {
+            "--skill" => skill = Some(value),
+            "--to" => match parse_trust_level(command, &value) {
+                Ok(parsed) => to = Some(parsed),
+                Err(outcome) => return outcome,
+            },
+            "--registry" => registry = Some(PathBuf::from(value)),
+            "--ack-file" => ack_file = Some(PathBuf::from(value)),
+            "--trust-store" => trust_store = Some(PathBuf::from(value)),
+            "--skill-dir" => skill_dir = Some(PathBuf::from(value)),
+            "--risk-config" => risk_config = Some(PathBuf::from(value)),
+            "--audit-log" => audit_log = Some(PathBuf::from(value)),
+            _ => match value.parse::<u64>() {
+                Ok(n) if option == "--min-uses" => policy = policy.with_min_uses(n),
+                Ok(days) => policy = policy.with_min_age(Duration::from_secs(days * 86_400)),
+                Err(_) => return governance_error(command, "invalid_number", token),
+            },
+        }
+    }
+
+    let Some(skill) = skill else {
+        return missing_required_value(command, "--skill");
+    };
+    let Some(registry) = registry else {
+        return missing_required_value(command, "--registry");
+    };
+    let to = match to {
+        Some(to) => to,
+        None if !promote => TrustLevel::Untrusted,
+        None => return missing_required_value(command, "--to"),
+    };
+    let record = match load_project_registry(&registry) {
+        Ok(loaded) => loaded
+            .skills
+            .into_iter()
+            .find(|record| record.name == skill),
+        Err(err) => {
+            return governance_error(
+                command,
+                "registry_load_failed",
+                &format!("{}: {err}", registry.display()),
+            )
+        }
+    };
+    let Some(record) = record else {
+        return governance_error(command, "skill_not_registered", &skill);
+    };
+    if promote != (trust_rank(&to) > trust_rank(&record.trust_level)) {
+        let code = if promote {
+            "not_a_promotion"
+        } else {
+            "not_a_demotion"
+        };
+        return governance_error(
+            command,
+            code,
+            &format!(
+                "{skill} is {}, target is {}",
+                trust_level_as_str(&record.trust_level),
+                trust_level_as_str(&to)
+            ),
+        );
+    }
+
+    let mut evidence = PromotionEvidence::default();
+    if promote {
+        if let Some(ack_file) = ack_file {
+            let Some(trust_store) = trust_store else {
+                return missing_required_value(command, "--trust-store");
+            };
+            let digest = skill_ack_digest(&record);
+            if let Err(outcome) =
+                verify_skill_ack_file(command, &ack_file, &trust_store, &skill, &digest)
+            {
+                return outcome;
+            }
+            evidence.signed = true;
+        }
+        if let Some(dir) = skill_dir {
+            let scanner = match load_risk_scanner(command, risk_config.as_deref()) {
+                Ok(scanner) => scanner,
+                Err(outcome) => return outcome,
+            };
+            match scan_skill_dir_with(&dir, &scanner) {
+                Ok(scan) => evidence.findings = Some(scan.findings),
+                Err(err) => {
+                    return governance_error(command, "skill_dir_unreadable", &err.to_string())
+                }
+            }
+        }
+        if let Some(audit_log) = &audit_log {
+            match CapabilityUsageReport::new().with_log(audit_log) {
+                Ok(report) => {
+                    for usage in report.entries().filter(|usage| usage.plugin == skill) {
+                        evidence.uses += usage.uses;
+                        evidence.first_seen_unix = Some(
+                            evidence
+                                .first_seen_unix
+                                .map_or(usage.first_used_unix, |first| {
+                                    first.min(usage.first_used_unix)
+                                }),
+                        );
+                    }
+                }
+                Err(err) => {
+                    return governance_error(command, "audit_log_read_failed", &err.to_string())
+                }
+            }
+        }
+    }
+
+    let now = now_unix_timestamp();
+    let change = match change_trust(
+        &registry,
+        SkillScope::Project,
+        &skill,
+        to,
+        &policy,
+        &evidence,
+        now,
+    ) {
+        Ok(change) => change,
+        Err(PromotionError::Unmet { unmet, .. }) => {
+            return GovernanceOutcome {
+                exit_code: 1,
+                body: GovernanceBody::Json(json!({
+                    "command": command,
+                    "status": "blocked",
+                    "error_code": "promotion_requirements_unmet",
+                    "skill": skill,
+                    "unmet": unmet.iter().map(promotion_requirement_json).collect::<Vec<_>>(),
+                })),
+            }
+        }
+        Err(err) => {
+            return governance_error(
+                command,
+                "registry_write_failed",
+                &format!("{}: {err}", registry.display()),
+            )
+        }
+    };
+
+    let (event_type, severity) = if change.is_promotion() {
+        ("governance.trust.promoted", Severity::Notice)
+    } else {
+        ("governance.trust.demoted", Severity::Warning)
+    };
+    let audit = AuditRecord {
+        ts_unix: now,
+        event_type: event_type.to_string(),
+        severity,
+        request_id: None,
+        task_id: None,
+        project: None,
+        trace_id: None,
+        metadata: json!({
+            "skill": change.skill,
+            "from": trust_level_as_str(&change.from),
+            "to": trust_level_as_str(&change.to),
+            "registry": registry,
+            "signed": evidence.signed,
+            "uses": evidence.uses,
+            "first_seen_unix": evidence.first_seen_unix,
+        }),
+    };
+    if let Some(audit_log) = &audit_log {
+        if let Err(err) = FileAuditSink::open(audit_log).and_then(|sink| sink.record(audit.clone()))
+        {
+            return governance_error(command, "audit_write_failed", &err.to_string());
+        }
+    }
+    GovernanceOutcome {
+        exit_code: 0,
+        body: GovernanceBody::Json(json!({
+            "command": command,
+            "status": "ok",
+            "skill": change.skill,
+            "from": trust_level_as_str(&change.from),
+            "to": trust_level_as_str(&change.to),
+            "registry": registry,
+            "audit": audit,
+        })),
+    }
+}
+
+fn handle_governance_review(tokens: &[String]) -> GovernanceOutcome {
+    let command = "review";
+    let mut scope: Option<SkillScope> = None;
+    let mut registry: Option<PathBuf> = None;
+    let mut audit_log: Option<PathBuf> = None;
+    let mut demote = false;
+    let mut idx = 0usize;
+
+    if tokens
+        .iter()
+        .any(|token| token == "--help" || token == "-h")
+    {
+        return GovernanceOutcome {
+            exit_code: 0,
+            body: GovernanceBody::Text(governance_help_text(Some(command))),
+        };
+    }
+
+    while idx < tokens.len() {
+        if skip_global_option(tokens, &mut idx) {
+            continue;
+        }
+
+        let token = tokens[idx].as_str();
+        if token == "--demote-expired" {
+            demote = true;
+            idx += 1;
+            continue;
+        }
+        let option = token.split('=').next().unwrap_or(token);
+        let value = match option {
+            "--scope" | "--registry" | "--audit-log" => {
+                match command_value_or_inline(tokens, &mut idx, command, option) {
+                    Ok(value) => value,
+                    Err(outcome) => return outcome,
+                }
+            }
+            _ => return governance_error(command, "unknown_argument", token),
+        };
+        match option {
+            "--scope" => match parse_governance_scope(command, &value) {
+                Ok(parsed) => scope = Some(parsed),
+                Err(outcome) => return outcome,
+            },
+            "--registry" => registry = Some(PathBuf::from(value)),
+            _ => audit_log = Some(PathBuf::from(value)),
+        }
+    }
+
+    let Some(scope) = scope else {
+        return missing_required_value(command, "--scope");
+    };
+    let registry = registry.unwrap_or_else(|| PathBuf::from(default_registry_path(&scope)));
+    let now = now_unix_timestamp();
+
+    let demoted = if demote {
+        match demote_expired(&registry, scope.clone(), now) {
+            Ok(changes) => changes,
+            Err(err) => {
+                return governance_error(
+                    command,
+                    "registry_write_failed",
+                    &format!("{}: {err}", registry.display()),
+                )
+            }
+        }
+    } else {
+        Vec::new()
+    };
+    for change in &demoted {
+        let audit = AuditRecord {
+            ts_unix: now,
+            event_type: "governance.trust.demoted".to_string(),
+            severity: Severity::Warning,
+            request_id: None,
+            task_id: None,
+            project: None,
+            trace_id: None,
+            metadata: json!({
+                "skill": change.skill,
+                "from": trust_level_as_str(&change.from),
+                "to": trust_level_as_str(&change.to),
+                "registry": registry,
+                "reason_code": "skill_expired",
+            }),
+        };
+        if let Some(audit_log) = &audit_log {
+            if let Err(err) = FileAuditSink::open(audit_log).and_then(|sink| sink.record(audit)) {
+                return governance_error(command, "audit_write_failed", &err.to_string());
+            }
+        }
+    }
+
+    let overdue = match load_registry(&scope, &registry) {
+        Ok(loaded) => overdue_skills(&loaded.skills, now),
+        Err(detail) => return governance_error(command, "registry_unreadable", &detail),
+    };
+    GovernanceOutcome {
+        exit_code: if overdue.is_empty() { 0 } else { 1 },
+        body: GovernanceBody::Json(json!({
+            "command": command,
+            "status": if overdue.is_empty() { "ok" } else { "flagged" },
+            "registry": registry.display().to_string(),
+            "overdue": overdue
                 .iter()
-                .map(risk_finding_json)
-                .collect::<Vec<_>>();
+                .map(|expiry| json!({
+                    "skill": expiry.skill,
+                    "trust_level": trust_level_as_str(&expiry.trust_level),
+                    "state": expiry.state.as_str(),
+                    "due_unix": expiry.due_unix,
+                }))
+                .collect::<Vec<_>>(),
+            "demoted": demoted
+                .iter()
+                .map(|change| json!({
+                    "skill": change.skill,
+                    "from": trust_level_as_str(&change.from),
+                    "to": trust_level_as_str(&change.to),
+                }))
+                .collect::<Vec<_>>(),
+        })),
+    }
+}
 
-            match plan.status {
-                InstallGateStatus::Allowed => GovernanceOutcome {
-                    exit_code: 0,
-                    body: GovernanceBody::Json(json!({
-                        "command": command,
-                        "status": "ok",
-                        "reasons": plan.reasons,
-                        "findings": findings,
-                    })),
-                },
-                InstallGateStatus::BlockedAckRequired => GovernanceOutcome {
-                    exit_code: 1,
-                    body: GovernanceBody::Json(json!({
-                        "command": command,
-                        "status": "blocked",
-                        "error_code": "ack_required",
-                        "reasons": plan.reasons,
-                        "findings": findings,
-                    })),
-                },
+fn handle_governance_revoke(tokens: &[String]) -> GovernanceOutcome {
+    let command = "revoke";
+    let mut plugin: Option<String> = None;
+    let mut capability: Option<String> = None;
+    let mut scope: Option<String> = None;
+    let mut permissions: Option<PathBuf> = None;
+    let mut approvals_dir: Option<PathBuf> = None;
+    let mut audit_log: Option<PathBuf> = None;
+    let mut idx = 0usize;
+
+    if tokens
+        .iter()
+        .any(|token| token == "--help" || token == "-h")
+    {
+        return GovernanceOutcome {
+            exit_code: 0,
+            body: GovernanceBody::Text(governance_help_text(Some(command))),
+        };
+    }
+
+    while idx < tokens.len() {
+        if skip_global_option(tokens, &mut idx) {
+            continue;
+        }
+        let token = tokens[idx].as_str();
+        let option = token.split('=').next().unwrap_or(token);
+        if !matches!(
+            option,
+            "--plugin"
+                | "--capability"
+                | "--scope"
+                | "--permissions"
+                | "--approvals-dir"
+                | "--audit-log"
+        ) {
+            return governance_error(command, "unknown_argument", token);
+        }
+        let value = match command_value_or_inline(tokens, &mut idx, command, option) {
+            Ok(value) => value,
+            Err(outcome) => return outcome,
+        };
+        match option {
+            "--plugin" => plugin = Some(value),
+            "--capability" => capability = Some(value),
+            "--scope" => scope = Some(value),
+            "--permissions" => permissions = Some(PathBuf::from(value)),
+            "--approvals-dir" => approvals_dir = Some(PathBuf::from(value)),
+            _ => audit_log = Some(PathBuf::from(value)),
+        }
+    }
+
+    let Some(plugin) = plugin else {
+        return missing_required_value(command, "--plugin");
+    };
+    let Some(capability) = capability else {
+        return missing_required_value(command, "--capability");
+    };
+    let Some(permissions) = permissions else {
+        return missing_required_value(command, "--permissions");
+    };
+
+    let grants_revoked =
+        match revoke_capability(&permissions, &plugin, &capability, scope.as_deref()) {
+            Ok(changed) => changed,
+            Err(err @ EnvelopeFileError::NotGranted(_)) => {
+                return governance_error(command, "not_granted", &err.to_string())
             }
+            Err(err) => {
+                return governance_error(command, "permissions_update_failed", &err.to_string())
+            }
+        };
+
+    let mut revocation = CapabilityRevocation::new(plugin.clone(), capability.clone());
+    if let Some(scope) = &scope {
+        revocation = revocation.with_scope(scope.clone());
+    }
+    let revocations = permissions
+        .parent()
+        .unwrap_or(Path::new(""))
+        .join(REVOCATIONS_FILE);
+    if let Err(err) = RevocationList::file(&revocations).revoke(revocation.clone()) {
+        return governance_error(command, "revocations_write_failed", &err.to_string());
+    }
+    let mut revoked_approvals = Vec::new();
+    if let Some(dir) = &approvals_dir {
+        let store = FileApprovalStore::new(dir);
+        let dropped = store.list().and_then(|pending| {
+            pending
+                .into_iter()
+                .filter(|pending| revocation.covers(&pending.request.capability))
+                .map(|pending| {
+                    store.remove(pending.request_id())?;
+                    Ok(pending.request.request_id)
+                })
+                .collect::<Result<Vec<_>, _>>()
+        });
+        match dropped {
+            Ok(dropped) => revoked_approvals = dropped,
+            Err(err) => return governance_error(command, "approvals_unreadable", &err.to_string()),
         }
-        Err(err) => governance_error(command, "invalid_name", &err.to_string()),
+    }
+
+    let audit = AuditRecord {
+        ts_unix: now_unix_timestamp(),
+        event_type: "governance.capability.revoked".to_string(),
+        severity: Severity::Warning,
+        request_id: None,
+        task_id: None,
+        project: None,
+        trace_id: None,
+        metadata: json!({
+            "plugin": plugin,
+            "capability": capability,
+            "scope": scope,
+            "permissions": permissions,
+            "revocations": revocations,
+            "grants_revoked": grants_revoked,
+            "revoked_approvals": revoked_approvals,
+        }),
+    };
+    if let Some(audit_log) = &audit_log {
+        if let Err(err) = FileAuditSink::open(audit_log).and_then(|sink| sink.record(audit.clone()))
+        {
+            return governance_error(command, "audit_write_failed", &err.to_string());
+        }
+    }
+    GovernanceOutcome {
+        exit_code: 0,
+        body: GovernanceBody::Json(json!({
+            "command": command,
+            "status": "ok",
+            "plugin": plugin,
+            "capability": capability,
+            "scope": scope,
+            "grants_revoked": grants_revoked,
+            "revoked_approvals": revoked_approvals,
+            "revocations": revocations,
+            "audit": audit,
+        })),
+    }
+}
+
+fn promotion_requirement_json(requirement: &PromotionRequirement) -> Value {
+    let mut body = json!({ "code": requirement.code() });
+    match requirement {
+        PromotionRequirement::MinimumAge {
+            required_secs,
+            actual_secs,
+        } => {
+            body["required_secs"] = json!(required_secs);
+            body["actual_secs"] = json!(actual_secs);
+        }
+        PromotionRequirement::UsageHistory { required, actual } => {
+            body["required"] = json!(required);
+            body["actual"] = json!(actual);
+        }
+        PromotionRequirement::Signature | PromotionRequirement::CleanRiskScan => {}
+    }
+    body
+}
+
+/// The scanner configured by `--risk-config`, or the built-in patterns.
+fn load_risk_scanner(
+    command: &str,
+    risk_config: Option<&Path>,
+) -> Result<RiskScanner, GovernanceOutcome> {
+    let Some(path) = risk_config else {
+        return Ok(RiskScanner::default());
+    };
+    fs::read_to_string(path)
+        .map_err(|err| err.to_string())
+        .and_then(|raw| RiskScanner::from_yaml(&raw).map_err(|err| err.to_string()))
+        .map_err(|detail| {
+            governance_error(
+                command,
+                "risk_config_invalid",
+                &format!("{}: {detail}", path.display()),
+            )
+        })
+}
+
+fn verify_skill_ack_file(
+    command: &str,
+    ack_file: &Path,
+    trust_store: &Path,
+    skill: &str,
+    digest: &str,
+) -> Result<VerifiedAck, GovernanceOutcome> {
+    let raw = fs::read_to_string(ack_file).map_err(|err| {
+        governance_error(
+            command,
+            "ack_file_unreadable",
+            &format!("{}: {err}", ack_file.display()),
+        )
+    })?;
+    let trust = fs::read_to_string(trust_store)
+        .map_err(|err| err.to_string())
+        .and_then(|raw| AckTrustStore::from_yaml(&raw).map_err(|err| err.to_string()))
+        .map_err(|detail| {
+            governance_error(
+                command,
+                "trust_store_load_failed",
+                &format!("{}: {detail}", trust_store.display()),
+            )
+        })?;
+    verify_ack_file(
+        &raw,
+        &trust,
+        AckKind::Skill,
+        skill,
+        digest,
+        now_unix_timestamp(),
+    )
+    .map_err(|err| governance_error(command, err.code(), &err.to_string()))
+}
+
+/// Audit record for an install satisfied by an ack file; the file is kept verbatim.
+fn ack_audit_record(verified: &VerifiedAck) -> AuditRecord {
+    AuditRecord {
+        ts_unix: now_unix_timestamp(),
+        event_type: "governance.ack.accepted".to_string(),
+        severity: Severity::Notice,
+        request_id: None,
+        task_id: None,
+        project: None,
+        trace_id: None,
+        metadata: json!({
+            "kind": verified.ack.kind,
+            "subject": verified.ack.subject,
+            "sha256": verified.ack.sha256,
+            "operator": verified.ack.operator,
+            "expires_at_unix": verified.ack.expires_at_unix,
+            "ack_file": verified.raw,
+        }),
     }
 }
 
@@ -843,6 +2706,9 @@ fn handle_governance_verify(tokens: &[String]) -> GovernanceOutcome {
     let command = "verify";
     let mut scope: Option<SkillScope> = None;
     let mut registry: Option<PathBuf> = None;
+    let mut plugins_dir: Option<PathBuf> = None;
+    let mut check_pins = false;
+    let mut skills_root = PathBuf::from(".");
     let mut idx = 0usize;
 
     if tokens
@@ -862,6 +2728,14 @@ fn handle_governance_verify(tokens: &[String]) -> GovernanceOutcome {
 
         let token = tokens[idx].as_str();
         match token {
+            "--check-pins" => {
+                check_pins = true;
+                idx += 1;
+            }
+            "--skills-root" => match command_value(tokens, &mut idx, command, "--skills-root") {
+                Ok(value) => skills_root = PathBuf::from(value),
+                Err(outcome) => return outcome,
+            },
             "--scope" => match command_value(tokens, &mut idx, command, "--scope") {
                 Ok(value) => match parse_governance_scope(command, &value) {
                     Ok(parsed) => scope = Some(parsed),
@@ -873,6 +2747,10 @@ fn handle_governance_verify(tokens: &[String]) -> GovernanceOutcome {
                 Ok(value) => registry = Some(PathBuf::from(value)),
                 Err(outcome) => return outcome,
             },
+            "--plugins-dir" => match command_value(tokens, &mut idx, command, "--plugins-dir") {
+                Ok(value) => plugins_dir = Some(PathBuf::from(value)),
+                Err(outcome) => return outcome,
+            },
             _ if token.starts_with("--scope=") => {
                 let value = token.trim_start_matches("--scope=");
                 match parse_governance_scope(command, value) {
@@ -885,6 +2763,14 @@ fn handle_governance_verify(tokens: &[String]) -> GovernanceOutcome {
                 registry = Some(PathBuf::from(token.trim_start_matches("--registry=")));
                 idx += 1;
             }
+            _ if token.starts_with("--plugins-dir=") => {
+                plugins_dir = Some(PathBuf::from(token.trim_start_matches("--plugins-dir=")));
+                idx += 1;
+            }
+            _ if token.starts_with("--skills-root=") => {
+                skills_root = PathBuf::from(token.trim_start_matches("--skills-root="));
+                idx += 1;
+            }
             _ => return governance_error(command, "unknown_argument", token),
         }
     }
@@ -894,6 +2780,9 @@ fn handle_governance_verify(tokens: &[String]) -> GovernanceOutcome {
     };
     let registry_path = registry.unwrap_or_else(|| PathBuf::from(default_registry_path(&scope)));
     let mut checks = Vec::new();
+    if let Some(plugins_dir) = &plugins_dir {
+        checks.push(observe_only_classification_check(plugins_dir));
+    }
 
     match load_registry(&scope, &registry_path) {
         Ok(registry) => {
@@ -919,34 +2808,111 @@ fn handle_governance_verify(tokens: &[String]) -> GovernanceOutcome {
                 },
             }));
 
+            let pins = check_pins.then(|| {
+                let verifier = PinVerifier::new(&skills_root);
+                let pins: Vec<PinCheck> =
+                    registry.skills.iter().map(|r| verifier.check(r)).collect();
+                checks.push(pinned_versions_check(&pins));
+                pins.iter().map(pin_check_json).collect::<Vec<_>>()
+            });
+
             let failed = checks.iter().any(|check| check["status"] == "fail");
+            let mut body = json!({
+                "command": command,
+                "status": if failed { "failed" } else { "ok" },
+                "registry": registry_path.display().to_string(),
+                "checks": checks,
+            });
+            if let Some(pins) = pins {
+                body["pins"] = json!(pins);
+            }
             GovernanceOutcome {
                 exit_code: if failed { 1 } else { 0 },
+                body: GovernanceBody::Json(body),
+            }
+        }
+        Err(detail) => {
+            checks.push(json!({
+                "name": "registry_load",
+                "status": "fail",
+                "detail": detail,
+            }));
+            GovernanceOutcome {
+                exit_code: 1,
                 body: GovernanceBody::Json(json!({
                     "command": command,
-                    "status": if failed { "failed" } else { "ok" },
+                    "status": "failed",
                     "registry": registry_path.display().to_string(),
                     "checks": checks,
                 })),
             }
         }
-        Err(detail) => {
-            checks.push(json!({
-                "name": "registry_load",
+    }
+}
+
+/// Fails when a pinned skill's source no longer matches its pin, or cannot be resolved.
+fn pinned_versions_check(pins: &[PinCheck]) -> Value {
+    let failures: Vec<String> = pins
+        .iter()
+        .filter(|pin| pin.status.is_failure())
+        .map(|pin| format!("{}: {}", pin.skill, pin.status.as_str()))
+        .collect();
+    json!({
+        "name": "pinned_versions",
+        "status": if failures.is_empty() { "pass" } else { "fail" },
+        "detail": if failures.is_empty() {
+            "pinned skills match their sources".to_string()
+        } else {
+            format!("pinned skills drifted from their sources: {}", failures.join("; "))
+        },
+    })
+}
+
+fn pin_check_json(pin: &PinCheck) -> Value {
+    let mut body = json!({
+        "skill": pin.skill,
+        "source": pin.source,
+        "pinned_version": pin.pinned_version,
+        "status": pin.status.as_str(),
+    });
+    match &pin.status {
+        PinStatus::Drifted { actual } => body["actual"] = json!(actual),
+        PinStatus::Unresolvable(detail) => body["detail"] = json!(detail),
+        PinStatus::Unpinned | PinStatus::Match => {}
+    }
+    body
+}
+
+/// Fails when an installed `observe_only` plugin declares a mutating capability.
+fn observe_only_classification_check(plugins_dir: &Path) -> Value {
+    let manifests = match ExternalProcessPluginRunner::new(plugins_dir).installed_manifests() {
+        Ok(manifests) => manifests,
+        Err(err) => {
+            return json!({
+                "name": "observe_only_classification",
                 "status": "fail",
-                "detail": detail,
-            }));
-            GovernanceOutcome {
-                exit_code: 1,
-                body: GovernanceBody::Json(json!({
-                    "command": command,
-                    "status": "failed",
-                    "registry": registry_path.display().to_string(),
-                    "checks": checks,
-                })),
-            }
+                "detail": err.to_string(),
+            })
         }
-    }
+    };
+    let violations: Vec<String> = manifests
+        .iter()
+        .filter(|manifest| manifest.plugin.class == PluginClass::ObserveOnly)
+        .filter_map(|manifest| {
+            let mutating = manifest.mutating_capabilities();
+            (!mutating.is_empty())
+                .then(|| format!("{}: {}", manifest.plugin.name, mutating.join(", ")))
+        })
+        .collect();
+    json!({
+        "name": "observe_only_classification",
+        "status": if violations.is_empty() { "pass" } else { "fail" },
+        "detail": if violations.is_empty() {
+            "observe_only plugins declare only read capabilities".to_string()
+        } else {
+            format!("observe_only plugins declare mutating capabilities: {}", violations.join("; "))
+        },
+    })
 }
 
 fn canonicalize_domain_probe(value: &str) -> String {
@@ -964,6 +2930,7 @@ fn handle_governance_enable_plugin(tokens: &[String]) -> GovernanceOutcome {
     let mut domains: Vec<String> = Vec::new();
     let mut workspaces: Vec<String> = Vec::new();
     let mut commands: Vec<String> = Vec::new();
+    let mut permissions_path: Option<PathBuf> = None;
     let mut idx = 0usize;
 
     if tokens
@@ -1003,6 +2970,12 @@ fn handle_governance_enable_plugin(tokens: &[String]) -> GovernanceOutcome {
                     Err(outcome) => return outcome,
                 }
             }
+            "--permissions" => {
+                match command_value_or_inline(tokens, &mut idx, command, "--permissions") {
+                    Ok(value) => permissions_path = Some(PathBuf::from(value)),
+                    Err(outcome) => return outcome,
+                }
+            }
             _ if token.starts_with("--plugin=") => {
                 plugin = Some(token.trim_start_matches("--plugin=").to_ascii_lowercase());
                 idx += 1;
@@ -1049,11 +3022,12 @@ fn handle_governance_enable_plugin(tokens: &[String]) -> GovernanceOutcome {
         });
     }
 
-    let policy = huginn_policy_from_envelope(&PluginPermissionEnvelope {
+    let envelope = PluginPermissionEnvelope {
         plugin: "huginn".to_string(),
         trust_level: TrustLevel::Caution,
         permissions,
-    });
+    };
+    let policy = huginn_policy_from_envelope(&envelope);
 
     let mut checks = Vec::new();
     for domain in &domains {
@@ -1075,7 +3049,8 @@ fn handle_governance_enable_plugin(tokens: &[String]) -> GovernanceOutcome {
         }));
     }
     for command_value in &commands {
-        let decision = policy.evaluate(HuginnAction::RunCommand(command_value.clone()));
+        let probe = command_scope_probe(command_value).unwrap_or_else(|| command_value.clone());
+        let decision = policy.evaluate(HuginnAction::RunCommand(probe));
         checks.push(json!({
             "name": "command_allowlist",
             "input": command_value,
@@ -1111,6 +3086,23 @@ fn handle_governance_enable_plugin(tokens: &[String]) -> GovernanceOutcome {
         };
     }
 
+    if let Some(path) = &permissions_path {
+        let mut registry = if path.exists() {
+            match load_permission_registry(path) {
+                Ok(registry) => registry,
+                Err(err) => {
+                    return governance_error(command, "permissions_unreadable", &err.to_string())
+                }
+            }
+        } else {
+            PluginPermissionRegistry::new()
+        };
+        registry.insert(envelope);
+        if let Err(err) = save_permission_registry(path, &registry) {
+            return governance_error(command, "permissions_write_failed", &err.to_string());
+        }
+    }
+
     GovernanceOutcome {
         exit_code: 0,
         body: GovernanceBody::Json(json!({
@@ -1118,6 +3110,7 @@ fn handle_governance_enable_plugin(tokens: &[String]) -> GovernanceOutcome {
             "status": "ok",
             "plugin": plugin,
             "checks": checks,
+            "permissions": permissions_path.map(|path| path.display().to_string()),
         })),
     }
 }
@@ -1141,6 +3134,14 @@ fn try_handle_governance_command(raw_args: &[String]) -> Option<GovernanceOutcom
         "install" => handle_governance_install(tokens),
         "verify" => handle_governance_verify(tokens),
         "enable-plugin" => handle_governance_enable_plugin(tokens),
+        "diff" => handle_governance_diff(tokens),
+        "usage" => handle_governance_usage(tokens),
+        "recommend" => handle_governance_recommend(tokens),
+        "promote" => handle_governance_trust_change(tokens, "promote"),
+        "demote" => handle_governance_trust_change(tokens, "demote"),
+        "review" => handle_governance_review(tokens),
+        "revoke" => handle_governance_revoke(tokens),
+        "report" => handle_governance_report(tokens),
         other => governance_error("governance", "unknown_subcommand", other),
     })
 }
@@ -1345,7 +3346,278 @@ fn handle_skill_command(command: SkillCommand) -> anyhow::Result<()> {
     }
 }
 
-fn handle_bootstrap_command(command: CliCommand) -> anyhow::Result<()> {
+fn selfcheck_config(odin_dir: PathBuf, approvals_dir: Option<PathBuf>) -> SelfCheckConfig {
+    let config = SelfCheckConfig::new(odin_dir);
+    match approvals_dir {
+        Some(dir) => config.with_approvals_dir(dir),
+        None => config,
+    }
+}
+
+fn handle_selfcheck_command(
+    cfg: &CliConfig,
+    odin_dir: PathBuf,
+    approvals_dir: Option<PathBuf>,
+) -> anyhow::Result<()> {
+    let audit = open_audit_sink(cfg)?;
+    let report = run_selfcheck(&selfcheck_config(odin_dir, approvals_dir), &audit)
+        .context("selfcheck failed")?;
+    let report_json =
+        serde_json::to_string_pretty(&report).context("failed to format selfcheck report")?;
+    println!("{report_json}");
+    Ok(())
+}
+
+fn handle_audit_command(command: AuditSubcommand, cfg: &CliConfig) -> anyhow::Result<()> {
+    let audit_log = |log: Option<PathBuf>| {
+        log.or_else(|| cfg.audit_log.clone())
+            .ok_or_else(|| anyhow!("audit commands need --log or --audit-log"))
+    };
+    match command {
+        AuditSubcommand::Prune {
+            log,
+            older_than_days,
+        } => {
+            let log = audit_log(log)?;
+            let sink = FileAuditSink::open(&log)
+                .with_context(|| format!("failed to open audit log {}", log.display()))?;
+            let cutoff = now_unix_timestamp().saturating_sub(older_than_days * 86_400);
+            let report = sink.prune(cutoff).context("audit prune failed")?;
+            let payload = serde_json::to_string_pretty(&json!({
+                "log": log,
+                "rollups": sink.rollup_path(),
+                "report": report,
+            }))
+            .context("failed to format audit prune report")?;
+            println!("{payload}");
+            Ok(())
+        }
+        AuditSubcommand::Verify { log, from, to } => {
+            let log = audit_log(log)?;
+            let report = verify_log(&log, VerifyRange { from, to })
+                .with_context(|| format!("failed to verify audit log {}", log.display()))?;
+            let payload = serde_json::to_string_pretty(&report)
+                .context("failed to format audit verify report")?;
+            println!("{payload}");
+            if !report.intact() {
+                process::exit(1);
+            }
+            Ok(())
+        }
+    }
+}
+
+fn handle_secrets_command(command: SecretsSubcommand, cfg: &CliConfig) -> anyhow::Result<()> {
+    let rotation: RotationPolicy = match config_section(cfg, "secret_rotation")? {
+        Some(section) => serde_json::from_value(section)
+            .with_context(|| format!("invalid secret_rotation section in {}", cfg.config_path))?,
+        None => RotationPolicy::default(),
+    };
+    let store = secret_store_with_rotation(cfg, &rotation)?;
+    match command {
+        SecretsSubcommand::Status => {
+            let info = store
+                .rotation_info()
+                .context("failed to list stored secrets")?;
+            let report = rotation.status(&info, now_unix_timestamp());
+            let overdue = report.iter().filter(|status| status.overdue).count();
+            let payload = serde_json::to_string_pretty(&json!({
+                "overdue": overdue,
+                "secrets": report,
+            }))
+            .context("failed to format secrets status report")?;
+            println!("{payload}");
+            if overdue > 0 {
+                process::exit(1);
+            }
+            Ok(())
+        }
+        SecretsSubcommand::Rotate { handle } => {
+            let handle = SecretHandle(handle);
+            let version = store
+                .rotate(&handle)
+                .with_context(|| format!("failed to rotate {}", handle.0))?;
+            let payload = serde_json::to_string_pretty(&json!({
+                "handle": handle.0,
+                "version": version,
+            }))
+            .context("failed to format secrets rotate report")?;
+            println!("{payload}");
+            Ok(())
+        }
+    }
+}
+
+fn handle_doctor_command(cfg: &CliConfig, plugin: Option<String>) -> anyhow::Result<()> {
+    let plugins_root = &cfg.plugins_root;
+    let runner = ExternalProcessPluginRunner::new(plugins_root)
+        .with_entrypoint_policy(entrypoint_policy(cfg)?);
+    let plugins = match plugin {
+        Some(plugin) => vec![plugin],
+        None => runner
+            .installed_manifests()
+            .context("failed to list installed plugins")?
+            .into_iter()
+            .map(|manifest| manifest.plugin.name)
+            .collect(),
+    };
+    let report: Vec<PluginHealth> = plugins
+        .iter()
+        .map(|plugin| check_plugin(&runner, plugin))
+        .collect();
+    let report_json = serde_json::to_string_pretty(&json!({
+        "plugins_root": plugins_root,
+        "plugins": report,
+    }))
+    .context("failed to format doctor report")?;
+    println!("{report_json}");
+    if report.iter().any(|health| !health.healthy) {
+        process::exit(1);
+    }
+    Ok(())
+}
+
+fn handle_policy_command(command: PolicySubcommand, cfg: &CliConfig) -> anyhow::Result<()> {
+    match command {
+        PolicySubcommand::Init { out_dir, force } => {
+            let stdin = io::stdin();
+            let mut stdout = io::stdout();
+            let answers = policy_init::interview(&mut stdin.lock(), &mut stdout)?;
+            for path in policy_init::write_policy_files(&out_dir, &answers, force)? {
+                println!("wrote {}", path.display());
+            }
+            Ok(())
+        }
+        PolicySubcommand::Diff { old, new, json } => {
+            let changes = policy_diff::diff_policy_files(&old, &new)?;
+            if json {
+                let report = governance_diff_report(&changes);
+                let payload =
+                    serde_json::to_string_pretty(&report).context("failed to format diff")?;
+                println!("{payload}");
+            } else if changes.is_empty() {
+                println!("no semantic changes");
+            } else {
+                for change in &changes {
+                    println!("{}", change.render());
+                }
+            }
+            Ok(())
+        }
+        PolicySubcommand::Elevate {
+            overlay,
+            plugin,
+            project,
+            capability,
+            scope,
+            ttl_secs,
+            reason,
+        } => {
+            let elevation = ElevationOverlay::file(&overlay)
+                .grant(
+                    ElevationGrant {
+                        plugin,
+                        project,
+                        capability,
+                        scope,
+                        ttl: Duration::from_secs(ttl_secs),
+                        reason,
+                    },
+                    now_unix_timestamp(),
+                )
+                .map_err(|e| anyhow!("elevation failed: {e}"))?;
+            let payload =
+                serde_json::to_string_pretty(&elevation).context("failed to format elevation")?;
+            println!("{payload}");
+            Ok(())
+        }
+        PolicySubcommand::Explain {
+            plugin,
+            project,
+            capability,
+            risk_tier,
+            scope,
+            input,
+            json,
+        } => {
+            let input = match input {
+                Some(raw) => serde_json::from_str(&raw).context("--input is not valid JSON")?,
+                None => Value::Null,
+            };
+            let request = ActionRequest {
+                request_id: "policy-explain".to_string(),
+                risk_tier,
+                capability: CapabilityRequest {
+                    plugin,
+                    project,
+                    capability,
+                    scope,
+                    reason: "policy explain".to_string(),
+                },
+                trace_id: None,
+                input,
+                requested_by: None,
+            };
+            let explanation = load_policy(cfg)?
+                .explain(&request)
+                .map_err(|e| anyhow!("policy evaluation failed: {e}"))?;
+            if json {
+                let payload = serde_json::to_string_pretty(&explanation)
+                    .context("failed to format explanation")?;
+                println!("{payload}");
+            } else {
+                println!("decision: {}", describe_decision(&explanation.decision));
+                for step in &explanation.steps {
+                    let mark = if step.matched { "match" } else { "skip " };
+                    println!("  {mark} {}: {}", step.rule, step.detail);
+                }
+            }
+            Ok(())
+        }
+        PolicySubcommand::Simulate {
+            candidate,
+            requests,
+            json,
+            fail_on_change,
+        } => {
+            let requests = load_simulation_requests(&requests)?;
+            let active = load_policy(cfg)?;
+            let candidate =
+                FilePolicyEngine::load(&candidate).context("failed to load candidate policy")?;
+            let report = simulate(active.as_ref(), &candidate, &requests)
+                .map_err(|e| anyhow!("policy simulation failed: {e}"))?;
+            if json {
+                let payload = serde_json::to_string_pretty(&report)
+                    .context("failed to format simulation report")?;
+                println!("{payload}");
+            } else {
+                for change in report.changes() {
+                    println!(
+                        "{} {} {} {}: {} -> {} ({})",
+                        change.request_id,
+                        change.plugin,
+                        change.project,
+                        change.capability,
+                        describe_decision(&change.active),
+                        describe_decision(&change.candidate),
+                        change.change.as_str()
+                    );
+                }
+                println!(
+                    "{} of {} decisions change",
+                    report.changes().count(),
+                    report.requests.len()
+                );
+            }
+            if fail_on_change && report.has_changes() {
+                process::exit(1);
+            }
+            Ok(())
+        }
+    }
+}
+
+fn handle_bootstrap_command(command: CliCommand, cfg: &CliConfig) -> anyhow::Result<()> {
     match command {
         CliCommand::Connect {
             provider,
@@ -1433,11 +3705,40 @@ fn handle_bootstrap_command(command: CliCommand) -> anyhow::Result<()> {
             }
         }
         CliCommand::Skill { command } => handle_skill_command(command),
+        CliCommand::Policy { command } => handle_policy_command(command, cfg),
+        CliCommand::Doctor { plugin } => handle_doctor_command(cfg, plugin),
+        CliCommand::Selfcheck {
+            odin_dir,
+            approvals_dir,
+        } => handle_selfcheck_command(cfg, odin_dir, approvals_dir),
+        #[cfg(feature = "chaos")]
+        CliCommand::Scenario {
+            command: ScenarioSubcommand::Chaos { seed },
+        } => {
+            let report = odin_core_runtime::chaos::run_chaos_scenarios(seed);
+            let payload =
+                serde_json::to_string_pretty(&report).context("failed to format chaos report")?;
+            println!("{payload}");
+            if !report.passed() {
+                process::exit(1);
+            }
+            Ok(())
+        }
+        CliCommand::RestoreSnapshot { snapshot_root, id } => {
+            let snapshotter = CopySnapshotter::new(snapshot_root);
+            let snapshot = snapshotter.load(&id)?;
+            snapshotter.restore(&snapshot)?;
+            println!("restored {} into {}", snapshot.id, snapshot.workspace);
+            Ok(())
+        }
+        CliCommand::Audit { command } => handle_audit_command(command, cfg),
+        CliCommand::Secrets { command } => handle_secrets_command(command, cfg),
         CliCommand::Migrate { command } => match command {
             MigrateSubcommand::Export {
                 source_root,
                 odin_dir,
                 out_dir,
+                include_audit,
             } => {
                 let source_root = match source_root {
                     Some(p) => p,
@@ -1446,10 +3747,15 @@ fn handle_bootstrap_command(command: CliCommand) -> anyhow::Result<()> {
                         process::exit(1);
                     }
                 };
+                if include_audit && cfg.audit_log.is_none() {
+                    eprintln!("--include-audit requires --audit-log");
+                    process::exit(1);
+                }
                 odin_migration::run(odin_migration::MigrationCommand::Export {
                     source_root,
                     odin_dir,
                     out_dir,
+                    audit_log: cfg.audit_log.clone().filter(|_| include_audit),
                 })
             }
             MigrateSubcommand::Validate { bundle } => {
@@ -1462,8 +3768,32 @@ fn handle_bootstrap_command(command: CliCommand) -> anyhow::Result<()> {
                 };
                 odin_migration::run(odin_migration::MigrationCommand::Validate { bundle_dir })
             }
-            MigrateSubcommand::Import => {
-                odin_migration::run(odin_migration::MigrationCommand::Import)
+            MigrateSubcommand::Import {
+                bundle,
+                source_root,
+                odin_dir,
+                dry_run,
+            } => {
+                let bundle_dir = match bundle {
+                    Some(p) => p,
+                    None => {
+                        eprintln!("missing required flag: --bundle");
+                        process::exit(1);
+                    }
+                };
+                let source_root = match source_root {
+                    Some(p) => p,
+                    None => {
+                        eprintln!("missing required flag: --source-root");
+                        process::exit(1);
+                    }
+                };
+                odin_migration::run(odin_migration::MigrationCommand::Import {
+                    bundle_dir,
+                    source_root,
+                    odin_dir,
+                    dry_run,
+                })
             }
             MigrateSubcommand::Unknown(args) => {
                 let name = args
@@ -1477,6 +3807,77 @@ fn handle_bootstrap_command(command: CliCommand) -> anyhow::Result<()> {
     }
 }
 
+fn print_runtime_metrics(metrics: &dyn RuntimeMetrics) -> anyhow::Result<()> {
+    let metrics_json = serde_json::to_string_pretty(&metrics.snapshot())
+        .context("failed to format runtime metrics")?;
+    println!("runtime metrics:\n{metrics_json}");
+    Ok(())
+}
+
+/// `*.json` files directly under an inbox directory, in name order.
+fn inbox_task_files(task_dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(task_dir)
+        .with_context(|| format!("failed to read task dir {}", task_dir.display()))?
+    {
+        let path = entry?.path();
+        if path.is_file() && path.extension().and_then(|ext| ext.to_str()) == Some("json") {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Moves an inbox task file into `outcome_dir`, suffixing its stem (`task-1.json`, ...) rather
+/// than overwriting a file archived earlier under the same name.
+fn archive_task_file(path: &Path, outcome_dir: &Path) -> anyhow::Result<PathBuf> {
+    fs::create_dir_all(outcome_dir)
+        .with_context(|| format!("failed to create {}", outcome_dir.display()))?;
+    let name = path
+        .file_name()
+        .with_context(|| format!("task file has no name: {}", path.display()))?;
+    let mut target = outcome_dir.join(name);
+    let stem = path.file_stem().unwrap_or(name).to_string_lossy();
+    let mut suffix = 1;
+    while target.exists() {
+        target = outcome_dir.join(format!("{stem}-{suffix}.json"));
+        suffix += 1;
+    }
+    fs::rename(path, &target)
+        .with_context(|| format!("failed to move task file {}", path.display()))?;
+    Ok(target)
+}
+
+/// How often the daemon applies task archive retention between signals.
+const TASK_RETENTION_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+fn task_retention_config(cfg: &CliConfig) -> Option<TaskRetentionConfig> {
+    let queue_dir = cfg.task_queue_dir.as_ref()?;
+    let config = TaskRetentionConfig::new(queue_dir)
+        .with_max_age(Duration::from_secs(cfg.task_retention_days * 24 * 60 * 60))
+        .with_daily_archives(cfg.task_archive_daily);
+    Some(match cfg.task_retention_max_bytes {
+        Some(max_bytes) => config.with_max_bytes(max_bytes),
+        None => config,
+    })
+}
+
+fn run_task_archive_retention(
+    config: &TaskRetentionConfig,
+    audit: &Arc<dyn AuditSink>,
+) -> anyhow::Result<()> {
+    let report = run_task_retention(config, audit, SystemTime::now())
+        .context("task archive retention failed")?;
+    println!(
+        "task retention: {} archives, {} removed, {} bytes freed",
+        report.archives.len(),
+        report.removed.len(),
+        report.bytes_freed
+    );
+    Ok(())
+}
+
 fn run_legacy_runtime(cfg: CliConfig) -> anyhow::Result<()> {
     println!("odin-cli starting with config: {}", cfg.config_path);
     println!("plugins root: {}", cfg.plugins_root.display());
@@ -1512,31 +3913,200 @@ fn run_legacy_runtime(cfg: CliConfig) -> anyhow::Result<()> {
         }
     }
 
-    let mut policy = StaticPolicyEngine::default();
-    policy.set_require_approval_for_destructive(true);
-    policy.allow_capability("example.safe-github", "*", "repo.read");
-    policy.allow_capability("private.ops-watchdog", "*", "monitoring.sentry.read");
-    policy.allow_capability("private.ops-watchdog", "*", "vcs.pr.read");
-    policy.allow_capability("private.ops-watchdog", "*", "task.enqueue");
+    let audit: Arc<dyn AuditSink> = Arc::from(audit_sink(&cfg)?);
+    if cfg.legacy_odin_dir.is_dir() {
+        let report = run_selfcheck(
+            &selfcheck_config(cfg.legacy_odin_dir.clone(), Some(approvals_dir(&cfg))),
+            &audit,
+        )
+        .context("startup selfcheck failed")?;
+        println!(
+            "startup selfcheck: {} checked, {} findings",
+            report.checked.len(),
+            report.findings.len()
+        );
+    }
+
+    let policy = load_policy(&cfg)?;
+    if let Some(dir) = &cfg.policy_bundle {
+        println!(
+            "policy bundle: {} version {}",
+            dir.display(),
+            policy.version().unwrap_or_default()
+        );
+    } else if let Some(path) = &cfg.policy_file {
+        println!("policy file: {}", path.display());
+    }
+
+    // SIGINT/SIGTERM let the current directive finish, kill plugin children, and flush
+    // audit before exiting.
+    let shutdown = CancellationToken::new();
+    {
+        let shutdown = shutdown.clone();
+        ctrlc::set_handler(move || shutdown.cancel())
+            .context("failed to install shutdown signal handler")?;
+    }
 
-    let runtime = OrchestratorRuntime::new(policy, NoopAuditSink, DryRunExecutor);
+    // Secret resolutions are decided by the same policy and audited to the same log.
+    let policy: Arc<dyn PolicyEngine> = Arc::from(policy);
+    let secrets: Arc<dyn SecretStore> = Arc::new(PolicyGatedSecretStore::new(
+        secret_store(&cfg)?,
+        policy.clone(),
+        audit.clone(),
+    ));
+
+    let egress = egress_policy(&cfg)?;
+    let entrypoints = entrypoint_policy(&cfg)?;
+    let plugin_runner = || {
+        let runner = ExternalProcessPluginRunner::new(cfg.plugins_root.clone())
+            .with_entrypoint_policy(entrypoints.clone())
+            .with_cancellation(shutdown.clone())
+            .with_secret_store(secrets.clone())
+            .with_audit_sink(audit.clone());
+        match &egress {
+            Some(egress) => runner.with_egress_policy(egress.clone()),
+            None => runner,
+        }
+    };
+
+    let metrics = Arc::new(InMemoryRuntimeMetrics::default());
+    let executor = action_executor(&cfg, &audit, egress.as_ref())?;
+    let mut runtime = OrchestratorRuntime::new(policy, audit.clone(), executor)
+        .with_elevation_overlay(Arc::new(ElevationOverlay::file(
+            cfg.legacy_odin_dir.join("policy-elevations.json"),
+        )))
+        .with_metrics(metrics.clone())
+        .with_cancellation(shutdown.clone())
+        .with_secret_store(secrets.clone())
+        .with_permission_registry(permission_registry(&cfg, &audit)?)
+        .with_revocation_list(RevocationList::file(
+            config_dir(&cfg).join(REVOCATIONS_FILE),
+        ))
+        .with_separation_of_duties(separation_of_duties(&cfg)?)
+        .with_task_router(task_router(&cfg)?);
+
+    if cfg.legacy_odin_dir.is_dir() {
+        runtime = runtime
+            .with_approval_store(FileApprovalStore::new(approvals_dir(&cfg)))
+            .with_checkpoint_store(FileCheckpointStore::new(
+                cfg.legacy_odin_dir.join("checkpoints"),
+            ))
+            .with_dead_letter_queue(FileDeadLetterQueue::new(
+                cfg.legacy_odin_dir.join("dead-letter"),
+            ));
+        let plugin_runner = plugin_runner();
+        let report = match &legacy_paths {
+            Some(paths) => runtime.reconcile(
+                cfg.reconcile_policy,
+                &plugin_runner,
+                &BashTaskIngressAdapter::from_paths(paths),
+            ),
+            None => runtime.reconcile(cfg.reconcile_policy, &plugin_runner, &StdoutTaskIngress),
+        }
+        .context("startup reconciliation failed")?;
+        println!("startup reconciliation: {}", report.summary());
+    }
 
     if let Some(task_file) = &cfg.task_file {
         let task_json = fs::read_to_string(task_file)
             .with_context(|| format!("failed to read task file {}", task_file.display()))?;
-        let plugin_runner = ExternalProcessPluginRunner::new(cfg.plugins_root.clone());
+        let plugin_runner = plugin_runner();
 
         let outcomes = if let Some(paths) = &legacy_paths {
             let ingress = BashTaskIngressAdapter::from_paths(paths);
-            runtime.handle_watchdog_task(&task_json, &plugin_runner, &ingress)?
+            runtime.handle_task(&task_json, &plugin_runner, &ingress)?
         } else {
             let ingress = StdoutTaskIngress;
-            runtime.handle_watchdog_task(&task_json, &plugin_runner, &ingress)?
+            runtime.handle_task(&task_json, &plugin_runner, &ingress)?
         };
 
         let outcomes_json =
             serde_json::to_string_pretty(&outcomes).context("failed to format task outcomes")?;
         println!("task outcomes:\n{outcomes_json}");
+        let warnings = aggregate_warnings(&outcomes);
+        if !warnings.is_empty() {
+            let warnings_json = serde_json::to_string_pretty(&warnings)
+                .context("failed to format task warnings")?;
+            println!("task warnings:\n{warnings_json}");
+        }
+        print_runtime_metrics(metrics.as_ref())?;
+        if shutdown.is_cancelled() {
+            runtime
+                .shutdown("signal")
+                .context("failed to flush audit on shutdown")?;
+        }
+        return Ok(());
+    }
+
+    if let Some(task_dir) = &cfg.task_dir {
+        // An unreadable file fails on its own; the readable ones still run.
+        let mut files = Vec::new();
+        let mut payloads = Vec::new();
+        let mut unreadable = Vec::new();
+        for path in inbox_task_files(task_dir)? {
+            match fs::read_to_string(&path) {
+                Ok(payload) => {
+                    files.push(path);
+                    payloads.push(payload);
+                }
+                Err(err) => {
+                    let error = format!("failed to read task file {}: {err}", path.display());
+                    unreadable.push((path, error));
+                }
+            }
+        }
+        let raw_tasks: Vec<&str> = payloads.iter().map(String::as_str).collect();
+        let plugin_runner = plugin_runner();
+
+        let mut summary = if let Some(paths) = &legacy_paths {
+            let ingress = BashTaskIngressAdapter::from_paths(paths);
+            runtime.handle_task_batch(
+                &raw_tasks,
+                &plugin_runner,
+                &ingress,
+                DEFAULT_TASK_BATCH_CONCURRENCY,
+            )?
+        } else {
+            let ingress = StdoutTaskIngress;
+            runtime.handle_task_batch(
+                &raw_tasks,
+                &plugin_runner,
+                &ingress,
+                DEFAULT_TASK_BATCH_CONCURRENCY,
+            )?
+        };
+        for (path, task) in files.iter().zip(&summary.tasks) {
+            let outcome = if task.error.is_some() {
+                "failed"
+            } else {
+                "done"
+            };
+            archive_task_file(path, &task_dir.join(outcome))?;
+        }
+        for (path, error) in unreadable {
+            eprintln!("{error}");
+            if let Err(err) = archive_task_file(&path, &task_dir.join("failed")) {
+                eprintln!("{err:#}");
+            }
+            summary.tasks.push(TaskBatchResult {
+                index: summary.total,
+                task_id: None,
+                outcomes: Vec::new(),
+                error: Some(error),
+            });
+            summary.total += 1;
+            summary.failed += 1;
+        }
+
+        let summary_json =
+            serde_json::to_string_pretty(&summary).context("failed to format task batch")?;
+        println!("task batch:\n{summary_json}");
+        print_runtime_metrics(metrics.as_ref())?;
+        if shutdown.is_cancelled() {
+            runtime
+                .shutdown("signal")
+                .context("failed to flush audit on shutdown")?;
+        }
         return Ok(());
     }
 
@@ -1548,16 +4118,280 @@ fn run_legacy_runtime(cfg: CliConfig) -> anyhow::Result<()> {
         serde_json::to_string_pretty(&outcome).context("failed to format bootstrap outcome")?;
     println!("bootstrap outcome:\n{outcome_json}");
 
+    let retention = task_retention_config(&cfg);
+    if let Some(retention) = &retention {
+        run_task_archive_retention(retention, &audit)?;
+    }
     if cfg.run_once {
         return Ok(());
     }
 
-    loop {
-        thread::sleep(Duration::from_secs(60));
+    match &retention {
+        Some(retention) => {
+            // A failed sweep is retried next interval rather than taking the daemon down.
+            while !shutdown.wait_timeout(TASK_RETENTION_INTERVAL) {
+                if let Err(err) = run_task_archive_retention(retention, &audit) {
+                    eprintln!("{err:#}");
+                }
+            }
+        }
+        None => shutdown.wait(),
+    }
+    runtime
+        .shutdown("signal")
+        .context("failed to flush audit on shutdown")?;
+    println!("odin-cli shut down");
+    Ok(())
+}
+
+/// Audit log rotation threshold for `--audit-log`.
+const AUDIT_LOG_MAX_BYTES: u64 = 64 * 1024 * 1024;
+
+fn audit_sink(cfg: &CliConfig) -> anyhow::Result<Box<dyn AuditSink>> {
+    let sink = open_audit_sink(cfg)?;
+    if let Some(path) = &cfg.audit_log {
+        println!("audit log: {}", path.display());
+    }
+    Ok(sink)
+}
+
+/// `audit_sink` without the startup banner, for commands whose stdout is a report.
+fn open_audit_sink(cfg: &CliConfig) -> anyhow::Result<Box<dyn AuditSink>> {
+    let Some(path) = &cfg.audit_log else {
+        return Ok(Box::new(NoopAuditSink));
+    };
+    let sink = FileAuditSink::open(path)
+        .with_context(|| format!("failed to open audit log {}", path.display()))?
+        .with_max_bytes(AUDIT_LOG_MAX_BYTES);
+    Ok(Box::new(
+        EnrichedAuditSink::new(sink).with_enricher(Redactor::default()),
+    ))
+}
+
+/// Pending approvals of the daemon, under `--legacy-odin-dir`.
+fn approvals_dir(cfg: &CliConfig) -> PathBuf {
+    cfg.legacy_odin_dir.join("approvals")
+}
+
+/// Top-level `key` of the config file; `None` when the file or the section is missing.
+fn config_section(cfg: &CliConfig, key: &str) -> anyhow::Result<Option<Value>> {
+    let raw = match fs::read_to_string(&cfg.config_path) {
+        Ok(raw) => raw,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => {
+            return Err(err).with_context(|| format!("failed to read config {}", cfg.config_path))
+        }
+    };
+    let mut config: Value = serde_yml::from_str(&raw)
+        .with_context(|| format!("failed to parse config {}", cfg.config_path))?;
+    Ok(config.get_mut(key).map(Value::take))
+}
+
+/// The backend named by the config's `secrets:` section; handle-only when there is none. A
+/// `secret_cache:` section puts an in-memory value cache in front of it.
+fn secret_store(cfg: &CliConfig) -> anyhow::Result<Box<dyn SecretStore>> {
+    let store = secret_store_with_rotation(cfg, &RotationPolicy::default())?;
+    let Some(section) = config_section(cfg, "secret_cache")? else {
+        return Ok(store);
+    };
+    let cache: SecretCacheConfig = serde_json::from_value(section)
+        .with_context(|| format!("invalid secret_cache section in {}", cfg.config_path))?;
+    Ok(Box::new(
+        CachedSecretStore::new(store).with_ttl(Duration::from_secs(cache.ttl_secs)),
+    ))
+}
+
+/// Directory of the config file, where `plugin-permissions.yaml` and its revocations live.
+fn config_dir(cfg: &CliConfig) -> &Path {
+    Path::new(&cfg.config_path)
+        .parent()
+        .unwrap_or(Path::new(""))
+}
+
+/// `plugin-permissions.yaml` beside the config file; no envelopes are registered without it.
+/// Scope templates from `scope-templates.yaml` there are expanded once here, and each expansion
+/// is audited.
+fn permission_registry(
+    cfg: &CliConfig,
+    audit: &Arc<dyn AuditSink>,
+) -> anyhow::Result<PluginPermissionRegistry> {
+    let path = config_dir(cfg).join(PERMISSIONS_FILE);
+    if !path.exists() {
+        return Ok(PluginPermissionRegistry::new());
+    }
+    let registry = load_permission_registry(&path)
+        .with_context(|| format!("failed to load permission envelopes {}", path.display()))?;
+    let templates_path = config_dir(cfg).join(SCOPE_TEMPLATES_FILE);
+    if !templates_path.exists() {
+        return Ok(registry);
+    }
+    let (registry, expansions) = load_scope_templates(&templates_path)
+        .and_then(|templates| templates.expand_registry(&registry))
+        .with_context(|| {
+            format!(
+                "failed to expand scope templates {}",
+                templates_path.display()
+            )
+        })?;
+    for expansion in expansions {
+        audit.record(AuditRecord {
+            ts_unix: now_unix_timestamp(),
+            event_type: taxonomy::GOVERNANCE_SCOPE_EXPANDED.to_string(),
+            severity: taxonomy::severity(taxonomy::GOVERNANCE_SCOPE_EXPANDED),
+            request_id: None,
+            task_id: None,
+            project: Some(expansion.project),
+            trace_id: None,
+            metadata: json!({
+                "capability": expansion.capability,
+                "template": expansion.template,
+                "scopes": expansion.scopes
+            }),
+        })?;
+    }
+    Ok(registry)
+}
+
+/// The `egress:` config section; plugin processes get no egress rules when it is absent.
+fn egress_policy(cfg: &CliConfig) -> anyhow::Result<Option<Arc<EgressPolicy>>> {
+    let Some(section) = config_section(cfg, "egress")? else {
+        return Ok(None);
+    };
+    let policy: EgressPolicy = serde_json::from_value(section)
+        .with_context(|| format!("invalid egress section in {}", cfg.config_path))?;
+    policy
+        .validate()
+        .with_context(|| format!("invalid egress section in {}", cfg.config_path))?;
+    Ok(Some(Arc::new(policy)))
+}
+
+/// Follow-up kinds the bundled plugins enqueue, routed to their plugin besides `watchdog_poll`.
+const FOLLOWUP_TASK_KINDS: &[&str] = &["watchdog.remediation.dispatch"];
+
+/// Routes `watchdog_poll`, the bundled follow-up kinds and any kinds listed in a `task_kinds:`
+/// config section to their plugin as `task.received` events.
+fn task_router(cfg: &CliConfig) -> anyhow::Result<TaskRouter> {
+    let configured: Vec<String> = match config_section(cfg, "task_kinds")? {
+        Some(section) => serde_json::from_value(section)
+            .with_context(|| format!("invalid task_kinds section in {}", cfg.config_path))?,
+        None => Vec::new(),
+    };
+    let mut router = TaskRouter::default();
+    for kind in FOLLOWUP_TASK_KINDS
+        .iter()
+        .map(|kind| kind.to_string())
+        .chain(configured)
+    {
+        router.register(kind, PluginTaskHandler);
+    }
+    Ok(router)
+}
+
+/// Executor for allowed actions: the native HTTP executor for the capabilities listed in an
+/// `http_capabilities:` config section, dry runs for everything else.
+struct CliExecutor {
+    http: Option<HttpActionExecutor>,
+}
+
+impl ActionExecutor for CliExecutor {
+    fn execute(&self, request: &ActionRequest) -> Result<Value, RuntimeError> {
+        match &self.http {
+            Some(http) if http.handles(&request.capability.capability) => http.execute(request),
+            _ => DryRunExecutor.execute(request),
+        }
+    }
+}
+
+/// HTTP requests are audited to `audit` and checked against the `egress:` rules as well as
+/// each capability's own allowlist.
+fn action_executor(
+    cfg: &CliConfig,
+    audit: &Arc<dyn AuditSink>,
+    egress: Option<&Arc<EgressPolicy>>,
+) -> anyhow::Result<CliExecutor> {
+    let Some(section) = config_section(cfg, "http_capabilities")? else {
+        return Ok(CliExecutor { http: None });
+    };
+    let capabilities: Vec<HttpCapability> = serde_json::from_value(section)
+        .with_context(|| format!("invalid http_capabilities section in {}", cfg.config_path))?;
+    let mut executor = capabilities
+        .into_iter()
+        .fold(
+            HttpActionExecutor::new(),
+            HttpActionExecutor::with_capability,
+        )
+        .with_audit_sink(audit.clone());
+    if let Some(egress) = egress {
+        executor = executor.with_egress_policy(egress.clone());
+    }
+    Ok(CliExecutor {
+        http: Some(executor),
+    })
+}
+
+/// Interpreters the bundled plugins (`plugins/huginn`, `plugins/gmail`) name as their entrypoint.
+const BUNDLED_PLUGIN_INTERPRETERS: &[&str] = &["node"];
+
+/// The `plugin_entrypoints:` config section (`allowed_bins`, `allow_path_lookup`). Without it,
+/// entrypoints stay inside the plugin directory except for the bundled plugins' interpreters.
+fn entrypoint_policy(cfg: &CliConfig) -> anyhow::Result<EntrypointPolicy> {
+    match config_section(cfg, "plugin_entrypoints")? {
+        Some(section) => serde_json::from_value(section)
+            .with_context(|| format!("invalid plugin_entrypoints section in {}", cfg.config_path)),
+        None => Ok(BUNDLED_PLUGIN_INTERPRETERS
+            .iter()
+            .fold(EntrypointPolicy::plugin_dir_only(), |policy, bin| {
+                policy.allow_bin(*bin)
+            })),
+    }
+}
+
+/// The `separation_of_duties:` config section; self-approval is allowed when it is absent.
+fn separation_of_duties(cfg: &CliConfig) -> anyhow::Result<SeparationOfDuties> {
+    match config_section(cfg, "separation_of_duties")? {
+        Some(section) => serde_json::from_value(section).with_context(|| {
+            format!(
+                "invalid separation_of_duties section in {}",
+                cfg.config_path
+            )
+        }),
+        None => Ok(SeparationOfDuties::default()),
     }
 }
 
+fn secret_store_with_rotation(
+    cfg: &CliConfig,
+    rotation: &RotationPolicy,
+) -> anyhow::Result<Box<dyn SecretStore>> {
+    let backend: SecretBackendConfig = match config_section(cfg, "secrets")? {
+        Some(section) => serde_json::from_value(section)
+            .with_context(|| format!("invalid secrets section in {}", cfg.config_path))?,
+        None => SecretBackendConfig::default(),
+    };
+    backend
+        .build_with_rotation(rotation)
+        .with_context(|| format!("failed to open secret backend {backend:?}"))
+}
+
 fn main() -> anyhow::Result<()> {
+    let Err(err) = run() else {
+        return Ok(());
+    };
+    // Runtime failures are reported as a structured envelope so scripts can match on
+    // `error.code` instead of parsing messages.
+    let Some(runtime_err) = err
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<RuntimeError>())
+    else {
+        return Err(err);
+    };
+    let mut envelope = runtime_err.envelope();
+    envelope.message = format!("{err:#}");
+    eprintln!("{}", json!({ "error": envelope }));
+    process::exit(1);
+}
+
+fn run() -> anyhow::Result<()> {
     let raw_args: Vec<String> = env::args().collect();
     if let Some(outcome) = try_handle_governance_command(&raw_args[1..]) {
         match outcome.body {
@@ -1579,11 +4413,21 @@ fn main() -> anyhow::Result<()> {
                 legacy_odin_dir: cli.legacy_odin_dir.clone(),
                 plugins_root: cli.plugins_root.clone(),
                 task_file: cli.task_file.clone(),
+                task_dir: cli.task_dir.clone(),
                 run_once: cli.run_once,
+                task_queue_dir: cli.task_queue_dir.clone(),
+                task_retention_days: cli.task_retention_days,
+                task_retention_max_bytes: cli.task_retention_max_bytes,
+                task_archive_daily: cli.task_archive_daily,
+                reconcile_policy: cli.reconcile_policy,
+                policy_file: cli.policy_file.clone(),
+                policy_bundle: cli.policy_bundle.clone(),
+                policy_bundle_key: cli.policy_bundle_key.clone(),
+                audit_log: cli.audit_log.clone(),
             };
 
             if let Some(command) = cli.command {
-                handle_bootstrap_command(command)?;
+                handle_bootstrap_command(command, &cfg)?;
                 return Ok(());
             }
 
@@ -0,0 +1,145 @@
+//! `odin-cli policy diff`: semantic comparison of two `policy.yaml` or
+//! `plugin-permissions.yaml` files.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{anyhow, Context};
+use odin_governance::diff::{diff_permission_envelopes, GovernanceChange, RiskDelta};
+use odin_plugin_protocol::PluginPermissionEnvelope;
+use serde_json::Value;
+
+pub fn diff_policy_files(old: &Path, new: &Path) -> anyhow::Result<Vec<GovernanceChange>> {
+    let old_doc = load_yaml(old)?;
+    let new_doc = load_yaml(new)?;
+    match (old_doc.get("envelopes"), new_doc.get("envelopes")) {
+        (Some(old_envelopes), Some(new_envelopes)) => Ok(diff_permission_envelopes(
+            &envelopes(old, old_envelopes)?,
+            &envelopes(new, new_envelopes)?,
+        )),
+        (None, None) => diff_policies(&old_doc, &new_doc),
+        _ => Err(anyhow!(
+            "cannot diff a policy file against a permission envelope file"
+        )),
+    }
+}
+
+fn load_yaml(path: &Path) -> anyhow::Result<Value> {
+    let raw =
+        fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+    serde_yml::from_str(&raw).with_context(|| format!("failed to parse {}", path.display()))
+}
+
+fn envelopes(path: &Path, value: &Value) -> anyhow::Result<Vec<PluginPermissionEnvelope>> {
+    serde_json::from_value(value.clone())
+        .with_context(|| format!("invalid envelopes in {}", path.display()))
+}
+
+fn diff_policies(old: &Value, new: &Value) -> anyhow::Result<Vec<GovernanceChange>> {
+    let mut changes = Vec::new();
+    let approval = |doc: &Value| {
+        doc.get("require_approval_for_destructive")
+            .and_then(Value::as_bool)
+            .unwrap_or(false)
+    };
+    if approval(old) != approval(new) {
+        changes.push(GovernanceChange::new(
+            "destructive_approval_changed",
+            "policy",
+            format!("{} -> {}", approval(old), approval(new)),
+            if approval(new) {
+                RiskDelta::Decrease
+            } else {
+                RiskDelta::Increase
+            },
+        ));
+    }
+
+    let old_grants = grants(old)?;
+    let new_grants = grants(new)?;
+    for (plugin, project, capability) in new_grants.difference(&old_grants) {
+        changes.push(GovernanceChange::new(
+            "grant_added",
+            plugin.as_str(),
+            format!("{capability} in {project}"),
+            RiskDelta::Increase,
+        ));
+    }
+    for (plugin, project, capability) in old_grants.difference(&new_grants) {
+        changes.push(GovernanceChange::new(
+            "grant_removed",
+            plugin.as_str(),
+            format!("{capability} in {project}"),
+            RiskDelta::Decrease,
+        ));
+    }
+    Ok(changes)
+}
+
+/// Expands `grants` into (plugin, project, capability) triples.
+fn grants(doc: &Value) -> anyhow::Result<BTreeSet<(String, String, String)>> {
+    let mut triples = BTreeSet::new();
+    let Some(grants) = doc.get("grants") else {
+        return Ok(triples);
+    };
+    let grants = grants
+        .as_array()
+        .ok_or_else(|| anyhow!("`grants` must be a list"))?;
+    let strings = |grant: &Value, key: &str| -> Vec<String> {
+        grant
+            .get(key)
+            .and_then(Value::as_array)
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .map(ToString::to_string)
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    for grant in grants {
+        let plugin = grant
+            .get("plugin")
+            .and_then(Value::as_str)
+            .ok_or_else(|| anyhow!("every grant needs a `plugin`"))?;
+        let mut projects = strings(grant, "projects");
+        if projects.is_empty() {
+            projects.push("*".to_string());
+        }
+        for project in &projects {
+            for capability in strings(grant, "capabilities") {
+                triples.insert((plugin.to_string(), project.clone(), capability));
+            }
+        }
+    }
+    Ok(triples)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn policy_diff_expands_grants_and_flags_approval_loss() {
+        let old: Value = serde_yml::from_str(
+            "schema_version: 1\nrequire_approval_for_destructive: true\ngrants:\n  - plugin: example.safe-github\n    projects: [demo]\n    capabilities: [repo.read]\n",
+        )
+        .expect("yaml");
+        let new: Value = serde_yml::from_str(
+            "schema_version: 1\nrequire_approval_for_destructive: false\ngrants:\n  - plugin: example.safe-github\n    projects: [demo, \"*\"]\n    capabilities: [repo.read]\n",
+        )
+        .expect("yaml");
+
+        let changes = diff_policies(&old, &new).expect("diff");
+        let rendered: Vec<String> = changes.iter().map(GovernanceChange::render).collect();
+        assert_eq!(
+            rendered,
+            vec![
+                "! destructive_approval_changed policy: true -> false",
+                "! grant_added example.safe-github: repo.read in *",
+            ]
+        );
+    }
+}
@@ -0,0 +1,262 @@
+//! `odin-cli policy init`: interviews the operator and writes a starting policy file
+//! plus matching plugin permission envelopes.
+
+use std::fs;
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context};
+
+pub const POLICY_FILE: &str = "policy.yaml";
+pub const ENVELOPES_FILE: &str = "plugin-permissions.yaml";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PluginGrant {
+    pub plugin: String,
+    pub capabilities: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PolicyInitAnswers {
+    pub grants: Vec<PluginGrant>,
+    pub projects: Vec<String>,
+    /// One of `trusted`, `caution`, `untrusted`.
+    pub trust_level: String,
+    pub require_approval_for_destructive: bool,
+}
+
+pub fn interview<R: BufRead, W: Write>(
+    input: &mut R,
+    output: &mut W,
+) -> anyhow::Result<PolicyInitAnswers> {
+    writeln!(
+        output,
+        "odin policy init: answer each prompt; defaults in [brackets]."
+    )?;
+
+    let plugins = loop {
+        let plugins = split_csv(&ask(
+            input,
+            output,
+            "Plugins to grant (comma-separated)",
+            "",
+        )?);
+        if !plugins.is_empty() {
+            break plugins;
+        }
+        writeln!(output, "at least one plugin is required")?;
+    };
+
+    let mut grants = Vec::with_capacity(plugins.len());
+    for plugin in plugins {
+        let capabilities = split_csv(&ask(
+            input,
+            output,
+            &format!("Capabilities for {plugin} (comma-separated)"),
+            "",
+        )?);
+        grants.push(PluginGrant {
+            plugin,
+            capabilities,
+        });
+    }
+
+    let projects = split_csv(&ask(
+        input,
+        output,
+        "Projects (comma-separated, * for all)",
+        "*",
+    )?);
+
+    let trust_level = loop {
+        let value = ask(
+            input,
+            output,
+            "Trust posture (trusted/caution/untrusted)",
+            "caution",
+        )?
+        .to_ascii_lowercase();
+        if matches!(value.as_str(), "trusted" | "caution" | "untrusted") {
+            break value;
+        }
+        writeln!(output, "expected one of: trusted, caution, untrusted")?;
+    };
+
+    let require_approval_for_destructive = loop {
+        let value = ask(
+            input,
+            output,
+            "Require approval for destructive actions? (y/n)",
+            "y",
+        )?
+        .to_ascii_lowercase();
+        match value.as_str() {
+            "y" | "yes" => break true,
+            "n" | "no" => break false,
+            _ => writeln!(output, "expected y or n")?,
+        }
+    };
+
+    Ok(PolicyInitAnswers {
+        grants,
+        projects,
+        trust_level,
+        require_approval_for_destructive,
+    })
+}
+
+pub fn render_policy(answers: &PolicyInitAnswers) -> String {
+    let mut out = String::new();
+    out.push_str("# Odin policy generated by `odin-cli policy init`.\n");
+    out.push_str("# Default deny: only the grants below are allowed.\n");
+    out.push_str("schema_version: 1\n\n");
+    out.push_str("# Destructive-tier requests pause for an explicit approval when true.\n");
+    out.push_str(&format!(
+        "require_approval_for_destructive: {}\n\n",
+        answers.require_approval_for_destructive
+    ));
+    out.push_str("# Each grant allows `capabilities` for `plugin` in `projects` (\"*\" = any).\n");
+    out.push_str("grants:\n");
+    for grant in &answers.grants {
+        out.push_str(&format!("  - plugin: {}\n", yaml_string(&grant.plugin)));
+        out.push_str(&format!("    projects: {}\n", yaml_list(&answers.projects)));
+        if grant.capabilities.is_empty() {
+            out.push_str("    # TODO: list the capability ids this plugin may use.\n");
+        }
+        out.push_str(&format!(
+            "    capabilities: {}\n",
+            yaml_list(&grant.capabilities)
+        ));
+    }
+    out
+}
+
+pub fn render_envelopes(answers: &PolicyInitAnswers) -> String {
+    let mut out = String::new();
+    out.push_str("# Plugin permission envelopes generated by `odin-cli policy init`.\n");
+    out.push_str("# Scopes default to `project`; narrow them to domains or paths as needed.\n");
+    out.push_str("envelopes:\n");
+    for grant in &answers.grants {
+        out.push_str(&format!("  - plugin: {}\n", yaml_string(&grant.plugin)));
+        out.push_str(&format!("    trust_level: {}\n", answers.trust_level));
+        if grant.capabilities.is_empty() {
+            out.push_str("    permissions: []\n");
+            continue;
+        }
+        out.push_str("    permissions:\n");
+        for capability in &grant.capabilities {
+            out.push_str(&format!("      - id: {}\n", yaml_string(capability)));
+            out.push_str("        scope: [\"project\"]\n");
+        }
+    }
+    out
+}
+
+/// Writes both generated files into `out_dir`, refusing to clobber existing files
+/// unless `force` is set. Returns the written paths.
+pub fn write_policy_files(
+    out_dir: &Path,
+    answers: &PolicyInitAnswers,
+    force: bool,
+) -> anyhow::Result<Vec<PathBuf>> {
+    let files = [
+        (out_dir.join(POLICY_FILE), render_policy(answers)),
+        (out_dir.join(ENVELOPES_FILE), render_envelopes(answers)),
+    ];
+    if !force {
+        if let Some((path, _)) = files.iter().find(|(path, _)| path.exists()) {
+            return Err(anyhow!(
+                "{} already exists; pass --force to overwrite",
+                path.display()
+            ));
+        }
+    }
+
+    fs::create_dir_all(out_dir)
+        .with_context(|| format!("failed to create {}", out_dir.display()))?;
+    let mut written = Vec::with_capacity(files.len());
+    for (path, body) in files {
+        fs::write(&path, body).with_context(|| format!("failed to write {}", path.display()))?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+fn ask<R: BufRead, W: Write>(
+    input: &mut R,
+    output: &mut W,
+    prompt: &str,
+    default: &str,
+) -> anyhow::Result<String> {
+    if default.is_empty() {
+        write!(output, "{prompt}: ")?;
+    } else {
+        write!(output, "{prompt} [{default}]: ")?;
+    }
+    output.flush()?;
+
+    let mut line = String::new();
+    if input.read_line(&mut line)? == 0 {
+        return Err(anyhow!("policy init aborted: unexpected end of input"));
+    }
+    let answer = line.trim();
+    Ok(if answer.is_empty() {
+        default.to_string()
+    } else {
+        answer.to_string()
+    })
+}
+
+fn split_csv(value: &str) -> Vec<String> {
+    let mut values = Vec::new();
+    for item in value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+    {
+        if !values.iter().any(|existing| existing == item) {
+            values.push(item.to_string());
+        }
+    }
+    values
+}
+
+fn yaml_string(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_else(|_| "\"\"".to_string())
+}
+
+fn yaml_list(values: &[String]) -> String {
+    let items: Vec<String> = values.iter().map(|value| yaml_string(value)).collect();
+    format!("[{}]", items.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interview_applies_defaults_and_reprompts_invalid_answers() {
+        let mut input = "\nexample.safe-github, private.ops-watchdog\nrepo.read\n\n\nparanoid\nuntrusted\nmaybe\nn\n"
+            .as_bytes();
+        let mut output = Vec::new();
+
+        let answers = interview(&mut input, &mut output).expect("answers");
+
+        assert_eq!(answers.grants.len(), 2);
+        assert_eq!(answers.grants[0].capabilities, vec!["repo.read"]);
+        assert!(answers.grants[1].capabilities.is_empty());
+        assert_eq!(answers.projects, vec!["*"]);
+        assert_eq!(answers.trust_level, "untrusted");
+        assert!(!answers.require_approval_for_destructive);
+        let transcript = String::from_utf8(output).expect("utf8");
+        assert!(transcript.contains("at least one plugin is required"));
+        assert!(transcript.contains("expected y or n"));
+    }
+
+    #[test]
+    fn interview_fails_on_truncated_input() {
+        let mut input = "example.safe-github\n".as_bytes();
+        let err = interview(&mut input, &mut Vec::new()).expect_err("truncated");
+        assert!(err.to_string().contains("unexpected end of input"));
+    }
+}
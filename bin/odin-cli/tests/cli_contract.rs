@@ -1,5 +1,7 @@
 use std::time::Duration;
 
+use odin_secrets::file::{EncryptedFileSecretStore, KeySource};
+use odin_secrets::SecretHandle;
 use predicates::str::contains;
 
 fn assert_dry_run_contract(args: &[&str], expected_fragment: &str) {
@@ -91,3 +93,417 @@ fn unknown_args_fallback_to_legacy_parser() {
         .success()
         .stdout(contains("bootstrap outcome:"));
 }
+
+#[test]
+fn runtime_errors_are_reported_as_structured_envelopes() {
+    let root = tempfile::tempdir().expect("tempdir");
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("odin-cli");
+    cmd.args(["restore-snapshot", "--snapshot-root"])
+        .arg(root.path())
+        .arg("snap-missing")
+        .timeout(Duration::from_secs(3));
+
+    cmd.assert()
+        .failure()
+        .stderr(contains(r#""code":"invalid_input""#))
+        .stderr(contains(r#""phase":"validation""#));
+}
+
+#[test]
+fn http_capabilities_from_config_run_through_the_http_executor() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let config = dir.path().join("config.yaml");
+    std::fs::write(
+        &config,
+        "http_capabilities:\n  - capability: repo.read\n    allow: [api.github.com]\n",
+    )
+    .expect("write config");
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("odin-cli");
+    cmd.args(["--run-once", "--config"])
+        .arg(&config)
+        .timeout(Duration::from_secs(3));
+    cmd.assert()
+        .success()
+        .stdout(contains("repo.read requires input.url"));
+}
+
+#[test]
+fn run_once_applies_task_archive_retention() {
+    let queue = tempfile::tempdir().expect("tempdir");
+    let done = queue.path().join("done");
+    std::fs::create_dir_all(&done).expect("create done dir");
+    std::fs::write(done.join("task-1.json"), "{}").expect("write task");
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("odin-cli");
+    cmd.args(["--run-once", "--task-queue-dir"])
+        .arg(queue.path())
+        .args(["--task-retention-max-bytes", "0"])
+        .timeout(Duration::from_secs(3));
+
+    cmd.assert().success().stdout(contains(
+        "task retention: 0 archives, 1 removed, 2 bytes freed",
+    ));
+    assert!(!done.join("task-1.json").exists());
+}
+
+#[test]
+fn audit_log_retains_runtime_audit_records() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let log = dir.path().join("audit").join("audit.jsonl");
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("odin-cli");
+    cmd.args(["--run-once", "--audit-log"])
+        .arg(&log)
+        .timeout(Duration::from_secs(3));
+    cmd.assert().success();
+
+    let contents = std::fs::read_to_string(&log).expect("read audit log");
+    let events: Vec<String> = contents
+        .lines()
+        .map(|line| {
+            let record: serde_json::Value = serde_json::from_str(line).expect("json line");
+            record["event_type"]
+                .as_str()
+                .expect("event type")
+                .to_string()
+        })
+        .collect();
+    assert!(events.iter().any(|event| event == "policy.decision"));
+}
+
+#[test]
+fn audit_prune_compacts_old_records_into_rollups() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let log = dir.path().join("audit.jsonl");
+    let records = [1_000, u64::MAX / 2]
+        .map(|ts| format!(r#"{{"ts_unix":{ts},"event_type":"policy.decision","metadata":null}}"#));
+    std::fs::write(&log, records.join("\n") + "\n").expect("write log");
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("odin-cli");
+    cmd.args(["audit", "prune", "--older-than-days", "30", "--log"])
+        .arg(&log)
+        .timeout(Duration::from_secs(3));
+    cmd.assert()
+        .success()
+        .stdout(contains("\"records_pruned\": 1"));
+
+    let remaining = std::fs::read_to_string(&log).expect("read log");
+    assert_eq!(remaining.lines().count(), 1);
+    let rollups =
+        std::fs::read_to_string(dir.path().join("audit.jsonl.rollups.json")).expect("read rollups");
+    assert!(rollups.contains("\"policy.decision\": 1"));
+}
+
+#[test]
+fn audit_verify_reports_the_first_edited_record() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let log = dir.path().join("audit.jsonl");
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("odin-cli");
+    cmd.args(["--run-once", "--audit-log"])
+        .arg(&log)
+        .timeout(Duration::from_secs(3));
+    cmd.assert().success();
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("odin-cli");
+    cmd.args(["audit", "verify", "--log"])
+        .arg(&log)
+        .timeout(Duration::from_secs(3));
+    cmd.assert()
+        .success()
+        .stdout(contains("\"divergence\": null"));
+
+    let contents = std::fs::read_to_string(&log).expect("read audit log");
+    std::fs::write(
+        &log,
+        contents.replacen("policy.decision", "policy.override", 1),
+    )
+    .expect("tamper");
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("odin-cli");
+    cmd.args(["--audit-log"])
+        .arg(&log)
+        .args(["audit", "verify", "--from", "0"])
+        .timeout(Duration::from_secs(3));
+    cmd.assert()
+        .failure()
+        .stdout(contains("does not match the record"));
+}
+
+#[test]
+fn config_secrets_section_selects_the_secret_backend() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let keyfile = dir.path().join("secrets.key");
+    std::fs::write(&keyfile, "ab".repeat(32)).expect("write keyfile");
+    let store = dir.path().join("secrets.json");
+    let config = dir.path().join("config.yaml");
+    std::fs::write(
+        &config,
+        format!(
+            "schema_version: 1\nsecrets:\n  backend: encrypted_file\n  path: {}\n  keyfile: {}\n",
+            store.display(),
+            keyfile.display()
+        ),
+    )
+    .expect("write config");
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("odin-cli");
+    cmd.args(["--run-once", "--config"])
+        .arg(&config)
+        .timeout(Duration::from_secs(3));
+    cmd.assert().success();
+    assert!(store.is_file(), "encrypted store should be created");
+
+    std::fs::write(&config, "secrets:\n  backend: vault\n").expect("write config");
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("odin-cli");
+    cmd.args(["--run-once", "--config"])
+        .arg(&config)
+        .timeout(Duration::from_secs(3));
+    cmd.assert()
+        .failure()
+        .stderr(contains("invalid secrets section"));
+}
+
+#[cfg(unix)]
+#[test]
+fn secrets_status_reports_overdue_handles_and_rotate_replaces_them() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let keyfile = dir.path().join("secrets.key");
+    std::fs::write(&keyfile, "ab".repeat(32)).expect("write keyfile");
+    let store_path = dir.path().join("secrets.json");
+    let handle = SecretHandle("secret://demo/example.git/token".to_string());
+    EncryptedFileSecretStore::open(&store_path, KeySource::Keyfile(keyfile.clone()))
+        .expect("open store")
+        .create(&handle, b"old")
+        .expect("create");
+    let config = dir.path().join("config.yaml");
+    std::fs::write(
+        &config,
+        format!(
+            "secrets:\n  backend: encrypted_file\n  path: {}\n  keyfile: {}\n\
+             secret_rotation:\n  max_age_days: 90\n  rules:\n    - handle: \"secret://demo/*\"\n      \
+             max_age_days: 0\n  hook: [sh, -c, \"echo rotated\"]\n",
+            store_path.display(),
+            keyfile.display()
+        ),
+    )
+    .expect("write config");
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("odin-cli");
+    cmd.args(["secrets", "status", "--config"])
+        .arg(&config)
+        .timeout(Duration::from_secs(3));
+    cmd.assert()
+        .failure()
+        .stdout(contains("\"overdue\": 1"))
+        .stdout(contains("secret://demo/example.git/token"));
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("odin-cli");
+    cmd.args(["secrets", "rotate", "--handle", &handle.0, "--config"])
+        .arg(&config)
+        .timeout(Duration::from_secs(3));
+    cmd.assert().success().stdout(contains("\"version\": 2"));
+    let store = EncryptedFileSecretStore::open(&store_path, KeySource::Keyfile(keyfile))
+        .expect("reopen store");
+    assert_eq!(
+        store.reveal(&handle).expect("reveal").as_slice(),
+        b"rotated"
+    );
+}
+
+#[test]
+fn task_dir_processes_inbox_as_a_batch_and_files_results() {
+    let inbox = tempfile::tempdir().expect("tempdir");
+    std::fs::write(inbox.path().join("broken.json"), "{not json").expect("write task");
+    std::fs::write(inbox.path().join("binary.json"), [0xff, 0xfe, 0x00]).expect("write task");
+    std::fs::write(inbox.path().join("notes.txt"), "ignored").expect("write note");
+    std::fs::create_dir(inbox.path().join("failed")).expect("failed dir");
+    std::fs::write(inbox.path().join("failed/broken.json"), "earlier").expect("archived task");
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("odin-cli");
+    cmd.arg("--task-dir")
+        .arg(inbox.path())
+        .timeout(Duration::from_secs(3));
+
+    cmd.assert()
+        .success()
+        .stdout(contains("task batch:"))
+        .stdout(contains("\"failed\": 2"))
+        .stdout(contains("failed to read task file"))
+        .stdout(contains("runtime metrics:"));
+    assert_eq!(
+        std::fs::read_to_string(inbox.path().join("failed/broken.json")).expect("earlier"),
+        "earlier"
+    );
+    assert!(inbox.path().join("failed/broken-1.json").is_file());
+    assert!(inbox.path().join("failed/binary.json").is_file());
+    assert!(inbox.path().join("notes.txt").is_file());
+}
+
+#[test]
+fn task_dir_routes_followup_kinds_and_configured_task_kinds() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let inbox = dir.path().join("inbox");
+    std::fs::create_dir(&inbox).expect("inbox");
+    let config = dir.path().join("config.yaml");
+    std::fs::write(&config, "task_kinds: [custom.followup]\n").expect("write config");
+    for kind in [
+        "watchdog.remediation.dispatch",
+        "custom.followup",
+        "other.kind",
+    ] {
+        let task = serde_json::json!({
+            "schema_version": 1,
+            "task_id": format!("task-{kind}"),
+            "type": kind,
+            "source": "plugin",
+            "created_at": "2026-02-25T00:00:00Z",
+            "payload": {
+                "task_type": "watchdog.followup",
+                "source_key": "followup",
+                "project": "private",
+                "plugin": "private.ops-watchdog",
+                "trigger": "followup"
+            }
+        });
+        std::fs::write(inbox.join(format!("{kind}.json")), task.to_string()).expect("task");
+    }
+    let plugins_root =
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../examples/private-plugins");
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("odin-cli");
+    cmd.arg("--plugins-root")
+        .arg(&plugins_root)
+        .arg("--config")
+        .arg(&config)
+        .arg("--task-dir")
+        .arg(&inbox)
+        .timeout(Duration::from_secs(10));
+    cmd.assert()
+        .success()
+        .stdout(contains("\"succeeded\": 2"))
+        .stdout(contains("unsupported task type: other.kind"));
+    assert!(inbox.join("done/custom.followup.json").is_file());
+    assert!(inbox
+        .join("done/watchdog.remediation.dispatch.json")
+        .is_file());
+}
+
+#[test]
+fn doctor_pings_installed_plugins_and_fails_on_unhealthy_ones() {
+    let plugins_root =
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../examples/private-plugins");
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("odin-cli");
+    cmd.arg("--plugins-root")
+        .arg(&plugins_root)
+        .arg("doctor")
+        .timeout(Duration::from_secs(10));
+    cmd.assert()
+        .success()
+        .stdout(contains("\"plugin\": \"private.ops-watchdog\""))
+        .stdout(contains("\"healthy\": true"));
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("odin-cli");
+    cmd.arg("--plugins-root")
+        .arg(&plugins_root)
+        .args(["doctor", "--plugin", "missing.plugin"])
+        .timeout(Duration::from_secs(10));
+    cmd.assert()
+        .code(1)
+        .stdout(contains("\"failed_stage\": \"manifest\""));
+}
+
+#[test]
+fn doctor_allows_entrypoint_interpreters_from_config() {
+    let root = tempfile::tempdir().expect("tempdir");
+    let plugin_dir = root.path().join("plugins/ops-watchdog");
+    std::fs::create_dir_all(plugin_dir.join("bin")).expect("create plugin dir");
+    let manifest = std::fs::read_to_string(
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../../examples/private-plugins/ops-watchdog/odin.plugin.yaml"),
+    )
+    .expect("read example manifest")
+    .replace(
+        "command: ./bin/plugin\n    args: [\"serve\"]",
+        "command: bash\n    args: [\"bin/plugin\"]",
+    );
+    assert!(manifest.contains("command: bash"));
+    std::fs::write(plugin_dir.join("odin.plugin.yaml"), manifest).expect("write manifest");
+    std::fs::write(
+        plugin_dir.join("bin/plugin"),
+        "read -r _event\necho '{\"action\":\"noop\"}'\n",
+    )
+    .expect("write plugin");
+    let config = root.path().join("config.yaml");
+    let doctor = |config_body: &str| {
+        std::fs::write(&config, config_body).expect("write config");
+        let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("odin-cli");
+        cmd.arg("--config")
+            .arg(&config)
+            .arg("--plugins-root")
+            .arg(root.path().join("plugins"))
+            .arg("doctor")
+            .timeout(Duration::from_secs(10));
+        cmd.assert()
+    };
+
+    doctor("schema_version: 1\n")
+        .code(1)
+        .stdout(contains("\"failed_stage\": \"entrypoint\""));
+    doctor("schema_version: 1\nplugin_entrypoints:\n  allowed_bins: [bash]\n")
+        .success()
+        .stdout(contains("\"healthy\": true"));
+}
+
+#[test]
+fn run_once_quarantines_interrupted_task_checkpoints() {
+    let odin_dir = tempfile::tempdir().expect("tempdir");
+    let checkpoints = odin_dir.path().join("checkpoints");
+    std::fs::create_dir_all(&checkpoints).expect("create checkpoints dir");
+    std::fs::write(
+        checkpoints.join("task-9.json"),
+        r#"{"task_id":"task-9","raw_task":"{}","started_at_unix":1}"#,
+    )
+    .expect("write checkpoint");
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("odin-cli");
+    cmd.args([
+        "--run-once",
+        "--reconcile",
+        "quarantine",
+        "--legacy-odin-dir",
+    ])
+    .arg(odin_dir.path())
+    .timeout(Duration::from_secs(3));
+
+    cmd.assert().success().stdout(contains(
+        "startup reconciliation: 0 resumed, 0 requeued, 1 quarantined",
+    ));
+    assert!(checkpoints.join("quarantine/task-9.json").is_file());
+}
+
+#[test]
+fn policy_file_is_schema_checked_before_the_runtime_starts() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let policy = dir.path().join("policy.yaml");
+    std::fs::write(
+        &policy,
+        "schema_version: 1\ngrants:\n  - plugin: example.safe-github\n    capabilities: [repo.read]\n",
+    )
+    .expect("write policy");
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("odin-cli");
+    cmd.args(["--run-once", "--policy-file"])
+        .arg(&policy)
+        .timeout(Duration::from_secs(3));
+    cmd.assert().success().stdout(contains("policy file:"));
+
+    std::fs::write(&policy, "schema_version: 2\n").expect("write policy");
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("odin-cli");
+    cmd.args(["--run-once", "--policy-file"])
+        .arg(&policy)
+        .timeout(Duration::from_secs(3));
+    cmd.assert()
+        .failure()
+        .stderr(contains("/schema_version: expected 1"));
+}
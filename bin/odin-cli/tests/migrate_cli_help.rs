@@ -116,17 +116,17 @@ fn migrate_validate_without_bundle_flag_exits_non_zero() {
 }
 
 #[test]
-fn migrate_import_without_extra_args_delegates_to_stub() {
+fn migrate_import_without_bundle_flag_exits_non_zero() {
     let output = run_cli(&["migrate", "import"]).expect("odin-cli should return promptly");
     assert!(
-        output.status.success(),
+        !output.status.success(),
         "stdout:\n{}\nstderr:\n{}",
         stdout_text(&output),
         stderr_text(&output)
     );
 
-    let stdout = stdout_text(&output);
-    assert!(stdout.contains("migrate import is not implemented yet"));
+    let stderr = stderr_text(&output);
+    assert!(stderr.contains("missing required flag: --bundle"));
 }
 
 #[test]
@@ -0,0 +1,53 @@
+use std::fs;
+
+use assert_cmd::Command;
+use predicates::str::contains;
+use tempfile::TempDir;
+
+const PUBLIC_KEY: &str = "untrusted comment: minisign public key\nRWQf6LRCGA9i53mlYecO4IzT51TGPpvWucNSCh1CBM0QTaLn73Y7GFO3\n";
+
+#[test]
+fn unsigned_policy_bundle_is_refused() {
+    let temp_dir = TempDir::new().expect("create temp dir");
+    let bundle = temp_dir.path().join("bundle");
+    fs::create_dir_all(&bundle).expect("bundle dir");
+    fs::write(bundle.join("policy.yaml"), "schema_version: 1\n").expect("policy");
+    fs::write(
+        bundle.join("bundle.json"),
+        r#"{"schema_version": 1, "version": 1, "policy": "policy.yaml", "sha256": "00"}"#,
+    )
+    .expect("manifest");
+    let key = temp_dir.path().join("bundle.pub");
+    fs::write(&key, PUBLIC_KEY).expect("key");
+
+    Command::new(assert_cmd::cargo::cargo_bin!("odin-cli"))
+        .arg("--legacy-odin-dir")
+        .arg(temp_dir.path())
+        .arg("--policy-bundle")
+        .arg(&bundle)
+        .arg("--policy-bundle-key")
+        .arg(&key)
+        .args([
+            "policy",
+            "explain",
+            "--plugin",
+            "example.safe-github",
+            "--project",
+            "demo",
+            "--capability",
+            "repo.read",
+        ])
+        .assert()
+        .failure()
+        .stderr(contains("is unsigned"));
+}
+
+#[test]
+fn policy_bundle_requires_a_trusted_key() {
+    Command::new(assert_cmd::cargo::cargo_bin!("odin-cli"))
+        .args(["--policy-bundle", "bundle", "policy", "explain"])
+        .args(["--plugin", "p", "--project", "demo", "--capability", "c"])
+        .assert()
+        .failure()
+        .stderr(contains("--policy-bundle-key"));
+}
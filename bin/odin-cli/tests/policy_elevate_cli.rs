@@ -0,0 +1,64 @@
+use std::fs;
+
+use assert_cmd::Command;
+use tempfile::TempDir;
+
+#[test]
+fn policy_elevate_writes_expiring_overlay_entry() {
+    let temp_dir = TempDir::new().expect("create temp dir");
+    let overlay = temp_dir.path().join("elevations.json");
+
+    let output = Command::new(assert_cmd::cargo::cargo_bin!("odin-cli"))
+        .args(["policy", "elevate", "--overlay"])
+        .arg(&overlay)
+        .args([
+            "--plugin",
+            "example.safe-github",
+            "--project",
+            "demo",
+            "--capability",
+            "repo.write",
+            "--scope",
+            "project",
+            "--ttl-secs",
+            "600",
+            "--reason",
+            "incident 42",
+        ])
+        .output()
+        .expect("run odin-cli");
+    assert!(output.status.success(), "policy elevate should succeed");
+
+    let printed: serde_json::Value = serde_json::from_slice(&output.stdout).expect("json");
+    let stored: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&overlay).expect("overlay")).expect("json");
+    assert_eq!(stored["elevations"][0]["id"], printed["id"]);
+    assert_eq!(stored["elevations"][0]["scope"][0], "project");
+    assert_eq!(
+        printed["expires_at_unix"].as_u64().expect("expiry")
+            - printed["created_at_unix"].as_u64().expect("created"),
+        600
+    );
+}
+
+#[test]
+fn policy_elevate_rejects_zero_ttl() {
+    let temp_dir = TempDir::new().expect("create temp dir");
+    Command::new(assert_cmd::cargo::cargo_bin!("odin-cli"))
+        .args(["policy", "elevate", "--overlay"])
+        .arg(temp_dir.path().join("elevations.json"))
+        .args([
+            "--plugin",
+            "example.safe-github",
+            "--project",
+            "demo",
+            "--capability",
+            "repo.write",
+            "--ttl-secs",
+            "0",
+            "--reason",
+            "oops",
+        ])
+        .assert()
+        .failure();
+}
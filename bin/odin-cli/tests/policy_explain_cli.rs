@@ -0,0 +1,77 @@
+use std::fs;
+
+use assert_cmd::Command;
+use tempfile::TempDir;
+
+#[test]
+fn policy_explain_reports_the_deciding_rule_and_the_misses() {
+    let temp_dir = TempDir::new().expect("create temp dir");
+    let policy = temp_dir.path().join("policy.yaml");
+    fs::write(
+        &policy,
+        "\
+schema_version: 1
+grants:
+  - plugin: example.safe-github
+    projects: [acme]
+    capabilities: [repo.write]
+denies:
+  - plugin: example.safe-github
+    projects: [acme/legacy]
+    capabilities: [repo.write]
+",
+    )
+    .expect("write policy");
+
+    let output = Command::new(assert_cmd::cargo::cargo_bin!("odin-cli"))
+        .arg("--policy-file")
+        .arg(&policy)
+        .args([
+            "policy",
+            "explain",
+            "--plugin",
+            "example.safe-github",
+            "--project",
+            "acme/legacy/app",
+            "--capability",
+            "repo.write",
+            "--json",
+        ])
+        .output()
+        .expect("run odin-cli");
+    assert!(output.status.success(), "policy explain should succeed");
+
+    let explanation: serde_json::Value = serde_json::from_slice(&output.stdout).expect("json");
+    assert_eq!(explanation["decision"]["decision"], "deny");
+    assert_eq!(explanation["decision"]["reason_code"], "capability_denied");
+    let steps = explanation["steps"].as_array().expect("steps");
+    assert_eq!(
+        steps[0]["rule"],
+        "grant example.safe-github acme/legacy/app repo.write"
+    );
+    assert_eq!(steps[0]["matched"], false);
+    assert_eq!(
+        steps[1]["rule"],
+        "deny example.safe-github acme/legacy repo.write"
+    );
+    assert_eq!(steps[1]["matched"], true);
+}
+
+#[test]
+fn policy_explain_rejects_unknown_risk_tiers() {
+    Command::new(assert_cmd::cargo::cargo_bin!("odin-cli"))
+        .args([
+            "policy",
+            "explain",
+            "--plugin",
+            "example.safe-github",
+            "--project",
+            "demo",
+            "--capability",
+            "repo.read",
+            "--risk-tier",
+            "spicy",
+        ])
+        .assert()
+        .failure();
+}
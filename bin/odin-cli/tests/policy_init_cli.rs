@@ -0,0 +1,69 @@
+use std::fs;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use tempfile::TempDir;
+
+fn run_policy_init(out_dir: &std::path::Path, answers: &str, force: bool) -> std::process::Output {
+    let mut command = Command::new(assert_cmd::cargo::cargo_bin!("odin-cli"));
+    command
+        .args(["policy", "init", "--out-dir"])
+        .arg(out_dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    if force {
+        command.arg("--force");
+    }
+    let mut child = command.spawn().expect("spawn odin-cli");
+    child
+        .stdin
+        .take()
+        .expect("stdin")
+        .write_all(answers.as_bytes())
+        .expect("write answers");
+    child.wait_with_output().expect("wait odin-cli")
+}
+
+#[test]
+fn policy_init_writes_policy_and_envelopes() {
+    let temp_dir = TempDir::new().expect("create temp dir");
+    let output = run_policy_init(
+        temp_dir.path(),
+        "example.safe-github\nrepo.read, repo.write\ndemo\ntrusted\ny\n",
+        false,
+    );
+    assert!(output.status.success(), "policy init should succeed");
+
+    let policy = fs::read_to_string(temp_dir.path().join("policy.yaml")).expect("policy");
+    assert!(policy.contains("require_approval_for_destructive: true"));
+    assert!(policy.contains("  - plugin: \"example.safe-github\""));
+    assert!(policy.contains("capabilities: [\"repo.read\", \"repo.write\"]"));
+    assert!(policy.contains("projects: [\"demo\"]"));
+    assert!(policy.starts_with('#'));
+
+    let envelopes =
+        fs::read_to_string(temp_dir.path().join("plugin-permissions.yaml")).expect("envelopes");
+    assert!(envelopes.contains("trust_level: trusted"));
+    assert!(envelopes.contains("- id: \"repo.write\""));
+}
+
+#[test]
+fn policy_init_refuses_to_overwrite_without_force() {
+    let temp_dir = TempDir::new().expect("create temp dir");
+    fs::write(temp_dir.path().join("policy.yaml"), "keep me\n").expect("seed policy");
+
+    let answers = "example.safe-github\nrepo.read\n\n\n\n";
+    let output = run_policy_init(temp_dir.path(), answers, false);
+    assert!(
+        !output.status.success(),
+        "existing policy must not be clobbered"
+    );
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("policy.yaml")).expect("policy"),
+        "keep me\n"
+    );
+
+    let output = run_policy_init(temp_dir.path(), answers, true);
+    assert!(output.status.success(), "--force should overwrite");
+}
@@ -0,0 +1,76 @@
+use std::fs;
+
+use assert_cmd::Command;
+use predicates::str::contains;
+use tempfile::TempDir;
+
+fn request_line(request_id: &str, capability: &str) -> String {
+    serde_json::json!({
+        "request_id": request_id,
+        "risk_tier": "safe",
+        "capability": {
+            "plugin": "example.safe-github",
+            "project": "acme/api",
+            "capability": capability,
+            "scope": ["project"],
+            "reason": "what-if"
+        },
+        "input": null
+    })
+    .to_string()
+}
+
+#[test]
+fn policy_simulate_reports_decision_diffs_against_the_active_policy() {
+    let temp_dir = TempDir::new().expect("create temp dir");
+    let active = temp_dir.path().join("active.yaml");
+    let candidate = temp_dir.path().join("candidate.yaml");
+    let requests = temp_dir.path().join("requests.jsonl");
+    fs::write(
+        &active,
+        "schema_version: 1\ngrants:\n  - plugin: example.safe-github\n    capabilities: [repo.read]\n",
+    )
+    .expect("write active");
+    fs::write(
+        &candidate,
+        "schema_version: 1\ngrants:\n  - plugin: example.safe-github\n    capabilities: [repo.read, repo.write]\n",
+    )
+    .expect("write candidate");
+    fs::write(
+        &requests,
+        format!(
+            "{}\n{}\n",
+            request_line("read", "repo.read"),
+            request_line("write", "repo.write")
+        ),
+    )
+    .expect("write requests");
+
+    let output = Command::new(assert_cmd::cargo::cargo_bin!("odin-cli"))
+        .arg("--policy-file")
+        .arg(&active)
+        .args(["policy", "simulate", "--candidate"])
+        .arg(&candidate)
+        .arg("--requests")
+        .arg(&requests)
+        .arg("--json")
+        .output()
+        .expect("run odin-cli");
+    assert!(output.status.success(), "policy simulate should succeed");
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).expect("json");
+    assert_eq!(report["requests"][0]["change"], "unchanged");
+    assert_eq!(report["requests"][1]["change"], "loosened");
+    assert_eq!(report["requests"][1]["active"]["decision"], "deny");
+    assert_eq!(report["requests"][1]["candidate"]["decision"], "allow");
+
+    Command::new(assert_cmd::cargo::cargo_bin!("odin-cli"))
+        .arg("--policy-file")
+        .arg(&active)
+        .args(["policy", "simulate", "--fail-on-change", "--candidate"])
+        .arg(&candidate)
+        .arg("--requests")
+        .arg(&requests)
+        .assert()
+        .failure()
+        .stdout(contains("1 of 2 decisions change"));
+}
@@ -0,0 +1,166 @@
+//! Capability usage aggregated from `governance.capability.used` events: how often each plugin
+//! used each capability, when it last did, in which projects and with which scopes. Grants that
+//! never show up here are candidates for removal.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::chain::ChainedRecord;
+use crate::taxonomy::GOVERNANCE_CAPABILITY_USED;
+use crate::{AuditError, AuditRecord};
+
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+pub struct CapabilityUsage {
+    pub plugin: String,
+    pub capability: String,
+    pub uses: u64,
+    pub first_used_unix: u64,
+    pub last_used_unix: u64,
+    pub projects: BTreeSet<String>,
+    /// Scope entries the uses requested; events recorded without a `scope` add none.
+    pub scopes: BTreeSet<String>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct CapabilityUsageReport {
+    since: Option<u64>,
+    usage: BTreeMap<(String, String), CapabilityUsage>,
+}
+
+impl CapabilityUsageReport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ignores events recorded before `since_unix`.
+    pub fn with_since(mut self, since_unix: u64) -> Self {
+        self.since = Some(since_unix);
+        self
+    }
+
+    /// Folds a `FileAuditSink` log and its rotated files into the report. Lines that do not
+    /// parse, such as a partial line from a crash, are skipped.
+    pub fn with_log(mut self, path: &Path) -> Result<Self, AuditError> {
+        for file in crate::file::log_files(path)? {
+            let contents = fs::read_to_string(&file).map_err(|e| {
+                AuditError::Write(format!("failed reading {}: {e}", file.display()))
+            })?;
+            for line in contents.lines() {
+                if let Ok(entry) = serde_json::from_str::<ChainedRecord>(line) {
+                    self.add(&entry.record);
+                }
+            }
+        }
+        Ok(self)
+    }
+
+    /// Counts `record` when it is a capability use; other events are ignored.
+    pub fn add(&mut self, record: &AuditRecord) {
+        if record.event_type != GOVERNANCE_CAPABILITY_USED
+            || self.since.is_some_and(|since| record.ts_unix < since)
+        {
+            return;
+        }
+        let field = |name: &str| record.metadata.get(name).and_then(|value| value.as_str());
+        let (Some(plugin), Some(capability)) = (field("plugin"), field("capability")) else {
+            return;
+        };
+        let usage = self
+            .usage
+            .entry((plugin.to_string(), capability.to_string()))
+            .or_insert_with(|| CapabilityUsage {
+                plugin: plugin.to_string(),
+                capability: capability.to_string(),
+                uses: 0,
+                first_used_unix: record.ts_unix,
+                last_used_unix: record.ts_unix,
+                projects: BTreeSet::new(),
+                scopes: BTreeSet::new(),
+            });
+        usage.uses += 1;
+        usage.first_used_unix = usage.first_used_unix.min(record.ts_unix);
+        usage.last_used_unix = usage.last_used_unix.max(record.ts_unix);
+        if let Some(project) = &record.project {
+            usage.projects.insert(project.clone());
+        }
+        if let Some(scope) = record
+            .metadata
+            .get("scope")
+            .and_then(|value| value.as_array())
+        {
+            usage.scopes.extend(
+                scope
+                    .iter()
+                    .filter_map(|entry| entry.as_str())
+                    .map(str::to_string),
+            );
+        }
+    }
+
+    /// Usage ordered by plugin, then capability.
+    pub fn entries(&self) -> impl Iterator<Item = &CapabilityUsage> {
+        self.usage.values()
+    }
+
+    pub fn get(&self, plugin: &str, capability: &str) -> Option<&CapabilityUsage> {
+        self.usage
+            .get(&(plugin.to_string(), capability.to_string()))
+    }
+
+    /// The `(plugin, capability)` pairs from `granted` with no recorded use.
+    pub fn unused<'a>(&self, granted: &'a [(String, String)]) -> Vec<&'a (String, String)> {
+        granted
+            .iter()
+            .filter(|(plugin, capability)| self.get(plugin, capability).is_none())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::Severity;
+
+    fn used(ts_unix: u64, plugin: &str, capability: &str, project: &str) -> AuditRecord {
+        AuditRecord {
+            ts_unix,
+            event_type: GOVERNANCE_CAPABILITY_USED.to_string(),
+            severity: Severity::Info,
+            request_id: None,
+            task_id: None,
+            project: Some(project.to_string()),
+            trace_id: None,
+            metadata: json!({ "plugin": plugin, "capability": capability, "scope": [project] }),
+        }
+    }
+
+    #[test]
+    fn aggregates_uses_per_plugin_and_capability() {
+        let mut report = CapabilityUsageReport::new().with_since(10);
+        report.add(&used(5, "example.git", "repo.read", "demo"));
+        report.add(&used(20, "example.git", "repo.read", "demo"));
+        report.add(&used(40, "example.git", "repo.read", "ops"));
+        report.add(&used(30, "example.git", "repo.write", "demo"));
+        let mut decision = used(50, "example.git", "repo.read", "demo");
+        decision.event_type = "policy.decision".to_string();
+        report.add(&decision);
+
+        let read = report.get("example.git", "repo.read").expect("repo.read");
+        assert_eq!(read.uses, 2);
+        assert_eq!((read.first_used_unix, read.last_used_unix), (20, 40));
+        assert_eq!(read.projects.len(), 2);
+        assert_eq!(read.scopes, read.projects);
+        assert_eq!(report.entries().count(), 2);
+
+        let granted = vec![
+            ("example.git".to_string(), "repo.write".to_string()),
+            ("example.git".to_string(), "repo.delete".to_string()),
+        ];
+        assert_eq!(report.unused(&granted), vec![&granted[1]]);
+    }
+}
@@ -0,0 +1,217 @@
+//! Moves audit writes off the action hot path: records go into a bounded queue drained by a
+//! background thread. `flush` waits until everything queued before it has reached the inner
+//! sink, and reports write failures that happened in the background since the last flush.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, SyncSender, TrySendError};
+use std::thread::{self, JoinHandle};
+
+use crate::{AuditError, AuditRecord, AuditSink};
+
+/// What `record` does when the queue is full.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Wait for room, so a slow sink slows actions down but nothing is lost.
+    #[default]
+    Block,
+    /// Discard the record and count it in `dropped`.
+    DropNewest,
+}
+
+enum Message {
+    Record(AuditRecord),
+    Flush(SyncSender<Result<(), AuditError>>),
+}
+
+pub struct BufferedAuditSink {
+    sender: Option<SyncSender<Message>>,
+    worker: Option<JoinHandle<()>>,
+    overflow: OverflowPolicy,
+    dropped: AtomicU64,
+}
+
+impl BufferedAuditSink {
+    /// Starts the background writer with room for `capacity` queued records.
+    pub fn new(inner: impl AuditSink + 'static, capacity: usize) -> Self {
+        let (sender, receiver) = mpsc::sync_channel(capacity);
+        let worker = thread::Builder::new()
+            .name("odin-audit-writer".to_string())
+            .spawn(move || drain(inner, receiver))
+            .expect("spawn audit writer thread");
+        Self {
+            sender: Some(sender),
+            worker: Some(worker),
+            overflow: OverflowPolicy::default(),
+            dropped: AtomicU64::new(0),
+        }
+    }
+
+    pub fn with_overflow(mut self, overflow: OverflowPolicy) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    /// Records discarded because the queue was full.
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    fn sender(&self) -> Result<&SyncSender<Message>, AuditError> {
+        self.sender
+            .as_ref()
+            .ok_or_else(|| AuditError::Write("audit writer stopped".to_string()))
+    }
+}
+
+fn drain(inner: impl AuditSink, receiver: Receiver<Message>) {
+    let mut failure: Option<AuditError> = None;
+    for message in receiver {
+        match message {
+            Message::Record(record) => {
+                if let Err(err) = inner.record(record) {
+                    tracing::warn!(error = %err, "buffered audit write failed");
+                    failure.get_or_insert(err);
+                }
+            }
+            Message::Flush(reply) => {
+                let result = match failure.take() {
+                    Some(err) => Err(AuditError::Write(format!(
+                        "buffered audit write failed: {err}"
+                    ))),
+                    None => inner.flush(),
+                };
+                let _ = reply.send(result);
+            }
+        }
+    }
+    if let Err(err) = inner.flush() {
+        tracing::warn!(error = %err, "audit flush on writer shutdown failed");
+    }
+}
+
+impl AuditSink for BufferedAuditSink {
+    fn record(&self, record: AuditRecord) -> Result<(), AuditError> {
+        let sender = self.sender()?;
+        let stopped = || AuditError::Write("audit writer stopped".to_string());
+        match self.overflow {
+            OverflowPolicy::Block => sender.send(Message::Record(record)).map_err(|_| stopped()),
+            OverflowPolicy::DropNewest => match sender.try_send(Message::Record(record)) {
+                Ok(()) => Ok(()),
+                Err(TrySendError::Full(_)) => {
+                    self.dropped.fetch_add(1, Ordering::Relaxed);
+                    tracing::warn!("audit queue full; dropping record");
+                    Ok(())
+                }
+                Err(TrySendError::Disconnected(_)) => Err(stopped()),
+            },
+        }
+    }
+
+    fn flush(&self) -> Result<(), AuditError> {
+        let (reply, result) = mpsc::sync_channel(1);
+        self.sender()?
+            .send(Message::Flush(reply))
+            .map_err(|_| AuditError::Write("audit writer stopped".to_string()))?;
+        result
+            .recv()
+            .map_err(|_| AuditError::Write("audit writer stopped".to_string()))?
+    }
+}
+
+/// Closes the queue and waits for the writer to drain it.
+impl Drop for BufferedAuditSink {
+    fn drop(&mut self) {
+        self.sender.take();
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Barrier, Mutex};
+
+    use serde_json::Value;
+
+    use super::*;
+    use crate::Severity;
+
+    fn record(ts_unix: u64) -> AuditRecord {
+        AuditRecord {
+            ts_unix,
+            event_type: "action.executed".to_string(),
+            severity: Severity::Info,
+            request_id: None,
+            task_id: None,
+            project: None,
+            trace_id: None,
+            metadata: Value::Null,
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct Capture(Arc<Mutex<Vec<u64>>>);
+
+    impl AuditSink for Capture {
+        fn record(&self, record: AuditRecord) -> Result<(), AuditError> {
+            if record.ts_unix == 0 {
+                return Err(AuditError::Write("disk full".to_string()));
+            }
+            self.0.lock().expect("lock").push(record.ts_unix);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn flush_waits_for_queued_records_and_reports_background_failures() {
+        let capture = Capture::default();
+        let sink = BufferedAuditSink::new(capture.clone(), 16);
+        for ts in 1..=5 {
+            sink.record(record(ts)).expect("record");
+        }
+        sink.flush().expect("flush");
+        assert_eq!(*capture.0.lock().expect("lock"), vec![1, 2, 3, 4, 5]);
+
+        sink.record(record(0)).expect("queued");
+        assert!(sink.flush().is_err());
+        sink.flush().expect("failure reported once");
+
+        sink.record(record(6)).expect("record");
+        drop(sink);
+        assert_eq!(capture.0.lock().expect("lock").last(), Some(&6));
+    }
+
+    /// Holds the writer on each record until the test releases it.
+    struct Stalled(Arc<Barrier>);
+
+    impl AuditSink for Stalled {
+        fn record(&self, _record: AuditRecord) -> Result<(), AuditError> {
+            self.0.wait();
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn drop_newest_discards_records_when_the_queue_is_full() {
+        let barrier = Arc::new(Barrier::new(2));
+        let sink = BufferedAuditSink::new(Stalled(barrier.clone()), 1)
+            .with_overflow(OverflowPolicy::DropNewest);
+        sink.record(record(1)).expect("record");
+        // Wait until the writer holds record 1, leaving the queue empty.
+        while sink
+            .sender()
+            .expect("sender")
+            .try_send(Message::Record(record(2)))
+            .is_err()
+        {
+            thread::yield_now();
+        }
+        sink.record(record(3)).expect("dropped, not failed");
+        assert_eq!(sink.dropped(), 1);
+
+        barrier.wait();
+        barrier.wait();
+        sink.flush().expect("flush");
+    }
+}
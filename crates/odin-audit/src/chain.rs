@@ -0,0 +1,253 @@
+//! Hash chain over the file audit log. Each line carries `chain: {prev, hash}`, where `hash` is
+//! SHA-256 over `prev` and the record's JSON. An edited, removed or reordered record breaks the
+//! chain at that line. The first record of a log may point at a pruned record, so its `prev` is
+//! taken as the anchor rather than checked.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{AuditError, AuditRecord};
+
+/// `prev` of the first record ever written to a log.
+pub const GENESIS: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ChainLink {
+    pub prev: String,
+    pub hash: String,
+}
+
+/// One line of a chained log: the record's fields plus its link.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ChainedRecord {
+    #[serde(flatten)]
+    pub record: AuditRecord,
+    /// Absent on lines written before the log was chained.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chain: Option<ChainLink>,
+}
+
+impl ChainedRecord {
+    /// Links `record` after the record whose hash is `prev`.
+    pub fn link(record: AuditRecord, prev: &str) -> Result<Self, AuditError> {
+        let hash = link_hash(prev, &record)?;
+        Ok(Self {
+            record,
+            chain: Some(ChainLink {
+                prev: prev.to_string(),
+                hash,
+            }),
+        })
+    }
+}
+
+pub fn link_hash(prev: &str, record: &AuditRecord) -> Result<String, AuditError> {
+    let body = serde_json::to_vec(record)
+        .map_err(|e| AuditError::Write(format!("unserializable audit record: {e}")))?;
+    let mut hasher = Sha256::new();
+    hasher.update(prev.as_bytes());
+    hasher.update(b"\n");
+    hasher.update(&body);
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Inclusive `ts_unix` bounds on the records to check; `None` is unbounded.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct VerifyRange {
+    pub from: Option<u64>,
+    pub to: Option<u64>,
+}
+
+impl VerifyRange {
+    fn contains(&self, ts_unix: u64) -> bool {
+        self.from.is_none_or(|from| ts_unix >= from) && self.to.is_none_or(|to| ts_unix <= to)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+pub struct Divergence {
+    pub file: PathBuf,
+    /// 1-based line number within `file`.
+    pub line: usize,
+    pub ts_unix: Option<u64>,
+    pub reason: String,
+}
+
+#[derive(Clone, Debug, Default, Serialize, PartialEq, Eq)]
+pub struct VerifyReport {
+    /// Oldest first.
+    pub files: Vec<PathBuf>,
+    pub records_checked: u64,
+    /// Records written before the log was chained, which cannot be verified.
+    pub unchained_records: u64,
+    /// `prev` of the first chained record checked.
+    pub anchor: Option<String>,
+    /// `hash` of the last chained record checked.
+    pub head: Option<String>,
+    pub divergence: Option<Divergence>,
+}
+
+impl VerifyReport {
+    pub fn intact(&self) -> bool {
+        self.divergence.is_none()
+    }
+}
+
+/// Recomputes the chain over the lines of `files` (oldest first) and stops at the first record
+/// in `range` that does not match. Records outside `range` are skipped but still anchor the
+/// next record's `prev`.
+pub fn verify_files(files: &[PathBuf], range: VerifyRange) -> Result<VerifyReport, AuditError> {
+    let mut report = VerifyReport {
+        files: files.to_vec(),
+        ..VerifyReport::default()
+    };
+    let mut previous: Option<String> = None;
+    let mut chained = false;
+    for file in files {
+        let contents = fs::read_to_string(file).map_err(|e| read_err(file, e))?;
+        for (index, line) in contents.lines().enumerate() {
+            let diverged = |ts_unix, reason: String| Divergence {
+                file: file.clone(),
+                line: index + 1,
+                ts_unix,
+                reason,
+            };
+            let entry = match serde_json::from_str::<ChainedRecord>(line) {
+                Ok(entry) => entry,
+                Err(e) => {
+                    report.divergence = Some(diverged(None, format!("unparseable record: {e}")));
+                    return Ok(report);
+                }
+            };
+            let ts_unix = entry.record.ts_unix;
+            let in_range = range.contains(ts_unix);
+            let Some(link) = entry.chain else {
+                if chained && in_range {
+                    report.divergence = Some(diverged(
+                        Some(ts_unix),
+                        "record has no chain link after chaining began".to_string(),
+                    ));
+                    return Ok(report);
+                }
+                report.unchained_records += u64::from(in_range);
+                continue;
+            };
+            chained = true;
+            if in_range {
+                if let Some(previous) = previous.as_deref().filter(|p| *p != link.prev) {
+                    report.divergence = Some(diverged(
+                        Some(ts_unix),
+                        format!(
+                            "prev {} does not match the preceding hash {previous}",
+                            link.prev
+                        ),
+                    ));
+                    return Ok(report);
+                }
+                let expected = link_hash(&link.prev, &entry.record)?;
+                if expected != link.hash {
+                    report.divergence = Some(diverged(
+                        Some(ts_unix),
+                        format!(
+                            "hash {} does not match the record (expected {expected})",
+                            link.hash
+                        ),
+                    ));
+                    return Ok(report);
+                }
+                report.anchor.get_or_insert_with(|| link.prev.clone());
+                report.head = Some(link.hash.clone());
+                report.records_checked += 1;
+            }
+            previous = Some(link.hash);
+        }
+    }
+    Ok(report)
+}
+
+/// Verifies a `FileAuditSink` log and its rotated files without opening it for writing.
+pub fn verify_log(path: &Path, range: VerifyRange) -> Result<VerifyReport, AuditError> {
+    verify_files(&crate::file::log_files(path)?, range)
+}
+
+fn read_err(path: &Path, err: std::io::Error) -> AuditError {
+    AuditError::Write(format!("failed reading {}: {err}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::*;
+    use crate::Severity;
+
+    fn record(ts_unix: u64) -> AuditRecord {
+        AuditRecord {
+            ts_unix,
+            event_type: "policy.decision".to_string(),
+            severity: Severity::Info,
+            request_id: None,
+            task_id: None,
+            project: None,
+            trace_id: None,
+            metadata: serde_json::json!({ "decision": "allow", "n": 1.5 }),
+        }
+    }
+
+    fn write_chain(path: &Path, count: u64) -> Vec<String> {
+        let mut prev = GENESIS.to_string();
+        let mut lines = Vec::new();
+        for ts in 1..=count {
+            let entry = ChainedRecord::link(record(ts), &prev).expect("link");
+            prev = entry.chain.clone().expect("chain").hash;
+            lines.push(serde_json::to_string(&entry).expect("json"));
+        }
+        fs::write(path, lines.join("\n") + "\n").expect("write");
+        lines
+    }
+
+    #[test]
+    fn detects_the_first_edited_record() {
+        let dir = std::env::temp_dir().join(format!("odin-audit-chain-{}", std::process::id()));
+        fs::create_dir_all(&dir).expect("dir");
+        let path = dir.join("audit.jsonl");
+        let mut lines = write_chain(&path, 4);
+        let files = [path.clone()];
+
+        let report = verify_files(&files, VerifyRange::default()).expect("verify");
+        assert!(report.intact());
+        assert_eq!(report.records_checked, 4);
+        assert_eq!(report.anchor.as_deref(), Some(GENESIS));
+
+        lines[2] = lines[2].replace("allow", "deny");
+        fs::write(&path, lines.join("\n") + "\n").expect("write");
+        let report = verify_files(&files, VerifyRange::default()).expect("verify");
+        let divergence = report.divergence.expect("diverged");
+        assert_eq!((divergence.line, divergence.ts_unix), (3, Some(3)));
+        assert!(divergence.reason.contains("does not match the record"));
+
+        // Records outside the range are not checked, but a removed record still breaks the link.
+        let range = VerifyRange {
+            from: Some(4),
+            to: None,
+        };
+        assert!(verify_files(&files, range).expect("verify").intact());
+        lines.remove(2);
+        fs::write(&path, lines.join("\n") + "\n").expect("write");
+        let report = verify_files(&files, range).expect("verify");
+        assert!(report.divergence.expect("diverged").reason.contains("prev"));
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn chained_lines_still_parse_as_plain_records() {
+        let entry = ChainedRecord::link(record(1), GENESIS).expect("link");
+        let line = serde_json::to_string(&entry).expect("json");
+        let plain: AuditRecord = serde_json::from_str(&line).expect("record");
+        assert_eq!(plain, record(1));
+        let _: Value = serde_json::from_str(&line).expect("value");
+    }
+}
@@ -0,0 +1,154 @@
+//! Sends every record to several sinks, e.g. the audit log plus telemetry exporters. Each sink
+//! has its own failure policy so a broken exporter cannot fail the action being audited.
+
+use crate::{AuditError, AuditRecord, AuditSink};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SinkFailurePolicy {
+    /// A failed write fails the whole record, blocking the audited action.
+    #[default]
+    FailClosed,
+    /// A failed write is logged and otherwise ignored.
+    BestEffort,
+}
+
+struct FanoutTarget {
+    name: String,
+    sink: Box<dyn AuditSink>,
+    policy: SinkFailurePolicy,
+}
+
+/// Writes to every sink in registration order, even after one fails, and returns the first
+/// fail-closed error.
+#[derive(Default)]
+pub struct FanoutAuditSink {
+    targets: Vec<FanoutTarget>,
+}
+
+impl FanoutAuditSink {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `name` identifies the sink in errors and logs.
+    pub fn with_sink(
+        mut self,
+        name: impl Into<String>,
+        sink: impl AuditSink + 'static,
+        policy: SinkFailurePolicy,
+    ) -> Self {
+        self.targets.push(FanoutTarget {
+            name: name.into(),
+            sink: Box::new(sink),
+            policy,
+        });
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.targets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.targets.is_empty()
+    }
+
+    fn each(
+        &self,
+        op: &str,
+        mut write: impl FnMut(&dyn AuditSink) -> Result<(), AuditError>,
+    ) -> Result<(), AuditError> {
+        let mut failure = None;
+        for target in &self.targets {
+            let Err(err) = write(target.sink.as_ref()) else {
+                continue;
+            };
+            match target.policy {
+                SinkFailurePolicy::FailClosed => {
+                    failure.get_or_insert(AuditError::Write(format!(
+                        "audit sink {} {op} failed: {err}",
+                        target.name
+                    )));
+                }
+                SinkFailurePolicy::BestEffort => {
+                    tracing::warn!(sink = %target.name, error = %err, "best-effort audit sink {op} failed");
+                }
+            }
+        }
+        failure.map_or(Ok(()), Err)
+    }
+}
+
+impl AuditSink for FanoutAuditSink {
+    fn record(&self, record: AuditRecord) -> Result<(), AuditError> {
+        self.each("record", |sink| sink.record(record.clone()))
+    }
+
+    fn flush(&self) -> Result<(), AuditError> {
+        self.each("flush", |sink| sink.flush())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use serde_json::Value;
+
+    use super::*;
+    use crate::Severity;
+
+    #[derive(Clone, Default)]
+    struct Capture(Arc<Mutex<Vec<AuditRecord>>>);
+
+    impl AuditSink for Capture {
+        fn record(&self, record: AuditRecord) -> Result<(), AuditError> {
+            self.0.lock().expect("lock").push(record);
+            Ok(())
+        }
+    }
+
+    struct Broken;
+
+    impl AuditSink for Broken {
+        fn record(&self, _record: AuditRecord) -> Result<(), AuditError> {
+            Err(AuditError::Write("exporter unreachable".to_string()))
+        }
+    }
+
+    fn record() -> AuditRecord {
+        AuditRecord {
+            ts_unix: 1,
+            event_type: "action.executed".to_string(),
+            severity: Severity::Info,
+            request_id: None,
+            task_id: None,
+            project: None,
+            trace_id: None,
+            metadata: Value::Null,
+        }
+    }
+
+    #[test]
+    fn best_effort_failures_do_not_fail_the_record() {
+        let primary = Capture::default();
+        let sink = FanoutAuditSink::new()
+            .with_sink("exporter", Broken, SinkFailurePolicy::BestEffort)
+            .with_sink("file", primary.clone(), SinkFailurePolicy::FailClosed);
+
+        sink.record(record()).expect("record");
+        assert_eq!(primary.0.lock().expect("lock").len(), 1);
+    }
+
+    #[test]
+    fn fail_closed_failures_fail_the_record_after_every_sink_ran() {
+        let exporter = Capture::default();
+        let sink = FanoutAuditSink::new()
+            .with_sink("file", Broken, SinkFailurePolicy::FailClosed)
+            .with_sink("exporter", exporter.clone(), SinkFailurePolicy::BestEffort);
+
+        let err = sink.record(record()).expect_err("primary failed");
+        assert!(err.to_string().contains("audit sink file record failed"));
+        assert_eq!(exporter.0.lock().expect("lock").len(), 1);
+    }
+}
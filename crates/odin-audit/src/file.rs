@@ -0,0 +1,609 @@
+//! JSON-lines audit log with size/age rotation. Each record is written with a single `write` call
+//! so a crash leaves at most one partial trailing line, which is dropped when the log is reopened.
+//! `prune` removes old records, keeping per-day counts of them in a rollup file next to the log.
+//! Lines are hash-chained (see `chain`), so the log can be checked for edits with `verify_log`.
+
+use std::collections::BTreeMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+
+use crate::chain::{ChainedRecord, GENESIS};
+use crate::{AuditError, AuditRecord, AuditSink, Severity};
+
+const DAY_SECS: u64 = 86_400;
+
+/// When written records are forced to disk with `fsync`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FsyncPolicy {
+    /// After every record; slowest, but nothing acknowledged is lost on power failure.
+    Always,
+    /// On `flush` (shutdown) and before a rotation.
+    #[default]
+    OnFlush,
+    /// Left to the OS.
+    Never,
+}
+
+/// Counts of the pruned records from one UTC day.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DailyRollup {
+    pub day_start_unix: u64,
+    pub records: u64,
+    #[serde(default)]
+    pub by_event_type: BTreeMap<String, u64>,
+    #[serde(default)]
+    pub by_severity: BTreeMap<Severity, u64>,
+}
+
+impl DailyRollup {
+    fn add(&mut self, record: &AuditRecord) {
+        self.records += 1;
+        *self
+            .by_event_type
+            .entry(record.event_type.clone())
+            .or_default() += 1;
+        *self.by_severity.entry(record.severity).or_default() += 1;
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RollupFile {
+    schema_version: u32,
+    #[serde(default)]
+    days: Vec<DailyRollup>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, PartialEq, Eq)]
+pub struct PruneReport {
+    pub records_pruned: u64,
+    /// Rotated logs deleted because every record in them was pruned.
+    pub files_removed: usize,
+    /// Days whose rollups gained records.
+    pub days_compacted: Vec<u64>,
+}
+
+struct ActiveLog {
+    file: File,
+    size: u64,
+    opened_at: SystemTime,
+    /// Hash of the last record written, carried across rotations and prunes.
+    head: String,
+}
+
+/// Appends records to `path`. Rotated logs are renamed to `path.1` (newest) through
+/// `path.<max_files>`; older ones are deleted.
+pub struct FileAuditSink {
+    path: PathBuf,
+    max_bytes: Option<u64>,
+    max_age: Option<Duration>,
+    max_files: usize,
+    fsync: FsyncPolicy,
+    active: Mutex<ActiveLog>,
+}
+
+impl FileAuditSink {
+    /// Opens (or creates) the log, truncating a partial line left by a crash.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, AuditError> {
+        let path = path.into();
+        let mut active = open_log(&path)?;
+        active.head = chain_head(&path)?;
+        Ok(Self {
+            path,
+            max_bytes: None,
+            max_age: None,
+            max_files: 10,
+            fsync: FsyncPolicy::default(),
+            active: Mutex::new(active),
+        })
+    }
+
+    /// Rotates before a record would grow the log past `max_bytes`.
+    pub fn with_max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Rotates once the active log is older than `max_age`.
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Number of rotated logs kept; defaults to 10.
+    pub fn with_max_files(mut self, max_files: usize) -> Self {
+        self.max_files = max_files;
+        self
+    }
+
+    pub fn with_fsync(mut self, fsync: FsyncPolicy) -> Self {
+        self.fsync = fsync;
+        self
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Where `prune` keeps its daily rollups: `<path>.rollups.json`.
+    pub fn rollup_path(&self) -> PathBuf {
+        suffixed(&self.path, ".rollups.json")
+    }
+
+    /// Daily rollups of everything pruned so far, oldest first.
+    pub fn rollups(&self) -> Result<Vec<DailyRollup>, AuditError> {
+        let path = self.rollup_path();
+        match fs::read(&path) {
+            Ok(raw) => serde_json::from_slice::<RollupFile>(&raw)
+                .map(|file| file.days)
+                .map_err(|e| AuditError::Write(format!("corrupt {}: {e}", path.display()))),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(io_err("reading", &path, e)),
+        }
+    }
+
+    /// Removes records older than `older_than_unix` from the active and rotated logs, folding
+    /// them into the daily rollups first. Lines that do not parse are kept.
+    pub fn prune(&self, older_than_unix: u64) -> Result<PruneReport, AuditError> {
+        let mut active = self.lock()?;
+        let files = log_files(&self.path)?;
+        let mut rollups: BTreeMap<u64, DailyRollup> = BTreeMap::new();
+        let mut rewrites = Vec::new();
+        for path in files {
+            let contents = fs::read_to_string(&path).map_err(|e| io_err("reading", &path, e))?;
+            let mut kept = String::new();
+            let mut pruned = 0;
+            for line in contents.lines() {
+                match serde_json::from_str::<AuditRecord>(line) {
+                    Ok(record) if record.ts_unix < older_than_unix => {
+                        let day = record.ts_unix - record.ts_unix % DAY_SECS;
+                        rollups
+                            .entry(day)
+                            .or_insert_with(|| DailyRollup {
+                                day_start_unix: day,
+                                ..DailyRollup::default()
+                            })
+                            .add(&record);
+                        pruned += 1;
+                    }
+                    _ => {
+                        kept.push_str(line);
+                        kept.push('\n');
+                    }
+                }
+            }
+            if pruned > 0 {
+                rewrites.push((path, kept, pruned));
+            }
+        }
+
+        let mut report = PruneReport {
+            days_compacted: rollups.keys().copied().collect(),
+            ..PruneReport::default()
+        };
+        if rollups.is_empty() {
+            return Ok(report);
+        }
+        // Rollups are saved before any record is removed, so a crash can only double-count.
+        self.merge_rollups(rollups)?;
+        for (path, kept, pruned) in rewrites {
+            report.records_pruned += pruned;
+            if kept.is_empty() && path != self.path {
+                remove_if_exists(&path)?;
+                report.files_removed += 1;
+            } else {
+                write_atomic(&path, kept.as_bytes())?;
+            }
+        }
+        reopen(&self.path, &mut active)?;
+        Ok(report)
+    }
+
+    fn merge_rollups(&self, new: BTreeMap<u64, DailyRollup>) -> Result<(), AuditError> {
+        let mut days: BTreeMap<u64, DailyRollup> = self
+            .rollups()?
+            .into_iter()
+            .map(|day| (day.day_start_unix, day))
+            .collect();
+        for (day, rollup) in new {
+            let merged = days.entry(day).or_insert_with(|| DailyRollup {
+                day_start_unix: day,
+                ..DailyRollup::default()
+            });
+            merged.records += rollup.records;
+            for (event_type, count) in rollup.by_event_type {
+                *merged.by_event_type.entry(event_type).or_default() += count;
+            }
+            for (severity, count) in rollup.by_severity {
+                *merged.by_severity.entry(severity).or_default() += count;
+            }
+        }
+        let body = serde_json::to_vec_pretty(&RollupFile {
+            schema_version: 1,
+            days: days.into_values().collect(),
+        })
+        .map_err(|e| AuditError::Write(format!("failed serializing audit rollups: {e}")))?;
+        write_atomic(&self.rollup_path(), &body)
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, ActiveLog>, AuditError> {
+        self.active
+            .lock()
+            .map_err(|_| AuditError::Write("audit log lock poisoned".to_string()))
+    }
+
+    fn due_for_rotation(&self, active: &ActiveLog, incoming: u64) -> bool {
+        let full = self
+            .max_bytes
+            .is_some_and(|max| active.size > 0 && active.size + incoming > max);
+        let aged = self.max_age.is_some_and(|max| {
+            active
+                .opened_at
+                .elapsed()
+                .is_ok_and(|elapsed| elapsed >= max)
+        });
+        full || aged
+    }
+
+    fn rotate(&self, active: &mut ActiveLog) -> Result<(), AuditError> {
+        if self.fsync != FsyncPolicy::Never {
+            active
+                .file
+                .sync_data()
+                .map_err(|e| io_err("syncing", &self.path, e))?;
+        }
+        if self.max_files == 0 {
+            remove_if_exists(&self.path)?;
+        } else {
+            remove_if_exists(&rotated(&self.path, self.max_files))?;
+            for index in (1..self.max_files).rev() {
+                let from = rotated(&self.path, index);
+                if from.exists() {
+                    fs::rename(&from, rotated(&self.path, index + 1))
+                        .map_err(|e| io_err("rotating", &from, e))?;
+                }
+            }
+            fs::rename(&self.path, rotated(&self.path, 1))
+                .map_err(|e| io_err("rotating", &self.path, e))?;
+        }
+        reopen(&self.path, active)?;
+        Ok(())
+    }
+}
+
+impl AuditSink for FileAuditSink {
+    fn record(&self, record: AuditRecord) -> Result<(), AuditError> {
+        let mut active = self.lock()?;
+        let entry = ChainedRecord::link(record, &active.head)?;
+        let mut line = serde_json::to_vec(&entry)
+            .map_err(|e| AuditError::Write(format!("unserializable audit record: {e}")))?;
+        line.push(b'\n');
+        if self.due_for_rotation(&active, line.len() as u64) {
+            self.rotate(&mut active)?;
+        }
+        active
+            .file
+            .write_all(&line)
+            .map_err(|e| io_err("writing", &self.path, e))?;
+        active.size += line.len() as u64;
+        if let Some(link) = entry.chain {
+            active.head = link.hash;
+        }
+        if self.fsync == FsyncPolicy::Always {
+            active
+                .file
+                .sync_data()
+                .map_err(|e| io_err("syncing", &self.path, e))?;
+        }
+        Ok(())
+    }
+
+    fn flush(&self) -> Result<(), AuditError> {
+        let active = self.lock()?;
+        if self.fsync != FsyncPolicy::Never {
+            active
+                .file
+                .sync_data()
+                .map_err(|e| io_err("syncing", &self.path, e))?;
+        }
+        Ok(())
+    }
+}
+
+/// The log at `path` and its rotated files, oldest first.
+pub fn log_files(path: &Path) -> Result<Vec<PathBuf>, AuditError> {
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let prefix = format!("{name}.");
+    let mut rotations: Vec<(usize, PathBuf)> = match fs::read_dir(parent) {
+        Ok(entries) => entries
+            .filter_map(Result::ok)
+            .filter_map(|entry| {
+                let file_name = entry.file_name();
+                let index = file_name.to_str()?.strip_prefix(&prefix)?.parse().ok()?;
+                Some((index, entry.path()))
+            })
+            .collect(),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+        Err(e) => return Err(io_err("listing", parent, e)),
+    };
+    rotations.sort_by_key(|(index, _)| std::cmp::Reverse(*index));
+    let mut files: Vec<PathBuf> = rotations.into_iter().map(|(_, path)| path).collect();
+    if path.exists() {
+        files.push(path.to_path_buf());
+    }
+    Ok(files)
+}
+
+/// Hash of the newest chained record across the log's files, or `GENESIS`.
+fn chain_head(path: &Path) -> Result<String, AuditError> {
+    for file in log_files(path)?.iter().rev() {
+        let contents = fs::read_to_string(file).map_err(|e| io_err("reading", file, e))?;
+        let Some(line) = contents.lines().last() else {
+            continue;
+        };
+        let entry: ChainedRecord = serde_json::from_str(line)
+            .map_err(|e| AuditError::Write(format!("corrupt {}: {e}", file.display())))?;
+        return Ok(entry
+            .chain
+            .map_or_else(|| GENESIS.to_string(), |link| link.hash));
+    }
+    Ok(GENESIS.to_string())
+}
+
+fn reopen(path: &Path, active: &mut ActiveLog) -> Result<(), AuditError> {
+    let head = std::mem::take(&mut active.head);
+    *active = open_log(path)?;
+    active.head = head;
+    Ok(())
+}
+
+fn rotated(path: &Path, index: usize) -> PathBuf {
+    suffixed(path, &format!(".{index}"))
+}
+
+fn suffixed(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+fn write_atomic(path: &Path, body: &[u8]) -> Result<(), AuditError> {
+    let tmp = suffixed(path, ".tmp");
+    fs::write(&tmp, body)
+        .and_then(|_| fs::rename(&tmp, path))
+        .map_err(|e| io_err("writing", path, e))
+}
+
+fn open_log(path: &Path) -> Result<ActiveLog, AuditError> {
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent).map_err(|e| io_err("creating dir for", parent, e))?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .read(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| io_err("opening", path, e))?;
+    let size = drop_partial_line(&mut file).map_err(|e| io_err("repairing", path, e))?;
+    let metadata = file.metadata().map_err(|e| io_err("inspecting", path, e))?;
+    let opened_at = if size == 0 {
+        SystemTime::now()
+    } else {
+        metadata
+            .created()
+            .or_else(|_| metadata.modified())
+            .unwrap_or_else(|_| SystemTime::now())
+    };
+    Ok(ActiveLog {
+        file,
+        size,
+        opened_at,
+        head: GENESIS.to_string(),
+    })
+}
+
+/// Truncates the file after its last newline and returns the remaining length.
+fn drop_partial_line(file: &mut File) -> std::io::Result<u64> {
+    const CHUNK: u64 = 8 * 1024;
+    let len = file.metadata()?.len();
+    let mut end = len;
+    let mut buf = vec![0u8; CHUNK as usize];
+    while end > 0 {
+        let start = end.saturating_sub(CHUNK);
+        let chunk = &mut buf[..(end - start) as usize];
+        file.seek(SeekFrom::Start(start))?;
+        file.read_exact(chunk)?;
+        if let Some(pos) = chunk.iter().rposition(|byte| *byte == b'\n') {
+            end = start + pos as u64 + 1;
+            break;
+        }
+        end = start;
+    }
+    if end < len {
+        tracing::warn!(
+            dropped_bytes = len - end,
+            "dropping partial audit record left by an interrupted write"
+        );
+        file.set_len(end)?;
+    }
+    Ok(end)
+}
+
+fn remove_if_exists(path: &Path) -> Result<(), AuditError> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(io_err("removing", path, e)),
+        _ => Ok(()),
+    }
+}
+
+fn io_err(action: &str, path: &Path, err: std::io::Error) -> AuditError {
+    AuditError::Write(format!("failed {action} {}: {err}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::*;
+    use crate::Severity;
+
+    fn record(ts_unix: u64) -> AuditRecord {
+        AuditRecord {
+            ts_unix,
+            event_type: "policy.decision".to_string(),
+            severity: Severity::Info,
+            request_id: Some(format!("req-{ts_unix}")),
+            task_id: None,
+            project: Some("demo".to_string()),
+            trace_id: None,
+            metadata: Value::Null,
+        }
+    }
+
+    fn line_len(ts_unix: u64) -> u64 {
+        let entry = ChainedRecord::link(record(ts_unix), GENESIS).expect("link");
+        serde_json::to_vec(&entry).expect("json").len() as u64 + 1
+    }
+
+    fn read_lines(path: &Path) -> Vec<AuditRecord> {
+        fs::read_to_string(path)
+            .expect("read log")
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("record"))
+            .collect()
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("odin-audit-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn rotates_by_size_and_keeps_max_files() {
+        let dir = temp_dir("rotate");
+        let path = dir.join("audit.jsonl");
+        let line_len = line_len(1);
+        let sink = FileAuditSink::open(&path)
+            .expect("open")
+            .with_max_bytes(line_len * 2)
+            .with_max_files(2)
+            .with_fsync(FsyncPolicy::Always);
+
+        for ts in 1..=7 {
+            sink.record(record(ts)).expect("record");
+        }
+        sink.flush().expect("flush");
+
+        assert_eq!(
+            read_lines(&path)
+                .iter()
+                .map(|r| r.ts_unix)
+                .collect::<Vec<_>>(),
+            vec![7]
+        );
+        assert_eq!(read_lines(&rotated(&path, 1))[0].ts_unix, 5);
+        assert_eq!(read_lines(&rotated(&path, 2))[0].ts_unix, 3);
+        assert!(!rotated(&path, 3).exists());
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn reopening_drops_a_partial_trailing_line() {
+        let dir = temp_dir("partial");
+        let path = dir.join("audit.jsonl");
+        let sink = FileAuditSink::open(&path).expect("open");
+        sink.record(record(1)).expect("record");
+        drop(sink);
+        let mut file = OpenOptions::new().append(true).open(&path).expect("open");
+        file.write_all(b"{\"ts_unix\":2,\"event_ty").expect("write");
+        drop(file);
+
+        let sink = FileAuditSink::open(&path).expect("reopen");
+        sink.record(record(3)).expect("record");
+        assert_eq!(
+            read_lines(&path)
+                .iter()
+                .map(|r| r.ts_unix)
+                .collect::<Vec<_>>(),
+            vec![1, 3]
+        );
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn prune_compacts_old_records_into_daily_rollups() {
+        let dir = temp_dir("prune");
+        let path = dir.join("audit.jsonl");
+        let day = 86_400;
+        let line_len = line_len(2 * day);
+        let sink = FileAuditSink::open(&path)
+            .expect("open")
+            .with_max_bytes(line_len * 2);
+        for ts in [10, 20, day + 10, 2 * day + 10] {
+            sink.record(record(ts)).expect("record");
+        }
+
+        let report = sink.prune(2 * day).expect("prune");
+        assert_eq!(report.records_pruned, 3);
+        assert_eq!(report.files_removed, 1);
+        assert_eq!(report.days_compacted, vec![0, day]);
+        assert_eq!(
+            read_lines(&path)
+                .iter()
+                .map(|r| r.ts_unix)
+                .collect::<Vec<_>>(),
+            vec![2 * day + 10]
+        );
+        let rollups = sink.rollups().expect("rollups");
+        assert_eq!(rollups[0].records, 2);
+        assert_eq!(rollups[0].by_event_type["policy.decision"], 2);
+        assert_eq!(rollups[1].by_severity[&Severity::Info], 1);
+
+        // The sink keeps appending after a prune, and a second prune merges into the rollups.
+        sink.record(record(2 * day + 20)).expect("record");
+        sink.prune(3 * day).expect("prune");
+        assert_eq!(sink.rollups().expect("rollups")[2].records, 2);
+        assert_eq!(fs::read_to_string(&path).expect("read"), "");
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn chain_continues_across_rotation_and_reopen() {
+        let dir = temp_dir("chain");
+        let path = dir.join("audit.jsonl");
+        let sink = FileAuditSink::open(&path)
+            .expect("open")
+            .with_max_bytes(line_len(1) * 2);
+        for ts in 1..=3 {
+            sink.record(record(ts)).expect("record");
+        }
+        drop(sink);
+        let sink = FileAuditSink::open(&path).expect("reopen");
+        sink.record(record(4)).expect("record");
+
+        let report = crate::chain::verify_log(&path, Default::default()).expect("verify");
+        assert!(report.intact(), "{report:?}");
+        assert_eq!(report.records_checked, 4);
+        assert_eq!(report.files.len(), 2);
+
+        // A prune keeps the chain verifiable from the first surviving record.
+        sink.prune(3).expect("prune");
+        let report = crate::chain::verify_log(&path, Default::default()).expect("verify");
+        assert!(report.intact(), "{report:?}");
+        assert_eq!(report.records_checked, 2);
+        assert_ne!(report.anchor.as_deref(), Some(GENESIS));
+        let _ = fs::remove_dir_all(dir);
+    }
+}
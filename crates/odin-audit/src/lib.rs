@@ -1,28 +1,83 @@
 //! Audit interface and baseline record types.
 
+pub mod analytics;
+pub mod buffered;
+pub mod chain;
+pub mod fanout;
+pub mod file;
+#[cfg(feature = "otel")]
+pub mod otel;
+pub mod redact;
+pub mod taxonomy;
+
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{Map, Value};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
 pub enum AuditError {
     #[error("write failure: {0}")]
     Write(String),
+    #[error("invalid audit event: {0}")]
+    Taxonomy(String),
+}
+
+/// Ordered from least to most severe, so sinks can filter with `>=`.
+#[derive(
+    Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    #[default]
+    Info,
+    Notice,
+    Warning,
+    Critical,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct AuditRecord {
     pub ts_unix: u64,
     pub event_type: String,
+    /// See `taxonomy::EVENT_TYPES` for the severity each event type is recorded with.
+    #[serde(default)]
+    pub severity: Severity,
     pub request_id: Option<String>,
     pub task_id: Option<String>,
     pub project: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_id: Option<String>,
     #[serde(default)]
     pub metadata: Value,
 }
 
 pub trait AuditSink: Send + Sync {
     fn record(&self, record: AuditRecord) -> Result<(), AuditError>;
+
+    /// Persists anything buffered; called before shutdown.
+    fn flush(&self) -> Result<(), AuditError> {
+        Ok(())
+    }
+}
+
+impl<S: AuditSink + ?Sized> AuditSink for Box<S> {
+    fn record(&self, record: AuditRecord) -> Result<(), AuditError> {
+        (**self).record(record)
+    }
+
+    fn flush(&self) -> Result<(), AuditError> {
+        (**self).flush()
+    }
+}
+
+impl<S: AuditSink + ?Sized> AuditSink for std::sync::Arc<S> {
+    fn record(&self, record: AuditRecord) -> Result<(), AuditError> {
+        (**self).record(record)
+    }
+
+    fn flush(&self) -> Result<(), AuditError> {
+        (**self).flush()
+    }
 }
 
 #[derive(Clone, Debug, Default)]
@@ -34,6 +89,101 @@ impl AuditSink for NoopAuditSink {
     }
 }
 
+/// Adds deployment-wide context to records before they reach a sink.
+pub trait AuditEnricher: Send + Sync {
+    fn enrich(&self, record: &mut AuditRecord);
+}
+
+/// Writes fixed key/value pairs under `metadata.context`. Call-site metadata keeps
+/// priority: existing `context` keys are never overwritten.
+#[derive(Clone, Debug, Default)]
+pub struct ContextEnricher {
+    context: Map<String, Value>,
+}
+
+impl ContextEnricher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads `ODIN_ENVIRONMENT`, `ODIN_REGION` and `HOSTNAME` when set.
+    pub fn from_env() -> Self {
+        [
+            ("environment", "ODIN_ENVIRONMENT"),
+            ("region", "ODIN_REGION"),
+            ("host", "HOSTNAME"),
+        ]
+        .into_iter()
+        .filter_map(|(key, var)| {
+            std::env::var(var)
+                .ok()
+                .filter(|value| !value.trim().is_empty())
+                .map(|value| (key, value))
+        })
+        .fold(Self::new(), |enricher, (key, value)| {
+            enricher.with_field(key, value)
+        })
+    }
+
+    pub fn with_field(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.context.insert(key.into(), value.into());
+        self
+    }
+}
+
+impl AuditEnricher for ContextEnricher {
+    fn enrich(&self, record: &mut AuditRecord) {
+        if record.metadata.is_null() {
+            record.metadata = Value::Object(Map::new());
+        }
+        let Some(metadata) = record.metadata.as_object_mut() else {
+            return;
+        };
+        let context = metadata
+            .entry("context")
+            .or_insert_with(|| Value::Object(Map::new()));
+        let Some(context) = context.as_object_mut() else {
+            return;
+        };
+        for (key, value) in &self.context {
+            context.entry(key.clone()).or_insert_with(|| value.clone());
+        }
+    }
+}
+
+/// Runs every enricher, in registration order, before forwarding to `inner`.
+pub struct EnrichedAuditSink<S> {
+    inner: S,
+    enrichers: Vec<Box<dyn AuditEnricher>>,
+}
+
+impl<S: AuditSink> EnrichedAuditSink<S> {
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            enrichers: Vec::new(),
+        }
+    }
+
+    pub fn with_enricher(mut self, enricher: impl AuditEnricher + 'static) -> Self {
+        self.enrichers.push(Box::new(enricher));
+        self
+    }
+}
+
+impl<S: AuditSink> AuditSink for EnrichedAuditSink<S> {
+    fn record(&self, mut record: AuditRecord) -> Result<(), AuditError> {
+        for enricher in &self.enrichers {
+            enricher.enrich(&mut record);
+        }
+        self.inner.record(record)
+    }
+
+    fn flush(&self) -> Result<(), AuditError> {
+        self.inner.flush()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -44,12 +194,69 @@ mod tests {
         let result = sink.record(AuditRecord {
             ts_unix: 1,
             event_type: "policy.decision".to_string(),
+            severity: Severity::Info,
             request_id: Some("r1".to_string()),
             task_id: None,
             project: Some("demo".to_string()),
+            trace_id: None,
             metadata: Value::Null,
         });
 
         assert!(result.is_ok());
     }
+
+    #[derive(Default)]
+    struct Capture(std::sync::Mutex<Vec<AuditRecord>>);
+
+    impl AuditSink for &Capture {
+        fn record(&self, record: AuditRecord) -> Result<(), AuditError> {
+            self.0.lock().expect("lock").push(record);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn enriched_sink_adds_context_without_overriding_call_site() {
+        let capture = Capture::default();
+        let sink = EnrichedAuditSink::new(&capture).with_enricher(
+            ContextEnricher::new()
+                .with_field("host", "odin-1")
+                .with_field("environment", "prod"),
+        );
+
+        sink.record(AuditRecord {
+            ts_unix: 1,
+            event_type: "action.executed".to_string(),
+            severity: Severity::Info,
+            request_id: None,
+            task_id: None,
+            project: None,
+            trace_id: None,
+            metadata: serde_json::json!({
+                "plugin": "example.safe-github",
+                "context": { "environment": "staging" }
+            }),
+        })
+        .expect("record");
+        sink.record(AuditRecord {
+            ts_unix: 2,
+            event_type: "policy.decision".to_string(),
+            severity: Severity::Info,
+            request_id: None,
+            task_id: None,
+            project: None,
+            trace_id: None,
+            metadata: Value::Null,
+        })
+        .expect("record");
+
+        let records = capture.0.lock().expect("lock");
+        assert_eq!(records[0].metadata["plugin"], "example.safe-github");
+        assert_eq!(records[0].metadata["context"]["environment"], "staging");
+        assert_eq!(records[0].metadata["context"]["host"], "odin-1");
+        assert_eq!(
+            records[1].metadata["context"],
+            serde_json::json!({ "environment": "prod", "host": "odin-1" })
+        );
+    }
 }
@@ -0,0 +1,212 @@
+//! Exports audit records as OpenTelemetry log records over OTLP/HTTP (JSON encoding), so
+//! governance events land next to the traces and logs a deployment already collects. A record's
+//! `trace_id` becomes the log record's `traceId` when it is a valid 32-digit hex id, which lets
+//! Grafana/Tempo/Loki link the event to its trace.
+
+use std::time::Duration;
+
+use serde_json::{json, Value};
+
+use crate::{AuditError, AuditRecord, AuditSink, Severity};
+
+/// The OpenTelemetry severity number and text for a record's severity.
+fn otel_severity(severity: Severity) -> (u8, &'static str) {
+    match severity {
+        Severity::Info => (9, "INFO"),
+        Severity::Notice => (10, "INFO2"),
+        Severity::Warning => (13, "WARN"),
+        Severity::Critical => (17, "ERROR"),
+    }
+}
+
+pub trait OtlpTransport: Send + Sync {
+    /// Delivers one `ExportLogsServiceRequest` document.
+    fn export(&self, request: &Value) -> Result<(), AuditError>;
+}
+
+/// Posts to an OTLP/HTTP logs endpoint, e.g. `http://otel-collector:4318/v1/logs`.
+#[derive(Clone, Debug)]
+pub struct HttpOtlpTransport {
+    endpoint: String,
+    headers: Vec<(String, String)>,
+    timeout: Duration,
+}
+
+impl HttpOtlpTransport {
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            headers: Vec::new(),
+            timeout: Duration::from_secs(5),
+        }
+    }
+
+    /// Adds a header to every export, e.g. collector authentication.
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+impl OtlpTransport for HttpOtlpTransport {
+    fn export(&self, request: &Value) -> Result<(), AuditError> {
+        let agent = ureq::AgentBuilder::new().timeout(self.timeout).build();
+        let mut call = agent.post(&self.endpoint);
+        for (name, value) in &self.headers {
+            call = call.set(name, value);
+        }
+        call.send_json(request)
+            .map(|_| ())
+            .map_err(|e| AuditError::Write(format!("otlp export to {} failed: {e}", self.endpoint)))
+    }
+}
+
+pub struct OtelAuditSink<T> {
+    transport: T,
+    service_name: String,
+}
+
+impl<T: OtlpTransport> OtelAuditSink<T> {
+    pub fn new(transport: T) -> Self {
+        Self {
+            transport,
+            service_name: "odin-orchestrator".to_string(),
+        }
+    }
+
+    /// The `service.name` resource attribute; defaults to `odin-orchestrator`.
+    pub fn with_service_name(mut self, service_name: impl Into<String>) -> Self {
+        self.service_name = service_name.into();
+        self
+    }
+}
+
+impl<T: OtlpTransport> AuditSink for OtelAuditSink<T> {
+    fn record(&self, record: AuditRecord) -> Result<(), AuditError> {
+        self.transport
+            .export(&export_request(&self.service_name, &[record]))
+    }
+}
+
+/// Wraps log records in an `ExportLogsServiceRequest` for one service.
+pub fn export_request(service_name: &str, records: &[AuditRecord]) -> Value {
+    json!({
+        "resourceLogs": [{
+            "resource": {
+                "attributes": [string_attribute("service.name", service_name)]
+            },
+            "scopeLogs": [{
+                "scope": { "name": "odin-audit" },
+                "logRecords": records.iter().map(log_record).collect::<Vec<_>>()
+            }]
+        }]
+    })
+}
+
+/// Maps an audit record onto an OTLP log record: the event type is the body, the remaining
+/// fields are `odin.*` attributes and the metadata is carried as a JSON string.
+pub fn log_record(record: &AuditRecord) -> Value {
+    let mut attributes = vec![string_attribute("odin.event_type", &record.event_type)];
+    for (key, value) in [
+        ("odin.request_id", &record.request_id),
+        ("odin.task_id", &record.task_id),
+        ("odin.project", &record.project),
+    ] {
+        if let Some(value) = value {
+            attributes.push(string_attribute(key, value));
+        }
+    }
+    if !record.metadata.is_null() {
+        attributes.push(string_attribute(
+            "odin.metadata",
+            &record.metadata.to_string(),
+        ));
+    }
+    let (severity_number, severity_text) = otel_severity(record.severity);
+    let mut log = json!({
+        "timeUnixNano": (u128::from(record.ts_unix) * 1_000_000_000).to_string(),
+        "severityNumber": severity_number,
+        "severityText": severity_text,
+        "body": { "stringValue": record.event_type },
+        "attributes": attributes,
+    });
+    if let Some(trace_id) = record.trace_id.as_deref().filter(|id| is_trace_id(id)) {
+        log["traceId"] = Value::String(trace_id.to_ascii_lowercase());
+    }
+    log
+}
+
+fn string_attribute(key: &str, value: &str) -> Value {
+    json!({ "key": key, "value": { "stringValue": value } })
+}
+
+/// OTLP trace ids are 16 bytes, hex-encoded, and never all zero.
+fn is_trace_id(id: &str) -> bool {
+    id.len() == 32 && id.chars().all(|c| c.is_ascii_hexdigit()) && id.chars().any(|c| c != '0')
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct Capture(Mutex<Vec<Value>>);
+
+    impl OtlpTransport for &Capture {
+        fn export(&self, request: &Value) -> Result<(), AuditError> {
+            self.0.lock().expect("lock").push(request.clone());
+            Ok(())
+        }
+    }
+
+    fn record(trace_id: Option<&str>) -> AuditRecord {
+        AuditRecord {
+            ts_unix: 1_700_000_000,
+            event_type: "policy.decision".to_string(),
+            severity: Severity::Info,
+            request_id: Some("req-1".to_string()),
+            task_id: None,
+            project: Some("demo".to_string()),
+            trace_id: trace_id.map(str::to_string),
+            metadata: json!({ "decision": "allow" }),
+        }
+    }
+
+    #[test]
+    fn records_export_as_otlp_logs_correlated_with_their_trace() {
+        let capture = Capture::default();
+        let sink = OtelAuditSink::new(&capture).with_service_name("odin-test");
+        sink.record(record(Some("0123456789ABCDEF0123456789abcdef")))
+            .expect("record");
+        sink.record(record(Some("task-7"))).expect("record");
+
+        let exports = capture.0.lock().expect("lock");
+        let resource = &exports[0]["resourceLogs"][0];
+        assert_eq!(
+            resource["resource"]["attributes"][0]["value"]["stringValue"],
+            "odin-test"
+        );
+        let log = &resource["scopeLogs"][0]["logRecords"][0];
+        assert_eq!(log["timeUnixNano"], "1700000000000000000");
+        assert_eq!(log["body"]["stringValue"], "policy.decision");
+        assert_eq!(log["severityText"], "INFO");
+        assert_eq!(log["traceId"], "0123456789abcdef0123456789abcdef");
+        let attributes = log["attributes"].as_array().expect("attributes");
+        assert!(attributes.contains(&string_attribute("odin.project", "demo")));
+        assert!(attributes.contains(&string_attribute(
+            "odin.metadata",
+            r#"{"decision":"allow"}"#
+        )));
+
+        // Ids that are not OTLP trace ids are left out rather than sent malformed.
+        let log = &exports[1]["resourceLogs"][0]["scopeLogs"][0]["logRecords"][0];
+        assert!(log.get("traceId").is_none());
+    }
+}
@@ -0,0 +1,190 @@
+//! Registered audit event types and the minimum severity each is recorded with, so sinks can
+//! filter and alert on severity instead of matching event-type strings.
+
+use crate::{AuditError, AuditRecord, Severity};
+
+pub const ACTION_EXECUTED: &str = "action.executed";
+pub const ACTION_FAILED: &str = "action.failed";
+pub const ACTION_INTERCEPTED: &str = "action.intercepted";
+pub const APPROVAL_EXPIRED: &str = "approval.expired";
+pub const APPROVAL_GRANTED: &str = "approval.granted";
+pub const APPROVAL_PENDING: &str = "approval.pending";
+pub const APPROVAL_REVOKED: &str = "approval.revoked";
+pub const APPROVAL_SELF_APPROVAL_BLOCKED: &str = "approval.self_approval_blocked";
+pub const CAPABILITY_DEPRECATED: &str = "capability.deprecated";
+pub const CAPABILITY_SCOPES_QUERIED: &str = "capability.scopes.queried";
+pub const EVENT_SCHEMA_INVALID: &str = "event.schema.invalid";
+pub const GOVERNANCE_ACK_ACCEPTED: &str = "governance.ack.accepted";
+pub const GOVERNANCE_CAPABILITY_REVOKED: &str = "governance.capability.revoked";
+pub const GOVERNANCE_CAPABILITY_USED: &str = "governance.capability.used";
+pub const GOVERNANCE_MANIFEST_DENIED: &str = "governance.manifest.denied";
+pub const GOVERNANCE_MANIFEST_VALIDATED: &str = "governance.manifest.validated";
+pub const GOVERNANCE_SCOPE_EXPANDED: &str = "governance.scope.expanded";
+pub const GOVERNANCE_TRUST_DEMOTED: &str = "governance.trust.demoted";
+pub const GOVERNANCE_TRUST_PROMOTED: &str = "governance.trust.promoted";
+pub const HTTP_EGRESS: &str = "http.egress";
+pub const PLUGIN_CIRCUIT_CLOSED: &str = "plugin.circuit.closed";
+pub const PLUGIN_CIRCUIT_HALF_OPEN: &str = "plugin.circuit.half_open";
+pub const PLUGIN_CIRCUIT_OPENED: &str = "plugin.circuit.opened";
+pub const PLUGIN_CONCURRENCY_LIMITED: &str = "plugin.concurrency.limited";
+pub const PLUGIN_DISPATCH_CANCELLED: &str = "plugin.dispatch.cancelled";
+pub const PLUGIN_DISPATCHED: &str = "plugin.dispatched";
+pub const PLUGIN_EVENT_DELIVERED: &str = "plugin.event.delivered";
+pub const PLUGIN_NOOP: &str = "plugin.noop";
+pub const PLUGIN_OUTPUT_TRUNCATED: &str = "plugin.output.truncated";
+pub const POLICY_DECISION: &str = "policy.decision";
+pub const POLICY_ELEVATION_EXPIRED: &str = "policy.elevation.expired";
+pub const POLICY_ELEVATION_GRANTED: &str = "policy.elevation.granted";
+pub const POLICY_GRANT_EXPIRED: &str = "policy.grant.expired";
+pub const POLICY_OVERRIDE: &str = "policy.override";
+pub const POLICY_OVERRIDE_EXPIRED: &str = "policy.override.expired";
+pub const POLICY_OVERRIDE_ISSUED: &str = "policy.override.issued";
+pub const POLICY_OVERRIDE_REJECTED: &str = "policy.override.rejected";
+pub const POLICY_RECORD_FAILED: &str = "policy.record.failed";
+pub const RUNTIME_RECONCILED: &str = "runtime.reconciled";
+pub const RUNTIME_SHUTDOWN: &str = "runtime.shutdown";
+pub const SECRET_DENIED: &str = "secret.denied";
+pub const SECRET_INJECTED: &str = "secret.injected";
+pub const SECRET_LEASED: &str = "secret.leased";
+pub const SECRET_RESOLVE_FAILED: &str = "secret.resolve_failed";
+pub const SECRET_RESOLVED: &str = "secret.resolved";
+pub const SELFCHECK_COMPLETED: &str = "selfcheck.completed";
+pub const SELFCHECK_QUARANTINED: &str = "selfcheck.quarantined";
+pub const SELFCHECK_REPAIRED: &str = "selfcheck.repaired";
+pub const SESSION_REAUTH_COMPLETED: &str = "session.reauth.completed";
+pub const SESSION_REAUTH_REQUESTED: &str = "session.reauth.requested";
+pub const TASK_ARCHIVE_RETENTION: &str = "task.archive.retention";
+pub const TASK_BATCH_COMPLETED: &str = "task.batch.completed";
+pub const TASK_CANCELLED: &str = "task.cancelled";
+pub const TASK_DEAD_LETTER_RETRIED: &str = "task.dead_letter.retried";
+pub const TASK_DEAD_LETTER_RETRY_FAILED: &str = "task.dead_letter.retry_failed";
+pub const TASK_DEAD_LETTERED: &str = "task.dead_lettered";
+pub const TASK_ENQUEUED: &str = "task.enqueued";
+pub const TASK_RESUMED: &str = "task.resumed";
+pub const WORKSPACE_SNAPSHOT_CREATED: &str = "workspace.snapshot.created";
+
+/// Every registered event type with its minimum severity.
+pub const EVENT_TYPES: &[(&str, Severity)] = &[
+    (ACTION_EXECUTED, Severity::Info),
+    (ACTION_FAILED, Severity::Warning),
+    (ACTION_INTERCEPTED, Severity::Notice),
+    (APPROVAL_EXPIRED, Severity::Notice),
+    (APPROVAL_GRANTED, Severity::Notice),
+    (APPROVAL_PENDING, Severity::Notice),
+    (APPROVAL_REVOKED, Severity::Notice),
+    (APPROVAL_SELF_APPROVAL_BLOCKED, Severity::Warning),
+    (CAPABILITY_DEPRECATED, Severity::Notice),
+    (CAPABILITY_SCOPES_QUERIED, Severity::Info),
+    (EVENT_SCHEMA_INVALID, Severity::Warning),
+    (GOVERNANCE_ACK_ACCEPTED, Severity::Notice),
+    (GOVERNANCE_CAPABILITY_REVOKED, Severity::Warning),
+    (GOVERNANCE_CAPABILITY_USED, Severity::Info),
+    (GOVERNANCE_MANIFEST_DENIED, Severity::Critical),
+    (GOVERNANCE_MANIFEST_VALIDATED, Severity::Info),
+    (GOVERNANCE_SCOPE_EXPANDED, Severity::Notice),
+    (GOVERNANCE_TRUST_DEMOTED, Severity::Warning),
+    (GOVERNANCE_TRUST_PROMOTED, Severity::Notice),
+    (HTTP_EGRESS, Severity::Info),
+    (PLUGIN_CIRCUIT_CLOSED, Severity::Notice),
+    (PLUGIN_CIRCUIT_HALF_OPEN, Severity::Notice),
+    (PLUGIN_CIRCUIT_OPENED, Severity::Warning),
+    (PLUGIN_CONCURRENCY_LIMITED, Severity::Notice),
+    (PLUGIN_DISPATCH_CANCELLED, Severity::Notice),
+    (PLUGIN_DISPATCHED, Severity::Info),
+    (PLUGIN_EVENT_DELIVERED, Severity::Info),
+    (PLUGIN_NOOP, Severity::Info),
+    (PLUGIN_OUTPUT_TRUNCATED, Severity::Warning),
+    (POLICY_DECISION, Severity::Info),
+    (POLICY_ELEVATION_EXPIRED, Severity::Notice),
+    (POLICY_ELEVATION_GRANTED, Severity::Warning),
+    (POLICY_GRANT_EXPIRED, Severity::Notice),
+    (POLICY_OVERRIDE, Severity::Critical),
+    (POLICY_OVERRIDE_EXPIRED, Severity::Notice),
+    (POLICY_OVERRIDE_ISSUED, Severity::Critical),
+    (POLICY_OVERRIDE_REJECTED, Severity::Warning),
+    (POLICY_RECORD_FAILED, Severity::Warning),
+    (RUNTIME_RECONCILED, Severity::Notice),
+    (RUNTIME_SHUTDOWN, Severity::Notice),
+    (SECRET_DENIED, Severity::Warning),
+    (SECRET_INJECTED, Severity::Notice),
+    (SECRET_LEASED, Severity::Notice),
+    (SECRET_RESOLVE_FAILED, Severity::Warning),
+    (SECRET_RESOLVED, Severity::Info),
+    (SELFCHECK_COMPLETED, Severity::Info),
+    (SELFCHECK_QUARANTINED, Severity::Warning),
+    (SELFCHECK_REPAIRED, Severity::Notice),
+    (SESSION_REAUTH_COMPLETED, Severity::Notice),
+    (SESSION_REAUTH_REQUESTED, Severity::Notice),
+    (TASK_ARCHIVE_RETENTION, Severity::Info),
+    (TASK_BATCH_COMPLETED, Severity::Info),
+    (TASK_CANCELLED, Severity::Notice),
+    (TASK_DEAD_LETTER_RETRIED, Severity::Notice),
+    (TASK_DEAD_LETTER_RETRY_FAILED, Severity::Warning),
+    (TASK_DEAD_LETTERED, Severity::Warning),
+    (TASK_ENQUEUED, Severity::Info),
+    (TASK_RESUMED, Severity::Notice),
+    (WORKSPACE_SNAPSHOT_CREATED, Severity::Info),
+];
+
+/// The registered minimum severity, or `None` for an unregistered event type.
+pub fn registered_severity(event_type: &str) -> Option<Severity> {
+    EVENT_TYPES
+        .iter()
+        .find(|(name, _)| *name == event_type)
+        .map(|(_, severity)| *severity)
+}
+
+/// Severity to record `event_type` with; unregistered types are `Info`.
+pub fn severity(event_type: &str) -> Severity {
+    registered_severity(event_type).unwrap_or_default()
+}
+
+/// Rejects records with an unregistered event type or a severity below the registered one.
+pub fn validate(record: &AuditRecord) -> Result<(), AuditError> {
+    let registered = registered_severity(&record.event_type).ok_or_else(|| {
+        AuditError::Taxonomy(format!("unregistered event type {:?}", record.event_type))
+    })?;
+    if record.severity < registered {
+        return Err(AuditError::Taxonomy(format!(
+            "{} must be recorded at {registered:?} or above, got {:?}",
+            record.event_type, record.severity
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_is_sorted_and_unique() {
+        let names: Vec<&str> = EVENT_TYPES.iter().map(|(name, _)| *name).collect();
+        let mut sorted = names.clone();
+        sorted.sort_unstable();
+        sorted.dedup();
+        assert_eq!(names, sorted);
+    }
+
+    #[test]
+    fn validate_checks_registration_and_minimum_severity() {
+        let mut record = AuditRecord {
+            ts_unix: 1,
+            event_type: POLICY_OVERRIDE.to_string(),
+            severity: Severity::Critical,
+            request_id: None,
+            task_id: None,
+            project: None,
+            trace_id: None,
+            metadata: serde_json::Value::Null,
+        };
+        validate(&record).expect("registered");
+
+        record.severity = Severity::Warning;
+        assert!(validate(&record).is_err());
+        record.event_type = "policy.made_up".to_string();
+        record.severity = Severity::Critical;
+        assert!(validate(&record).is_err());
+        assert_eq!(severity("policy.made_up"), Severity::Info);
+    }
+}
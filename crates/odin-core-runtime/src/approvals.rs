@@ -0,0 +1,189 @@
+//! Persistence for actions parked on a `RequireApproval` decision.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use odin_plugin_protocol::ActionRequest;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{RuntimeError, RuntimeResult};
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct PendingApproval {
+    pub request: ActionRequest,
+    pub reason_code: String,
+    pub created_at_unix: u64,
+    pub expires_at_unix: u64,
+}
+
+impl PendingApproval {
+    pub fn request_id(&self) -> &str {
+        &self.request.request_id
+    }
+
+    pub fn is_expired(&self, now_unix: u64) -> bool {
+        now_unix >= self.expires_at_unix
+    }
+}
+
+pub trait ApprovalStore: Send + Sync {
+    fn save(&self, pending: &PendingApproval) -> RuntimeResult<()>;
+    fn get(&self, request_id: &str) -> RuntimeResult<Option<PendingApproval>>;
+    fn remove(&self, request_id: &str) -> RuntimeResult<()>;
+    fn list(&self) -> RuntimeResult<Vec<PendingApproval>>;
+}
+
+#[derive(Debug, Default)]
+pub struct InMemoryApprovalStore {
+    pending: Mutex<BTreeMap<String, PendingApproval>>,
+}
+
+impl InMemoryApprovalStore {
+    fn lock(&self) -> RuntimeResult<std::sync::MutexGuard<'_, BTreeMap<String, PendingApproval>>> {
+        self.pending
+            .lock()
+            .map_err(|_| RuntimeError::Execution("approval store lock poisoned".to_string()))
+    }
+}
+
+impl ApprovalStore for InMemoryApprovalStore {
+    fn save(&self, pending: &PendingApproval) -> RuntimeResult<()> {
+        self.lock()?
+            .insert(pending.request_id().to_string(), pending.clone());
+        Ok(())
+    }
+
+    fn get(&self, request_id: &str) -> RuntimeResult<Option<PendingApproval>> {
+        Ok(self.lock()?.get(request_id).cloned())
+    }
+
+    fn remove(&self, request_id: &str) -> RuntimeResult<()> {
+        self.lock()?.remove(request_id);
+        Ok(())
+    }
+
+    fn list(&self) -> RuntimeResult<Vec<PendingApproval>> {
+        Ok(self.lock()?.values().cloned().collect())
+    }
+}
+
+/// File name of the pending approval for `request_id`: `<request_id>.json`, or
+/// `_sha256-<hex>.json` for ids that are not plain file names.
+pub fn approval_file_name(request_id: &str) -> String {
+    let plain = !request_id.is_empty()
+        && request_id.len() <= 128
+        && request_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        && !request_id.starts_with(['.', '_']);
+    if plain {
+        format!("{request_id}.json")
+    } else {
+        format!("_sha256-{:x}.json", Sha256::digest(request_id.as_bytes()))
+    }
+}
+
+/// Stores one JSON document per pending request under `dir`, written atomically.
+#[derive(Clone, Debug)]
+pub struct FileApprovalStore {
+    dir: PathBuf,
+}
+
+impl FileApprovalStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn path_for(&self, request_id: &str) -> PathBuf {
+        self.dir.join(approval_file_name(request_id))
+    }
+
+    fn read(path: &Path) -> RuntimeResult<Option<PendingApproval>> {
+        let raw = match fs::read(path) {
+            Ok(raw) => raw,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => {
+                return Err(RuntimeError::Execution(format!(
+                    "failed reading pending approval {}: {e}",
+                    path.display()
+                )))
+            }
+        };
+        serde_json::from_slice(&raw).map(Some).map_err(|e| {
+            RuntimeError::Execution(format!("corrupt pending approval {}: {e}", path.display()))
+        })
+    }
+}
+
+impl ApprovalStore for FileApprovalStore {
+    fn save(&self, pending: &PendingApproval) -> RuntimeResult<()> {
+        let path = self.path_for(pending.request_id());
+        fs::create_dir_all(&self.dir).map_err(|e| {
+            RuntimeError::Execution(format!(
+                "failed creating approval dir {}: {e}",
+                self.dir.display()
+            ))
+        })?;
+        let body = serde_json::to_vec_pretty(pending).map_err(|e| {
+            RuntimeError::Execution(format!("failed serializing pending approval: {e}"))
+        })?;
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, body)
+            .and_then(|_| fs::rename(&tmp, &path))
+            .map_err(|e| {
+                RuntimeError::Execution(format!(
+                    "failed writing pending approval {}: {e}",
+                    path.display()
+                ))
+            })
+    }
+
+    fn get(&self, request_id: &str) -> RuntimeResult<Option<PendingApproval>> {
+        Self::read(&self.path_for(request_id))
+    }
+
+    fn remove(&self, request_id: &str) -> RuntimeResult<()> {
+        let path = self.path_for(request_id);
+        match fs::remove_file(&path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(RuntimeError::Execution(format!(
+                "failed removing pending approval {}: {e}",
+                path.display()
+            ))),
+        }
+    }
+
+    fn list(&self) -> RuntimeResult<Vec<PendingApproval>> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => {
+                return Err(RuntimeError::Execution(format!(
+                    "failed listing approvals in {}: {e}",
+                    self.dir.display()
+                )))
+            }
+        };
+
+        let mut pending = Vec::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            if let Some(item) = Self::read(&path)? {
+                pending.push(item);
+            }
+        }
+        pending.sort_by(|a, b| a.request.request_id.cmp(&b.request.request_id));
+        Ok(pending)
+    }
+}
@@ -0,0 +1,194 @@
+//! Result cache for idempotent read capabilities. Outputs are keyed by a hash of the
+//! plugin, project, capability, scope and input, and served until the capability's TTL
+//! lapses so bursty polls do not repeat the same external call.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{Hash, Hasher};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use odin_plugin_protocol::ActionRequest;
+use serde_json::Value;
+
+use crate::{RuntimeError, RuntimeResult};
+
+/// Capabilities not listed here are never cached.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ResultCacheConfig {
+    ttls: BTreeMap<String, Duration>,
+}
+
+impl ResultCacheConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capability(mut self, capability: impl Into<String>, ttl: Duration) -> Self {
+        self.ttls.insert(capability.into(), ttl);
+        self
+    }
+
+    pub fn ttl_for(&self, capability: &str) -> Option<Duration> {
+        self.ttls
+            .get(capability)
+            .copied()
+            .filter(|ttl| !ttl.is_zero())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CacheHit {
+    pub key: String,
+    pub age: Duration,
+    pub output: Value,
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    stored_at: Instant,
+    expires_at: Instant,
+    output: Value,
+}
+
+#[derive(Debug, Default)]
+pub struct ResultCache {
+    config: ResultCacheConfig,
+    entries: Mutex<HashMap<String, CacheEntry>>,
+}
+
+impl ResultCache {
+    pub fn new(config: ResultCacheConfig) -> Self {
+        Self {
+            config,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn config(&self) -> &ResultCacheConfig {
+        &self.config
+    }
+
+    /// The cache key for `request`, or `None` when its capability is not cacheable.
+    pub fn key(&self, request: &ActionRequest) -> Option<String> {
+        let capability = &request.capability;
+        self.config.ttl_for(&capability.capability)?;
+        let mut hasher = DefaultHasher::new();
+        (
+            &capability.plugin,
+            &capability.project,
+            &capability.capability,
+            &capability.scope,
+            request.input.to_string(),
+        )
+            .hash(&mut hasher);
+        Some(format!(
+            "{}:{:016x}",
+            capability.capability,
+            hasher.finish()
+        ))
+    }
+
+    pub fn get(&self, request: &ActionRequest, now: Instant) -> RuntimeResult<Option<CacheHit>> {
+        let Some(key) = self.key(request) else {
+            return Ok(None);
+        };
+        let mut entries = self.lock()?;
+        match entries.get(&key) {
+            Some(entry) if now < entry.expires_at => Ok(Some(CacheHit {
+                age: now.saturating_duration_since(entry.stored_at),
+                output: entry.output.clone(),
+                key,
+            })),
+            Some(_) => {
+                entries.remove(&key);
+                Ok(None)
+            }
+            None => Ok(None),
+        }
+    }
+
+    pub fn insert(
+        &self,
+        request: &ActionRequest,
+        output: &Value,
+        now: Instant,
+    ) -> RuntimeResult<()> {
+        let (Some(key), Some(ttl)) = (
+            self.key(request),
+            self.config.ttl_for(&request.capability.capability),
+        ) else {
+            return Ok(());
+        };
+        let mut entries = self.lock()?;
+        entries.retain(|_, entry| now < entry.expires_at);
+        entries.insert(
+            key,
+            CacheEntry {
+                stored_at: now,
+                expires_at: now + ttl,
+                output: output.clone(),
+            },
+        );
+        Ok(())
+    }
+
+    fn lock(&self) -> RuntimeResult<MutexGuard<'_, HashMap<String, CacheEntry>>> {
+        self.entries
+            .lock()
+            .map_err(|_| RuntimeError::Execution("result cache lock poisoned".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use odin_plugin_protocol::{CapabilityRequest, RiskTier};
+
+    use super::*;
+
+    fn request(capability: &str, input: Value) -> ActionRequest {
+        ActionRequest {
+            request_id: "req-1".to_string(),
+            risk_tier: RiskTier::Safe,
+            capability: CapabilityRequest {
+                plugin: "private.ops-watchdog".to_string(),
+                project: "private".to_string(),
+                capability: capability.to_string(),
+                scope: vec!["project".to_string()],
+                reason: "poll".to_string(),
+            },
+            trace_id: None,
+            input,
+            requested_by: None,
+        }
+    }
+
+    #[test]
+    fn entries_expire_after_ttl_and_differ_by_input() {
+        let cache = ResultCache::new(
+            ResultCacheConfig::new().with_capability("repo.read", Duration::from_secs(30)),
+        );
+        let now = Instant::now();
+        let read = request("repo.read", serde_json::json!({ "repo": "odin" }));
+        cache
+            .insert(&read, &serde_json::json!({ "stars": 3 }), now)
+            .expect("insert");
+
+        let hit = cache
+            .get(&read, now + Duration::from_secs(10))
+            .expect("get")
+            .expect("hit");
+        assert_eq!(hit.output["stars"], 3);
+        assert_eq!(hit.age, Duration::from_secs(10));
+
+        let other = request("repo.read", serde_json::json!({ "repo": "other" }));
+        assert_eq!(cache.get(&other, now).expect("get"), None);
+        assert_eq!(
+            cache
+                .get(&read, now + Duration::from_secs(30))
+                .expect("get"),
+            None
+        );
+        assert_eq!(cache.key(&request("repo.write", Value::Null)), None);
+    }
+}
@@ -0,0 +1,76 @@
+//! Cooperative cancellation shared by the runtime, plugin runner and CLI signal handler.
+//! Cancelling lets in-flight work finish its current step; nothing new is started.
+
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::Duration;
+
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken {
+    inner: Arc<(Mutex<bool>, Condvar)>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Marks the token cancelled and wakes every waiter. Idempotent.
+    pub fn cancel(&self) {
+        let (cancelled, changed) = &*self.inner;
+        if let Ok(mut cancelled) = cancelled.lock() {
+            *cancelled = true;
+        }
+        changed.notify_all();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.inner
+            .0
+            .lock()
+            .map(|cancelled| *cancelled)
+            .unwrap_or(true)
+    }
+
+    /// Blocks until cancelled or `timeout` elapses; returns whether the token is cancelled.
+    pub fn wait_timeout(&self, timeout: Duration) -> bool {
+        let (cancelled, changed) = &*self.inner;
+        let Ok(guard) = cancelled.lock() else {
+            return true;
+        };
+        changed
+            .wait_timeout_while(guard, timeout, |cancelled| !*cancelled)
+            .map(|(cancelled, _)| *cancelled)
+            .unwrap_or(true)
+    }
+
+    /// Blocks until cancelled.
+    pub fn wait(&self) {
+        let (cancelled, changed) = &*self.inner;
+        if let Ok(guard) = cancelled.lock() {
+            drop(changed.wait_while(guard, |cancelled| !*cancelled));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+
+    #[test]
+    fn cancel_wakes_waiters_on_clones() {
+        let token = CancellationToken::new();
+        assert!(!token.wait_timeout(Duration::from_millis(5)));
+
+        let waiter = {
+            let token = token.clone();
+            thread::spawn(move || token.wait_timeout(Duration::from_secs(5)))
+        };
+        thread::sleep(Duration::from_millis(20));
+        token.cancel();
+        assert!(waiter.join().expect("join"));
+        assert!(token.is_cancelled());
+        token.wait();
+    }
+}
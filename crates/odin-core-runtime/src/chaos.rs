@@ -0,0 +1,547 @@
+//! Fault injection for resilience testing, compiled only with the `chaos` feature. The
+//! wrappers here sit at the runtime's seams (plugin runner, audit sink, task ingress and
+//! the wall clock) and fail on a seeded, reproducible schedule. `run_chaos_scenarios`
+//! drives the runtime through each fault and reports whether it degraded as designed.
+
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use odin_audit::{AuditError, AuditRecord, AuditSink, NoopAuditSink};
+use odin_plugin_protocol::{
+    ActionRequest, ActionStatus, CapabilityManifest, CapabilityRequest, EventEnvelope, RiskTier,
+};
+use odin_policy_engine::StaticPolicyEngine;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::circuit::CircuitBreakerConfig;
+use crate::versioning::PluginVersionInfo;
+use crate::{
+    ActionExecutor, DispatchDiagnostics, DryRunExecutor, OrchestratorRuntime, PluginDirective,
+    PluginEventRunner, RuntimeError, RuntimeResult, TaskIngress,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChaosFault {
+    PluginSpawn,
+    AuditWrite,
+    IngressTimeout,
+}
+
+/// Failure rates are probabilities in `[0, 1]`; the same seed yields the same schedule.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChaosConfig {
+    pub seed: u64,
+    pub plugin_spawn_failure_rate: f64,
+    pub audit_write_failure_rate: f64,
+    pub ingress_timeout_rate: f64,
+    /// How long an injected ingress timeout blocks before failing.
+    pub ingress_timeout: Duration,
+}
+
+impl Default for ChaosConfig {
+    fn default() -> Self {
+        Self {
+            seed: 0x0d1e,
+            plugin_spawn_failure_rate: 0.0,
+            audit_write_failure_rate: 0.0,
+            ingress_timeout_rate: 0.0,
+            ingress_timeout: Duration::from_millis(50),
+        }
+    }
+}
+
+impl ChaosConfig {
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn with_plugin_spawn_failures(mut self, rate: f64) -> Self {
+        self.plugin_spawn_failure_rate = rate;
+        self
+    }
+
+    pub fn with_audit_write_failures(mut self, rate: f64) -> Self {
+        self.audit_write_failure_rate = rate;
+        self
+    }
+
+    pub fn with_ingress_timeouts(mut self, rate: f64, timeout: Duration) -> Self {
+        self.ingress_timeout_rate = rate;
+        self.ingress_timeout = timeout;
+        self
+    }
+}
+
+/// Shared fault schedule handed to every chaos wrapper; counts what it injected.
+#[derive(Debug)]
+pub struct ChaosMonkey {
+    config: ChaosConfig,
+    state: Mutex<u64>,
+    injected: Mutex<BTreeMap<ChaosFault, u64>>,
+}
+
+impl ChaosMonkey {
+    pub fn new(config: ChaosConfig) -> Arc<Self> {
+        Arc::new(Self {
+            state: Mutex::new(config.seed | 1),
+            config,
+            injected: Mutex::new(BTreeMap::new()),
+        })
+    }
+
+    pub fn config(&self) -> &ChaosConfig {
+        &self.config
+    }
+
+    /// Rolls the schedule for `fault`, counting the injection when it fires.
+    pub fn inject(&self, fault: ChaosFault) -> bool {
+        let rate = match fault {
+            ChaosFault::PluginSpawn => self.config.plugin_spawn_failure_rate,
+            ChaosFault::AuditWrite => self.config.audit_write_failure_rate,
+            ChaosFault::IngressTimeout => self.config.ingress_timeout_rate,
+        };
+        if rate <= 0.0 {
+            return false;
+        }
+        let fire = rate >= 1.0 || self.next_unit() < rate;
+        if fire {
+            if let Ok(mut injected) = self.injected.lock() {
+                *injected.entry(fault).or_default() += 1;
+            }
+        }
+        fire
+    }
+
+    pub fn injected(&self, fault: ChaosFault) -> u64 {
+        self.injected
+            .lock()
+            .map(|injected| injected.get(&fault).copied().unwrap_or(0))
+            .unwrap_or(0)
+    }
+
+    /// xorshift64*; good enough for a failure schedule and needs no extra dependency.
+    fn next_unit(&self) -> f64 {
+        let Ok(mut state) = self.state.lock() else {
+            return 1.0;
+        };
+        *state ^= *state >> 12;
+        *state ^= *state << 25;
+        *state ^= *state >> 27;
+        let value = state.wrapping_mul(0x2545_f491_4f6c_dd1d);
+        (value >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Fails `dispatch_event` as if the plugin process could not be spawned.
+pub struct ChaosPluginRunner<R> {
+    inner: R,
+    monkey: Arc<ChaosMonkey>,
+}
+
+impl<R> ChaosPluginRunner<R> {
+    pub fn new(inner: R, monkey: Arc<ChaosMonkey>) -> Self {
+        Self { inner, monkey }
+    }
+}
+
+impl<R: PluginEventRunner> PluginEventRunner for ChaosPluginRunner<R> {
+    fn dispatch_event(
+        &self,
+        plugin: &str,
+        event: &EventEnvelope,
+    ) -> RuntimeResult<Vec<PluginDirective>> {
+        if self.monkey.inject(ChaosFault::PluginSpawn) {
+            return Err(RuntimeError::Plugin(format!(
+                "chaos: injected spawn failure for {plugin}"
+            )));
+        }
+        self.inner.dispatch_event(plugin, event)
+    }
+
+    fn subscribers(&self, event_type: &str) -> RuntimeResult<Vec<String>> {
+        self.inner.subscribers(event_type)
+    }
+
+    fn version_info(&self, plugin: &str) -> RuntimeResult<Option<PluginVersionInfo>> {
+        self.inner.version_info(plugin)
+    }
+
+    fn max_concurrent_dispatches(&self, plugin: &str) -> RuntimeResult<Option<u32>> {
+        self.inner.max_concurrent_dispatches(plugin)
+    }
+
+    fn capability_manifest(&self, plugin: &str) -> RuntimeResult<Option<CapabilityManifest>> {
+        self.inner.capability_manifest(plugin)
+    }
+
+    fn dispatch_with_diagnostics(
+        &self,
+        plugin: &str,
+        event: &EventEnvelope,
+    ) -> RuntimeResult<(Vec<PluginDirective>, Option<DispatchDiagnostics>)> {
+        if self.monkey.inject(ChaosFault::PluginSpawn) {
+            return Err(RuntimeError::Plugin(format!(
+                "chaos: injected spawn failure for {plugin}"
+            )));
+        }
+        self.inner.dispatch_with_diagnostics(plugin, event)
+    }
+}
+
+pub struct ChaosAuditSink<S> {
+    inner: S,
+    monkey: Arc<ChaosMonkey>,
+}
+
+impl<S> ChaosAuditSink<S> {
+    pub fn new(inner: S, monkey: Arc<ChaosMonkey>) -> Self {
+        Self { inner, monkey }
+    }
+}
+
+impl<S: AuditSink> AuditSink for ChaosAuditSink<S> {
+    fn record(&self, record: AuditRecord) -> Result<(), AuditError> {
+        if self.monkey.inject(ChaosFault::AuditWrite) {
+            return Err(AuditError::Write(format!(
+                "chaos: injected write failure for {}",
+                record.event_type
+            )));
+        }
+        self.inner.record(record)
+    }
+
+    fn flush(&self) -> Result<(), AuditError> {
+        self.inner.flush()
+    }
+}
+
+/// Blocks for the configured timeout and then fails, like a wedged ingress writer.
+pub struct ChaosTaskIngress<T> {
+    inner: T,
+    monkey: Arc<ChaosMonkey>,
+}
+
+impl<T> ChaosTaskIngress<T> {
+    pub fn new(inner: T, monkey: Arc<ChaosMonkey>) -> Self {
+        Self { inner, monkey }
+    }
+}
+
+impl<T: TaskIngress> TaskIngress for ChaosTaskIngress<T> {
+    fn write_task_payload(&self, payload: &str) -> RuntimeResult<()> {
+        if self.monkey.inject(ChaosFault::IngressTimeout) {
+            let timeout = self.monkey.config().ingress_timeout;
+            thread::sleep(timeout);
+            return Err(RuntimeError::Execution(format!(
+                "chaos: ingress write timed out after {}ms",
+                timeout.as_millis()
+            )));
+        }
+        self.inner.write_task_payload(payload)
+    }
+}
+
+thread_local! {
+    static CLOCK_SKEW_SECS: Cell<i64> = const { Cell::new(0) };
+}
+
+/// Shifts the runtime's wall clock on the calling thread until the guard drops.
+pub fn skew_clock(secs: i64) -> ClockSkewGuard {
+    let previous = CLOCK_SKEW_SECS.with(|skew| skew.replace(secs));
+    ClockSkewGuard { previous }
+}
+
+pub struct ClockSkewGuard {
+    previous: i64,
+}
+
+impl Drop for ClockSkewGuard {
+    fn drop(&mut self) {
+        CLOCK_SKEW_SECS.with(|skew| skew.set(self.previous));
+    }
+}
+
+pub(crate) fn skewed(now_unix: u64) -> u64 {
+    let skew = CLOCK_SKEW_SECS.with(Cell::get);
+    now_unix.saturating_add_signed(skew)
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+pub struct ChaosScenarioResult {
+    pub name: String,
+    pub passed: bool,
+    pub detail: String,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+pub struct ChaosReport {
+    pub seed: u64,
+    pub scenarios: Vec<ChaosScenarioResult>,
+}
+
+impl ChaosReport {
+    pub fn passed(&self) -> bool {
+        self.scenarios.iter().all(|scenario| scenario.passed)
+    }
+}
+
+/// Runs every built-in scenario in-process against stub plugins and sinks.
+pub fn run_chaos_scenarios(seed: u64) -> ChaosReport {
+    let scenarios = [
+        (
+            "plugin_spawn_failures_open_circuit",
+            spawn_failures_open_circuit as fn(u64) -> _,
+        ),
+        (
+            "audit_write_failures_fail_closed",
+            audit_failures_fail_closed,
+        ),
+        ("ingress_timeouts_surface_errors", ingress_timeouts_surface),
+        ("clock_skew_expires_approvals", clock_skew_expires_approvals),
+    ];
+    ChaosReport {
+        seed,
+        scenarios: scenarios
+            .into_iter()
+            .map(|(name, scenario)| {
+                let (passed, detail) = match scenario(seed) {
+                    Ok(detail) => (true, detail),
+                    Err(detail) => (false, detail),
+                };
+                ChaosScenarioResult {
+                    name: name.to_string(),
+                    passed,
+                    detail,
+                }
+            })
+            .collect(),
+    }
+}
+
+type ScenarioResult = Result<String, String>;
+
+const SCENARIO_PLUGIN: &str = "chaos.scenario";
+
+struct NoDirectives;
+
+impl PluginEventRunner for NoDirectives {
+    fn dispatch_event(&self, _: &str, _: &EventEnvelope) -> RuntimeResult<Vec<PluginDirective>> {
+        Ok(Vec::new())
+    }
+}
+
+struct EnqueueFollowup;
+
+impl PluginEventRunner for EnqueueFollowup {
+    fn dispatch_event(&self, _: &str, _: &EventEnvelope) -> RuntimeResult<Vec<PluginDirective>> {
+        Ok(vec![PluginDirective::EnqueueTask {
+            task_type: "chaos.followup".to_string(),
+            project: None,
+            reason: Some("chaos scenario".to_string()),
+            payload: Value::Null,
+        }])
+    }
+}
+
+struct NullIngress;
+
+impl TaskIngress for NullIngress {
+    fn write_task_payload(&self, _: &str) -> RuntimeResult<()> {
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct CountingExecutor(AtomicUsize);
+
+impl ActionExecutor for &CountingExecutor {
+    fn execute(&self, request: &ActionRequest) -> RuntimeResult<Value> {
+        self.0.fetch_add(1, Ordering::SeqCst);
+        DryRunExecutor.execute(request)
+    }
+}
+
+fn scenario_policy() -> StaticPolicyEngine {
+    let mut policy = StaticPolicyEngine::default();
+    policy.allow_capability(SCENARIO_PLUGIN, "chaos", "repo.read");
+    policy.allow_capability(SCENARIO_PLUGIN, "chaos", "task.enqueue");
+    policy
+}
+
+fn scenario_task() -> String {
+    serde_json::json!({
+        "schema_version": 1,
+        "task_id": "chaos-task",
+        "type": "watchdog_poll",
+        "payload": {
+            "task_type": "chaos.poll",
+            "project": "chaos",
+            "plugin": SCENARIO_PLUGIN
+        }
+    })
+    .to_string()
+}
+
+fn scenario_request(risk_tier: RiskTier) -> ActionRequest {
+    ActionRequest {
+        request_id: "chaos-req".to_string(),
+        risk_tier,
+        capability: CapabilityRequest {
+            plugin: SCENARIO_PLUGIN.to_string(),
+            project: "chaos".to_string(),
+            capability: "repo.read".to_string(),
+            scope: vec!["project".to_string()],
+            reason: "chaos scenario".to_string(),
+        },
+        trace_id: None,
+        input: Value::Null,
+        requested_by: None,
+    }
+}
+
+fn spawn_failures_open_circuit(seed: u64) -> ScenarioResult {
+    let threshold = 3;
+    let monkey = ChaosMonkey::new(
+        ChaosConfig::default()
+            .with_seed(seed)
+            .with_plugin_spawn_failures(1.0),
+    );
+    let runner = ChaosPluginRunner::new(NoDirectives, monkey.clone());
+    let runtime = OrchestratorRuntime::new(scenario_policy(), NoopAuditSink, DryRunExecutor)
+        .with_circuit_breaker(CircuitBreakerConfig {
+            failure_threshold: threshold,
+            cooldown: Duration::from_secs(60),
+        });
+
+    for attempt in 1..=threshold {
+        if runtime
+            .handle_task(&scenario_task(), &runner, &NullIngress)
+            .is_ok()
+        {
+            return Err(format!(
+                "dispatch {attempt} succeeded despite spawn failure"
+            ));
+        }
+    }
+    let outcomes = runtime
+        .handle_task(&scenario_task(), &runner, &NullIngress)
+        .map_err(|e| format!("open circuit still dispatched: {e}"))?;
+    match outcomes.first() {
+        Some(outcome) if outcome.detail == "plugin_circuit_open" => Ok(format!(
+            "circuit opened after {} injected spawn failures",
+            monkey.injected(ChaosFault::PluginSpawn)
+        )),
+        other => Err(format!("expected plugin_circuit_open, got {other:?}")),
+    }
+}
+
+fn audit_failures_fail_closed(seed: u64) -> ScenarioResult {
+    let monkey = ChaosMonkey::new(
+        ChaosConfig::default()
+            .with_seed(seed)
+            .with_audit_write_failures(1.0),
+    );
+    let executor = CountingExecutor::default();
+    let runtime = OrchestratorRuntime::new(
+        scenario_policy(),
+        ChaosAuditSink::new(NoopAuditSink, monkey.clone()),
+        &executor,
+    );
+    match runtime.handle_action(scenario_request(RiskTier::Safe)) {
+        Err(RuntimeError::Audit(_)) if executor.0.load(Ordering::SeqCst) == 0 => Ok(format!(
+            "action refused without executing after {} injected audit failures",
+            monkey.injected(ChaosFault::AuditWrite)
+        )),
+        Err(RuntimeError::Audit(_)) => Err("executor ran without an audit trail".to_string()),
+        other => Err(format!("expected an audit failure, got {other:?}")),
+    }
+}
+
+fn ingress_timeouts_surface(seed: u64) -> ScenarioResult {
+    let monkey = ChaosMonkey::new(
+        ChaosConfig::default()
+            .with_seed(seed)
+            .with_ingress_timeouts(1.0, Duration::from_millis(20)),
+    );
+    let runtime = OrchestratorRuntime::new(scenario_policy(), NoopAuditSink, DryRunExecutor);
+    let ingress = ChaosTaskIngress::new(NullIngress, monkey.clone());
+    match runtime.handle_task(&scenario_task(), &EnqueueFollowup, &ingress) {
+        Err(err) if monkey.injected(ChaosFault::IngressTimeout) > 0 => {
+            Ok(format!("enqueue failed instead of hanging: {err}"))
+        }
+        other => Err(format!("expected an ingress timeout, got {other:?}")),
+    }
+}
+
+fn clock_skew_expires_approvals(_seed: u64) -> ScenarioResult {
+    let ttl = Duration::from_secs(300);
+    let mut policy = scenario_policy();
+    policy.set_require_approval_for_destructive(true);
+    let runtime =
+        OrchestratorRuntime::new(policy, NoopAuditSink, DryRunExecutor).with_approval_ttl(ttl);
+    let parked = runtime
+        .handle_action(scenario_request(RiskTier::Destructive))
+        .map_err(|e| format!("approval request failed: {e}"))?;
+    if parked.status != ActionStatus::ApprovalPending {
+        return Err(format!(
+            "expected approval_pending, got {:?}",
+            parked.status
+        ));
+    }
+
+    let _skew = skew_clock(ttl.as_secs() as i64 + 1);
+    let resumed = runtime
+        .resume_approved(&parked.request_id, "chaos")
+        .map_err(|e| format!("resume failed: {e}"))?;
+    if resumed.detail == "approval_expired" {
+        Ok(format!(
+            "approval expired under +{}s clock skew",
+            ttl.as_secs() + 1
+        ))
+    } else {
+        Err(format!("expected approval_expired, got {}", resumed.detail))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schedule_is_reproducible_per_seed() {
+        let config = ChaosConfig::default()
+            .with_seed(42)
+            .with_plugin_spawn_failures(0.5);
+        let roll = |monkey: Arc<ChaosMonkey>| -> Vec<bool> {
+            (0..32)
+                .map(|_| monkey.inject(ChaosFault::PluginSpawn))
+                .collect()
+        };
+        let first = roll(ChaosMonkey::new(config.clone()));
+        assert_eq!(first, roll(ChaosMonkey::new(config)));
+        assert!(first.contains(&true) && first.contains(&false));
+    }
+
+    #[test]
+    fn clock_skew_is_scoped_to_the_guard() {
+        {
+            let _skew = skew_clock(-30);
+            assert_eq!(skewed(100), 70);
+        }
+        assert_eq!(skewed(100), 100);
+    }
+
+    #[test]
+    fn built_in_scenarios_pass() {
+        let report = run_chaos_scenarios(7);
+        assert!(report.passed(), "{report:#?}");
+        assert_eq!(report.scenarios.len(), 4);
+    }
+}
@@ -0,0 +1,263 @@
+//! In-flight task journal. A checkpoint is written when `handle_task` takes a task up, advanced
+//! as its directives are applied and removed once the task settles, so checkpoints found at
+//! startup belong to work that was interrupted by a crash or shutdown.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use odin_plugin_protocol::ActionOutcome;
+use serde::{Deserialize, Serialize};
+
+use crate::{PluginDirective, RuntimeError, RuntimeResult};
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TaskCheckpoint {
+    pub task_id: String,
+    /// The task envelope as ingested (with its `trace_id`), used to requeue or resume it.
+    pub raw_task: String,
+    pub started_at_unix: u64,
+    /// Directives the plugin returned; `None` until the dispatch completes.
+    #[serde(default)]
+    pub directives: Option<Vec<PluginDirective>>,
+    /// Index of the first directive not yet applied.
+    #[serde(default)]
+    pub directive_index: usize,
+    /// Outcomes of the directives before `directive_index`.
+    #[serde(default)]
+    pub outcomes: Vec<ActionOutcome>,
+}
+
+impl TaskCheckpoint {
+    pub fn new(task_id: impl Into<String>, raw_task: impl Into<String>) -> Self {
+        Self {
+            task_id: task_id.into(),
+            raw_task: raw_task.into(),
+            started_at_unix: crate::now_unix(),
+            directives: None,
+            directive_index: 0,
+            outcomes: Vec::new(),
+        }
+    }
+}
+
+pub trait CheckpointStore: Send + Sync {
+    fn save(&self, checkpoint: &TaskCheckpoint) -> RuntimeResult<()>;
+    fn remove(&self, task_id: &str) -> RuntimeResult<()>;
+    /// Checkpoints still open, oldest first.
+    fn list(&self) -> RuntimeResult<Vec<TaskCheckpoint>>;
+    /// Moves a checkpoint out of the open set so it is kept for inspection but never retried.
+    fn quarantine(&self, task_id: &str) -> RuntimeResult<()>;
+}
+
+/// What startup reconciliation does with each interrupted task.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReconcilePolicy {
+    /// Continue the task in-process from its checkpointed directive before accepting new work.
+    Resume,
+    /// Write the task back to ingress.
+    #[default]
+    Requeue,
+    /// Set the task aside without running it.
+    Quarantine,
+}
+
+#[derive(Clone, Debug, Default, Serialize, PartialEq, Eq)]
+pub struct ReconcileReport {
+    pub policy: ReconcilePolicy,
+    pub resumed: Vec<String>,
+    pub requeued: Vec<String>,
+    pub quarantined: Vec<String>,
+    /// Approvals still waiting on an operator; they resume through `resume_approved`.
+    pub approvals_pending: usize,
+    /// Approvals that expired while the runtime was down; they are dropped.
+    pub approvals_expired: Vec<String>,
+}
+
+impl ReconcileReport {
+    pub fn summary(&self) -> String {
+        format!(
+            "{} resumed, {} requeued, {} quarantined, {} approvals pending, {} approvals expired",
+            self.resumed.len(),
+            self.requeued.len(),
+            self.quarantined.len(),
+            self.approvals_pending,
+            self.approvals_expired.len()
+        )
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct InMemoryCheckpointStore {
+    open: Mutex<BTreeMap<String, TaskCheckpoint>>,
+    quarantined: Mutex<BTreeMap<String, TaskCheckpoint>>,
+}
+
+impl InMemoryCheckpointStore {
+    pub fn quarantined(&self) -> RuntimeResult<Vec<TaskCheckpoint>> {
+        Ok(lock(&self.quarantined)?.values().cloned().collect())
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> RuntimeResult<std::sync::MutexGuard<'_, T>> {
+    mutex
+        .lock()
+        .map_err(|_| RuntimeError::Execution("checkpoint store lock poisoned".to_string()))
+}
+
+impl CheckpointStore for InMemoryCheckpointStore {
+    fn save(&self, checkpoint: &TaskCheckpoint) -> RuntimeResult<()> {
+        lock(&self.open)?.insert(checkpoint.task_id.clone(), checkpoint.clone());
+        Ok(())
+    }
+
+    fn remove(&self, task_id: &str) -> RuntimeResult<()> {
+        lock(&self.open)?.remove(task_id);
+        Ok(())
+    }
+
+    fn list(&self) -> RuntimeResult<Vec<TaskCheckpoint>> {
+        let mut open: Vec<TaskCheckpoint> = lock(&self.open)?.values().cloned().collect();
+        open.sort_by_key(|checkpoint| checkpoint.started_at_unix);
+        Ok(open)
+    }
+
+    fn quarantine(&self, task_id: &str) -> RuntimeResult<()> {
+        if let Some(checkpoint) = lock(&self.open)?.remove(task_id) {
+            lock(&self.quarantined)?.insert(task_id.to_string(), checkpoint);
+        }
+        Ok(())
+    }
+}
+
+/// One JSON document per open task under `dir`; quarantined ones move to `dir/quarantine`.
+#[derive(Clone, Debug)]
+pub struct FileCheckpointStore {
+    dir: PathBuf,
+}
+
+impl FileCheckpointStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn path_for(&self, task_id: &str) -> RuntimeResult<PathBuf> {
+        let valid = !task_id.is_empty()
+            && task_id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+            && !task_id.starts_with('.');
+        if !valid {
+            return Err(RuntimeError::InvalidInput(format!(
+                "task_id not usable as checkpoint key: {task_id}"
+            )));
+        }
+        Ok(self.dir.join(format!("{task_id}.json")))
+    }
+}
+
+fn io_err(action: &str, path: &Path, err: std::io::Error) -> RuntimeError {
+    RuntimeError::Execution(format!(
+        "failed {action} checkpoint {}: {err}",
+        path.display()
+    ))
+}
+
+impl CheckpointStore for FileCheckpointStore {
+    fn save(&self, checkpoint: &TaskCheckpoint) -> RuntimeResult<()> {
+        let path = self.path_for(&checkpoint.task_id)?;
+        fs::create_dir_all(&self.dir).map_err(|e| io_err("creating dir for", &self.dir, e))?;
+        let body = serde_json::to_vec_pretty(checkpoint)
+            .map_err(|e| RuntimeError::Execution(format!("failed serializing checkpoint: {e}")))?;
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, body)
+            .and_then(|_| fs::rename(&tmp, &path))
+            .map_err(|e| io_err("writing", &path, e))
+    }
+
+    fn remove(&self, task_id: &str) -> RuntimeResult<()> {
+        let path = self.path_for(task_id)?;
+        match fs::remove_file(&path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(io_err("removing", &path, e)),
+        }
+    }
+
+    fn list(&self) -> RuntimeResult<Vec<TaskCheckpoint>> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(io_err("listing", &self.dir, e)),
+        };
+        let mut open = Vec::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            let raw = fs::read(&path).map_err(|e| io_err("reading", &path, e))?;
+            let checkpoint: TaskCheckpoint = serde_json::from_slice(&raw).map_err(|e| {
+                RuntimeError::Execution(format!("corrupt checkpoint {}: {e}", path.display()))
+            })?;
+            open.push(checkpoint);
+        }
+        open.sort_by_key(|checkpoint| checkpoint.started_at_unix);
+        Ok(open)
+    }
+
+    fn quarantine(&self, task_id: &str) -> RuntimeResult<()> {
+        let path = self.path_for(task_id)?;
+        let quarantine_dir = self.dir.join("quarantine");
+        fs::create_dir_all(&quarantine_dir)
+            .map_err(|e| io_err("creating quarantine for", &quarantine_dir, e))?;
+        let target = quarantine_dir.join(format!("{task_id}.json"));
+        fs::rename(&path, &target).map_err(|e| io_err("quarantining", &path, e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_store_lists_open_checkpoints_oldest_first_and_quarantines() {
+        let dir = std::env::temp_dir().join(format!(
+            "odin-checkpoints-{}-{}",
+            std::process::id(),
+            crate::now_unix()
+        ));
+        let store = FileCheckpointStore::new(&dir);
+        for (task_id, started_at_unix) in [("task-b", 20), ("task-a", 10)] {
+            store
+                .save(&TaskCheckpoint {
+                    started_at_unix,
+                    ..TaskCheckpoint::new(task_id, "{}")
+                })
+                .expect("save");
+        }
+
+        let ids = |store: &FileCheckpointStore| -> Vec<String> {
+            store
+                .list()
+                .expect("list")
+                .into_iter()
+                .map(|checkpoint| checkpoint.task_id)
+                .collect()
+        };
+        assert_eq!(ids(&store), vec!["task-a", "task-b"]);
+
+        store.quarantine("task-a").expect("quarantine");
+        store.remove("task-b").expect("remove");
+        assert!(ids(&store).is_empty());
+        assert!(dir.join("quarantine/task-a.json").is_file());
+        assert!(store.save(&TaskCheckpoint::new("../escape", "{}")).is_err());
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,211 @@
+//! Per-plugin circuit breaker. Consecutive dispatch or execution failures open the
+//! circuit for a cooldown; afterwards it half-opens and the next result closes or
+//! reopens it.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use crate::{RuntimeError, RuntimeResult};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive failures that open the circuit.
+    pub failure_threshold: u32,
+    pub cooldown: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            cooldown: Duration::from_secs(60),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CircuitState {
+    Closed,
+    Open { until: Instant },
+    HalfOpen,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CircuitTransition {
+    Opened { consecutive_failures: u32 },
+    HalfOpened,
+    Closed,
+}
+
+impl CircuitTransition {
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            Self::Opened { .. } => "plugin.circuit.opened",
+            Self::HalfOpened => "plugin.circuit.half_open",
+            Self::Closed => "plugin.circuit.closed",
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Circuit {
+    state: CircuitState,
+    consecutive_failures: u32,
+}
+
+impl Default for Circuit {
+    fn default() -> Self {
+        Self {
+            state: CircuitState::Closed,
+            consecutive_failures: 0,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    circuits: Mutex<HashMap<String, Circuit>>,
+}
+
+impl CircuitBreaker {
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            circuits: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn config(&self) -> &CircuitBreakerConfig {
+        &self.config
+    }
+
+    pub fn state(&self, plugin: &str) -> RuntimeResult<CircuitState> {
+        Ok(self
+            .lock()?
+            .get(plugin)
+            .map_or(CircuitState::Closed, |circuit| circuit.state))
+    }
+
+    /// Returns whether `plugin` may run at `now`, half-opening an open circuit whose
+    /// cooldown has elapsed.
+    pub fn admit(
+        &self,
+        plugin: &str,
+        now: Instant,
+    ) -> RuntimeResult<(bool, Option<CircuitTransition>)> {
+        let mut circuits = self.lock()?;
+        let Some(circuit) = circuits.get_mut(plugin) else {
+            return Ok((true, None));
+        };
+        match circuit.state {
+            CircuitState::Open { until } if now < until => Ok((false, None)),
+            CircuitState::Open { .. } => {
+                circuit.state = CircuitState::HalfOpen;
+                Ok((true, Some(CircuitTransition::HalfOpened)))
+            }
+            CircuitState::Closed | CircuitState::HalfOpen => Ok((true, None)),
+        }
+    }
+
+    pub fn record_success(&self, plugin: &str) -> RuntimeResult<Option<CircuitTransition>> {
+        let mut circuits = self.lock()?;
+        let Some(circuit) = circuits.get_mut(plugin) else {
+            return Ok(None);
+        };
+        circuit.consecutive_failures = 0;
+        if circuit.state == CircuitState::Closed {
+            return Ok(None);
+        }
+        circuit.state = CircuitState::Closed;
+        Ok(Some(CircuitTransition::Closed))
+    }
+
+    pub fn record_failure(
+        &self,
+        plugin: &str,
+        now: Instant,
+    ) -> RuntimeResult<Option<CircuitTransition>> {
+        let mut circuits = self.lock()?;
+        let circuit = circuits.entry(plugin.to_string()).or_default();
+        circuit.consecutive_failures = circuit.consecutive_failures.saturating_add(1);
+        let trips = match circuit.state {
+            CircuitState::HalfOpen => true,
+            CircuitState::Closed => {
+                circuit.consecutive_failures >= self.config.failure_threshold.max(1)
+            }
+            CircuitState::Open { .. } => false,
+        };
+        if !trips {
+            return Ok(None);
+        }
+        circuit.state = CircuitState::Open {
+            until: now + self.config.cooldown,
+        };
+        Ok(Some(CircuitTransition::Opened {
+            consecutive_failures: circuit.consecutive_failures,
+        }))
+    }
+
+    fn lock(&self) -> RuntimeResult<MutexGuard<'_, HashMap<String, Circuit>>> {
+        self.circuits
+            .lock()
+            .map_err(|_| RuntimeError::Execution("circuit breaker lock poisoned".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn opens_after_threshold_and_recovers_through_half_open() {
+        let breaker = CircuitBreaker::new(CircuitBreakerConfig {
+            failure_threshold: 2,
+            cooldown: Duration::from_secs(30),
+        });
+        let start = Instant::now();
+
+        assert_eq!(breaker.record_failure("p", start).expect("failure"), None);
+        assert_eq!(
+            breaker.record_failure("p", start).expect("failure"),
+            Some(CircuitTransition::Opened {
+                consecutive_failures: 2
+            })
+        );
+        assert_eq!(breaker.admit("p", start).expect("admit"), (false, None));
+        assert_eq!(breaker.admit("other", start).expect("admit"), (true, None));
+
+        let later = start + Duration::from_secs(30);
+        assert_eq!(
+            breaker.admit("p", later).expect("admit"),
+            (true, Some(CircuitTransition::HalfOpened))
+        );
+        assert_eq!(
+            breaker.record_success("p").expect("success"),
+            Some(CircuitTransition::Closed)
+        );
+        assert_eq!(breaker.state("p").expect("state"), CircuitState::Closed);
+    }
+
+    #[test]
+    fn half_open_failure_reopens_immediately() {
+        let breaker = CircuitBreaker::new(CircuitBreakerConfig {
+            failure_threshold: 3,
+            cooldown: Duration::from_secs(10),
+        });
+        let start = Instant::now();
+        for _ in 0..3 {
+            breaker.record_failure("p", start).expect("failure");
+        }
+        let later = start + Duration::from_secs(10);
+        breaker.admit("p", later).expect("admit");
+
+        assert!(matches!(
+            breaker.record_failure("p", later).expect("failure"),
+            Some(CircuitTransition::Opened { .. })
+        ));
+        assert_eq!(breaker.admit("p", later).expect("admit"), (false, None));
+    }
+}
@@ -0,0 +1,176 @@
+//! Native executor for `command.run`. Commands and workspaces are checked with the same
+//! `HuginnPolicy` rules that govern Huginn command scopes, then run without a shell in the
+//! workspace as `workspace_boundary` resolved it.
+
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use odin_governance::plugins::{
+    huginn_default_policy, parse_command, Action, HuginnPolicy, PermissionDecision,
+};
+use odin_governance::workspace_boundary;
+use odin_plugin_protocol::ActionRequest;
+use serde_json::Value;
+
+use crate::{read_capped_draining, ActionExecutor, RuntimeError, RuntimeResult};
+
+pub const DEFAULT_COMMAND_TIMEOUT: Duration = Duration::from_secs(60);
+pub const DEFAULT_COMMAND_OUTPUT_BYTES: usize = 256 * 1024;
+
+type CappedReader = thread::JoinHandle<std::io::Result<(Vec<u8>, bool)>>;
+
+/// Runs `{"command": "git status --short", "workspace": "/srv/repo"}` inside the
+/// workspace. Non-zero exits fail the action with the stderr tail.
+#[derive(Clone, Debug)]
+pub struct CommandActionExecutor {
+    policy: HuginnPolicy,
+    timeout: Duration,
+    max_output_bytes: usize,
+}
+
+impl CommandActionExecutor {
+    pub fn new<C, W, S, T>(commands: C, workspaces: W) -> Self
+    where
+        C: IntoIterator<Item = S>,
+        S: AsRef<str>,
+        W: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        Self::from_policy(
+            huginn_default_policy()
+                .with_enabled(true)
+                .with_commands(commands)
+                .with_workspaces(workspaces),
+        )
+    }
+
+    pub fn from_policy(policy: HuginnPolicy) -> Self {
+        Self {
+            policy,
+            timeout: DEFAULT_COMMAND_TIMEOUT,
+            max_output_bytes: DEFAULT_COMMAND_OUTPUT_BYTES,
+        }
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn with_max_output_bytes(mut self, max_output_bytes: usize) -> Self {
+        self.max_output_bytes = max_output_bytes;
+        self
+    }
+
+    fn check(&self, action: Action, command: &str) -> RuntimeResult<()> {
+        match self.policy.evaluate(action) {
+            PermissionDecision::Allow { .. } => Ok(()),
+            // Nothing here can wait for an approval, so an approval requirement fails closed.
+            PermissionDecision::Deny { reason_code }
+            | PermissionDecision::RequireApproval { reason_code } => {
+                Err(RuntimeError::CommandDenied {
+                    command: command.to_string(),
+                    reason: reason_code,
+                })
+            }
+        }
+    }
+}
+
+impl ActionExecutor for CommandActionExecutor {
+    fn execute(&self, request: &ActionRequest) -> RuntimeResult<Value> {
+        let input_str = |key: &str| {
+            request
+                .input
+                .get(key)
+                .and_then(Value::as_str)
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .ok_or_else(|| {
+                    RuntimeError::InvalidInput(format!(
+                        "{} requires input.{key}",
+                        request.capability.capability
+                    ))
+                })
+        };
+        let command_line = input_str("command")?;
+        let workspace = input_str("workspace")?;
+        self.check(Action::RunCommand(command_line.to_string()), command_line)?;
+        self.check(Action::ReadWorkspace(workspace.to_string()), command_line)?;
+        let (program, args) = parse_command(command_line).ok_or_else(|| {
+            RuntimeError::InvalidInput(format!("unparseable command: {command_line}"))
+        })?;
+        let workspace_dir = workspace_boundary::normalize(workspace.as_ref())
+            .filter(|dir| dir.is_dir())
+            .ok_or_else(|| {
+                RuntimeError::InvalidInput(format!("workspace is not a directory: {workspace}"))
+            })?;
+
+        let mut child = Command::new(&program)
+            .args(&args)
+            .current_dir(&workspace_dir)
+            .env_clear()
+            .envs(std::env::var_os("PATH").map(|path| ("PATH", path)))
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| RuntimeError::Execution(format!("failed to start {program}: {e}")))?;
+
+        let limit = self.max_output_bytes;
+        let stdout = child
+            .stdout
+            .take()
+            .map(|stdout| thread::spawn(move || read_capped_draining(stdout, limit)));
+        let stderr = child
+            .stderr
+            .take()
+            .map(|stderr| thread::spawn(move || read_capped_draining(stderr, limit)));
+
+        let deadline = Instant::now() + self.timeout;
+        let status = loop {
+            match child.try_wait() {
+                Ok(Some(status)) => break status,
+                Ok(None) if Instant::now() >= deadline => {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Err(RuntimeError::Execution(format!(
+                        "{command_line} timed out after {}ms",
+                        self.timeout.as_millis()
+                    )));
+                }
+                Ok(None) => thread::sleep(Duration::from_millis(10)),
+                Err(e) => {
+                    return Err(RuntimeError::Execution(format!(
+                        "{command_line} wait failed: {e}"
+                    )))
+                }
+            }
+        };
+        let collect = |reader: Option<CappedReader>| {
+            reader
+                .and_then(|reader| reader.join().ok())
+                .and_then(Result::ok)
+                .map(|(bytes, truncated)| (String::from_utf8_lossy(&bytes).into_owned(), truncated))
+                .unwrap_or_default()
+        };
+        let (stdout, stdout_truncated) = collect(stdout);
+        let (stderr, stderr_truncated) = collect(stderr);
+
+        if !status.success() {
+            return Err(RuntimeError::Execution(format!(
+                "{command_line} failed (exit={status}): {}",
+                stderr.trim()
+            )));
+        }
+        Ok(serde_json::json!({
+            "command": command_line,
+            "workspace": workspace,
+            "exit_code": status.code(),
+            "stdout": stdout,
+            "stderr": stderr,
+            "truncated": stdout_truncated || stderr_truncated
+        }))
+    }
+}
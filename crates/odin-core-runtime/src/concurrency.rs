@@ -0,0 +1,144 @@
+//! Per-plugin dispatch concurrency limits declared by `max_concurrent_dispatches` in the
+//! plugin manifest. Excess dispatches wait for a slot or are rejected, depending on the
+//! configured `DispatchOverflow`.
+
+use std::collections::HashMap;
+use std::sync::{Condvar, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use crate::{RuntimeError, RuntimeResult};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DispatchOverflow {
+    /// Fail the dispatch immediately with `plugin_concurrency_limited`.
+    Reject,
+    /// Wait up to `max_wait` for a slot before rejecting.
+    Queue { max_wait: Duration },
+}
+
+impl Default for DispatchOverflow {
+    fn default() -> Self {
+        Self::Queue {
+            max_wait: Duration::from_secs(30),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct DispatchLimiter {
+    overflow: DispatchOverflow,
+    active: Mutex<HashMap<String, u32>>,
+    released: Condvar,
+}
+
+impl DispatchLimiter {
+    pub fn new(overflow: DispatchOverflow) -> Self {
+        Self {
+            overflow,
+            active: Mutex::new(HashMap::new()),
+            released: Condvar::new(),
+        }
+    }
+
+    pub fn overflow(&self) -> DispatchOverflow {
+        self.overflow
+    }
+
+    pub fn active(&self, plugin: &str) -> RuntimeResult<u32> {
+        Ok(self.lock()?.get(plugin).copied().unwrap_or(0))
+    }
+
+    /// Takes one of `plugin`'s `limit` slots, or returns `None` when none frees up in time.
+    pub fn acquire(&self, plugin: &str, limit: u32) -> RuntimeResult<Option<DispatchPermit<'_>>> {
+        let deadline = match self.overflow {
+            DispatchOverflow::Reject => None,
+            DispatchOverflow::Queue { max_wait } => Some(Instant::now() + max_wait),
+        };
+        let mut active = self.lock()?;
+        loop {
+            let running = active.get(plugin).copied().unwrap_or(0);
+            if running < limit.max(1) {
+                active.insert(plugin.to_string(), running + 1);
+                return Ok(Some(DispatchPermit {
+                    limiter: self,
+                    plugin: plugin.to_string(),
+                }));
+            }
+            let Some(wait) = deadline.and_then(|d| d.checked_duration_since(Instant::now())) else {
+                return Ok(None);
+            };
+            active = self
+                .released
+                .wait_timeout(active, wait)
+                .map_err(|_| poisoned())?
+                .0;
+        }
+    }
+
+    fn release(&self, plugin: &str) {
+        if let Ok(mut active) = self.active.lock() {
+            if let Some(running) = active.get_mut(plugin) {
+                *running = running.saturating_sub(1);
+                if *running == 0 {
+                    active.remove(plugin);
+                }
+            }
+        }
+        self.released.notify_all();
+    }
+
+    fn lock(&self) -> RuntimeResult<MutexGuard<'_, HashMap<String, u32>>> {
+        self.active.lock().map_err(|_| poisoned())
+    }
+}
+
+fn poisoned() -> RuntimeError {
+    RuntimeError::Execution("dispatch limiter lock poisoned".to_string())
+}
+
+/// Holds a dispatch slot until dropped.
+#[derive(Debug)]
+pub struct DispatchPermit<'a> {
+    limiter: &'a DispatchLimiter,
+    plugin: String,
+}
+
+impl Drop for DispatchPermit<'_> {
+    fn drop(&mut self) {
+        self.limiter.release(&self.plugin);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+
+    #[test]
+    fn reject_mode_refuses_beyond_limit() {
+        let limiter = DispatchLimiter::new(DispatchOverflow::Reject);
+        let first = limiter.acquire("p", 1).expect("acquire");
+        assert!(first.is_some());
+        assert!(limiter.acquire("p", 1).expect("acquire").is_none());
+        assert!(limiter.acquire("other", 1).expect("acquire").is_some());
+
+        drop(first);
+        assert_eq!(limiter.active("p").expect("active"), 0);
+        assert!(limiter.acquire("p", 1).expect("acquire").is_some());
+    }
+
+    #[test]
+    fn queue_mode_waits_for_a_released_slot() {
+        let limiter = DispatchLimiter::new(DispatchOverflow::Queue {
+            max_wait: Duration::from_secs(5),
+        });
+        let held = limiter.acquire("p", 1).expect("acquire").expect("slot");
+        thread::scope(|scope| {
+            let waiter = scope.spawn(|| limiter.acquire("p", 1).expect("acquire").is_some());
+            thread::sleep(Duration::from_millis(20));
+            drop(held);
+            assert!(waiter.join().expect("join"));
+        });
+    }
+}
@@ -0,0 +1,220 @@
+//! Dead-letter queue for tasks the runtime could not process. Rejected or failed task payloads
+//! are kept verbatim with the rejection reason so an operator can inspect and retry them.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{RuntimeError, RuntimeResult};
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DeadLetterEntry {
+    pub id: String,
+    /// Parsed task id, when the payload got that far.
+    pub task_id: Option<String>,
+    /// The task payload exactly as it was handed to `handle_task`.
+    pub raw_task: String,
+    /// Stable reason code, e.g. `invalid_task` or `plugin_failed`.
+    pub reason: String,
+    /// The error message that rejected the task.
+    pub detail: String,
+    pub failed_at_unix: u64,
+    /// Processing attempts so far, including the first.
+    pub attempts: u32,
+}
+
+pub trait DeadLetterQueue: Send + Sync {
+    /// Stores `entry`, replacing any entry with the same id.
+    fn push(&self, entry: &DeadLetterEntry) -> RuntimeResult<()>;
+    /// Entries in the queue, oldest first.
+    fn list(&self) -> RuntimeResult<Vec<DeadLetterEntry>>;
+    fn remove(&self, id: &str) -> RuntimeResult<()>;
+
+    fn get(&self, id: &str) -> RuntimeResult<Option<DeadLetterEntry>> {
+        Ok(self.list()?.into_iter().find(|entry| entry.id == id))
+    }
+}
+
+/// Reason code recorded for a task rejected with `err`.
+pub fn dead_letter_reason(err: &RuntimeError) -> &'static str {
+    match err {
+        RuntimeError::InvalidInput(_) => "invalid_task",
+        RuntimeError::Plugin(_)
+        | RuntimeError::PluginOutputLimitExceeded { .. }
+        | RuntimeError::EntrypointDenied { .. } => "plugin_failed",
+        RuntimeError::Policy(_) => "policy_error",
+        RuntimeError::Audit(_) => "audit_error",
+        RuntimeError::Execution(_)
+        | RuntimeError::CommandDenied { .. }
+        | RuntimeError::EgressDenied { .. }
+        | RuntimeError::Cancelled(_) => "execution_failed",
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct InMemoryDeadLetterQueue {
+    entries: Mutex<BTreeMap<String, DeadLetterEntry>>,
+}
+
+impl InMemoryDeadLetterQueue {
+    fn entries(
+        &self,
+    ) -> RuntimeResult<std::sync::MutexGuard<'_, BTreeMap<String, DeadLetterEntry>>> {
+        self.entries
+            .lock()
+            .map_err(|_| RuntimeError::Execution("dead-letter queue lock poisoned".to_string()))
+    }
+}
+
+impl DeadLetterQueue for InMemoryDeadLetterQueue {
+    fn push(&self, entry: &DeadLetterEntry) -> RuntimeResult<()> {
+        self.entries()?.insert(entry.id.clone(), entry.clone());
+        Ok(())
+    }
+
+    fn list(&self) -> RuntimeResult<Vec<DeadLetterEntry>> {
+        let mut entries: Vec<DeadLetterEntry> = self.entries()?.values().cloned().collect();
+        entries.sort_by_key(|entry| entry.failed_at_unix);
+        Ok(entries)
+    }
+
+    fn remove(&self, id: &str) -> RuntimeResult<()> {
+        self.entries()?.remove(id);
+        Ok(())
+    }
+}
+
+/// One JSON document per entry under `dir`.
+#[derive(Clone, Debug)]
+pub struct FileDeadLetterQueue {
+    dir: PathBuf,
+}
+
+impl FileDeadLetterQueue {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn path_for(&self, id: &str) -> RuntimeResult<PathBuf> {
+        let valid = !id.is_empty()
+            && id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_'));
+        if !valid {
+            return Err(RuntimeError::InvalidInput(format!(
+                "dead-letter id not usable as a file name: {id}"
+            )));
+        }
+        Ok(self.dir.join(format!("{id}.json")))
+    }
+}
+
+fn io_err(action: &str, path: &Path, err: std::io::Error) -> RuntimeError {
+    RuntimeError::Execution(format!(
+        "failed {action} dead-letter entry {}: {err}",
+        path.display()
+    ))
+}
+
+impl DeadLetterQueue for FileDeadLetterQueue {
+    fn push(&self, entry: &DeadLetterEntry) -> RuntimeResult<()> {
+        let path = self.path_for(&entry.id)?;
+        fs::create_dir_all(&self.dir).map_err(|e| io_err("creating dir for", &self.dir, e))?;
+        let body = serde_json::to_vec_pretty(entry).map_err(|e| {
+            RuntimeError::Execution(format!("failed serializing dead-letter entry: {e}"))
+        })?;
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, body)
+            .and_then(|_| fs::rename(&tmp, &path))
+            .map_err(|e| io_err("writing", &path, e))
+    }
+
+    fn list(&self) -> RuntimeResult<Vec<DeadLetterEntry>> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(io_err("listing", &self.dir, e)),
+        };
+        let mut listed = Vec::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            let raw = fs::read(&path).map_err(|e| io_err("reading", &path, e))?;
+            let entry: DeadLetterEntry = serde_json::from_slice(&raw).map_err(|e| {
+                RuntimeError::Execution(format!(
+                    "corrupt dead-letter entry {}: {e}",
+                    path.display()
+                ))
+            })?;
+            listed.push(entry);
+        }
+        listed.sort_by_key(|entry| entry.failed_at_unix);
+        Ok(listed)
+    }
+
+    fn remove(&self, id: &str) -> RuntimeResult<()> {
+        let path = self.path_for(id)?;
+        match fs::remove_file(&path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(io_err("removing", &path, e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_queue_round_trips_entries_oldest_first() {
+        let dir = std::env::temp_dir().join(format!(
+            "odin-dlq-{}-{}",
+            std::process::id(),
+            crate::now_unix()
+        ));
+        let queue = FileDeadLetterQueue::new(&dir);
+        for (id, failed_at_unix) in [("b", 20), ("a", 10)] {
+            queue
+                .push(&DeadLetterEntry {
+                    id: id.to_string(),
+                    task_id: None,
+                    raw_task: "not json".to_string(),
+                    reason: "invalid_task".to_string(),
+                    detail: "invalid watchdog task JSON".to_string(),
+                    failed_at_unix,
+                    attempts: 1,
+                })
+                .expect("push");
+        }
+
+        let ids: Vec<String> = queue
+            .list()
+            .expect("list")
+            .into_iter()
+            .map(|entry| entry.id)
+            .collect();
+        assert_eq!(ids, vec!["a", "b"]);
+        assert_eq!(
+            queue
+                .get("b")
+                .expect("get")
+                .map(|entry| entry.failed_at_unix),
+            Some(20)
+        );
+
+        queue.remove("a").expect("remove");
+        assert_eq!(queue.list().expect("list").len(), 1);
+        assert!(queue.remove("../b").is_err());
+        let _ = fs::remove_dir_all(dir);
+    }
+}
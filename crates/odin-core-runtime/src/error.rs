@@ -0,0 +1,159 @@
+//! Machine-readable view of `RuntimeError`. Codes are stable identifiers that callers may
+//! match on; messages are for humans and may change.
+
+use serde::{Deserialize, Serialize, Serializer};
+use serde_json::Value;
+
+use crate::RuntimeError;
+
+/// Where in the request pipeline an error arose.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorPhase {
+    Validation,
+    Policy,
+    Dispatch,
+    Execution,
+    Audit,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ErrorEnvelope {
+    pub code: String,
+    pub message: String,
+    pub phase: ErrorPhase,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plugin: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capability: Option<String>,
+    /// Variant-specific fields such as `limit_bytes` or `url`.
+    #[serde(default, skip_serializing_if = "Value::is_null")]
+    pub details: Value,
+}
+
+impl ErrorEnvelope {
+    /// Fills `plugin` when the error itself did not carry one.
+    pub fn with_plugin(mut self, plugin: impl Into<String>) -> Self {
+        self.plugin.get_or_insert_with(|| plugin.into());
+        self
+    }
+
+    pub fn with_capability(mut self, capability: impl Into<String>) -> Self {
+        self.capability.get_or_insert_with(|| capability.into());
+        self
+    }
+}
+
+impl RuntimeError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Policy(_) => "policy_error",
+            Self::Audit(_) => "audit_error",
+            Self::Execution(_) => "execution_failed",
+            Self::Plugin(_) => "plugin_failed",
+            Self::InvalidInput(_) => "invalid_input",
+            Self::PluginOutputLimitExceeded { .. } => "plugin_output_limit_exceeded",
+            Self::CommandDenied { .. } => "command_denied",
+            Self::EgressDenied { .. } => "egress_denied",
+            Self::EntrypointDenied { .. } => "entrypoint_denied",
+            Self::Cancelled(_) => "cancelled",
+        }
+    }
+
+    pub fn phase(&self) -> ErrorPhase {
+        match self {
+            Self::InvalidInput(_) => ErrorPhase::Validation,
+            Self::Policy(_) => ErrorPhase::Policy,
+            Self::Plugin(_)
+            | Self::PluginOutputLimitExceeded { .. }
+            | Self::EntrypointDenied { .. }
+            | Self::Cancelled(_) => ErrorPhase::Dispatch,
+            Self::Execution(_) | Self::CommandDenied { .. } | Self::EgressDenied { .. } => {
+                ErrorPhase::Execution
+            }
+            Self::Audit(_) => ErrorPhase::Audit,
+        }
+    }
+
+    pub fn envelope(&self) -> ErrorEnvelope {
+        let (plugin, capability, details) = match self {
+            Self::PluginOutputLimitExceeded {
+                plugin,
+                limit_bytes,
+                ..
+            } => (
+                Some(plugin.clone()),
+                None,
+                serde_json::json!({ "limit_bytes": limit_bytes }),
+            ),
+            Self::CommandDenied { command, reason } => (
+                None,
+                None,
+                serde_json::json!({ "command": command, "reason": reason }),
+            ),
+            Self::EgressDenied {
+                capability,
+                url,
+                reason,
+            } => (
+                None,
+                Some(capability.clone()),
+                serde_json::json!({ "url": url, "reason": reason }),
+            ),
+            Self::EntrypointDenied {
+                plugin,
+                command,
+                reason,
+            } => (
+                Some(plugin.clone()),
+                None,
+                serde_json::json!({ "command": command, "reason": reason }),
+            ),
+            _ => (None, None, Value::Null),
+        };
+        ErrorEnvelope {
+            code: self.code().to_string(),
+            message: self.to_string(),
+            phase: self.phase(),
+            plugin,
+            capability,
+            details,
+        }
+    }
+}
+
+impl Serialize for RuntimeError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.envelope().serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn envelopes_carry_code_phase_and_fields() {
+        let err = RuntimeError::EgressDenied {
+            capability: "http.get".to_string(),
+            url: "https://evil.example".to_string(),
+            reason: "egress_not_allowlisted".to_string(),
+        };
+        let value = serde_json::to_value(&err).expect("serialize");
+        assert_eq!(value["code"], "egress_denied");
+        assert_eq!(value["phase"], "execution");
+        assert_eq!(value["capability"], "http.get");
+        assert_eq!(value["details"]["url"], "https://evil.example");
+
+        let envelope = RuntimeError::Execution("boom".to_string())
+            .envelope()
+            .with_plugin("example.safe-github")
+            .with_capability("repo.read");
+        assert_eq!(envelope.code, "execution_failed");
+        assert_eq!(envelope.plugin.as_deref(), Some("example.safe-github"));
+        let round_trip: ErrorEnvelope =
+            serde_json::from_value(serde_json::to_value(&envelope).expect("serialize"))
+                .expect("deserialize");
+        assert_eq!(round_trip, envelope);
+    }
+}
@@ -0,0 +1,144 @@
+//! Plugin health checks: a `plugin.ping` event dispatched outside any task to confirm a plugin
+//! has a readable manifest, an entrypoint that starts, and a well-formed directive reply.
+
+use std::time::Instant;
+
+use odin_plugin_protocol::EventEnvelope;
+use serde::Serialize;
+
+use crate::versioning::PluginVersionInfo;
+use crate::{new_trace_id, PluginEventRunner, RuntimeError};
+
+pub const PLUGIN_PING_EVENT: &str = "plugin.ping";
+
+/// Where a health check stopped.
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthCheckStage {
+    /// The manifest is missing, unreadable or declares an unsupported protocol.
+    Manifest,
+    /// The entrypoint command was denied or could not be resolved.
+    Entrypoint,
+    /// The process failed to start, exited non-zero or printed an invalid directive.
+    Dispatch,
+    /// The process exited cleanly without printing a directive.
+    Response,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct PluginHealth {
+    pub plugin: String,
+    pub healthy: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failed_stage: Option<HealthCheckStage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<PluginVersionInfo>,
+    pub directives: usize,
+    pub duration_ms: u64,
+}
+
+/// Pings `plugin` through `runner`. Used by `odin-cli doctor` and to verify a fresh install.
+pub fn check_plugin<R: PluginEventRunner + ?Sized>(runner: &R, plugin: &str) -> PluginHealth {
+    let started = Instant::now();
+    let mut health = PluginHealth {
+        plugin: plugin.to_string(),
+        healthy: false,
+        failed_stage: None,
+        detail: None,
+        version: None,
+        directives: 0,
+        duration_ms: 0,
+    };
+    if let Err((stage, detail)) = ping(runner, plugin, &mut health) {
+        health.failed_stage = Some(stage);
+        health.detail = Some(detail);
+    } else {
+        health.healthy = true;
+    }
+    health.duration_ms = started.elapsed().as_millis() as u64;
+    health
+}
+
+fn ping<R: PluginEventRunner + ?Sized>(
+    runner: &R,
+    plugin: &str,
+    health: &mut PluginHealth,
+) -> Result<(), (HealthCheckStage, String)> {
+    health.version = runner
+        .version_info(plugin)
+        .map_err(|e| (HealthCheckStage::Manifest, e.to_string()))?;
+
+    let nonce = new_trace_id();
+    let event = EventEnvelope {
+        event_id: format!("ping-{nonce}"),
+        event_type: PLUGIN_PING_EVENT.to_string(),
+        task_id: None,
+        request_id: None,
+        project: None,
+        trace_id: Some(nonce.clone()),
+        payload: serde_json::json!({ "nonce": nonce }),
+    };
+    let (directives, diagnostics) =
+        runner
+            .dispatch_with_diagnostics(plugin, &event)
+            .map_err(|e| match e {
+                RuntimeError::EntrypointDenied { .. } => {
+                    (HealthCheckStage::Entrypoint, e.to_string())
+                }
+                _ => (HealthCheckStage::Dispatch, e.to_string()),
+            })?;
+    health.directives = directives.len();
+    // Process runners substitute a `noop` for empty output; a ping must print one itself.
+    let printed = diagnostics.map_or(!directives.is_empty(), |d| d.stdout_bytes > 0);
+    if !printed {
+        return Err((
+            HealthCheckStage::Response,
+            "plugin printed no directive".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PluginDirective, RuntimeResult};
+
+    struct PingRunner(fn() -> RuntimeResult<Vec<PluginDirective>>);
+
+    impl PluginEventRunner for PingRunner {
+        fn dispatch_event(
+            &self,
+            _plugin: &str,
+            event: &EventEnvelope,
+        ) -> RuntimeResult<Vec<PluginDirective>> {
+            assert_eq!(event.event_type, PLUGIN_PING_EVENT);
+            (self.0)()
+        }
+    }
+
+    #[test]
+    fn ping_reports_the_failing_stage() {
+        let healthy = check_plugin(&PingRunner(|| Ok(vec![PluginDirective::Noop])), "demo");
+        assert!(healthy.healthy);
+        assert_eq!(healthy.directives, 1);
+
+        let silent = check_plugin(&PingRunner(|| Ok(Vec::new())), "demo");
+        assert_eq!(silent.failed_stage, Some(HealthCheckStage::Response));
+
+        let denied = check_plugin(
+            &PingRunner(|| {
+                Err(RuntimeError::EntrypointDenied {
+                    plugin: "demo".to_string(),
+                    command: "/bin/sh".to_string(),
+                    reason: "path_lookup_not_allowed".to_string(),
+                })
+            }),
+            "demo",
+        );
+        assert!(!denied.healthy);
+        assert_eq!(denied.failed_stage, Some(HealthCheckStage::Entrypoint));
+    }
+}
@@ -0,0 +1,306 @@
+//! Native HTTP executor for capabilities such as `http.get` and `webhook.post`. Each
+//! capability carries its own egress allowlist, timeout and response cap, and every
+//! attempted request is audited as `http.egress` with its URL and status. An optional
+//! `EgressPolicy` additionally limits each plugin's hosts, ports and methods.
+
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::sync::Arc;
+use std::time::Duration;
+
+use odin_audit::{AuditRecord, AuditSink, Severity};
+use odin_governance::egress::EgressPolicy;
+use odin_plugin_protocol::ActionRequest;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{now_unix, ActionExecutor, RuntimeError, RuntimeResult};
+
+pub const DEFAULT_HTTP_TIMEOUT: Duration = Duration::from_secs(10);
+pub const DEFAULT_MAX_RESPONSE_BYTES: usize = 1024 * 1024;
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct HttpCapability {
+    pub capability: String,
+    #[serde(default = "default_method")]
+    pub method: String,
+    /// Hosts (`api.github.com`, `*.sentry.io`, `127.0.0.1:8080`) or URL prefixes
+    /// (`https://api.github.com/repos/`). Anything else is denied.
+    #[serde(default)]
+    pub allow: Vec<String>,
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+    #[serde(default)]
+    pub max_response_bytes: Option<usize>,
+}
+
+impl HttpCapability {
+    pub fn new(capability: impl Into<String>, method: impl Into<String>) -> Self {
+        Self {
+            capability: capability.into(),
+            method: method.into(),
+            allow: Vec::new(),
+            timeout_ms: None,
+            max_response_bytes: None,
+        }
+    }
+
+    pub fn allow(mut self, entry: impl Into<String>) -> Self {
+        self.allow.push(entry.into());
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout_ms = Some(timeout.as_millis() as u64);
+        self
+    }
+
+    pub fn with_max_response_bytes(mut self, max_response_bytes: usize) -> Self {
+        self.max_response_bytes = Some(max_response_bytes);
+        self
+    }
+
+    /// Whether `url` falls inside this capability's egress allowlist.
+    pub fn permits(&self, url: &str) -> bool {
+        let Some((scheme, rest)) = url.split_once("://") else {
+            return false;
+        };
+        let authority = rest
+            .split(['/', '?', '#'])
+            .next()
+            .unwrap_or_default()
+            .rsplit('@')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        let host = authority
+            .rsplit_once(':')
+            .filter(|(_, port)| port.chars().all(|c| c.is_ascii_digit()))
+            .map_or(authority.as_str(), |(host, _)| host);
+        if !matches!(scheme, "http" | "https") || host.is_empty() {
+            return false;
+        }
+
+        self.allow.iter().any(|entry| {
+            let entry = entry.trim();
+            if entry.contains("://") {
+                url.strip_prefix(entry).is_some_and(|tail| {
+                    entry.ends_with('/') || tail.is_empty() || tail.starts_with(['/', '?', '#'])
+                })
+            } else if let Some(domain) = entry.strip_prefix("*.") {
+                host.ends_with(&format!(".{}", domain.to_ascii_lowercase()))
+            } else if entry.contains(':') {
+                authority.eq_ignore_ascii_case(entry)
+            } else {
+                host.eq_ignore_ascii_case(entry)
+            }
+        })
+    }
+}
+
+fn default_method() -> String {
+    "GET".to_string()
+}
+
+/// Executes configured HTTP capabilities. Request input is
+/// `{"url": ..., "headers": {..}?, "body": ..?}`; redirects are not followed so a
+/// response cannot steer the request outside the allowlist.
+pub struct HttpActionExecutor {
+    capabilities: BTreeMap<String, HttpCapability>,
+    audit: Option<Arc<dyn AuditSink>>,
+    egress: Option<Arc<EgressPolicy>>,
+}
+
+impl std::fmt::Debug for HttpActionExecutor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HttpActionExecutor")
+            .field("capabilities", &self.capabilities)
+            .field("egress", &self.egress)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for HttpActionExecutor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HttpActionExecutor {
+    pub fn new() -> Self {
+        Self {
+            capabilities: BTreeMap::new(),
+            audit: None,
+            egress: None,
+        }
+    }
+
+    pub fn with_capability(mut self, capability: HttpCapability) -> Self {
+        self.capabilities
+            .insert(capability.capability.clone(), capability);
+        self
+    }
+
+    pub fn with_audit_sink(mut self, audit: Arc<dyn AuditSink>) -> Self {
+        self.audit = Some(audit);
+        self
+    }
+
+    /// Whether `capability` is configured here; any other fails to execute.
+    pub fn handles(&self, capability: &str) -> bool {
+        self.capabilities.contains_key(capability)
+    }
+
+    /// Requests must also pass `egress` for the requesting plugin, on top of the capability's
+    /// own allowlist.
+    pub fn with_egress_policy(mut self, egress: Arc<EgressPolicy>) -> Self {
+        self.egress = Some(egress);
+        self
+    }
+
+    fn record(&self, request: &ActionRequest, url: &str, status: Option<u16>, error: Option<&str>) {
+        self.record_with_reason(request, url, status, error, None);
+    }
+
+    fn record_with_reason(
+        &self,
+        request: &ActionRequest,
+        url: &str,
+        status: Option<u16>,
+        error: Option<&str>,
+        reason: Option<&str>,
+    ) {
+        let Some(audit) = &self.audit else {
+            return;
+        };
+        let mut record = AuditRecord {
+            ts_unix: now_unix(),
+            event_type: "http.egress".to_string(),
+            severity: Severity::Info,
+            request_id: Some(request.request_id.clone()),
+            task_id: None,
+            project: Some(request.capability.project.clone()),
+            trace_id: request.trace_id.clone(),
+            metadata: serde_json::json!({
+                "plugin": request.capability.plugin,
+                "capability": request.capability.capability,
+                "url": url,
+                "status": status,
+                "error": error
+            }),
+        };
+        if let Some(reason) = reason {
+            record.metadata["reason"] = Value::String(reason.to_string());
+        }
+        if let Err(err) = audit.record(record) {
+            tracing::warn!(error = %err, url, "http egress audit failed");
+        }
+    }
+
+    /// Sends the request; errors carry the HTTP status when one was received.
+    fn send(
+        &self,
+        spec: &HttpCapability,
+        input: &Value,
+        url: &str,
+    ) -> Result<(u16, Value), (Option<u16>, RuntimeError)> {
+        let timeout = spec
+            .timeout_ms
+            .map_or(DEFAULT_HTTP_TIMEOUT, Duration::from_millis);
+        let limit = spec
+            .max_response_bytes
+            .unwrap_or(DEFAULT_MAX_RESPONSE_BYTES);
+        let agent = ureq::AgentBuilder::new()
+            .timeout(timeout)
+            .redirects(0)
+            .build();
+        let mut request = agent.request(&spec.method.to_ascii_uppercase(), url);
+        if let Some(headers) = input.get("headers").and_then(Value::as_object) {
+            for (name, value) in headers {
+                if let Some(value) = value.as_str() {
+                    request = request.set(name, value);
+                }
+            }
+        }
+        let sent = match input.get("body") {
+            None | Some(Value::Null) => request.call(),
+            Some(Value::String(body)) => request.send_string(body),
+            Some(body) => request.send_json(body),
+        };
+        let response = match sent {
+            Ok(response) => response,
+            Err(ureq::Error::Status(status, _)) => {
+                return Err((
+                    Some(status),
+                    RuntimeError::Execution(format!("{url} returned HTTP {status}")),
+                ));
+            }
+            Err(err) => return Err((None, RuntimeError::Execution(format!("{url}: {err}")))),
+        };
+        let status = response.status();
+        let fail = |message: String| (Some(status), RuntimeError::Execution(message));
+        let mut body = Vec::new();
+        response
+            .into_reader()
+            .take(limit as u64 + 1)
+            .read_to_end(&mut body)
+            .map_err(|e| fail(format!("{url}: response read failed: {e}")))?;
+        if body.len() > limit {
+            return Err(fail(format!("{url}: response exceeds {limit} bytes")));
+        }
+        let body = serde_json::from_slice(&body)
+            .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(&body).into_owned()));
+        Ok((status, body))
+    }
+}
+
+impl ActionExecutor for HttpActionExecutor {
+    fn execute(&self, request: &ActionRequest) -> RuntimeResult<Value> {
+        let capability = &request.capability.capability;
+        let spec = self.capabilities.get(capability).ok_or_else(|| {
+            RuntimeError::Execution(format!("no http executor configured for {capability}"))
+        })?;
+        let url = request
+            .input
+            .get("url")
+            .and_then(Value::as_str)
+            .filter(|url| !url.trim().is_empty())
+            .ok_or_else(|| {
+                RuntimeError::InvalidInput(format!("{capability} requires input.url"))
+            })?;
+
+        let denial = if !spec.permits(url) {
+            Some("egress_not_allowlisted")
+        } else {
+            self.egress.as_ref().and_then(|egress| {
+                egress
+                    .check(&request.capability.plugin, &spec.method, url)
+                    .err()
+                    .map(|denial| denial.reason_code)
+            })
+        };
+        if let Some(reason) = denial {
+            self.record_with_reason(request, url, None, Some("egress_denied"), Some(reason));
+            return Err(RuntimeError::EgressDenied {
+                capability: capability.clone(),
+                url: url.to_string(),
+                reason: reason.to_string(),
+            });
+        }
+
+        match self.send(spec, &request.input, url) {
+            Ok((status, body)) => {
+                self.record(request, url, Some(status), None);
+                Ok(serde_json::json!({
+                    "url": url,
+                    "status": status,
+                    "body": body
+                }))
+            }
+            Err((status, err)) => {
+                self.record(request, url, status, Some(&err.to_string()));
+                Err(err)
+            }
+        }
+    }
+}
@@ -0,0 +1,134 @@
+//! Secret values delivered to one plugin process as files. They are written into a private
+//! directory (mode 0700, files 0600). That directory is on tmpfs (`/dev/shm`) when the host has
+//! one, so values never reach persistent disk. The files are overwritten and removed when
+//! `SecretFiles` is dropped, which the runner does once the process has exited.
+
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static NEXT_DIR: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug, Default)]
+pub struct SecretFiles {
+    dir: Option<PathBuf>,
+    /// Written files with their lengths, so they can be overwritten before removal.
+    files: Vec<(PathBuf, usize)>,
+}
+
+impl SecretFiles {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Writes `value` to a new file named `name` and returns its path. The directory is created
+    /// on first use.
+    pub fn write(&mut self, name: &str, value: &[u8]) -> io::Result<PathBuf> {
+        if name.is_empty() || name.contains(['/', '\\']) || name.starts_with('.') {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid secret file name {name:?}"),
+            ));
+        }
+        let dir = match &self.dir {
+            Some(dir) => dir.clone(),
+            None => {
+                let dir = private_dir()?;
+                self.dir = Some(dir.clone());
+                dir
+            }
+        };
+        let path = dir.join(name);
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = options.open(&path)?;
+        self.files.push((path.clone(), value.len()));
+        file.write_all(value)?;
+        file.sync_all()?;
+        Ok(path)
+    }
+
+    pub fn dir(&self) -> Option<&Path> {
+        self.dir.as_deref()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+}
+
+impl Drop for SecretFiles {
+    fn drop(&mut self) {
+        for (path, len) in &self.files {
+            if let Ok(mut file) = OpenOptions::new().write(true).open(path) {
+                let _ = file.write_all(&vec![0u8; *len]);
+                let _ = file.sync_all();
+            }
+        }
+        if let Some(dir) = &self.dir {
+            if let Err(e) = fs::remove_dir_all(dir) {
+                tracing::warn!(dir = %dir.display(), error = %e, "failed to remove secret files");
+            }
+        }
+    }
+}
+
+/// `/dev/shm` when present, the system temp directory otherwise.
+fn secret_files_root() -> PathBuf {
+    let shm = Path::new("/dev/shm");
+    if shm.is_dir() {
+        shm.to_path_buf()
+    } else {
+        std::env::temp_dir()
+    }
+}
+
+fn private_dir() -> io::Result<PathBuf> {
+    let dir = secret_files_root().join(format!(
+        "odin-secrets-{}-{}-{}",
+        std::process::id(),
+        NEXT_DIR.fetch_add(1, Ordering::Relaxed),
+        crate::now_unix()
+    ));
+    let mut builder = fs::DirBuilder::new();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::DirBuilderExt;
+        builder.mode(0o700);
+    }
+    builder.create(&dir)?;
+    Ok(dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn files_are_private_and_removed_on_drop() {
+        let mut files = SecretFiles::new();
+        assert!(files.dir().is_none());
+        let path = files.write("GITHUB_TOKEN", b"ghp_value").expect("write");
+        assert_eq!(fs::read(&path).expect("read"), b"ghp_value");
+        assert!(files.write("GITHUB_TOKEN", b"again").is_err());
+        assert!(files.write("../escape", b"x").is_err());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = |p: &Path| fs::metadata(p).expect("meta").permissions().mode() & 0o777;
+            assert_eq!(mode(&path), 0o600);
+            assert_eq!(mode(files.dir().expect("dir")), 0o700);
+        }
+
+        let dir = files.dir().expect("dir").to_path_buf();
+        drop(files);
+        assert!(!path.exists());
+        assert!(!dir.exists());
+    }
+}
@@ -1,25 +1,81 @@
 //! Core runtime contracts and baseline orchestration flow.
 
+pub mod approvals;
+pub mod cache;
+pub mod cancel;
+#[cfg(feature = "chaos")]
+pub mod chaos;
+pub mod checkpoint;
+pub mod circuit;
+pub mod command;
+pub mod concurrency;
+pub mod dlq;
+pub mod error;
+pub mod health;
+pub mod http;
+pub mod injection;
+pub mod metrics;
+pub mod middleware;
+pub mod ratelimit;
+pub mod reauth;
+pub mod retention;
+pub mod revocation;
+pub mod router;
+pub mod routing;
+pub mod secrets;
+pub mod selfcheck;
+pub mod snapshot;
+pub mod versioning;
+
+use std::collections::BTreeSet;
 use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
-use std::process::{Command, Stdio};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-use odin_audit::{AuditError, AuditRecord, AuditSink};
-use odin_governance::plugins::{
-    huginn_policy_from_envelope, Action as HuginnAction,
-    PermissionDecision as HuginnPermissionDecision,
-};
+use approvals::{ApprovalStore, InMemoryApprovalStore, PendingApproval};
+use cache::{ResultCache, ResultCacheConfig};
+use cancel::CancellationToken;
+use checkpoint::{CheckpointStore, ReconcilePolicy, ReconcileReport, TaskCheckpoint};
+use circuit::{CircuitBreaker, CircuitBreakerConfig, CircuitTransition};
+use concurrency::{DispatchLimiter, DispatchOverflow};
+use dlq::{dead_letter_reason, DeadLetterEntry, DeadLetterQueue};
+use injection::SecretFiles;
+use metrics::{MetricsSnapshot, RuntimeMetrics};
+use middleware::ActionMiddleware;
+use odin_audit::{taxonomy, AuditError, AuditRecord, AuditSink, Severity};
+use odin_governance::adapters::{PluginPolicyAdapter, PluginPolicyAdapterRegistry};
+use odin_governance::deprecations::CapabilityDeprecations;
+use odin_governance::duties::SeparationOfDuties;
+use odin_governance::egress::EgressPolicy;
+use odin_governance::plugins::{PermissionDecision, PluginPermissionRegistry};
+use odin_governance::scopes::TEMPLATE_REF_PREFIX;
+use odin_governance::workspace_boundary;
+use odin_plugin_manager::{FilesystemPluginManager, PluginManager};
+use odin_plugin_protocol::events::validate_event;
+use odin_plugin_protocol::is_observe_capability;
 use odin_plugin_protocol::{
     ActionOutcome, ActionRequest, ActionStatus, CapabilityManifest, CapabilityRequest,
-    DelegationCapability, EventEnvelope, PluginManifest, PluginPermissionEnvelope, PolicyDecision,
-    RiskTier, TrustLevel,
+    DelegationCapability, EventEnvelope, OutcomeWarning, PluginClass, PluginManifest,
+    PluginPermissionEnvelope, PolicyDecision, RiskTier, SecretDelivery, SecretEnvSpec, SnapshotRef,
+    TrustLevel,
 };
+use odin_policy_engine::breakglass::{BreakGlass, BreakGlassRequest, BreakGlassStore};
+use odin_policy_engine::elevation::{Elevation, ElevationGrant, ElevationOverlay};
 use odin_policy_engine::{PolicyEngine, PolicyError};
+use odin_secrets::{AccessContext, HandleOnlyStore, SecretError, SecretHandle, SecretStore};
+use ratelimit::{RateLimitConfig, RateLimiter};
+use revocation::{CapabilityRevocation, RevocationList};
+use router::TaskRouter;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use snapshot::Snapshotter;
 use thiserror::Error;
+use versioning::PluginVersionInfo;
 
 #[derive(Debug, Error)]
 pub enum RuntimeError {
@@ -33,6 +89,29 @@ pub enum RuntimeError {
     Plugin(String),
     #[error("invalid input: {0}")]
     InvalidInput(String),
+    #[error("plugin {plugin} exceeded the {limit_bytes}-byte output limit")]
+    PluginOutputLimitExceeded {
+        plugin: String,
+        limit_bytes: usize,
+        /// Leading bytes of the discarded output, capped for audit.
+        prefix: String,
+    },
+    #[error("command denied: {reason} ({command})")]
+    CommandDenied { command: String, reason: String },
+    #[error("egress denied for {capability}: {reason} ({url})")]
+    EgressDenied {
+        capability: String,
+        url: String,
+        reason: String,
+    },
+    #[error("entrypoint denied for {plugin}: {reason} ({command})")]
+    EntrypointDenied {
+        plugin: String,
+        command: String,
+        reason: String,
+    },
+    #[error("cancelled: {0}")]
+    Cancelled(String),
 }
 
 impl From<PolicyError> for RuntimeError {
@@ -76,6 +155,9 @@ pub struct WatchdogTaskEnvelope {
     pub source: Option<String>,
     #[serde(default)]
     pub created_at: Option<String>,
+    /// Assigned at ingestion when absent and inherited by follow-up tasks.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_id: Option<String>,
     pub payload: WatchdogTaskPayload,
 }
 
@@ -88,6 +170,11 @@ pub struct WatchdogTaskPayload {
     pub plugin: String,
     #[serde(default)]
     pub trigger: Option<String>,
+    /// Set on followup tasks enqueued by a plugin.
+    #[serde(default)]
+    pub origin_task_id: Option<String>,
+    #[serde(default)]
+    pub data: Value,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -118,33 +205,396 @@ pub enum PluginDirective {
         #[serde(default)]
         payload: Value,
     },
+    RequestSecret {
+        handle: String,
+        #[serde(default)]
+        reason: String,
+    },
+    EmitEvent {
+        event_type: String,
+        #[serde(default)]
+        payload: Value,
+        /// Restricts delivery to these subscribers; empty means every subscriber.
+        #[serde(default)]
+        targets: Vec<String>,
+    },
+    /// Asks which scopes the plugin holds for `capability`; answered in the outcome output.
+    QueryScopes {
+        capability: String,
+        #[serde(default)]
+        project: Option<String>,
+    },
     Noop,
 }
 
+/// Answer to a scope query: whether policy grants the capability and the scopes (domains,
+/// workspaces, commands, ...) the plugin's permission envelope allows for it.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ScopeGrant {
+    pub plugin: String,
+    pub project: String,
+    pub capability: String,
+    pub granted: bool,
+    pub reason_code: String,
+    pub scopes: Vec<String>,
+}
+
+/// Plugin-emitted event types must use this namespace so they cannot spoof core events.
+pub const PLUGIN_EVENT_PREFIX: &str = "plugin.";
+
+/// Maximum chain of `emit_event` deliveries triggered from a single task.
+pub const MAX_EVENT_FANOUT_DEPTH: usize = 3;
+
 pub trait PluginEventRunner: Send + Sync {
     fn dispatch_event(
         &self,
         plugin: &str,
         event: &EventEnvelope,
     ) -> RuntimeResult<Vec<PluginDirective>>;
+
+    /// Plugins whose manifest `hooks` subscribe to `event_type`.
+    fn subscribers(&self, _event_type: &str) -> RuntimeResult<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    /// Negotiated version details recorded with each dispatch, when known.
+    fn version_info(&self, _plugin: &str) -> RuntimeResult<Option<PluginVersionInfo>> {
+        Ok(None)
+    }
+
+    /// Manifest `max_concurrent_dispatches`; `None` means unlimited.
+    fn max_concurrent_dispatches(&self, _plugin: &str) -> RuntimeResult<Option<u32>> {
+        Ok(None)
+    }
+
+    /// Capabilities the installed plugin declares. When known, every capability a directive
+    /// requests is enforced against it; `None` leaves directive requests unchecked.
+    fn capability_manifest(&self, _plugin: &str) -> RuntimeResult<Option<CapabilityManifest>> {
+        Ok(None)
+    }
+
+    /// `dispatch_event` plus process diagnostics for the `plugin.dispatched` audit
+    /// record; runners without a process to inspect report `None`.
+    fn dispatch_with_diagnostics(
+        &self,
+        plugin: &str,
+        event: &EventEnvelope,
+    ) -> RuntimeResult<(Vec<PluginDirective>, Option<DispatchDiagnostics>)> {
+        Ok((self.dispatch_event(plugin, event)?, None))
+    }
+}
+
+/// What a successful plugin process left behind besides its directives.
+#[derive(Clone, Debug, Default, Serialize, PartialEq, Eq)]
+pub struct DispatchDiagnostics {
+    pub exit_code: Option<i32>,
+    pub stdout_bytes: usize,
+    pub stderr_bytes: usize,
+    /// The first 4 KiB of stderr, lossily decoded.
+    pub stderr: String,
+    pub stderr_truncated: bool,
+}
+
+/// Controls which executables a plugin manifest may name as its entrypoint. By default
+/// only executables inside the plugin directory are allowed.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EntrypointPolicy {
+    allow_path_lookup: bool,
+    allowed_bins: Vec<String>,
+}
+
+impl EntrypointPolicy {
+    pub fn plugin_dir_only() -> Self {
+        Self::default()
+    }
+
+    /// Restores PATH lookup for bare command names and absolute paths.
+    pub fn unrestricted() -> Self {
+        Self {
+            allow_path_lookup: true,
+            allowed_bins: Vec::new(),
+        }
+    }
+
+    /// Allows a bare command name (resolved via PATH) or an absolute path outside the
+    /// plugin directory, e.g. an interpreter.
+    pub fn allow_bin(mut self, bin: impl Into<String>) -> Self {
+        let bin = bin.into();
+        if !self.allowed_bins.contains(&bin) {
+            self.allowed_bins.push(bin);
+        }
+        self
+    }
+
+    fn permits_external(&self, command: &str) -> bool {
+        self.allow_path_lookup || self.allowed_bins.iter().any(|bin| bin == command)
+    }
 }
 
-#[derive(Clone, Debug)]
+/// Default cap on buffered plugin stdout (and stderr) per dispatch.
+pub const DEFAULT_MAX_PLUGIN_OUTPUT_BYTES: usize = 1024 * 1024;
+
+/// How much of an oversized plugin output is kept for the audit trail.
+const TRUNCATED_OUTPUT_PREFIX_BYTES: usize = 4096;
+
+/// Host variables every plugin process receives so interpreters and shebangs resolve.
+const BASE_ENV_PASSTHROUGH: &[&str] = &["PATH"];
+
+#[derive(Clone)]
 pub struct ExternalProcessPluginRunner {
     plugins_root: PathBuf,
+    secrets: Arc<dyn SecretStore>,
+    audit: Option<Arc<dyn AuditSink>>,
+    entrypoints: EntrypointPolicy,
+    max_output_bytes: usize,
+    cancellation: Option<CancellationToken>,
+    egress: Option<Arc<EgressPolicy>>,
+}
+
+impl std::fmt::Debug for ExternalProcessPluginRunner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExternalProcessPluginRunner")
+            .field("plugins_root", &self.plugins_root)
+            .field("entrypoints", &self.entrypoints)
+            .field("max_output_bytes", &self.max_output_bytes)
+            .finish_non_exhaustive()
+    }
 }
 
 impl ExternalProcessPluginRunner {
     pub fn new(plugins_root: impl Into<PathBuf>) -> Self {
         Self {
             plugins_root: plugins_root.into(),
+            secrets: Arc::new(HandleOnlyStore),
+            audit: None,
+            entrypoints: EntrypointPolicy::default(),
+            max_output_bytes: DEFAULT_MAX_PLUGIN_OUTPUT_BYTES,
+            cancellation: None,
+            egress: None,
         }
     }
 
+    pub fn with_max_output_bytes(mut self, max_output_bytes: usize) -> Self {
+        self.max_output_bytes = max_output_bytes;
+        self
+    }
+
+    pub fn with_entrypoint_policy(mut self, entrypoints: EntrypointPolicy) -> Self {
+        self.entrypoints = entrypoints;
+        self
+    }
+
+    pub fn with_secret_store(mut self, secrets: impl SecretStore + 'static) -> Self {
+        self.secrets = Arc::new(secrets);
+        self
+    }
+
+    /// Sink for `secret.injected` events, one per secret value delivered to a plugin process.
+    pub fn with_audit_sink(mut self, audit: Arc<dyn AuditSink>) -> Self {
+        self.audit = Some(audit);
+        self
+    }
+
+    /// Kills a running plugin process once `token` is cancelled; the dispatch then fails
+    /// with `RuntimeError::Cancelled`.
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Exports each plugin's egress rules as `ODIN_EGRESS_POLICY` (a JSON array, empty when the
+    /// plugin has no egress). This is advisory: the runner does not confine the process's network,
+    /// so the rules only hold if a sandbox wrapping the plugin enforces them.
+    pub fn with_egress_policy(mut self, egress: Arc<EgressPolicy>) -> Self {
+        self.egress = Some(egress);
+        self
+    }
+
     pub fn plugins_root(&self) -> &Path {
         &self.plugins_root
     }
 
+    /// Builds the scrubbed child environment: base passthrough, manifest-declared
+    /// host variables, runtime context, and secret handle references. Secrets delivered as
+    /// files are written into the returned `SecretFiles`, which must outlive the process.
+    fn plugin_environment(
+        &self,
+        manifest: &PluginManifest,
+        event: &EventEnvelope,
+    ) -> RuntimeResult<(Vec<(String, String)>, SecretFiles)> {
+        let entrypoint = &manifest.plugin.entrypoint;
+        let mut env = Vec::new();
+        let mut files = SecretFiles::new();
+
+        for name in BASE_ENV_PASSTHROUGH
+            .iter()
+            .copied()
+            .chain(entrypoint.env.iter().map(String::as_str))
+        {
+            if name.starts_with("ODIN_") {
+                return Err(RuntimeError::Plugin(format!(
+                    "manifest env {name} uses the reserved ODIN_ prefix"
+                )));
+            }
+            if let Ok(value) = std::env::var(name) {
+                env.push((name.to_string(), value));
+            }
+        }
+
+        for spec in &entrypoint.secrets {
+            if spec.env.trim().is_empty() || spec.env.starts_with("ODIN_") {
+                return Err(RuntimeError::Plugin(format!(
+                    "invalid secret env name for handle {}",
+                    spec.handle
+                )));
+            }
+            let handle = SecretHandle(spec.handle.clone());
+            let ctx = AccessContext {
+                plugin: manifest.plugin.name.clone(),
+                project: event.project.clone().unwrap_or_default(),
+                capability: "plugin.dispatch".to_string(),
+                reason: format!("inject {} for {}", spec.env, event.event_type),
+            };
+            let resolution_failed = |e: SecretError| {
+                RuntimeError::Plugin(format!(
+                    "secret handle resolution failed for {}: {e}",
+                    spec.env
+                ))
+            };
+            match spec.delivery {
+                SecretDelivery::Handle => {
+                    let secret = self
+                        .secrets
+                        .resolve_secret_handle(&handle, &ctx)
+                        .map_err(resolution_failed)?;
+                    env.push((spec.env.clone(), secret.handle.0));
+                }
+                SecretDelivery::File => {
+                    let value = self
+                        .secrets
+                        .reveal_secret(&handle, &ctx)
+                        .map_err(resolution_failed)?;
+                    let path = files.write(&spec.env, &value).map_err(|e| {
+                        RuntimeError::Plugin(format!(
+                            "failed to write secret file for {}: {e}",
+                            spec.env
+                        ))
+                    })?;
+                    self.audit_injection(manifest, event, spec)?;
+                    env.push((spec.env.clone(), path.display().to_string()));
+                }
+            }
+        }
+
+        env.push(("ODIN_PLUGIN".to_string(), manifest.plugin.name.clone()));
+        env.push((
+            "ODIN_PROTOCOL_VERSION".to_string(),
+            versioning::negotiate(manifest)?
+                .protocol_version
+                .to_string(),
+        ));
+        env.push(("ODIN_EVENT_ID".to_string(), event.event_id.clone()));
+        env.push(("ODIN_EVENT_TYPE".to_string(), event.event_type.clone()));
+        if let Some(project) = &event.project {
+            env.push(("ODIN_PROJECT".to_string(), project.clone()));
+        }
+        if let Some(task_id) = &event.task_id {
+            env.push(("ODIN_TASK_ID".to_string(), task_id.clone()));
+        }
+        if let Some(trace_id) = &event.trace_id {
+            env.push(("ODIN_TRACE_ID".to_string(), trace_id.clone()));
+        }
+        if let Some(egress) = &self.egress {
+            let rules = serde_json::to_string(egress.rules_for(&manifest.plugin.name))
+                .map_err(|e| RuntimeError::Plugin(format!("egress policy encode failed: {e}")))?;
+            env.push(("ODIN_EGRESS_POLICY".to_string(), rules));
+        }
+
+        Ok((env, files))
+    }
+
+    fn audit_injection(
+        &self,
+        manifest: &PluginManifest,
+        event: &EventEnvelope,
+        spec: &SecretEnvSpec,
+    ) -> RuntimeResult<()> {
+        tracing::info!(
+            plugin = %manifest.plugin.name,
+            handle = %spec.handle,
+            env = %spec.env,
+            "secret injected"
+        );
+        let Some(audit) = &self.audit else {
+            return Ok(());
+        };
+        audit.record(AuditRecord {
+            ts_unix: now_unix(),
+            event_type: taxonomy::SECRET_INJECTED.to_string(),
+            severity: taxonomy::severity(taxonomy::SECRET_INJECTED),
+            request_id: event.request_id.clone(),
+            task_id: event.task_id.clone(),
+            project: event.project.clone(),
+            trace_id: event.trace_id.clone(),
+            metadata: serde_json::json!({
+                "plugin": manifest.plugin.name,
+                "handle": spec.handle,
+                "env": spec.env,
+                "delivery": "file",
+                "event_id": event.event_id,
+            }),
+        })?;
+        Ok(())
+    }
+
+    /// Collects stdout while polling `child`, killing it when `token` is cancelled or the
+    /// output limit is exceeded.
+    fn wait_cancellable(
+        &self,
+        plugin: &str,
+        child: &mut Child,
+        token: &CancellationToken,
+    ) -> RuntimeResult<(Vec<u8>, bool, ExitStatus)> {
+        let limit = self.max_output_bytes;
+        let mut stdout_reader = child
+            .stdout
+            .take()
+            .map(|stdout| thread::spawn(move || read_capped(stdout, limit)));
+        let mut stdout = (Vec::new(), false);
+        loop {
+            if stdout_reader.as_ref().is_some_and(|r| r.is_finished()) {
+                if let Some(reader) = stdout_reader.take() {
+                    stdout = reader
+                        .join()
+                        .map_err(|_| RuntimeError::Plugin("plugin stdout reader panicked".into()))?
+                        .map_err(|e| {
+                            RuntimeError::Plugin(format!("plugin stdout read failed: {e}"))
+                        })?;
+                }
+                if stdout.1 {
+                    let _ = child.kill();
+                }
+            }
+            match child.try_wait() {
+                Ok(Some(status)) if stdout_reader.is_none() => {
+                    return Ok((stdout.0, stdout.1, status))
+                }
+                Ok(_) if token.is_cancelled() => {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Err(RuntimeError::Cancelled(format!(
+                        "dispatch to {plugin} interrupted; plugin process killed"
+                    )));
+                }
+                Ok(_) => {
+                    token.wait_timeout(Duration::from_millis(10));
+                }
+                Err(e) => return Err(RuntimeError::Plugin(format!("plugin wait failed: {e}"))),
+            }
+        }
+    }
+
     fn resolve_plugin_dir(&self, plugin_name: &str) -> RuntimeResult<PathBuf> {
         let normalized = plugin_name.replace('.', "-");
         let leaf = plugin_name.rsplit('.').next().unwrap_or(plugin_name);
@@ -166,27 +616,94 @@ impl ExternalProcessPluginRunner {
         )))
     }
 
-    fn resolve_command(plugin_dir: &Path, command: &str) -> PathBuf {
+    /// Resolves the manifest entrypoint to an executable file, enforcing the entrypoint
+    /// policy: relative commands must stay inside the plugin directory (symlinks
+    /// included) while bare names and absolute paths need an explicit allowance.
+    fn resolve_command(
+        &self,
+        plugin: &str,
+        plugin_dir: &Path,
+        command: &str,
+    ) -> RuntimeResult<PathBuf> {
+        let denied = |reason: &str| RuntimeError::EntrypointDenied {
+            plugin: plugin.to_string(),
+            command: command.to_string(),
+            reason: reason.to_string(),
+        };
+
         let cmd_path = Path::new(command);
-        if cmd_path.is_absolute() {
-            return cmd_path.to_path_buf();
+        let resolved = if cmd_path.is_absolute() {
+            let canonical_dir = plugin_dir
+                .canonicalize()
+                .map_err(|_| denied("plugin_dir_unresolvable"))?;
+            let canonical = cmd_path.canonicalize().map_err(|_| denied("not_found"))?;
+            if !canonical.starts_with(&canonical_dir) && !self.entrypoints.permits_external(command)
+            {
+                return Err(denied("outside_plugin_dir"));
+            }
+            canonical
+        } else if command.starts_with("./") || command.contains('/') {
+            let canonical_dir = plugin_dir
+                .canonicalize()
+                .map_err(|_| denied("plugin_dir_unresolvable"))?;
+            let canonical = plugin_dir
+                .join(cmd_path)
+                .canonicalize()
+                .map_err(|_| denied("not_found"))?;
+            if !canonical.starts_with(&canonical_dir) {
+                return Err(denied("outside_plugin_dir"));
+            }
+            canonical
+        } else {
+            if !self.entrypoints.permits_external(command) {
+                return Err(denied("path_lookup_not_allowed"));
+            }
+            find_on_path(command).ok_or_else(|| denied("not_found"))?
+        };
+
+        if !is_executable_file(&resolved) {
+            return Err(denied("not_executable"));
         }
-        if command.starts_with("./") || command.contains('/') {
-            return plugin_dir.join(cmd_path);
+        Ok(resolved)
+    }
+
+    /// Installed plugins whose manifest declares `class: observe_only`, for
+    /// `OrchestratorRuntime::with_observe_only_plugins`.
+    pub fn observe_only_plugins(&self) -> RuntimeResult<Vec<String>> {
+        Ok(self
+            .installed_manifests()?
+            .into_iter()
+            .filter(|manifest| manifest.plugin.class == PluginClass::ObserveOnly)
+            .map(|manifest| manifest.plugin.name)
+            .collect())
+    }
+
+    pub fn installed_manifests(&self) -> RuntimeResult<Vec<PluginManifest>> {
+        let entries = match fs::read_dir(&self.plugins_root) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => {
+                return Err(RuntimeError::Plugin(format!(
+                    "failed reading plugins root {}: {e}",
+                    self.plugins_root.display()
+                )))
+            }
+        };
+
+        let mut manifests = Vec::new();
+        for entry in entries.flatten() {
+            let plugin_dir = entry.path();
+            if plugin_dir.join("odin.plugin.yaml").is_file() {
+                manifests.push(Self::load_manifest(&plugin_dir)?);
+            }
         }
-        cmd_path.to_path_buf()
+        Ok(manifests)
     }
 
     fn load_manifest(plugin_dir: &Path) -> RuntimeResult<PluginManifest> {
-        let manifest_path = plugin_dir.join("odin.plugin.yaml");
-        let raw = fs::read_to_string(&manifest_path).map_err(|e| {
-            RuntimeError::Plugin(format!(
-                "failed reading manifest {}: {e}",
-                manifest_path.display()
-            ))
-        })?;
-        serde_yml::from_str::<PluginManifest>(&raw)
-            .map_err(|e| RuntimeError::Plugin(format!("manifest parse failed: {e}")))
+        FilesystemPluginManager::default()
+            .load_manifest(plugin_dir)
+            .map_err(|e| RuntimeError::Plugin(format!("{}: {e}", plugin_dir.display())))
     }
 }
 
@@ -196,6 +713,24 @@ impl PluginEventRunner for ExternalProcessPluginRunner {
         plugin: &str,
         event: &EventEnvelope,
     ) -> RuntimeResult<Vec<PluginDirective>> {
+        self.dispatch_with_diagnostics(plugin, event)
+            .map(|(directives, _)| directives)
+    }
+
+    fn dispatch_with_diagnostics(
+        &self,
+        plugin: &str,
+        event: &EventEnvelope,
+    ) -> RuntimeResult<(Vec<PluginDirective>, Option<DispatchDiagnostics>)> {
+        let violations = validate_event(event);
+        if !violations.is_empty() {
+            let violations: Vec<String> = violations.iter().map(ToString::to_string).collect();
+            return Err(RuntimeError::InvalidInput(format!(
+                "{} event failed schema validation: {}",
+                event.event_type,
+                violations.join("; ")
+            )));
+        }
         let plugin_dir = self.resolve_plugin_dir(plugin)?;
         let manifest = Self::load_manifest(&plugin_dir)?;
         if manifest.plugin.name != plugin {
@@ -205,9 +740,14 @@ impl PluginEventRunner for ExternalProcessPluginRunner {
             )));
         }
 
-        let command = Self::resolve_command(&plugin_dir, &manifest.plugin.entrypoint.command);
+        let command =
+            self.resolve_command(plugin, &plugin_dir, &manifest.plugin.entrypoint.command)?;
+        // Dropped after the process has exited (or been killed), removing its secret files.
+        let (env, _secret_files) = self.plugin_environment(&manifest, event)?;
         let mut child = Command::new(command)
             .args(&manifest.plugin.entrypoint.args)
+            .env_clear()
+            .envs(env)
             .current_dir(&plugin_dir)
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
@@ -215,7 +755,7 @@ impl PluginEventRunner for ExternalProcessPluginRunner {
             .spawn()
             .map_err(|e| RuntimeError::Plugin(format!("failed to start plugin process: {e}")))?;
 
-        if let Some(stdin) = child.stdin.as_mut() {
+        if let Some(mut stdin) = child.stdin.take() {
             let event_json = serde_json::to_string(event)
                 .map_err(|e| RuntimeError::Plugin(format!("event serialization failed: {e}")))?;
             stdin.write_all(event_json.as_bytes()).map_err(|e| {
@@ -226,18 +766,50 @@ impl PluginEventRunner for ExternalProcessPluginRunner {
                 .map_err(|e| RuntimeError::Plugin(format!("failed to flush plugin event: {e}")))?;
         }
 
-        let output = child
-            .wait_with_output()
-            .map_err(|e| RuntimeError::Plugin(format!("plugin wait failed: {e}")))?;
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr).replace('\n', " ");
+        let stderr_reader = child.stderr.take().map(|stderr| {
+            let limit = self.max_output_bytes;
+            thread::spawn(move || read_capped_draining(stderr, limit))
+        });
+        let (stdout_bytes, exceeded, status) = match &self.cancellation {
+            Some(token) => self.wait_cancellable(plugin, &mut child, token)?,
+            None => {
+                let (stdout_bytes, exceeded) = match child.stdout.take() {
+                    Some(stdout) => read_capped(stdout, self.max_output_bytes).map_err(|e| {
+                        RuntimeError::Plugin(format!("plugin stdout read failed: {e}"))
+                    })?,
+                    None => (Vec::new(), false),
+                };
+                if exceeded {
+                    let _ = child.kill();
+                }
+                let status = child
+                    .wait()
+                    .map_err(|e| RuntimeError::Plugin(format!("plugin wait failed: {e}")))?;
+                (stdout_bytes, exceeded, status)
+            }
+        };
+        let (stderr_bytes, stderr_exceeded) = stderr_reader
+            .and_then(|reader| reader.join().ok())
+            .and_then(Result::ok)
+            .unwrap_or_default();
+
+        if exceeded {
+            let prefix_len = stdout_bytes.len().min(TRUNCATED_OUTPUT_PREFIX_BYTES);
+            return Err(RuntimeError::PluginOutputLimitExceeded {
+                plugin: plugin.to_string(),
+                limit_bytes: self.max_output_bytes,
+                prefix: String::from_utf8_lossy(&stdout_bytes[..prefix_len]).into_owned(),
+            });
+        }
+        if !status.success() {
+            let stderr = String::from_utf8_lossy(&stderr_bytes).replace('\n', " ");
             return Err(RuntimeError::Plugin(format!(
                 "plugin process failed (exit={}): {}",
-                output.status, stderr
+                status, stderr
             )));
         }
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stdout = String::from_utf8_lossy(&stdout_bytes);
         let mut directives = Vec::new();
         for line in stdout.lines() {
             let line = line.trim();
@@ -253,8 +825,113 @@ impl PluginEventRunner for ExternalProcessPluginRunner {
         if directives.is_empty() {
             directives.push(PluginDirective::Noop);
         }
-        Ok(directives)
+        let stderr_prefix = stderr_bytes.len().min(TRUNCATED_OUTPUT_PREFIX_BYTES);
+        let diagnostics = DispatchDiagnostics {
+            exit_code: status.code(),
+            stdout_bytes: stdout_bytes.len(),
+            stderr_bytes: stderr_bytes.len(),
+            stderr: String::from_utf8_lossy(&stderr_bytes[..stderr_prefix]).into_owned(),
+            stderr_truncated: stderr_exceeded || stderr_prefix < stderr_bytes.len(),
+        };
+        Ok((directives, Some(diagnostics)))
+    }
+
+    fn subscribers(&self, event_type: &str) -> RuntimeResult<Vec<String>> {
+        let mut subscribers: Vec<String> = self
+            .installed_manifests()?
+            .into_iter()
+            .filter(|manifest| {
+                manifest
+                    .plugin
+                    .hooks
+                    .iter()
+                    .any(|hook| hook.event == event_type)
+            })
+            .map(|manifest| manifest.plugin.name)
+            .collect();
+        subscribers.sort();
+        subscribers.dedup();
+        Ok(subscribers)
+    }
+
+    fn version_info(&self, plugin: &str) -> RuntimeResult<Option<PluginVersionInfo>> {
+        let manifest = Self::load_manifest(&self.resolve_plugin_dir(plugin)?)?;
+        versioning::negotiate(&manifest).map(Some)
+    }
+
+    fn max_concurrent_dispatches(&self, plugin: &str) -> RuntimeResult<Option<u32>> {
+        let manifest = Self::load_manifest(&self.resolve_plugin_dir(plugin)?)?;
+        Ok(manifest.plugin.max_concurrent_dispatches)
+    }
+
+    fn capability_manifest(&self, plugin: &str) -> RuntimeResult<Option<CapabilityManifest>> {
+        let manifest = Self::load_manifest(&self.resolve_plugin_dir(plugin)?)?;
+        Ok(Some(CapabilityManifest {
+            schema_version: 1,
+            plugin: manifest.plugin.name,
+            capabilities: manifest
+                .plugin
+                .capabilities
+                .into_iter()
+                .map(|capability| DelegationCapability {
+                    id: capability.id,
+                    scope: capability.scope,
+                })
+                .collect(),
+        }))
+    }
+}
+
+/// Reads at most `limit` bytes, reporting whether the stream had more to give.
+fn read_capped(mut reader: impl std::io::Read, limit: usize) -> std::io::Result<(Vec<u8>, bool)> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 8192];
+    loop {
+        let read = reader.read(&mut chunk)?;
+        if read == 0 {
+            return Ok((buffer, false));
+        }
+        let room = limit.saturating_sub(buffer.len());
+        if read > room {
+            buffer.extend_from_slice(&chunk[..room]);
+            return Ok((buffer, true));
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    }
+}
+
+/// Like `read_capped`, but keeps reading to EOF and discards what is past `limit`, so a process
+/// that keeps writing never blocks on a full pipe.
+pub(crate) fn read_capped_draining(
+    mut reader: impl std::io::Read,
+    limit: usize,
+) -> std::io::Result<(Vec<u8>, bool)> {
+    let (buffer, exceeded) = read_capped(&mut reader, limit)?;
+    if exceeded {
+        std::io::copy(&mut reader, &mut std::io::sink())?;
     }
+    Ok((buffer, exceeded))
+}
+
+fn find_on_path(command: &str) -> Option<PathBuf> {
+    let path = std::env::var_os("PATH")?;
+    std::env::split_paths(&path)
+        .map(|dir| dir.join(command))
+        .find(|candidate| is_executable_file(candidate))
+}
+
+#[cfg(unix)]
+fn is_executable_file(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    fs::metadata(path)
+        .map(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable_file(path: &Path) -> bool {
+    path.is_file()
 }
 
 #[derive(Clone, Debug, Default)]
@@ -271,6 +948,125 @@ impl ActionExecutor for DryRunExecutor {
     }
 }
 
+/// Executor retry behaviour; backoff doubles per attempt up to `max_backoff`. Only actions in
+/// `retried_tiers` (by default just `Safe`) are retried: a failed destructive action may have
+/// partly applied, so running it again is opt-in.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    pub retried_tiers: Vec<RiskTier>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 1,
+            initial_backoff: Duration::ZERO,
+            max_backoff: Duration::ZERO,
+            retried_tiers: vec![RiskTier::Safe],
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn new(max_attempts: u32, initial_backoff: Duration) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            initial_backoff,
+            max_backoff: initial_backoff.saturating_mul(8),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Replaces the risk tiers whose failed actions are retried.
+    pub fn with_retried_tiers(mut self, tiers: impl IntoIterator<Item = RiskTier>) -> Self {
+        self.retried_tiers = tiers.into_iter().collect();
+        self
+    }
+
+    /// Attempts allowed for an action of `tier`: one unless the tier is retried.
+    pub fn attempts_for(&self, tier: &RiskTier) -> u32 {
+        if self.retried_tiers.contains(tier) {
+            self.max_attempts.max(1)
+        } else {
+            1
+        }
+    }
+
+    /// Delay before retry number `attempt` (1-based count of failures so far).
+    pub fn backoff_for(&self, attempt: u32) -> Duration {
+        let factor = 1u32
+            .checked_shl(attempt.saturating_sub(1))
+            .unwrap_or(u32::MAX);
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+/// What `handle_task` does with an ingress event that fails its bundled JSON Schema.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EventValidation {
+    Off,
+    /// Audit `event.schema.invalid` and dispatch anyway.
+    Flag,
+    /// Audit and return a `Failed` outcome without dispatching.
+    #[default]
+    Reject,
+}
+
+/// How `request_capability` directives returned by a single dispatch are executed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DirectiveExecution {
+    #[default]
+    Sequential,
+    /// Consecutive `request_capability` directives run on up to `max_concurrency` threads;
+    /// any other directive waits for the batch before it. Outcomes keep directive order.
+    Parallel { max_concurrency: usize },
+}
+
+/// How long a `RequireApproval` request stays resumable.
+pub const DEFAULT_APPROVAL_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Deny reason codes a break-glass override may lift: a grant that is missing, denied, lapsed
+/// or too narrow. Deny-list hits, observe-only constraints and open circuits always hold.
+pub const OVERRIDABLE_DENY_REASONS: &[&str] = &[
+    "capability_not_granted",
+    "capability_denied",
+    "capability_grant_expired",
+    "scope_not_granted",
+];
+
+/// Tasks `handle_task_batch` processes at once unless told otherwise.
+pub const DEFAULT_TASK_BATCH_CONCURRENCY: usize = 4;
+
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct TaskBatchResult {
+    /// Position of the payload in the batch.
+    pub index: usize,
+    pub task_id: Option<String>,
+    pub outcomes: Vec<ActionOutcome>,
+    /// Set when the task failed as a whole; its outcomes are then empty.
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, PartialEq)]
+pub struct TaskBatchSummary {
+    pub total: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub duration_ms: u64,
+    /// One entry per payload, in batch order.
+    pub tasks: Vec<TaskBatchResult>,
+}
+
 pub struct OrchestratorRuntime<P, A, E>
 where
     P: PolicyEngine,
@@ -280,6 +1076,31 @@ where
     policy: P,
     audit: A,
     executor: E,
+    permissions: PluginPermissionRegistry,
+    policy_adapters: PluginPolicyAdapterRegistry,
+    secrets: Arc<dyn SecretStore>,
+    deprecations: CapabilityDeprecations,
+    retry: RetryPolicy,
+    approvals: Arc<dyn ApprovalStore>,
+    approval_ttl: Duration,
+    revocations: RevocationList,
+    duties: SeparationOfDuties,
+    router: TaskRouter,
+    directive_execution: DirectiveExecution,
+    middleware: Vec<Arc<dyn ActionMiddleware>>,
+    elevations: Option<Arc<ElevationOverlay>>,
+    break_glass: Option<Arc<BreakGlassStore>>,
+    snapshotter: Option<Arc<dyn Snapshotter>>,
+    snapshot_workspaces: Vec<PathBuf>,
+    circuit: Option<CircuitBreaker>,
+    cache: Option<ResultCache>,
+    dispatch_limiter: DispatchLimiter,
+    event_validation: EventValidation,
+    observe_only: BTreeSet<String>,
+    cancellation: Option<CancellationToken>,
+    checkpoints: Option<Arc<dyn CheckpointStore>>,
+    dead_letters: Option<Arc<dyn DeadLetterQueue>>,
+    metrics: Option<Arc<dyn RuntimeMetrics>>,
 }
 
 impl<P, A, E> OrchestratorRuntime<P, A, E>
@@ -293,707 +1114,4339 @@ where
             policy,
             audit,
             executor,
+            permissions: PluginPermissionRegistry::default(),
+            policy_adapters: PluginPolicyAdapterRegistry::builtin(),
+            secrets: Arc::new(HandleOnlyStore),
+            deprecations: CapabilityDeprecations::default(),
+            retry: RetryPolicy::default(),
+            approvals: Arc::new(InMemoryApprovalStore::default()),
+            approval_ttl: DEFAULT_APPROVAL_TTL,
+            revocations: RevocationList::default(),
+            duties: SeparationOfDuties::default(),
+            router: TaskRouter::default(),
+            directive_execution: DirectiveExecution::default(),
+            middleware: Vec::new(),
+            elevations: None,
+            break_glass: None,
+            snapshotter: None,
+            snapshot_workspaces: Vec::new(),
+            circuit: None,
+            cache: None,
+            dispatch_limiter: DispatchLimiter::default(),
+            event_validation: EventValidation::default(),
+            observe_only: BTreeSet::new(),
+            cancellation: None,
+            checkpoints: None,
+            dead_letters: None,
+            metrics: None,
         }
     }
 
-    pub fn handle_action(&self, request: ActionRequest) -> RuntimeResult<ActionOutcome> {
-        let decision = self.evaluate_policy(&request)?;
-        match decision {
-            PolicyDecision::Deny { reason_code } => Ok(ActionOutcome {
-                request_id: request.request_id,
-                status: ActionStatus::Blocked,
-                detail: reason_code,
-                output: Value::Null,
-            }),
-            PolicyDecision::RequireApproval { reason_code, .. } => Ok(ActionOutcome {
-                request_id: request.request_id,
-                status: ActionStatus::ApprovalPending,
-                detail: reason_code,
-                output: Value::Null,
-            }),
-            PolicyDecision::Allow { .. } => {
-                let output = self.executor.execute(&request)?;
-                self.audit.record(AuditRecord {
-                    ts_unix: now_unix(),
-                    event_type: "action.executed".to_string(),
-                    request_id: Some(request.request_id.clone()),
-                    task_id: None,
-                    project: Some(request.capability.project.clone()),
-                    metadata: serde_json::json!({
-                        "plugin": request.capability.plugin,
-                        "capability": request.capability.capability
-                    }),
-                })?;
+    /// Task kinds accepted by `handle_task`; defaults to `watchdog_poll` only.
+    pub fn with_task_router(mut self, router: TaskRouter) -> Self {
+        self.router = router;
+        self
+    }
 
-                Ok(ActionOutcome {
-                    request_id: request.request_id,
-                    status: ActionStatus::Executed,
-                    detail: "executed".to_string(),
-                    output,
-                })
-            }
+    /// Overlay of temporary elevations consulted when the base policy reports
+    /// `capability_not_granted`.
+    pub fn with_elevation_overlay(mut self, overlay: Arc<ElevationOverlay>) -> Self {
+        self.elevations = Some(overlay);
+        self
+    }
+
+    /// Grants a temporary elevation that reverts on its own once `grant.ttl` elapses.
+    pub fn grant_elevation(&self, grant: ElevationGrant) -> RuntimeResult<Elevation> {
+        let overlay = self.elevations.as_ref().ok_or_else(|| {
+            RuntimeError::InvalidInput("no elevation overlay configured".to_string())
+        })?;
+        let elevation = overlay.grant(grant, now_unix())?;
+        self.record_elevation_event("policy.elevation.granted", &elevation)?;
+        Ok(elevation)
+    }
+
+    /// Store of break-glass overrides redeemed through `handle_action_with_override`.
+    pub fn with_break_glass_store(mut self, store: Arc<BreakGlassStore>) -> Self {
+        self.break_glass = Some(store);
+        self
+    }
+
+    /// Issues a break-glass override and returns it with its token. Issuance is audited
+    /// (`policy.override.issued`) and fails if the audit cannot be written.
+    pub fn issue_break_glass(
+        &self,
+        request: BreakGlassRequest,
+    ) -> RuntimeResult<(BreakGlass, String)> {
+        let store = self.break_glass.as_ref().ok_or_else(|| {
+            RuntimeError::InvalidInput("no break-glass store configured".to_string())
+        })?;
+        let (entry, token) = store.issue(request, now_unix())?;
+        if let Err(err) = self.record_break_glass_event("policy.override.issued", &entry, None) {
+            // An override nobody can see must not stay usable.
+            let _ = store.revoke(&entry.id);
+            return Err(err);
         }
+        Ok((entry, token))
     }
 
-    pub fn handle_action_with_manifest(
+    fn record_break_glass_event(
         &self,
-        request: ActionRequest,
-        manifest: &CapabilityManifest,
-    ) -> RuntimeResult<ActionOutcome> {
-        validate_capability(&request.capability)?;
-        let manifest_denial = if manifest.schema_version != 1 {
-            Some("manifest_schema_version_unsupported".to_string())
-        } else {
-            manifest_denial_reason(&request, manifest)
-        };
-        if let Some(reason_code) = manifest_denial {
-            self.audit.record(AuditRecord {
-                ts_unix: now_unix(),
-                event_type: "governance.manifest.denied".to_string(),
-                request_id: Some(request.request_id.clone()),
-                task_id: None,
-                project: Some(request.capability.project.clone()),
-                metadata: serde_json::json!({
-                    "plugin": request.capability.plugin,
-                    "manifest_plugin": manifest.plugin,
-                    "capability": request.capability.capability,
-                    "reason_code": reason_code
-                }),
-            })?;
-            return Ok(ActionOutcome {
-                request_id: request.request_id,
-                status: ActionStatus::Blocked,
-                detail: reason_code,
-                output: Value::Null,
-            });
+        event_type: &str,
+        entry: &BreakGlass,
+        request: Option<(&ActionRequest, &str)>,
+    ) -> RuntimeResult<()> {
+        let mut metadata = serde_json::json!({
+            "override_id": entry.id,
+            "operator": entry.operator,
+            "reason": entry.reason,
+            "plugin": entry.plugin,
+            "capability": entry.capability,
+            "expires_at_unix": entry.expires_at_unix
+        });
+        if let Some((_, denied_reason)) = request {
+            metadata["overridden_reason_code"] = Value::String(denied_reason.to_string());
         }
+        self.audit.record(AuditRecord {
+            ts_unix: now_unix(),
+            event_type: event_type.to_string(),
+            severity: taxonomy::severity(event_type),
+            request_id: request.map(|(request, _)| request.request_id.clone()),
+            task_id: None,
+            project: Some(entry.project.clone()),
+            trace_id: request.and_then(|(request, _)| request.trace_id.clone()),
+            metadata,
+        })?;
+        self.audit.flush()?;
+        Ok(())
+    }
 
+    fn record_elevation_event(&self, event_type: &str, elevation: &Elevation) -> RuntimeResult<()> {
         self.audit.record(AuditRecord {
             ts_unix: now_unix(),
-            event_type: "governance.manifest.validated".to_string(),
-            request_id: Some(request.request_id.clone()),
+            event_type: event_type.to_string(),
+            severity: taxonomy::severity(event_type),
+            request_id: None,
             task_id: None,
-            project: Some(request.capability.project.clone()),
+            project: Some(elevation.project.clone()),
+            trace_id: None,
             metadata: serde_json::json!({
-                "plugin": request.capability.plugin,
-                "manifest_plugin": manifest.plugin,
-                "capability": request.capability.capability
+                "elevation_id": elevation.id,
+                "plugin": elevation.plugin,
+                "capability": elevation.capability,
+                "scope": elevation.scope,
+                "reason": elevation.reason,
+                "expires_at_unix": elevation.expires_at_unix
             }),
         })?;
+        Ok(())
+    }
 
-        let request_id = request.request_id.clone();
-        let project = request.capability.project.clone();
-        let plugin = request.capability.plugin.clone();
-        let capability = request.capability.capability.clone();
-        let outcome = self.handle_action(request)?;
-        if outcome.status == ActionStatus::Executed {
-            self.audit.record(AuditRecord {
-                ts_unix: now_unix(),
-                event_type: "governance.capability.used".to_string(),
-                request_id: Some(request_id),
-                task_id: None,
-                project: Some(project),
-                metadata: serde_json::json!({
-                    "plugin": plugin,
-                    "capability": capability
-                }),
-            })?;
-        }
+    /// Blocks requests over their (plugin, capability, project) token bucket with
+    /// `rate_limited`; installed as middleware ahead of later `with_middleware` calls.
+    pub fn with_rate_limits(self, config: RateLimitConfig) -> Self {
+        self.with_middleware(RateLimiter::new(config))
+    }
 
-        Ok(outcome)
+    /// Snapshots `input.workspace` before destructive actions execute. The workspace comes from
+    /// the plugin, so it must lie inside one of `workspaces`; any other fails the action.
+    pub fn with_snapshotter<W, T>(
+        mut self,
+        snapshotter: impl Snapshotter + 'static,
+        workspaces: W,
+    ) -> Self
+    where
+        W: IntoIterator<Item = T>,
+        T: Into<PathBuf>,
+    {
+        self.snapshotter = Some(Arc::new(snapshotter));
+        self.snapshot_workspaces = workspaces.into_iter().map(Into::into).collect();
+        self
     }
 
-    pub fn handle_watchdog_task<R, T>(
-        &self,
-        raw_task: &str,
-        runner: &R,
-        ingress: &T,
-    ) -> RuntimeResult<Vec<ActionOutcome>>
+    /// Plugins classed `observe_only`: any non-read capability they request is denied with
+    /// `observe_only_plugin_mutation`, whatever the policy grants.
+    pub fn with_observe_only_plugins<I, S>(mut self, plugins: I) -> Self
     where
-        R: PluginEventRunner,
-        T: TaskIngress,
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
     {
-        let task = parse_watchdog_task(raw_task)?;
-        let event = EventEnvelope {
-            event_id: format!("evt-{}-{}", task.task_id, now_unix()),
-            event_type: "task.received".to_string(),
-            task_id: Some(task.task_id.clone()),
-            request_id: None,
-            project: Some(task.payload.project.clone()),
-            payload: serde_json::json!({
-                "task_type": task.payload.task_type,
-                "source_key": task.payload.source_key,
-                "trigger": task.payload.trigger
-            }),
-        };
+        self.observe_only
+            .extend(plugins.into_iter().map(Into::into));
+        self
+    }
 
-        let directives = runner.dispatch_event(&task.payload.plugin, &event)?;
-        let mut outcomes = Vec::new();
+    /// Denies a plugin with `plugin_circuit_open` after repeated dispatch or execution
+    /// failures, until its cooldown elapses.
+    pub fn with_circuit_breaker(mut self, config: CircuitBreakerConfig) -> Self {
+        self.circuit = Some(CircuitBreaker::new(config));
+        self
+    }
 
-        for (idx, directive) in directives.into_iter().enumerate() {
-            match directive {
-                PluginDirective::RequestCapability {
-                    capability,
-                    reason,
-                    input,
-                    risk_tier,
-                } => {
-                    let project = capability
-                        .project
-                        .unwrap_or_else(|| task.payload.project.clone());
-                    let request = ActionRequest {
-                        request_id: format!("{}-{}-cap", task.task_id, idx),
-                        risk_tier: risk_tier.unwrap_or(RiskTier::Safe),
-                        capability: CapabilityRequest {
-                            plugin: task.payload.plugin.clone(),
-                            project,
-                            capability: capability.id,
-                            scope: vec!["project".to_string()],
-                            reason: if reason.trim().is_empty() {
-                                "plugin requested capability".to_string()
-                            } else {
-                                reason
-                            },
-                        },
-                        input,
-                    };
-                    let manifest = CapabilityManifest {
-                        schema_version: 1,
-                        plugin: request.capability.plugin.clone(),
-                        capabilities: vec![DelegationCapability {
-                            id: request.capability.capability.clone(),
-                            scope: request.capability.scope.clone(),
-                        }],
-                    };
-                    outcomes.push(self.handle_action_with_manifest(request, &manifest)?);
-                }
-                PluginDirective::EnqueueTask {
-                    task_type,
-                    project,
-                    reason,
-                    payload,
-                } => {
-                    if task_type.trim().is_empty() {
-                        return Err(RuntimeError::InvalidInput(
-                            "enqueue_task requires non-empty task_type".to_string(),
-                        ));
-                    }
-                    let project = project.unwrap_or_else(|| task.payload.project.clone());
-                    let request = ActionRequest {
-                        request_id: format!("{}-{}-enqueue", task.task_id, idx),
-                        risk_tier: RiskTier::Sensitive,
-                        capability: CapabilityRequest {
-                            plugin: task.payload.plugin.clone(),
-                            project: project.clone(),
-                            capability: "task.enqueue".to_string(),
-                            scope: vec!["project".to_string()],
-                            reason: reason.unwrap_or_else(|| {
-                                format!("plugin enqueue request for {}", task_type)
-                            }),
-                        },
-                        input: serde_json::json!({
-                            "task_type": task_type,
-                            "origin_task_id": task.task_id
-                        }),
-                    };
+    /// What happens to dispatches beyond a plugin's manifest `max_concurrent_dispatches`.
+    pub fn with_dispatch_overflow(mut self, overflow: DispatchOverflow) -> Self {
+        self.dispatch_limiter = DispatchLimiter::new(overflow);
+        self
+    }
 
-                    match self.evaluate_policy(&request)? {
-                        PolicyDecision::Deny { reason_code } => outcomes.push(ActionOutcome {
-                            request_id: request.request_id,
-                            status: ActionStatus::Blocked,
-                            detail: reason_code,
-                            output: Value::Null,
-                        }),
-                        PolicyDecision::RequireApproval { reason_code, .. } => {
-                            outcomes.push(ActionOutcome {
-                                request_id: request.request_id,
-                                status: ActionStatus::ApprovalPending,
-                                detail: reason_code,
-                                output: Value::Null,
-                            })
-                        }
-                        PolicyDecision::Allow { .. } => {
-                            let queued = build_enqueued_task(
-                                &task,
-                                idx,
-                                &task_type,
-                                &project,
-                                payload.clone(),
-                            );
-                            let queued_json = serde_json::to_string(&queued).map_err(|e| {
-                                RuntimeError::InvalidInput(format!(
-                                    "failed serializing enqueued task: {e}"
-                                ))
-                            })?;
-                            ingress.write_task_payload(&queued_json)?;
+    /// Serves repeated executions of the configured read capabilities from a TTL cache.
+    /// Destructive requests always run.
+    pub fn with_result_cache(mut self, config: ResultCacheConfig) -> Self {
+        self.cache = Some(ResultCache::new(config));
+        self
+    }
 
-                            self.audit.record(AuditRecord {
-                                ts_unix: now_unix(),
-                                event_type: "task.enqueued".to_string(),
-                                request_id: Some(request.request_id.clone()),
-                                task_id: Some(task.task_id.clone()),
-                                project: Some(project.clone()),
-                                metadata: serde_json::json!({
-                                    "plugin": task.payload.plugin,
-                                    "task_type": task_type,
-                                    "origin_task_id": task.task_id
-                                }),
-                            })?;
+    /// Appends `middleware` to the `handle_action` hook pipeline.
+    pub fn with_middleware(mut self, middleware: impl ActionMiddleware + 'static) -> Self {
+        self.middleware.push(Arc::new(middleware));
+        self
+    }
 
-                            outcomes.push(ActionOutcome {
-                                request_id: request.request_id,
-                                status: ActionStatus::Executed,
-                                detail: "task_enqueued".to_string(),
-                                output: serde_json::json!({
-                                    "task_type": task_type,
-                                    "project": project
-                                }),
-                            });
-                        }
-                    }
-                }
-                PluginDirective::Noop => {
-                    self.audit.record(AuditRecord {
-                        ts_unix: now_unix(),
-                        event_type: "plugin.noop".to_string(),
-                        request_id: None,
-                        task_id: Some(task.task_id.clone()),
-                        project: Some(task.payload.project.clone()),
-                        metadata: serde_json::json!({
-                            "plugin": task.payload.plugin
-                        }),
-                    })?;
-                }
-            }
-        }
+    /// Once `token` is cancelled, tasks stop before their next directive and return the
+    /// outcomes gathered so far; new tasks are not dispatched.
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
 
-        Ok(outcomes)
+    fn is_cancelled(&self) -> bool {
+        self.cancellation
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
     }
 
-    fn evaluate_policy(&self, request: &ActionRequest) -> RuntimeResult<PolicyDecision> {
-        validate_capability(&request.capability)?;
-        let decision = self.policy.decide(request)?;
+    /// Records `runtime.shutdown` and flushes the audit sink. Call after in-flight tasks
+    /// have returned.
+    pub fn shutdown(&self, reason: &str) -> RuntimeResult<()> {
         self.audit.record(AuditRecord {
             ts_unix: now_unix(),
-            event_type: "policy.decision".to_string(),
-            request_id: Some(request.request_id.clone()),
+            event_type: "runtime.shutdown".to_string(),
+            severity: Severity::Notice,
+            request_id: None,
             task_id: None,
-            project: Some(request.capability.project.clone()),
-            metadata: serde_json::json!({
-                "plugin": request.capability.plugin,
-                "capability": request.capability.capability,
-                "decision": decision_tag(&decision)
-            }),
+            project: None,
+            trace_id: None,
+            metadata: serde_json::json!({ "reason": reason }),
         })?;
-        Ok(decision)
+        self.audit.flush()?;
+        Ok(())
     }
-}
 
-fn parse_watchdog_task(raw_task: &str) -> RuntimeResult<WatchdogTaskEnvelope> {
-    let task: WatchdogTaskEnvelope = serde_json::from_str(raw_task)
-        .map_err(|e| RuntimeError::InvalidInput(format!("invalid watchdog task JSON: {e}")))?;
+    pub fn with_event_validation(mut self, event_validation: EventValidation) -> Self {
+        self.event_validation = event_validation;
+        self
+    }
 
-    if task.schema_version != 1 {
-        return Err(RuntimeError::InvalidInput(format!(
-            "unsupported schema_version: {}",
-            task.schema_version
-        )));
+    pub fn with_directive_execution(mut self, directive_execution: DirectiveExecution) -> Self {
+        self.directive_execution = directive_execution;
+        self
     }
-    if task.task_kind != "watchdog_poll" {
-        return Err(RuntimeError::InvalidInput(format!(
-            "unsupported task type: {}",
-            task.task_kind
-        )));
+
+    /// Permission envelopes consulted when plugins query their granted scopes, and handed to
+    /// policy adapters in place of the manifest-derived envelope.
+    pub fn with_permission_registry(mut self, permissions: PluginPermissionRegistry) -> Self {
+        self.permissions = permissions;
+        self
     }
-    if task.payload.plugin.trim().is_empty() {
-        return Err(RuntimeError::InvalidInput(
-            "watchdog task payload.plugin is required".to_string(),
-        ));
+
+    /// Replaces the per-plugin policy adapters; the default holds the built-in Huginn adapter.
+    pub fn with_policy_adapters(mut self, policy_adapters: PluginPolicyAdapterRegistry) -> Self {
+        self.policy_adapters = policy_adapters;
+        self
     }
-    if task.payload.project.trim().is_empty() {
-        return Err(RuntimeError::InvalidInput(
-            "watchdog task payload.project is required".to_string(),
-        ));
+
+    /// Adds `adapter` to the registry, replacing any adapter for the same plugin.
+    pub fn with_policy_adapter(mut self, adapter: Arc<dyn PluginPolicyAdapter>) -> Self {
+        self.policy_adapters = self.policy_adapters.with_adapter(adapter);
+        self
     }
-    if task.payload.task_type.trim().is_empty() {
-        return Err(RuntimeError::InvalidInput(
-            "watchdog task payload.task_type is required".to_string(),
-        ));
+
+    /// Scopes `plugin` holds for `capability` in `project`. The policy decision is evaluated
+    /// (and audited) as for a safe request; scopes come from the plugin's permission
+    /// envelope, with templates already expanded at load, and are empty when the capability is
+    /// not granted.
+    pub fn granted_scopes(
+        &self,
+        plugin: &str,
+        project: &str,
+        capability: &str,
+    ) -> RuntimeResult<ScopeGrant> {
+        let request = ActionRequest {
+            request_id: format!("scopes-{plugin}-{capability}"),
+            risk_tier: RiskTier::Safe,
+            capability: CapabilityRequest {
+                plugin: plugin.to_string(),
+                project: project.to_string(),
+                capability: capability.to_string(),
+                scope: vec!["project".to_string()],
+                reason: format!("scope introspection for {capability}"),
+            },
+            trace_id: None,
+            input: Value::Null,
+            requested_by: None,
+        };
+        let (granted, reason_code) = match self.evaluate_policy(&request)? {
+            PolicyDecision::Allow { reason_code } => (true, reason_code),
+            PolicyDecision::RequireApproval { reason_code, .. }
+            | PolicyDecision::Deny { reason_code } => (false, reason_code),
+        };
+        let mut scopes = Vec::new();
+        if granted {
+            let requested: Vec<DelegationCapability> = self
+                .permissions
+                .effective(plugin, project)
+                .map(|envelope| {
+                    envelope
+                        .permissions
+                        .iter()
+                        .filter(|permission| permission.id == capability)
+                        .cloned()
+                        .collect()
+                })
+                .unwrap_or_default();
+            for scope in requested
+                .into_iter()
+                .flat_map(|permission| permission.scope)
+            {
+                if !scopes.contains(&scope) {
+                    scopes.push(scope);
+                }
+            }
+        }
+        Ok(ScopeGrant {
+            plugin: plugin.to_string(),
+            project: project.to_string(),
+            capability: capability.to_string(),
+            granted,
+            reason_code,
+            scopes,
+        })
     }
 
-    Ok(task)
-}
+    /// Store used to resolve `request_secret` directives into opaque leases.
+    pub fn with_secret_store(mut self, secrets: impl SecretStore + 'static) -> Self {
+        self.secrets = Arc::new(secrets);
+        self
+    }
 
-fn build_enqueued_task(
-    origin: &WatchdogTaskEnvelope,
-    sequence: usize,
-    task_type: &str,
-    project: &str,
-    payload: Value,
-) -> Value {
-    let followup_task_id = format!("{}-followup-{}-{}", origin.task_id, sequence, now_unix());
-    serde_json::json!({
-        "schema_version": 1,
-        "task_id": followup_task_id,
-        "type": task_type,
-        "source": "plugin",
-        "created_at_unix": now_unix(),
-        "payload": {
-            "project": project,
-            "plugin": origin.payload.plugin,
-            "task_type": task_type,
-            "origin_task_id": origin.task_id,
-            "data": payload
-        }
-    })
-}
+    pub fn with_capability_deprecations(mut self, deprecations: CapabilityDeprecations) -> Self {
+        self.deprecations = deprecations;
+        self
+    }
 
-fn validate_capability(capability: &CapabilityRequest) -> RuntimeResult<()> {
-    if capability.plugin.trim().is_empty() {
-        return Err(RuntimeError::InvalidInput("plugin is required".to_string()));
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
     }
-    if capability.capability.trim().is_empty() {
-        return Err(RuntimeError::InvalidInput(
-            "capability is required".to_string(),
-        ));
+
+    pub fn with_approval_store(mut self, approvals: impl ApprovalStore + 'static) -> Self {
+        self.approvals = Arc::new(approvals);
+        self
     }
-    Ok(())
-}
 
-fn manifest_denial_reason(
-    request: &ActionRequest,
-    manifest: &CapabilityManifest,
-) -> Option<String> {
-    if manifest.plugin != request.capability.plugin {
-        return Some("manifest_plugin_mismatch".to_string());
+    /// Use `RevocationList::file` so revocations made by `governance revoke` apply to a
+    /// running daemon.
+    pub fn with_revocation_list(mut self, revocations: RevocationList) -> Self {
+        self.revocations = revocations;
+        self
     }
 
-    let matching_capabilities = manifest
-        .capabilities
-        .iter()
-        .filter(|capability| capability.id == request.capability.capability)
-        .collect::<Vec<_>>();
-    if matching_capabilities.is_empty() {
-        return Some("manifest_capability_not_granted".to_string());
+    /// Journals every task while it is in flight so `reconcile` can find interrupted work.
+    pub fn with_checkpoint_store(mut self, checkpoints: impl CheckpointStore + 'static) -> Self {
+        self.checkpoints = Some(Arc::new(checkpoints));
+        self
     }
-    if !matching_capabilities
-        .iter()
-        .any(|granted| manifest_scope_permits(&request.capability.scope, &granted.scope))
-    {
-        return Some("manifest_scope_not_granted".to_string());
+
+    /// Keeps payloads `handle_task` rejects or fails on, with the reason, for
+    /// `dead_letters` and `retry_dead_letter`.
+    pub fn with_dead_letter_queue(mut self, queue: impl DeadLetterQueue + 'static) -> Self {
+        self.dead_letters = Some(Arc::new(queue));
+        self
     }
 
-    let capability = request.capability.capability.as_str();
-    if is_huginn_capability(capability) && request.capability.plugin != "huginn" {
-        return Some("plugin_permission_denied".to_string());
+    /// Counts action outcomes and plugin dispatch failures and times both; shared so the
+    /// caller can keep reading `snapshot()` while the runtime runs.
+    pub fn with_metrics(mut self, metrics: Arc<dyn RuntimeMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
     }
 
-    huginn_permission_denial(capability, &request.input, manifest)
-}
+    pub fn metrics_snapshot(&self) -> Option<MetricsSnapshot> {
+        self.metrics.as_ref().map(|metrics| metrics.snapshot())
+    }
 
-fn huginn_permission_denial(
-    capability: &str,
-    input: &Value,
-    manifest: &CapabilityManifest,
-) -> Option<String> {
-    if manifest.plugin != "huginn" {
-        return None;
+    fn record_action_metrics(&self, capability: &str, outcome: &ActionOutcome, elapsed: Duration) {
+        let Some(metrics) = &self.metrics else {
+            return;
+        };
+        let counter = match outcome.status {
+            ActionStatus::Executed => metrics::ACTIONS_EXECUTED,
+            ActionStatus::Blocked => metrics::ACTIONS_BLOCKED,
+            ActionStatus::ApprovalPending => metrics::ACTIONS_APPROVAL_PENDING,
+            ActionStatus::Failed => metrics::ACTIONS_FAILED,
+        };
+        let labels = [("capability", capability)];
+        metrics.increment_counter(counter, &labels);
+        metrics.observe_duration(metrics::ACTION_DURATION, &labels, elapsed);
     }
 
-    let action = match huginn_action_from_capability(capability, input) {
-        Some(action) => action,
-        None if capability.starts_with("huginn.") => {
-            return Some("manifest_huginn_capability_unknown".to_string())
-        }
-        None => return None,
-    };
-    let policy = huginn_policy_from_envelope(&PluginPermissionEnvelope {
-        plugin: manifest.plugin.clone(),
-        trust_level: TrustLevel::Caution,
-        permissions: manifest.capabilities.clone(),
-    });
-    match policy.evaluate(action) {
-        HuginnPermissionDecision::Allow { .. } => None,
-        HuginnPermissionDecision::Deny { reason_code } => Some(reason_code),
-    }
-}
-
-fn huginn_action_from_capability(capability: &str, input: &Value) -> Option<HuginnAction> {
-    match capability {
-        "browser.observe" | "huginn.observe_url" => Some(HuginnAction::ObserveUrl(
-            input_string(input, "url").unwrap_or_default(),
-        )),
-        "huginn.observe_domain" => Some(HuginnAction::ObserveUrl(canonical_observe_domain_input(
-            input,
-        ))),
-        "workspace.read" | "huginn.workspace.read" => Some(HuginnAction::ReadWorkspace(
-            input_string(input, "workspace").unwrap_or_default(),
-        )),
-        "command.run" | "huginn.command.run" => Some(HuginnAction::RunCommand(
-            input_string(input, "command").unwrap_or_default(),
-        )),
-        "huginn.login" => Some(HuginnAction::Login),
-        "huginn.payment" => Some(HuginnAction::Payment),
-        "huginn.pii_submit" => Some(HuginnAction::PiiSubmit),
-        "huginn.file_upload" => Some(HuginnAction::FileUpload),
-        _ => None,
-    }
-}
-
-fn canonical_observe_domain_input(input: &Value) -> String {
-    let value = input_string(input, "domain")
-        .or_else(|| input_string(input, "url"))
-        .unwrap_or_default();
-    let trimmed = value.trim();
-    if trimmed.starts_with("http://") || trimmed.starts_with("https://") {
-        trimmed.to_string()
-    } else if trimmed.is_empty() {
-        String::new()
-    } else {
-        format!("https://{trimmed}")
-    }
-}
-
-fn is_huginn_capability(capability: &str) -> bool {
-    matches!(
-        capability,
-        "browser.observe" | "workspace.read" | "command.run"
-    ) || capability.starts_with("huginn.")
-}
-
-fn input_string(input: &Value, key: &str) -> Option<String> {
-    input
-        .get(key)
-        .and_then(Value::as_str)
-        .map(ToOwned::to_owned)
-}
+    pub fn with_approval_ttl(mut self, ttl: Duration) -> Self {
+        self.approval_ttl = ttl;
+        self
+    }
 
-fn manifest_scope_permits(requested_scope: &[String], granted_scope: &[String]) -> bool {
-    if requested_scope.is_empty() {
-        return granted_scope.is_empty();
+    /// Refuses `resume_approved` from the identity that requested the action in the projects
+    /// `duties` lists. Off by default.
+    pub fn with_separation_of_duties(mut self, duties: SeparationOfDuties) -> Self {
+        self.duties = duties;
+        self
     }
-    if granted_scope.is_empty() {
-        return false;
+
+    pub fn pending_approvals(&self) -> RuntimeResult<Vec<PendingApproval>> {
+        self.approvals.list()
     }
-    requested_scope
-        .iter()
-        .all(|requested| granted_scope.iter().any(|granted| granted == requested))
-}
 
-fn now_unix() -> u64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map(|d| d.as_secs())
-        .unwrap_or(0)
-}
+    /// Executes a previously parked request once `approver` signs off. Policy is
+    /// re-evaluated first so revoked grants and expired approvals still block. A self-approval
+    /// refused by separation of duties blocks but leaves the approval pending for someone else.
+    pub fn resume_approved(
+        &self,
+        request_id: &str,
+        approver: &str,
+    ) -> RuntimeResult<ActionOutcome> {
+        if approver.trim().is_empty() {
+            return Err(RuntimeError::InvalidInput(
+                "approver is required to resume an approval".to_string(),
+            ));
+        }
+        let pending = self.approvals.get(request_id)?.ok_or_else(|| {
+            RuntimeError::InvalidInput(format!("no pending approval for {request_id}"))
+        })?;
+        let expired = pending.is_expired(now_unix());
+        let request = pending.request;
 
-fn decision_tag(decision: &PolicyDecision) -> &'static str {
-    match decision {
-        PolicyDecision::Allow { .. } => "allow",
-        PolicyDecision::Deny { .. } => "deny",
-        PolicyDecision::RequireApproval { .. } => "require_approval",
+        if expired {
+            self.approvals.remove(request_id)?;
+            self.record_approval_event("approval.expired", &request, approver)?;
+            return Ok(ActionOutcome {
+                request_id: request.request_id,
+                status: ActionStatus::Blocked,
+                detail: "approval_expired".to_string(),
+                output: Value::Null,
+                warnings: Vec::new(),
+                snapshot: None,
+            });
+        }
+        if self.revocations.covers(&request.capability)? {
+            self.approvals.remove(request_id)?;
+            self.record_approval_event("approval.revoked", &request, approver)?;
+            return Ok(ActionOutcome {
+                request_id: request.request_id,
+                status: ActionStatus::Blocked,
+                detail: "capability_revoked".to_string(),
+                output: Value::Null,
+                warnings: Vec::new(),
+                snapshot: None,
+            });
+        }
+
+        if self.duties.violated(
+            &request.capability.project,
+            request.requested_by.as_deref(),
+            approver,
+        ) {
+            self.record_approval_event(
+                taxonomy::APPROVAL_SELF_APPROVAL_BLOCKED,
+                &request,
+                approver,
+            )?;
+            return Ok(ActionOutcome {
+                request_id: request.request_id,
+                status: ActionStatus::Blocked,
+                detail: "separation_of_duties_violation".to_string(),
+                output: Value::Null,
+                warnings: Vec::new(),
+                snapshot: None,
+            });
+        }
+
+        let mut decision = self.evaluate_policy(&request)?;
+        let warnings = self.deprecation_warnings(&request)?;
+        if !matches!(decision, PolicyDecision::Deny { .. }) {
+            // The approval covers a require-approval quota; only a spent deny quota blocks here.
+            if let Some(held @ PolicyDecision::Deny { .. }) = self
+                .policy
+                .reserve_execution(&request)
+                .map_err(|e| RuntimeError::Policy(e.to_string()))?
+            {
+                decision = held;
+            }
+        }
+        self.approvals.remove(request_id)?;
+        if let PolicyDecision::Deny { reason_code } = decision {
+            self.record_approval_event("approval.revoked", &request, approver)?;
+            return Ok(ActionOutcome {
+                request_id: request.request_id,
+                status: ActionStatus::Blocked,
+                detail: reason_code,
+                output: Value::Null,
+                warnings,
+                snapshot: None,
+            });
+        }
+
+        self.record_approval_event("approval.granted", &request, approver)?;
+        let started = Instant::now();
+        let capability = request.capability.capability.clone();
+        let outcome = self.execute_allowed(request, warnings)?;
+        self.record_action_metrics(&capability, &outcome, started.elapsed());
+        Ok(outcome)
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use std::sync::{Arc, Mutex};
+    /// Blocks every later request covered by `revocation` with `capability_revoked`, and
+    /// drops the pending approvals it covers. Returns the ids of the dropped approvals.
+    pub fn revoke_capability(
+        &self,
+        revocation: CapabilityRevocation,
+    ) -> RuntimeResult<Vec<String>> {
+        self.revocations.revoke(revocation.clone())?;
+        let mut dropped = Vec::new();
+        for pending in self.approvals.list()? {
+            if revocation.covers(&pending.request.capability) {
+                self.approvals.remove(pending.request_id())?;
+                self.record_approval_event("approval.revoked", &pending.request, "")?;
+                dropped.push(pending.request.request_id);
+            }
+        }
+        self.audit.record(AuditRecord {
+            ts_unix: now_unix(),
+            event_type: taxonomy::GOVERNANCE_CAPABILITY_REVOKED.to_string(),
+            severity: taxonomy::severity(taxonomy::GOVERNANCE_CAPABILITY_REVOKED),
+            request_id: None,
+            task_id: None,
+            project: None,
+            trace_id: None,
+            metadata: serde_json::json!({
+                "plugin": revocation.plugin,
+                "capability": revocation.capability,
+                "scope": revocation.scope,
+                "revoked_approvals": dropped
+            }),
+        })?;
+        Ok(dropped)
+    }
 
-    use odin_audit::{AuditRecord, AuditSink};
-    use odin_plugin_protocol::{ActionRequest, CapabilityRequest, RiskTier};
-    use odin_policy_engine::StaticPolicyEngine;
+    pub fn handle_action(&self, request: ActionRequest) -> RuntimeResult<ActionOutcome> {
+        self.handle_action_escalated(request, None)
+    }
 
-    use super::{
-        ActionExecutor, OrchestratorRuntime, PluginCapabilityRef, PluginDirective,
-        PluginEventRunner, RuntimeError, TaskIngress,
-    };
+    /// `handle_action`, except that a policy allow becomes an approval requirement with
+    /// `approval_reason` when one is given.
+    fn handle_action_escalated(
+        &self,
+        request: ActionRequest,
+        approval_reason: Option<String>,
+    ) -> RuntimeResult<ActionOutcome> {
+        let started = Instant::now();
+        let capability = request.capability.capability.clone();
+        let outcome = self.decide_action(request, None, approval_reason)?;
+        self.record_action_metrics(&capability, &outcome, started.elapsed());
+        Ok(outcome)
+    }
 
-    #[derive(Clone, Default)]
-    struct MemoryAuditSink(Arc<Mutex<Vec<AuditRecord>>>);
+    /// `handle_action`, except that a missing or denied grant is overridden by the break-glass
+    /// `token` issued for this plugin, project and capability. The override is audited as
+    /// `policy.override` and flushed before the action runs; without that record the action
+    /// does not run. Approval requirements and other denies (deny-list, observe-only, open
+    /// circuit) are not overridden.
+    pub fn handle_action_with_override(
+        &self,
+        request: ActionRequest,
+        token: &str,
+    ) -> RuntimeResult<ActionOutcome> {
+        let started = Instant::now();
+        let capability = request.capability.capability.clone();
+        let outcome = self.decide_action(request, Some(token), None)?;
+        self.record_action_metrics(&capability, &outcome, started.elapsed());
+        Ok(outcome)
+    }
 
-    impl MemoryAuditSink {
-        fn has_event(&self, event_type: &str) -> bool {
-            self.0
-                .lock()
-                .expect("lock")
-                .iter()
-                .any(|record| record.event_type == event_type)
+    fn decide_action(
+        &self,
+        mut request: ActionRequest,
+        break_glass: Option<&str>,
+        approval_reason: Option<String>,
+    ) -> RuntimeResult<ActionOutcome> {
+        for middleware in &self.middleware {
+            if let Some(outcome) = middleware.before_policy(&mut request)? {
+                return self.intercepted("before_policy", &request, outcome);
+            }
+        }
+        if self.revocations.covers(&request.capability)? {
+            return Ok(ActionOutcome {
+                request_id: request.request_id,
+                status: ActionStatus::Blocked,
+                detail: "capability_revoked".to_string(),
+                output: Value::Null,
+                warnings: Vec::new(),
+                snapshot: None,
+            });
+        }
+        let mut decision = self.evaluate_policy(&request)?;
+        if let (Some(token), PolicyDecision::Deny { reason_code }) = (break_glass, &decision) {
+            decision = self.break_glass(&request, token, reason_code)?;
+        }
+        if let (Some(reason_code), PolicyDecision::Allow { .. }) = (approval_reason, &decision) {
+            decision = PolicyDecision::RequireApproval {
+                reason_code,
+                tier: request.risk_tier.clone(),
+            };
+        }
+        for middleware in &self.middleware {
+            if let Some(outcome) = middleware.after_decision(&request, &decision)? {
+                return self.intercepted("after_decision", &request, outcome);
+            }
+        }
+        let warnings = self.deprecation_warnings(&request)?;
+        if let PolicyDecision::Allow { .. } = decision {
+            if let Some(held) = self
+                .policy
+                .reserve_execution(&request)
+                .map_err(|e| RuntimeError::Policy(e.to_string()))?
+            {
+                decision = held;
+            }
+        }
+        match decision {
+            PolicyDecision::Deny { reason_code } => Ok(ActionOutcome {
+                request_id: request.request_id,
+                status: ActionStatus::Blocked,
+                detail: reason_code,
+                output: Value::Null,
+                warnings,
+                snapshot: None,
+            }),
+            PolicyDecision::RequireApproval { reason_code, .. } => {
+                let created_at_unix = now_unix();
+                self.approvals.save(&PendingApproval {
+                    request: request.clone(),
+                    reason_code: reason_code.clone(),
+                    created_at_unix,
+                    expires_at_unix: created_at_unix.saturating_add(self.approval_ttl.as_secs()),
+                })?;
+                self.record_approval_event("approval.pending", &request, "")?;
+                Ok(ActionOutcome {
+                    request_id: request.request_id,
+                    status: ActionStatus::ApprovalPending,
+                    detail: reason_code,
+                    output: Value::Null,
+                    warnings,
+                    snapshot: None,
+                })
+            }
+            PolicyDecision::Allow { .. } => self.execute_allowed(request, warnings),
         }
     }
 
-    impl AuditSink for MemoryAuditSink {
-        fn record(&self, record: AuditRecord) -> Result<(), odin_audit::AuditError> {
-            self.0
-                .lock()
-                .map_err(|_| odin_audit::AuditError::Write("poisoned lock".to_string()))?
-                .push(record);
-            Ok(())
+    fn break_glass(
+        &self,
+        request: &ActionRequest,
+        token: &str,
+        denied_reason: &str,
+    ) -> RuntimeResult<PolicyDecision> {
+        let store = self.break_glass.as_ref().ok_or_else(|| {
+            RuntimeError::InvalidInput("no break-glass store configured".to_string())
+        })?;
+        let rejected = |reason: &str| -> RuntimeResult<PolicyDecision> {
+            self.audit.record(AuditRecord {
+                ts_unix: now_unix(),
+                event_type: "policy.override.rejected".to_string(),
+                severity: Severity::Warning,
+                request_id: Some(request.request_id.clone()),
+                task_id: None,
+                project: Some(request.capability.project.clone()),
+                trace_id: request.trace_id.clone(),
+                metadata: serde_json::json!({
+                    "plugin": request.capability.plugin,
+                    "capability": request.capability.capability,
+                    "denied_reason_code": denied_reason,
+                    "reason": reason
+                }),
+            })?;
+            self.audit.flush()?;
+            Ok(PolicyDecision::Deny {
+                reason_code: denied_reason.to_string(),
+            })
+        };
+        if !OVERRIDABLE_DENY_REASONS.contains(&denied_reason) {
+            return rejected("deny is not a missing or denied grant and cannot be overridden");
         }
+        let Some(entry) = store.find(token, request, now_unix())? else {
+            return rejected("token unknown, expired or issued for another target");
+        };
+        self.record_break_glass_event("policy.override", &entry, Some((request, denied_reason)))?;
+        tracing::warn!(
+            override_id = %entry.id,
+            operator = %entry.operator,
+            plugin = %entry.plugin,
+            capability = %entry.capability,
+            "break-glass override applied"
+        );
+        Ok(PolicyDecision::Allow {
+            reason_code: "break_glass_override".to_string(),
+        })
     }
 
-    #[derive(Default)]
-    struct MemoryIngress(Mutex<Vec<String>>);
+    fn execute_allowed(
+        &self,
+        request: ActionRequest,
+        warnings: Vec<OutcomeWarning>,
+    ) -> RuntimeResult<ActionOutcome> {
+        for middleware in &self.middleware {
+            let intercepted = middleware.before_execute(&request);
+            if !matches!(intercepted, Ok(None)) {
+                self.settle_execution(&request, false);
+            }
+            if let Some(outcome) = intercepted? {
+                return self.intercepted("before_execute", &request, outcome);
+            }
+        }
+        let executed = self.execute_unhooked(&request, warnings);
+        let mut outcome = match executed {
+            Ok(outcome) => {
+                self.settle_execution(&request, outcome.status == ActionStatus::Executed);
+                outcome
+            }
+            Err(err) => {
+                self.settle_execution(&request, false);
+                return Err(err);
+            }
+        };
+        for middleware in &self.middleware {
+            middleware.after_execute(&request, &mut outcome)?;
+        }
+        Ok(outcome)
+    }
+
+    /// Ends the use `reserve_execution` held for `request`: counts it when the action executed,
+    /// releases it otherwise. The action has already run or been skipped, so a policy failure
+    /// here is logged and audited instead of turning the outcome into an error.
+    fn settle_execution(&self, request: &ActionRequest, executed: bool) {
+        let settled = if executed {
+            self.policy.record_execution(request)
+        } else {
+            self.policy.release_execution(request)
+        };
+        let Err(err) = settled else {
+            return;
+        };
+        tracing::error!(
+            request_id = %request.request_id,
+            plugin = %request.capability.plugin,
+            capability = %request.capability.capability,
+            executed,
+            "policy failed to record an execution: {err}"
+        );
+        let audited = self.audit.record(AuditRecord {
+            ts_unix: now_unix(),
+            event_type: taxonomy::POLICY_RECORD_FAILED.to_string(),
+            severity: taxonomy::severity(taxonomy::POLICY_RECORD_FAILED),
+            request_id: Some(request.request_id.clone()),
+            task_id: None,
+            project: Some(request.capability.project.clone()),
+            trace_id: request.trace_id.clone(),
+            metadata: serde_json::json!({
+                "plugin": request.capability.plugin,
+                "capability": request.capability.capability,
+                "executed": executed,
+                "error": err.to_string()
+            }),
+        });
+        if let Err(audit_err) = audited {
+            tracing::error!("failed to audit policy record failure: {audit_err}");
+        }
+    }
+
+    /// Audits a middleware short-circuit and returns its outcome unchanged.
+    fn intercepted(
+        &self,
+        stage: &str,
+        request: &ActionRequest,
+        outcome: ActionOutcome,
+    ) -> RuntimeResult<ActionOutcome> {
+        self.audit.record(AuditRecord {
+            ts_unix: now_unix(),
+            event_type: "action.intercepted".to_string(),
+            severity: Severity::Notice,
+            request_id: Some(request.request_id.clone()),
+            task_id: None,
+            project: Some(request.capability.project.clone()),
+            trace_id: request.trace_id.clone(),
+            metadata: serde_json::json!({
+                "plugin": request.capability.plugin,
+                "capability": request.capability.capability,
+                "stage": stage,
+                "status": outcome.status,
+                "detail": outcome.detail
+            }),
+        })?;
+        Ok(outcome)
+    }
+
+    fn execute_unhooked(
+        &self,
+        request: &ActionRequest,
+        warnings: Vec<OutcomeWarning>,
+    ) -> RuntimeResult<ActionOutcome> {
+        let snapshot = match self.snapshot_workspace(request) {
+            Ok(snapshot) => snapshot,
+            Err(err) => {
+                return Ok(ActionOutcome {
+                    request_id: request.request_id.clone(),
+                    status: ActionStatus::Failed,
+                    detail: "snapshot_failed".to_string(),
+                    output: serde_json::json!({ "error": err.to_string() }),
+                    warnings,
+                    snapshot: None,
+                });
+            }
+        };
+        let cache = self
+            .cache
+            .as_ref()
+            .filter(|_| !matches!(request.risk_tier, RiskTier::Destructive));
+        if let Some(hit) = cache
+            .map(|cache| cache.get(request, Instant::now()))
+            .transpose()?
+            .flatten()
+        {
+            self.audit.record(AuditRecord {
+                ts_unix: now_unix(),
+                event_type: "action.executed".to_string(),
+                severity: Severity::Info,
+                request_id: Some(request.request_id.clone()),
+                task_id: None,
+                project: Some(request.capability.project.clone()),
+                trace_id: request.trace_id.clone(),
+                metadata: serde_json::json!({
+                    "plugin": request.capability.plugin,
+                    "capability": request.capability.capability,
+                    "cache": {
+                        "hit": true,
+                        "key": hit.key,
+                        "age_ms": hit.age.as_millis() as u64
+                    }
+                }),
+            })?;
+            return Ok(ActionOutcome {
+                request_id: request.request_id.clone(),
+                status: ActionStatus::Executed,
+                detail: "executed".to_string(),
+                output: hit.output,
+                warnings,
+                snapshot: None,
+            });
+        }
+        let result = self.execute_with_retry(request);
+        self.record_circuit_result(
+            &request.capability.plugin,
+            &request.capability.project,
+            request.trace_id.as_deref(),
+            result.is_ok(),
+        )?;
+        let output = match result {
+            Ok(output) => output,
+            Err((attempts, err)) => {
+                self.audit.record(AuditRecord {
+                    ts_unix: now_unix(),
+                    event_type: "action.failed".to_string(),
+                    severity: Severity::Warning,
+                    request_id: Some(request.request_id.clone()),
+                    task_id: None,
+                    project: Some(request.capability.project.clone()),
+                    trace_id: request.trace_id.clone(),
+                    metadata: serde_json::json!({
+                        "plugin": request.capability.plugin,
+                        "capability": request.capability.capability,
+                        "attempts": attempts,
+                        "error": err.to_string(),
+                        "error_code": err.code()
+                    }),
+                })?;
+                return Ok(ActionOutcome {
+                    request_id: request.request_id.clone(),
+                    status: ActionStatus::Failed,
+                    detail: "execution_failed".to_string(),
+                    output: serde_json::json!({
+                        "attempts": attempts,
+                        "error": err.to_string(),
+                        "error_code": err.code()
+                    }),
+                    warnings,
+                    snapshot,
+                });
+            }
+        };
+        let mut metadata = serde_json::json!({
+            "plugin": request.capability.plugin,
+            "capability": request.capability.capability
+        });
+        if let Some(cache) = cache {
+            if let Some(key) = cache.key(request) {
+                cache.insert(request, &output, Instant::now())?;
+                metadata["cache"] = serde_json::json!({ "hit": false, "key": key });
+            }
+        }
+        self.audit.record(AuditRecord {
+            ts_unix: now_unix(),
+            event_type: "action.executed".to_string(),
+            severity: Severity::Info,
+            request_id: Some(request.request_id.clone()),
+            task_id: None,
+            project: Some(request.capability.project.clone()),
+            trace_id: request.trace_id.clone(),
+            metadata,
+        })?;
+
+        Ok(ActionOutcome {
+            request_id: request.request_id.clone(),
+            status: ActionStatus::Executed,
+            detail: "executed".to_string(),
+            output,
+            warnings,
+            snapshot,
+        })
+    }
+
+    /// Snapshots the request's `input.workspace` before a destructive action runs. Errors
+    /// fail the action closed so nothing destructive executes without a recovery path.
+    fn snapshot_workspace(&self, request: &ActionRequest) -> RuntimeResult<Option<SnapshotRef>> {
+        let Some(snapshotter) = &self.snapshotter else {
+            return Ok(None);
+        };
+        if !matches!(request.risk_tier, RiskTier::Destructive) {
+            return Ok(None);
+        }
+        let Some(workspace) = request.input.get("workspace").and_then(Value::as_str) else {
+            return Ok(None);
+        };
+
+        let workspace = workspace_boundary::normalize(Path::new(workspace))
+            .filter(|path| {
+                self.snapshot_workspaces
+                    .iter()
+                    .any(|root| workspace_boundary::is_within(path, root))
+            })
+            .ok_or_else(|| {
+                RuntimeError::InvalidInput(format!(
+                    "snapshot workspace outside the allowlisted workspaces: {workspace}"
+                ))
+            })?;
+        let snapshot = snapshotter.snapshot(&workspace, &request.request_id)?;
+        self.audit.record(AuditRecord {
+            ts_unix: now_unix(),
+            event_type: "workspace.snapshot.created".to_string(),
+            severity: Severity::Info,
+            request_id: Some(request.request_id.clone()),
+            task_id: None,
+            project: Some(request.capability.project.clone()),
+            trace_id: request.trace_id.clone(),
+            metadata: serde_json::json!({
+                "plugin": request.capability.plugin,
+                "capability": request.capability.capability,
+                "snapshot": snapshot
+            }),
+        })?;
+        Ok(Some(snapshot))
+    }
+
+    fn record_approval_event(
+        &self,
+        event_type: &str,
+        request: &ActionRequest,
+        approver: &str,
+    ) -> RuntimeResult<()> {
+        let mut metadata = serde_json::json!({
+            "plugin": request.capability.plugin,
+            "capability": request.capability.capability
+        });
+        if !approver.is_empty() {
+            metadata["approver"] = Value::String(approver.to_string());
+        }
+        if let Some(requested_by) = &request.requested_by {
+            metadata["requested_by"] = Value::String(requested_by.clone());
+        }
+        self.audit.record(AuditRecord {
+            ts_unix: now_unix(),
+            event_type: event_type.to_string(),
+            severity: taxonomy::severity(event_type),
+            request_id: Some(request.request_id.clone()),
+            task_id: None,
+            project: Some(request.capability.project.clone()),
+            trace_id: request.trace_id.clone(),
+            metadata,
+        })?;
+        Ok(())
+    }
+
+    pub fn handle_action_with_manifest(
+        &self,
+        request: ActionRequest,
+        manifest: &CapabilityManifest,
+    ) -> RuntimeResult<ActionOutcome> {
+        self.handle_task_action(request, manifest, None)
+    }
+
+    /// `handle_action_with_manifest` for a request issued by task `task_id`, which policy
+    /// adapters use to key per-task state such as page budgets.
+    fn handle_task_action(
+        &self,
+        request: ActionRequest,
+        manifest: &CapabilityManifest,
+        task_id: Option<&str>,
+    ) -> RuntimeResult<ActionOutcome> {
+        validate_capability(&request.capability)?;
+        let manifest_decision = if manifest.schema_version != 1 {
+            Some(manifest_deny("manifest_schema_version_unsupported"))
+        } else if manifest
+            .capabilities
+            .iter()
+            .flat_map(|capability| &capability.scope)
+            .any(|scope| scope.trim().starts_with(TEMPLATE_REF_PREFIX))
+        {
+            // Templates are expanded in the permission envelopes when governance loads them;
+            // a manifest cannot name one.
+            Some(manifest_deny("manifest_scope_template_unknown"))
+        } else {
+            manifest_decision(
+                &request,
+                manifest,
+                &self.permissions,
+                &self.policy_adapters,
+                task_id,
+            )
+        };
+        let (manifest_denial, approval_reason) = match manifest_decision {
+            Some(PermissionDecision::Deny { reason_code }) => (Some(reason_code), None),
+            Some(PermissionDecision::RequireApproval { reason_code }) => (None, Some(reason_code)),
+            Some(PermissionDecision::Allow { .. }) | None => (None, None),
+        };
+        if let Some(reason_code) = manifest_denial {
+            self.audit.record(AuditRecord {
+                ts_unix: now_unix(),
+                event_type: "governance.manifest.denied".to_string(),
+                severity: Severity::Critical,
+                request_id: Some(request.request_id.clone()),
+                task_id: None,
+                project: Some(request.capability.project.clone()),
+                trace_id: request.trace_id.clone(),
+                metadata: serde_json::json!({
+                    "plugin": request.capability.plugin,
+                    "manifest_plugin": manifest.plugin,
+                    "capability": request.capability.capability,
+                    "reason_code": reason_code
+                }),
+            })?;
+            let outcome = ActionOutcome {
+                request_id: request.request_id,
+                status: ActionStatus::Blocked,
+                detail: reason_code,
+                output: Value::Null,
+                warnings: Vec::new(),
+                snapshot: None,
+            };
+            self.record_action_metrics(&request.capability.capability, &outcome, Duration::ZERO);
+            return Ok(outcome);
+        }
+
+        self.audit.record(AuditRecord {
+            ts_unix: now_unix(),
+            event_type: "governance.manifest.validated".to_string(),
+            severity: Severity::Info,
+            request_id: Some(request.request_id.clone()),
+            task_id: None,
+            project: Some(request.capability.project.clone()),
+            trace_id: request.trace_id.clone(),
+            metadata: serde_json::json!({
+                "plugin": request.capability.plugin,
+                "manifest_plugin": manifest.plugin,
+                "capability": request.capability.capability
+            }),
+        })?;
+
+        let request_id = request.request_id.clone();
+        let project = request.capability.project.clone();
+        let plugin = request.capability.plugin.clone();
+        let capability = request.capability.capability.clone();
+        let scope = request.capability.scope.clone();
+        let trace_id = request.trace_id.clone();
+        let outcome = self.handle_action_escalated(request, approval_reason)?;
+        if outcome.status == ActionStatus::Executed {
+            self.audit.record(AuditRecord {
+                ts_unix: now_unix(),
+                event_type: "governance.capability.used".to_string(),
+                severity: Severity::Info,
+                request_id: Some(request_id),
+                task_id: None,
+                project: Some(project),
+                trace_id,
+                metadata: serde_json::json!({
+                    "plugin": plugin,
+                    "capability": capability,
+                    "scope": scope
+                }),
+            })?;
+        }
+
+        Ok(outcome)
+    }
+
+    pub fn handle_watchdog_task<R, T>(
+        &self,
+        raw_task: &str,
+        runner: &R,
+        ingress: &T,
+    ) -> RuntimeResult<Vec<ActionOutcome>>
+    where
+        R: PluginEventRunner,
+        T: TaskIngress,
+    {
+        self.handle_task(raw_task, runner, ingress)
+    }
+
+    /// Parses a task envelope and dispatches it through the handler registered for its
+    /// `type` in the runtime's `TaskRouter`. A task that fails (other than by cancellation)
+    /// is written to the dead-letter queue, when one is configured, before the error returns.
+    pub fn handle_task<R, T>(
+        &self,
+        raw_task: &str,
+        runner: &R,
+        ingress: &T,
+    ) -> RuntimeResult<Vec<ActionOutcome>>
+    where
+        R: PluginEventRunner,
+        T: TaskIngress,
+    {
+        match self.process_task(raw_task, runner, ingress) {
+            Err(err) if !matches!(err, RuntimeError::Cancelled(_)) => {
+                self.dead_letter(raw_task, &err)?;
+                Err(err)
+            }
+            result => result,
+        }
+    }
+
+    /// Runs every payload through `handle_task` on up to `max_concurrency` threads. A failing
+    /// task is recorded in the summary (and dead-lettered) without stopping the rest; the
+    /// totals are audited as `task.batch.completed`.
+    pub fn handle_task_batch<R, T>(
+        &self,
+        raw_tasks: &[&str],
+        runner: &R,
+        ingress: &T,
+        max_concurrency: usize,
+    ) -> RuntimeResult<TaskBatchSummary>
+    where
+        R: PluginEventRunner,
+        T: TaskIngress,
+    {
+        let started = Instant::now();
+        let next = AtomicUsize::new(0);
+        let results: Vec<Mutex<Option<RuntimeResult<Vec<ActionOutcome>>>>> =
+            raw_tasks.iter().map(|_| Mutex::new(None)).collect();
+        thread::scope(|scope| {
+            for _ in 0..max_concurrency.max(1).min(raw_tasks.len()) {
+                scope.spawn(|| loop {
+                    let idx = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(raw_task) = raw_tasks.get(idx) else {
+                        break;
+                    };
+                    let result = self.handle_task(raw_task, runner, ingress);
+                    if let Ok(mut slot) = results[idx].lock() {
+                        *slot = Some(result);
+                    }
+                });
+            }
+        });
+
+        let mut summary = TaskBatchSummary {
+            total: raw_tasks.len(),
+            ..TaskBatchSummary::default()
+        };
+        for (index, (raw_task, slot)) in raw_tasks.iter().zip(results).enumerate() {
+            let task_id = raw_task_id(raw_task);
+            let result = slot.into_inner().ok().flatten().unwrap_or_else(|| {
+                Err(RuntimeError::Execution(
+                    "batch worker produced no outcome".to_string(),
+                ))
+            });
+            let (outcomes, error) = match result {
+                Ok(outcomes) => {
+                    summary.succeeded += 1;
+                    (outcomes, None)
+                }
+                Err(err) => {
+                    summary.failed += 1;
+                    (Vec::new(), Some(err.to_string()))
+                }
+            };
+            summary.tasks.push(TaskBatchResult {
+                index,
+                task_id,
+                outcomes,
+                error,
+            });
+        }
+        summary.duration_ms = started.elapsed().as_millis() as u64;
+
+        self.audit.record(AuditRecord {
+            ts_unix: now_unix(),
+            event_type: "task.batch.completed".to_string(),
+            severity: Severity::Info,
+            request_id: None,
+            task_id: None,
+            project: None,
+            trace_id: None,
+            metadata: serde_json::json!({
+                "total": summary.total,
+                "succeeded": summary.succeeded,
+                "failed": summary.failed,
+                "duration_ms": summary.duration_ms,
+                "max_concurrency": max_concurrency.max(1)
+            }),
+        })?;
+        Ok(summary)
+    }
+
+    fn process_task<R, T>(
+        &self,
+        raw_task: &str,
+        runner: &R,
+        ingress: &T,
+    ) -> RuntimeResult<Vec<ActionOutcome>>
+    where
+        R: PluginEventRunner,
+        T: TaskIngress,
+    {
+        let mut task = parse_task(raw_task)?;
+        task.trace_id.get_or_insert_with(new_trace_id);
+        if self.is_cancelled() {
+            return Ok(vec![self.cancel_task(
+                &task,
+                &format!("{}-dispatch", task.task_id),
+                1,
+            )?]);
+        }
+        let Some(checkpoints) = &self.checkpoints else {
+            let outcomes = self.run_task(&task, runner, ingress, None);
+            self.policy_adapters.finish_task(&task.task_id);
+            return outcomes;
+        };
+        let raw_task = serde_json::to_string(&task).map_err(|e| {
+            RuntimeError::Execution(format!("failed serializing task checkpoint: {e}"))
+        })?;
+        let mut checkpoint = TaskCheckpoint::new(task.task_id.clone(), raw_task);
+        checkpoints.save(&checkpoint)?;
+        let outcomes = self.run_task(&task, runner, ingress, Some(&mut checkpoint));
+        self.policy_adapters.finish_task(&task.task_id);
+        // A cancelled task stopped part-way; its checkpoint is left for `reconcile`.
+        if !self.is_cancelled() {
+            checkpoints.remove(&task.task_id)?;
+        }
+        outcomes
+    }
+
+    fn dead_letter(&self, raw_task: &str, err: &RuntimeError) -> RuntimeResult<()> {
+        let Some(queue) = &self.dead_letters else {
+            return Ok(());
+        };
+        let entry = DeadLetterEntry {
+            id: new_trace_id(),
+            task_id: raw_task_id(raw_task),
+            raw_task: raw_task.to_string(),
+            reason: dead_letter_reason(err).to_string(),
+            detail: err.to_string(),
+            failed_at_unix: now_unix(),
+            attempts: 1,
+        };
+        queue.push(&entry)?;
+        self.record_dead_letter_event("task.dead_lettered", &entry)
+    }
+
+    fn record_dead_letter_event(
+        &self,
+        event_type: &str,
+        entry: &DeadLetterEntry,
+    ) -> RuntimeResult<()> {
+        self.audit.record(AuditRecord {
+            ts_unix: now_unix(),
+            event_type: event_type.to_string(),
+            severity: taxonomy::severity(event_type),
+            request_id: None,
+            task_id: entry.task_id.clone(),
+            project: None,
+            trace_id: None,
+            metadata: serde_json::json!({
+                "dead_letter_id": entry.id,
+                "reason": entry.reason,
+                "detail": entry.detail,
+                "attempts": entry.attempts
+            }),
+        })?;
+        Ok(())
+    }
+
+    /// Entries in the dead-letter queue, oldest first; empty when none is configured.
+    pub fn dead_letters(&self) -> RuntimeResult<Vec<DeadLetterEntry>> {
+        match &self.dead_letters {
+            Some(queue) => queue.list(),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Runs the payload of dead-letter entry `id` again. On success the entry is removed
+    /// (`task.dead_letter.retried`); on failure it stays with the new reason and an extra
+    /// attempt counted (`task.dead_letter.retry_failed`).
+    pub fn retry_dead_letter<R, T>(
+        &self,
+        id: &str,
+        runner: &R,
+        ingress: &T,
+    ) -> RuntimeResult<Vec<ActionOutcome>>
+    where
+        R: PluginEventRunner,
+        T: TaskIngress,
+    {
+        let Some(queue) = &self.dead_letters else {
+            return Err(RuntimeError::InvalidInput(
+                "no dead-letter queue configured".to_string(),
+            ));
+        };
+        let Some(mut entry) = queue.get(id)? else {
+            return Err(RuntimeError::InvalidInput(format!(
+                "unknown dead-letter entry: {id}"
+            )));
+        };
+        entry.attempts += 1;
+        match self.process_task(&entry.raw_task, runner, ingress) {
+            Ok(outcomes) => {
+                queue.remove(id)?;
+                self.record_dead_letter_event("task.dead_letter.retried", &entry)?;
+                Ok(outcomes)
+            }
+            Err(err) => {
+                entry.reason = dead_letter_reason(&err).to_string();
+                entry.detail = err.to_string();
+                entry.failed_at_unix = now_unix();
+                queue.push(&entry)?;
+                self.record_dead_letter_event("task.dead_letter.retry_failed", &entry)?;
+                Err(err)
+            }
+        }
+    }
+
+    fn run_task<R, T>(
+        &self,
+        task: &WatchdogTaskEnvelope,
+        runner: &R,
+        ingress: &T,
+        checkpoint: Option<&mut TaskCheckpoint>,
+    ) -> RuntimeResult<Vec<ActionOutcome>>
+    where
+        R: PluginEventRunner,
+        T: TaskIngress,
+    {
+        let event = self.router.handler(&task.task_kind)?.event_for(task)?;
+        if let Some(outcome) = self.check_event_schema(task, &event)? {
+            return Ok(vec![outcome]);
+        }
+
+        let directives = match self.dispatch_plugin(
+            runner,
+            task,
+            &task.payload.plugin,
+            &event,
+            &format!("{}-dispatch", task.task_id),
+        )? {
+            Ok(directives) => directives,
+            Err(outcome) => return Ok(vec![outcome]),
+        };
+        let checkpoint = match (checkpoint, &self.checkpoints) {
+            (Some(checkpoint), Some(checkpoints)) => {
+                checkpoint.directives = Some(directives.clone());
+                checkpoints.save(checkpoint)?;
+                Some(checkpoint)
+            }
+            _ => None,
+        };
+        self.route_directives(
+            task,
+            &task.payload.plugin,
+            &task.task_id,
+            directives,
+            runner,
+            ingress,
+            0,
+            checkpoint,
+        )
+    }
+
+    /// Continues an interrupted task from `checkpoint`: directives before its
+    /// `directive_index` are not re-applied and their saved outcomes lead the result. A task
+    /// interrupted before its dispatch completed is dispatched again. Audited as `task.resumed`.
+    fn resume_checkpoint<R, T>(
+        &self,
+        mut checkpoint: TaskCheckpoint,
+        runner: &R,
+        ingress: &T,
+    ) -> RuntimeResult<Vec<ActionOutcome>>
+    where
+        R: PluginEventRunner,
+        T: TaskIngress,
+    {
+        let task = parse_task(&checkpoint.raw_task)?;
+        self.audit.record(AuditRecord {
+            ts_unix: now_unix(),
+            event_type: "task.resumed".to_string(),
+            severity: Severity::Notice,
+            request_id: None,
+            task_id: Some(task.task_id.clone()),
+            project: Some(task.payload.project.clone()),
+            trace_id: task.trace_id.clone(),
+            metadata: serde_json::json!({
+                "dispatched": checkpoint.directives.is_some(),
+                "directive_index": checkpoint.directive_index,
+                "outcomes": checkpoint.outcomes.len()
+            }),
+        })?;
+        let outcomes = match checkpoint.directives.clone() {
+            Some(directives) => self.route_directives(
+                &task,
+                &task.payload.plugin,
+                &task.task_id,
+                directives,
+                runner,
+                ingress,
+                0,
+                Some(&mut checkpoint),
+            ),
+            None => self.run_task(&task, runner, ingress, Some(&mut checkpoint)),
+        };
+        self.policy_adapters.finish_task(&task.task_id);
+        let outcomes = outcomes?;
+        if let (false, Some(checkpoints)) = (self.is_cancelled(), &self.checkpoints) {
+            checkpoints.remove(&task.task_id)?;
+        }
+        Ok(outcomes)
+    }
+
+    /// Resumes every unfinished task left by the previous run from its checkpoint; shorthand
+    /// for `reconcile(ReconcilePolicy::Resume, ..)`.
+    pub fn recover<R, T>(&self, runner: &R, ingress: &T) -> RuntimeResult<ReconcileReport>
+    where
+        R: PluginEventRunner,
+        T: TaskIngress,
+    {
+        self.reconcile(ReconcilePolicy::Resume, runner, ingress)
+    }
+
+    /// Settles work interrupted by the previous shutdown or crash: open task checkpoints are
+    /// resumed, requeued or quarantined per `policy`, and approvals that expired meanwhile
+    /// are dropped. The report is audited as `runtime.reconciled`.
+    pub fn reconcile<R, T>(
+        &self,
+        policy: ReconcilePolicy,
+        runner: &R,
+        ingress: &T,
+    ) -> RuntimeResult<ReconcileReport>
+    where
+        R: PluginEventRunner,
+        T: TaskIngress,
+    {
+        let mut report = ReconcileReport {
+            policy,
+            ..ReconcileReport::default()
+        };
+        if let Some(checkpoints) = &self.checkpoints {
+            for checkpoint in checkpoints.list()? {
+                let task_id = checkpoint.task_id.clone();
+                match policy {
+                    ReconcilePolicy::Resume => {
+                        if self.resume_checkpoint(checkpoint, runner, ingress).is_ok() {
+                            report.resumed.push(task_id);
+                        } else {
+                            checkpoints.quarantine(&task_id)?;
+                            report.quarantined.push(task_id);
+                        }
+                    }
+                    ReconcilePolicy::Requeue => {
+                        ingress.write_task_payload(&checkpoint.raw_task)?;
+                        checkpoints.remove(&task_id)?;
+                        report.requeued.push(task_id);
+                    }
+                    ReconcilePolicy::Quarantine => {
+                        checkpoints.quarantine(&task_id)?;
+                        report.quarantined.push(task_id);
+                    }
+                }
+            }
+        }
+
+        let now = now_unix();
+        for pending in self.approvals.list()? {
+            if pending.is_expired(now) {
+                self.approvals.remove(pending.request_id())?;
+                self.record_approval_event("approval.expired", &pending.request, "reconcile")?;
+                report
+                    .approvals_expired
+                    .push(pending.request_id().to_string());
+            } else {
+                report.approvals_pending += 1;
+            }
+        }
+
+        self.audit.record(AuditRecord {
+            ts_unix: now,
+            event_type: "runtime.reconciled".to_string(),
+            severity: Severity::Notice,
+            request_id: None,
+            task_id: None,
+            project: None,
+            trace_id: None,
+            metadata: serde_json::to_value(&report).unwrap_or(Value::Null),
+        })?;
+        Ok(report)
+    }
+
+    /// Audits `task.cancelled` and returns the outcome standing in for the `skipped`
+    /// directives that were never run.
+    fn cancel_task(
+        &self,
+        task: &WatchdogTaskEnvelope,
+        request_id: &str,
+        skipped: usize,
+    ) -> RuntimeResult<ActionOutcome> {
+        self.audit.record(AuditRecord {
+            ts_unix: now_unix(),
+            event_type: "task.cancelled".to_string(),
+            severity: Severity::Notice,
+            request_id: Some(request_id.to_string()),
+            task_id: Some(task.task_id.clone()),
+            project: Some(task.payload.project.clone()),
+            trace_id: task.trace_id.clone(),
+            metadata: serde_json::json!({
+                "plugin": task.payload.plugin,
+                "skipped": skipped
+            }),
+        })?;
+        Ok(ActionOutcome {
+            request_id: request_id.to_string(),
+            status: ActionStatus::Blocked,
+            detail: "runtime_cancelled".to_string(),
+            output: serde_json::json!({ "skipped": skipped }),
+            warnings: Vec::new(),
+            snapshot: None,
+        })
+    }
+
+    /// Validates an ingress event against its schema per `event_validation`, returning
+    /// the rejection outcome when the event must not be dispatched.
+    fn check_event_schema(
+        &self,
+        task: &WatchdogTaskEnvelope,
+        event: &EventEnvelope,
+    ) -> RuntimeResult<Option<ActionOutcome>> {
+        if self.event_validation == EventValidation::Off {
+            return Ok(None);
+        }
+        let violations = validate_event(event);
+        if violations.is_empty() {
+            return Ok(None);
+        }
+        let violations: Vec<String> = violations.iter().map(ToString::to_string).collect();
+        let rejected = self.event_validation == EventValidation::Reject;
+        self.audit.record(AuditRecord {
+            ts_unix: now_unix(),
+            event_type: "event.schema.invalid".to_string(),
+            severity: Severity::Warning,
+            request_id: None,
+            task_id: Some(task.task_id.clone()),
+            project: Some(task.payload.project.clone()),
+            trace_id: task.trace_id.clone(),
+            metadata: serde_json::json!({
+                "plugin": task.payload.plugin,
+                "event_type": event.event_type,
+                "violations": violations,
+                "rejected": rejected
+            }),
+        })?;
+        Ok(rejected.then(|| ActionOutcome {
+            request_id: format!("{}-dispatch", task.task_id),
+            status: ActionStatus::Failed,
+            detail: "event_schema_invalid".to_string(),
+            output: serde_json::json!({ "violations": violations }),
+            warnings: Vec::new(),
+            snapshot: None,
+        }))
+    }
+
+    /// Dispatches `event` to `plugin`, converting an output-limit breach into a `Failed`
+    /// outcome (with the truncated prefix audited) instead of aborting the task.
+    fn dispatch_plugin<R>(
+        &self,
+        runner: &R,
+        task: &WatchdogTaskEnvelope,
+        plugin: &str,
+        event: &EventEnvelope,
+        request_id: &str,
+    ) -> RuntimeResult<Result<Vec<PluginDirective>, ActionOutcome>>
+    where
+        R: PluginEventRunner,
+    {
+        let project = &task.payload.project;
+        if !self.circuit_admits(plugin, project, task.trace_id.as_deref())? {
+            return Ok(Err(ActionOutcome {
+                request_id: request_id.to_string(),
+                status: ActionStatus::Blocked,
+                detail: "plugin_circuit_open".to_string(),
+                output: serde_json::json!({ "plugin": plugin }),
+                warnings: Vec::new(),
+                snapshot: None,
+            }));
+        }
+        let _permit = match runner.max_concurrent_dispatches(plugin)? {
+            Some(limit) => match self.dispatch_limiter.acquire(plugin, limit)? {
+                Some(permit) => Some(permit),
+                None => {
+                    self.audit.record(AuditRecord {
+                        ts_unix: now_unix(),
+                        event_type: "plugin.concurrency.limited".to_string(),
+                        severity: Severity::Notice,
+                        request_id: Some(request_id.to_string()),
+                        task_id: Some(task.task_id.clone()),
+                        project: Some(project.clone()),
+                        trace_id: task.trace_id.clone(),
+                        metadata: serde_json::json!({
+                            "plugin": plugin,
+                            "event_type": event.event_type,
+                            "max_concurrent_dispatches": limit
+                        }),
+                    })?;
+                    return Ok(Err(ActionOutcome {
+                        request_id: request_id.to_string(),
+                        status: ActionStatus::Blocked,
+                        detail: "plugin_concurrency_limited".to_string(),
+                        output: serde_json::json!({
+                            "plugin": plugin,
+                            "max_concurrent_dispatches": limit
+                        }),
+                        warnings: Vec::new(),
+                        snapshot: None,
+                    }));
+                }
+            },
+            None => None,
+        };
+        let version = match runner.version_info(plugin) {
+            Ok(version) => version,
+            Err(err) => {
+                // An unreadable manifest or an unsupported protocol fails this dispatch like a
+                // crashed plugin would, rather than the whole task.
+                if let Some(metrics) = &self.metrics {
+                    let labels = [("plugin", plugin), ("plugin_version", "unknown")];
+                    metrics.increment_counter(metrics::PLUGIN_DISPATCH_FAILURES, &labels);
+                }
+                self.record_circuit_result(plugin, project, task.trace_id.as_deref(), false)?;
+                return Ok(Err(ActionOutcome {
+                    request_id: request_id.to_string(),
+                    status: ActionStatus::Failed,
+                    detail: "plugin_version_unavailable".to_string(),
+                    output: serde_json::json!({ "plugin": plugin, "error": err.to_string() }),
+                    warnings: Vec::new(),
+                    snapshot: None,
+                }));
+            }
+        };
+        let started = Instant::now();
+        let dispatched = runner.dispatch_with_diagnostics(plugin, event);
+        if let Some(metrics) = &self.metrics {
+            let labels = version.as_ref().map(PluginVersionInfo::metric_labels);
+            let labels: Vec<(&str, &str)> = match &labels {
+                Some(labels) => labels
+                    .iter()
+                    .map(|(name, value)| (*name, value.as_str()))
+                    .collect(),
+                None => vec![("plugin", plugin), ("plugin_version", "unknown")],
+            };
+            metrics.observe_duration(
+                metrics::PLUGIN_DISPATCH_DURATION,
+                &labels,
+                started.elapsed(),
+            );
+            if matches!(&dispatched, Err(err) if !matches!(err, RuntimeError::Cancelled(_))) {
+                metrics.increment_counter(metrics::PLUGIN_DISPATCH_FAILURES, &labels);
+            }
+        }
+        if !matches!(dispatched, Err(RuntimeError::Cancelled(_))) {
+            self.record_circuit_result(
+                plugin,
+                project,
+                task.trace_id.as_deref(),
+                dispatched.is_ok(),
+            )?;
+        }
+        match dispatched {
+            Ok((directives, diagnostics)) => {
+                let mut metadata = serde_json::json!({
+                    "plugin": plugin,
+                    "event_type": event.event_type,
+                    "duration_ms": started.elapsed().as_millis() as u64,
+                    "directives": directives.len(),
+                    "version": version
+                });
+                if let Some(diagnostics) = diagnostics {
+                    if let (Some(metadata), Ok(Value::Object(diagnostics))) =
+                        (metadata.as_object_mut(), serde_json::to_value(diagnostics))
+                    {
+                        metadata.extend(diagnostics);
+                    }
+                }
+                self.audit.record(AuditRecord {
+                    ts_unix: now_unix(),
+                    event_type: "plugin.dispatched".to_string(),
+                    severity: Severity::Info,
+                    request_id: Some(request_id.to_string()),
+                    task_id: Some(task.task_id.clone()),
+                    project: Some(task.payload.project.clone()),
+                    trace_id: task.trace_id.clone(),
+                    metadata,
+                })?;
+                Ok(Ok(directives))
+            }
+            Err(RuntimeError::PluginOutputLimitExceeded {
+                limit_bytes,
+                prefix,
+                ..
+            }) => {
+                self.audit.record(AuditRecord {
+                    ts_unix: now_unix(),
+                    event_type: "plugin.output.truncated".to_string(),
+                    severity: Severity::Warning,
+                    request_id: Some(request_id.to_string()),
+                    task_id: Some(task.task_id.clone()),
+                    project: Some(task.payload.project.clone()),
+                    trace_id: task.trace_id.clone(),
+                    metadata: serde_json::json!({
+                        "plugin": plugin,
+                        "event_type": event.event_type,
+                        "limit_bytes": limit_bytes,
+                        "prefix": prefix,
+                        "version": version
+                    }),
+                })?;
+                Ok(Err(ActionOutcome {
+                    request_id: request_id.to_string(),
+                    status: ActionStatus::Failed,
+                    detail: "plugin_output_limit_exceeded".to_string(),
+                    output: serde_json::json!({
+                        "plugin": plugin,
+                        "limit_bytes": limit_bytes
+                    }),
+                    warnings: Vec::new(),
+                    snapshot: None,
+                }))
+            }
+            Err(RuntimeError::Cancelled(reason)) => {
+                self.audit.record(AuditRecord {
+                    ts_unix: now_unix(),
+                    event_type: "plugin.dispatch.cancelled".to_string(),
+                    severity: Severity::Notice,
+                    request_id: Some(request_id.to_string()),
+                    task_id: Some(task.task_id.clone()),
+                    project: Some(task.payload.project.clone()),
+                    trace_id: task.trace_id.clone(),
+                    metadata: serde_json::json!({
+                        "plugin": plugin,
+                        "event_type": event.event_type,
+                        "reason": reason
+                    }),
+                })?;
+                Ok(Err(ActionOutcome {
+                    request_id: request_id.to_string(),
+                    status: ActionStatus::Failed,
+                    detail: "runtime_cancelled".to_string(),
+                    output: serde_json::json!({ "plugin": plugin }),
+                    warnings: Vec::new(),
+                    snapshot: None,
+                }))
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Applies `directives` returned by `plugin`; `depth` counts `emit_event` hops. With a
+    /// `checkpoint`, directives before its `directive_index` are skipped and progress is saved
+    /// as each later one settles.
+    #[allow(clippy::too_many_arguments)]
+    fn route_directives<R, T>(
+        &self,
+        task: &WatchdogTaskEnvelope,
+        plugin: &str,
+        id_prefix: &str,
+        directives: Vec<PluginDirective>,
+        runner: &R,
+        ingress: &T,
+        depth: usize,
+        mut checkpoint: Option<&mut TaskCheckpoint>,
+    ) -> RuntimeResult<Vec<ActionOutcome>>
+    where
+        R: PluginEventRunner,
+        T: TaskIngress,
+    {
+        let resume_from = checkpoint.as_ref().map_or(0, |cp| cp.directive_index);
+        let mut outcomes = checkpoint
+            .as_ref()
+            .map(|cp| cp.outcomes.clone())
+            .unwrap_or_default();
+        let mut capability_batch = Vec::new();
+        let total = directives.len();
+
+        for (idx, directive) in directives.into_iter().enumerate() {
+            if idx < resume_from {
+                continue;
+            }
+            // Batched capabilities have no outcome yet, so progress only advances past them
+            // once the batch has run.
+            if let (Some(cp), Some(checkpoints)) = (checkpoint.as_deref_mut(), &self.checkpoints) {
+                if idx > cp.directive_index && capability_batch.is_empty() {
+                    cp.directive_index = idx;
+                    cp.outcomes = outcomes.clone();
+                    checkpoints.save(cp)?;
+                }
+            }
+            if self.is_cancelled() {
+                outcomes.extend(
+                    self.run_capability_batch(
+                        &task.task_id,
+                        std::mem::take(&mut capability_batch),
+                    )?,
+                );
+                outcomes.push(self.cancel_task(
+                    task,
+                    &format!("{id_prefix}-{idx}-cancelled"),
+                    total - idx,
+                )?);
+                break;
+            }
+            if !matches!(directive, PluginDirective::RequestCapability { .. }) {
+                outcomes.extend(
+                    self.run_capability_batch(
+                        &task.task_id,
+                        std::mem::take(&mut capability_batch),
+                    )?,
+                );
+            }
+            match directive {
+                PluginDirective::RequestCapability {
+                    capability,
+                    reason,
+                    input,
+                    risk_tier,
+                } => {
+                    let project = capability
+                        .project
+                        .unwrap_or_else(|| task.payload.project.clone());
+                    let request = ActionRequest {
+                        request_id: format!("{id_prefix}-{idx}-cap"),
+                        risk_tier: risk_tier.unwrap_or(RiskTier::Safe),
+                        capability: CapabilityRequest {
+                            plugin: plugin.to_string(),
+                            project,
+                            capability: capability.id,
+                            scope: vec!["project".to_string()],
+                            reason: if reason.trim().is_empty() {
+                                "plugin requested capability".to_string()
+                            } else {
+                                reason
+                            },
+                        },
+                        trace_id: task.trace_id.clone(),
+                        input,
+                        requested_by: None,
+                    };
+                    let manifest = match runner.capability_manifest(plugin)? {
+                        Some(manifest) => manifest,
+                        None => CapabilityManifest {
+                            schema_version: 1,
+                            plugin: request.capability.plugin.clone(),
+                            capabilities: vec![DelegationCapability {
+                                id: request.capability.capability.clone(),
+                                scope: request.capability.scope.clone(),
+                            }],
+                        },
+                    };
+                    match self.directive_execution {
+                        DirectiveExecution::Sequential => outcomes.push(self.handle_task_action(
+                            request,
+                            &manifest,
+                            Some(&task.task_id),
+                        )?),
+                        DirectiveExecution::Parallel { .. } => {
+                            capability_batch.push((request, manifest))
+                        }
+                    }
+                }
+                PluginDirective::EnqueueTask {
+                    task_type,
+                    project,
+                    reason,
+                    payload,
+                } => {
+                    if task_type.trim().is_empty() {
+                        return Err(RuntimeError::InvalidInput(
+                            "enqueue_task requires non-empty task_type".to_string(),
+                        ));
+                    }
+                    let project = project.unwrap_or_else(|| task.payload.project.clone());
+                    let request = ActionRequest {
+                        request_id: format!("{id_prefix}-{idx}-enqueue"),
+                        risk_tier: RiskTier::Sensitive,
+                        capability: CapabilityRequest {
+                            plugin: plugin.to_string(),
+                            project: project.clone(),
+                            capability: "task.enqueue".to_string(),
+                            scope: vec!["project".to_string()],
+                            reason: reason.unwrap_or_else(|| {
+                                format!("plugin enqueue request for {}", task_type)
+                            }),
+                        },
+                        trace_id: task.trace_id.clone(),
+                        input: serde_json::json!({
+                            "task_type": task_type,
+                            "origin_task_id": task.task_id
+                        }),
+                        requested_by: None,
+                    };
+
+                    match self.evaluate_policy(&request)? {
+                        PolicyDecision::Deny { reason_code } => outcomes.push(ActionOutcome {
+                            request_id: request.request_id,
+                            status: ActionStatus::Blocked,
+                            detail: reason_code,
+                            output: Value::Null,
+                            warnings: Vec::new(),
+                            snapshot: None,
+                        }),
+                        PolicyDecision::RequireApproval { reason_code, .. } => {
+                            outcomes.push(ActionOutcome {
+                                request_id: request.request_id,
+                                status: ActionStatus::ApprovalPending,
+                                detail: reason_code,
+                                output: Value::Null,
+                                warnings: Vec::new(),
+                                snapshot: None,
+                            })
+                        }
+                        PolicyDecision::Allow { .. } => {
+                            let queued = build_enqueued_task(
+                                task,
+                                plugin,
+                                id_prefix,
+                                idx,
+                                &task_type,
+                                &project,
+                                payload.clone(),
+                            );
+                            let queued_json = serde_json::to_string(&queued).map_err(|e| {
+                                RuntimeError::InvalidInput(format!(
+                                    "failed serializing enqueued task: {e}"
+                                ))
+                            })?;
+                            ingress.write_task_payload(&queued_json)?;
+
+                            self.audit.record(AuditRecord {
+                                ts_unix: now_unix(),
+                                event_type: "task.enqueued".to_string(),
+                                severity: Severity::Info,
+                                request_id: Some(request.request_id.clone()),
+                                task_id: Some(task.task_id.clone()),
+                                project: Some(project.clone()),
+                                trace_id: task.trace_id.clone(),
+                                metadata: serde_json::json!({
+                                    "plugin": plugin,
+                                    "task_type": task_type,
+                                    "origin_task_id": task.task_id
+                                }),
+                            })?;
+
+                            outcomes.push(ActionOutcome {
+                                request_id: request.request_id,
+                                status: ActionStatus::Executed,
+                                detail: "task_enqueued".to_string(),
+                                output: serde_json::json!({
+                                    "task_type": task_type,
+                                    "project": project
+                                }),
+                                warnings: Vec::new(),
+                                snapshot: None,
+                            });
+                        }
+                    }
+                }
+                PluginDirective::RequestSecret { handle, reason } => {
+                    if handle.trim().is_empty() {
+                        return Err(RuntimeError::InvalidInput(
+                            "request_secret requires non-empty handle".to_string(),
+                        ));
+                    }
+                    let request = ActionRequest {
+                        request_id: format!("{id_prefix}-{idx}-secret"),
+                        risk_tier: RiskTier::Sensitive,
+                        capability: CapabilityRequest {
+                            plugin: plugin.to_string(),
+                            project: task.payload.project.clone(),
+                            capability: "secret.read".to_string(),
+                            scope: vec!["project".to_string()],
+                            reason: if reason.trim().is_empty() {
+                                "plugin requested secret".to_string()
+                            } else {
+                                reason
+                            },
+                        },
+                        trace_id: task.trace_id.clone(),
+                        input: serde_json::json!({ "handle": handle }),
+                        requested_by: None,
+                    };
+                    outcomes.push(self.lease_secret(request, &task.task_id, &handle)?);
+                }
+                PluginDirective::EmitEvent {
+                    event_type,
+                    payload,
+                    targets,
+                } => {
+                    if !event_type.starts_with(PLUGIN_EVENT_PREFIX) {
+                        return Err(RuntimeError::InvalidInput(format!(
+                            "emit_event type must start with {PLUGIN_EVENT_PREFIX}: {event_type}"
+                        )));
+                    }
+                    if depth >= MAX_EVENT_FANOUT_DEPTH {
+                        outcomes.push(ActionOutcome {
+                            request_id: format!("{id_prefix}-{idx}-emit"),
+                            status: ActionStatus::Blocked,
+                            detail: "event_fanout_depth_exceeded".to_string(),
+                            output: Value::Null,
+                            warnings: Vec::new(),
+                            snapshot: None,
+                        });
+                        continue;
+                    }
+
+                    let recipients = runner
+                        .subscribers(&event_type)?
+                        .into_iter()
+                        .filter(|target| target != plugin)
+                        .filter(|target| targets.is_empty() || targets.contains(target));
+                    for target in recipients {
+                        let request = ActionRequest {
+                            request_id: format!("{id_prefix}-{idx}-emit-{target}"),
+                            risk_tier: RiskTier::Sensitive,
+                            capability: CapabilityRequest {
+                                plugin: plugin.to_string(),
+                                project: task.payload.project.clone(),
+                                capability: "event.emit".to_string(),
+                                scope: vec!["project".to_string()],
+                                reason: format!("plugin event {event_type} for {target}"),
+                            },
+                            trace_id: task.trace_id.clone(),
+                            input: serde_json::json!({
+                                "event_type": event_type,
+                                "target": target
+                            }),
+                            requested_by: None,
+                        };
+
+                        match self.evaluate_policy(&request)? {
+                            PolicyDecision::Deny { reason_code } => outcomes.push(ActionOutcome {
+                                request_id: request.request_id,
+                                status: ActionStatus::Blocked,
+                                detail: reason_code,
+                                output: Value::Null,
+                                warnings: Vec::new(),
+                                snapshot: None,
+                            }),
+                            PolicyDecision::RequireApproval { reason_code, .. } => {
+                                outcomes.push(ActionOutcome {
+                                    request_id: request.request_id,
+                                    status: ActionStatus::ApprovalPending,
+                                    detail: reason_code,
+                                    output: Value::Null,
+                                    warnings: Vec::new(),
+                                    snapshot: None,
+                                })
+                            }
+                            PolicyDecision::Allow { .. } => {
+                                let event = EventEnvelope {
+                                    event_id: format!("evt-{}", request.request_id),
+                                    event_type: event_type.clone(),
+                                    task_id: Some(task.task_id.clone()),
+                                    request_id: Some(request.request_id.clone()),
+                                    project: Some(task.payload.project.clone()),
+                                    trace_id: task.trace_id.clone(),
+                                    payload: payload.clone(),
+                                };
+                                let delivered = match self.dispatch_plugin(
+                                    runner,
+                                    task,
+                                    &target,
+                                    &event,
+                                    &request.request_id,
+                                )? {
+                                    Ok(delivered) => delivered,
+                                    Err(outcome) => {
+                                        outcomes.push(outcome);
+                                        continue;
+                                    }
+                                };
+
+                                self.audit.record(AuditRecord {
+                                    ts_unix: now_unix(),
+                                    event_type: "plugin.event.delivered".to_string(),
+                                    severity: Severity::Info,
+                                    request_id: Some(request.request_id.clone()),
+                                    task_id: Some(task.task_id.clone()),
+                                    project: Some(task.payload.project.clone()),
+                                    trace_id: task.trace_id.clone(),
+                                    metadata: serde_json::json!({
+                                        "plugin": plugin,
+                                        "target": target,
+                                        "event_type": event_type
+                                    }),
+                                })?;
+
+                                outcomes.push(ActionOutcome {
+                                    request_id: request.request_id.clone(),
+                                    status: ActionStatus::Executed,
+                                    detail: "event_delivered".to_string(),
+                                    output: serde_json::json!({
+                                        "event_type": event_type,
+                                        "target": target
+                                    }),
+                                    warnings: Vec::new(),
+                                    snapshot: None,
+                                });
+                                outcomes.extend(self.route_directives(
+                                    task,
+                                    &target,
+                                    &request.request_id,
+                                    delivered,
+                                    runner,
+                                    ingress,
+                                    depth + 1,
+                                    None,
+                                )?);
+                            }
+                        }
+                    }
+                }
+                PluginDirective::QueryScopes {
+                    capability,
+                    project,
+                } => {
+                    let project = project.unwrap_or_else(|| task.payload.project.clone());
+                    let grant = self.granted_scopes(plugin, &project, &capability)?;
+                    let request_id = format!("{id_prefix}-{idx}-scopes");
+                    self.audit.record(AuditRecord {
+                        ts_unix: now_unix(),
+                        event_type: "capability.scopes.queried".to_string(),
+                        severity: Severity::Info,
+                        request_id: Some(request_id.clone()),
+                        task_id: Some(task.task_id.clone()),
+                        project: Some(project),
+                        trace_id: task.trace_id.clone(),
+                        metadata: serde_json::json!({
+                            "plugin": plugin,
+                            "capability": capability,
+                            "granted": grant.granted,
+                            "scopes": grant.scopes.len()
+                        }),
+                    })?;
+                    outcomes.push(ActionOutcome {
+                        request_id,
+                        status: ActionStatus::Executed,
+                        detail: "scopes_listed".to_string(),
+                        output: serde_json::to_value(&grant).map_err(|e| {
+                            RuntimeError::Execution(format!("scope grant serialization: {e}"))
+                        })?,
+                        warnings: Vec::new(),
+                        snapshot: None,
+                    });
+                }
+                PluginDirective::Noop => {
+                    self.audit.record(AuditRecord {
+                        ts_unix: now_unix(),
+                        event_type: "plugin.noop".to_string(),
+                        severity: Severity::Info,
+                        request_id: None,
+                        task_id: Some(task.task_id.clone()),
+                        project: Some(task.payload.project.clone()),
+                        trace_id: task.trace_id.clone(),
+                        metadata: serde_json::json!({
+                            "plugin": plugin
+                        }),
+                    })?;
+                }
+            }
+        }
+        outcomes.extend(self.run_capability_batch(&task.task_id, capability_batch)?);
+
+        Ok(outcomes)
+    }
+
+    /// Runs a batch of queued `request_capability` actions under the configured
+    /// concurrency limit, returning outcomes in batch order.
+    fn run_capability_batch(
+        &self,
+        task_id: &str,
+        batch: Vec<(ActionRequest, CapabilityManifest)>,
+    ) -> RuntimeResult<Vec<ActionOutcome>> {
+        let max_concurrency = match self.directive_execution {
+            DirectiveExecution::Sequential => 1,
+            DirectiveExecution::Parallel { max_concurrency } => max_concurrency.max(1),
+        };
+        if batch.len() <= 1 || max_concurrency == 1 {
+            return batch
+                .into_iter()
+                .map(|(request, manifest)| {
+                    self.handle_task_action(request, &manifest, Some(task_id))
+                })
+                .collect();
+        }
+
+        let next = AtomicUsize::new(0);
+        let results: Vec<Mutex<Option<RuntimeResult<ActionOutcome>>>> =
+            batch.iter().map(|_| Mutex::new(None)).collect();
+        thread::scope(|scope| {
+            for _ in 0..max_concurrency.min(batch.len()) {
+                scope.spawn(|| loop {
+                    let idx = next.fetch_add(1, Ordering::Relaxed);
+                    let Some((request, manifest)) = batch.get(idx) else {
+                        break;
+                    };
+                    let result = self.handle_task_action(request.clone(), manifest, Some(task_id));
+                    if let Ok(mut slot) = results[idx].lock() {
+                        *slot = Some(result);
+                    }
+                });
+            }
+        });
+
+        results
+            .into_iter()
+            .map(|slot| {
+                slot.into_inner().ok().flatten().unwrap_or_else(|| {
+                    Err(RuntimeError::Execution(
+                        "parallel capability worker produced no outcome".to_string(),
+                    ))
+                })
+            })
+            .collect()
+    }
+
+    fn lease_secret(
+        &self,
+        request: ActionRequest,
+        task_id: &str,
+        handle: &str,
+    ) -> RuntimeResult<ActionOutcome> {
+        match self.evaluate_policy(&request)? {
+            PolicyDecision::Deny { reason_code } => {
+                return Ok(ActionOutcome {
+                    request_id: request.request_id,
+                    status: ActionStatus::Blocked,
+                    detail: reason_code,
+                    output: Value::Null,
+                    warnings: Vec::new(),
+                    snapshot: None,
+                })
+            }
+            PolicyDecision::RequireApproval { reason_code, .. } => {
+                return Ok(ActionOutcome {
+                    request_id: request.request_id,
+                    status: ActionStatus::ApprovalPending,
+                    detail: reason_code,
+                    output: Value::Null,
+                    warnings: Vec::new(),
+                    snapshot: None,
+                })
+            }
+            PolicyDecision::Allow { .. } => {}
+        }
+
+        let ctx = AccessContext {
+            plugin: request.capability.plugin.clone(),
+            project: request.capability.project.clone(),
+            capability: request.capability.capability.clone(),
+            reason: request.capability.reason.clone(),
+        };
+        let lease = match self
+            .secrets
+            .resolve_secret_handle(&SecretHandle(handle.to_string()), &ctx)
+        {
+            Ok(secret) => secret.handle,
+            Err(SecretError::Backend(e)) => {
+                return Err(RuntimeError::Execution(format!(
+                    "secret backend failure: {e}"
+                )))
+            }
+            Err(err) => {
+                let reason_code = match err {
+                    SecretError::NotFound(_) => "secret_not_found",
+                    _ => "secret_unauthorized",
+                };
+                return Ok(ActionOutcome {
+                    request_id: request.request_id,
+                    status: ActionStatus::Blocked,
+                    detail: reason_code.to_string(),
+                    output: Value::Null,
+                    warnings: Vec::new(),
+                    snapshot: None,
+                });
+            }
+        };
+
+        self.audit.record(AuditRecord {
+            ts_unix: now_unix(),
+            event_type: "secret.leased".to_string(),
+            severity: Severity::Notice,
+            request_id: Some(request.request_id.clone()),
+            task_id: Some(task_id.to_string()),
+            project: Some(request.capability.project.clone()),
+            trace_id: request.trace_id.clone(),
+            metadata: serde_json::json!({
+                "plugin": request.capability.plugin,
+                "handle": handle
+            }),
+        })?;
+
+        Ok(ActionOutcome {
+            request_id: request.request_id,
+            status: ActionStatus::Executed,
+            detail: "secret_leased".to_string(),
+            output: serde_json::json!({ "lease": lease.0 }),
+            warnings: Vec::new(),
+            snapshot: None,
+        })
+    }
+
+    /// Runs the executor under the retry policy, returning the attempt count with the
+    /// last error once attempts are exhausted.
+    fn execute_with_retry(&self, request: &ActionRequest) -> Result<Value, (u32, RuntimeError)> {
+        let max_attempts = self.retry.attempts_for(&request.risk_tier);
+        let mut attempt = 1;
+        loop {
+            match self.executor.execute(request) {
+                Ok(output) => return Ok(output),
+                Err(err) if attempt >= max_attempts => return Err((attempt, err)),
+                Err(_) => {
+                    let delay = self.retry.backoff_for(attempt);
+                    if !delay.is_zero() {
+                        thread::sleep(delay);
+                    }
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    fn deprecation_warnings(&self, request: &ActionRequest) -> RuntimeResult<Vec<OutcomeWarning>> {
+        let Some(warning) = self
+            .deprecations
+            .warning_for(&request.capability.plugin, &request.capability.capability)
+        else {
+            return Ok(Vec::new());
+        };
+        self.audit.record(AuditRecord {
+            ts_unix: now_unix(),
+            event_type: "capability.deprecated".to_string(),
+            severity: Severity::Notice,
+            request_id: Some(request.request_id.clone()),
+            task_id: None,
+            project: Some(request.capability.project.clone()),
+            trace_id: request.trace_id.clone(),
+            metadata: serde_json::to_value(&warning).unwrap_or(Value::Null),
+        })?;
+        Ok(vec![warning])
+    }
+
+    /// Admits `plugin` through its circuit, auditing a half-open transition.
+    fn circuit_admits(
+        &self,
+        plugin: &str,
+        project: &str,
+        trace_id: Option<&str>,
+    ) -> RuntimeResult<bool> {
+        let Some(circuit) = &self.circuit else {
+            return Ok(true);
+        };
+        let (admitted, transition) = circuit.admit(plugin, Instant::now())?;
+        if let Some(transition) = transition {
+            self.record_circuit_transition(plugin, project, trace_id, transition)?;
+        }
+        Ok(admitted)
+    }
+
+    fn record_circuit_result(
+        &self,
+        plugin: &str,
+        project: &str,
+        trace_id: Option<&str>,
+        ok: bool,
+    ) -> RuntimeResult<()> {
+        let Some(circuit) = &self.circuit else {
+            return Ok(());
+        };
+        let transition = if ok {
+            circuit.record_success(plugin)?
+        } else {
+            circuit.record_failure(plugin, Instant::now())?
+        };
+        if let Some(transition) = transition {
+            self.record_circuit_transition(plugin, project, trace_id, transition)?;
+        }
+        Ok(())
+    }
+
+    fn record_circuit_transition(
+        &self,
+        plugin: &str,
+        project: &str,
+        trace_id: Option<&str>,
+        transition: CircuitTransition,
+    ) -> RuntimeResult<()> {
+        let mut metadata = serde_json::json!({ "plugin": plugin });
+        if let (
+            CircuitTransition::Opened {
+                consecutive_failures,
+            },
+            Some(circuit),
+        ) = (transition, &self.circuit)
+        {
+            metadata["consecutive_failures"] = consecutive_failures.into();
+            metadata["cooldown_secs"] = circuit.config().cooldown.as_secs().into();
+        }
+        self.audit.record(AuditRecord {
+            ts_unix: now_unix(),
+            event_type: transition.event_type().to_string(),
+            severity: taxonomy::severity(transition.event_type()),
+            request_id: None,
+            task_id: None,
+            project: Some(project.to_string()),
+            trace_id: trace_id.map(str::to_string),
+            metadata,
+        })?;
+        Ok(())
+    }
+
+    fn evaluate_policy(&self, request: &ActionRequest) -> RuntimeResult<PolicyDecision> {
+        validate_capability(&request.capability)?;
+        for expired in self.policy.take_expired_grants(now_unix()) {
+            self.audit.record(AuditRecord {
+                ts_unix: now_unix(),
+                event_type: "policy.grant.expired".to_string(),
+                severity: Severity::Notice,
+                request_id: None,
+                task_id: None,
+                project: Some(expired.project.clone()),
+                trace_id: request.trace_id.clone(),
+                metadata: serde_json::json!({
+                    "plugin": expired.plugin,
+                    "capability": expired.capability,
+                    "expired_at_unix": expired.expired_at_unix
+                }),
+            })?;
+        }
+        let cap = &request.capability;
+        let mut decision =
+            if self.observe_only.contains(&cap.plugin) && !is_observe_capability(&cap.capability) {
+                PolicyDecision::Deny {
+                    reason_code: "observe_only_plugin_mutation".to_string(),
+                }
+            } else if self.circuit_admits(&cap.plugin, &cap.project, request.trace_id.as_deref())? {
+                self.policy.decide(request)?
+            } else {
+                PolicyDecision::Deny {
+                    reason_code: "plugin_circuit_open".to_string(),
+                }
+            };
+        if let Some(store) = &self.break_glass {
+            for expired in store.take_expired(now_unix())? {
+                self.record_break_glass_event("policy.override.expired", &expired, None)?;
+            }
+        }
+        let mut elevation_id = None;
+        if let Some(overlay) = &self.elevations {
+            let now = now_unix();
+            for expired in overlay.take_expired(now)? {
+                self.record_elevation_event("policy.elevation.expired", &expired)?;
+            }
+            let not_granted = matches!(
+                &decision,
+                PolicyDecision::Deny { reason_code } if reason_code == "capability_not_granted"
+            );
+            if not_granted {
+                if let Some(elevation) = overlay.find_active(request, now)? {
+                    decision = if matches!(request.risk_tier, RiskTier::Destructive) {
+                        PolicyDecision::RequireApproval {
+                            reason_code: "elevated_destructive_requires_approval".to_string(),
+                            tier: RiskTier::Destructive,
+                        }
+                    } else {
+                        PolicyDecision::Allow {
+                            reason_code: "capability_elevated".to_string(),
+                        }
+                    };
+                    elevation_id = Some(elevation.id);
+                }
+            }
+        }
+        let mut metadata = serde_json::json!({
+            "plugin": request.capability.plugin,
+            "capability": request.capability.capability,
+            "decision": decision_tag(&decision)
+        });
+        if let Some(elevation_id) = elevation_id {
+            metadata["elevation_id"] = Value::String(elevation_id);
+        }
+        if let Some(version) = self.policy.version() {
+            metadata["policy_version"] = Value::String(version);
+        }
+        self.audit.record(AuditRecord {
+            ts_unix: now_unix(),
+            event_type: "policy.decision".to_string(),
+            severity: Severity::Info,
+            request_id: Some(request.request_id.clone()),
+            task_id: None,
+            project: Some(request.capability.project.clone()),
+            trace_id: request.trace_id.clone(),
+            metadata,
+        })?;
+        Ok(decision)
+    }
+}
+
+/// Collects outcome warnings for a run report, keeping the first occurrence of each
+/// plugin/capability pair.
+pub fn aggregate_warnings(outcomes: &[ActionOutcome]) -> Vec<OutcomeWarning> {
+    let mut aggregated: Vec<OutcomeWarning> = Vec::new();
+    for warning in outcomes.iter().flat_map(|outcome| &outcome.warnings) {
+        if !aggregated.iter().any(|existing| {
+            existing.plugin == warning.plugin && existing.capability == warning.capability
+        }) {
+            aggregated.push(warning.clone());
+        }
+    }
+    aggregated
+}
+
+/// `task_id` of a payload that may not parse as a full task envelope.
+fn raw_task_id(raw_task: &str) -> Option<String> {
+    serde_json::from_str::<Value>(raw_task)
+        .ok()
+        .and_then(|task| task.get("task_id")?.as_str().map(str::to_string))
+}
+
+fn parse_task(raw_task: &str) -> RuntimeResult<WatchdogTaskEnvelope> {
+    let task: WatchdogTaskEnvelope = serde_json::from_str(raw_task)
+        .map_err(|e| RuntimeError::InvalidInput(format!("invalid watchdog task JSON: {e}")))?;
+
+    if task.schema_version != 1 {
+        return Err(RuntimeError::InvalidInput(format!(
+            "unsupported schema_version: {}",
+            task.schema_version
+        )));
+    }
+    if task.task_kind.trim().is_empty() {
+        return Err(RuntimeError::InvalidInput(
+            "task type is required".to_string(),
+        ));
+    }
+    if task.payload.plugin.trim().is_empty() {
+        return Err(RuntimeError::InvalidInput(
+            "watchdog task payload.plugin is required".to_string(),
+        ));
+    }
+    if task.payload.project.trim().is_empty() {
+        return Err(RuntimeError::InvalidInput(
+            "watchdog task payload.project is required".to_string(),
+        ));
+    }
+    if task.payload.task_type.trim().is_empty() {
+        return Err(RuntimeError::InvalidInput(
+            "watchdog task payload.task_type is required".to_string(),
+        ));
+    }
+
+    Ok(task)
+}
+
+fn build_enqueued_task(
+    origin: &WatchdogTaskEnvelope,
+    plugin: &str,
+    id_prefix: &str,
+    sequence: usize,
+    task_type: &str,
+    project: &str,
+    payload: Value,
+) -> Value {
+    let followup_task_id = format!("{id_prefix}-followup-{sequence}-{}", now_unix());
+    serde_json::json!({
+        "schema_version": 1,
+        "task_id": followup_task_id,
+        "type": task_type,
+        "source": "plugin",
+        "created_at_unix": now_unix(),
+        "payload": {
+            "project": project,
+            "plugin": plugin,
+            "task_type": task_type,
+            "origin_task_id": origin.task_id,
+            "data": payload
+        },
+        "trace_id": origin.trace_id
+    })
+}
+
+fn validate_capability(capability: &CapabilityRequest) -> RuntimeResult<()> {
+    if capability.plugin.trim().is_empty() {
+        return Err(RuntimeError::InvalidInput("plugin is required".to_string()));
+    }
+    if capability.capability.trim().is_empty() {
+        return Err(RuntimeError::InvalidInput(
+            "capability is required".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// `None` when the manifest grants the request and no policy adapter has a say. Adapters judge
+/// against the plugin's registered envelope, or one derived from the manifest when none is
+/// registered.
+fn manifest_decision(
+    request: &ActionRequest,
+    manifest: &CapabilityManifest,
+    permissions: &PluginPermissionRegistry,
+    policy_adapters: &PluginPolicyAdapterRegistry,
+    task_id: Option<&str>,
+) -> Option<PermissionDecision> {
+    if manifest.plugin != request.capability.plugin {
+        return Some(manifest_deny("manifest_plugin_mismatch"));
+    }
+
+    let matching_capabilities = manifest
+        .capabilities
+        .iter()
+        .filter(|capability| capability.id == request.capability.capability)
+        .collect::<Vec<_>>();
+    if matching_capabilities.is_empty() {
+        return Some(manifest_deny("manifest_capability_not_granted"));
+    }
+    if !matching_capabilities
+        .iter()
+        .any(|granted| manifest_scope_permits(&request.capability.scope, &granted.scope))
+    {
+        return Some(manifest_deny("manifest_scope_not_granted"));
+    }
+
+    let envelope = permissions
+        .effective(&request.capability.plugin, &request.capability.project)
+        .cloned()
+        .unwrap_or_else(|| PluginPermissionEnvelope {
+            plugin: manifest.plugin.clone(),
+            trust_level: TrustLevel::Caution,
+            permissions: manifest.capabilities.clone(),
+        });
+    policy_adapters.decision(
+        &request.capability.plugin,
+        &request.capability.capability,
+        &request.input,
+        &envelope,
+        task_id,
+    )
+}
+
+fn manifest_deny(reason_code: &str) -> PermissionDecision {
+    PermissionDecision::Deny {
+        reason_code: reason_code.to_string(),
+    }
+}
+
+fn manifest_scope_permits(requested_scope: &[String], granted_scope: &[String]) -> bool {
+    if requested_scope.is_empty() {
+        return granted_scope.is_empty();
+    }
+    if granted_scope.is_empty() {
+        return false;
+    }
+    requested_scope
+        .iter()
+        .all(|requested| granted_scope.iter().any(|granted| granted == requested))
+}
+
+fn now_unix() -> u64 {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    #[cfg(feature = "chaos")]
+    let now = chaos::skewed(now);
+    now
+}
+
+/// Unique per process and call: wall-clock nanos, pid and a counter, hex-encoded.
+fn new_trace_id() -> String {
+    static NEXT: AtomicU64 = AtomicU64::new(0);
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0);
+    format!(
+        "{nanos:016x}{:08x}{:08x}",
+        std::process::id(),
+        NEXT.fetch_add(1, Ordering::Relaxed)
+    )
+}
+
+fn decision_tag(decision: &PolicyDecision) -> &'static str {
+    match decision {
+        PolicyDecision::Allow { .. } => "allow",
+        PolicyDecision::Deny { .. } => "deny",
+        PolicyDecision::RequireApproval { .. } => "require_approval",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use odin_audit::{AuditRecord, AuditSink};
+    use odin_plugin_protocol::{ActionRequest, CapabilityRequest, RiskTier};
+    use odin_policy_engine::StaticPolicyEngine;
+
+    use super::{
+        ActionExecutor, OrchestratorRuntime, PluginCapabilityRef, PluginDirective,
+        PluginEventRunner, RuntimeError, TaskIngress,
+    };
+
+    #[derive(Clone, Default)]
+    struct MemoryAuditSink(Arc<Mutex<Vec<AuditRecord>>>);
+
+    impl MemoryAuditSink {
+        fn has_event(&self, event_type: &str) -> bool {
+            self.0
+                .lock()
+                .expect("lock")
+                .iter()
+                .any(|record| record.event_type == event_type)
+        }
+    }
+
+    impl AuditSink for MemoryAuditSink {
+        fn record(&self, record: AuditRecord) -> Result<(), odin_audit::AuditError> {
+            // Every event the runtime emits must be registered at its taxonomy severity.
+            odin_audit::taxonomy::validate(&record)?;
+            self.0
+                .lock()
+                .map_err(|_| odin_audit::AuditError::Write("poisoned lock".to_string()))?
+                .push(record);
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    struct MemoryIngress(Mutex<Vec<String>>);
+
+    impl TaskIngress for MemoryIngress {
+        fn write_task_payload(&self, payload: &str) -> Result<(), RuntimeError> {
+            self.0
+                .lock()
+                .map_err(|_| RuntimeError::Execution("poisoned lock".to_string()))?
+                .push(payload.to_string());
+            Ok(())
+        }
+    }
+
+    #[derive(Clone)]
+    struct StubRunner {
+        directives: Vec<PluginDirective>,
+    }
+
+    impl PluginEventRunner for StubRunner {
+        fn dispatch_event(
+            &self,
+            _plugin: &str,
+            _event: &odin_plugin_protocol::EventEnvelope,
+        ) -> Result<Vec<PluginDirective>, RuntimeError> {
+            Ok(self.directives.clone())
+        }
+    }
+
+    struct FanoutRunner {
+        directives: std::collections::HashMap<String, Vec<PluginDirective>>,
+        subscribers: Vec<String>,
+        delivered: Mutex<Vec<(String, String)>>,
+    }
+
+    impl PluginEventRunner for FanoutRunner {
+        fn dispatch_event(
+            &self,
+            plugin: &str,
+            event: &odin_plugin_protocol::EventEnvelope,
+        ) -> Result<Vec<PluginDirective>, RuntimeError> {
+            self.delivered
+                .lock()
+                .expect("lock")
+                .push((plugin.to_string(), event.event_type.clone()));
+            Ok(self.directives.get(plugin).cloned().unwrap_or_default())
+        }
+
+        fn subscribers(&self, _event_type: &str) -> Result<Vec<String>, RuntimeError> {
+            Ok(self.subscribers.clone())
+        }
+    }
+
+    fn fanout_runner(targets: Vec<String>) -> FanoutRunner {
+        let mut directives = std::collections::HashMap::new();
+        directives.insert(
+            "private.ops-watchdog".to_string(),
+            vec![PluginDirective::EmitEvent {
+                event_type: "plugin.sentry.alert".to_string(),
+                payload: serde_json::json!({"issue": "ISSUE-1"}),
+                targets,
+            }],
+        );
+        directives.insert(
+            "private.pager".to_string(),
+            vec![PluginDirective::EmitEvent {
+                event_type: "plugin.sentry.alert".to_string(),
+                payload: serde_json::Value::Null,
+                targets: Vec::new(),
+            }],
+        );
+        FanoutRunner {
+            directives,
+            subscribers: vec![
+                "private.ops-watchdog".to_string(),
+                "private.pager".to_string(),
+                "private.chat".to_string(),
+            ],
+            delivered: Mutex::new(Vec::new()),
+        }
+    }
+
+    struct FlakyExecutor {
+        failures_left: Mutex<u32>,
+    }
+
+    impl ActionExecutor for FlakyExecutor {
+        fn execute(&self, request: &ActionRequest) -> Result<serde_json::Value, RuntimeError> {
+            let mut failures_left = self.failures_left.lock().expect("lock");
+            if *failures_left > 0 {
+                *failures_left -= 1;
+                return Err(RuntimeError::Execution("transient".to_string()));
+            }
+            Ok(serde_json::json!({ "request_id": request.request_id }))
+        }
+    }
+
+    struct FailingExecutor;
+
+    impl ActionExecutor for FailingExecutor {
+        fn execute(&self, _request: &ActionRequest) -> Result<serde_json::Value, RuntimeError> {
+            Err(RuntimeError::Execution("boom".to_string()))
+        }
+    }
+
+    fn request() -> ActionRequest {
+        ActionRequest {
+            request_id: "req-1".to_string(),
+            risk_tier: RiskTier::Safe,
+            capability: CapabilityRequest {
+                plugin: "example.safe-github".to_string(),
+                project: "demo".to_string(),
+                capability: "repo.read".to_string(),
+                scope: vec!["project".to_string()],
+                reason: "unit test".to_string(),
+            },
+            trace_id: None,
+            input: serde_json::Value::Null,
+            requested_by: None,
+        }
+    }
+
+    fn watchdog_task() -> String {
+        serde_json::json!({
+            "schema_version": 1,
+            "task_id": "watchdog-poll-sentry-123",
+            "type": "watchdog_poll",
+            "source": "keepalive",
+            "created_at": "2026-02-25T00:00:00Z",
+            "payload": {
+                "task_type": "watchdog.sentry.poll",
+                "source_key": "sentry-check",
+                "project": "private",
+                "plugin": "private.ops-watchdog",
+                "trigger": "feature_gate"
+            }
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn blocked_when_not_granted() {
+        let runtime = OrchestratorRuntime::new(
+            StaticPolicyEngine::default(),
+            MemoryAuditSink::default(),
+            super::DryRunExecutor,
+        );
+        let outcome = runtime.handle_action(request()).expect("outcome");
+        assert_eq!(outcome.status, odin_plugin_protocol::ActionStatus::Blocked);
+    }
+
+    #[test]
+    fn execution_failure_maps_to_failed_outcome() {
+        let mut policy = StaticPolicyEngine::default();
+        policy.allow_capability("example.safe-github", "demo", "repo.read");
+
+        let audit = MemoryAuditSink::default();
+        let runtime = OrchestratorRuntime::new(policy, audit.clone(), FailingExecutor);
+        let outcome = runtime.handle_action(request()).expect("outcome");
+        assert_eq!(outcome.status, odin_plugin_protocol::ActionStatus::Failed);
+        assert_eq!(outcome.detail, "execution_failed");
+        assert_eq!(
+            outcome.output.get("attempts").and_then(|v| v.as_u64()),
+            Some(1)
+        );
+        assert!(audit.has_event("action.failed"));
+    }
+
+    #[test]
+    fn repeated_failures_open_plugin_circuit() {
+        let mut policy = StaticPolicyEngine::default();
+        policy.allow_capability("example.safe-github", "demo", "repo.read");
+
+        let audit = MemoryAuditSink::default();
+        let runtime = OrchestratorRuntime::new(policy, audit.clone(), FailingExecutor)
+            .with_circuit_breaker(crate::circuit::CircuitBreakerConfig {
+                failure_threshold: 2,
+                cooldown: std::time::Duration::from_secs(60),
+            });
+        for _ in 0..2 {
+            let outcome = runtime.handle_action(request()).expect("outcome");
+            assert_eq!(outcome.detail, "execution_failed");
+        }
+        assert!(audit.has_event("plugin.circuit.opened"));
+
+        let outcome = runtime.handle_action(request()).expect("outcome");
+        assert_eq!(outcome.status, odin_plugin_protocol::ActionStatus::Blocked);
+        assert_eq!(outcome.detail, "plugin_circuit_open");
+    }
+
+    #[test]
+    fn cached_read_skips_executor_and_audits_hit() {
+        #[derive(Default)]
+        struct CountingExecutor(std::sync::atomic::AtomicUsize);
+
+        impl ActionExecutor for &CountingExecutor {
+            fn execute(&self, _request: &ActionRequest) -> Result<serde_json::Value, RuntimeError> {
+                let calls = self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+                Ok(serde_json::json!({ "calls": calls }))
+            }
+        }
+
+        let mut policy = StaticPolicyEngine::default();
+        policy.allow_capability("example.safe-github", "demo", "repo.read");
+        let executor = CountingExecutor::default();
+        let audit = MemoryAuditSink::default();
+        let runtime = OrchestratorRuntime::new(policy, audit.clone(), &executor).with_result_cache(
+            crate::cache::ResultCacheConfig::new()
+                .with_capability("repo.read", std::time::Duration::from_secs(60)),
+        );
+
+        let first = runtime.handle_action(request()).expect("first");
+        let second = runtime.handle_action(request()).expect("second");
+        assert_eq!(first.output, second.output);
+        assert_eq!(executor.0.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        let hits: Vec<bool> = audit
+            .0
+            .lock()
+            .expect("lock")
+            .iter()
+            .filter(|record| record.event_type == "action.executed")
+            .map(|record| record.metadata["cache"]["hit"] == true)
+            .collect();
+        assert_eq!(hits, vec![false, true]);
+    }
+
+    #[test]
+    fn dispatches_beyond_manifest_limit_are_rejected() {
+        use std::sync::mpsc;
+
+        struct BlockingRunner {
+            entered: Mutex<mpsc::Sender<()>>,
+            release: Mutex<mpsc::Receiver<()>>,
+        }
+
+        impl PluginEventRunner for BlockingRunner {
+            fn dispatch_event(
+                &self,
+                _plugin: &str,
+                _event: &odin_plugin_protocol::EventEnvelope,
+            ) -> Result<Vec<PluginDirective>, RuntimeError> {
+                self.entered.lock().expect("lock").send(()).expect("send");
+                self.release.lock().expect("lock").recv().expect("recv");
+                Ok(Vec::new())
+            }
+
+            fn max_concurrent_dispatches(
+                &self,
+                _plugin: &str,
+            ) -> crate::RuntimeResult<Option<u32>> {
+                Ok(Some(1))
+            }
+        }
+
+        let (entered_tx, entered_rx) = mpsc::channel();
+        let (release_tx, release_rx) = mpsc::channel();
+        let runner = BlockingRunner {
+            entered: Mutex::new(entered_tx),
+            release: Mutex::new(release_rx),
+        };
+        let audit = MemoryAuditSink::default();
+        let runtime = OrchestratorRuntime::new(
+            StaticPolicyEngine::default(),
+            audit.clone(),
+            crate::DryRunExecutor,
+        )
+        .with_dispatch_overflow(crate::concurrency::DispatchOverflow::Reject);
+
+        std::thread::scope(|scope| {
+            let first = scope.spawn(|| {
+                runtime.handle_watchdog_task(&watchdog_task(), &runner, &MemoryIngress::default())
+            });
+            entered_rx.recv().expect("first dispatch running");
+
+            let outcomes = runtime
+                .handle_watchdog_task(&watchdog_task(), &runner, &MemoryIngress::default())
+                .expect("outcomes");
+            assert_eq!(outcomes[0].detail, "plugin_concurrency_limited");
+
+            release_tx.send(()).expect("release");
+            assert!(first.join().expect("join").expect("first").is_empty());
+        });
+        assert!(audit.has_event("plugin.concurrency.limited"));
+    }
+
+    #[test]
+    fn malformed_ingress_event_is_rejected_or_flagged() {
+        struct EmptyTaskType;
+
+        impl crate::router::TaskHandler for EmptyTaskType {
+            fn event_for(
+                &self,
+                task: &crate::WatchdogTaskEnvelope,
+            ) -> crate::RuntimeResult<odin_plugin_protocol::EventEnvelope> {
+                let mut event = crate::router::PluginTaskHandler.event_for(task)?;
+                event.payload["task_type"] = serde_json::json!("");
+                Ok(event)
+            }
+        }
+
+        let router =
+            || crate::router::TaskRouter::empty().with_handler("watchdog_poll", EmptyTaskType);
+        let runner = StubRunner {
+            directives: Vec::new(),
+        };
+        let audit = MemoryAuditSink::default();
+        let runtime = OrchestratorRuntime::new(
+            StaticPolicyEngine::default(),
+            audit.clone(),
+            crate::DryRunExecutor,
+        )
+        .with_task_router(router());
+        let outcomes = runtime
+            .handle_watchdog_task(&watchdog_task(), &runner, &MemoryIngress::default())
+            .expect("outcomes");
+        assert_eq!(outcomes[0].detail, "event_schema_invalid");
+        assert!(audit.has_event("event.schema.invalid"));
+
+        let flagging = OrchestratorRuntime::new(
+            StaticPolicyEngine::default(),
+            MemoryAuditSink::default(),
+            crate::DryRunExecutor,
+        )
+        .with_task_router(router())
+        .with_event_validation(crate::EventValidation::Flag);
+        let outcomes = flagging
+            .handle_watchdog_task(&watchdog_task(), &runner, &MemoryIngress::default())
+            .expect("outcomes");
+        assert!(outcomes.is_empty());
+    }
+
+    #[test]
+    fn observe_only_plugin_cannot_mutate_despite_grant() {
+        let mut policy = StaticPolicyEngine::default();
+        policy.allow_capability("example.safe-github", "demo", "repo.read");
+        policy.allow_capability("example.safe-github", "demo", "repo.write");
+        let runtime =
+            OrchestratorRuntime::new(policy, MemoryAuditSink::default(), crate::DryRunExecutor)
+                .with_observe_only_plugins(["example.safe-github"]);
+
+        let read = runtime.handle_action(request()).expect("outcome");
+        assert_eq!(read.status, odin_plugin_protocol::ActionStatus::Executed);
+
+        let mut write = request();
+        write.capability.capability = "repo.write".to_string();
+        let outcome = runtime.handle_action(write).expect("outcome");
+        assert_eq!(outcome.status, odin_plugin_protocol::ActionStatus::Blocked);
+        assert_eq!(outcome.detail, "observe_only_plugin_mutation");
+    }
+
+    #[test]
+    fn retry_policy_recovers_transient_failures() {
+        let mut policy = StaticPolicyEngine::default();
+        policy.allow_capability("example.safe-github", "demo", "repo.read");
+
+        let executor = FlakyExecutor {
+            failures_left: Mutex::new(2),
+        };
+        let runtime = OrchestratorRuntime::new(policy, MemoryAuditSink::default(), executor)
+            .with_retry_policy(super::RetryPolicy::new(
+                3,
+                std::time::Duration::from_millis(1),
+            ));
+        let outcome = runtime.handle_action(request()).expect("outcome");
+        assert_eq!(outcome.status, odin_plugin_protocol::ActionStatus::Executed);
+    }
+
+    #[test]
+    fn destructive_actions_are_not_retried_unless_opted_in() {
+        let runtime = |retry: super::RetryPolicy| {
+            let executor = FlakyExecutor {
+                failures_left: Mutex::new(1),
+            };
+            OrchestratorRuntime::new(
+                StaticPolicyEngine::default(),
+                MemoryAuditSink::default(),
+                executor,
+            )
+            .with_retry_policy(retry)
+        };
+        let mut destructive = request();
+        destructive.risk_tier = RiskTier::Destructive;
+        let retry = super::RetryPolicy::new(3, std::time::Duration::ZERO);
+
+        let err = runtime(retry.clone())
+            .execute_with_retry(&destructive)
+            .expect_err("no retry");
+        assert_eq!(err.0, 1);
+        assert!(runtime(retry.clone())
+            .execute_with_retry(&request())
+            .is_ok());
+        assert!(
+            runtime(retry.with_retried_tiers([RiskTier::Safe, RiskTier::Destructive]))
+                .execute_with_retry(&destructive)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn retry_backoff_doubles_and_caps() {
+        let retry = super::RetryPolicy::new(5, std::time::Duration::from_millis(10))
+            .with_max_backoff(std::time::Duration::from_millis(25));
+        assert_eq!(retry.backoff_for(1), std::time::Duration::from_millis(10));
+        assert_eq!(retry.backoff_for(2), std::time::Duration::from_millis(20));
+        assert_eq!(retry.backoff_for(3), std::time::Duration::from_millis(25));
+    }
+
+    #[test]
+    fn failed_capability_does_not_abort_remaining_directives() {
+        let mut policy = StaticPolicyEngine::default();
+        policy.allow_capability("private.ops-watchdog", "private", "monitoring.sentry.read");
+        policy.allow_capability("private.ops-watchdog", "private", "task.enqueue");
+
+        let runtime = OrchestratorRuntime::new(policy, MemoryAuditSink::default(), FailingExecutor);
+        let ingress = MemoryIngress::default();
+        let runner = StubRunner {
+            directives: vec![
+                PluginDirective::RequestCapability {
+                    capability: PluginCapabilityRef {
+                        id: "monitoring.sentry.read".to_string(),
+                        project: None,
+                    },
+                    reason: "poll sentry".to_string(),
+                    input: serde_json::Value::Null,
+                    risk_tier: None,
+                },
+                PluginDirective::EnqueueTask {
+                    task_type: "watchdog.remediation.dispatch".to_string(),
+                    project: None,
+                    reason: None,
+                    payload: serde_json::Value::Null,
+                },
+            ],
+        };
+
+        let outcomes = runtime
+            .handle_watchdog_task(&watchdog_task(), &runner, &ingress)
+            .expect("watchdog outcome");
+        assert_eq!(
+            outcomes[0].status,
+            odin_plugin_protocol::ActionStatus::Failed
+        );
+        assert_eq!(
+            outcomes[1].status,
+            odin_plugin_protocol::ActionStatus::Executed
+        );
+        assert_eq!(ingress.0.lock().expect("lock").len(), 1);
+    }
+
+    #[test]
+    fn scope_query_directive_returns_envelope_scopes_only_when_granted() {
+        let mut policy = StaticPolicyEngine::default();
+        policy.allow_capability("private.ops-watchdog", "private", "browser.observe");
+        let permissions = odin_governance::plugins::PluginPermissionRegistry::from_envelopes([
+            odin_plugin_protocol::PluginPermissionEnvelope {
+                plugin: "private.ops-watchdog".to_string(),
+                trust_level: odin_plugin_protocol::TrustLevel::Caution,
+                permissions: vec![
+                    odin_plugin_protocol::DelegationCapability {
+                        id: "browser.observe".to_string(),
+                        scope: vec!["sentry.io".to_string(), "github.com".to_string()],
+                    },
+                    odin_plugin_protocol::DelegationCapability {
+                        id: "workspace.read".to_string(),
+                        scope: vec!["/srv/private".to_string()],
+                    },
+                ],
+            },
+        ]);
+        let audit = MemoryAuditSink::default();
+        let runtime = OrchestratorRuntime::new(policy, audit.clone(), super::DryRunExecutor)
+            .with_permission_registry(permissions);
+        let runner = StubRunner {
+            directives: vec![
+                PluginDirective::QueryScopes {
+                    capability: "browser.observe".to_string(),
+                    project: None,
+                },
+                PluginDirective::QueryScopes {
+                    capability: "workspace.read".to_string(),
+                    project: None,
+                },
+            ],
+        };
+
+        let outcomes = runtime
+            .handle_watchdog_task(&watchdog_task(), &runner, &MemoryIngress::default())
+            .expect("outcomes");
+        assert_eq!(outcomes[0].detail, "scopes_listed");
+        assert_eq!(outcomes[0].output["granted"], true);
+        assert_eq!(
+            outcomes[0].output["scopes"],
+            serde_json::json!(["sentry.io", "github.com"])
+        );
+        assert_eq!(outcomes[1].output["granted"], false);
+        assert_eq!(outcomes[1].output["reason_code"], "capability_not_granted");
+        assert_eq!(outcomes[1].output["scopes"], serde_json::json!([]));
+        assert!(audit.has_event("capability.scopes.queried"));
+    }
+
+    #[test]
+    fn cancellation_stops_before_next_directive_and_keeps_partial_outcomes() {
+        struct CancellingExecutor(crate::cancel::CancellationToken);
+
+        impl ActionExecutor for CancellingExecutor {
+            fn execute(&self, _request: &ActionRequest) -> Result<serde_json::Value, RuntimeError> {
+                self.0.cancel();
+                Ok(serde_json::Value::Null)
+            }
+        }
+
+        let mut policy = StaticPolicyEngine::default();
+        policy.allow_capability("private.ops-watchdog", "private", "monitoring.sentry.read");
+        policy.allow_capability("private.ops-watchdog", "private", "task.enqueue");
+        let token = crate::cancel::CancellationToken::new();
+        let audit = MemoryAuditSink::default();
+        let runtime =
+            OrchestratorRuntime::new(policy, audit.clone(), CancellingExecutor(token.clone()))
+                .with_cancellation(token);
+        let ingress = MemoryIngress::default();
+        let runner = StubRunner {
+            directives: vec![
+                PluginDirective::RequestCapability {
+                    capability: PluginCapabilityRef {
+                        id: "monitoring.sentry.read".to_string(),
+                        project: None,
+                    },
+                    reason: "poll sentry".to_string(),
+                    input: serde_json::Value::Null,
+                    risk_tier: None,
+                },
+                PluginDirective::EnqueueTask {
+                    task_type: "watchdog.remediation.dispatch".to_string(),
+                    project: None,
+                    reason: None,
+                    payload: serde_json::Value::Null,
+                },
+                PluginDirective::Noop,
+            ],
+        };
+
+        let outcomes = runtime
+            .handle_watchdog_task(&watchdog_task(), &runner, &ingress)
+            .expect("partial outcomes");
+        assert_eq!(outcomes.len(), 2);
+        assert_eq!(
+            outcomes[0].status,
+            odin_plugin_protocol::ActionStatus::Executed
+        );
+        assert_eq!(outcomes[1].detail, "runtime_cancelled");
+        assert_eq!(outcomes[1].output["skipped"], 2);
+        assert!(ingress.0.lock().expect("lock").is_empty());
+
+        let outcomes = runtime
+            .handle_watchdog_task(&watchdog_task(), &runner, &ingress)
+            .expect("cancelled before dispatch");
+        assert_eq!(outcomes[0].request_id, "watchdog-poll-sentry-123-dispatch");
+        assert_eq!(outcomes[0].detail, "runtime_cancelled");
+
+        runtime.shutdown("test").expect("shutdown");
+        assert!(audit.has_event("task.cancelled"));
+        assert!(audit.has_event("runtime.shutdown"));
+    }
+
+    #[derive(Default)]
+    struct ConcurrencyProbeExecutor {
+        active: std::sync::atomic::AtomicUsize,
+        peak: std::sync::atomic::AtomicUsize,
+    }
+
+    impl ActionExecutor for ConcurrencyProbeExecutor {
+        fn execute(&self, request: &ActionRequest) -> Result<serde_json::Value, RuntimeError> {
+            use std::sync::atomic::Ordering;
+            let active = self.active.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(active, Ordering::SeqCst);
+            std::thread::sleep(std::time::Duration::from_millis(50));
+            self.active.fetch_sub(1, Ordering::SeqCst);
+            Ok(serde_json::json!({ "request_id": request.request_id }))
+        }
+    }
+
+    #[test]
+    fn parallel_capabilities_respect_limit_and_keep_order() {
+        let mut policy = StaticPolicyEngine::default();
+        policy.allow_capability("private.ops-watchdog", "private", "monitoring.sentry.read");
+        policy.allow_capability("private.ops-watchdog", "private", "task.enqueue");
+        let audit = MemoryAuditSink::default();
+        let runtime =
+            OrchestratorRuntime::new(policy, audit.clone(), ConcurrencyProbeExecutor::default())
+                .with_directive_execution(super::DirectiveExecution::Parallel {
+                    max_concurrency: 2,
+                });
+
+        let capability = PluginDirective::RequestCapability {
+            capability: PluginCapabilityRef {
+                id: "monitoring.sentry.read".to_string(),
+                project: None,
+            },
+            reason: "poll sentry".to_string(),
+            input: serde_json::Value::Null,
+            risk_tier: None,
+        };
+        let mut directives = vec![capability.clone(); 4];
+        directives.push(PluginDirective::EnqueueTask {
+            task_type: "watchdog.remediation.dispatch".to_string(),
+            project: None,
+            reason: None,
+            payload: serde_json::Value::Null,
+        });
+        directives.push(capability);
+        let runner = StubRunner { directives };
+
+        let outcomes = runtime
+            .handle_watchdog_task(&watchdog_task(), &runner, &MemoryIngress::default())
+            .expect("watchdog outcome");
+
+        let ids: Vec<&str> = outcomes.iter().map(|o| o.request_id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                "watchdog-poll-sentry-123-0-cap",
+                "watchdog-poll-sentry-123-1-cap",
+                "watchdog-poll-sentry-123-2-cap",
+                "watchdog-poll-sentry-123-3-cap",
+                "watchdog-poll-sentry-123-4-enqueue",
+                "watchdog-poll-sentry-123-5-cap",
+            ]
+        );
+        assert!(outcomes
+            .iter()
+            .all(|o| o.status == odin_plugin_protocol::ActionStatus::Executed));
+        let peak = runtime
+            .executor
+            .peak
+            .load(std::sync::atomic::Ordering::SeqCst);
+        assert_eq!(peak, 2, "parallel batch should saturate the limit");
+        let executed = audit
+            .0
+            .lock()
+            .expect("lock")
+            .iter()
+            .filter(|record| record.event_type == "action.executed")
+            .count();
+        assert_eq!(executed, 5);
+    }
+
+    #[test]
+    fn runaway_capability_requests_are_rate_limited() {
+        let mut policy = StaticPolicyEngine::default();
+        policy.allow_capability("private.ops-watchdog", "private", "monitoring.sentry.read");
+        let audit = MemoryAuditSink::default();
+        let runtime = OrchestratorRuntime::new(policy, audit.clone(), super::DryRunExecutor)
+            .with_rate_limits(super::ratelimit::RateLimitConfig {
+                default: None,
+                rules: vec![super::ratelimit::RateLimitRule {
+                    plugin: "private.ops-watchdog".to_string(),
+                    capability: "*".to_string(),
+                    project: "*".to_string(),
+                    limit: super::ratelimit::RateLimit {
+                        capacity: 2,
+                        refill_per_sec: 0.0,
+                    },
+                }],
+            });
+        let capability = PluginDirective::RequestCapability {
+            capability: PluginCapabilityRef {
+                id: "monitoring.sentry.read".to_string(),
+                project: None,
+            },
+            reason: "poll sentry".to_string(),
+            input: serde_json::Value::Null,
+            risk_tier: None,
+        };
+        let runner = StubRunner {
+            directives: vec![capability; 3],
+        };
+
+        let outcomes = runtime
+            .handle_watchdog_task(&watchdog_task(), &runner, &MemoryIngress::default())
+            .expect("watchdog outcome");
+
+        let details: Vec<&str> = outcomes.iter().map(|o| o.detail.as_str()).collect();
+        assert_eq!(details, vec!["executed", "executed", "rate_limited"]);
+        assert_eq!(
+            outcomes[2].status,
+            odin_plugin_protocol::ActionStatus::Blocked
+        );
+        assert!(audit.has_event("action.intercepted"));
+    }
+
+    #[test]
+    fn watchdog_request_capability_routed() {
+        let mut policy = StaticPolicyEngine::default();
+        policy.allow_capability("private.ops-watchdog", "private", "monitoring.sentry.read");
+
+        let audit = MemoryAuditSink::default();
+        let runtime = OrchestratorRuntime::new(policy, audit.clone(), super::DryRunExecutor);
+        let ingress = MemoryIngress::default();
+        let runner = StubRunner {
+            directives: vec![PluginDirective::RequestCapability {
+                capability: PluginCapabilityRef {
+                    id: "monitoring.sentry.read".to_string(),
+                    project: None,
+                },
+                reason: "poll sentry".to_string(),
+                input: serde_json::json!({"sample": true}),
+                risk_tier: Some(RiskTier::Safe),
+            }],
+        };
+
+        let outcomes = runtime
+            .handle_watchdog_task(&watchdog_task(), &runner, &ingress)
+            .expect("watchdog outcome");
+        assert_eq!(outcomes.len(), 1);
+        assert_eq!(
+            outcomes[0].status,
+            odin_plugin_protocol::ActionStatus::Executed
+        );
+        assert_eq!(
+            outcomes[0]
+                .output
+                .get("capability")
+                .and_then(|v| v.as_str()),
+            Some("monitoring.sentry.read")
+        );
+        assert!(audit.has_event("governance.manifest.validated"));
+    }
+
+    #[test]
+    fn watchdog_enqueue_task_writes_ingress() {
+        let mut policy = StaticPolicyEngine::default();
+        policy.allow_capability("private.ops-watchdog", "private", "task.enqueue");
+
+        let runtime =
+            OrchestratorRuntime::new(policy, MemoryAuditSink::default(), super::DryRunExecutor);
+        let ingress = MemoryIngress::default();
+        let runner = StubRunner {
+            directives: vec![PluginDirective::EnqueueTask {
+                task_type: "watchdog.remediation.dispatch".to_string(),
+                project: None,
+                reason: Some("queue remediation".to_string()),
+                payload: serde_json::json!({"kind": "critical_issue"}),
+            }],
+        };
+
+        let outcomes = runtime
+            .handle_watchdog_task(&watchdog_task(), &runner, &ingress)
+            .expect("watchdog outcome");
+        assert_eq!(outcomes.len(), 1);
+        assert_eq!(
+            outcomes[0].status,
+            odin_plugin_protocol::ActionStatus::Executed
+        );
+
+        let writes = ingress.0.lock().expect("lock");
+        assert_eq!(writes.len(), 1);
+        let queued: serde_json::Value = serde_json::from_str(&writes[0]).expect("queued json");
+        assert_eq!(
+            queued.get("type").and_then(|v| v.as_str()),
+            Some("watchdog.remediation.dispatch")
+        );
+    }
+
+    #[derive(Default)]
+    struct RecordingRunner(Mutex<Vec<odin_plugin_protocol::EventEnvelope>>);
+
+    impl PluginEventRunner for RecordingRunner {
+        fn dispatch_event(
+            &self,
+            _plugin: &str,
+            event: &odin_plugin_protocol::EventEnvelope,
+        ) -> Result<Vec<PluginDirective>, RuntimeError> {
+            self.0.lock().expect("lock").push(event.clone());
+            Ok(Vec::new())
+        }
+    }
+
+    #[test]
+    fn enqueued_followup_is_consumed_through_task_router() {
+        let mut policy = StaticPolicyEngine::default();
+        policy.allow_capability("private.ops-watchdog", "private", "task.enqueue");
+        let ingress = MemoryIngress::default();
+        let runner = StubRunner {
+            directives: vec![PluginDirective::EnqueueTask {
+                task_type: "watchdog.remediation.dispatch".to_string(),
+                project: None,
+                reason: None,
+                payload: serde_json::json!({"issue": "SENTRY-1"}),
+            }],
+        };
+
+        let runtime =
+            OrchestratorRuntime::new(policy, MemoryAuditSink::default(), super::DryRunExecutor);
+        runtime
+            .handle_task(&watchdog_task(), &runner, &ingress)
+            .expect("watchdog outcome");
+        let followup = ingress.0.lock().expect("lock")[0].clone();
+
+        let err = runtime
+            .handle_task(&followup, &RecordingRunner::default(), &ingress)
+            .expect_err("followup kind not registered by default");
+        assert!(err.to_string().contains("unsupported task type"));
+
+        let runtime = runtime.with_task_router(super::router::TaskRouter::default().with_handler(
+            "watchdog.remediation.dispatch",
+            super::router::PluginTaskHandler,
+        ));
+        let recording = RecordingRunner::default();
+        let outcomes = runtime
+            .handle_task(&followup, &recording, &ingress)
+            .expect("followup outcome");
+        assert!(outcomes.is_empty());
+
+        let events = recording.0.lock().expect("lock");
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_type, "task.received");
+        assert_eq!(
+            events[0].payload["task_kind"],
+            "watchdog.remediation.dispatch"
+        );
+        assert_eq!(events[0].payload["data"]["issue"], "SENTRY-1");
+        assert_eq!(
+            events[0].payload["origin_task_id"],
+            "watchdog-poll-sentry-123"
+        );
+    }
+
+    #[test]
+    fn interrupted_task_is_checkpointed_and_reconciled_on_restart() {
+        use crate::approvals::ApprovalStore;
+        use crate::checkpoint::CheckpointStore;
+
+        struct CancellingExecutor(crate::cancel::CancellationToken);
+
+        impl ActionExecutor for CancellingExecutor {
+            fn execute(&self, _request: &ActionRequest) -> Result<serde_json::Value, RuntimeError> {
+                self.0.cancel();
+                Ok(serde_json::Value::Null)
+            }
+        }
+
+        let dir = std::env::temp_dir().join(format!(
+            "odin-reconcile-{}-{}",
+            std::process::id(),
+            super::now_unix()
+        ));
+        let mut policy = StaticPolicyEngine::default();
+        policy.allow_capability("private.ops-watchdog", "private", "monitoring.sentry.read");
+        let token = crate::cancel::CancellationToken::new();
+        let runner = StubRunner {
+            directives: vec![
+                PluginDirective::RequestCapability {
+                    capability: PluginCapabilityRef {
+                        id: "monitoring.sentry.read".to_string(),
+                        project: None,
+                    },
+                    reason: "poll sentry".to_string(),
+                    input: serde_json::Value::Null,
+                    risk_tier: None,
+                },
+                PluginDirective::Noop,
+            ],
+        };
+        OrchestratorRuntime::new(
+            policy.clone(),
+            MemoryAuditSink::default(),
+            CancellingExecutor(token.clone()),
+        )
+        .with_checkpoint_store(crate::checkpoint::FileCheckpointStore::new(&dir))
+        .with_cancellation(token)
+        .handle_task(&watchdog_task(), &runner, &MemoryIngress::default())
+        .expect("partial outcomes");
+
+        let open = crate::checkpoint::FileCheckpointStore::new(&dir)
+            .list()
+            .expect("list");
+        assert_eq!(open.len(), 1);
+        assert!(open[0].raw_task.contains("trace_id"));
+
+        let approvals = crate::approvals::InMemoryApprovalStore::default();
+        approvals
+            .save(&crate::approvals::PendingApproval {
+                request: request(),
+                reason_code: "destructive_requires_approval".to_string(),
+                created_at_unix: 1,
+                expires_at_unix: 2,
+            })
+            .expect("save approval");
+        let audit = MemoryAuditSink::default();
+        let ingress = MemoryIngress::default();
+        let runtime = OrchestratorRuntime::new(policy, audit.clone(), super::DryRunExecutor)
+            .with_checkpoint_store(crate::checkpoint::FileCheckpointStore::new(&dir))
+            .with_approval_store(approvals);
+        let report = runtime
+            .reconcile(
+                crate::checkpoint::ReconcilePolicy::Requeue,
+                &runner,
+                &ingress,
+            )
+            .expect("reconcile");
+
+        assert_eq!(report.requeued, vec!["watchdog-poll-sentry-123"]);
+        assert_eq!(report.approvals_expired, vec![request().request_id]);
+        assert_eq!(ingress.0.lock().expect("lock")[0], open[0].raw_task);
+        assert!(audit.has_event("runtime.reconciled"));
+
+        let report = runtime
+            .reconcile(
+                crate::checkpoint::ReconcilePolicy::Resume,
+                &runner,
+                &ingress,
+            )
+            .expect("reconcile again");
+        assert_eq!(
+            report.summary(),
+            "0 resumed, 0 requeued, 0 quarantined, 0 approvals pending, 0 approvals expired"
+        );
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn recover_resumes_a_task_from_its_checkpointed_directive() {
+        use crate::checkpoint::CheckpointStore;
+
+        struct CancellingExecutor(crate::cancel::CancellationToken);
+
+        impl ActionExecutor for CancellingExecutor {
+            fn execute(&self, _request: &ActionRequest) -> Result<serde_json::Value, RuntimeError> {
+                self.0.cancel();
+                Ok(serde_json::Value::Null)
+            }
+        }
+
+        #[derive(Clone, Default)]
+        struct RecordingExecutor(Arc<Mutex<Vec<String>>>);
+
+        impl ActionExecutor for RecordingExecutor {
+            fn execute(&self, request: &ActionRequest) -> Result<serde_json::Value, RuntimeError> {
+                self.0
+                    .lock()
+                    .expect("lock")
+                    .push(request.request_id.clone());
+                Ok(serde_json::Value::Null)
+            }
+        }
+
+        let dir = std::env::temp_dir().join(format!(
+            "odin-recover-{}-{}",
+            std::process::id(),
+            super::now_unix()
+        ));
+        let mut policy = StaticPolicyEngine::default();
+        policy.allow_capability("private.ops-watchdog", "private", "monitoring.sentry.read");
+        let read = PluginDirective::RequestCapability {
+            capability: PluginCapabilityRef {
+                id: "monitoring.sentry.read".to_string(),
+                project: None,
+            },
+            reason: "poll sentry".to_string(),
+            input: serde_json::Value::Null,
+            risk_tier: None,
+        };
+        let token = crate::cancel::CancellationToken::new();
+        OrchestratorRuntime::new(
+            policy.clone(),
+            MemoryAuditSink::default(),
+            CancellingExecutor(token.clone()),
+        )
+        .with_checkpoint_store(crate::checkpoint::FileCheckpointStore::new(&dir))
+        .with_cancellation(token)
+        .handle_task(
+            &watchdog_task(),
+            &StubRunner {
+                directives: vec![read.clone(), read.clone(), read],
+            },
+            &MemoryIngress::default(),
+        )
+        .expect("partial outcomes");
+
+        let store = crate::checkpoint::FileCheckpointStore::new(&dir);
+        let open = store.list().expect("list");
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].directive_index, 1);
+        assert_eq!(open[0].outcomes.len(), 1);
+        assert_eq!(open[0].directives.as_ref().map(Vec::len), Some(3));
+
+        // The restarted plugin would return nothing; recovery must use the saved directives.
+        let executor = RecordingExecutor::default();
+        let audit = MemoryAuditSink::default();
+        let report = OrchestratorRuntime::new(policy, audit.clone(), executor.clone())
+            .with_checkpoint_store(crate::checkpoint::FileCheckpointStore::new(&dir))
+            .recover(
+                &StubRunner {
+                    directives: Vec::new(),
+                },
+                &MemoryIngress::default(),
+            )
+            .expect("recover");
+
+        assert_eq!(report.resumed, vec!["watchdog-poll-sentry-123"]);
+        assert_eq!(
+            *executor.0.lock().expect("lock"),
+            vec![
+                "watchdog-poll-sentry-123-1-cap".to_string(),
+                "watchdog-poll-sentry-123-2-cap".to_string()
+            ]
+        );
+        assert!(audit.has_event("task.resumed"));
+        assert!(store.list().expect("list").is_empty());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn failed_tasks_are_dead_lettered_and_can_be_retried() {
+        struct BrokenRunner;
+
+        impl PluginEventRunner for BrokenRunner {
+            fn dispatch_event(
+                &self,
+                plugin: &str,
+                _event: &odin_plugin_protocol::EventEnvelope,
+            ) -> Result<Vec<PluginDirective>, RuntimeError> {
+                Err(RuntimeError::Plugin(format!("{plugin} crashed")))
+            }
+        }
+
+        let audit = MemoryAuditSink::default();
+        let runtime = OrchestratorRuntime::new(
+            StaticPolicyEngine::default(),
+            audit.clone(),
+            super::DryRunExecutor,
+        )
+        .with_dead_letter_queue(crate::dlq::InMemoryDeadLetterQueue::default());
+        let ingress = MemoryIngress::default();
+
+        assert!(runtime
+            .handle_task("{not json", &BrokenRunner, &ingress)
+            .is_err());
+        assert!(runtime
+            .handle_task(&watchdog_task(), &BrokenRunner, &ingress)
+            .is_err());
+        assert!(audit.has_event("task.dead_lettered"));
+
+        let entries = runtime.dead_letters().expect("list");
+        let reasons: Vec<(&str, Option<&str>)> = entries
+            .iter()
+            .map(|entry| (entry.reason.as_str(), entry.task_id.as_deref()))
+            .collect();
+        assert_eq!(
+            reasons,
+            vec![
+                ("invalid_task", None),
+                ("plugin_failed", Some("watchdog-poll-sentry-123"))
+            ]
+        );
+
+        let malformed = &entries[0].id;
+        assert!(runtime
+            .retry_dead_letter(malformed, &BrokenRunner, &ingress)
+            .is_err());
+        assert_eq!(
+            runtime
+                .dead_letters()
+                .expect("list")
+                .iter()
+                .find(|entry| &entry.id == malformed)
+                .map(|entry| entry.attempts),
+            Some(2)
+        );
 
-    impl TaskIngress for MemoryIngress {
-        fn write_task_payload(&self, payload: &str) -> Result<(), RuntimeError> {
-            self.0
-                .lock()
-                .map_err(|_| RuntimeError::Execution("poisoned lock".to_string()))?
-                .push(payload.to_string());
-            Ok(())
-        }
+        let outcomes = runtime
+            .retry_dead_letter(
+                &entries[1].id,
+                &StubRunner {
+                    directives: vec![PluginDirective::Noop],
+                },
+                &ingress,
+            )
+            .expect("retry succeeds");
+        assert!(outcomes.is_empty());
+        assert_eq!(runtime.dead_letters().expect("list").len(), 1);
+        assert!(audit.has_event("task.dead_letter.retried"));
     }
 
-    #[derive(Clone)]
-    struct StubRunner {
-        directives: Vec<PluginDirective>,
-    }
+    #[test]
+    fn task_batch_summarizes_each_payload_in_order() {
+        let audit = MemoryAuditSink::default();
+        let runtime = OrchestratorRuntime::new(
+            StaticPolicyEngine::default(),
+            audit.clone(),
+            super::DryRunExecutor,
+        );
+        let second =
+            watchdog_task().replace("watchdog-poll-sentry-123", "watchdog-poll-sentry-456");
+        let raw_tasks = [watchdog_task(), "{not json".to_string(), second];
+        let raw_tasks: Vec<&str> = raw_tasks.iter().map(String::as_str).collect();
 
-    impl PluginEventRunner for StubRunner {
-        fn dispatch_event(
-            &self,
-            _plugin: &str,
-            _event: &odin_plugin_protocol::EventEnvelope,
-        ) -> Result<Vec<PluginDirective>, RuntimeError> {
-            Ok(self.directives.clone())
-        }
+        let summary = runtime
+            .handle_task_batch(
+                &raw_tasks,
+                &StubRunner {
+                    directives: vec![PluginDirective::Noop],
+                },
+                &MemoryIngress::default(),
+                2,
+            )
+            .expect("batch summary");
+
+        assert_eq!(
+            (summary.total, summary.succeeded, summary.failed),
+            (3, 2, 1)
+        );
+        let task_ids: Vec<Option<&str>> = summary
+            .tasks
+            .iter()
+            .map(|task| task.task_id.as_deref())
+            .collect();
+        assert_eq!(
+            task_ids,
+            vec![
+                Some("watchdog-poll-sentry-123"),
+                None,
+                Some("watchdog-poll-sentry-456")
+            ]
+        );
+        assert!(summary.tasks[1]
+            .error
+            .as_deref()
+            .is_some_and(|error| error.contains("invalid watchdog task JSON")));
+        assert!(audit.has_event("task.batch.completed"));
     }
 
-    struct FailingExecutor;
+    #[test]
+    fn metrics_count_outcomes_and_dispatch_failures() {
+        struct BrokenRunner;
 
-    impl ActionExecutor for FailingExecutor {
-        fn execute(&self, _request: &ActionRequest) -> Result<serde_json::Value, RuntimeError> {
-            Err(RuntimeError::Execution("boom".to_string()))
+        impl PluginEventRunner for BrokenRunner {
+            fn dispatch_event(
+                &self,
+                _plugin: &str,
+                _event: &odin_plugin_protocol::EventEnvelope,
+            ) -> Result<Vec<PluginDirective>, RuntimeError> {
+                Err(RuntimeError::Plugin("boom".to_string()))
+            }
         }
-    }
 
-    fn request() -> ActionRequest {
-        ActionRequest {
-            request_id: "req-1".to_string(),
-            risk_tier: RiskTier::Safe,
-            capability: CapabilityRequest {
-                plugin: "example.safe-github".to_string(),
-                project: "demo".to_string(),
-                capability: "repo.read".to_string(),
-                scope: vec!["project".to_string()],
-                reason: "unit test".to_string(),
+        let capability = |id: &str| PluginDirective::RequestCapability {
+            capability: PluginCapabilityRef {
+                id: id.to_string(),
+                project: None,
             },
+            reason: "poll".to_string(),
             input: serde_json::Value::Null,
-        }
-    }
+            risk_tier: None,
+        };
+        let mut policy = StaticPolicyEngine::default();
+        policy.allow_capability("private.ops-watchdog", "private", "monitoring.sentry.read");
+        let metrics = Arc::new(crate::metrics::InMemoryRuntimeMetrics::default());
+        let runtime =
+            OrchestratorRuntime::new(policy, MemoryAuditSink::default(), super::DryRunExecutor)
+                .with_metrics(metrics.clone());
 
-    fn watchdog_task() -> String {
-        serde_json::json!({
-            "schema_version": 1,
-            "task_id": "watchdog-poll-sentry-123",
-            "type": "watchdog_poll",
-            "source": "keepalive",
-            "created_at": "2026-02-25T00:00:00Z",
-            "payload": {
-                "task_type": "watchdog.sentry.poll",
-                "source_key": "sentry-check",
-                "project": "private",
-                "plugin": "private.ops-watchdog",
-                "trigger": "feature_gate"
-            }
-        })
-        .to_string()
+        runtime
+            .handle_task(
+                &watchdog_task(),
+                &StubRunner {
+                    directives: vec![
+                        capability("monitoring.sentry.read"),
+                        capability("vcs.pr.read"),
+                    ],
+                },
+                &MemoryIngress::default(),
+            )
+            .expect("outcomes");
+        assert!(runtime
+            .handle_task(&watchdog_task(), &BrokenRunner, &MemoryIngress::default())
+            .is_err());
+
+        let snapshot = runtime.metrics_snapshot().expect("metrics configured");
+        assert_eq!(
+            snapshot.counters["odin_actions_executed_total{capability=\"monitoring.sentry.read\"}"],
+            1
+        );
+        assert_eq!(snapshot.counter_total(crate::metrics::ACTIONS_BLOCKED), 1);
+        assert_eq!(
+            snapshot.counter_total(crate::metrics::PLUGIN_DISPATCH_FAILURES),
+            1
+        );
+        assert_eq!(
+            snapshot.histograms["odin_action_duration_ms{capability=\"monitoring.sentry.read\"}"]
+                .count,
+            1
+        );
+        assert_eq!(
+            snapshot
+                .histograms
+                .keys()
+                .filter(|key| key.starts_with(crate::metrics::PLUGIN_DISPATCH_DURATION))
+                .count(),
+            1
+        );
     }
 
     #[test]
-    fn blocked_when_not_granted() {
+    fn version_lookup_failures_fail_the_dispatch_not_the_task() {
+        struct UnversionedRunner;
+
+        impl PluginEventRunner for UnversionedRunner {
+            fn dispatch_event(
+                &self,
+                _plugin: &str,
+                _event: &odin_plugin_protocol::EventEnvelope,
+            ) -> Result<Vec<PluginDirective>, RuntimeError> {
+                Ok(vec![PluginDirective::Noop])
+            }
+
+            fn version_info(
+                &self,
+                _plugin: &str,
+            ) -> Result<Option<crate::versioning::PluginVersionInfo>, RuntimeError> {
+                Err(RuntimeError::Plugin("manifest unreadable".to_string()))
+            }
+        }
+
+        let metrics = Arc::new(crate::metrics::InMemoryRuntimeMetrics::default());
         let runtime = OrchestratorRuntime::new(
             StaticPolicyEngine::default(),
             MemoryAuditSink::default(),
             super::DryRunExecutor,
+        )
+        .with_metrics(metrics.clone());
+
+        let outcomes = runtime
+            .handle_task(
+                &watchdog_task(),
+                &UnversionedRunner,
+                &MemoryIngress::default(),
+            )
+            .expect("outcomes");
+        assert_eq!(outcomes.len(), 1);
+        assert_eq!(
+            outcomes[0].status,
+            odin_plugin_protocol::ActionStatus::Failed
+        );
+        assert_eq!(outcomes[0].detail, "plugin_version_unavailable");
+        let snapshot = runtime.metrics_snapshot().expect("metrics configured");
+        assert_eq!(
+            snapshot.counter_total(crate::metrics::PLUGIN_DISPATCH_FAILURES),
+            1
         );
-        let outcome = runtime.handle_action(request()).expect("outcome");
-        assert_eq!(outcome.status, odin_plugin_protocol::ActionStatus::Blocked);
     }
 
     #[test]
-    fn execution_failure_bubbles_up() {
-        let mut policy = StaticPolicyEngine::default();
-        policy.allow_capability("example.safe-github", "demo", "repo.read");
+    fn successful_dispatch_records_one_dispatch_audit() {
+        let audit = MemoryAuditSink::default();
+        let runtime = OrchestratorRuntime::new(
+            StaticPolicyEngine::default(),
+            audit.clone(),
+            super::DryRunExecutor,
+        );
+        let runner = StubRunner {
+            directives: vec![PluginDirective::Noop, PluginDirective::Noop],
+        };
+        runtime
+            .handle_task(&watchdog_task(), &runner, &MemoryIngress::default())
+            .expect("watchdog outcome");
 
-        let runtime = OrchestratorRuntime::new(policy, MemoryAuditSink::default(), FailingExecutor);
-        let err = runtime
-            .handle_action(request())
-            .expect_err("expected execution error");
-        assert!(matches!(err, RuntimeError::Execution(_)));
+        let records = audit.0.lock().expect("lock");
+        let dispatched: Vec<_> = records
+            .iter()
+            .filter(|record| record.event_type.starts_with("plugin.dispatch"))
+            .collect();
+        assert_eq!(dispatched.len(), 1);
+        let completed = dispatched[0];
+        assert_eq!(completed.event_type, "plugin.dispatched");
+        assert_eq!(completed.metadata["plugin"], "private.ops-watchdog");
+        assert_eq!(completed.metadata["directives"], 2);
+        assert!(completed.metadata["duration_ms"].is_u64());
+        assert!(completed.metadata.get("exit_code").is_none());
     }
 
     #[test]
-    fn watchdog_request_capability_routed() {
+    fn trace_id_follows_a_task_through_requests_followups_and_audit() {
         let mut policy = StaticPolicyEngine::default();
+        policy.allow_capability("private.ops-watchdog", "private", "task.enqueue");
         policy.allow_capability("private.ops-watchdog", "private", "monitoring.sentry.read");
-
         let audit = MemoryAuditSink::default();
-        let runtime = OrchestratorRuntime::new(policy, audit.clone(), super::DryRunExecutor);
         let ingress = MemoryIngress::default();
         let runner = StubRunner {
-            directives: vec![PluginDirective::RequestCapability {
-                capability: PluginCapabilityRef {
-                    id: "monitoring.sentry.read".to_string(),
+            directives: vec![
+                PluginDirective::RequestCapability {
+                    capability: PluginCapabilityRef {
+                        id: "monitoring.sentry.read".to_string(),
+                        project: None,
+                    },
+                    reason: String::new(),
+                    input: serde_json::Value::Null,
+                    risk_tier: None,
+                },
+                PluginDirective::EnqueueTask {
+                    task_type: "watchdog.remediation.dispatch".to_string(),
                     project: None,
+                    reason: None,
+                    payload: serde_json::json!({"issue": "SENTRY-1"}),
                 },
-                reason: "poll sentry".to_string(),
-                input: serde_json::json!({"sample": true}),
-                risk_tier: Some(RiskTier::Safe),
-            }],
+            ],
+        };
+
+        let runtime = OrchestratorRuntime::new(policy, audit.clone(), super::DryRunExecutor)
+            .with_task_router(super::router::TaskRouter::default().with_handler(
+                "watchdog.remediation.dispatch",
+                super::router::PluginTaskHandler,
+            ));
+        runtime
+            .handle_task(&watchdog_task(), &runner, &ingress)
+            .expect("watchdog outcome");
+
+        let records = audit.0.lock().expect("lock").clone();
+        let trace_id = records[0].trace_id.clone().expect("trace id assigned");
+        assert!(records
+            .iter()
+            .all(|record| record.trace_id.as_deref() == Some(trace_id.as_str())));
+        assert!(records
+            .iter()
+            .any(|record| record.event_type == "action.executed"));
+
+        let followup = ingress.0.lock().expect("lock")[0].clone();
+        let queued: serde_json::Value = serde_json::from_str(&followup).expect("queued json");
+        assert_eq!(queued["trace_id"], trace_id.as_str());
+
+        let recording = RecordingRunner::default();
+        runtime
+            .handle_task(&followup, &recording, &ingress)
+            .expect("followup outcome");
+        assert_eq!(
+            recording.0.lock().expect("lock")[0].trace_id.as_deref(),
+            Some(trace_id.as_str())
+        );
+    }
+
+    #[test]
+    fn deprecated_capability_warns_and_aggregates() {
+        let mut policy = StaticPolicyEngine::default();
+        policy.allow_capability("private.ops-watchdog", "private", "monitoring.sentry.read");
+        let mut deprecations = odin_governance::deprecations::CapabilityDeprecations::new();
+        deprecations.schedule_sunset(
+            "monitoring.sentry.read",
+            "2026-12-31",
+            Some("monitoring.errors.read".to_string()),
+        );
+
+        let audit = MemoryAuditSink::default();
+        let runtime = OrchestratorRuntime::new(policy, audit.clone(), super::DryRunExecutor)
+            .with_capability_deprecations(deprecations);
+        let directive = PluginDirective::RequestCapability {
+            capability: PluginCapabilityRef {
+                id: "monitoring.sentry.read".to_string(),
+                project: None,
+            },
+            reason: "poll sentry".to_string(),
+            input: serde_json::Value::Null,
+            risk_tier: None,
+        };
+        let runner = StubRunner {
+            directives: vec![directive.clone(), directive],
         };
 
         let outcomes = runtime
-            .handle_watchdog_task(&watchdog_task(), &runner, &ingress)
+            .handle_watchdog_task(&watchdog_task(), &runner, &MemoryIngress::default())
             .expect("watchdog outcome");
-        assert_eq!(outcomes.len(), 1);
+        assert_eq!(outcomes[0].warnings.len(), 1);
         assert_eq!(
-            outcomes[0].status,
-            odin_plugin_protocol::ActionStatus::Executed
+            outcomes[0].warnings[0].replacement.as_deref(),
+            Some("monitoring.errors.read")
         );
+        assert!(audit.has_event("capability.deprecated"));
+        assert_eq!(super::aggregate_warnings(&outcomes).len(), 1);
+    }
+
+    #[test]
+    fn watchdog_emit_event_fans_out_to_subscribers() {
+        let mut policy = StaticPolicyEngine::default();
+        policy.allow_capability("private.ops-watchdog", "private", "event.emit");
+
+        let audit = MemoryAuditSink::default();
+        let runtime = OrchestratorRuntime::new(policy, audit.clone(), super::DryRunExecutor);
+        let runner = fanout_runner(Vec::new());
+
+        let outcomes = runtime
+            .handle_watchdog_task(&watchdog_task(), &runner, &MemoryIngress::default())
+            .expect("watchdog outcome");
+
+        let delivered = runner.delivered.lock().expect("lock");
+        assert!(delivered.contains(&(
+            "private.chat".to_string(),
+            "plugin.sentry.alert".to_string()
+        )));
+        assert!(delivered.contains(&(
+            "private.pager".to_string(),
+            "plugin.sentry.alert".to_string()
+        )));
+        assert!(!delivered
+            .iter()
+            .skip(1)
+            .any(|(plugin, _)| plugin == "private.ops-watchdog"));
+        assert!(audit.has_event("plugin.event.delivered"));
+        // The pager re-emits without an event.emit grant, so its deliveries are blocked.
         assert_eq!(
-            outcomes[0]
-                .output
-                .get("capability")
-                .and_then(|v| v.as_str()),
-            Some("monitoring.sentry.read")
+            outcomes
+                .iter()
+                .filter(|outcome| outcome.detail == "event_delivered")
+                .count(),
+            2
         );
-        assert!(audit.has_event("governance.manifest.validated"));
+        assert!(outcomes
+            .iter()
+            .any(|outcome| outcome.status == odin_plugin_protocol::ActionStatus::Blocked));
     }
 
     #[test]
-    fn watchdog_enqueue_task_writes_ingress() {
+    fn watchdog_emit_event_respects_targets_and_namespace() {
         let mut policy = StaticPolicyEngine::default();
-        policy.allow_capability("private.ops-watchdog", "private", "task.enqueue");
+        policy.allow_capability("private.ops-watchdog", "private", "event.emit");
 
         let runtime =
             OrchestratorRuntime::new(policy, MemoryAuditSink::default(), super::DryRunExecutor);
-        let ingress = MemoryIngress::default();
-        let runner = StubRunner {
-            directives: vec![PluginDirective::EnqueueTask {
-                task_type: "watchdog.remediation.dispatch".to_string(),
-                project: None,
-                reason: Some("queue remediation".to_string()),
-                payload: serde_json::json!({"kind": "critical_issue"}),
+        let runner = fanout_runner(vec!["private.chat".to_string()]);
+        let outcomes = runtime
+            .handle_watchdog_task(&watchdog_task(), &runner, &MemoryIngress::default())
+            .expect("watchdog outcome");
+        assert_eq!(outcomes.len(), 1);
+        assert_eq!(
+            outcomes[0].output.get("target").and_then(|v| v.as_str()),
+            Some("private.chat")
+        );
+
+        let spoofing = StubRunner {
+            directives: vec![PluginDirective::EmitEvent {
+                event_type: "task.received".to_string(),
+                payload: serde_json::Value::Null,
+                targets: Vec::new(),
             }],
         };
+        let err = runtime
+            .handle_watchdog_task(&watchdog_task(), &spoofing, &MemoryIngress::default())
+            .expect_err("core event types are reserved");
+        assert!(matches!(err, RuntimeError::InvalidInput(_)));
+    }
+
+    struct LeasingStore;
+
+    impl odin_secrets::SecretStore for LeasingStore {
+        fn resolve_secret_handle(
+            &self,
+            handle: &odin_secrets::SecretHandle,
+            ctx: &odin_secrets::AccessContext,
+        ) -> Result<odin_secrets::SecretRef, odin_secrets::SecretError> {
+            if handle.0.ends_with("/missing") {
+                return Err(odin_secrets::SecretError::NotFound(handle.0.clone()));
+            }
+            Ok(odin_secrets::SecretRef {
+                handle: odin_secrets::SecretHandle(format!(
+                    "lease://{}/{}",
+                    ctx.plugin, ctx.project
+                )),
+            })
+        }
+    }
+
+    fn secret_runner(handle: &str) -> StubRunner {
+        StubRunner {
+            directives: vec![PluginDirective::RequestSecret {
+                handle: handle.to_string(),
+                reason: "call sentry api".to_string(),
+            }],
+        }
+    }
+
+    #[test]
+    fn watchdog_request_secret_returns_opaque_lease() {
+        let mut policy = StaticPolicyEngine::default();
+        policy.allow_capability("private.ops-watchdog", "private", "secret.read");
+
+        let audit = MemoryAuditSink::default();
+        let runtime = OrchestratorRuntime::new(policy, audit.clone(), super::DryRunExecutor)
+            .with_secret_store(LeasingStore);
 
         let outcomes = runtime
-            .handle_watchdog_task(&watchdog_task(), &runner, &ingress)
+            .handle_watchdog_task(
+                &watchdog_task(),
+                &secret_runner("secret://private/sentry"),
+                &MemoryIngress::default(),
+            )
             .expect("watchdog outcome");
-        assert_eq!(outcomes.len(), 1);
         assert_eq!(
             outcomes[0].status,
             odin_plugin_protocol::ActionStatus::Executed
         );
+        assert_eq!(
+            outcomes[0].output.get("lease").and_then(|v| v.as_str()),
+            Some("lease://private.ops-watchdog/private")
+        );
+        assert!(audit.has_event("secret.leased"));
+    }
 
-        let writes = ingress.0.lock().expect("lock");
-        assert_eq!(writes.len(), 1);
-        let queued: serde_json::Value = serde_json::from_str(&writes[0]).expect("queued json");
+    #[test]
+    fn watchdog_request_secret_requires_policy_grant() {
+        let runtime = OrchestratorRuntime::new(
+            StaticPolicyEngine::default(),
+            MemoryAuditSink::default(),
+            super::DryRunExecutor,
+        )
+        .with_secret_store(LeasingStore);
+
+        let outcomes = runtime
+            .handle_watchdog_task(
+                &watchdog_task(),
+                &secret_runner("secret://private/sentry"),
+                &MemoryIngress::default(),
+            )
+            .expect("watchdog outcome");
         assert_eq!(
-            queued.get("type").and_then(|v| v.as_str()),
-            Some("watchdog.remediation.dispatch")
+            outcomes[0].status,
+            odin_plugin_protocol::ActionStatus::Blocked
+        );
+        assert_eq!(outcomes[0].output, serde_json::Value::Null);
+    }
+
+    #[test]
+    fn watchdog_request_secret_unknown_handle_is_blocked() {
+        let mut policy = StaticPolicyEngine::default();
+        policy.allow_capability("private.ops-watchdog", "private", "secret.read");
+
+        let runtime =
+            OrchestratorRuntime::new(policy, MemoryAuditSink::default(), super::DryRunExecutor)
+                .with_secret_store(LeasingStore);
+
+        let outcomes = runtime
+            .handle_watchdog_task(
+                &watchdog_task(),
+                &secret_runner("secret://private/missing"),
+                &MemoryIngress::default(),
+            )
+            .expect("watchdog outcome");
+        assert_eq!(outcomes[0].detail, "secret_not_found");
+    }
+
+    struct OversizedRunner;
+
+    impl PluginEventRunner for OversizedRunner {
+        fn dispatch_event(
+            &self,
+            plugin: &str,
+            _event: &odin_plugin_protocol::EventEnvelope,
+        ) -> Result<Vec<PluginDirective>, RuntimeError> {
+            Err(RuntimeError::PluginOutputLimitExceeded {
+                plugin: plugin.to_string(),
+                limit_bytes: 16,
+                prefix: "{\"action\":".to_string(),
+            })
+        }
+    }
+
+    #[test]
+    fn watchdog_output_limit_maps_to_failed_outcome() {
+        let audit = MemoryAuditSink::default();
+        let runtime = OrchestratorRuntime::new(
+            StaticPolicyEngine::default(),
+            audit.clone(),
+            super::DryRunExecutor,
+        );
+
+        let outcomes = runtime
+            .handle_watchdog_task(
+                &watchdog_task(),
+                &OversizedRunner,
+                &MemoryIngress::default(),
+            )
+            .expect("watchdog outcome");
+        assert_eq!(outcomes.len(), 1);
+        assert_eq!(
+            outcomes[0].status,
+            odin_plugin_protocol::ActionStatus::Failed
         );
+        assert_eq!(outcomes[0].detail, "plugin_output_limit_exceeded");
+        assert!(audit.has_event("plugin.output.truncated"));
     }
 
     #[test]
@@ -0,0 +1,137 @@
+//! Runtime counters and latency histograms. Series are keyed Prometheus-style as
+//! `name{label="value",...}` so a snapshot can be printed or scraped as-is.
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::Serialize;
+
+pub const ACTIONS_EXECUTED: &str = "odin_actions_executed_total";
+pub const ACTIONS_BLOCKED: &str = "odin_actions_blocked_total";
+pub const ACTIONS_APPROVAL_PENDING: &str = "odin_actions_approval_pending_total";
+pub const ACTIONS_FAILED: &str = "odin_actions_failed_total";
+pub const PLUGIN_DISPATCH_FAILURES: &str = "odin_plugin_dispatch_failures_total";
+/// Time from `handle_action` to its outcome, labeled by capability.
+pub const ACTION_DURATION: &str = "odin_action_duration_ms";
+/// Plugin process round trip, labeled by plugin and plugin version.
+pub const PLUGIN_DISPATCH_DURATION: &str = "odin_plugin_dispatch_duration_ms";
+
+/// Upper bounds (inclusive, in milliseconds) of the histogram buckets.
+pub const DURATION_BUCKETS_MS: [u64; 12] =
+    [1, 5, 10, 25, 50, 100, 250, 500, 1_000, 2_500, 5_000, 10_000];
+
+pub trait RuntimeMetrics: Send + Sync {
+    fn increment_counter(&self, name: &str, labels: &[(&str, &str)]);
+    fn observe_duration(&self, name: &str, labels: &[(&str, &str)], duration: Duration);
+    fn snapshot(&self) -> MetricsSnapshot;
+}
+
+#[derive(Clone, Debug, Default, Serialize, PartialEq, Eq)]
+pub struct HistogramSnapshot {
+    pub count: u64,
+    pub sum_ms: u64,
+    pub max_ms: u64,
+    /// Cumulative counts per entry of `DURATION_BUCKETS_MS`; slower observations only show
+    /// in `count`.
+    pub buckets: Vec<(u64, u64)>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, PartialEq, Eq)]
+pub struct MetricsSnapshot {
+    pub counters: BTreeMap<String, u64>,
+    pub histograms: BTreeMap<String, HistogramSnapshot>,
+}
+
+impl MetricsSnapshot {
+    /// Sum of every series of counter `name`, across label sets.
+    pub fn counter_total(&self, name: &str) -> u64 {
+        self.counters
+            .iter()
+            .filter(|(key, _)| series_name(key) == name)
+            .map(|(_, value)| value)
+            .sum()
+    }
+}
+
+fn series_name(key: &str) -> &str {
+    key.split_once('{').map_or(key, |(name, _)| name)
+}
+
+fn series_key(name: &str, labels: &[(&str, &str)]) -> String {
+    if labels.is_empty() {
+        return name.to_string();
+    }
+    let labels: Vec<String> = labels
+        .iter()
+        .map(|(label, value)| format!("{label}=\"{}\"", value.replace('"', "\\\"")))
+        .collect();
+    format!("{name}{{{}}}", labels.join(","))
+}
+
+#[derive(Debug, Default)]
+pub struct InMemoryRuntimeMetrics {
+    inner: Mutex<MetricsSnapshot>,
+}
+
+impl RuntimeMetrics for InMemoryRuntimeMetrics {
+    fn increment_counter(&self, name: &str, labels: &[(&str, &str)]) {
+        if let Ok(mut inner) = self.inner.lock() {
+            *inner.counters.entry(series_key(name, labels)).or_default() += 1;
+        }
+    }
+
+    fn observe_duration(&self, name: &str, labels: &[(&str, &str)], duration: Duration) {
+        let ms = duration.as_millis() as u64;
+        if let Ok(mut inner) = self.inner.lock() {
+            let histogram = inner
+                .histograms
+                .entry(series_key(name, labels))
+                .or_insert_with(|| HistogramSnapshot {
+                    buckets: DURATION_BUCKETS_MS.iter().map(|le| (*le, 0)).collect(),
+                    ..HistogramSnapshot::default()
+                });
+            histogram.count += 1;
+            histogram.sum_ms += ms;
+            histogram.max_ms = histogram.max_ms.max(ms);
+            for (le, count) in &mut histogram.buckets {
+                if ms <= *le {
+                    *count += 1;
+                }
+            }
+        }
+    }
+
+    fn snapshot(&self) -> MetricsSnapshot {
+        self.inner
+            .lock()
+            .map(|inner| inner.clone())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn histograms_fill_cumulative_buckets() {
+        let metrics = InMemoryRuntimeMetrics::default();
+        let labels = [("capability", "repo.read")];
+        metrics.observe_duration(ACTION_DURATION, &labels, Duration::from_millis(3));
+        metrics.observe_duration(ACTION_DURATION, &labels, Duration::from_millis(40));
+        metrics.increment_counter(ACTIONS_EXECUTED, &labels);
+        metrics.increment_counter(ACTIONS_EXECUTED, &[("capability", "repo.write")]);
+
+        let snapshot = metrics.snapshot();
+        let histogram = &snapshot.histograms["odin_action_duration_ms{capability=\"repo.read\"}"];
+        assert_eq!(
+            (histogram.count, histogram.sum_ms, histogram.max_ms),
+            (2, 43, 40)
+        );
+        assert_eq!(histogram.buckets[0], (1, 0));
+        assert_eq!(histogram.buckets[1], (5, 1));
+        assert_eq!(histogram.buckets[4], (50, 2));
+        assert_eq!(snapshot.counter_total(ACTIONS_EXECUTED), 2);
+    }
+}
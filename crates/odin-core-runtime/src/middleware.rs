@@ -0,0 +1,37 @@
+//! Hook pipeline around `OrchestratorRuntime::handle_action`. Middleware runs in
+//! registration order; a hook returning an outcome short-circuits the action.
+
+use odin_plugin_protocol::{ActionOutcome, ActionRequest, PolicyDecision};
+
+use crate::RuntimeResult;
+
+pub trait ActionMiddleware: Send + Sync {
+    /// Runs before policy evaluation; may rewrite the request.
+    fn before_policy(&self, _request: &mut ActionRequest) -> RuntimeResult<Option<ActionOutcome>> {
+        Ok(None)
+    }
+
+    /// Observes the policy decision. Middleware can block an allowed action by returning
+    /// an outcome but cannot turn a deny into an allow.
+    fn after_decision(
+        &self,
+        _request: &ActionRequest,
+        _decision: &PolicyDecision,
+    ) -> RuntimeResult<Option<ActionOutcome>> {
+        Ok(None)
+    }
+
+    /// Runs immediately before the executor for allowed (or approved) actions.
+    fn before_execute(&self, _request: &ActionRequest) -> RuntimeResult<Option<ActionOutcome>> {
+        Ok(None)
+    }
+
+    /// Runs on the executed or failed outcome before it is returned.
+    fn after_execute(
+        &self,
+        _request: &ActionRequest,
+        _outcome: &mut ActionOutcome,
+    ) -> RuntimeResult<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,225 @@
+//! Token-bucket rate limiting keyed by (plugin, capability, project), applied as
+//! `before_policy` middleware so a runaway plugin is blocked before policy or execution.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use odin_plugin_protocol::{ActionOutcome, ActionRequest, ActionStatus};
+use serde::{Deserialize, Serialize};
+
+use crate::middleware::ActionMiddleware;
+use crate::{RuntimeError, RuntimeResult};
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct RateLimit {
+    /// Burst size: requests allowed back to back from a full bucket.
+    pub capacity: u32,
+    pub refill_per_sec: f64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct RateLimitRule {
+    #[serde(default = "wildcard")]
+    pub plugin: String,
+    #[serde(default = "wildcard")]
+    pub capability: String,
+    #[serde(default = "wildcard")]
+    pub project: String,
+    #[serde(flatten)]
+    pub limit: RateLimit,
+}
+
+impl RateLimitRule {
+    fn matches(&self, plugin: &str, capability: &str, project: &str) -> bool {
+        [
+            (&self.plugin, plugin),
+            (&self.capability, capability),
+            (&self.project, project),
+        ]
+        .iter()
+        .all(|(pattern, value)| *pattern == "*" || pattern == value)
+    }
+}
+
+/// Rules are checked in order and the first match wins; `default` applies when none
+/// match, and without a default unmatched keys are unlimited.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct RateLimitConfig {
+    #[serde(default)]
+    pub default: Option<RateLimit>,
+    #[serde(default)]
+    pub rules: Vec<RateLimitRule>,
+}
+
+impl RateLimitConfig {
+    pub fn limit_for(&self, plugin: &str, capability: &str, project: &str) -> Option<&RateLimit> {
+        self.rules
+            .iter()
+            .find(|rule| rule.matches(plugin, capability, project))
+            .map(|rule| &rule.limit)
+            .or(self.default.as_ref())
+    }
+}
+
+fn wildcard() -> String {
+    "*".to_string()
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+type BucketKey = (String, String, String);
+
+#[derive(Debug)]
+pub struct RateLimiter {
+    config: RateLimitConfig,
+    buckets: Mutex<HashMap<BucketKey, Bucket>>,
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self {
+            config,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Takes a token for the request's key at `now`. Returns `Err(retry_after_secs)`
+    /// when the bucket is empty.
+    pub fn try_acquire(
+        &self,
+        request: &ActionRequest,
+        now: Instant,
+    ) -> RuntimeResult<Result<(), f64>> {
+        let cap = &request.capability;
+        let Some(limit) = self
+            .config
+            .limit_for(&cap.plugin, &cap.capability, &cap.project)
+        else {
+            return Ok(Ok(()));
+        };
+
+        let mut buckets = self
+            .buckets
+            .lock()
+            .map_err(|_| RuntimeError::Execution("rate limiter lock poisoned".to_string()))?;
+        let bucket = buckets
+            .entry((
+                cap.plugin.clone(),
+                cap.capability.clone(),
+                cap.project.clone(),
+            ))
+            .or_insert(Bucket {
+                tokens: f64::from(limit.capacity),
+                updated: now,
+            });
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens =
+            (bucket.tokens + elapsed * limit.refill_per_sec).min(f64::from(limit.capacity));
+        bucket.updated = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            return Ok(Ok(()));
+        }
+        let retry_after = if limit.refill_per_sec > 0.0 {
+            (1.0 - bucket.tokens) / limit.refill_per_sec
+        } else {
+            f64::INFINITY
+        };
+        Ok(Err(retry_after))
+    }
+}
+
+impl ActionMiddleware for RateLimiter {
+    fn before_policy(&self, request: &mut ActionRequest) -> RuntimeResult<Option<ActionOutcome>> {
+        match self.try_acquire(request, Instant::now())? {
+            Ok(()) => Ok(None),
+            Err(retry_after) => Ok(Some(ActionOutcome {
+                request_id: request.request_id.clone(),
+                status: ActionStatus::Blocked,
+                detail: "rate_limited".to_string(),
+                output: serde_json::json!({
+                    "retry_after_ms": retry_after
+                        .is_finite()
+                        .then(|| (retry_after * 1000.0).ceil() as u64)
+                }),
+                warnings: Vec::new(),
+                snapshot: None,
+            })),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use odin_plugin_protocol::{CapabilityRequest, RiskTier};
+
+    use super::*;
+
+    fn request(capability: &str) -> ActionRequest {
+        ActionRequest {
+            request_id: "req-1".to_string(),
+            risk_tier: RiskTier::Safe,
+            capability: CapabilityRequest {
+                plugin: "private.ops-watchdog".to_string(),
+                project: "private".to_string(),
+                capability: capability.to_string(),
+                scope: vec!["project".to_string()],
+                reason: "poll".to_string(),
+            },
+            trace_id: None,
+            input: serde_json::Value::Null,
+            requested_by: None,
+        }
+    }
+
+    #[test]
+    fn bucket_refills_over_time() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            default: Some(RateLimit {
+                capacity: 2,
+                refill_per_sec: 1.0,
+            }),
+            rules: Vec::new(),
+        });
+        let start = Instant::now();
+        let req = request("monitoring.sentry.read");
+
+        assert!(limiter.try_acquire(&req, start).expect("acquire").is_ok());
+        assert!(limiter.try_acquire(&req, start).expect("acquire").is_ok());
+        let retry = limiter
+            .try_acquire(&req, start)
+            .expect("acquire")
+            .expect_err("limited");
+        assert!((retry - 1.0).abs() < 1e-9);
+        assert!(limiter
+            .try_acquire(&req, start + Duration::from_secs(1))
+            .expect("acquire")
+            .is_ok());
+    }
+
+    #[test]
+    fn first_matching_rule_wins_and_unmatched_is_unlimited() {
+        let config: RateLimitConfig = serde_json::from_value(serde_json::json!({
+            "rules": [
+                {"capability": "vcs.pr.read", "capacity": 0, "refill_per_sec": 0.0},
+                {"plugin": "private.ops-watchdog", "capacity": 5, "refill_per_sec": 1.0}
+            ]
+        }))
+        .expect("config");
+        assert_eq!(
+            config
+                .limit_for("private.ops-watchdog", "vcs.pr.read", "private")
+                .map(|limit| limit.capacity),
+            Some(0)
+        );
+        assert!(config.limit_for("other", "repo.read", "demo").is_none());
+    }
+}
@@ -0,0 +1,337 @@
+//! Interactive reauthentication for session-backed plugins. `ReauthingSessionVault` wraps a
+//! `SessionVault`. When a lease comes back `reauth_required`, it audits `session.reauth.requested`
+//! and hands a `ReauthRequest` to a `ReauthNotifier` once. Once a fresh lease is issued for the
+//! handle, the request is completed and `session.reauth.completed` is audited.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+use odin_audit::{taxonomy, AuditRecord, AuditSink};
+use odin_secrets::{AccessContext, SecretError, SessionHandle, SessionLease, SessionVault};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::{now_unix, RuntimeError, RuntimeResult};
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ReauthRequest {
+    pub handle: SessionHandle,
+    pub plugin: String,
+    pub project: String,
+    pub reason: String,
+    /// When the current session lapsed or lapses; 0 when there was none.
+    pub expires_at_unix: u64,
+    pub requested_at_unix: u64,
+}
+
+/// Asks a human to log in again, e.g. through a notification channel or an approval queue.
+pub trait ReauthNotifier: Send + Sync {
+    fn request_reauth(&self, request: &ReauthRequest) -> RuntimeResult<()>;
+
+    /// Withdraws the request once the session is fresh again.
+    fn complete_reauth(&self, _handle: &SessionHandle) -> RuntimeResult<()> {
+        Ok(())
+    }
+}
+
+/// Keeps one JSON document per outstanding request under `dir`, for operators to list.
+#[derive(Clone, Debug)]
+pub struct FileReauthQueue {
+    dir: PathBuf,
+}
+
+impl FileReauthQueue {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Outstanding requests, oldest first.
+    pub fn pending(&self) -> RuntimeResult<Vec<ReauthRequest>> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => {
+                return Err(RuntimeError::Execution(format!(
+                    "failed listing reauth requests in {}: {e}",
+                    self.dir.display()
+                )))
+            }
+        };
+        let mut pending = Vec::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            let raw = fs::read(&path).map_err(|e| {
+                RuntimeError::Execution(format!(
+                    "failed reading reauth request {}: {e}",
+                    path.display()
+                ))
+            })?;
+            pending.push(serde_json::from_slice::<ReauthRequest>(&raw).map_err(|e| {
+                RuntimeError::Execution(format!("corrupt reauth request {}: {e}", path.display()))
+            })?);
+        }
+        pending.sort_by(|a, b| {
+            (a.requested_at_unix, &a.handle.0).cmp(&(b.requested_at_unix, &b.handle.0))
+        });
+        Ok(pending)
+    }
+
+    /// Handles may contain `/` and `:`, so files are named by the hex of the handle.
+    fn path_for(&self, handle: &SessionHandle) -> PathBuf {
+        let name: String = handle.0.bytes().map(|b| format!("{b:02x}")).collect();
+        self.dir.join(format!("{name}.json"))
+    }
+}
+
+impl ReauthNotifier for FileReauthQueue {
+    fn request_reauth(&self, request: &ReauthRequest) -> RuntimeResult<()> {
+        fs::create_dir_all(&self.dir).map_err(|e| {
+            RuntimeError::Execution(format!(
+                "failed creating reauth dir {}: {e}",
+                self.dir.display()
+            ))
+        })?;
+        let path = self.path_for(&request.handle);
+        let body = serde_json::to_vec_pretty(request).map_err(|e| {
+            RuntimeError::Execution(format!("failed serializing reauth request: {e}"))
+        })?;
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, body)
+            .and_then(|_| fs::rename(&tmp, &path))
+            .map_err(|e| {
+                RuntimeError::Execution(format!(
+                    "failed writing reauth request {}: {e}",
+                    path.display()
+                ))
+            })
+    }
+
+    fn complete_reauth(&self, handle: &SessionHandle) -> RuntimeResult<()> {
+        let path = self.path_for(handle);
+        match fs::remove_file(&path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(RuntimeError::Execution(format!(
+                "failed removing reauth request {}: {e}",
+                path.display()
+            ))),
+        }
+    }
+}
+
+pub struct ReauthingSessionVault<V, N, A> {
+    vault: V,
+    notifier: N,
+    audit: A,
+    /// Handles with an outstanding request, so each lapse notifies once.
+    requested: Mutex<HashSet<SessionHandle>>,
+}
+
+impl<V, N, A> ReauthingSessionVault<V, N, A>
+where
+    V: SessionVault,
+    N: ReauthNotifier,
+    A: AuditSink,
+{
+    pub fn new(vault: V, notifier: N, audit: A) -> Self {
+        Self {
+            vault,
+            notifier,
+            audit,
+            requested: Mutex::new(HashSet::new()),
+        }
+    }
+
+    fn request(&self, lease: &SessionLease, ctx: &AccessContext) -> Result<(), SecretError> {
+        if !self.lock()?.insert(lease.handle.clone()) {
+            return Ok(());
+        }
+        let request = ReauthRequest {
+            handle: lease.handle.clone(),
+            plugin: ctx.plugin.clone(),
+            project: ctx.project.clone(),
+            reason: ctx.reason.clone(),
+            expires_at_unix: lease.expires_at_unix,
+            requested_at_unix: now_unix(),
+        };
+        let notified = self
+            .record(taxonomy::SESSION_REAUTH_REQUESTED, &lease.handle, ctx)
+            .and_then(|()| self.notifier.request_reauth(&request));
+        if let Err(e) = notified {
+            // Not recorded as requested, so the next lease tries again.
+            self.lock()?.remove(&lease.handle);
+            return Err(SecretError::Backend(format!(
+                "failed to request reauthentication for {}: {e}",
+                lease.handle.0
+            )));
+        }
+        Ok(())
+    }
+
+    fn complete(&self, handle: &SessionHandle, ctx: &AccessContext) -> Result<(), SecretError> {
+        if !self.lock()?.remove(handle) {
+            return Ok(());
+        }
+        self.notifier
+            .complete_reauth(handle)
+            .and_then(|()| self.record(taxonomy::SESSION_REAUTH_COMPLETED, handle, ctx))
+            .map_err(|e| {
+                SecretError::Backend(format!(
+                    "failed to complete reauthentication for {}: {e}",
+                    handle.0
+                ))
+            })
+    }
+
+    fn record(
+        &self,
+        event_type: &str,
+        handle: &SessionHandle,
+        ctx: &AccessContext,
+    ) -> RuntimeResult<()> {
+        self.audit.record(AuditRecord {
+            ts_unix: now_unix(),
+            event_type: event_type.to_string(),
+            severity: taxonomy::severity(event_type),
+            request_id: None,
+            task_id: None,
+            project: Some(ctx.project.clone()),
+            trace_id: None,
+            metadata: json!({
+                "plugin": ctx.plugin,
+                "capability": ctx.capability,
+                "handle": handle.0,
+                "reason": ctx.reason,
+            }),
+        })?;
+        Ok(())
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, HashSet<SessionHandle>>, SecretError> {
+        self.requested
+            .lock()
+            .map_err(|_| SecretError::Backend("reauth tracker lock poisoned".to_string()))
+    }
+}
+
+impl<V, N, A> SessionVault for ReauthingSessionVault<V, N, A>
+where
+    V: SessionVault,
+    N: ReauthNotifier,
+    A: AuditSink,
+{
+    fn issue_session_lease(
+        &self,
+        handle: &SessionHandle,
+        ctx: &AccessContext,
+    ) -> Result<SessionLease, SecretError> {
+        let lease = self.vault.issue_session_lease(handle, ctx)?;
+        if lease.reauth_required {
+            self.request(&lease, ctx)?;
+        } else {
+            self.complete(handle, ctx)?;
+        }
+        Ok(lease)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use odin_audit::AuditError;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct Recorded(Mutex<Vec<AuditRecord>>);
+
+    impl AuditSink for &Recorded {
+        fn record(&self, record: AuditRecord) -> Result<(), AuditError> {
+            self.0.lock().expect("lock").push(record);
+            Ok(())
+        }
+    }
+
+    /// Leases are stale until `fresh` is set, as after a human logs in again.
+    #[derive(Default)]
+    struct BrowserVault {
+        fresh: AtomicBool,
+    }
+
+    impl SessionVault for &BrowserVault {
+        fn issue_session_lease(
+            &self,
+            handle: &SessionHandle,
+            _ctx: &AccessContext,
+        ) -> Result<SessionLease, SecretError> {
+            let fresh = self.fresh.load(Ordering::SeqCst);
+            Ok(SessionLease {
+                handle: handle.clone(),
+                expires_at_unix: if fresh { u64::MAX } else { 0 },
+                reauth_required: !fresh,
+            })
+        }
+    }
+
+    #[test]
+    fn stale_sessions_request_reauth_once_until_renewed() {
+        let dir = std::env::temp_dir().join(format!("odin-reauth-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let queue = FileReauthQueue::new(&dir);
+        let browser = BrowserVault::default();
+        let audit = Recorded::default();
+        let vault = ReauthingSessionVault::new(&browser, queue.clone(), &audit);
+        let ctx = AccessContext {
+            plugin: "stagehand".to_string(),
+            project: "demo".to_string(),
+            capability: "browser.session".to_string(),
+            reason: "check invoices".to_string(),
+        };
+        let portal = SessionHandle("session://demo/stagehand/portal".to_string());
+
+        assert!(
+            vault
+                .issue_session_lease(&portal, &ctx)
+                .expect("lease")
+                .reauth_required
+        );
+        vault.issue_session_lease(&portal, &ctx).expect("lease");
+        let pending = queue.pending().expect("pending");
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].handle, portal);
+        assert_eq!(pending[0].reason, "check invoices");
+
+        browser.fresh.store(true, Ordering::SeqCst);
+        assert!(
+            !vault
+                .issue_session_lease(&portal, &ctx)
+                .expect("lease")
+                .reauth_required
+        );
+        assert!(queue.pending().expect("pending").is_empty());
+
+        let records = audit.0.lock().expect("lock");
+        let events: Vec<&str> = records.iter().map(|r| r.event_type.as_str()).collect();
+        assert_eq!(
+            events,
+            [
+                taxonomy::SESSION_REAUTH_REQUESTED,
+                taxonomy::SESSION_REAUTH_COMPLETED
+            ]
+        );
+        records
+            .iter()
+            .for_each(|r| taxonomy::validate(r).expect("taxonomy"));
+        let _ = fs::remove_dir_all(dir);
+    }
+}
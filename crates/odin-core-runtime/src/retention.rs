@@ -0,0 +1,278 @@
+//! Retention for processed task archives: the `done/` and `failed/` directories under a task
+//! queue, which a long-running daemon otherwise fills with one small file per task.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use odin_audit::{AuditRecord, AuditSink, Severity};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::{now_unix, RuntimeError, RuntimeResult};
+
+/// Processed-task directories retention applies to, relative to the queue directory.
+pub const TASK_ARCHIVE_DIRS: [&str; 2] = ["done", "failed"];
+
+pub const DEFAULT_TASK_ARCHIVE_MAX_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+#[derive(Clone, Debug)]
+pub struct TaskRetentionConfig {
+    pub queue_dir: PathBuf,
+    /// Task files older than this are archived or deleted.
+    pub max_age: Duration,
+    /// Cap on the task files kept per directory; the oldest are deleted beyond it.
+    pub max_bytes: Option<u64>,
+    /// Roll aged files into `archive/<dir>-<YYYY-MM-DD>.tar.gz` (with a `.sha256` alongside)
+    /// instead of deleting them.
+    pub daily_archives: bool,
+}
+
+impl TaskRetentionConfig {
+    pub fn new(queue_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            queue_dir: queue_dir.into(),
+            max_age: DEFAULT_TASK_ARCHIVE_MAX_AGE,
+            max_bytes: None,
+            daily_archives: false,
+        }
+    }
+
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    pub fn with_max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+
+    pub fn with_daily_archives(mut self, daily_archives: bool) -> Self {
+        self.daily_archives = daily_archives;
+        self
+    }
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+pub struct DailyArchive {
+    pub path: PathBuf,
+    pub sha256: String,
+    pub files: usize,
+}
+
+#[derive(Clone, Debug, Default, Serialize, PartialEq, Eq)]
+pub struct RetentionReport {
+    pub archives: Vec<DailyArchive>,
+    /// Task files deleted without being archived.
+    pub removed: Vec<PathBuf>,
+    pub bytes_freed: u64,
+}
+
+struct TaskFile {
+    path: PathBuf,
+    modified: SystemTime,
+    len: u64,
+}
+
+/// Applies the age and size caps to every directory in `TASK_ARCHIVE_DIRS`, recording a
+/// `task.archive.retention` audit event with the totals.
+pub fn run_task_retention<A: AuditSink>(
+    config: &TaskRetentionConfig,
+    audit: &A,
+    now: SystemTime,
+) -> RuntimeResult<RetentionReport> {
+    let mut report = RetentionReport::default();
+    for name in TASK_ARCHIVE_DIRS {
+        let dir = config.queue_dir.join(name);
+        if dir.is_dir() {
+            apply_to_dir(config, &dir, name, now, &mut report)?;
+        }
+    }
+
+    audit.record(AuditRecord {
+        ts_unix: now_unix(),
+        event_type: "task.archive.retention".to_string(),
+        severity: Severity::Info,
+        request_id: None,
+        task_id: None,
+        project: None,
+        trace_id: None,
+        metadata: serde_json::json!({
+            "queue_dir": config.queue_dir,
+            "archived": report.archives.iter().map(|archive| archive.files).sum::<usize>(),
+            "archives": report.archives,
+            "removed": report.removed.len(),
+            "bytes_freed": report.bytes_freed
+        }),
+    })?;
+    Ok(report)
+}
+
+fn apply_to_dir(
+    config: &TaskRetentionConfig,
+    dir: &Path,
+    name: &str,
+    now: SystemTime,
+    report: &mut RetentionReport,
+) -> RuntimeResult<()> {
+    let mut files = task_files(dir)?;
+    files.sort_by_key(|file| file.modified);
+    let (aged, mut kept): (Vec<TaskFile>, Vec<TaskFile>) = files.into_iter().partition(|file| {
+        now.duration_since(file.modified)
+            .is_ok_and(|age| age >= config.max_age)
+    });
+
+    if config.daily_archives {
+        let mut days: Vec<(String, Vec<TaskFile>)> = Vec::new();
+        for file in aged {
+            let day = utc_day(file.modified);
+            match days.last_mut() {
+                Some((last, group)) if *last == day => group.push(file),
+                _ => days.push((day, vec![file])),
+            }
+        }
+        for (day, group) in days {
+            report
+                .archives
+                .push(archive_day(dir, &format!("{name}-{day}"), &group)?);
+            for file in group {
+                remove(&file, report)?;
+            }
+        }
+    } else {
+        for file in aged {
+            remove(&file, report)?;
+            report.removed.push(file.path);
+        }
+    }
+
+    if let Some(max_bytes) = config.max_bytes {
+        let mut total: u64 = kept.iter().map(|file| file.len).sum();
+        let mut oldest = kept.drain(..);
+        while total > max_bytes {
+            let Some(file) = oldest.next() else {
+                break;
+            };
+            total -= file.len;
+            remove(&file, report)?;
+            report.removed.push(file.path);
+        }
+    }
+    Ok(())
+}
+
+fn task_files(dir: &Path) -> RuntimeResult<Vec<TaskFile>> {
+    let entries = fs::read_dir(dir).map_err(|e| io_err("reading", dir, e))?;
+    let mut files = Vec::new();
+    for entry in entries.flatten() {
+        let Ok(meta) = entry.metadata() else {
+            continue;
+        };
+        if !meta.is_file() {
+            continue;
+        }
+        files.push(TaskFile {
+            path: entry.path(),
+            modified: meta.modified().unwrap_or(UNIX_EPOCH),
+            len: meta.len(),
+        });
+    }
+    Ok(files)
+}
+
+/// Writes `files` into `<dir>/archive/<stem>.tar.gz` (suffixed `-1`, `-2`, ... when a run on
+/// the same day already produced one) and a `sha256sum`-style checksum file next to it.
+fn archive_day(dir: &Path, stem: &str, files: &[TaskFile]) -> RuntimeResult<DailyArchive> {
+    let archive_dir = dir.join("archive");
+    fs::create_dir_all(&archive_dir).map_err(|e| io_err("creating", &archive_dir, e))?;
+    let mut path = archive_dir.join(format!("{stem}.tar.gz"));
+    let mut suffix = 0;
+    while path.exists() {
+        suffix += 1;
+        path = archive_dir.join(format!("{stem}-{suffix}.tar.gz"));
+    }
+
+    let status = Command::new("tar")
+        .arg("-czf")
+        .arg(&path)
+        .arg("-C")
+        .arg(dir)
+        .args(files.iter().filter_map(|file| file.path.file_name()))
+        .status()
+        .map_err(|e| io_err("archiving into", &path, e))?;
+    if !status.success() {
+        let _ = fs::remove_file(&path);
+        return Err(RuntimeError::Execution(format!(
+            "tar failed writing {} ({status})",
+            path.display()
+        )));
+    }
+
+    let bytes = fs::read(&path).map_err(|e| io_err("reading", &path, e))?;
+    let sha256 = format!("{:x}", Sha256::digest(&bytes));
+    let file_name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default();
+    let checksum_path = archive_dir.join(format!("{file_name}.sha256"));
+    fs::write(&checksum_path, format!("{sha256}  {file_name}\n"))
+        .map_err(|e| io_err("writing", &checksum_path, e))?;
+
+    Ok(DailyArchive {
+        path,
+        sha256,
+        files: files.len(),
+    })
+}
+
+fn remove(file: &TaskFile, report: &mut RetentionReport) -> RuntimeResult<()> {
+    fs::remove_file(&file.path).map_err(|e| io_err("removing", &file.path, e))?;
+    report.bytes_freed += file.len;
+    Ok(())
+}
+
+fn io_err(action: &str, path: &Path, err: std::io::Error) -> RuntimeError {
+    RuntimeError::Execution(format!(
+        "task retention failed {action} {}: {err}",
+        path.display()
+    ))
+}
+
+/// `YYYY-MM-DD` (UTC) of `time`.
+fn utc_day(time: SystemTime) -> String {
+    let days = time
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() / 86_400)
+        .unwrap_or(0) as i64;
+    // Civil-from-days (proleptic Gregorian), shifted so eras start on March 1st.
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn utc_day_formats_civil_dates() {
+        assert_eq!(utc_day(UNIX_EPOCH), "1970-01-01");
+        assert_eq!(
+            utc_day(UNIX_EPOCH + Duration::from_secs(1_709_164_800)),
+            "2024-02-29"
+        );
+        assert_eq!(
+            utc_day(UNIX_EPOCH + Duration::from_secs(1_735_689_599)),
+            "2024-12-31"
+        );
+    }
+}
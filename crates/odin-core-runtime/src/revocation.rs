@@ -0,0 +1,193 @@
+//! Capability grants revoked while the runtime is running. From the moment of revocation, every
+//! request relying on the grant is blocked, including requests already parked for approval.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+use odin_plugin_protocol::CapabilityRequest;
+use serde::{Deserialize, Serialize};
+
+use crate::{RuntimeError, RuntimeResult};
+
+/// File name of the persisted revocation list, kept beside `plugin-permissions.yaml`.
+pub const REVOCATIONS_FILE: &str = "capability-revocations.json";
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CapabilityRevocation {
+    pub plugin: String,
+    pub capability: String,
+    /// Only requests for this scope are covered; `None` covers the whole capability.
+    #[serde(default)]
+    pub scope: Option<String>,
+}
+
+impl CapabilityRevocation {
+    pub fn new(plugin: impl Into<String>, capability: impl Into<String>) -> Self {
+        Self {
+            plugin: plugin.into(),
+            capability: capability.into(),
+            scope: None,
+        }
+    }
+
+    pub fn with_scope(mut self, scope: impl Into<String>) -> Self {
+        self.scope = Some(scope.into());
+        self
+    }
+
+    pub fn covers(&self, request: &CapabilityRequest) -> bool {
+        request.plugin == self.plugin
+            && request.capability == self.capability
+            && self
+                .scope
+                .as_ref()
+                .is_none_or(|scope| request.scope.contains(scope))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct RevocationFile {
+    schema_version: u32,
+    #[serde(default)]
+    revocations: Vec<CapabilityRevocation>,
+}
+
+/// Revoked grants, optionally persisted as JSON. File-backed lists re-read the file on every
+/// check so revocations made by another process (e.g. `governance revoke`) apply immediately.
+#[derive(Debug, Default)]
+pub struct RevocationList {
+    path: Option<PathBuf>,
+    revoked: Mutex<Vec<CapabilityRevocation>>,
+}
+
+impl RevocationList {
+    pub fn file(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: Some(path.into()),
+            revoked: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn revoke(&self, revocation: CapabilityRevocation) -> RuntimeResult<()> {
+        let mut memory = self.lock()?;
+        let mut revoked = self.load(&memory)?;
+        if revoked.contains(&revocation) {
+            return Ok(());
+        }
+        revoked.push(revocation);
+        self.store(&mut memory, revoked)
+    }
+
+    pub fn covers(&self, request: &CapabilityRequest) -> RuntimeResult<bool> {
+        let memory = self.lock()?;
+        Ok(self
+            .load(&memory)?
+            .iter()
+            .any(|revocation| revocation.covers(request)))
+    }
+
+    fn lock(&self) -> RuntimeResult<MutexGuard<'_, Vec<CapabilityRevocation>>> {
+        self.revoked
+            .lock()
+            .map_err(|_| RuntimeError::Execution("revocation list lock poisoned".to_string()))
+    }
+
+    fn load(&self, memory: &[CapabilityRevocation]) -> RuntimeResult<Vec<CapabilityRevocation>> {
+        let Some(path) = &self.path else {
+            return Ok(memory.to_vec());
+        };
+        let raw = match fs::read(path) {
+            Ok(raw) => raw,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(revocation_io(path, e)),
+        };
+        let file: RevocationFile = serde_json::from_slice(&raw).map_err(|e| {
+            RuntimeError::Execution(format!(
+                "revocation list {} is invalid: {e}",
+                path.display()
+            ))
+        })?;
+        if file.schema_version != 1 {
+            return Err(RuntimeError::Execution(format!(
+                "unsupported revocation list schema_version: {}",
+                file.schema_version
+            )));
+        }
+        Ok(file.revocations)
+    }
+
+    fn store(
+        &self,
+        memory: &mut Vec<CapabilityRevocation>,
+        revocations: Vec<CapabilityRevocation>,
+    ) -> RuntimeResult<()> {
+        let Some(path) = &self.path else {
+            *memory = revocations;
+            return Ok(());
+        };
+        let body = serde_json::to_vec_pretty(&RevocationFile {
+            schema_version: 1,
+            revocations,
+        })
+        .map_err(|e| RuntimeError::Execution(format!("revocation list encode failed: {e}")))?;
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(parent).map_err(|e| revocation_io(path, e))?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, body)
+            .and_then(|_| fs::rename(&tmp, path))
+            .map_err(|e| revocation_io(path, e))
+    }
+}
+
+fn revocation_io(path: &Path, err: std::io::Error) -> RuntimeError {
+    RuntimeError::Execution(format!("revocation list {}: {err}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(scope: &[&str]) -> CapabilityRequest {
+        CapabilityRequest {
+            plugin: "example.git".to_string(),
+            project: "demo".to_string(),
+            capability: "repo.push".to_string(),
+            scope: scope.iter().map(|value| value.to_string()).collect(),
+            reason: "unit test".to_string(),
+        }
+    }
+
+    #[test]
+    fn scoped_revocations_cover_only_that_scope() {
+        let revocation = CapabilityRevocation::new("example.git", "repo.push").with_scope("main");
+        assert!(revocation.covers(&request(&["main"])));
+        assert!(!revocation.covers(&request(&["feature"])));
+        assert!(CapabilityRevocation::new("example.git", "repo.push").covers(&request(&[])));
+        assert!(!CapabilityRevocation::new("example.git", "repo.read").covers(&request(&[])));
+    }
+
+    #[test]
+    fn file_backed_lists_see_revocations_from_other_handles() {
+        let dir = std::env::temp_dir().join(format!(
+            "odin-revocations-{}-{}",
+            std::process::id(),
+            crate::now_unix()
+        ));
+        let path = dir.join(REVOCATIONS_FILE);
+        let daemon = RevocationList::file(&path);
+        assert!(!daemon.covers(&request(&["main"])).expect("covers"));
+
+        let cli = RevocationList::file(&path);
+        let revocation = CapabilityRevocation::new("example.git", "repo.push").with_scope("main");
+        cli.revoke(revocation.clone()).expect("revoke");
+        cli.revoke(revocation).expect("revoke again");
+        assert!(daemon.covers(&request(&["main"])).expect("covers"));
+        assert!(!daemon.covers(&request(&["feature"])).expect("covers"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
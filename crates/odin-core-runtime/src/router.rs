@@ -0,0 +1,116 @@
+//! Maps task kinds (the envelope `type`) to handlers that turn a task into the event
+//! dispatched to its plugin.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use odin_plugin_protocol::EventEnvelope;
+
+use crate::{now_unix, RuntimeError, RuntimeResult, WatchdogTaskEnvelope};
+
+/// Task kind written by the legacy keepalive loop.
+pub const WATCHDOG_POLL_TASK_KIND: &str = "watchdog_poll";
+
+pub trait TaskHandler: Send + Sync {
+    /// Builds the event delivered to `task.payload.plugin`.
+    fn event_for(&self, task: &WatchdogTaskEnvelope) -> RuntimeResult<EventEnvelope>;
+}
+
+/// Delivers the task to its plugin as a `task.received` event. Used for watchdog polls
+/// and for followup tasks enqueued by plugins, whose `data` and `origin_task_id` are
+/// passed through.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PluginTaskHandler;
+
+impl TaskHandler for PluginTaskHandler {
+    fn event_for(&self, task: &WatchdogTaskEnvelope) -> RuntimeResult<EventEnvelope> {
+        let mut payload = serde_json::json!({
+            "task_type": task.payload.task_type,
+            "source_key": task.payload.source_key,
+            "trigger": task.payload.trigger
+        });
+        if task.task_kind != WATCHDOG_POLL_TASK_KIND {
+            payload["task_kind"] = serde_json::json!(task.task_kind);
+            payload["origin_task_id"] = serde_json::json!(task.payload.origin_task_id);
+            payload["data"] = task.payload.data.clone();
+        }
+        Ok(EventEnvelope {
+            event_id: format!("evt-{}-{}", task.task_id, now_unix()),
+            event_type: "task.received".to_string(),
+            task_id: Some(task.task_id.clone()),
+            request_id: None,
+            project: Some(task.payload.project.clone()),
+            trace_id: task.trace_id.clone(),
+            payload,
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct TaskRouter {
+    handlers: BTreeMap<String, Arc<dyn TaskHandler>>,
+}
+
+impl std::fmt::Debug for TaskRouter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TaskRouter")
+            .field("kinds", &self.kinds())
+            .finish()
+    }
+}
+
+/// Routes only `watchdog_poll`, matching the runtime's historical behaviour.
+impl Default for TaskRouter {
+    fn default() -> Self {
+        Self::empty().with_handler(WATCHDOG_POLL_TASK_KIND, PluginTaskHandler)
+    }
+}
+
+impl TaskRouter {
+    pub fn empty() -> Self {
+        Self {
+            handlers: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_handler(
+        mut self,
+        kind: impl Into<String>,
+        handler: impl TaskHandler + 'static,
+    ) -> Self {
+        self.register(kind, handler);
+        self
+    }
+
+    /// Registers `handler` for `kind`, replacing any existing handler.
+    pub fn register(&mut self, kind: impl Into<String>, handler: impl TaskHandler + 'static) {
+        self.handlers.insert(kind.into(), Arc::new(handler));
+    }
+
+    pub fn kinds(&self) -> Vec<&str> {
+        self.handlers.keys().map(String::as_str).collect()
+    }
+
+    pub fn handler(&self, kind: &str) -> RuntimeResult<&dyn TaskHandler> {
+        self.handlers
+            .get(kind)
+            .map(|handler| handler.as_ref())
+            .ok_or_else(|| RuntimeError::InvalidInput(format!("unsupported task type: {kind}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_router_only_accepts_watchdog_poll() {
+        let router = TaskRouter::default();
+        assert_eq!(router.kinds(), vec![WATCHDOG_POLL_TASK_KIND]);
+        let err = router
+            .handler("watchdog.remediation.dispatch")
+            .err()
+            .expect("unregistered kind");
+        assert!(err.to_string().contains("unsupported task type"));
+    }
+}
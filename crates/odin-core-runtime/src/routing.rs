@@ -0,0 +1,139 @@
+//! Capability-prefix routing across executors, so one runtime can mix dry-run, HTTP and
+//! command execution.
+
+use std::sync::Arc;
+
+use odin_plugin_protocol::ActionRequest;
+use serde_json::Value;
+
+use crate::{ActionExecutor, RuntimeResult};
+
+/// Sends each request to the executor whose pattern matches its capability. Patterns are
+/// exact ids (`command.run`) or prefixes ending in `.*` (`http.*`); the longest matching
+/// pattern wins and unmatched capabilities go to the fallback.
+#[derive(Clone)]
+pub struct RoutingExecutor {
+    routes: Vec<(String, Arc<dyn ActionExecutor>)>,
+    fallback: Arc<dyn ActionExecutor>,
+}
+
+impl std::fmt::Debug for RoutingExecutor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RoutingExecutor")
+            .field("routes", &self.patterns().collect::<Vec<_>>())
+            .finish_non_exhaustive()
+    }
+}
+
+impl RoutingExecutor {
+    pub fn new(fallback: impl ActionExecutor + 'static) -> Self {
+        Self {
+            routes: Vec::new(),
+            fallback: Arc::new(fallback),
+        }
+    }
+
+    /// Adds a route; re-adding a pattern replaces its executor.
+    pub fn with_route(
+        mut self,
+        pattern: impl Into<String>,
+        executor: impl ActionExecutor + 'static,
+    ) -> Self {
+        self.insert(pattern.into(), Arc::new(executor));
+        self
+    }
+
+    pub fn with_shared_route(
+        mut self,
+        pattern: impl Into<String>,
+        executor: Arc<dyn ActionExecutor>,
+    ) -> Self {
+        self.insert(pattern.into(), executor);
+        self
+    }
+
+    pub fn patterns(&self) -> impl Iterator<Item = &str> {
+        self.routes.iter().map(|(pattern, _)| pattern.as_str())
+    }
+
+    /// The pattern `capability` routes to, or `None` for the fallback.
+    pub fn route_for(&self, capability: &str) -> Option<&str> {
+        self.routes
+            .iter()
+            .filter(|(pattern, _)| pattern_matches(pattern, capability))
+            .max_by_key(|(pattern, _)| pattern.len())
+            .map(|(pattern, _)| pattern.as_str())
+    }
+
+    fn insert(&mut self, pattern: String, executor: Arc<dyn ActionExecutor>) {
+        match self.routes.iter_mut().find(|(known, _)| *known == pattern) {
+            Some(route) => route.1 = executor,
+            None => self.routes.push((pattern, executor)),
+        }
+    }
+}
+
+impl ActionExecutor for RoutingExecutor {
+    fn execute(&self, request: &ActionRequest) -> RuntimeResult<Value> {
+        let executor = self
+            .route_for(&request.capability.capability)
+            .and_then(|pattern| self.routes.iter().find(|(known, _)| known == pattern))
+            .map_or(&self.fallback, |(_, executor)| executor);
+        executor.execute(request)
+    }
+}
+
+fn pattern_matches(pattern: &str, capability: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => capability.starts_with(prefix),
+        None => pattern == capability,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use odin_plugin_protocol::{CapabilityRequest, RiskTier};
+
+    use super::*;
+    use crate::DryRunExecutor;
+
+    struct Tagged(&'static str);
+
+    impl ActionExecutor for Tagged {
+        fn execute(&self, _request: &ActionRequest) -> RuntimeResult<Value> {
+            Ok(Value::String(self.0.to_string()))
+        }
+    }
+
+    fn request(capability: &str) -> ActionRequest {
+        ActionRequest {
+            request_id: "req-1".to_string(),
+            risk_tier: RiskTier::Safe,
+            capability: CapabilityRequest {
+                plugin: "example.safe-github".to_string(),
+                project: "demo".to_string(),
+                capability: capability.to_string(),
+                scope: vec!["project".to_string()],
+                reason: "unit test".to_string(),
+            },
+            trace_id: None,
+            input: Value::Null,
+            requested_by: None,
+        }
+    }
+
+    #[test]
+    fn routes_by_most_specific_pattern_with_fallback() {
+        let executor = RoutingExecutor::new(DryRunExecutor)
+            .with_route("http.*", Tagged("http"))
+            .with_route("command.*", Tagged("command"))
+            .with_route("command.run", Tagged("command-run"));
+
+        let run = |capability: &str| executor.execute(&request(capability)).expect("executes");
+        assert_eq!(run("http.get"), "http");
+        assert_eq!(run("command.run"), "command-run");
+        assert_eq!(run("command.kill"), "command");
+        assert_eq!(run("httpx.get")["result"], "dry_run");
+        assert_eq!(executor.route_for("task.enqueue"), None);
+    }
+}
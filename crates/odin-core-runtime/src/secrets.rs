@@ -0,0 +1,305 @@
+//! Policy gate in front of a `SecretStore`. Handles must be namespaced
+//! (`secret://<project>/<plugin>/<name>`). A plugin may resolve handles in its own namespace, and
+//! in another only with a `secret.cross_namespace` grant scoped to it. The `PolicyEngine` then
+//! decides the requesting capability, scoped to the handle's namespace. Only allowed resolutions
+//! reach the inner store, and every outcome is audited. Values revealed for injection pass the
+//! same gate.
+
+use odin_audit::{taxonomy, AuditRecord, AuditSink};
+use odin_plugin_protocol::{ActionRequest, CapabilityRequest, PolicyDecision, RiskTier};
+use odin_policy_engine::{PolicyEngine, PolicyError};
+use odin_secrets::namespace::NamespacedHandle;
+use odin_secrets::rotation::RotationInfo;
+use odin_secrets::{AccessContext, SecretError, SecretHandle, SecretRef, SecretStore};
+use serde_json::{json, Value};
+use zeroize::Zeroizing;
+
+use crate::now_unix;
+
+/// Lets a plugin resolve handles outside its own namespace; scope it to the namespaces
+/// (`secret://<project>/<plugin>`) it may reach.
+pub const CROSS_NAMESPACE_CAPABILITY: &str = "secret.cross_namespace";
+
+pub struct PolicyGatedSecretStore<S, P, A> {
+    inner: S,
+    policy: P,
+    audit: A,
+}
+
+impl<S, P, A> PolicyGatedSecretStore<S, P, A>
+where
+    S: SecretStore,
+    P: PolicyEngine,
+    A: AuditSink,
+{
+    pub fn new(inner: S, policy: P, audit: A) -> Self {
+        Self {
+            inner,
+            policy,
+            audit,
+        }
+    }
+
+    /// Checks the handle's namespace, then asks the policy whether `ctx` may resolve it.
+    /// Denials are audited.
+    fn authorize(
+        &self,
+        handle: &SecretHandle,
+        ctx: &AccessContext,
+        access: &str,
+    ) -> Result<(), SecretError> {
+        let deny = |reason_code: &str, detail: Value| -> Result<(), SecretError> {
+            let mut metadata = json!({ "access": access, "reason_code": reason_code });
+            if let (Some(metadata), Value::Object(detail)) = (metadata.as_object_mut(), detail) {
+                metadata.extend(detail);
+            }
+            self.record(taxonomy::SECRET_DENIED, handle, ctx, metadata)?;
+            Err(SecretError::Unauthorized(format!(
+                "{} may not resolve {} via {}: {reason_code}",
+                ctx.plugin, handle.0, ctx.capability
+            )))
+        };
+        let parsed = match NamespacedHandle::parse(handle) {
+            Ok(parsed) => parsed,
+            Err(e) => return deny("handle_not_namespaced", json!({ "error": e.to_string() })),
+        };
+        let namespace = parsed.namespace();
+        if !parsed.owned_by(&ctx.plugin, &ctx.project) {
+            match self.decide(CROSS_NAMESPACE_CAPABILITY, &namespace, ctx) {
+                Ok(None) => {}
+                Ok(Some(reason_code)) => {
+                    return deny(
+                        "cross_namespace_not_granted",
+                        json!({ "policy_reason_code": reason_code }),
+                    )
+                }
+                Err(e) => return self.policy_failed(handle, ctx, access, e),
+            }
+        }
+        match self.decide(&ctx.capability, &namespace, ctx) {
+            Ok(None) => Ok(()),
+            Ok(Some(reason_code)) => deny(&reason_code, Value::Null),
+            Err(e) => self.policy_failed(handle, ctx, access, e),
+        }
+    }
+
+    /// `None` when the policy allows `capability` on `namespace`, else the reason it does not.
+    fn decide(
+        &self,
+        capability: &str,
+        namespace: &str,
+        ctx: &AccessContext,
+    ) -> Result<Option<String>, PolicyError> {
+        let request = ActionRequest {
+            request_id: format!("secret:{namespace}"),
+            risk_tier: RiskTier::Sensitive,
+            capability: CapabilityRequest {
+                plugin: ctx.plugin.clone(),
+                project: ctx.project.clone(),
+                capability: capability.to_string(),
+                scope: vec![namespace.to_string()],
+                reason: ctx.reason.clone(),
+            },
+            trace_id: None,
+            input: Value::Null,
+            requested_by: None,
+        };
+        Ok(match self.policy.decide(&request)? {
+            PolicyDecision::Allow { .. } => None,
+            PolicyDecision::Deny { reason_code }
+            | PolicyDecision::RequireApproval { reason_code, .. } => Some(reason_code),
+        })
+    }
+
+    fn policy_failed(
+        &self,
+        handle: &SecretHandle,
+        ctx: &AccessContext,
+        access: &str,
+        err: PolicyError,
+    ) -> Result<(), SecretError> {
+        self.record(
+            taxonomy::SECRET_DENIED,
+            handle,
+            ctx,
+            json!({ "access": access, "reason_code": "policy_error", "error": err.to_string() }),
+        )?;
+        Err(SecretError::Backend(format!(
+            "secret policy evaluation failed: {err}"
+        )))
+    }
+
+    fn record_outcome(
+        &self,
+        handle: &SecretHandle,
+        ctx: &AccessContext,
+        access: &str,
+        error: Option<&SecretError>,
+    ) -> Result<(), SecretError> {
+        match error {
+            None => self.record(
+                taxonomy::SECRET_RESOLVED,
+                handle,
+                ctx,
+                json!({ "access": access }),
+            ),
+            Some(err) => self.record(
+                taxonomy::SECRET_RESOLVE_FAILED,
+                handle,
+                ctx,
+                json!({ "access": access, "error": err.to_string() }),
+            ),
+        }
+    }
+
+    /// Records one resolution outcome. A resolution that cannot be audited fails.
+    fn record(
+        &self,
+        event_type: &str,
+        handle: &SecretHandle,
+        ctx: &AccessContext,
+        detail: Value,
+    ) -> Result<(), SecretError> {
+        let mut metadata = json!({
+            "plugin": ctx.plugin,
+            "capability": ctx.capability,
+            "handle": handle.0,
+            "namespace": NamespacedHandle::parse(handle).ok().map(|h| h.namespace()),
+            "reason": ctx.reason,
+            "policy_version": self.policy.version(),
+        });
+        if let (Some(metadata), Value::Object(detail)) = (metadata.as_object_mut(), detail) {
+            metadata.extend(detail);
+        }
+        self.audit
+            .record(AuditRecord {
+                ts_unix: now_unix(),
+                event_type: event_type.to_string(),
+                severity: taxonomy::severity(event_type),
+                request_id: None,
+                task_id: None,
+                project: Some(ctx.project.clone()),
+                trace_id: None,
+                metadata,
+            })
+            .map_err(|e| SecretError::Backend(format!("failed to audit secret resolution: {e}")))
+    }
+}
+
+impl<S, P, A> SecretStore for PolicyGatedSecretStore<S, P, A>
+where
+    S: SecretStore,
+    P: PolicyEngine,
+    A: AuditSink,
+{
+    fn resolve_secret_handle(
+        &self,
+        handle: &SecretHandle,
+        ctx: &AccessContext,
+    ) -> Result<SecretRef, SecretError> {
+        self.authorize(handle, ctx, "handle")?;
+        let resolved = self.inner.resolve_secret_handle(handle, ctx);
+        self.record_outcome(handle, ctx, "handle", resolved.as_ref().err())?;
+        resolved
+    }
+
+    fn reveal_secret(
+        &self,
+        handle: &SecretHandle,
+        ctx: &AccessContext,
+    ) -> Result<Zeroizing<Vec<u8>>, SecretError> {
+        self.authorize(handle, ctx, "value")?;
+        let revealed = self.inner.reveal_secret(handle, ctx);
+        self.record_outcome(handle, ctx, "value", revealed.as_ref().err())?;
+        revealed
+    }
+
+    /// Operator rotation is not a plugin resolution, so it bypasses the gate.
+    fn rotate(&self, handle: &SecretHandle) -> Result<u64, SecretError> {
+        self.inner.rotate(handle)
+    }
+
+    fn rotation_info(&self) -> Result<Vec<RotationInfo>, SecretError> {
+        self.inner.rotation_info()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use odin_audit::AuditError;
+    use odin_policy_engine::StaticPolicyEngine;
+    use odin_secrets::HandleOnlyStore;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct Recorded(Mutex<Vec<AuditRecord>>);
+
+    impl AuditSink for &Recorded {
+        fn record(&self, record: AuditRecord) -> Result<(), AuditError> {
+            self.0.lock().expect("lock").push(record);
+            Ok(())
+        }
+    }
+
+    fn ctx(project: &str) -> AccessContext {
+        AccessContext {
+            plugin: "example.git".to_string(),
+            project: project.to_string(),
+            capability: "repo.push".to_string(),
+            reason: "unit".to_string(),
+        }
+    }
+
+    #[test]
+    fn resolves_own_namespace_and_granted_cross_namespace_handles_only() {
+        let mut policy = StaticPolicyEngine::default();
+        policy.allow_capability("example.git", "demo", "repo.push");
+        policy.allow_capability("example.git", "demo", CROSS_NAMESPACE_CAPABILITY);
+        policy.restrict_scopes(
+            "example.git",
+            "demo",
+            CROSS_NAMESPACE_CAPABILITY,
+            &["secret://shared/*".to_string()],
+        );
+        let audit = Recorded::default();
+        let store = PolicyGatedSecretStore::new(HandleOnlyStore, policy, &audit);
+        let resolve = |handle: &str, project: &str| {
+            store.resolve_secret_handle(&SecretHandle(handle.to_string()), &ctx(project))
+        };
+
+        resolve("secret://demo/example.git/github", "demo").expect("own namespace");
+        resolve("secret://shared/ci-bot/token", "demo").expect("granted cross namespace");
+        let err = resolve("secret://demo/other.plugin/token", "demo").expect_err("foreign");
+        assert!(err.to_string().contains("cross_namespace_not_granted"));
+        let err = resolve("secret://demo/github", "demo").expect_err("unstructured");
+        assert!(err.to_string().contains("handle_not_namespaced"));
+        assert!(matches!(
+            resolve("secret://ops/example.git/github", "ops"),
+            Err(SecretError::Unauthorized(_))
+        ));
+
+        let records = audit.0.lock().expect("lock");
+        let events: Vec<&str> = records.iter().map(|r| r.event_type.as_str()).collect();
+        assert_eq!(
+            events,
+            [
+                taxonomy::SECRET_RESOLVED,
+                taxonomy::SECRET_RESOLVED,
+                taxonomy::SECRET_DENIED,
+                taxonomy::SECRET_DENIED,
+                taxonomy::SECRET_DENIED
+            ]
+        );
+        assert_eq!(
+            records[2].metadata["namespace"],
+            "secret://demo/other.plugin"
+        );
+        assert_eq!(records[4].metadata["reason_code"], "capability_not_granted");
+        records
+            .iter()
+            .for_each(|r| taxonomy::validate(r).expect("taxonomy"));
+    }
+}
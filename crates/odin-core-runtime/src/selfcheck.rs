@@ -0,0 +1,336 @@
+//! Integrity self-check for on-disk runtime state: `state.json`, `routing.json`,
+//! stale lock directories, and the pending-approval store.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use odin_audit::{taxonomy, AuditRecord, AuditSink, Severity};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::approvals::{approval_file_name, PendingApproval};
+use crate::{now_unix, RuntimeError, RuntimeResult};
+
+/// Lock directories older than this are assumed to belong to a crashed writer.
+pub const DEFAULT_STALE_LOCK_AGE: Duration = Duration::from_secs(10 * 60);
+
+#[derive(Clone, Debug)]
+pub struct SelfCheckConfig {
+    pub odin_dir: PathBuf,
+    pub approvals_dir: Option<PathBuf>,
+    pub quarantine_dir: PathBuf,
+    pub stale_lock_age: Duration,
+}
+
+impl SelfCheckConfig {
+    pub fn new(odin_dir: impl Into<PathBuf>) -> Self {
+        let odin_dir = odin_dir.into();
+        Self {
+            quarantine_dir: odin_dir.join("quarantine"),
+            odin_dir,
+            approvals_dir: None,
+            stale_lock_age: DEFAULT_STALE_LOCK_AGE,
+        }
+    }
+
+    pub fn with_approvals_dir(mut self, approvals_dir: impl Into<PathBuf>) -> Self {
+        self.approvals_dir = Some(approvals_dir.into());
+        self
+    }
+
+    pub fn with_quarantine_dir(mut self, quarantine_dir: impl Into<PathBuf>) -> Self {
+        self.quarantine_dir = quarantine_dir.into();
+        self
+    }
+
+    pub fn with_stale_lock_age(mut self, stale_lock_age: Duration) -> Self {
+        self.stale_lock_age = stale_lock_age;
+        self
+    }
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SelfCheckAction {
+    /// The problem was fixed in place (e.g. stale lock or temp file removed).
+    Repaired,
+    /// The file was moved aside into the quarantine directory.
+    Quarantined,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+pub struct SelfCheckFinding {
+    pub check: String,
+    pub path: PathBuf,
+    pub action: SelfCheckAction,
+    pub detail: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quarantined_to: Option<PathBuf>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, PartialEq, Eq)]
+pub struct SelfCheckReport {
+    pub checked: Vec<PathBuf>,
+    pub findings: Vec<SelfCheckFinding>,
+}
+
+impl SelfCheckReport {
+    pub fn is_clean(&self) -> bool {
+        self.findings.is_empty()
+    }
+}
+
+/// Validates and repairs runtime state, recording one audit event per finding plus a
+/// `selfcheck.completed` summary.
+pub fn run_selfcheck<A: AuditSink>(
+    config: &SelfCheckConfig,
+    audit: &A,
+) -> RuntimeResult<SelfCheckReport> {
+    let mut report = SelfCheckReport::default();
+
+    check_json_file(
+        config,
+        &mut report,
+        "state",
+        &config.odin_dir.join("state.json"),
+        validate_state,
+    )?;
+    check_json_file(
+        config,
+        &mut report,
+        "routing",
+        &config.odin_dir.join("routing.json"),
+        validate_routing,
+    )?;
+    check_stale_locks(config, &mut report)?;
+    if let Some(approvals_dir) = &config.approvals_dir {
+        check_approvals(config, approvals_dir, &mut report)?;
+    }
+
+    for finding in &report.findings {
+        let event_type = match finding.action {
+            SelfCheckAction::Repaired => "selfcheck.repaired",
+            SelfCheckAction::Quarantined => "selfcheck.quarantined",
+        };
+        audit.record(AuditRecord {
+            ts_unix: now_unix(),
+            event_type: event_type.to_string(),
+            severity: taxonomy::severity(event_type),
+            request_id: None,
+            task_id: None,
+            project: None,
+            trace_id: None,
+            metadata: serde_json::json!({
+                "check": finding.check,
+                "path": finding.path,
+                "detail": finding.detail,
+                "quarantined_to": finding.quarantined_to
+            }),
+        })?;
+    }
+    audit.record(AuditRecord {
+        ts_unix: now_unix(),
+        event_type: "selfcheck.completed".to_string(),
+        severity: Severity::Info,
+        request_id: None,
+        task_id: None,
+        project: None,
+        trace_id: None,
+        metadata: serde_json::json!({
+            "odin_dir": config.odin_dir,
+            "checked": report.checked.len(),
+            "findings": report.findings.len()
+        }),
+    })?;
+
+    Ok(report)
+}
+
+fn check_json_file(
+    config: &SelfCheckConfig,
+    report: &mut SelfCheckReport,
+    check: &str,
+    path: &Path,
+    validate: fn(&Value) -> Result<(), String>,
+) -> RuntimeResult<()> {
+    let raw = match fs::read(path) {
+        Ok(raw) => raw,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => {
+            return Err(RuntimeError::Execution(format!(
+                "selfcheck failed reading {}: {e}",
+                path.display()
+            )))
+        }
+    };
+    report.checked.push(path.to_path_buf());
+
+    let problem = match serde_json::from_slice::<Value>(&raw) {
+        Ok(value) => validate(&value).err(),
+        Err(e) => Some(format!("invalid json: {e}")),
+    };
+    if let Some(detail) = problem {
+        quarantine(config, report, check, path, detail)?;
+    }
+    Ok(())
+}
+
+fn validate_state(value: &Value) -> Result<(), String> {
+    let object = value
+        .as_object()
+        .ok_or_else(|| "state must be a JSON object".to_string())?;
+    if let Some(tasks) = object.get("dispatched_tasks") {
+        let tasks = tasks
+            .as_object()
+            .ok_or_else(|| "dispatched_tasks must be an object".to_string())?;
+        if let Some((task_id, _)) = tasks.iter().find(|(_, info)| !info.is_object()) {
+            return Err(format!("dispatched task {task_id} must be an object"));
+        }
+    }
+    Ok(())
+}
+
+fn validate_routing(value: &Value) -> Result<(), String> {
+    if value.is_object() {
+        Ok(())
+    } else {
+        Err("routing must be a JSON object".to_string())
+    }
+}
+
+fn check_stale_locks(config: &SelfCheckConfig, report: &mut SelfCheckReport) -> RuntimeResult<()> {
+    let Ok(entries) = fs::read_dir(&config.odin_dir) else {
+        return Ok(());
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let is_lock_dir = path.is_dir()
+            && path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.ends_with(".lock.d"));
+        if !is_lock_dir {
+            continue;
+        }
+        report.checked.push(path.clone());
+
+        let age = entry
+            .metadata()
+            .and_then(|meta| meta.modified())
+            .ok()
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+            .unwrap_or_default();
+        if age < config.stale_lock_age {
+            continue;
+        }
+        fs::remove_dir_all(&path).map_err(|e| {
+            RuntimeError::Execution(format!(
+                "selfcheck failed removing stale lock {}: {e}",
+                path.display()
+            ))
+        })?;
+        report.findings.push(SelfCheckFinding {
+            check: "lock".to_string(),
+            path,
+            action: SelfCheckAction::Repaired,
+            detail: format!("removed stale lock held for {}s", age.as_secs()),
+            quarantined_to: None,
+        });
+    }
+    Ok(())
+}
+
+fn check_approvals(
+    config: &SelfCheckConfig,
+    approvals_dir: &Path,
+    report: &mut SelfCheckReport,
+) -> RuntimeResult<()> {
+    let Ok(entries) = fs::read_dir(approvals_dir) else {
+        return Ok(());
+    };
+    let mut paths: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).collect();
+    paths.sort();
+
+    for path in paths {
+        let name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default()
+            .to_string();
+        if name.ends_with(".json.tmp") {
+            fs::remove_file(&path).map_err(|e| {
+                RuntimeError::Execution(format!(
+                    "selfcheck failed removing {}: {e}",
+                    path.display()
+                ))
+            })?;
+            report.findings.push(SelfCheckFinding {
+                check: "approvals".to_string(),
+                path,
+                action: SelfCheckAction::Repaired,
+                detail: "removed interrupted approval write".to_string(),
+                quarantined_to: None,
+            });
+            continue;
+        }
+        if !name.ends_with(".json") {
+            continue;
+        }
+        report.checked.push(path.clone());
+
+        let problem = match fs::read(&path).map_err(|e| e.to_string()).and_then(|raw| {
+            serde_json::from_slice::<PendingApproval>(&raw).map_err(|e| e.to_string())
+        }) {
+            Ok(pending) if approval_file_name(pending.request_id()) != name => Some(format!(
+                "approval file {name} holds unrelated request {}",
+                pending.request_id()
+            )),
+            Ok(pending) if pending.expires_at_unix < pending.created_at_unix => {
+                Some("approval expires before it was created".to_string())
+            }
+            Ok(_) => None,
+            Err(e) => Some(format!("invalid pending approval: {e}")),
+        };
+        if let Some(detail) = problem {
+            quarantine(config, report, "approvals", &path, detail)?;
+        }
+    }
+    Ok(())
+}
+
+fn quarantine(
+    config: &SelfCheckConfig,
+    report: &mut SelfCheckReport,
+    check: &str,
+    path: &Path,
+    detail: String,
+) -> RuntimeResult<()> {
+    fs::create_dir_all(&config.quarantine_dir).map_err(|e| {
+        RuntimeError::Execution(format!(
+            "selfcheck failed creating quarantine dir {}: {e}",
+            config.quarantine_dir.display()
+        ))
+    })?;
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "unnamed".to_string());
+    let target = config
+        .quarantine_dir
+        .join(format!("{file_name}.{}", now_unix()));
+    fs::rename(path, &target).map_err(|e| {
+        RuntimeError::Execution(format!(
+            "selfcheck failed quarantining {}: {e}",
+            path.display()
+        ))
+    })?;
+    report.findings.push(SelfCheckFinding {
+        check: check.to_string(),
+        path: path.to_path_buf(),
+        action: SelfCheckAction::Quarantined,
+        detail,
+        quarantined_to: Some(target),
+    });
+    Ok(())
+}
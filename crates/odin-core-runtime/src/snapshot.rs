@@ -0,0 +1,250 @@
+//! Pre-execution workspace snapshots for destructive actions. A destructive request whose
+//! input names a `workspace` directory is snapshotted before the executor runs, giving
+//! approved-but-regretted actions a recovery path.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use odin_plugin_protocol::SnapshotRef;
+
+use crate::{now_unix, RuntimeError, RuntimeResult};
+
+/// Metadata file written next to each copy snapshot's data directory.
+pub const SNAPSHOT_META_FILE: &str = "snapshot.json";
+
+pub trait Snapshotter: Send + Sync {
+    fn snapshot(&self, workspace: &Path, label: &str) -> RuntimeResult<SnapshotRef>;
+    fn restore(&self, snapshot: &SnapshotRef) -> RuntimeResult<()>;
+}
+
+/// Copies the workspace tree into `<root>/<id>/data`.
+#[derive(Clone, Debug)]
+pub struct CopySnapshotter {
+    root: PathBuf,
+}
+
+impl CopySnapshotter {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Loads the metadata of a snapshot previously taken under this root.
+    pub fn load(&self, id: &str) -> RuntimeResult<SnapshotRef> {
+        if id.is_empty() || id.contains(['/', '\\']) || id.starts_with('.') {
+            return Err(RuntimeError::InvalidInput(format!(
+                "invalid snapshot id: {id}"
+            )));
+        }
+        let meta = self.root.join(id).join(SNAPSHOT_META_FILE);
+        let raw = fs::read(&meta).map_err(|e| {
+            RuntimeError::InvalidInput(format!(
+                "snapshot {id} not found under {}: {e}",
+                self.root.display()
+            ))
+        })?;
+        serde_json::from_slice(&raw).map_err(|e| {
+            RuntimeError::Execution(format!("corrupt snapshot metadata {}: {e}", meta.display()))
+        })
+    }
+}
+
+impl Snapshotter for CopySnapshotter {
+    fn snapshot(&self, workspace: &Path, label: &str) -> RuntimeResult<SnapshotRef> {
+        if !workspace.is_dir() {
+            return Err(RuntimeError::InvalidInput(format!(
+                "snapshot workspace is not a directory: {}",
+                workspace.display()
+            )));
+        }
+        let created_at_unix = now_unix();
+        let id = snapshot_id(label, created_at_unix);
+        let dir = self.root.join(&id);
+        let data = dir.join("data");
+        fs::create_dir_all(&self.root)
+            .and_then(|()| fs::create_dir(&dir))
+            .map_err(|e| {
+                RuntimeError::Execution(format!("snapshot dir {} failed: {e}", dir.display()))
+            })?;
+        copy_tree(workspace, &data).map_err(|e| {
+            RuntimeError::Execution(format!("snapshot of {} failed: {e}", workspace.display()))
+        })?;
+
+        let snapshot = SnapshotRef {
+            id,
+            snapshotter: "copy".to_string(),
+            workspace: workspace.display().to_string(),
+            location: data.display().to_string(),
+            created_at_unix,
+        };
+        let meta = serde_json::to_vec_pretty(&snapshot).map_err(|e| {
+            RuntimeError::Execution(format!("snapshot metadata encode failed: {e}"))
+        })?;
+        fs::write(dir.join(SNAPSHOT_META_FILE), meta)
+            .map_err(|e| RuntimeError::Execution(format!("snapshot metadata write failed: {e}")))?;
+        Ok(snapshot)
+    }
+
+    fn restore(&self, snapshot: &SnapshotRef) -> RuntimeResult<()> {
+        let data = Path::new(&snapshot.location);
+        if !data.is_dir() {
+            return Err(RuntimeError::InvalidInput(format!(
+                "snapshot data missing: {}",
+                data.display()
+            )));
+        }
+        let workspace = Path::new(&snapshot.workspace);
+        let restore_err = |e: std::io::Error| {
+            RuntimeError::Execution(format!(
+                "restore of {} into {} failed: {e}",
+                snapshot.id,
+                workspace.display()
+            ))
+        };
+        if workspace.exists() {
+            for entry in fs::read_dir(workspace).map_err(restore_err)? {
+                let path = entry.map_err(restore_err)?.path();
+                if path.is_dir() && !path.is_symlink() {
+                    fs::remove_dir_all(&path).map_err(restore_err)?;
+                } else {
+                    fs::remove_file(&path).map_err(restore_err)?;
+                }
+            }
+        }
+        copy_tree(data, workspace).map_err(restore_err)
+    }
+}
+
+/// Delegates to external tooling (e.g. btrfs or zfs). `{workspace}` and `{snapshot}` in
+/// the argv are replaced with the workspace path and snapshot id.
+#[derive(Clone, Debug)]
+pub struct CommandSnapshotter {
+    name: String,
+    snapshot_argv: Vec<String>,
+    restore_argv: Vec<String>,
+}
+
+impl CommandSnapshotter {
+    pub fn new(
+        name: impl Into<String>,
+        snapshot_argv: Vec<String>,
+        restore_argv: Vec<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            snapshot_argv,
+            restore_argv,
+        }
+    }
+
+    fn run(&self, argv: &[String], workspace: &str, snapshot: &str) -> RuntimeResult<String> {
+        let argv: Vec<String> = argv
+            .iter()
+            .map(|arg| {
+                arg.replace("{workspace}", workspace)
+                    .replace("{snapshot}", snapshot)
+            })
+            .collect();
+        let (program, args) = argv.split_first().ok_or_else(|| {
+            RuntimeError::InvalidInput(format!("{} snapshot command is empty", self.name))
+        })?;
+        let output = Command::new(program)
+            .args(args)
+            .output()
+            .map_err(|e| RuntimeError::Execution(format!("{program} failed to start: {e}")))?;
+        if !output.status.success() {
+            return Err(RuntimeError::Execution(format!(
+                "{program} failed (exit={}): {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+}
+
+impl Snapshotter for CommandSnapshotter {
+    fn snapshot(&self, workspace: &Path, label: &str) -> RuntimeResult<SnapshotRef> {
+        let created_at_unix = now_unix();
+        let id = snapshot_id(label, created_at_unix);
+        let workspace = workspace.display().to_string();
+        let printed = self.run(&self.snapshot_argv, &workspace, &id)?;
+        Ok(SnapshotRef {
+            location: if printed.is_empty() {
+                id.clone()
+            } else {
+                printed
+            },
+            id,
+            snapshotter: self.name.clone(),
+            workspace,
+            created_at_unix,
+        })
+    }
+
+    fn restore(&self, snapshot: &SnapshotRef) -> RuntimeResult<()> {
+        self.run(&self.restore_argv, &snapshot.workspace, &snapshot.id)
+            .map(|_| ())
+    }
+}
+
+/// `snap-<label>-<unix secs>-<pid>-<seq>`: the process id and a per-process sequence keep ids
+/// taken within the same second apart.
+fn snapshot_id(label: &str, created_at_unix: u64) -> String {
+    static SEQUENCE: AtomicU64 = AtomicU64::new(0);
+    let seq = SEQUENCE.fetch_add(1, Ordering::Relaxed);
+    format!(
+        "snap-{}-{created_at_unix}-{}-{seq}",
+        sanitize(label),
+        std::process::id()
+    )
+}
+
+fn sanitize(label: &str) -> String {
+    label
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn copy_tree(from: &Path, to: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let source = entry.path();
+        let target = to.join(entry.file_name());
+        let file_type = entry.file_type()?;
+        if file_type.is_symlink() {
+            copy_symlink(&source, &target)?;
+        } else if file_type.is_dir() {
+            copy_tree(&source, &target)?;
+        } else {
+            fs::copy(&source, &target)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn copy_symlink(source: &Path, target: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(fs::read_link(source)?, target)
+}
+
+#[cfg(not(unix))]
+fn copy_symlink(source: &Path, _target: &Path) -> std::io::Result<()> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        format!("cannot snapshot symlink {}", source.display()),
+    ))
+}
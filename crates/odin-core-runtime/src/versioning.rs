@@ -0,0 +1,115 @@
+//! Plugin-to-core version negotiation. The result is attached to every dispatch audit
+//! record and exposed as metric labels so operators can see which plugin versions are
+//! active before a breaking core upgrade.
+
+use std::collections::BTreeMap;
+
+use odin_plugin_protocol::PluginManifest;
+use semver::{Version, VersionReq};
+use serde::Serialize;
+
+use crate::{RuntimeError, RuntimeResult};
+
+/// Version of this runtime, matched against manifest `compatibility.core_version`.
+pub const CORE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Plugin protocol versions this core can speak, oldest first.
+pub const SUPPORTED_PROTOCOL_VERSIONS: &[u32] = &[1];
+
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+pub struct PluginVersionInfo {
+    pub plugin: String,
+    pub plugin_version: String,
+    /// The manifest's declared `compatibility.core_version` range.
+    pub core_compatibility: String,
+    pub core_version: String,
+    /// False when the running core falls outside the declared range (or the range does
+    /// not parse); dispatch still proceeds so the mismatch can be observed first.
+    pub core_compatible: bool,
+    pub protocol_version: u32,
+}
+
+impl PluginVersionInfo {
+    /// Labels of the plugin dispatch duration and failure metrics.
+    pub fn metric_labels(&self) -> BTreeMap<&'static str, String> {
+        BTreeMap::from([
+            ("plugin", self.plugin.clone()),
+            ("plugin_version", self.plugin_version.clone()),
+            ("core_compatibility", self.core_compatibility.clone()),
+            ("core_compatible", self.core_compatible.to_string()),
+            ("protocol_version", self.protocol_version.to_string()),
+        ])
+    }
+}
+
+/// Picks the highest protocol version supported by both sides; the manifest
+/// `schema_version` is the newest protocol the plugin speaks.
+pub fn negotiate_protocol_version(plugin_max: u32) -> Option<u32> {
+    SUPPORTED_PROTOCOL_VERSIONS
+        .iter()
+        .copied()
+        .filter(|version| *version <= plugin_max)
+        .max()
+}
+
+pub fn negotiate(manifest: &PluginManifest) -> RuntimeResult<PluginVersionInfo> {
+    let protocol_version =
+        negotiate_protocol_version(manifest.schema_version).ok_or_else(|| {
+            RuntimeError::Plugin(format!(
+                "plugin {} speaks protocol {} but core supports {:?}",
+                manifest.plugin.name, manifest.schema_version, SUPPORTED_PROTOCOL_VERSIONS
+            ))
+        })?;
+    let core_compatibility = manifest.plugin.compatibility.core_version.clone();
+    Ok(PluginVersionInfo {
+        plugin: manifest.plugin.name.clone(),
+        plugin_version: manifest.plugin.version.clone(),
+        core_compatible: core_satisfies(&core_compatibility, CORE_VERSION),
+        core_compatibility,
+        core_version: CORE_VERSION.to_string(),
+        protocol_version,
+    })
+}
+
+/// Evaluates a manifest range such as `>=0.1.0 <0.2.0` (space- or comma-separated).
+pub fn core_satisfies(range: &str, core_version: &str) -> bool {
+    let Ok(version) = Version::parse(core_version) else {
+        return false;
+    };
+    let mut comparators = Vec::new();
+    let mut pending_op = String::new();
+    for token in range.split(|c: char| c.is_whitespace() || c == ',') {
+        if token.is_empty() {
+            continue;
+        }
+        if token
+            .chars()
+            .all(|c| matches!(c, '<' | '>' | '=' | '~' | '^'))
+        {
+            pending_op.push_str(token);
+            continue;
+        }
+        comparators.push(format!("{}{token}", std::mem::take(&mut pending_op)));
+    }
+    VersionReq::parse(&comparators.join(", ")).is_ok_and(|req| req.matches(&version))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifest_ranges_are_evaluated_against_core() {
+        assert!(core_satisfies(">=0.1.0 <0.2.0", "0.1.5"));
+        assert!(core_satisfies(">= 0.1.0, < 1.0.0", "0.9.0"));
+        assert!(!core_satisfies(">=0.1.0 <0.2.0", "0.2.0"));
+        assert!(!core_satisfies("not a range", "0.1.0"));
+    }
+
+    #[test]
+    fn protocol_negotiation_picks_highest_common_version() {
+        assert_eq!(negotiate_protocol_version(1), Some(1));
+        assert_eq!(negotiate_protocol_version(7), Some(1));
+        assert_eq!(negotiate_protocol_version(0), None);
+    }
+}
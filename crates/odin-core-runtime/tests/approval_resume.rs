@@ -0,0 +1,339 @@
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use odin_audit::{AuditError, AuditRecord, AuditSink};
+use odin_core_runtime::approvals::{ApprovalStore, FileApprovalStore};
+use odin_core_runtime::revocation::CapabilityRevocation;
+use odin_core_runtime::{DryRunExecutor, OrchestratorRuntime};
+use odin_governance::duties::SeparationOfDuties;
+use odin_plugin_protocol::{ActionRequest, ActionStatus, CapabilityRequest, RiskTier};
+use odin_policy_engine::budget::BudgetPolicyEngine;
+use odin_policy_engine::quota::{QuotaAction, QuotaPolicyEngine, QuotaRule, QuotaWindow};
+use odin_policy_engine::StaticPolicyEngine;
+
+#[derive(Clone, Default)]
+struct MemoryAuditSink {
+    records: Arc<Mutex<Vec<AuditRecord>>>,
+}
+
+impl MemoryAuditSink {
+    fn find(&self, event_type: &str) -> Option<AuditRecord> {
+        self.records
+            .lock()
+            .expect("lock")
+            .iter()
+            .find(|record| record.event_type == event_type)
+            .cloned()
+    }
+}
+
+impl AuditSink for MemoryAuditSink {
+    fn record(&self, record: AuditRecord) -> Result<(), AuditError> {
+        self.records
+            .lock()
+            .map_err(|_| AuditError::Write("poisoned lock".to_string()))?
+            .push(record);
+        Ok(())
+    }
+}
+
+fn temp_dir(name: &str) -> PathBuf {
+    let unique = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    std::env::temp_dir().join(format!(
+        "odin-approvals-{name}-{}-{unique}",
+        std::process::id()
+    ))
+}
+
+fn destructive_request() -> ActionRequest {
+    ActionRequest {
+        request_id: "req-delete-branch".to_string(),
+        risk_tier: RiskTier::Destructive,
+        capability: CapabilityRequest {
+            plugin: "example.safe-github".to_string(),
+            project: "demo".to_string(),
+            capability: "repo.branch.delete".to_string(),
+            scope: vec!["project".to_string()],
+            reason: "cleanup".to_string(),
+        },
+        trace_id: None,
+        input: serde_json::Value::Null,
+        requested_by: None,
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn approval_policy() -> StaticPolicyEngine {
+    let mut policy = StaticPolicyEngine::default();
+    policy.set_require_approval_for_destructive(true);
+    policy.allow_capability("example.safe-github", "demo", "repo.branch.delete");
+    policy
+}
+
+#[test]
+fn pending_approval_persists_and_resumes_after_restart() {
+    let dir = temp_dir("resume");
+    let runtime = OrchestratorRuntime::new(
+        approval_policy(),
+        MemoryAuditSink::default(),
+        DryRunExecutor,
+    )
+    .with_approval_store(FileApprovalStore::new(&dir));
+    let outcome = runtime
+        .handle_action(destructive_request())
+        .expect("outcome");
+    assert_eq!(outcome.status, ActionStatus::ApprovalPending);
+    assert_eq!(runtime.pending_approvals().expect("list").len(), 1);
+
+    // A fresh runtime sharing the same store picks the request back up.
+    let audit = MemoryAuditSink::default();
+    let resumed_runtime =
+        OrchestratorRuntime::new(approval_policy(), audit.clone(), DryRunExecutor)
+            .with_approval_store(FileApprovalStore::new(&dir));
+    let outcome = resumed_runtime
+        .resume_approved("req-delete-branch", "ops-lead")
+        .expect("resume");
+
+    assert_eq!(outcome.status, ActionStatus::Executed);
+    let granted = audit.find("approval.granted").expect("granted audit");
+    assert_eq!(granted.metadata["approver"], "ops-lead");
+    assert!(FileApprovalStore::new(&dir)
+        .get("req-delete-branch")
+        .expect("get")
+        .is_none());
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+#[test]
+fn request_ids_that_are_not_file_names_are_parked_under_a_hashed_name() {
+    let dir = temp_dir("hashed");
+    let runtime = OrchestratorRuntime::new(
+        approval_policy(),
+        MemoryAuditSink::default(),
+        DryRunExecutor,
+    )
+    .with_approval_store(FileApprovalStore::new(&dir));
+    let mut request = destructive_request();
+    request.request_id = "task:42/../delete branch".to_string();
+    let outcome = runtime.handle_action(request).expect("outcome");
+    assert_eq!(outcome.status, ActionStatus::ApprovalPending);
+
+    let names: Vec<String> = std::fs::read_dir(&dir)
+        .expect("read dir")
+        .map(|entry| {
+            entry
+                .expect("entry")
+                .file_name()
+                .to_string_lossy()
+                .into_owned()
+        })
+        .collect();
+    assert_eq!(names.len(), 1);
+    assert!(names[0].starts_with("_sha256-"));
+    let outcome = runtime
+        .resume_approved("task:42/../delete branch", "ops-lead")
+        .expect("resume");
+    assert_eq!(outcome.status, ActionStatus::Executed);
+    assert!(runtime.pending_approvals().expect("list").is_empty());
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+#[test]
+fn resume_rechecks_policy_and_expiry() {
+    let runtime = OrchestratorRuntime::new(
+        approval_policy(),
+        MemoryAuditSink::default(),
+        DryRunExecutor,
+    )
+    .with_approval_ttl(Duration::ZERO);
+    runtime
+        .handle_action(destructive_request())
+        .expect("outcome");
+    let outcome = runtime
+        .resume_approved("req-delete-branch", "ops-lead")
+        .expect("resume");
+    assert_eq!(outcome.status, ActionStatus::Blocked);
+    assert_eq!(outcome.detail, "approval_expired");
+
+    let dir = temp_dir("revoked");
+    OrchestratorRuntime::new(
+        approval_policy(),
+        MemoryAuditSink::default(),
+        DryRunExecutor,
+    )
+    .with_approval_store(FileApprovalStore::new(&dir))
+    .handle_action(destructive_request())
+    .expect("outcome");
+    let revoked = OrchestratorRuntime::new(
+        StaticPolicyEngine::default(),
+        MemoryAuditSink::default(),
+        DryRunExecutor,
+    )
+    .with_approval_store(FileApprovalStore::new(&dir));
+    let outcome = revoked
+        .resume_approved("req-delete-branch", "ops-lead")
+        .expect("resume");
+    assert_eq!(outcome.status, ActionStatus::Blocked);
+    assert_eq!(outcome.detail, "capability_not_granted");
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+#[test]
+fn revoking_a_capability_drops_pending_approvals_and_blocks_new_requests() {
+    let audit = MemoryAuditSink::default();
+    let runtime = OrchestratorRuntime::new(approval_policy(), audit.clone(), DryRunExecutor);
+    let outcome = runtime
+        .handle_action(destructive_request())
+        .expect("outcome");
+    assert_eq!(outcome.status, ActionStatus::ApprovalPending);
+
+    let dropped = runtime
+        .revoke_capability(
+            CapabilityRevocation::new("example.safe-github", "repo.branch.delete")
+                .with_scope("project"),
+        )
+        .expect("revoke");
+    assert_eq!(dropped, ["req-delete-branch"]);
+    assert!(runtime.pending_approvals().expect("list").is_empty());
+    let revoked = audit
+        .find("governance.capability.revoked")
+        .expect("revocation audited");
+    assert_eq!(
+        revoked.metadata["revoked_approvals"][0],
+        "req-delete-branch"
+    );
+    assert!(audit.find("approval.revoked").is_some());
+
+    let outcome = runtime
+        .handle_action(destructive_request())
+        .expect("outcome");
+    assert_eq!(outcome.status, ActionStatus::Blocked);
+    assert_eq!(outcome.detail, "capability_revoked");
+}
+
+#[test]
+fn resume_requires_known_request_and_approver() {
+    let runtime = OrchestratorRuntime::new(
+        approval_policy(),
+        MemoryAuditSink::default(),
+        DryRunExecutor,
+    );
+    assert!(runtime.resume_approved("req-unknown", "ops-lead").is_err());
+
+    runtime
+        .handle_action(destructive_request())
+        .expect("outcome");
+    assert!(runtime.resume_approved("req-delete-branch", " ").is_err());
+}
+
+#[test]
+fn separation_of_duties_blocks_self_approval_and_keeps_it_pending() {
+    let audit = MemoryAuditSink::default();
+    let runtime = OrchestratorRuntime::new(approval_policy(), audit.clone(), DryRunExecutor)
+        .with_separation_of_duties(SeparationOfDuties::default().with_project("demo"));
+    let request = ActionRequest {
+        requested_by: Some("dev-alice".to_string()),
+        ..destructive_request()
+    };
+    runtime.handle_action(request).expect("outcome");
+
+    let outcome = runtime
+        .resume_approved("req-delete-branch", "Dev-Alice")
+        .expect("resume");
+    assert_eq!(outcome.status, ActionStatus::Blocked);
+    assert_eq!(outcome.detail, "separation_of_duties_violation");
+    let blocked = audit
+        .find("approval.self_approval_blocked")
+        .expect("self approval audited");
+    assert_eq!(blocked.metadata["requested_by"], "dev-alice");
+    assert_eq!(runtime.pending_approvals().expect("list").len(), 1);
+
+    let outcome = runtime
+        .resume_approved("req-delete-branch", "ops-lead")
+        .expect("resume");
+    assert_ne!(outcome.status, ActionStatus::Blocked);
+}
+
+#[test]
+fn separation_of_duties_blocks_requests_with_an_unknown_requester() {
+    let runtime = OrchestratorRuntime::new(
+        approval_policy(),
+        MemoryAuditSink::default(),
+        DryRunExecutor,
+    )
+    .with_separation_of_duties(SeparationOfDuties::default().with_project("demo"));
+    runtime
+        .handle_action(destructive_request())
+        .expect("outcome");
+
+    let outcome = runtime
+        .resume_approved("req-delete-branch", "ops-lead")
+        .expect("resume");
+    assert_eq!(outcome.status, ActionStatus::Blocked);
+    assert_eq!(outcome.detail, "separation_of_duties_violation");
+    assert_eq!(runtime.pending_approvals().expect("list").len(), 1);
+}
+
+#[test]
+fn scope_introspection_and_resume_rechecks_do_not_spend_quota() {
+    let quota = Arc::new(
+        QuotaPolicyEngine::new(approval_policy()).with_quota(QuotaRule {
+            plugin: "example.safe-github".to_string(),
+            capability: "repo.branch.delete".to_string(),
+            limit: 1,
+            window: QuotaWindow::Day,
+            on_exceeded: QuotaAction::Deny,
+        }),
+    );
+    let runtime =
+        OrchestratorRuntime::new(quota.clone(), MemoryAuditSink::default(), DryRunExecutor);
+    runtime
+        .handle_action(destructive_request())
+        .expect("outcome");
+    runtime
+        .granted_scopes("example.safe-github", "demo", "repo.branch.delete")
+        .expect("scopes");
+    assert!(quota.counters(now()).expect("counters").is_empty());
+
+    let outcome = runtime
+        .resume_approved("req-delete-branch", "ops-lead")
+        .expect("resume");
+    assert_eq!(outcome.status, ActionStatus::Executed);
+    let counters = quota.counters(now()).expect("counters");
+    assert_eq!(counters.len(), 1);
+    assert_eq!(counters[0].count, 1);
+}
+
+#[test]
+fn scope_introspection_and_resume_rechecks_do_not_charge_budgets() {
+    let budget = Arc::new(
+        BudgetPolicyEngine::new(approval_policy())
+            .with_cost("repo.branch.delete", 5)
+            .with_daily_budget("demo", 5),
+    );
+    let runtime =
+        OrchestratorRuntime::new(budget.clone(), MemoryAuditSink::default(), DryRunExecutor);
+    runtime
+        .handle_action(destructive_request())
+        .expect("outcome");
+    runtime
+        .granted_scopes("example.safe-github", "demo", "repo.branch.delete")
+        .expect("scopes");
+    assert!(budget.spend(now()).expect("spend").is_empty());
+
+    let outcome = runtime
+        .resume_approved("req-delete-branch", "ops-lead")
+        .expect("resume");
+    assert_eq!(outcome.status, ActionStatus::Executed);
+    assert_eq!(budget.spend(now()).expect("spend")[0].spent, 5);
+}
@@ -1,10 +1,15 @@
 use std::sync::{Arc, Mutex};
 
 use odin_audit::{AuditError, AuditRecord, AuditSink};
-use odin_core_runtime::{DryRunExecutor, OrchestratorRuntime};
+use odin_core_runtime::{
+    DryRunExecutor, OrchestratorRuntime, PluginCapabilityRef, PluginDirective, PluginEventRunner,
+    RuntimeResult, TaskIngress,
+};
+use odin_governance::adapters::PluginPolicyAdapter;
+use odin_governance::plugins::PermissionDecision;
 use odin_plugin_protocol::{
     ActionRequest, ActionStatus, CapabilityManifest, CapabilityRequest, DelegationCapability,
-    RiskTier,
+    EventEnvelope, PluginPermissionEnvelope, RiskTier,
 };
 use odin_policy_engine::StaticPolicyEngine;
 
@@ -45,9 +50,11 @@ fn request_for(plugin: &str, capability: &str) -> ActionRequest {
             scope: vec!["project".to_string()],
             reason: "unit test".to_string(),
         },
+        trace_id: None,
         input: serde_json::json!({
             "url": "https://example.com"
         }),
+        requested_by: None,
     }
 }
 
@@ -62,9 +69,11 @@ fn request_for_with_scope(plugin: &str, capability: &str, scope: &[&str]) -> Act
             scope: scope.iter().map(|value| value.to_string()).collect(),
             reason: "unit test".to_string(),
         },
+        trace_id: None,
         input: serde_json::json!({
             "url": "https://example.com"
         }),
+        requested_by: None,
     }
 }
 
@@ -79,6 +88,17 @@ fn manifest_allowing(plugin: &str, capability: &str) -> CapabilityManifest {
     }
 }
 
+fn manifest_allowing_scope(plugin: &str, capability: &str, scope: &str) -> CapabilityManifest {
+    CapabilityManifest {
+        schema_version: 1,
+        plugin: plugin.to_string(),
+        capabilities: vec![DelegationCapability {
+            id: capability.to_string(),
+            scope: vec![scope.to_string()],
+        }],
+    }
+}
+
 #[test]
 fn denies_capability_not_in_manifest() {
     let mut policy = StaticPolicyEngine::default();
@@ -123,6 +143,68 @@ fn denies_huginn_for_non_browser_agent() {
         .any(|event| event == "governance.manifest.denied"));
 }
 
+/// A git-writer plugin that may only push to branches listed in its `git.push` scope.
+struct BranchAdapter;
+
+impl PluginPolicyAdapter for BranchAdapter {
+    fn plugin(&self) -> &str {
+        "example.git-writer"
+    }
+
+    fn reserves(&self, capability: &str) -> bool {
+        capability == "git.push"
+    }
+
+    fn evaluate(
+        &self,
+        _capability: &str,
+        input: &serde_json::Value,
+        _envelope: &PluginPermissionEnvelope,
+        _task_id: Option<&str>,
+    ) -> Option<PermissionDecision> {
+        let branch = input.get("branch").and_then(|branch| branch.as_str());
+        Some(if branch == Some("main") {
+            PermissionDecision::Deny {
+                reason_code: "git_protected_branch".to_string(),
+            }
+        } else {
+            PermissionDecision::Allow {
+                reason_code: "git_branch_allowed".to_string(),
+            }
+        })
+    }
+}
+
+#[test]
+fn registered_policy_adapters_refine_manifest_grants() {
+    let mut policy = StaticPolicyEngine::default();
+    policy.allow_capability("example.git-writer", "demo", "git.push");
+    policy.allow_capability("example.safe-github", "demo", "git.push");
+
+    let runtime = OrchestratorRuntime::new(policy, MemoryAuditSink::default(), DryRunExecutor)
+        .with_policy_adapter(Arc::new(BranchAdapter));
+    let push = |plugin: &str, branch: &str| {
+        let mut request = request_for(plugin, "git.push");
+        request.input = serde_json::json!({ "branch": branch });
+        runtime
+            .handle_action_with_manifest(request, &manifest_allowing(plugin, "git.push"))
+            .expect("outcome")
+    };
+
+    assert_eq!(
+        push("example.git-writer", "feature").status,
+        ActionStatus::Executed
+    );
+    assert_eq!(
+        push("example.git-writer", "main").detail,
+        "git_protected_branch"
+    );
+    assert_eq!(
+        push("example.safe-github", "feature").detail,
+        "plugin_permission_denied"
+    );
+}
+
 #[test]
 fn denies_unknown_huginn_capability_fail_closed() {
     let mut policy = StaticPolicyEngine::default();
@@ -262,9 +344,11 @@ fn executes_huginn_observe_domain_with_domain_input() {
                     scope: vec!["example.com".to_string()],
                     reason: "unit test".to_string(),
                 },
+                trace_id: None,
                 input: serde_json::json!({
                     "domain": "example.com"
                 }),
+                requested_by: None,
             },
             &CapabilityManifest {
                 schema_version: 1,
@@ -285,3 +369,199 @@ fn executes_huginn_observe_domain_with_domain_input() {
 
     assert_eq!(outcome.status, ActionStatus::Executed);
 }
+
+#[test]
+fn huginn_interactions_wait_for_approval_only_in_interact_mode() {
+    let mut policy = StaticPolicyEngine::default();
+    policy.allow_capability("huginn", "demo", "huginn.click");
+    let runtime = OrchestratorRuntime::new(policy, MemoryAuditSink::default(), DryRunExecutor);
+    let grant = |id: &str, scope: &[&str]| DelegationCapability {
+        id: id.to_string(),
+        scope: scope.iter().map(|value| value.to_string()).collect(),
+    };
+    let mut manifest = CapabilityManifest {
+        schema_version: 1,
+        plugin: "huginn".to_string(),
+        capabilities: vec![
+            grant("huginn.enabled", &[]),
+            grant("huginn.observe_url", &["example.com"]),
+            grant("huginn.click", &["example.com"]),
+        ],
+    };
+    let click = || request_for_with_scope("huginn", "huginn.click", &["example.com"]);
+
+    let outcome = runtime
+        .handle_action_with_manifest(click(), &manifest)
+        .expect("read-only outcome");
+    assert_eq!(outcome.status, ActionStatus::Blocked);
+    assert_eq!(outcome.detail, "mode_not_supported");
+
+    manifest.capabilities.push(grant("huginn.interact", &[]));
+    let outcome = runtime
+        .handle_action_with_manifest(click(), &manifest)
+        .expect("interact outcome");
+    assert_eq!(outcome.status, ActionStatus::ApprovalPending);
+    assert_eq!(outcome.detail, "interaction_requires_approval");
+
+    let resumed = runtime
+        .resume_approved(&outcome.request_id, "operator")
+        .expect("resume");
+    assert_eq!(resumed.status, ActionStatus::Executed);
+}
+
+#[test]
+fn registered_envelopes_replace_the_manifest_for_policy_adapters() {
+    let mut policy = StaticPolicyEngine::default();
+    policy.allow_capability("huginn", "demo", "huginn.observe_url");
+    let manifest = CapabilityManifest {
+        schema_version: 1,
+        plugin: "huginn".to_string(),
+        capabilities: vec![
+            DelegationCapability {
+                id: "huginn.enabled".to_string(),
+                scope: vec![],
+            },
+            DelegationCapability {
+                id: "huginn.observe_url".to_string(),
+                scope: vec!["project".to_string()],
+            },
+        ],
+    };
+    let permissions = odin_governance::envelopes::parse_permission_registry(
+        "envelopes:\n  - plugin: huginn\n    trust_level: caution\n    permissions:\n      \
+         - id: browser.observe\n        scope: [example.com]\n",
+    )
+    .expect("envelopes");
+    let runtime = OrchestratorRuntime::new(policy, MemoryAuditSink::default(), DryRunExecutor)
+        .with_permission_registry(permissions);
+
+    let outcome = runtime
+        .handle_action_with_manifest(request_for("huginn", "huginn.observe_url"), &manifest)
+        .expect("outcome");
+    assert_eq!(outcome.status, ActionStatus::Blocked);
+    assert_eq!(outcome.detail, "plugin_disabled");
+}
+
+#[test]
+fn envelopes_expanded_at_load_are_not_expanded_again_per_request() {
+    let mut policy = StaticPolicyEngine::default();
+    policy.allow_capability("example.safe-github", "demo", "repo.read");
+
+    let templates = odin_governance::scopes::parse_scope_templates(
+        "schema_version: 1\nprojects:\n  demo:\n    templates:\n      repo-readonly: [project, tenant]\n",
+    )
+    .expect("templates");
+    let permissions = odin_governance::envelopes::parse_permission_registry(
+        "projects:\n  - project: demo\n    envelopes:\n      - plugin: example.safe-github\n        \
+         trust_level: caution\n        permissions:\n          - id: repo.read\n            \
+         scope: [\"template:repo-readonly\"]\n",
+    )
+    .expect("envelopes");
+    let (permissions, expansions) = templates.expand_registry(&permissions).expect("expand");
+    assert_eq!(expansions.len(), 1);
+
+    let audit = MemoryAuditSink::default();
+    let runtime = OrchestratorRuntime::new(policy, audit.clone(), DryRunExecutor)
+        .with_permission_registry(permissions);
+    let grant = runtime
+        .granted_scopes("example.safe-github", "demo", "repo.read")
+        .expect("scopes");
+    assert_eq!(grant.scopes, vec!["project", "tenant"]);
+
+    let outcome = runtime
+        .handle_action_with_manifest(
+            request_for_with_scope("example.safe-github", "repo.read", &["tenant"]),
+            &manifest_allowing_scope("example.safe-github", "repo.read", "tenant"),
+        )
+        .expect("outcome");
+    assert_eq!(outcome.status, ActionStatus::Executed);
+    assert!(!audit
+        .events()
+        .iter()
+        .any(|event| event == "governance.scope.expanded"));
+}
+
+#[test]
+fn denies_manifest_scope_template_references() {
+    let mut policy = StaticPolicyEngine::default();
+    policy.allow_capability("example.safe-github", "demo", "repo.read");
+
+    let audit = MemoryAuditSink::default();
+    let runtime = OrchestratorRuntime::new(policy, audit.clone(), DryRunExecutor);
+    let outcome = runtime
+        .handle_action_with_manifest(
+            request_for("example.safe-github", "repo.read"),
+            &manifest_allowing_scope("example.safe-github", "repo.read", "template:missing"),
+        )
+        .expect("outcome");
+
+    assert_eq!(outcome.status, ActionStatus::Blocked);
+    assert_eq!(outcome.detail, "manifest_scope_template_unknown");
+}
+
+struct InstalledRunner;
+
+impl PluginEventRunner for InstalledRunner {
+    fn dispatch_event(
+        &self,
+        _plugin: &str,
+        _event: &EventEnvelope,
+    ) -> RuntimeResult<Vec<PluginDirective>> {
+        let request = |id: &str| PluginDirective::RequestCapability {
+            capability: PluginCapabilityRef {
+                id: id.to_string(),
+                project: None,
+            },
+            reason: "directive".to_string(),
+            input: serde_json::Value::Null,
+            risk_tier: None,
+        };
+        Ok(vec![request("repo.read"), request("repo.delete")])
+    }
+
+    fn capability_manifest(&self, plugin: &str) -> RuntimeResult<Option<CapabilityManifest>> {
+        Ok(Some(manifest_allowing(plugin, "repo.read")))
+    }
+}
+
+struct DiscardIngress;
+
+impl TaskIngress for DiscardIngress {
+    fn write_task_payload(&self, _payload: &str) -> RuntimeResult<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn directive_requests_are_checked_against_installed_manifest() {
+    let mut policy = StaticPolicyEngine::default();
+    policy.allow_capability("example.safe-github", "demo", "repo.read");
+    policy.allow_capability("example.safe-github", "demo", "repo.delete");
+
+    let audit = MemoryAuditSink::default();
+    let runtime = OrchestratorRuntime::new(policy, audit.clone(), DryRunExecutor);
+    let task = serde_json::json!({
+        "schema_version": 1,
+        "task_id": "task-manifest",
+        "type": "watchdog_poll",
+        "source": "keepalive",
+        "created_at": "2026-02-25T00:00:00Z",
+        "payload": {
+            "task_type": "repo.poll",
+            "source_key": "repo-check",
+            "project": "demo",
+            "plugin": "example.safe-github"
+        }
+    });
+    let outcomes = runtime
+        .handle_task(&task.to_string(), &InstalledRunner, &DiscardIngress)
+        .expect("outcomes");
+
+    assert_eq!(outcomes[0].status, ActionStatus::Executed);
+    assert_eq!(outcomes[1].status, ActionStatus::Blocked);
+    assert_eq!(outcomes[1].detail, "manifest_capability_not_granted");
+    assert!(audit
+        .events()
+        .iter()
+        .any(|event| event == "governance.manifest.denied"));
+}
@@ -0,0 +1,131 @@
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use odin_core_runtime::command::CommandActionExecutor;
+use odin_core_runtime::{ActionExecutor, RuntimeError};
+use odin_plugin_protocol::{ActionRequest, CapabilityRequest, RiskTier};
+
+fn temp_dir(name: &str) -> PathBuf {
+    let unique = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    let dir = std::env::temp_dir().join(format!(
+        "odin-command-{name}-{}-{unique}",
+        std::process::id()
+    ));
+    fs::create_dir_all(&dir).expect("create temp dir");
+    dir
+}
+
+fn request(command: &str, workspace: &str) -> ActionRequest {
+    ActionRequest {
+        request_id: "req-command".to_string(),
+        risk_tier: RiskTier::Safe,
+        capability: CapabilityRequest {
+            plugin: "private.ops-watchdog".to_string(),
+            project: "private".to_string(),
+            capability: "command.run".to_string(),
+            scope: vec!["project".to_string()],
+            reason: "inspect workspace".to_string(),
+        },
+        trace_id: None,
+        input: serde_json::json!({ "command": command, "workspace": workspace }),
+        requested_by: None,
+    }
+}
+
+#[test]
+fn allowlisted_command_returns_real_output() {
+    let workspace = temp_dir("ok");
+    fs::write(workspace.join("notes.txt"), "hello from the workspace\n").expect("write");
+    let ws = workspace.display().to_string();
+    let executor = CommandActionExecutor::new(["cat"], [ws.as_str()]);
+
+    let output = executor
+        .execute(&request(&format!("cat {ws}/notes.txt"), &ws))
+        .expect("command runs");
+
+    assert_eq!(output["exit_code"], 0);
+    assert_eq!(output["stdout"], "hello from the workspace\n");
+    assert_eq!(output["truncated"], false);
+    let _ = fs::remove_dir_all(workspace);
+}
+
+#[test]
+fn output_past_the_limit_is_truncated_without_stalling_the_command() {
+    let workspace = temp_dir("flood");
+    fs::write(workspace.join("big.txt"), vec![b'x'; 1024 * 1024]).expect("write");
+    let ws = workspace.display().to_string();
+    let executor = CommandActionExecutor::new(["cat"], [ws.as_str()])
+        .with_max_output_bytes(1024)
+        .with_timeout(Duration::from_secs(20));
+
+    let started = Instant::now();
+    let output = executor
+        .execute(&request(&format!("cat {ws}/big.txt"), &ws))
+        .expect("command runs");
+
+    assert!(started.elapsed() < Duration::from_secs(10));
+    assert_eq!(output["exit_code"], 0);
+    assert_eq!(output["stdout"].as_str().map(str::len), Some(1024));
+    assert_eq!(output["truncated"], true);
+    let _ = fs::remove_dir_all(workspace);
+}
+
+#[test]
+fn command_policy_rejects_before_spawning() {
+    let workspace = temp_dir("deny");
+    let outside = temp_dir("outside");
+    fs::write(workspace.join("a"), "a").expect("write");
+    let ws = workspace.display().to_string();
+    let executor = CommandActionExecutor::new(["cat"], [ws.as_str()]);
+
+    let cases = [
+        (
+            format!("rm -rf {ws}"),
+            ws.clone(),
+            "command_not_allowlisted",
+        ),
+        (
+            format!("cat {ws}/a; cat /etc/passwd"),
+            ws.clone(),
+            "command_unsafe_shell_syntax",
+        ),
+        (
+            "cat /etc/passwd".to_string(),
+            ws.clone(),
+            "command_path_outside_allowlisted_workspace",
+        ),
+        (
+            format!("cat {ws}/a"),
+            outside.display().to_string(),
+            "workspace_not_allowlisted",
+        ),
+    ];
+    for (command, cwd, expected) in cases {
+        let err = executor
+            .execute(&request(&command, &cwd))
+            .expect_err("command denied");
+        assert!(
+            matches!(&err, RuntimeError::CommandDenied { reason, .. } if reason == expected),
+            "{command}: {err}"
+        );
+    }
+    let _ = fs::remove_dir_all(workspace);
+    let _ = fs::remove_dir_all(outside);
+}
+
+#[test]
+fn non_zero_exit_fails_the_action() {
+    let workspace = temp_dir("fail");
+    let ws = workspace.display().to_string();
+    let executor = CommandActionExecutor::new(["cat"], [ws.as_str()]);
+
+    let err = executor
+        .execute(&request(&format!("cat {ws}"), &ws))
+        .expect_err("cat of a directory fails");
+    assert!(matches!(err, RuntimeError::Execution(_)), "{err}");
+    let _ = fs::remove_dir_all(workspace);
+}
@@ -0,0 +1,165 @@
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use odin_audit::{AuditError, AuditRecord, AuditSink};
+use odin_core_runtime::http::{HttpActionExecutor, HttpCapability};
+use odin_core_runtime::{ActionExecutor, RuntimeError};
+use odin_governance::egress::{EgressPolicy, EgressRule};
+use odin_plugin_protocol::{ActionRequest, CapabilityRequest, RiskTier};
+
+#[derive(Clone, Default)]
+struct MemoryAuditSink {
+    records: Arc<Mutex<Vec<AuditRecord>>>,
+}
+
+impl AuditSink for MemoryAuditSink {
+    fn record(&self, record: AuditRecord) -> Result<(), AuditError> {
+        self.records
+            .lock()
+            .map_err(|_| AuditError::Write("poisoned lock".to_string()))?
+            .push(record);
+        Ok(())
+    }
+}
+
+/// Serves one request with `body` and returns the base URL.
+fn serve_once(body: &'static str) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+    let addr = listener.local_addr().expect("addr");
+    thread::spawn(move || {
+        let (mut stream, _) = listener.accept().expect("accept");
+        let mut reader = BufReader::new(stream.try_clone().expect("clone"));
+        let mut line = String::new();
+        while reader.read_line(&mut line).is_ok_and(|n| n > 0) && line != "\r\n" {
+            line.clear();
+        }
+        let _ = write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+    });
+    format!("http://{addr}")
+}
+
+fn request(url: &str) -> ActionRequest {
+    ActionRequest {
+        request_id: "req-poll".to_string(),
+        risk_tier: RiskTier::Safe,
+        capability: CapabilityRequest {
+            plugin: "private.ops-watchdog".to_string(),
+            project: "private".to_string(),
+            capability: "http.get".to_string(),
+            scope: vec!["project".to_string()],
+            reason: "poll sentry".to_string(),
+        },
+        trace_id: None,
+        input: serde_json::json!({ "url": url }),
+        requested_by: None,
+    }
+}
+
+#[test]
+fn allowlisted_get_returns_status_and_body_and_is_audited() {
+    let base = serve_once(r#"{"issues":[]}"#);
+    let audit = MemoryAuditSink::default();
+    let executor = HttpActionExecutor::new()
+        .with_capability(HttpCapability::new("http.get", "GET").allow(format!("{base}/api/")))
+        .with_audit_sink(Arc::new(audit.clone()));
+
+    let output = executor
+        .execute(&request(&format!("{base}/api/issues")))
+        .expect("http get");
+
+    assert_eq!(output["status"], 200);
+    assert_eq!(output["body"], serde_json::json!({ "issues": [] }));
+    let records = audit.records.lock().expect("lock");
+    assert_eq!(records[0].event_type, "http.egress");
+    assert_eq!(records[0].metadata["url"], format!("{base}/api/issues"));
+    assert_eq!(records[0].metadata["status"], 200);
+}
+
+#[test]
+fn egress_outside_allowlist_is_denied_without_connecting() {
+    let audit = MemoryAuditSink::default();
+    let executor = HttpActionExecutor::new()
+        .with_capability(HttpCapability::new("http.get", "GET").allow("sentry.io"))
+        .with_audit_sink(Arc::new(audit.clone()));
+
+    let err = executor
+        .execute(&request("https://sentry.io.evil.example/api"))
+        .expect_err("denied");
+
+    assert!(matches!(err, RuntimeError::EgressDenied { .. }));
+    assert_eq!(
+        audit.records.lock().expect("lock")[0].metadata["error"],
+        "egress_denied"
+    );
+}
+
+#[test]
+fn plugin_egress_policy_applies_on_top_of_the_capability_allowlist() {
+    let base = serve_once(r#"{"ok":true}"#);
+    let authority = base.trim_start_matches("http://").to_string();
+    let (host, port) = authority.rsplit_once(':').expect("host:port");
+    let rule = EgressRule {
+        domains: vec![host.to_string()],
+        ports: vec![port.parse().expect("port")],
+        methods: vec!["GET".to_string()],
+    };
+    let audit = MemoryAuditSink::default();
+    let executor = |policy: EgressPolicy| {
+        HttpActionExecutor::new()
+            .with_capability(HttpCapability::new("http.get", "GET").allow(authority.clone()))
+            .with_audit_sink(Arc::new(audit.clone()))
+            .with_egress_policy(Arc::new(policy))
+    };
+
+    let err = executor(EgressPolicy::default().with_rule("example.other", rule.clone()))
+        .execute(&request(&format!("{base}/api")))
+        .expect_err("plugin has no egress");
+    let RuntimeError::EgressDenied { reason, .. } = err else {
+        panic!("unexpected error: {err:?}");
+    };
+    assert_eq!(reason, "egress_not_allowlisted");
+    assert_eq!(
+        audit.records.lock().expect("lock")[0].metadata["reason"],
+        "egress_not_allowlisted"
+    );
+
+    let output = executor(EgressPolicy::default().with_rule("private.ops-watchdog", rule))
+        .execute(&request(&format!("{base}/api")))
+        .expect("allowed");
+    assert_eq!(output["status"], 200);
+}
+
+#[test]
+fn oversized_response_fails() {
+    let base = serve_once(r#"{"payload":"0123456789abcdef"}"#);
+    let executor = HttpActionExecutor::new().with_capability(
+        HttpCapability::new("http.get", "GET")
+            .allow(base.trim_start_matches("http://"))
+            .with_max_response_bytes(8),
+    );
+
+    let err = executor
+        .execute(&request(&format!("{base}/big")))
+        .expect_err("too large");
+
+    assert!(err.to_string().contains("exceeds 8 bytes"));
+}
+
+#[test]
+fn allowlist_matches_hosts_wildcards_and_prefix_boundaries() {
+    let spec = HttpCapability::new("webhook.post", "POST")
+        .allow("*.sentry.io")
+        .allow("https://api.github.com/repos");
+
+    assert!(spec.permits("https://us.sentry.io/api/0/"));
+    assert!(!spec.permits("https://sentry.io.evil.example/"));
+    assert!(spec.permits("https://api.github.com/repos/odin/core"));
+    assert!(!spec.permits("https://api.github.com/repository-takeover"));
+    assert!(!spec.permits("file:///etc/passwd"));
+}
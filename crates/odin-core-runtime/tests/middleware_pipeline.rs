@@ -0,0 +1,194 @@
+use std::sync::{Arc, Mutex};
+
+use odin_audit::{AuditError, AuditRecord, AuditSink};
+use odin_core_runtime::middleware::ActionMiddleware;
+use odin_core_runtime::{DryRunExecutor, OrchestratorRuntime, RuntimeResult};
+use odin_plugin_protocol::{
+    ActionOutcome, ActionRequest, ActionStatus, CapabilityRequest, PolicyDecision, RiskTier,
+};
+use odin_policy_engine::StaticPolicyEngine;
+
+#[derive(Clone, Default)]
+struct MemoryAuditSink {
+    records: Arc<Mutex<Vec<AuditRecord>>>,
+}
+
+impl MemoryAuditSink {
+    fn find(&self, event_type: &str) -> Option<AuditRecord> {
+        self.records
+            .lock()
+            .expect("lock")
+            .iter()
+            .find(|record| record.event_type == event_type)
+            .cloned()
+    }
+}
+
+impl AuditSink for MemoryAuditSink {
+    fn record(&self, record: AuditRecord) -> Result<(), AuditError> {
+        self.records
+            .lock()
+            .map_err(|_| AuditError::Write("poisoned lock".to_string()))?
+            .push(record);
+        Ok(())
+    }
+}
+
+/// Records every hook invocation and tags the request reason.
+#[derive(Clone, Default)]
+struct Tracing {
+    calls: Arc<Mutex<Vec<String>>>,
+}
+
+impl ActionMiddleware for Tracing {
+    fn before_policy(&self, request: &mut ActionRequest) -> RuntimeResult<Option<ActionOutcome>> {
+        request.capability.reason.push_str(" [traced]");
+        self.calls
+            .lock()
+            .expect("lock")
+            .push("before_policy".into());
+        Ok(None)
+    }
+
+    fn after_decision(
+        &self,
+        _request: &ActionRequest,
+        decision: &PolicyDecision,
+    ) -> RuntimeResult<Option<ActionOutcome>> {
+        let allowed = matches!(decision, PolicyDecision::Allow { .. });
+        self.calls
+            .lock()
+            .expect("lock")
+            .push(format!("after_decision:{allowed}"));
+        Ok(None)
+    }
+
+    fn before_execute(&self, request: &ActionRequest) -> RuntimeResult<Option<ActionOutcome>> {
+        self.calls
+            .lock()
+            .expect("lock")
+            .push(format!("before_execute:{}", request.capability.reason));
+        Ok(None)
+    }
+
+    fn after_execute(
+        &self,
+        _request: &ActionRequest,
+        outcome: &mut ActionOutcome,
+    ) -> RuntimeResult<()> {
+        outcome.output["traced"] = serde_json::Value::Bool(true);
+        self.calls
+            .lock()
+            .expect("lock")
+            .push("after_execute".into());
+        Ok(())
+    }
+}
+
+/// Blocks one capability just before execution.
+struct Freeze(&'static str);
+
+impl ActionMiddleware for Freeze {
+    fn before_execute(&self, request: &ActionRequest) -> RuntimeResult<Option<ActionOutcome>> {
+        if request.capability.capability != self.0 {
+            return Ok(None);
+        }
+        Ok(Some(ActionOutcome {
+            request_id: request.request_id.clone(),
+            status: ActionStatus::Blocked,
+            detail: "change_freeze".to_string(),
+            output: serde_json::Value::Null,
+            warnings: Vec::new(),
+            snapshot: None,
+        }))
+    }
+}
+
+fn request(capability: &str) -> ActionRequest {
+    ActionRequest {
+        request_id: format!("req-{capability}"),
+        risk_tier: RiskTier::Safe,
+        capability: CapabilityRequest {
+            plugin: "example.safe-github".to_string(),
+            project: "demo".to_string(),
+            capability: capability.to_string(),
+            scope: vec!["project".to_string()],
+            reason: "sync".to_string(),
+        },
+        trace_id: None,
+        input: serde_json::Value::Null,
+        requested_by: None,
+    }
+}
+
+fn policy() -> StaticPolicyEngine {
+    let mut policy = StaticPolicyEngine::default();
+    policy.allow_capability("example.safe-github", "demo", "repo.read");
+    policy.allow_capability("example.safe-github", "demo", "repo.push");
+    policy
+}
+
+#[test]
+fn hooks_run_in_order_and_can_mutate_request_and_outcome() {
+    let tracing = Tracing::default();
+    let runtime = OrchestratorRuntime::new(policy(), MemoryAuditSink::default(), DryRunExecutor)
+        .with_middleware(tracing.clone());
+
+    let outcome = runtime
+        .handle_action(request("repo.read"))
+        .expect("outcome");
+
+    assert_eq!(outcome.status, ActionStatus::Executed);
+    assert_eq!(outcome.output["traced"], true);
+    assert_eq!(
+        *tracing.calls.lock().expect("lock"),
+        vec![
+            "before_policy",
+            "after_decision:true",
+            "before_execute:sync [traced]",
+            "after_execute"
+        ]
+    );
+}
+
+#[test]
+fn short_circuit_skips_execution_and_is_audited() {
+    let tracing = Tracing::default();
+    let audit = MemoryAuditSink::default();
+    let runtime = OrchestratorRuntime::new(policy(), audit.clone(), DryRunExecutor)
+        .with_middleware(Freeze("repo.push"))
+        .with_middleware(tracing.clone());
+
+    let outcome = runtime
+        .handle_action(request("repo.push"))
+        .expect("outcome");
+
+    assert_eq!(outcome.status, ActionStatus::Blocked);
+    assert_eq!(outcome.detail, "change_freeze");
+    assert!(audit.find("action.executed").is_none());
+    let intercepted = audit.find("action.intercepted").expect("intercept audit");
+    assert_eq!(intercepted.metadata["stage"], "before_execute");
+    assert!(!tracing
+        .calls
+        .lock()
+        .expect("lock")
+        .iter()
+        .any(|call| call.starts_with("before_execute") || call == "after_execute"));
+}
+
+#[test]
+fn denied_actions_skip_execute_hooks() {
+    let tracing = Tracing::default();
+    let runtime = OrchestratorRuntime::new(policy(), MemoryAuditSink::default(), DryRunExecutor)
+        .with_middleware(tracing.clone());
+
+    let outcome = runtime
+        .handle_action(request("repo.delete"))
+        .expect("outcome");
+
+    assert_eq!(outcome.status, ActionStatus::Blocked);
+    assert_eq!(
+        *tracing.calls.lock().expect("lock"),
+        vec!["before_policy", "after_decision:false"]
+    );
+}
@@ -0,0 +1,486 @@
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use odin_audit::{AuditError, AuditRecord, AuditSink};
+use odin_core_runtime::cancel::CancellationToken;
+use odin_core_runtime::health::{check_plugin, HealthCheckStage};
+use odin_core_runtime::{
+    EntrypointPolicy, ExternalProcessPluginRunner, PluginDirective, PluginEventRunner, RuntimeError,
+};
+use odin_governance::egress::{EgressPolicy, EgressRule};
+use odin_plugin_protocol::EventEnvelope;
+use odin_secrets::{AccessContext, SecretError, SecretHandle, SecretRef, SecretStore};
+use zeroize::Zeroizing;
+
+const REPORT_SCRIPT: &str = r#"#!/usr/bin/env bash
+read -r _event
+printf '{"action":"enqueue_task","task_type":"env.report","payload":{"project":"%s","event_id":"%s","manifest_dir":"%s","token":"%s","protocol":"%s"}}\n' \
+  "${ODIN_PROJECT:-}" "${ODIN_EVENT_ID:-}" "${CARGO_MANIFEST_DIR:-}" "${GITHUB_TOKEN:-}" "${ODIN_PROTOCOL_VERSION:-}"
+"#;
+
+fn temp_plugins_root(name: &str) -> PathBuf {
+    let unique = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    std::env::temp_dir().join(format!(
+        "odin-runtime-env-{name}-{}-{unique}",
+        std::process::id()
+    ))
+}
+
+fn write_plugin(root: &Path, entrypoint_extra: &str) {
+    let plugin_dir = root.join("env-probe");
+    fs::create_dir_all(plugin_dir.join("bin")).expect("mkdir plugin");
+    let manifest = format!(
+        "schema_version: 1\nplugin:\n  name: env-probe\n  version: 0.1.0\n  runtime: external-process\n  compatibility:\n    core_version: \">=0.1.0 <0.2.0\"\n  entrypoint:\n    command: ./bin/plugin\n{entrypoint_extra}distribution:\n  source:\n    type: local-path\n    ref: .\n  integrity:\n    checksum_sha256: \"0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef\"\n"
+    );
+    fs::write(plugin_dir.join("odin.plugin.yaml"), manifest).expect("write manifest");
+    let script = plugin_dir.join("bin/plugin");
+    fs::write(&script, REPORT_SCRIPT).expect("write script");
+    fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).expect("chmod");
+}
+
+fn event() -> EventEnvelope {
+    EventEnvelope {
+        event_id: "evt-env-1".to_string(),
+        event_type: "task.received".to_string(),
+        task_id: Some("task-env".to_string()),
+        request_id: None,
+        project: Some("demo".to_string()),
+        trace_id: None,
+        payload: serde_json::json!({ "task_type": "sentry_poll" }),
+    }
+}
+
+fn reported_payload(directives: Vec<PluginDirective>) -> serde_json::Value {
+    match directives.into_iter().next() {
+        Some(PluginDirective::EnqueueTask { payload, .. }) => payload,
+        other => panic!("unexpected directive: {other:?}"),
+    }
+}
+
+struct PrefixingStore;
+
+impl SecretStore for PrefixingStore {
+    fn resolve_secret_handle(
+        &self,
+        handle: &SecretHandle,
+        ctx: &AccessContext,
+    ) -> Result<SecretRef, SecretError> {
+        if ctx.plugin != "env-probe" {
+            return Err(SecretError::Unauthorized(ctx.plugin.clone()));
+        }
+        Ok(SecretRef {
+            handle: SecretHandle(format!("{}#lease", handle.0)),
+        })
+    }
+
+    fn reveal_secret(
+        &self,
+        handle: &SecretHandle,
+        ctx: &AccessContext,
+    ) -> Result<Zeroizing<Vec<u8>>, SecretError> {
+        self.resolve_secret_handle(handle, ctx)?;
+        Ok(Zeroizing::new(b"ghp_value".to_vec()))
+    }
+}
+
+#[derive(Default)]
+struct RecordingAudit(Mutex<Vec<AuditRecord>>);
+
+impl AuditSink for RecordingAudit {
+    fn record(&self, record: AuditRecord) -> Result<(), AuditError> {
+        self.0.lock().expect("lock").push(record);
+        Ok(())
+    }
+}
+
+#[test]
+fn plugin_process_does_not_inherit_undeclared_host_env() {
+    let root = temp_plugins_root("scrubbed");
+    write_plugin(&root, "");
+
+    let runner = ExternalProcessPluginRunner::new(&root);
+    let payload = reported_payload(
+        runner
+            .dispatch_event("env-probe", &event())
+            .expect("dispatch"),
+    );
+
+    assert_eq!(payload["manifest_dir"], "");
+    assert_eq!(payload["project"], "demo");
+    assert_eq!(payload["event_id"], "evt-env-1");
+    assert_eq!(payload["protocol"], "1");
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn version_info_reports_negotiated_plugin_versions() {
+    let root = temp_plugins_root("version");
+    write_plugin(&root, "");
+
+    let info = ExternalProcessPluginRunner::new(&root)
+        .version_info("env-probe")
+        .expect("version info")
+        .expect("runner knows versions");
+
+    assert_eq!(info.plugin_version, "0.1.0");
+    assert_eq!(info.core_compatibility, ">=0.1.0 <0.2.0");
+    assert!(info.core_compatible);
+    assert_eq!(info.protocol_version, 1);
+    assert_eq!(info.metric_labels()["plugin_version"], "0.1.0");
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn plugin_process_receives_declared_env_and_secret_handles() {
+    let root = temp_plugins_root("declared");
+    write_plugin(
+        &root,
+        "    env: [CARGO_MANIFEST_DIR]\n    secrets:\n      - env: GITHUB_TOKEN\n        handle: secret://demo/github\n",
+    );
+
+    let runner = ExternalProcessPluginRunner::new(&root).with_secret_store(PrefixingStore);
+    let payload = reported_payload(
+        runner
+            .dispatch_event("env-probe", &event())
+            .expect("dispatch"),
+    );
+
+    assert_eq!(payload["manifest_dir"], env!("CARGO_MANIFEST_DIR"));
+    assert_eq!(payload["token"], "secret://demo/github#lease");
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn file_delivered_secrets_live_only_as_long_as_the_process() {
+    let root = temp_plugins_root("secret-file");
+    write_plugin(
+        &root,
+        "    secrets:\n      - env: GITHUB_TOKEN\n        handle: secret://demo/github\n        delivery: file\n",
+    );
+    fs::write(
+        root.join("env-probe/bin/plugin"),
+        "#!/usr/bin/env bash\nread -r _event\nprintf '{\"action\":\"enqueue_task\",\"task_type\":\"env.report\",\"payload\":{\"path\":\"%s\",\"value\":\"%s\"}}\\n' \"$GITHUB_TOKEN\" \"$(cat \"$GITHUB_TOKEN\")\"\n",
+    )
+    .expect("write script");
+    let audit = Arc::new(RecordingAudit::default());
+
+    let runner = ExternalProcessPluginRunner::new(&root)
+        .with_secret_store(PrefixingStore)
+        .with_audit_sink(audit.clone());
+    let payload = reported_payload(
+        runner
+            .dispatch_event("env-probe", &event())
+            .expect("dispatch"),
+    );
+
+    let path = payload["path"].as_str().expect("path");
+    assert_ne!(path, "ghp_value");
+    assert_eq!(payload["value"], "ghp_value");
+    assert!(!Path::new(path).exists());
+    let records = audit.0.lock().expect("lock");
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].event_type, "secret.injected");
+    assert_eq!(records[0].metadata["handle"], "secret://demo/github");
+    assert!(!records[0].metadata.to_string().contains("ghp_value"));
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn plugin_process_receives_its_egress_rules() {
+    let root = temp_plugins_root("egress");
+    write_plugin(&root, "");
+    fs::write(
+        root.join("env-probe/bin/plugin"),
+        "#!/usr/bin/env bash\nread -r _event\nprintf '{\"action\":\"enqueue_task\",\"task_type\":\"env.report\",\"payload\":{\"egress\":%s}}\\n' \"$ODIN_EGRESS_POLICY\"\n",
+    )
+    .expect("write script");
+    let policy = EgressPolicy::default().with_rule(
+        "env-probe",
+        EgressRule {
+            domains: vec!["api.github.com".to_string()],
+            ports: Vec::new(),
+            methods: vec!["GET".to_string()],
+        },
+    );
+
+    let runner = ExternalProcessPluginRunner::new(&root).with_egress_policy(Arc::new(policy));
+    let payload = reported_payload(
+        runner
+            .dispatch_event("env-probe", &event())
+            .expect("dispatch"),
+    );
+
+    assert_eq!(payload["egress"][0]["domains"][0], "api.github.com");
+    assert_eq!(payload["egress"][0]["methods"][0], "GET");
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn reserved_env_prefix_in_manifest_is_rejected() {
+    let root = temp_plugins_root("reserved");
+    write_plugin(&root, "    env: [ODIN_PROJECT]\n");
+
+    let runner = ExternalProcessPluginRunner::new(&root);
+    let err = runner
+        .dispatch_event("env-probe", &event())
+        .expect_err("reserved env rejected");
+
+    assert!(err.to_string().contains("reserved ODIN_ prefix"));
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn malformed_core_event_is_rejected_before_spawning() {
+    let root = temp_plugins_root("schema");
+    write_plugin(&root, "");
+    let mut malformed = event();
+    malformed.payload = serde_json::json!({ "trigger": 7 });
+
+    let err = ExternalProcessPluginRunner::new(&root)
+        .dispatch_event("env-probe", &malformed)
+        .expect_err("schema violation");
+
+    assert!(matches!(err, RuntimeError::InvalidInput(_)));
+    assert!(err.to_string().contains("missing required field task_type"));
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn bare_entrypoint_requires_allowlisted_bin() {
+    let root = temp_plugins_root("bare-entrypoint");
+    write_plugin(&root, "");
+    let manifest_path = root.join("env-probe/odin.plugin.yaml");
+    let manifest = fs::read_to_string(&manifest_path)
+        .expect("read manifest")
+        .replace(
+            "command: ./bin/plugin",
+            "command: bash\n    args: [./bin/plugin]",
+        );
+    fs::write(&manifest_path, manifest).expect("write manifest");
+
+    let err = ExternalProcessPluginRunner::new(&root)
+        .dispatch_event("env-probe", &event())
+        .expect_err("bare command denied");
+    assert!(matches!(
+        err,
+        RuntimeError::EntrypointDenied { ref reason, .. } if reason == "path_lookup_not_allowed"
+    ));
+
+    let runner = ExternalProcessPluginRunner::new(&root)
+        .with_entrypoint_policy(EntrypointPolicy::plugin_dir_only().allow_bin("bash"));
+    let payload = reported_payload(
+        runner
+            .dispatch_event("env-probe", &event())
+            .expect("dispatch"),
+    );
+    assert_eq!(payload["project"], "demo");
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn entrypoint_escaping_plugin_dir_is_denied() {
+    let root = temp_plugins_root("escape-entrypoint");
+    write_plugin(&root, "");
+    let outside = root.join("outside.sh");
+    fs::write(&outside, REPORT_SCRIPT).expect("write outside script");
+    fs::set_permissions(&outside, fs::Permissions::from_mode(0o755)).expect("chmod");
+    let manifest_path = root.join("env-probe/odin.plugin.yaml");
+    let manifest = fs::read_to_string(&manifest_path)
+        .expect("read manifest")
+        .replace("command: ./bin/plugin", "command: ../outside.sh");
+    fs::write(&manifest_path, manifest).expect("write manifest");
+
+    let err = ExternalProcessPluginRunner::new(&root)
+        .dispatch_event("env-probe", &event())
+        .expect_err("escape denied");
+    assert!(matches!(
+        err,
+        RuntimeError::EntrypointDenied { ref reason, .. } if reason == "outside_plugin_dir"
+    ));
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn non_executable_entrypoint_is_denied() {
+    let root = temp_plugins_root("noexec-entrypoint");
+    write_plugin(&root, "");
+    fs::set_permissions(
+        root.join("env-probe/bin/plugin"),
+        fs::Permissions::from_mode(0o644),
+    )
+    .expect("chmod");
+
+    let err = ExternalProcessPluginRunner::new(&root)
+        .dispatch_event("env-probe", &event())
+        .expect_err("non-executable denied");
+    assert!(err.to_string().contains("not_executable"));
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn health_check_pings_the_plugin_and_names_the_failing_stage() {
+    let root = temp_plugins_root("health");
+    write_plugin(&root, "");
+    let runner = ExternalProcessPluginRunner::new(&root);
+
+    let health = check_plugin(&runner, "env-probe");
+    assert!(health.healthy, "{health:?}");
+    assert_eq!(health.directives, 1);
+    assert!(health.version.is_some());
+
+    let missing = check_plugin(&runner, "absent");
+    assert_eq!(missing.failed_stage, Some(HealthCheckStage::Manifest));
+
+    fs::set_permissions(
+        root.join("env-probe/bin/plugin"),
+        fs::Permissions::from_mode(0o644),
+    )
+    .expect("chmod");
+    let broken = check_plugin(&runner, "env-probe");
+    assert!(!broken.healthy);
+    assert_eq!(broken.failed_stage, Some(HealthCheckStage::Entrypoint));
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn oversized_plugin_output_is_cut_off_with_prefix() {
+    let root = temp_plugins_root("chatty");
+    write_plugin(&root, "");
+    fs::write(
+        root.join("env-probe/bin/plugin"),
+        "#!/usr/bin/env bash\nread -r _event\nyes '{\"action\":\"noop\"}'\n",
+    )
+    .expect("write chatty script");
+
+    let runner = ExternalProcessPluginRunner::new(&root).with_max_output_bytes(1024);
+    let err = runner
+        .dispatch_event("env-probe", &event())
+        .expect_err("output limit");
+
+    match err {
+        RuntimeError::PluginOutputLimitExceeded {
+            limit_bytes,
+            prefix,
+            ..
+        } => {
+            assert_eq!(limit_bytes, 1024);
+            assert!(prefix.starts_with("{\"action\":\"noop\"}"));
+            assert!(prefix.len() <= 1024);
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn successful_dispatch_reports_stderr_and_output_sizes() {
+    let root = temp_plugins_root("stderr");
+    write_plugin(&root, "");
+    fs::write(
+        root.join("env-probe/bin/plugin"),
+        "#!/usr/bin/env bash\nread -r _event\necho 'retrying sentry fetch' >&2\necho '{\"action\":\"noop\"}'\n",
+    )
+    .expect("write noisy script");
+
+    let (directives, diagnostics) = ExternalProcessPluginRunner::new(&root)
+        .dispatch_with_diagnostics("env-probe", &event())
+        .expect("dispatch");
+    let diagnostics = diagnostics.expect("process diagnostics");
+
+    assert_eq!(directives, vec![PluginDirective::Noop]);
+    assert_eq!(diagnostics.exit_code, Some(0));
+    assert_eq!(diagnostics.stderr, "retrying sentry fetch\n");
+    assert!(!diagnostics.stderr_truncated);
+    assert_eq!(diagnostics.stdout_bytes, 18);
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn stderr_past_the_output_limit_is_drained_not_left_to_block() {
+    let root = temp_plugins_root("stderr-flood");
+    write_plugin(&root, "");
+    fs::write(
+        root.join("env-probe/bin/plugin"),
+        "#!/usr/bin/env bash\nread -r _event\nhead -c 1048576 /dev/zero >&2\necho '{\"action\":\"noop\"}'\n",
+    )
+    .expect("write flooding script");
+
+    let runner = ExternalProcessPluginRunner::new(&root).with_max_output_bytes(1024);
+    let (sender, receiver) = std::sync::mpsc::channel();
+    thread::spawn(move || {
+        let _ = sender.send(runner.dispatch_with_diagnostics("env-probe", &event()));
+    });
+    let (directives, diagnostics) = receiver
+        .recv_timeout(Duration::from_secs(30))
+        .expect("dispatch finished instead of blocking on stderr")
+        .expect("dispatch");
+
+    assert_eq!(directives, vec![PluginDirective::Noop]);
+    let diagnostics = diagnostics.expect("process diagnostics");
+    assert_eq!(diagnostics.stderr_bytes, 1024);
+    assert!(diagnostics.stderr_truncated);
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn cancellation_kills_a_hung_plugin_process() {
+    let root = temp_plugins_root("hung");
+    write_plugin(&root, "");
+    let token = CancellationToken::new();
+    let runner = ExternalProcessPluginRunner::new(&root).with_cancellation(token.clone());
+    let payload = reported_payload(
+        runner
+            .dispatch_event("env-probe", &event())
+            .expect("dispatch"),
+    );
+    assert_eq!(payload["project"], "demo");
+
+    fs::write(
+        root.join("env-probe/bin/plugin"),
+        "#!/usr/bin/env bash\nread -r _event\nexec sleep 30\n",
+    )
+    .expect("write hung script");
+    let canceller = {
+        let token = token.clone();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(100));
+            token.cancel();
+        })
+    };
+    let started = Instant::now();
+    let err = runner
+        .dispatch_event("env-probe", &event())
+        .expect_err("cancelled");
+    canceller.join().expect("join");
+
+    assert!(matches!(err, RuntimeError::Cancelled(_)));
+    assert_eq!(err.code(), "cancelled");
+    assert!(started.elapsed() < Duration::from_secs(10));
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn capability_manifest_comes_from_installed_plugin() {
+    let root = temp_plugins_root("capabilities");
+    write_plugin(
+        &root,
+        "  capabilities:\n    - id: repo.read\n      scope: [project]\n",
+    );
+
+    let manifest = ExternalProcessPluginRunner::new(&root)
+        .capability_manifest("env-probe")
+        .expect("manifest")
+        .expect("installed plugin has a manifest");
+    assert_eq!(manifest.plugin, "env-probe");
+    assert_eq!(manifest.capabilities.len(), 1);
+    assert_eq!(manifest.capabilities[0].id, "repo.read");
+    assert_eq!(manifest.capabilities[0].scope, vec!["project".to_string()]);
+    let _ = fs::remove_dir_all(root);
+}
@@ -0,0 +1,360 @@
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use odin_audit::{AuditError, AuditRecord, AuditSink};
+use odin_core_runtime::{DryRunExecutor, OrchestratorRuntime};
+use odin_plugin_protocol::{
+    ActionRequest, ActionStatus, CapabilityRequest, PolicyDecision, RiskTier,
+};
+use odin_policy_engine::breakglass::{BreakGlassRequest, BreakGlassStore};
+use odin_policy_engine::denylist::{DenyListPolicyEngine, DenyRule};
+use odin_policy_engine::elevation::{ElevationGrant, ElevationOverlay};
+use odin_policy_engine::{PolicyEngine, PolicyError, PolicyResult, StaticPolicyEngine};
+
+#[derive(Clone, Default)]
+struct MemoryAuditSink {
+    records: Arc<Mutex<Vec<AuditRecord>>>,
+}
+
+impl MemoryAuditSink {
+    fn find(&self, event_type: &str) -> Option<AuditRecord> {
+        self.records
+            .lock()
+            .expect("lock")
+            .iter()
+            .find(|record| record.event_type == event_type)
+            .cloned()
+    }
+}
+
+impl AuditSink for MemoryAuditSink {
+    fn record(&self, record: AuditRecord) -> Result<(), AuditError> {
+        self.records
+            .lock()
+            .map_err(|_| AuditError::Write("poisoned lock".to_string()))?
+            .push(record);
+        Ok(())
+    }
+}
+
+fn temp_overlay(name: &str) -> PathBuf {
+    let unique = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    std::env::temp_dir().join(format!(
+        "odin-elevation-{name}-{}-{unique}.json",
+        std::process::id()
+    ))
+}
+
+fn request(risk_tier: RiskTier) -> ActionRequest {
+    ActionRequest {
+        request_id: "req-write".to_string(),
+        risk_tier,
+        capability: CapabilityRequest {
+            plugin: "example.safe-github".to_string(),
+            project: "demo".to_string(),
+            capability: "repo.write".to_string(),
+            scope: vec!["project".to_string()],
+            reason: "push hotfix".to_string(),
+        },
+        trace_id: None,
+        input: serde_json::Value::Null,
+        requested_by: None,
+    }
+}
+
+fn grant(ttl: Duration) -> ElevationGrant {
+    ElevationGrant {
+        plugin: "example.safe-github".to_string(),
+        project: "demo".to_string(),
+        capability: "repo.write".to_string(),
+        scope: Vec::new(),
+        ttl,
+        reason: "incident 42".to_string(),
+    }
+}
+
+#[test]
+fn elevation_allows_ungranted_capability_and_is_audited() {
+    let path = temp_overlay("allow");
+    let audit = MemoryAuditSink::default();
+    let runtime =
+        OrchestratorRuntime::new(StaticPolicyEngine::default(), audit.clone(), DryRunExecutor)
+            .with_elevation_overlay(Arc::new(ElevationOverlay::file(&path)));
+
+    let denied = runtime
+        .handle_action(request(RiskTier::Safe))
+        .expect("outcome");
+    assert_eq!(denied.status, ActionStatus::Blocked);
+
+    let elevation = runtime
+        .grant_elevation(grant(Duration::from_secs(300)))
+        .expect("grant");
+    let granted = audit.find("policy.elevation.granted").expect("grant audit");
+    assert_eq!(granted.metadata["elevation_id"], elevation.id.as_str());
+    assert_eq!(granted.metadata["reason"], "incident 42");
+
+    let allowed = runtime
+        .handle_action(request(RiskTier::Safe))
+        .expect("outcome");
+    assert_eq!(allowed.status, ActionStatus::Executed);
+
+    let destructive = runtime
+        .handle_action(request(RiskTier::Destructive))
+        .expect("outcome");
+    assert_eq!(destructive.status, ActionStatus::ApprovalPending);
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn parent_project_elevation_is_inherited_by_child_projects() {
+    let runtime = OrchestratorRuntime::new(
+        StaticPolicyEngine::default(),
+        MemoryAuditSink::default(),
+        DryRunExecutor,
+    )
+    .with_elevation_overlay(Arc::new(ElevationOverlay::in_memory()));
+    runtime
+        .grant_elevation(grant(Duration::from_secs(300)))
+        .expect("grant");
+    let in_project = |project: &str| {
+        let mut request = request(RiskTier::Safe);
+        request.capability.project = project.to_string();
+        runtime.handle_action(request).expect("outcome").status
+    };
+
+    assert_eq!(in_project("demo/api"), ActionStatus::Executed);
+    assert_eq!(in_project("demo-legacy"), ActionStatus::Blocked);
+}
+
+#[test]
+fn expired_elevation_reverts_and_is_audited() {
+    let path = temp_overlay("expire");
+    let overlay = ElevationOverlay::file(&path);
+    let stale = overlay
+        .grant(grant(Duration::from_secs(1)), 1)
+        .expect("grant");
+
+    let audit = MemoryAuditSink::default();
+    let runtime =
+        OrchestratorRuntime::new(StaticPolicyEngine::default(), audit.clone(), DryRunExecutor)
+            .with_elevation_overlay(Arc::new(ElevationOverlay::file(&path)));
+
+    let outcome = runtime
+        .handle_action(request(RiskTier::Safe))
+        .expect("outcome");
+
+    assert_eq!(outcome.status, ActionStatus::Blocked);
+    let expired = audit
+        .find("policy.elevation.expired")
+        .expect("expiry audit");
+    assert_eq!(expired.metadata["elevation_id"], stale.id.as_str());
+    assert!(overlay.active(0).expect("active").is_empty());
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn time_boxed_grant_lapses_to_deny_and_is_audited_once() {
+    let mut policy = StaticPolicyEngine::default();
+    policy.allow_capability_until("example.safe-github", "demo", "repo.write", 1);
+
+    let audit = MemoryAuditSink::default();
+    let runtime = OrchestratorRuntime::new(policy, audit.clone(), DryRunExecutor);
+    for _ in 0..2 {
+        let outcome = runtime
+            .handle_action(request(RiskTier::Safe))
+            .expect("outcome");
+        assert_eq!(outcome.status, ActionStatus::Blocked);
+        assert_eq!(outcome.detail, "capability_grant_expired");
+    }
+
+    let expired = audit.find("policy.grant.expired").expect("expiry audit");
+    assert_eq!(expired.metadata["capability"], "repo.write");
+    assert_eq!(expired.metadata["expired_at_unix"], 1);
+    let expiries = audit
+        .records
+        .lock()
+        .expect("lock")
+        .iter()
+        .filter(|record| record.event_type == "policy.grant.expired")
+        .count();
+    assert_eq!(expiries, 1);
+}
+
+/// A versioned policy, standing in for a signed bundle.
+struct VersionedPolicy(StaticPolicyEngine);
+
+impl PolicyEngine for VersionedPolicy {
+    fn decide(&self, request: &ActionRequest) -> PolicyResult<PolicyDecision> {
+        self.0.decide(request)
+    }
+
+    fn version(&self) -> Option<String> {
+        Some("42".to_string())
+    }
+}
+
+#[test]
+fn policy_decisions_record_the_active_policy_version() {
+    let audit = MemoryAuditSink::default();
+    let runtime = OrchestratorRuntime::new(
+        VersionedPolicy(StaticPolicyEngine::default()),
+        audit.clone(),
+        DryRunExecutor,
+    );
+    runtime
+        .handle_action(request(RiskTier::Safe))
+        .expect("outcome");
+
+    let decision = audit.find("policy.decision").expect("decision audit");
+    assert_eq!(decision.metadata["policy_version"], "42");
+}
+
+/// A policy whose usage store is unavailable.
+struct UnrecordablePolicy(StaticPolicyEngine);
+
+impl PolicyEngine for UnrecordablePolicy {
+    fn decide(&self, request: &ActionRequest) -> PolicyResult<PolicyDecision> {
+        self.0.decide(request)
+    }
+
+    fn record_execution(&self, _request: &ActionRequest) -> PolicyResult<()> {
+        Err(PolicyError::Evaluation(
+            "quota state file is read-only".to_string(),
+        ))
+    }
+}
+
+#[test]
+fn a_failed_execution_record_keeps_the_outcome_and_is_audited() {
+    let mut policy = StaticPolicyEngine::default();
+    policy.allow_capability("example.safe-github", "demo", "repo.write");
+    let audit = MemoryAuditSink::default();
+    let runtime =
+        OrchestratorRuntime::new(UnrecordablePolicy(policy), audit.clone(), DryRunExecutor);
+    let outcome = runtime
+        .handle_action(request(RiskTier::Safe))
+        .expect("outcome");
+    assert_eq!(outcome.status, ActionStatus::Executed);
+
+    let failed = audit
+        .find("policy.record.failed")
+        .expect("record failure audit");
+    assert_eq!(failed.metadata["executed"], true);
+    assert_eq!(
+        failed.metadata["error"],
+        "evaluation error: quota state file is read-only"
+    );
+}
+
+fn break_glass_request() -> BreakGlassRequest {
+    BreakGlassRequest {
+        operator: "oncall@acme".to_string(),
+        reason: "incident 7: revert bad deploy".to_string(),
+        plugin: "example.safe-github".to_string(),
+        project: "demo".to_string(),
+        capability: "repo.write".to_string(),
+        ttl: Duration::from_secs(600),
+    }
+}
+
+#[test]
+fn break_glass_overrides_a_deny_with_a_dedicated_audit_event() {
+    let audit = MemoryAuditSink::default();
+    let runtime =
+        OrchestratorRuntime::new(StaticPolicyEngine::default(), audit.clone(), DryRunExecutor)
+            .with_break_glass_store(Arc::new(BreakGlassStore::in_memory()));
+    let (entry, token) = runtime
+        .issue_break_glass(break_glass_request())
+        .expect("issue");
+    assert!(audit.find("policy.override.issued").is_some());
+
+    let rejected = runtime
+        .handle_action_with_override(request(RiskTier::Safe), "bg-wrong")
+        .expect("outcome");
+    assert_eq!(rejected.status, ActionStatus::Blocked);
+    assert!(audit.find("policy.override.rejected").is_some());
+
+    let outcome = runtime
+        .handle_action_with_override(request(RiskTier::Safe), &token)
+        .expect("outcome");
+    assert_eq!(outcome.status, ActionStatus::Executed);
+    let record = audit.find("policy.override").expect("override audit");
+    assert_eq!(record.metadata["override_id"], entry.id.as_str());
+    assert_eq!(record.metadata["operator"], "oncall@acme");
+    assert_eq!(
+        record.metadata["overridden_reason_code"],
+        "capability_not_granted"
+    );
+    assert_eq!(record.request_id.as_deref(), Some("req-write"));
+}
+
+#[test]
+fn break_glass_cannot_lift_a_deny_list_hit() {
+    let audit = MemoryAuditSink::default();
+    let runtime = OrchestratorRuntime::new(
+        DenyListPolicyEngine::new().with_rule(DenyRule::new("*", "repo.write")),
+        audit.clone(),
+        DryRunExecutor,
+    )
+    .with_break_glass_store(Arc::new(BreakGlassStore::in_memory()));
+    let (_, token) = runtime
+        .issue_break_glass(break_glass_request())
+        .expect("issue");
+
+    let outcome = runtime
+        .handle_action_with_override(request(RiskTier::Safe), &token)
+        .expect("outcome");
+    assert_eq!(outcome.status, ActionStatus::Blocked);
+    assert_eq!(outcome.detail, "deny_listed");
+    let rejected = audit
+        .find("policy.override.rejected")
+        .expect("rejection audit");
+    assert_eq!(rejected.metadata["denied_reason_code"], "deny_listed");
+    assert!(audit.find("policy.override").is_none());
+}
+
+/// Drops every break-glass audit record.
+#[derive(Clone, Default)]
+struct OverrideBlindAudit;
+
+impl AuditSink for OverrideBlindAudit {
+    fn record(&self, record: AuditRecord) -> Result<(), AuditError> {
+        if record.event_type.starts_with("policy.override") {
+            return Err(AuditError::Write("audit unavailable".to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn break_glass_cannot_be_used_without_an_audit_trail() {
+    let store = Arc::new(BreakGlassStore::in_memory());
+    let runtime = OrchestratorRuntime::new(
+        StaticPolicyEngine::default(),
+        OverrideBlindAudit,
+        DryRunExecutor,
+    )
+    .with_break_glass_store(store.clone());
+
+    assert!(runtime.issue_break_glass(break_glass_request()).is_err());
+    assert!(store.active(0).expect("active").is_empty());
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("clock")
+        .as_secs();
+    let (_, token) = store.issue(break_glass_request(), now).expect("issue");
+    assert!(store
+        .find(&token, &request(RiskTier::Safe), now)
+        .expect("find")
+        .is_some());
+    let mut request = request(RiskTier::Safe);
+    request.request_id = "req-silent".to_string();
+    assert!(runtime
+        .handle_action_with_override(request, &token)
+        .is_err());
+}
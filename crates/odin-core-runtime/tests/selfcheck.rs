@@ -0,0 +1,144 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use odin_audit::{AuditError, AuditRecord, AuditSink};
+use odin_core_runtime::approvals::{ApprovalStore, FileApprovalStore, PendingApproval};
+use odin_core_runtime::selfcheck::{run_selfcheck, SelfCheckAction, SelfCheckConfig};
+use odin_plugin_protocol::{ActionRequest, CapabilityRequest, RiskTier};
+
+#[derive(Clone, Default)]
+struct MemoryAuditSink {
+    records: Arc<Mutex<Vec<AuditRecord>>>,
+}
+
+impl MemoryAuditSink {
+    fn events(&self) -> Vec<String> {
+        self.records
+            .lock()
+            .expect("lock")
+            .iter()
+            .map(|record| record.event_type.clone())
+            .collect()
+    }
+}
+
+impl AuditSink for MemoryAuditSink {
+    fn record(&self, record: AuditRecord) -> Result<(), AuditError> {
+        self.records
+            .lock()
+            .map_err(|_| AuditError::Write("poisoned lock".to_string()))?
+            .push(record);
+        Ok(())
+    }
+}
+
+fn temp_odin_dir(name: &str) -> PathBuf {
+    let unique = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    let dir = std::env::temp_dir().join(format!(
+        "odin-selfcheck-{name}-{}-{unique}",
+        std::process::id()
+    ));
+    fs::create_dir_all(&dir).expect("create odin dir");
+    dir
+}
+
+fn pending(request_id: &str) -> PendingApproval {
+    PendingApproval {
+        request: ActionRequest {
+            request_id: request_id.to_string(),
+            risk_tier: RiskTier::Destructive,
+            capability: CapabilityRequest {
+                plugin: "example.safe-github".to_string(),
+                project: "demo".to_string(),
+                capability: "repo.branch.delete".to_string(),
+                scope: vec!["project".to_string()],
+                reason: "cleanup".to_string(),
+            },
+            trace_id: None,
+            input: serde_json::Value::Null,
+            requested_by: None,
+        },
+        reason_code: "destructive_requires_approval".to_string(),
+        created_at_unix: 100,
+        expires_at_unix: 200,
+    }
+}
+
+#[test]
+fn clean_state_produces_no_findings() {
+    let odin_dir = temp_odin_dir("clean");
+    fs::write(
+        odin_dir.join("state.json"),
+        r#"{"dispatched_tasks":{"t1":{"agent":"qa"}}}"#,
+    )
+    .expect("state");
+    fs::write(odin_dir.join("routing.json"), "{}").expect("routing");
+    let approvals = odin_dir.join("approvals");
+    FileApprovalStore::new(&approvals)
+        .save(&pending("req-1"))
+        .expect("save approval");
+
+    let audit = MemoryAuditSink::default();
+    let report = run_selfcheck(
+        &SelfCheckConfig::new(&odin_dir).with_approvals_dir(&approvals),
+        &audit,
+    )
+    .expect("selfcheck");
+
+    assert!(report.is_clean(), "{report:?}");
+    assert_eq!(report.checked.len(), 3);
+    assert_eq!(audit.events(), vec!["selfcheck.completed"]);
+    let _ = fs::remove_dir_all(odin_dir);
+}
+
+#[test]
+fn corrupt_files_are_quarantined_and_stale_locks_removed() {
+    let odin_dir = temp_odin_dir("corrupt");
+    fs::write(odin_dir.join("state.json"), "{not json").expect("state");
+    fs::write(odin_dir.join("routing.json"), "[]").expect("routing");
+    fs::create_dir_all(odin_dir.join("bootstrap-state.json.lock.d")).expect("lock dir");
+    let approvals = odin_dir.join("approvals");
+    fs::create_dir_all(&approvals).expect("approvals dir");
+    fs::write(
+        approvals.join("req-2.json"),
+        serde_json::to_vec(&pending("req-other")).expect("encode"),
+    )
+    .expect("mismatched approval");
+    fs::write(approvals.join("req-3.json.tmp"), "partial").expect("tmp approval");
+
+    let audit = MemoryAuditSink::default();
+    let report = run_selfcheck(
+        &SelfCheckConfig::new(&odin_dir)
+            .with_approvals_dir(&approvals)
+            .with_stale_lock_age(Duration::ZERO),
+        &audit,
+    )
+    .expect("selfcheck");
+
+    let quarantined: Vec<_> = report
+        .findings
+        .iter()
+        .filter(|finding| finding.action == SelfCheckAction::Quarantined)
+        .collect();
+    assert_eq!(quarantined.len(), 3);
+    assert!(quarantined
+        .iter()
+        .all(|finding| finding.quarantined_to.as_ref().is_some_and(|p| p.exists())));
+    assert!(!odin_dir.join("state.json").exists());
+    assert!(!odin_dir.join("bootstrap-state.json.lock.d").exists());
+    assert!(!approvals.join("req-3.json.tmp").exists());
+    assert!(audit
+        .events()
+        .iter()
+        .any(|event| event == "selfcheck.quarantined"));
+    assert!(audit
+        .events()
+        .iter()
+        .any(|event| event == "selfcheck.repaired"));
+    let _ = fs::remove_dir_all(odin_dir);
+}
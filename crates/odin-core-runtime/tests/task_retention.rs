@@ -0,0 +1,104 @@
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use odin_audit::NoopAuditSink;
+use odin_core_runtime::retention::{run_task_retention, TaskRetentionConfig};
+use sha2::{Digest, Sha256};
+
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+fn temp_queue_dir(name: &str) -> PathBuf {
+    let unique = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    let dir = std::env::temp_dir().join(format!(
+        "odin-retention-{name}-{}-{unique}",
+        std::process::id()
+    ));
+    fs::create_dir_all(dir.join("done")).expect("create done dir");
+    fs::create_dir_all(dir.join("failed")).expect("create failed dir");
+    dir
+}
+
+fn task_file(dir: &Path, name: &str, bytes: usize, modified: SystemTime) -> PathBuf {
+    let path = dir.join(name);
+    fs::write(&path, "x".repeat(bytes)).expect("write task file");
+    File::options()
+        .write(true)
+        .open(&path)
+        .and_then(|file| file.set_modified(modified))
+        .expect("set mtime");
+    path
+}
+
+#[test]
+fn aged_task_files_roll_into_daily_tarballs_with_checksums() {
+    let queue = temp_queue_dir("archive");
+    let now = UNIX_EPOCH + Duration::from_secs(1_709_164_800) + 10 * DAY;
+    let old_a = task_file(&queue.join("done"), "a.json", 10, now - 9 * DAY);
+    let old_b = task_file(&queue.join("done"), "b.json", 10, now - 9 * DAY);
+    let old_failed = task_file(&queue.join("failed"), "c.json", 10, now - 8 * DAY);
+    let fresh = task_file(&queue.join("done"), "d.json", 10, now - DAY);
+
+    let config = TaskRetentionConfig::new(&queue).with_daily_archives(true);
+    let report = run_task_retention(&config, &NoopAuditSink, now).expect("retention");
+
+    assert_eq!(report.archives.len(), 2);
+    assert_eq!(report.archives[0].files, 2);
+    assert_eq!(
+        report.archives[0].path,
+        queue.join("done/archive/done-2024-03-01.tar.gz")
+    );
+    assert_eq!(
+        report.archives[1].path,
+        queue.join("failed/archive/failed-2024-03-02.tar.gz")
+    );
+    assert!(report.removed.is_empty());
+    assert_eq!(report.bytes_freed, 30);
+    assert!(!old_a.exists() && !old_b.exists() && !old_failed.exists());
+    assert!(fresh.exists());
+
+    let archive = &report.archives[0];
+    let digest = format!(
+        "{:x}",
+        Sha256::digest(fs::read(&archive.path).expect("read tarball"))
+    );
+    assert_eq!(archive.sha256, digest);
+    let checksum = fs::read_to_string(queue.join("done/archive/done-2024-03-01.tar.gz.sha256"))
+        .expect("checksum file");
+    assert_eq!(checksum, format!("{digest}  done-2024-03-01.tar.gz\n"));
+}
+
+#[test]
+fn size_cap_deletes_oldest_task_files_first() {
+    let queue = temp_queue_dir("size");
+    let now = SystemTime::now();
+    let oldest = task_file(
+        &queue.join("failed"),
+        "1.json",
+        40,
+        now - Duration::from_secs(30),
+    );
+    let middle = task_file(
+        &queue.join("failed"),
+        "2.json",
+        40,
+        now - Duration::from_secs(20),
+    );
+    let newest = task_file(
+        &queue.join("failed"),
+        "3.json",
+        40,
+        now - Duration::from_secs(10),
+    );
+
+    let config = TaskRetentionConfig::new(&queue).with_max_bytes(100);
+    let report = run_task_retention(&config, &NoopAuditSink, now).expect("retention");
+
+    assert_eq!(report.removed, vec![oldest.clone()]);
+    assert_eq!(report.bytes_freed, 40);
+    assert!(!oldest.exists());
+    assert!(middle.exists() && newest.exists());
+}
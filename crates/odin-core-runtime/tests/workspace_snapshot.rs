@@ -0,0 +1,142 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use odin_audit::NoopAuditSink;
+use odin_core_runtime::snapshot::{CopySnapshotter, Snapshotter};
+use odin_core_runtime::{ActionExecutor, OrchestratorRuntime, RuntimeResult};
+use odin_plugin_protocol::{ActionRequest, ActionStatus, CapabilityRequest, RiskTier};
+use odin_policy_engine::StaticPolicyEngine;
+
+/// Deletes `doomed.txt` from the request's workspace.
+struct DeletingExecutor;
+
+impl ActionExecutor for DeletingExecutor {
+    fn execute(&self, request: &ActionRequest) -> RuntimeResult<serde_json::Value> {
+        let workspace = request.input["workspace"].as_str().unwrap_or_default();
+        let _ = fs::remove_file(Path::new(workspace).join("doomed.txt"));
+        Ok(serde_json::json!({ "deleted": "doomed.txt" }))
+    }
+}
+
+fn temp_dir(name: &str) -> PathBuf {
+    let unique = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    std::env::temp_dir().join(format!(
+        "odin-snapshot-{name}-{}-{unique}",
+        std::process::id()
+    ))
+}
+
+fn destructive_request(workspace: &Path) -> ActionRequest {
+    ActionRequest {
+        request_id: "req-clean".to_string(),
+        risk_tier: RiskTier::Destructive,
+        capability: CapabilityRequest {
+            plugin: "example.safe-github".to_string(),
+            project: "demo".to_string(),
+            capability: "workspace.clean".to_string(),
+            scope: vec!["project".to_string()],
+            reason: "clean build outputs".to_string(),
+        },
+        trace_id: None,
+        input: serde_json::json!({ "workspace": workspace }),
+        requested_by: None,
+    }
+}
+
+fn policy() -> StaticPolicyEngine {
+    let mut policy = StaticPolicyEngine::default();
+    policy.set_require_approval_for_destructive(false);
+    policy.allow_capability("example.safe-github", "demo", "workspace.clean");
+    policy
+}
+
+#[test]
+fn destructive_action_is_snapshotted_and_restorable() {
+    let dir = temp_dir("restore");
+    let workspace = dir.join("workspace");
+    fs::create_dir_all(workspace.join("nested")).expect("workspace");
+    fs::write(workspace.join("doomed.txt"), "keep me").expect("file");
+    fs::write(workspace.join("nested/a.txt"), "a").expect("file");
+    let snapshotter = CopySnapshotter::new(dir.join("snapshots"));
+
+    let runtime = OrchestratorRuntime::new(policy(), NoopAuditSink, DeletingExecutor)
+        .with_snapshotter(snapshotter.clone(), [&dir]);
+    let outcome = runtime
+        .handle_action(destructive_request(&workspace))
+        .expect("outcome");
+
+    assert_eq!(outcome.status, ActionStatus::Executed);
+    assert!(!workspace.join("doomed.txt").exists());
+    let snapshot = outcome.snapshot.expect("snapshot recorded in outcome");
+    assert_eq!(snapshot.snapshotter, "copy");
+
+    let loaded = snapshotter.load(&snapshot.id).expect("load snapshot");
+    snapshotter.restore(&loaded).expect("restore");
+    assert_eq!(
+        fs::read_to_string(workspace.join("doomed.txt")).expect("restored"),
+        "keep me"
+    );
+    assert!(workspace.join("nested/a.txt").is_file());
+    let _ = fs::remove_dir_all(dir);
+}
+
+#[test]
+fn snapshot_failure_blocks_execution() {
+    let dir = temp_dir("missing");
+    let runtime = OrchestratorRuntime::new(policy(), NoopAuditSink, DeletingExecutor)
+        .with_snapshotter(CopySnapshotter::new(dir.join("snapshots")), [&dir]);
+
+    let outcome = runtime
+        .handle_action(destructive_request(&dir.join("missing")))
+        .expect("outcome");
+
+    assert_eq!(outcome.status, ActionStatus::Failed);
+    assert_eq!(outcome.detail, "snapshot_failed");
+    assert!(outcome.snapshot.is_none());
+}
+
+#[test]
+fn workspaces_outside_the_allowlist_are_not_snapshotted_or_touched() {
+    let dir = temp_dir("outside");
+    let workspace = dir.join("elsewhere");
+    fs::create_dir_all(&workspace).expect("workspace");
+    fs::write(workspace.join("doomed.txt"), "keep me").expect("file");
+    let runtime = OrchestratorRuntime::new(policy(), NoopAuditSink, DeletingExecutor)
+        .with_snapshotter(
+            CopySnapshotter::new(dir.join("snapshots")),
+            [dir.join("allowed")],
+        );
+
+    let outcome = runtime
+        .handle_action(destructive_request(&workspace.join("../elsewhere")))
+        .expect("outcome");
+
+    assert_eq!(outcome.status, ActionStatus::Failed);
+    assert_eq!(outcome.detail, "snapshot_failed");
+    assert!(workspace.join("doomed.txt").exists());
+    assert!(!dir.join("snapshots").exists());
+    let _ = fs::remove_dir_all(dir);
+}
+
+#[test]
+fn snapshots_taken_in_the_same_second_get_distinct_ids() {
+    let dir = temp_dir("ids");
+    let workspace = dir.join("workspace");
+    fs::create_dir_all(&workspace).expect("workspace");
+    let snapshotter = CopySnapshotter::new(dir.join("snapshots"));
+
+    let first = snapshotter
+        .snapshot(&workspace, "req-clean")
+        .expect("first");
+    let second = snapshotter
+        .snapshot(&workspace, "req-clean")
+        .expect("second");
+
+    assert_ne!(first.id, second.id);
+    assert_ne!(first.location, second.location);
+    let _ = fs::remove_dir_all(dir);
+}
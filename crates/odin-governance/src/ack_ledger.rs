@@ -0,0 +1,181 @@
+//! Append-only ledger of install acknowledgements. Each line records who acknowledged which
+//! skill and the digest of the findings they saw, so a later install can reuse the ack only
+//! while a re-scan still produces the same findings.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use crate::risk_scan::RiskFinding;
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AckLedgerEntry {
+    pub operator: String,
+    pub skill: String,
+    /// `findings_digest` of the findings the operator acknowledged.
+    pub findings_sha256: String,
+    pub acked_at_unix: u64,
+    /// Who asked for the install, when it was recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requested_by: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LedgerMatch {
+    /// The latest ack for the skill covers these findings.
+    Current(AckLedgerEntry),
+    /// The skill was acknowledged, but for different findings.
+    Stale(AckLedgerEntry),
+    None,
+}
+
+#[derive(Debug, Error)]
+pub enum AckLedgerError {
+    #[error("ack ledger {path} is unreadable: {message}")]
+    Io { path: PathBuf, message: String },
+    #[error("ack ledger {path} line {line} is malformed: {message}")]
+    Parse {
+        path: PathBuf,
+        line: usize,
+        message: String,
+    },
+}
+
+/// sha256 over the sorted (category, pattern, severity, file) of each finding. Line numbers are
+/// left out so edits that only move a finding keep the ack.
+pub fn findings_digest(findings: &[RiskFinding]) -> String {
+    let mut keys: Vec<String> = findings
+        .iter()
+        .map(|finding| {
+            let file = finding
+                .location
+                .as_ref()
+                .map(|location| location.file.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            format!(
+                "{}\t{}\t{}\t{file}\n",
+                finding.category.as_str(),
+                finding.pattern,
+                finding.severity.as_str()
+            )
+        })
+        .collect();
+    keys.sort();
+    keys.dedup();
+    format!("{:x}", Sha256::digest(keys.concat()))
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AckLedger {
+    path: PathBuf,
+}
+
+impl AckLedger {
+    /// The file is created on the first `append`; a missing ledger has no entries.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn entries(&self) -> Result<Vec<AckLedgerEntry>, AckLedgerError> {
+        let raw = match fs::read_to_string(&self.path) {
+            Ok(raw) => raw,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(self.io_error(e)),
+        };
+        raw.lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                serde_json::from_str(line).map_err(|e| AckLedgerError::Parse {
+                    path: self.path.clone(),
+                    line: index + 1,
+                    message: e.to_string(),
+                })
+            })
+            .collect()
+    }
+
+    pub fn append(&self, entry: &AckLedgerEntry) -> Result<(), AckLedgerError> {
+        if let Some(parent) = self
+            .path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(parent).map_err(|e| self.io_error(e))?;
+        }
+        let mut line = serde_json::to_string(entry).map_err(|e| AckLedgerError::Io {
+            path: self.path.clone(),
+            message: e.to_string(),
+        })?;
+        line.push('\n');
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|e| self.io_error(e))?;
+        file.write_all(line.as_bytes())
+            .and_then(|()| file.sync_data())
+            .map_err(|e| self.io_error(e))
+    }
+
+    /// Checks the most recent ack for `skill` against `findings_sha256`; older acks for the same
+    /// skill never apply once a newer one exists.
+    pub fn lookup(
+        &self,
+        skill: &str,
+        findings_sha256: &str,
+    ) -> Result<LedgerMatch, AckLedgerError> {
+        let latest = self
+            .entries()?
+            .into_iter()
+            .rev()
+            .find(|entry| entry.skill == skill);
+        Ok(match latest {
+            Some(entry) if entry.findings_sha256 == findings_sha256 => LedgerMatch::Current(entry),
+            Some(entry) => LedgerMatch::Stale(entry),
+            None => LedgerMatch::None,
+        })
+    }
+
+    fn io_error(&self, err: std::io::Error) -> AckLedgerError {
+        AckLedgerError::Io {
+            path: self.path.clone(),
+            message: err.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::risk_scan::{FindingLocation, RiskCategory, RiskSeverity};
+
+    use super::*;
+
+    fn finding(pattern: &str, line: usize) -> RiskFinding {
+        RiskFinding {
+            category: RiskCategory::Shell,
+            pattern: pattern.to_string(),
+            severity: RiskSeverity::High,
+            location: Some(FindingLocation {
+                file: PathBuf::from("install.sh"),
+                line,
+            }),
+        }
+    }
+
+    #[test]
+    fn digest_ignores_order_and_line_moves() {
+        let a = findings_digest(&[finding("curl | sh", 3), finding("rm -rf", 9)]);
+        let b = findings_digest(&[finding("rm -rf", 12), finding("curl | sh", 4)]);
+        assert_eq!(a, b);
+        assert_ne!(a, findings_digest(&[finding("curl | sh", 3)]));
+    }
+}
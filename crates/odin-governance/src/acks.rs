@@ -0,0 +1,271 @@
+//! Signed ack files: a non-interactive stand-in for `--ack` so fleet automation can satisfy
+//! install gates. An operator signs (kind, subject, sha256, expiry) with an ed25519 key that
+//! must be listed in the ack trust store.
+
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use odin_plugin_protocol::SkillRecord;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+/// What an ack covers. Only skill installs are gated today; there is no plugin install gate for
+/// an ack to satisfy, so ack files of any other kind are rejected as malformed.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AckKind {
+    Skill,
+}
+
+impl AckKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Skill => "skill",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AckFile {
+    pub schema_version: u32,
+    pub kind: AckKind,
+    /// Skill name.
+    pub subject: String,
+    /// `skill_ack_digest` of what is being installed.
+    pub sha256: String,
+    /// Id of the signing key in the trust store.
+    pub operator: String,
+    pub expires_at_unix: u64,
+    /// Hex-encoded ed25519 signature over `signed_message()`.
+    pub signature: String,
+}
+
+impl AckFile {
+    pub fn signed_message(&self) -> String {
+        format!(
+            "odin-ack-v1\n{}\n{}\n{}\n{}\n{}\n",
+            self.kind.as_str(),
+            self.subject,
+            self.sha256,
+            self.operator,
+            self.expires_at_unix
+        )
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TrustedOperator {
+    pub id: String,
+    /// Hex-encoded 32-byte ed25519 public key.
+    pub public_key: String,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AckTrustStore {
+    pub schema_version: u32,
+    #[serde(default)]
+    pub operators: Vec<TrustedOperator>,
+}
+
+impl AckTrustStore {
+    pub fn from_yaml(raw: &str) -> Result<Self, AckError> {
+        serde_yml::from_str(raw).map_err(|e| AckError::Malformed(e.to_string()))
+    }
+
+    fn key_for(&self, operator: &str) -> Result<VerifyingKey, AckError> {
+        let entry = self
+            .operators
+            .iter()
+            .find(|entry| entry.id == operator)
+            .ok_or_else(|| AckError::UnknownOperator(operator.to_string()))?;
+        let bytes: [u8; 32] = decode_hex(&entry.public_key)
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| AckError::Malformed(format!("public key for {operator}")))?;
+        VerifyingKey::from_bytes(&bytes)
+            .map_err(|_| AckError::Malformed(format!("public key for {operator}")))
+    }
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum AckError {
+    #[error("malformed ack: {0}")]
+    Malformed(String),
+    #[error("operator {0} is not in the ack trust store")]
+    UnknownOperator(String),
+    #[error("ack is for {found}, expected {expected}")]
+    SubjectMismatch { expected: String, found: String },
+    #[error("ack sha256 does not match the {0} being installed")]
+    HashMismatch(String),
+    #[error("ack expired at {0}")]
+    Expired(u64),
+    #[error("ack signature does not verify")]
+    BadSignature,
+}
+
+impl AckError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Malformed(_) => "ack_malformed",
+            Self::UnknownOperator(_) => "ack_operator_untrusted",
+            Self::SubjectMismatch { .. } => "ack_subject_mismatch",
+            Self::HashMismatch(_) => "ack_hash_mismatch",
+            Self::Expired(_) => "ack_expired",
+            Self::BadSignature => "ack_signature_invalid",
+        }
+    }
+}
+
+/// A verified ack together with the file exactly as supplied, for the audit trail.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerifiedAck {
+    pub ack: AckFile,
+    pub raw: String,
+}
+
+/// Digest a skill ack must carry: sha256 of the record's canonical JSON.
+pub fn skill_ack_digest(record: &SkillRecord) -> String {
+    let canonical = serde_json::to_vec(record).unwrap_or_default();
+    format!("{:x}", Sha256::digest(canonical))
+}
+
+/// Parses `raw` and checks it acknowledges exactly `kind`/`subject`/`sha256`, has not
+/// expired at `now_unix`, and is signed by an operator in `trust`.
+pub fn verify_ack_file(
+    raw: &str,
+    trust: &AckTrustStore,
+    kind: AckKind,
+    subject: &str,
+    sha256: &str,
+    now_unix: u64,
+) -> Result<VerifiedAck, AckError> {
+    let ack: AckFile = serde_yml::from_str(raw).map_err(|e| AckError::Malformed(e.to_string()))?;
+    if ack.schema_version != 1 {
+        return Err(AckError::Malformed(format!(
+            "unsupported schema_version {}",
+            ack.schema_version
+        )));
+    }
+    if ack.kind != kind || ack.subject != subject {
+        return Err(AckError::SubjectMismatch {
+            expected: format!("{} {subject}", kind.as_str()),
+            found: format!("{} {}", ack.kind.as_str(), ack.subject),
+        });
+    }
+    if !ack.sha256.eq_ignore_ascii_case(sha256) {
+        return Err(AckError::HashMismatch(kind.as_str().to_string()));
+    }
+    if ack.expires_at_unix <= now_unix {
+        return Err(AckError::Expired(ack.expires_at_unix));
+    }
+    let key = trust.key_for(&ack.operator)?;
+    let signature: [u8; 64] = decode_hex(&ack.signature)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(AckError::BadSignature)?;
+    key.verify(
+        ack.signed_message().as_bytes(),
+        &Signature::from_bytes(&signature),
+    )
+    .map_err(|_| AckError::BadSignature)?;
+
+    Ok(VerifiedAck {
+        ack,
+        raw: raw.to_string(),
+    })
+}
+
+fn decode_hex(value: &str) -> Option<Vec<u8>> {
+    let value = value.trim();
+    if !value.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..value.len())
+        .step_by(2)
+        .map(|idx| u8::from_str_radix(value.get(idx..idx + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use ed25519_dalek::{Signer, SigningKey};
+
+    use super::*;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    fn signed_ack(key: &SigningKey, sha256: &str, expires_at_unix: u64) -> String {
+        let mut ack = AckFile {
+            schema_version: 1,
+            kind: AckKind::Skill,
+            subject: "brainstorming".to_string(),
+            sha256: sha256.to_string(),
+            operator: "fleet-ops".to_string(),
+            expires_at_unix,
+            signature: String::new(),
+        };
+        ack.signature = hex(&key.sign(ack.signed_message().as_bytes()).to_bytes());
+        serde_yml::to_string(&ack).expect("yaml")
+    }
+
+    #[test]
+    fn ack_verifies_only_for_matching_subject_key_and_expiry() {
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let trust = AckTrustStore {
+            schema_version: 1,
+            operators: vec![TrustedOperator {
+                id: "fleet-ops".to_string(),
+                public_key: hex(key.verifying_key().as_bytes()),
+            }],
+        };
+        let digest = skill_ack_digest(&SkillRecord::default_for("brainstorming"));
+        let raw = signed_ack(&key, &digest, 2_000);
+
+        let verified = verify_ack_file(
+            &raw,
+            &trust,
+            AckKind::Skill,
+            "brainstorming",
+            &digest,
+            1_000,
+        )
+        .expect("verified");
+        assert_eq!(verified.ack.operator, "fleet-ops");
+        assert_eq!(verified.raw, raw);
+
+        let verify = |raw: &str, subject: &str, sha256: &str, now: u64| {
+            verify_ack_file(raw, &trust, AckKind::Skill, subject, sha256, now)
+                .expect_err("rejected")
+                .code()
+        };
+        assert_eq!(verify(&raw, "brainstorming", &digest, 2_000), "ack_expired");
+        assert_eq!(
+            verify(&raw, "other", &digest, 1_000),
+            "ack_subject_mismatch"
+        );
+        assert_eq!(
+            verify(&raw, "brainstorming", "00", 1_000),
+            "ack_hash_mismatch"
+        );
+
+        let forged = signed_ack(&SigningKey::from_bytes(&[9; 32]), &digest, 2_000);
+        assert_eq!(
+            verify(&forged, "brainstorming", &digest, 1_000),
+            "ack_signature_invalid"
+        );
+        let tampered = raw.replace("2000", "3000");
+        assert_eq!(
+            verify(&tampered, "brainstorming", &digest, 1_000),
+            "ack_signature_invalid"
+        );
+        let unknown = raw.replace("fleet-ops", "intruder");
+        assert_eq!(
+            verify(&unknown, "brainstorming", &digest, 1_000),
+            "ack_operator_untrusted"
+        );
+        let plugin = raw.replace("kind: skill", "kind: plugin");
+        assert_eq!(
+            verify(&plugin, "brainstorming", &digest, 1_000),
+            "ack_malformed"
+        );
+    }
+}
@@ -0,0 +1,349 @@
+//! Per-plugin permission models layered on top of capability manifests. A plugin with a
+//! finer-grained model than "capability granted for these scopes" ships a
+//! `PluginPolicyAdapter`, and the runtime consults the adapter registered under the plugin's
+//! name for every request the manifest already allows.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use odin_plugin_protocol::PluginPermissionEnvelope;
+use serde_json::Value;
+
+use crate::plugins::{
+    huginn_policy_from_envelope, Action as HuginnAction, NavigationLedger, PermissionDecision,
+};
+
+pub trait PluginPolicyAdapter: Send + Sync {
+    /// Name of the plugin this adapter governs.
+    fn plugin(&self) -> &str;
+
+    /// Capabilities only this plugin may request; other plugins asking for them are denied.
+    fn reserves(&self, capability: &str) -> bool;
+
+    /// Decision for a request the manifest grants; `None` leaves it to the manifest. `task_id` is
+    /// the runtime's id of the task that issued the request, never a value from `input`.
+    fn evaluate(
+        &self,
+        capability: &str,
+        input: &Value,
+        envelope: &PluginPermissionEnvelope,
+        task_id: Option<&str>,
+    ) -> Option<PermissionDecision>;
+
+    /// Called once the runtime has finished `task_id`, to drop per-task state.
+    fn finish_task(&self, _task_id: &str) {}
+}
+
+/// Adapters keyed by plugin name.
+#[derive(Clone, Default)]
+pub struct PluginPolicyAdapterRegistry {
+    adapters: BTreeMap<String, Arc<dyn PluginPolicyAdapter>>,
+}
+
+impl PluginPolicyAdapterRegistry {
+    /// An empty registry; see `builtin` for the adapters core ships with.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn builtin() -> Self {
+        Self::new().with_adapter(Arc::new(HuginnPolicyAdapter::default()))
+    }
+
+    /// Registers `adapter`, replacing any adapter for the same plugin.
+    pub fn with_adapter(mut self, adapter: Arc<dyn PluginPolicyAdapter>) -> Self {
+        self.adapters.insert(adapter.plugin().to_string(), adapter);
+        self
+    }
+
+    pub fn get(&self, plugin: &str) -> Option<&Arc<dyn PluginPolicyAdapter>> {
+        self.adapters.get(plugin)
+    }
+
+    pub fn plugins(&self) -> impl Iterator<Item = &str> {
+        self.adapters.keys().map(String::as_str)
+    }
+
+    /// The adapters' decision for `plugin` requesting `capability`, or `None` when no adapter
+    /// has one.
+    pub fn decision(
+        &self,
+        plugin: &str,
+        capability: &str,
+        input: &Value,
+        envelope: &PluginPermissionEnvelope,
+        task_id: Option<&str>,
+    ) -> Option<PermissionDecision> {
+        if self
+            .adapters
+            .iter()
+            .any(|(owner, adapter)| owner != plugin && adapter.reserves(capability))
+        {
+            return Some(PermissionDecision::Deny {
+                reason_code: "plugin_permission_denied".to_string(),
+            });
+        }
+        self.get(plugin)?
+            .evaluate(capability, input, envelope, task_id)
+    }
+
+    /// Lets every adapter drop the per-task state it kept for `task_id`.
+    pub fn finish_task(&self, task_id: &str) {
+        for adapter in self.adapters.values() {
+            adapter.finish_task(task_id);
+        }
+    }
+
+    /// Reason code denying `plugin` the `capability`, or `None` when no adapter objects.
+    /// Approval requirements are not denials; see `decision`.
+    pub fn denial(
+        &self,
+        plugin: &str,
+        capability: &str,
+        input: &Value,
+        envelope: &PluginPermissionEnvelope,
+    ) -> Option<String> {
+        match self.decision(plugin, capability, input, envelope, None)? {
+            PermissionDecision::Deny { reason_code } => Some(reason_code),
+            PermissionDecision::Allow { .. } | PermissionDecision::RequireApproval { .. } => None,
+        }
+    }
+}
+
+/// Huginn browser automation: observe, workspace and command requests are checked against
+/// the domain, workspace and command allowlists in its envelope. Form fills and clicks on
+/// allowlisted domains require approval. Observes draw on the envelope's `huginn.budget`
+/// domain budgets, counted per adapter and per runtime task; requests outside a task share one
+/// page budget.
+#[derive(Clone, Debug, Default)]
+pub struct HuginnPolicyAdapter {
+    ledger: Arc<Mutex<NavigationLedger>>,
+}
+
+impl PluginPolicyAdapter for HuginnPolicyAdapter {
+    fn plugin(&self) -> &str {
+        "huginn"
+    }
+
+    fn reserves(&self, capability: &str) -> bool {
+        matches!(
+            capability,
+            "browser.observe" | "workspace.read" | "command.run"
+        ) || capability.starts_with("huginn.")
+    }
+
+    fn evaluate(
+        &self,
+        capability: &str,
+        input: &Value,
+        envelope: &PluginPermissionEnvelope,
+        task_id: Option<&str>,
+    ) -> Option<PermissionDecision> {
+        let Some(action) = huginn_action_from_capability(capability, input) else {
+            return capability
+                .starts_with("huginn.")
+                .then(|| PermissionDecision::Deny {
+                    reason_code: "manifest_huginn_capability_unknown".to_string(),
+                });
+        };
+        let Ok(mut ledger) = self.ledger.lock() else {
+            return Some(PermissionDecision::Deny {
+                reason_code: "navigation_budget_unavailable".to_string(),
+            });
+        };
+        let now_unix = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        Some(huginn_policy_from_envelope(envelope).evaluate_navigation(
+            action,
+            task_id.unwrap_or_default(),
+            &mut ledger,
+            now_unix,
+        ))
+    }
+
+    fn finish_task(&self, task_id: &str) {
+        if let Ok(mut ledger) = self.ledger.lock() {
+            ledger.finish_task(task_id);
+        }
+    }
+}
+
+fn huginn_action_from_capability(capability: &str, input: &Value) -> Option<HuginnAction> {
+    match capability {
+        "browser.observe" | "huginn.observe_url" => Some(HuginnAction::ObserveUrl(
+            input_string(input, "url").unwrap_or_default(),
+        )),
+        "huginn.observe_domain" => Some(HuginnAction::ObserveUrl(canonical_observe_domain_input(
+            input,
+        ))),
+        "workspace.read" | "huginn.workspace.read" => Some(HuginnAction::ReadWorkspace(
+            input_string(input, "workspace").unwrap_or_default(),
+        )),
+        "command.run" | "huginn.command.run" => Some(HuginnAction::RunCommand(
+            input_string(input, "command").unwrap_or_default(),
+        )),
+        "huginn.login" => Some(HuginnAction::Login),
+        "huginn.payment" => Some(HuginnAction::Payment),
+        "huginn.pii_submit" => Some(HuginnAction::PiiSubmit),
+        "huginn.file_upload" => Some(HuginnAction::FileUpload),
+        "huginn.fill_form" => Some(HuginnAction::FillForm(
+            input_string(input, "url").unwrap_or_default(),
+        )),
+        "huginn.click" => Some(HuginnAction::Click(
+            input_string(input, "url").unwrap_or_default(),
+        )),
+        _ => None,
+    }
+}
+
+fn canonical_observe_domain_input(input: &Value) -> String {
+    let value = input_string(input, "domain")
+        .or_else(|| input_string(input, "url"))
+        .unwrap_or_default();
+    let trimmed = value.trim();
+    if trimmed.starts_with("http://") || trimmed.starts_with("https://") {
+        trimmed.to_string()
+    } else if trimmed.is_empty() {
+        String::new()
+    } else {
+        format!("https://{trimmed}")
+    }
+}
+
+fn input_string(input: &Value, key: &str) -> Option<String> {
+    input
+        .get(key)
+        .and_then(Value::as_str)
+        .map(ToOwned::to_owned)
+}
+
+#[cfg(test)]
+mod tests {
+    use odin_plugin_protocol::{DelegationCapability, TrustLevel};
+    use serde_json::json;
+
+    use super::*;
+
+    /// Deploys only to environments listed in the `deploy.run` scope.
+    struct DeployAdapter;
+
+    impl PluginPolicyAdapter for DeployAdapter {
+        fn plugin(&self) -> &str {
+            "deployer"
+        }
+
+        fn reserves(&self, capability: &str) -> bool {
+            capability == "deploy.run"
+        }
+
+        fn evaluate(
+            &self,
+            capability: &str,
+            input: &Value,
+            envelope: &PluginPermissionEnvelope,
+            _task_id: Option<&str>,
+        ) -> Option<PermissionDecision> {
+            let env = input.get("environment")?.as_str()?;
+            let allowed = envelope
+                .permissions
+                .iter()
+                .any(|grant| grant.id == capability && grant.scope.iter().any(|s| s == env));
+            Some(if allowed {
+                PermissionDecision::Allow {
+                    reason_code: "deploy_environment_allowed".to_string(),
+                }
+            } else {
+                PermissionDecision::Deny {
+                    reason_code: "deploy_environment_not_allowed".to_string(),
+                }
+            })
+        }
+    }
+
+    fn envelope(plugin: &str, id: &str, scope: &[&str]) -> PluginPermissionEnvelope {
+        PluginPermissionEnvelope {
+            plugin: plugin.to_string(),
+            trust_level: TrustLevel::Caution,
+            permissions: vec![DelegationCapability {
+                id: id.to_string(),
+                scope: scope.iter().map(|s| s.to_string()).collect(),
+            }],
+        }
+    }
+
+    #[test]
+    fn adapters_govern_their_plugin_and_reserve_their_capabilities() {
+        let adapters = PluginPolicyAdapterRegistry::builtin().with_adapter(Arc::new(DeployAdapter));
+        assert_eq!(
+            adapters.plugins().collect::<Vec<_>>(),
+            ["deployer", "huginn"]
+        );
+
+        let deploy = envelope("deployer", "deploy.run", &["staging"]);
+        let denial = |plugin, env: &str| {
+            adapters.denial(
+                plugin,
+                "deploy.run",
+                &json!({ "environment": env }),
+                &deploy,
+            )
+        };
+        assert_eq!(denial("deployer", "staging"), None);
+        assert_eq!(
+            denial("deployer", "production").as_deref(),
+            Some("deploy_environment_not_allowed")
+        );
+        assert_eq!(
+            denial("example.git", "staging").as_deref(),
+            Some("plugin_permission_denied")
+        );
+
+        let huginn = envelope("huginn", "huginn.observe_url", &["example.com"]);
+        assert_eq!(
+            adapters
+                .denial("huginn", "huginn.superpower", &Value::Null, &huginn)
+                .as_deref(),
+            Some("manifest_huginn_capability_unknown")
+        );
+        assert_eq!(
+            adapters.denial("example.git", "repo.read", &Value::Null, &deploy),
+            None
+        );
+    }
+
+    #[test]
+    fn huginn_page_budgets_follow_the_runtime_task_not_the_input() {
+        let adapters = PluginPolicyAdapterRegistry::builtin();
+        let huginn = PluginPermissionEnvelope {
+            permissions: vec![
+                DelegationCapability {
+                    id: "huginn.enabled".to_string(),
+                    scope: vec![],
+                },
+                DelegationCapability {
+                    id: "browser.observe".to_string(),
+                    scope: vec!["example.com".to_string()],
+                },
+                DelegationCapability {
+                    id: "huginn.budget".to_string(),
+                    scope: vec!["example.com:pages_per_task=1".to_string()],
+                },
+            ],
+            ..envelope("huginn", "browser.observe", &[])
+        };
+        let observe = |page: &str, claimed_task: &str, task_id| {
+            let input =
+                json!({ "url": format!("https://example.com/{page}"), "task_id": claimed_task });
+            adapters.decision("huginn", "browser.observe", &input, &huginn, task_id)
+        };
+        let allowed = |decision| matches!(decision, Some(PermissionDecision::Allow { .. }));
+
+        assert!(allowed(observe("a", "t1", Some("task-1"))));
+        assert!(!allowed(observe("b", "fresh-task", Some("task-1"))));
+        adapters.finish_task("task-1");
+        assert!(allowed(observe("b", "t1", Some("task-1"))));
+    }
+}
@@ -0,0 +1,123 @@
+use std::collections::BTreeMap;
+
+use odin_plugin_protocol::{OutcomeWarning, OutcomeWarningKind};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CapabilityDeprecation {
+    pub kind: OutcomeWarningKind,
+    pub replacement: Option<String>,
+    /// Date (or release) after which the capability id stops resolving.
+    pub sunset: Option<String>,
+}
+
+/// Capability ids that still dispatch but should be migrated away from.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CapabilityDeprecations {
+    entries: BTreeMap<String, CapabilityDeprecation>,
+}
+
+impl CapabilityDeprecations {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Registers `alias` as a deprecated spelling of `replacement`.
+    pub fn deprecate_alias(&mut self, alias: impl Into<String>, replacement: impl Into<String>) {
+        self.entries.insert(
+            alias.into(),
+            CapabilityDeprecation {
+                kind: OutcomeWarningKind::DeprecatedAlias,
+                replacement: Some(replacement.into()),
+                sunset: None,
+            },
+        );
+    }
+
+    /// Registers `capability` as scheduled for removal at `sunset`.
+    pub fn schedule_sunset(
+        &mut self,
+        capability: impl Into<String>,
+        sunset: impl Into<String>,
+        replacement: Option<String>,
+    ) {
+        self.entries.insert(
+            capability.into(),
+            CapabilityDeprecation {
+                kind: OutcomeWarningKind::Sunset,
+                replacement,
+                sunset: Some(sunset.into()),
+            },
+        );
+    }
+
+    pub fn lookup(&self, capability: &str) -> Option<&CapabilityDeprecation> {
+        self.entries.get(capability)
+    }
+
+    pub fn warning_for(&self, plugin: &str, capability: &str) -> Option<OutcomeWarning> {
+        let deprecation = self.lookup(capability)?;
+        let mut message = match deprecation.kind {
+            OutcomeWarningKind::DeprecatedAlias => {
+                format!("capability {capability} is a deprecated alias")
+            }
+            OutcomeWarningKind::Sunset => format!(
+                "capability {capability} is scheduled for removal on {}",
+                deprecation
+                    .sunset
+                    .as_deref()
+                    .unwrap_or("an unannounced date")
+            ),
+        };
+        if let Some(replacement) = &deprecation.replacement {
+            message.push_str(&format!("; use {replacement}"));
+        }
+
+        Some(OutcomeWarning {
+            kind: deprecation.kind.clone(),
+            plugin: plugin.to_string(),
+            capability: capability.to_string(),
+            replacement: deprecation.replacement.clone(),
+            sunset: deprecation.sunset.clone(),
+            message,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn alias_warning_names_replacement() {
+        let mut deprecations = CapabilityDeprecations::new();
+        deprecations.deprecate_alias("repo.fetch", "repo.read");
+
+        let warning = deprecations
+            .warning_for("example.safe-github", "repo.fetch")
+            .expect("warning");
+
+        assert_eq!(warning.kind, OutcomeWarningKind::DeprecatedAlias);
+        assert_eq!(warning.replacement.as_deref(), Some("repo.read"));
+        assert!(warning.message.contains("use repo.read"));
+        assert!(deprecations
+            .warning_for("example.safe-github", "repo.read")
+            .is_none());
+    }
+
+    #[test]
+    fn sunset_warning_carries_date() {
+        let mut deprecations = CapabilityDeprecations::new();
+        deprecations.schedule_sunset("vcs.pr.read", "2026-12-31", None);
+
+        let warning = deprecations
+            .warning_for("private.ops-watchdog", "vcs.pr.read")
+            .expect("warning");
+
+        assert_eq!(warning.sunset.as_deref(), Some("2026-12-31"));
+        assert!(warning.message.contains("2026-12-31"));
+    }
+}
@@ -0,0 +1,375 @@
+//! Semantic diffs of governance files: which grants, trust levels and scopes changed, and
+//! whether each change widens or narrows what a skill or plugin may do.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use odin_plugin_protocol::{
+    DelegationCapability, PluginPermissionEnvelope, SkillRecord, SkillRegistry, TrustLevel,
+};
+use serde::Serialize;
+
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RiskDelta {
+    Increase,
+    Decrease,
+    Neutral,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+pub struct GovernanceChange {
+    /// e.g. `skill_added`, `trust_changed`, `capability_added`, `scope_expanded`.
+    pub kind: String,
+    /// The skill or plugin the change applies to.
+    pub subject: String,
+    pub detail: String,
+    pub risk: RiskDelta,
+}
+
+impl GovernanceChange {
+    pub fn new(
+        kind: &str,
+        subject: impl Into<String>,
+        detail: impl Into<String>,
+        risk: RiskDelta,
+    ) -> Self {
+        Self {
+            kind: kind.to_string(),
+            subject: subject.into(),
+            detail: detail.into(),
+            risk,
+        }
+    }
+
+    /// One line per change; risk-increasing changes are marked with `!`.
+    pub fn render(&self) -> String {
+        let marker = match self.risk {
+            RiskDelta::Increase => '!',
+            RiskDelta::Decrease => '-',
+            RiskDelta::Neutral => ' ',
+        };
+        format!("{marker} {} {}: {}", self.kind, self.subject, self.detail)
+    }
+}
+
+pub fn diff_skill_registries(old: &SkillRegistry, new: &SkillRegistry) -> Vec<GovernanceChange> {
+    let old_skills = by_name(&old.skills, |skill| &skill.name);
+    let new_skills = by_name(&new.skills, |skill| &skill.name);
+    let mut changes = Vec::new();
+
+    for (name, skill) in &new_skills {
+        let Some(previous) = old_skills.get(name) else {
+            changes.push(GovernanceChange::new(
+                "skill_added",
+                *name,
+                format!("{} from {}", trust_label(&skill.trust_level), skill.source),
+                RiskDelta::Increase,
+            ));
+            continue;
+        };
+        changes.extend(trust_change(
+            name,
+            &previous.trust_level,
+            &skill.trust_level,
+        ));
+        changes.extend(source_changes(name, previous, skill));
+        changes.extend(capability_changes(
+            name,
+            &previous.capabilities,
+            &skill.capabilities,
+        ));
+    }
+    for name in old_skills
+        .keys()
+        .filter(|name| !new_skills.contains_key(*name))
+    {
+        changes.push(GovernanceChange::new(
+            "skill_removed",
+            *name,
+            "no longer registered",
+            RiskDelta::Decrease,
+        ));
+    }
+    changes
+}
+
+pub fn diff_permission_envelopes(
+    old: &[PluginPermissionEnvelope],
+    new: &[PluginPermissionEnvelope],
+) -> Vec<GovernanceChange> {
+    let old_envelopes = by_name(old, |envelope| &envelope.plugin);
+    let new_envelopes = by_name(new, |envelope| &envelope.plugin);
+    let mut changes = Vec::new();
+
+    for (plugin, envelope) in &new_envelopes {
+        let Some(previous) = old_envelopes.get(plugin) else {
+            let ids: Vec<&str> = envelope.permissions.iter().map(|p| p.id.as_str()).collect();
+            changes.push(GovernanceChange::new(
+                "plugin_added",
+                *plugin,
+                format!(
+                    "{} with [{}]",
+                    trust_label(&envelope.trust_level),
+                    ids.join(", ")
+                ),
+                RiskDelta::Increase,
+            ));
+            continue;
+        };
+        changes.extend(trust_change(
+            plugin,
+            &previous.trust_level,
+            &envelope.trust_level,
+        ));
+        changes.extend(capability_changes(
+            plugin,
+            &previous.permissions,
+            &envelope.permissions,
+        ));
+    }
+    for plugin in old_envelopes
+        .keys()
+        .filter(|plugin| !new_envelopes.contains_key(*plugin))
+    {
+        changes.push(GovernanceChange::new(
+            "plugin_removed",
+            *plugin,
+            "envelope removed",
+            RiskDelta::Decrease,
+        ));
+    }
+    changes
+}
+
+fn by_name<T>(items: &[T], name: impl Fn(&T) -> &String) -> BTreeMap<&str, &T> {
+    items
+        .iter()
+        .map(|item| (name(item).as_str(), item))
+        .collect()
+}
+
+/// Trusted carries the most privilege, so moving toward it increases risk.
+fn trust_rank(level: &TrustLevel) -> u8 {
+    match level {
+        TrustLevel::Untrusted => 0,
+        TrustLevel::Caution => 1,
+        TrustLevel::Trusted => 2,
+    }
+}
+
+fn trust_label(level: &TrustLevel) -> &'static str {
+    match level {
+        TrustLevel::Trusted => "trusted",
+        TrustLevel::Caution => "caution",
+        TrustLevel::Untrusted => "untrusted",
+    }
+}
+
+fn trust_change(subject: &str, old: &TrustLevel, new: &TrustLevel) -> Option<GovernanceChange> {
+    if old == new {
+        return None;
+    }
+    let risk = if trust_rank(new) > trust_rank(old) {
+        RiskDelta::Increase
+    } else {
+        RiskDelta::Decrease
+    };
+    Some(GovernanceChange::new(
+        "trust_changed",
+        subject,
+        format!("{} -> {}", trust_label(old), trust_label(new)),
+        risk,
+    ))
+}
+
+fn source_changes(name: &str, old: &SkillRecord, new: &SkillRecord) -> Vec<GovernanceChange> {
+    let mut changes = Vec::new();
+    if old.source != new.source {
+        changes.push(GovernanceChange::new(
+            "source_changed",
+            name,
+            format!("{} -> {}", old.source, new.source),
+            RiskDelta::Increase,
+        ));
+    }
+    let risk = match (&old.pinned_version, &new.pinned_version) {
+        (old_pin, new_pin) if old_pin == new_pin => return changes,
+        (Some(_), None) => RiskDelta::Increase,
+        (None, Some(_)) => RiskDelta::Decrease,
+        _ => RiskDelta::Neutral,
+    };
+    let pin = |pin: &Option<String>| pin.clone().unwrap_or_else(|| "unpinned".to_string());
+    changes.push(GovernanceChange::new(
+        "pin_changed",
+        name,
+        format!(
+            "{} -> {}",
+            pin(&old.pinned_version),
+            pin(&new.pinned_version)
+        ),
+        risk,
+    ));
+    changes
+}
+
+fn capability_changes(
+    subject: &str,
+    old: &[DelegationCapability],
+    new: &[DelegationCapability],
+) -> Vec<GovernanceChange> {
+    let scopes = |capabilities: &[DelegationCapability]| -> BTreeMap<String, BTreeSet<String>> {
+        let mut scopes: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        for capability in capabilities {
+            scopes
+                .entry(capability.id.clone())
+                .or_default()
+                .extend(capability.scope.iter().cloned());
+        }
+        scopes
+    };
+    let old_scopes = scopes(old);
+    let new_scopes = scopes(new);
+    let list = |values: &BTreeSet<String>| values.iter().cloned().collect::<Vec<_>>().join(", ");
+    let mut changes = Vec::new();
+
+    for (id, scope) in &new_scopes {
+        let Some(previous) = old_scopes.get(id) else {
+            changes.push(GovernanceChange::new(
+                "capability_added",
+                subject,
+                format!("{id} [{}]", list(scope)),
+                RiskDelta::Increase,
+            ));
+            continue;
+        };
+        let added: BTreeSet<String> = scope.difference(previous).cloned().collect();
+        let removed: BTreeSet<String> = previous.difference(scope).cloned().collect();
+        if !added.is_empty() {
+            changes.push(GovernanceChange::new(
+                "scope_expanded",
+                subject,
+                format!("{id} +[{}]", list(&added)),
+                RiskDelta::Increase,
+            ));
+        }
+        if !removed.is_empty() {
+            changes.push(GovernanceChange::new(
+                "scope_narrowed",
+                subject,
+                format!("{id} -[{}]", list(&removed)),
+                RiskDelta::Decrease,
+            ));
+        }
+    }
+    for id in old_scopes.keys().filter(|id| !new_scopes.contains_key(*id)) {
+        changes.push(GovernanceChange::new(
+            "capability_removed",
+            subject,
+            id.clone(),
+            RiskDelta::Decrease,
+        ));
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use odin_plugin_protocol::SkillScope;
+
+    use super::*;
+
+    fn capability(id: &str, scope: &[&str]) -> DelegationCapability {
+        DelegationCapability {
+            id: id.to_string(),
+            scope: scope.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    fn skill(
+        name: &str,
+        trust_level: TrustLevel,
+        capabilities: Vec<DelegationCapability>,
+    ) -> SkillRecord {
+        SkillRecord {
+            name: name.to_string(),
+            trust_level,
+            source: "registry://core".to_string(),
+            pinned_version: Some("1.0.0".to_string()),
+            capabilities,
+            ..SkillRecord::default_for(name)
+        }
+    }
+
+    fn registry(skills: Vec<SkillRecord>) -> SkillRegistry {
+        SkillRegistry {
+            schema_version: 1,
+            scope: SkillScope::Project,
+            skills,
+        }
+    }
+
+    #[test]
+    fn registry_diff_flags_risk_increasing_changes() {
+        let old = registry(vec![
+            skill(
+                "brainstorming",
+                TrustLevel::Caution,
+                vec![capability("repo.read", &["project"])],
+            ),
+            skill("legacy", TrustLevel::Untrusted, Vec::new()),
+        ]);
+        let new = registry(vec![
+            skill(
+                "brainstorming",
+                TrustLevel::Trusted,
+                vec![
+                    capability("repo.read", &["project", "global"]),
+                    capability("repo.write", &["project"]),
+                ],
+            ),
+            skill("fresh", TrustLevel::Caution, Vec::new()),
+        ]);
+
+        let changes = diff_skill_registries(&old, &new);
+        let kinds: Vec<(&str, RiskDelta)> = changes
+            .iter()
+            .map(|change| (change.kind.as_str(), change.risk))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("trust_changed", RiskDelta::Increase),
+                ("scope_expanded", RiskDelta::Increase),
+                ("capability_added", RiskDelta::Increase),
+                ("skill_added", RiskDelta::Increase),
+                ("skill_removed", RiskDelta::Decrease),
+            ]
+        );
+        assert_eq!(
+            changes[0].render(),
+            "! trust_changed brainstorming: caution -> trusted"
+        );
+    }
+
+    #[test]
+    fn envelope_diff_reports_narrowing_as_decrease() {
+        let old = vec![PluginPermissionEnvelope {
+            plugin: "example.safe-github".to_string(),
+            trust_level: TrustLevel::Trusted,
+            permissions: vec![
+                capability("repo.read", &["project", "global"]),
+                capability("repo.write", &["project"]),
+            ],
+        }];
+        let mut new = old.clone();
+        new[0].trust_level = TrustLevel::Caution;
+        new[0].permissions = vec![capability("repo.read", &["project"])];
+
+        let changes = diff_permission_envelopes(&old, &new);
+        assert_eq!(changes.len(), 3);
+        assert!(changes
+            .iter()
+            .all(|change| change.risk == RiskDelta::Decrease));
+        assert!(diff_permission_envelopes(&old, &old).is_empty());
+    }
+}
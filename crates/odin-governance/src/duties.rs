@@ -0,0 +1,75 @@
+//! Separation of duties: in the projects listed, the identity that requested an install or a
+//! destructive action may not be the one that approves it. A parent project covers its
+//! children and `*` covers every project. The rule fails closed: when the requester is unknown,
+//! nobody can approve in those projects.
+//!
+//! ```yaml
+//! separation_of_duties:
+//!   projects: [payments, "infra/prod"]
+//! ```
+
+use odin_plugin_protocol::project_lineage;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct SeparationOfDuties {
+    #[serde(default)]
+    pub projects: Vec<String>,
+}
+
+impl SeparationOfDuties {
+    /// Applies to every project.
+    pub fn everywhere() -> Self {
+        Self {
+            projects: vec!["*".to_string()],
+        }
+    }
+
+    pub fn with_project(mut self, project: impl Into<String>) -> Self {
+        self.projects.push(project.into());
+        self
+    }
+
+    pub fn applies_to(&self, project: &str) -> bool {
+        let lineage = project_lineage(project);
+        self.projects
+            .iter()
+            .map(|project| project.trim().trim_matches('/'))
+            .any(|project| project == "*" || lineage.iter().any(|level| level == project))
+    }
+
+    /// Whether `approver` approving what `requested_by` asked for in `project` breaks the rule.
+    /// Identities compare trimmed and case-insensitively; an unknown requester always conflicts,
+    /// since the approver cannot be shown to be someone else.
+    pub fn violated(&self, project: &str, requested_by: Option<&str>, approver: &str) -> bool {
+        self.applies_to(project)
+            && requested_by
+                .map(str::trim)
+                .filter(|requester| !requester.is_empty())
+                .is_none_or(|requester| requester.eq_ignore_ascii_case(approver.trim()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn self_approval_is_refused_only_in_listed_projects() {
+        let duties = SeparationOfDuties::default().with_project("infra");
+        assert!(duties.violated("infra/prod", Some("alice"), " Alice "));
+        assert!(!duties.violated("infra/prod", Some("alice"), "bob"));
+        assert!(!duties.violated("payments", Some("alice"), "alice"));
+        assert!(!duties.violated("infrastructure", Some("alice"), "alice"));
+        assert!(SeparationOfDuties::everywhere().violated("payments", Some("alice"), "alice"));
+    }
+
+    #[test]
+    fn an_unknown_requester_fails_closed_where_the_rule_applies() {
+        let duties = SeparationOfDuties::default().with_project("infra");
+        assert!(duties.violated("infra/prod", None, "alice"));
+        assert!(duties.violated("infra/prod", Some("  "), "alice"));
+        assert!(!duties.violated("payments", None, "alice"));
+    }
+}
@@ -0,0 +1,239 @@
+//! Network egress allowed per plugin: which hosts, ports and HTTP methods each plugin may use.
+//! The HTTP executor checks requests against it, and plugin processes receive their own rules
+//! so a sandbox wrapper can enforce them. Plugins without rules get no egress.
+//!
+//! ```yaml
+//! schema_version: 1
+//! plugins:
+//!   example.github:
+//!     - domains: [api.github.com, "*.githubusercontent.com"]
+//!       ports: [443]
+//!       methods: [GET, POST]
+//! ```
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct EgressRule {
+    /// Hosts, or `*.example.com` for any subdomain of `example.com`.
+    pub domains: Vec<String>,
+    /// Empty allows the scheme's default port only.
+    #[serde(default)]
+    pub ports: Vec<u16>,
+    /// Empty allows any method.
+    #[serde(default)]
+    pub methods: Vec<String>,
+}
+
+impl EgressRule {
+    fn matches_host(&self, host: &str) -> bool {
+        self.domains.iter().any(|domain| {
+            let domain = domain.trim().to_ascii_lowercase();
+            match domain.strip_prefix("*.") {
+                Some(parent) => host.ends_with(&format!(".{parent}")),
+                None => host == domain,
+            }
+        })
+    }
+
+    fn allows_port(&self, port: u16, default_port: u16) -> bool {
+        if self.ports.is_empty() {
+            port == default_port
+        } else {
+            self.ports.contains(&port)
+        }
+    }
+
+    fn allows_method(&self, method: &str) -> bool {
+        self.methods.is_empty()
+            || self
+                .methods
+                .iter()
+                .any(|allowed| allowed.eq_ignore_ascii_case(method))
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct EgressPolicy {
+    pub schema_version: u32,
+    #[serde(default)]
+    pub plugins: BTreeMap<String, Vec<EgressRule>>,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum EgressPolicyError {
+    #[error("egress policy parse failed: {0}")]
+    Parse(String),
+}
+
+/// Why a request was refused; `reason_code` is what executors report.
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+#[error("{reason_code}: {plugin} {method} {url}")]
+pub struct EgressDenial {
+    pub reason_code: &'static str,
+    pub plugin: String,
+    pub method: String,
+    pub url: String,
+}
+
+impl EgressPolicy {
+    pub fn from_yaml(raw: &str) -> Result<Self, EgressPolicyError> {
+        let policy: Self =
+            serde_yml::from_str(raw).map_err(|e| EgressPolicyError::Parse(e.to_string()))?;
+        policy.validate()?;
+        Ok(policy)
+    }
+
+    /// Checks what the field types cannot: the schema version and that no rule is empty.
+    pub fn validate(&self) -> Result<(), EgressPolicyError> {
+        if self.schema_version != 1 {
+            return Err(EgressPolicyError::Parse(format!(
+                "unsupported schema_version: {}",
+                self.schema_version
+            )));
+        }
+        for (plugin, rules) in &self.plugins {
+            if rules.iter().any(|rule| rule.domains.is_empty()) {
+                return Err(EgressPolicyError::Parse(format!(
+                    "{plugin}: every rule needs at least one domain"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn with_rule(mut self, plugin: impl Into<String>, rule: EgressRule) -> Self {
+        self.plugins.entry(plugin.into()).or_default().push(rule);
+        self
+    }
+
+    /// Rules for `plugin`; empty means it has no egress.
+    pub fn rules_for(&self, plugin: &str) -> &[EgressRule] {
+        self.plugins.get(plugin).map_or(&[], Vec::as_slice)
+    }
+
+    /// Allows the request when one rule covers its host, port and method. The denial names the
+    /// closest miss: a host no rule lists, then a port, then a method.
+    pub fn check(&self, plugin: &str, method: &str, url: &str) -> Result<(), EgressDenial> {
+        let deny = |reason_code| EgressDenial {
+            reason_code,
+            plugin: plugin.to_string(),
+            method: method.to_ascii_uppercase(),
+            url: url.to_string(),
+        };
+        let Some((host, port, default_port)) = parse_url(url) else {
+            return Err(deny("egress_url_invalid"));
+        };
+        let rules: Vec<&EgressRule> = self
+            .rules_for(plugin)
+            .iter()
+            .filter(|rule| rule.matches_host(&host))
+            .collect();
+        if rules.is_empty() {
+            return Err(deny("egress_not_allowlisted"));
+        }
+        let rules: Vec<&EgressRule> = rules
+            .into_iter()
+            .filter(|rule| rule.allows_port(port, default_port))
+            .collect();
+        if rules.is_empty() {
+            return Err(deny("egress_port_not_allowed"));
+        }
+        if !rules.iter().any(|rule| rule.allows_method(method)) {
+            return Err(deny("egress_method_not_allowed"));
+        }
+        Ok(())
+    }
+}
+
+/// Lowercased host, port and the scheme's default port for an http(s) URL.
+fn parse_url(url: &str) -> Option<(String, u16, u16)> {
+    let (scheme, rest) = url.trim().split_once("://")?;
+    let default_port = match scheme.to_ascii_lowercase().as_str() {
+        "http" => 80,
+        "https" => 443,
+        _ => return None,
+    };
+    let authority = rest
+        .split(['/', '?', '#'])
+        .next()?
+        .rsplit('@')
+        .next()?
+        .to_ascii_lowercase();
+    // Bracketed IPv6 literals keep their colons inside the brackets.
+    let port_sep = authority
+        .rfind(':')
+        .filter(|&idx| authority.rfind(']').is_none_or(|end| idx > end));
+    let (host, port) = match port_sep {
+        Some(idx) => (
+            authority[..idx].to_string(),
+            authority[idx + 1..].parse().ok()?,
+        ),
+        None => (authority, default_port),
+    };
+    if host.is_empty() {
+        return None;
+    }
+    Some((host, port, default_port))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_host_then_port_then_method() {
+        let policy = EgressPolicy::from_yaml(
+            "schema_version: 1\nplugins:\n  example.github:\n    - domains: [api.github.com, \"*.githubusercontent.com\"]\n      methods: [GET]\n    - domains: [hooks.example.com]\n      ports: [8443]\n",
+        )
+        .expect("policy");
+        let check = |plugin, method, url| {
+            policy
+                .check(plugin, method, url)
+                .err()
+                .map(|denial| denial.reason_code)
+        };
+
+        assert_eq!(
+            check("example.github", "get", "https://api.github.com/repos"),
+            None
+        );
+        assert_eq!(
+            check(
+                "example.github",
+                "GET",
+                "https://raw.githubusercontent.com/a"
+            ),
+            None
+        );
+        assert_eq!(
+            check("example.github", "POST", "https://hooks.example.com:8443/x"),
+            None
+        );
+        assert_eq!(
+            check("example.github", "GET", "https://evil.example/"),
+            Some("egress_not_allowlisted")
+        );
+        assert_eq!(
+            check("example.github", "GET", "https://api.github.com:8080/"),
+            Some("egress_port_not_allowed")
+        );
+        assert_eq!(
+            check("example.github", "DELETE", "https://api.github.com/repos"),
+            Some("egress_method_not_allowed")
+        );
+        assert_eq!(
+            check("example.other", "GET", "https://api.github.com/"),
+            Some("egress_not_allowlisted")
+        );
+        assert_eq!(
+            check("example.github", "GET", "ftp://api.github.com/"),
+            Some("egress_url_invalid")
+        );
+    }
+}
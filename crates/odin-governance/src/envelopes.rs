@@ -0,0 +1,274 @@
+//! `plugin-permissions.yaml`: plugin permission envelopes persisted between runs. Plugin-wide
+//! envelopes sit under `envelopes`; `projects` holds the per-project overrides.
+
+use std::collections::BTreeSet;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use odin_plugin_protocol::{DelegationCapability, PluginPermissionEnvelope, TrustLevel};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::plugins::PluginPermissionRegistry;
+
+pub const PERMISSIONS_FILE: &str = "plugin-permissions.yaml";
+
+#[derive(Debug, Error)]
+pub enum EnvelopeFileError {
+    #[error("permission envelopes read failed: {0}")]
+    Io(String),
+    #[error("permission envelopes parse failed: {0}")]
+    Parse(String),
+    #[error("invalid permission envelopes: {0}")]
+    Invalid(String),
+    #[error("no envelope grants {0}")]
+    NotGranted(String),
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct RawEnvelopeFile {
+    #[serde(default = "default_schema_version")]
+    schema_version: u32,
+    #[serde(default)]
+    envelopes: Vec<RawEnvelope>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    projects: Vec<RawProjectEnvelopes>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct RawProjectEnvelopes {
+    project: String,
+    envelopes: Vec<RawEnvelope>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct RawEnvelope {
+    plugin: String,
+    trust_level: TrustLevel,
+    #[serde(default)]
+    permissions: Vec<RawPermission>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct RawPermission {
+    id: String,
+    #[serde(default)]
+    scope: Vec<String>,
+}
+
+fn default_schema_version() -> u32 {
+    1
+}
+
+pub fn load_permission_registry(
+    path: &Path,
+) -> Result<PluginPermissionRegistry, EnvelopeFileError> {
+    let raw = fs::read_to_string(path)
+        .map_err(|e| EnvelopeFileError::Io(format!("{}: {e}", path.display())))?;
+    parse_permission_registry(&raw)
+}
+
+pub fn parse_permission_registry(raw: &str) -> Result<PluginPermissionRegistry, EnvelopeFileError> {
+    let file: RawEnvelopeFile =
+        serde_yml::from_str(raw).map_err(|e| EnvelopeFileError::Parse(e.to_string()))?;
+    if file.schema_version != 1 {
+        return Err(EnvelopeFileError::Parse(format!(
+            "unsupported schema_version: {}",
+            file.schema_version
+        )));
+    }
+
+    let mut registry = PluginPermissionRegistry::new();
+    for envelope in checked_envelopes(file.envelopes, "plugin-wide")? {
+        registry.insert(envelope);
+    }
+    let mut projects = BTreeSet::new();
+    for level in file.projects {
+        let project = level.project.trim().trim_matches('/').to_string();
+        if project.is_empty() {
+            return Err(EnvelopeFileError::Invalid(
+                "project must not be empty".to_string(),
+            ));
+        }
+        if !projects.insert(project.clone()) {
+            return Err(EnvelopeFileError::Invalid(format!(
+                "project listed twice: {project}"
+            )));
+        }
+        for envelope in checked_envelopes(level.envelopes, &project)? {
+            registry.insert_for_project(&project, envelope);
+        }
+    }
+    Ok(registry)
+}
+
+/// Writes every envelope in `registry` to `path` through a temp file and a rename, so the
+/// runtime never loads a partial file. Leading comment lines of the existing file are kept.
+pub fn save_permission_registry(
+    path: &Path,
+    registry: &PluginPermissionRegistry,
+) -> Result<(), EnvelopeFileError> {
+    let mut file = RawEnvelopeFile {
+        schema_version: 1,
+        envelopes: registry.envelopes().map(raw_envelope).collect(),
+        projects: Vec::new(),
+    };
+    for (project, envelope) in registry.project_envelopes() {
+        match file
+            .projects
+            .iter_mut()
+            .find(|level| level.project == project)
+        {
+            Some(level) => level.envelopes.push(raw_envelope(envelope)),
+            None => file.projects.push(RawProjectEnvelopes {
+                project: project.to_string(),
+                envelopes: vec![raw_envelope(envelope)],
+            }),
+        }
+    }
+    let body = serde_yml::to_string(&file).map_err(|e| EnvelopeFileError::Io(e.to_string()))?;
+
+    let previous = fs::read_to_string(path).unwrap_or_default();
+    let mut out: String = previous
+        .lines()
+        .take_while(|line| line.trim_start().starts_with('#'))
+        .map(|line| format!("{line}\n"))
+        .collect();
+    out.push_str(&body);
+
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+    let io = |e: std::io::Error| EnvelopeFileError::Io(format!("{}: {e}", path.display()));
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent).map_err(io)?;
+    }
+    let mut handle = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(&tmp)
+        .map_err(io)?;
+    handle
+        .write_all(out.as_bytes())
+        .and_then(|()| handle.sync_data())
+        .map_err(io)?;
+    fs::rename(&tmp, path).map_err(io)
+}
+
+/// Revokes `capability` (or one `scope` of it) from `plugin` in the file at `path`; see
+/// `PluginPermissionRegistry::revoke`. Returns the grants changed.
+pub fn revoke_capability(
+    path: &Path,
+    plugin: &str,
+    capability: &str,
+    scope: Option<&str>,
+) -> Result<usize, EnvelopeFileError> {
+    let mut registry = load_permission_registry(path)?;
+    let changed = registry.revoke(plugin, capability, scope);
+    if changed == 0 {
+        let target = match scope {
+            Some(scope) => format!("{capability} ({scope}) to {plugin}"),
+            None => format!("{capability} to {plugin}"),
+        };
+        return Err(EnvelopeFileError::NotGranted(target));
+    }
+    save_permission_registry(path, &registry)?;
+    Ok(changed)
+}
+
+fn checked_envelopes(
+    envelopes: Vec<RawEnvelope>,
+    level: &str,
+) -> Result<Vec<PluginPermissionEnvelope>, EnvelopeFileError> {
+    let mut plugins = BTreeSet::new();
+    envelopes
+        .into_iter()
+        .map(|envelope| {
+            let plugin = envelope.plugin.trim().to_string();
+            if plugin.is_empty() {
+                return Err(EnvelopeFileError::Invalid(format!(
+                    "{level}: plugin must not be empty"
+                )));
+            }
+            if !plugins.insert(plugin.clone()) {
+                return Err(EnvelopeFileError::Invalid(format!(
+                    "{level}: plugin listed twice: {plugin}"
+                )));
+            }
+            let permissions = envelope
+                .permissions
+                .into_iter()
+                .map(|permission| {
+                    let id = permission.id.trim().to_string();
+                    if id.is_empty() {
+                        return Err(EnvelopeFileError::Invalid(format!(
+                            "{level}: {plugin} has a permission without an id"
+                        )));
+                    }
+                    Ok(DelegationCapability {
+                        id,
+                        scope: permission.scope,
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(PluginPermissionEnvelope {
+                plugin,
+                trust_level: envelope.trust_level,
+                permissions,
+            })
+        })
+        .collect()
+}
+
+fn raw_envelope(envelope: &PluginPermissionEnvelope) -> RawEnvelope {
+    RawEnvelope {
+        plugin: envelope.plugin.clone(),
+        trust_level: envelope.trust_level.clone(),
+        permissions: envelope
+            .permissions
+            .iter()
+            .map(|permission| RawPermission {
+                id: permission.id.clone(),
+                scope: permission.scope.clone(),
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_unknown_fields_versions_and_duplicates() {
+        let parse = |raw: &str| parse_permission_registry(raw).map(|_| ());
+        assert!(matches!(
+            parse("schema_version: 2\nenvelopes: []\n"),
+            Err(EnvelopeFileError::Parse(_))
+        ));
+        assert!(matches!(
+            parse("envelopes:\n  - plugin: huginn\n    trust_level: caution\n    grants: []\n"),
+            Err(EnvelopeFileError::Parse(_))
+        ));
+        assert!(matches!(
+            parse(
+                "envelopes:\n  - plugin: huginn\n    trust_level: caution\n  \
+                 - plugin: huginn\n    trust_level: trusted\n"
+            ),
+            Err(EnvelopeFileError::Invalid(_))
+        ));
+        assert!(matches!(
+            parse("envelopes:\n  - plugin: huginn\n    trust_level: caution\n    permissions:\n      - id: ' '\n"),
+            Err(EnvelopeFileError::Invalid(_))
+        ));
+    }
+}
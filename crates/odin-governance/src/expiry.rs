@@ -0,0 +1,120 @@
+//! Review and expiry dates on caution and untrusted skills, so a temporarily imported skill
+//! does not silently become permanent. Trusted records are exempt.
+
+use std::path::Path;
+
+use odin_plugin_protocol::{SkillRecord, SkillScope, TrustLevel};
+
+use crate::promotion::{
+    change_trust, PromotionError, PromotionEvidence, PromotionPolicy, TrustChange,
+};
+use crate::skills::{load_scoped_registry, SkillRegistryWriteError};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExpiryState {
+    ReviewOverdue,
+    Expired,
+}
+
+impl ExpiryState {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::ReviewOverdue => "review_overdue",
+            Self::Expired => "expired",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SkillExpiry {
+    pub skill: String,
+    pub trust_level: TrustLevel,
+    pub state: ExpiryState,
+    /// The `expires_at_unix` or `review_by_unix` that passed.
+    pub due_unix: u64,
+}
+
+/// Caution and untrusted skills past their expiry or review date at `now_unix`. Expiry wins
+/// when both have passed.
+pub fn overdue_skills(skills: &[SkillRecord], now_unix: u64) -> Vec<SkillExpiry> {
+    skills
+        .iter()
+        .filter(|skill| skill.trust_level != TrustLevel::Trusted)
+        .filter_map(|skill| {
+            let passed = |due: Option<u64>| due.filter(|due| now_unix >= *due);
+            let (state, due_unix) =
+                match (passed(skill.expires_at_unix), passed(skill.review_by_unix)) {
+                    (Some(due), _) => (ExpiryState::Expired, due),
+                    (None, Some(due)) => (ExpiryState::ReviewOverdue, due),
+                    (None, None) => return None,
+                };
+            Some(SkillExpiry {
+                skill: skill.name.clone(),
+                trust_level: skill.trust_level.clone(),
+                state,
+                due_unix,
+            })
+        })
+        .collect()
+}
+
+/// Demotes every expired caution skill in the `scope` registry at `path` to untrusted.
+/// Demotions need no evidence, so this only fails when the registry cannot be rewritten.
+pub fn demote_expired(
+    path: &Path,
+    scope: SkillScope,
+    now_unix: u64,
+) -> Result<Vec<TrustChange>, PromotionError> {
+    let registry =
+        load_scoped_registry(path, scope.clone()).map_err(SkillRegistryWriteError::from)?;
+    overdue_skills(&registry.skills, now_unix)
+        .into_iter()
+        .filter(|expiry| {
+            expiry.state == ExpiryState::Expired && expiry.trust_level == TrustLevel::Caution
+        })
+        .map(|expiry| {
+            change_trust(
+                path,
+                scope.clone(),
+                &expiry.skill,
+                TrustLevel::Untrusted,
+                &PromotionPolicy::default(),
+                &PromotionEvidence::default(),
+                now_unix,
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trusted_skills_are_exempt_and_expiry_wins_over_review() {
+        let skill = |name: &str, trust_level, review_by_unix, expires_at_unix| SkillRecord {
+            trust_level,
+            review_by_unix,
+            expires_at_unix,
+            ..SkillRecord::default_for(name)
+        };
+        let skills = [
+            skill("trusted", TrustLevel::Trusted, Some(10), Some(10)),
+            skill("both", TrustLevel::Caution, Some(10), Some(20)),
+            skill("review", TrustLevel::Untrusted, Some(10), Some(200)),
+            skill("current", TrustLevel::Caution, Some(200), None),
+        ];
+        let overdue = overdue_skills(&skills, 100);
+        let states: Vec<_> = overdue
+            .iter()
+            .map(|expiry| (expiry.skill.as_str(), expiry.state, expiry.due_unix))
+            .collect();
+        assert_eq!(
+            states,
+            [
+                ("both", ExpiryState::Expired, 20),
+                ("review", ExpiryState::ReviewOverdue, 10)
+            ]
+        );
+    }
+}
@@ -1,7 +1,13 @@
-use odin_plugin_protocol::{SkillRecord, TrustLevel};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use odin_plugin_protocol::{SkillRecord, SkillSignatureRecord, TrustLevel};
 use thiserror::Error;
 
-use crate::risk_scan::{scan_skill_content, RiskCategory, RiskFinding};
+use crate::ack_ledger::{findings_digest, AckLedger, AckLedgerEntry, AckLedgerError, LedgerMatch};
+use crate::risk_scan::{RiskCategory, RiskFinding, RiskScanner};
+use crate::skill_dir::{scan_skill_dir_with, SkillScanError};
+use crate::skill_signature::{signed_digest, SkillSignature, SkillSignatureError};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Ack {
@@ -13,6 +19,9 @@ pub enum Ack {
 pub enum InstallGateStatus {
     Allowed,
     BlockedAckRequired,
+    /// A finding reached the scanner's `block_at` severity, or a required signature is missing
+    /// or invalid; no ack lifts this.
+    Blocked,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -27,48 +36,205 @@ pub struct InstallPlan {
     pub status: InstallGateStatus,
     pub findings: Vec<RiskFinding>,
     pub reasons: Vec<String>,
+    /// `ack_ledger::findings_digest` of `findings`; what a ledger ack must match.
+    pub findings_sha256: String,
+    /// The ledger ack that stood in for an explicit one, if any.
+    pub ledger_ack: Option<AckLedgerEntry>,
+    /// What a signature over the skill directory must cover; `None` without a directory.
+    pub content_sha256: Option<String>,
+    /// The verified signature, for the registry record.
+    pub signature: Option<SkillSignatureRecord>,
+    /// Why the signature was rejected (`signature_invalid`).
+    pub signature_rejection: Option<String>,
 }
 
 #[derive(Debug, Error)]
 pub enum ImportGateError {
     #[error("skill name must not be empty")]
     EmptyName,
+    #[error(transparent)]
+    Scan(#[from] SkillScanError),
+    #[error(transparent)]
+    Ledger(#[from] AckLedgerError),
+    #[error(transparent)]
+    Signature(#[from] SkillSignatureError),
 }
 
 pub fn evaluate_install(
     candidate: &SkillImportCandidate,
     ack: Ack,
 ) -> Result<InstallPlan, ImportGateError> {
-    if candidate.record.name.trim().is_empty() {
-        return Err(ImportGateError::EmptyName);
+    InstallGate::default().evaluate(candidate, ack)
+}
+
+/// `evaluate_install` for a skill on disk: every file under `dir` is scanned, and the findings
+/// carry file and line locations.
+pub fn evaluate_install_dir(
+    record: &SkillRecord,
+    dir: &Path,
+    ack: Ack,
+) -> Result<InstallPlan, ImportGateError> {
+    InstallGate::default().evaluate_dir(record, dir, ack)
+}
+
+/// Install gate using a configured `RiskScanner`; the free functions use the built-in patterns.
+/// Trusted skills also need a valid signature over their directory (`with_signature`), so they
+/// only pass through `evaluate_dir`.
+#[derive(Clone, Debug, Default)]
+pub struct InstallGate {
+    scanner: RiskScanner,
+    ledger: Option<AckLedger>,
+    signature: Option<SkillSignature>,
+}
+
+impl InstallGate {
+    pub fn new(scanner: RiskScanner) -> Self {
+        Self {
+            scanner,
+            ledger: None,
+            signature: None,
+        }
     }
 
-    let findings = scan_skill_content(&candidate.scripts, candidate.readme.as_deref());
-    let mut reasons = Vec::new();
-    let has_secret_finding = findings
-        .iter()
-        .any(|finding| finding.category == RiskCategory::Secret);
+    /// Checks `signature` against the skill directory in `evaluate_dir`. An invalid signature
+    /// blocks at every trust level.
+    pub fn with_signature(mut self, signature: SkillSignature) -> Self {
+        self.signature = Some(signature);
+        self
+    }
 
-    if candidate.record.trust_level == TrustLevel::Untrusted {
-        reasons.push("untrusted_skill".to_string());
+    /// Lets a ledger ack for the same skill and findings stand in for `Ack::Accepted`.
+    pub fn with_ack_ledger(mut self, ledger: AckLedger) -> Self {
+        self.ledger = Some(ledger);
+        self
     }
-    if !candidate.scripts.is_empty() {
-        reasons.push("script_present".to_string());
+
+    pub fn evaluate(
+        &self,
+        candidate: &SkillImportCandidate,
+        ack: Ack,
+    ) -> Result<InstallPlan, ImportGateError> {
+        if candidate.record.name.trim().is_empty() {
+            return Err(ImportGateError::EmptyName);
+        }
+
+        let findings = self
+            .scanner
+            .scan_skill_content(&candidate.scripts, candidate.readme.as_deref());
+        self.gate(
+            &candidate.record,
+            findings,
+            !candidate.scripts.is_empty(),
+            SignatureCheck::default(),
+            ack,
+        )
     }
-    if has_secret_finding {
-        reasons.push("secret_touching_risk".to_string());
+
+    pub fn evaluate_dir(
+        &self,
+        record: &SkillRecord,
+        dir: &Path,
+        ack: Ack,
+    ) -> Result<InstallPlan, ImportGateError> {
+        if record.name.trim().is_empty() {
+            return Err(ImportGateError::EmptyName);
+        }
+
+        let scan = scan_skill_dir_with(dir, &self.scanner)?;
+        let has_scripts = scan.has_scripts();
+        let signature_path = self.signature.as_ref().map(|s| s.signature.as_path());
+        let mut check = SignatureCheck {
+            content_sha256: Some(signed_digest(dir, signature_path)?),
+            ..SignatureCheck::default()
+        };
+        if let Some(signature) = &self.signature {
+            match signature.verify(dir, now_unix()) {
+                Ok(verified) => check.verified = Some(verified),
+                Err(SkillSignatureError::Rejected(detail)) => check.rejection = Some(detail),
+                Err(err) => return Err(err.into()),
+            }
+        }
+        self.gate(record, scan.findings, has_scripts, check, ack)
     }
 
-    let ack_required = !reasons.is_empty();
-    let status = if ack_required && matches!(ack, Ack::None) {
-        InstallGateStatus::BlockedAckRequired
-    } else {
-        InstallGateStatus::Allowed
-    };
-
-    Ok(InstallPlan {
-        status,
-        findings,
-        reasons,
-    })
+    fn gate(
+        &self,
+        record: &SkillRecord,
+        findings: Vec<RiskFinding>,
+        has_scripts: bool,
+        signature: SignatureCheck,
+        ack: Ack,
+    ) -> Result<InstallPlan, ImportGateError> {
+        let mut reasons = Vec::new();
+        let has_secret_finding = findings
+            .iter()
+            .any(|finding| finding.category == RiskCategory::Secret);
+        let blocking = findings.iter().any(|finding| self.scanner.blocks(finding));
+        let signature_blocks = signature.rejection.is_some()
+            || (record.trust_level == TrustLevel::Trusted && signature.verified.is_none());
+
+        if record.trust_level == TrustLevel::Untrusted {
+            reasons.push("untrusted_skill".to_string());
+        }
+        if has_scripts {
+            reasons.push("script_present".to_string());
+        }
+        if has_secret_finding {
+            reasons.push("secret_touching_risk".to_string());
+        }
+        if blocking {
+            reasons.push("blocking_finding".to_string());
+        }
+        if signature.rejection.is_some() {
+            reasons.push("signature_invalid".to_string());
+        } else if signature_blocks {
+            reasons.push("signature_required".to_string());
+        }
+
+        let ack_required = !reasons.is_empty();
+        let findings_sha256 = findings_digest(&findings);
+        let mut ledger_ack = None;
+        if ack_required && !blocking && !signature_blocks && matches!(ack, Ack::None) {
+            if let Some(ledger) = &self.ledger {
+                match ledger.lookup(record.name.trim(), &findings_sha256)? {
+                    LedgerMatch::Current(entry) => ledger_ack = Some(entry),
+                    LedgerMatch::Stale(_) => reasons.push("stale_ack".to_string()),
+                    LedgerMatch::None => {}
+                }
+            }
+        }
+
+        let status = if blocking || signature_blocks {
+            InstallGateStatus::Blocked
+        } else if ack_required && matches!(ack, Ack::None) && ledger_ack.is_none() {
+            InstallGateStatus::BlockedAckRequired
+        } else {
+            InstallGateStatus::Allowed
+        };
+
+        Ok(InstallPlan {
+            status,
+            findings,
+            reasons,
+            findings_sha256,
+            ledger_ack,
+            content_sha256: signature.content_sha256,
+            signature: signature.verified,
+            signature_rejection: signature.rejection,
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+struct SignatureCheck {
+    content_sha256: Option<String>,
+    verified: Option<SkillSignatureRecord>,
+    rejection: Option<String>,
+}
+
+fn now_unix() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
 }
@@ -1,6 +1,22 @@
 //! Governance helpers for scoped skill and plugin policy controls.
 
+pub mod ack_ledger;
+pub mod acks;
+pub mod adapters;
+pub mod deprecations;
+pub mod diff;
+pub mod duties;
+pub mod egress;
+pub mod envelopes;
+pub mod expiry;
 pub mod import;
+pub mod pins;
 pub mod plugins;
+pub mod promotion;
+pub mod recommend;
 pub mod risk_scan;
+pub mod scopes;
+pub mod skill_dir;
+pub mod skill_signature;
 pub mod skills;
+pub mod workspace_boundary;
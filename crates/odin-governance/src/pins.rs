@@ -0,0 +1,253 @@
+//! Verifies `SkillRecord.pinned_version` against the skill's source. A pin is either a git
+//! commit (full or abbreviated to at least 7 hex digits) or `sha256:<hex>` over the contents of
+//! a local skill directory.
+//!
+//! Sources resolve as follows: `git+<url>[#ref]` and `https://`, `ssh://`, `git://` or `git@`
+//! URLs are asked for the commit at `ref` (default `HEAD`) with `git ls-remote`; `global:/`,
+//! `project:/` and `user:/` sources, `local:<path>` and bare paths are directories under the
+//! skills root. A directory's commit is the last one touching it, and uncommitted changes in it
+//! count as drift.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use odin_plugin_protocol::SkillRecord;
+use sha2::{Digest, Sha256};
+
+use crate::skill_dir::collect_files;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PinStatus {
+    Unpinned,
+    Match,
+    Drifted {
+        actual: String,
+    },
+    /// The source could not be resolved, so the pin cannot be confirmed.
+    Unresolvable(String),
+}
+
+impl PinStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Unpinned => "unpinned",
+            Self::Match => "match",
+            Self::Drifted { .. } => "drifted",
+            Self::Unresolvable(_) => "unresolvable",
+        }
+    }
+
+    /// Drifted and unresolvable pins both fail verification.
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Self::Drifted { .. } | Self::Unresolvable(_))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PinCheck {
+    pub skill: String,
+    pub source: String,
+    pub pinned_version: Option<String>,
+    pub status: PinStatus,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum PinSource {
+    Git { url: String, git_ref: String },
+    Dir(PathBuf),
+}
+
+#[derive(Clone, Debug)]
+pub struct PinVerifier {
+    skills_root: PathBuf,
+}
+
+impl PinVerifier {
+    /// Scope-prefixed and relative sources resolve under `skills_root`.
+    pub fn new(skills_root: impl Into<PathBuf>) -> Self {
+        Self {
+            skills_root: skills_root.into(),
+        }
+    }
+
+    pub fn check(&self, record: &SkillRecord) -> PinCheck {
+        let status = match record.pinned_version.as_deref().map(str::trim) {
+            None | Some("") => PinStatus::Unpinned,
+            Some(pin) => self.compare(&record.source, pin),
+        };
+        PinCheck {
+            skill: record.name.clone(),
+            source: record.source.clone(),
+            pinned_version: record.pinned_version.clone(),
+            status,
+        }
+    }
+
+    fn compare(&self, source: &str, pin: &str) -> PinStatus {
+        let actual = match (self.resolve(source), pin.strip_prefix("sha256:")) {
+            (PinSource::Dir(dir), Some(_)) => content_sha256(&dir),
+            (PinSource::Dir(dir), None) => dir_commit(&dir),
+            (PinSource::Git { .. }, Some(_)) => {
+                Err("content pins need a local source; pin git sources to a commit".to_string())
+            }
+            (PinSource::Git { url, git_ref }, None) => remote_commit(&url, &git_ref),
+        };
+        match actual {
+            Ok(actual) if pin_matches(pin, &actual) => PinStatus::Match,
+            Ok(actual) => PinStatus::Drifted { actual },
+            Err(detail) => PinStatus::Unresolvable(detail),
+        }
+    }
+
+    fn resolve(&self, source: &str) -> PinSource {
+        let source = source.trim();
+        let remote = ["https://", "http://", "ssh://", "git://", "git@"]
+            .iter()
+            .any(|prefix| source.starts_with(prefix));
+        if let Some(url) = source.strip_prefix("git+").or(remote.then_some(source)) {
+            let (url, git_ref) = url.split_once('#').unwrap_or((url, "HEAD"));
+            return PinSource::Git {
+                url: url.to_string(),
+                git_ref: git_ref.to_string(),
+            };
+        }
+        let path = ["global:", "project:", "user:"]
+            .iter()
+            .find_map(|prefix| source.strip_prefix(prefix))
+            .map(|rest| rest.trim_start_matches('/'))
+            .or_else(|| source.strip_prefix("local:"))
+            .unwrap_or(source);
+        PinSource::Dir(self.skills_root.join(path))
+    }
+}
+
+/// `sha256:<hex>` over every file under `dir` in path order: its relative path, a NUL, its
+/// bytes and a NUL. Tooling directories such as `.git` are skipped.
+pub fn content_sha256(dir: &Path) -> Result<String, String> {
+    content_sha256_excluding(dir, None)
+}
+
+/// `content_sha256` leaving out `excluded`, such as a signature stored beside the files.
+pub(crate) fn content_sha256_excluding(
+    dir: &Path,
+    excluded: Option<&Path>,
+) -> Result<String, String> {
+    if !dir.is_dir() {
+        return Err(format!("{} is not a directory", dir.display()));
+    }
+    let mut paths = Vec::new();
+    collect_files(dir, &mut paths).map_err(|e| e.to_string())?;
+    if let Some(excluded) = excluded.and_then(|path| fs::canonicalize(path).ok()) {
+        paths.retain(|path| fs::canonicalize(path).ok().as_deref() != Some(excluded.as_path()));
+    }
+    paths.sort();
+    let mut hasher = Sha256::new();
+    for path in paths {
+        let bytes = fs::read(&path).map_err(|e| format!("{}: {e}", path.display()))?;
+        let relative = path.strip_prefix(dir).unwrap_or(&path);
+        hasher.update(relative.to_string_lossy().as_bytes());
+        hasher.update([0]);
+        hasher.update(&bytes);
+        hasher.update([0]);
+    }
+    Ok(format!("sha256:{:x}", hasher.finalize()))
+}
+
+fn pin_matches(pin: &str, actual: &str) -> bool {
+    match pin.strip_prefix("sha256:") {
+        Some(digest) => actual
+            .strip_prefix("sha256:")
+            .is_some_and(|actual| actual.eq_ignore_ascii_case(digest)),
+        None => {
+            pin.len() >= 7
+                && pin.chars().all(|c| c.is_ascii_hexdigit())
+                && !actual.ends_with("+dirty")
+                && actual.starts_with(&pin.to_ascii_lowercase())
+        }
+    }
+}
+
+/// The last commit touching `dir`, suffixed `+dirty` when `dir` has uncommitted changes.
+fn dir_commit(dir: &Path) -> Result<String, String> {
+    if !dir.is_dir() {
+        return Err(format!("{} is not a directory", dir.display()));
+    }
+    let commit = git(dir, &["log", "-1", "--format=%H", "--", "."])?;
+    if commit.is_empty() {
+        return Err(format!("{} has no git history", dir.display()));
+    }
+    let changes = git(dir, &["status", "--porcelain", "--", "."])?;
+    Ok(if changes.is_empty() {
+        commit
+    } else {
+        format!("{commit}+dirty")
+    })
+}
+
+fn remote_commit(url: &str, git_ref: &str) -> Result<String, String> {
+    let listing = git(Path::new("."), &["ls-remote", url, git_ref])?;
+    listing
+        .lines()
+        .next()
+        .and_then(|line| line.split_whitespace().next())
+        .map(str::to_ascii_lowercase)
+        .ok_or_else(|| format!("{url} has no ref {git_ref}"))
+}
+
+fn git(dir: &Path, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(|e| format!("git {}: {e}", args[0]))?;
+    if !output.status.success() {
+        return Err(format!(
+            "git {} failed: {}",
+            args[0],
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_sources_and_matches_pins() {
+        let verifier = PinVerifier::new("/repo");
+        assert_eq!(
+            verifier.resolve("git+https://example.com/skills.git#v1"),
+            PinSource::Git {
+                url: "https://example.com/skills.git".to_string(),
+                git_ref: "v1".to_string(),
+            }
+        );
+        assert_eq!(
+            verifier.resolve("git@example.com:skills.git"),
+            PinSource::Git {
+                url: "git@example.com:skills.git".to_string(),
+                git_ref: "HEAD".to_string(),
+            }
+        );
+        assert_eq!(
+            verifier.resolve("project:/skills/huginn"),
+            PinSource::Dir(PathBuf::from("/repo/skills/huginn"))
+        );
+        assert_eq!(
+            verifier.resolve("local:/opt/skills/muninn"),
+            PinSource::Dir(PathBuf::from("/opt/skills/muninn"))
+        );
+
+        let commit = "0123456789abcdef0123456789abcdef01234567";
+        assert!(pin_matches("0123456", commit));
+        assert!(pin_matches("0123456789ABCDEF", commit));
+        assert!(!pin_matches("012345", commit));
+        assert!(!pin_matches(commit, &format!("{commit}+dirty")));
+        assert!(pin_matches("sha256:ABCD", "sha256:abcd"));
+        assert!(!pin_matches("sha256:abcd", commit));
+    }
+}
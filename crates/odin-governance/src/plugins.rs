@@ -1,13 +1,19 @@
-use std::collections::{BTreeMap, BTreeSet};
-use std::ffi::OsString;
-use std::fs;
-use std::path::{Component, Path, PathBuf};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::path::{Path, PathBuf};
 
-use odin_plugin_protocol::{DelegationCapability, PluginPermissionEnvelope, TrustLevel};
+use odin_plugin_protocol::{
+    project_lineage, DelegationCapability, PluginPermissionEnvelope, TrustLevel,
+};
+
+use crate::workspace_boundary::{
+    is_within, normalize, strip_wrapping_quotes, validate_command_paths,
+};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum HuginnMode {
     ReadObserve,
+    /// Read/observe plus form fills and clicks, each of which needs operator approval.
+    InteractWithApproval,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -16,6 +22,72 @@ struct DomainRule {
     allow_subdomains: bool,
 }
 
+impl DomainRule {
+    /// The rule as written in a scope, e.g. `*.example.com`.
+    fn key(&self) -> String {
+        if self.allow_subdomains {
+            format!("*.{}", self.host)
+        } else {
+            self.host.clone()
+        }
+    }
+}
+
+/// Request budgets for an allowlisted domain; unset limits are unlimited.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DomainBudget {
+    pub max_observes_per_hour: Option<u32>,
+    /// Distinct pages (URLs without fragment) one task may observe.
+    pub max_pages_per_task: Option<u32>,
+}
+
+/// Observes counted against `DomainBudget`s. One ledger is shared by every evaluation that
+/// should draw from the same budgets.
+#[derive(Clone, Debug, Default)]
+pub struct NavigationLedger {
+    /// Observe times within the last hour, by budget domain.
+    observes: BTreeMap<String, VecDeque<u64>>,
+    /// Pages observed, by task and budget domain.
+    pages: BTreeMap<(String, String), BTreeSet<String>>,
+}
+
+impl NavigationLedger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Drops the pages `task` observed; its observes still count toward the hourly limit.
+    pub fn finish_task(&mut self, task: &str) {
+        self.pages.retain(|(owner, _), _| owner != task);
+    }
+}
+
+/// A command scope entry: the executable name, optionally pinned to subcommands with
+/// `name:{sub,...}` and refusing flags with `!{flag,...}`, e.g. `git:{status,log,diff}!{-f}`.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct CommandRule {
+    name: String,
+    subcommands: Option<BTreeSet<String>>,
+    denied_flags: BTreeSet<String>,
+}
+
+impl CommandRule {
+    /// The reason code refusing `args`, if any.
+    fn refusal(&self, args: &[String]) -> Option<&'static str> {
+        if let Some(subcommands) = &self.subcommands {
+            if !args
+                .first()
+                .is_some_and(|first| subcommands.contains(first))
+            {
+                return Some("command_subcommand_not_allowlisted");
+            }
+        }
+        args.iter()
+            .any(|arg| self.denied_flags.iter().any(|flag| flag_matches(arg, flag)))
+            .then_some("command_flag_denied")
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Action {
     ObserveUrl(String),
@@ -25,12 +97,15 @@ pub enum Action {
     Payment,
     PiiSubmit,
     FileUpload,
+    FillForm(String),
+    Click(String),
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum PermissionDecision {
     Allow { reason_code: String },
     Deny { reason_code: String },
+    RequireApproval { reason_code: String },
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -39,7 +114,8 @@ pub struct HuginnPolicy {
     mode: HuginnMode,
     allowed_domains: BTreeSet<DomainRule>,
     allowed_workspaces: BTreeSet<String>,
-    allowed_commands: BTreeSet<String>,
+    allowed_commands: BTreeSet<CommandRule>,
+    domain_budgets: BTreeMap<DomainRule, DomainBudget>,
 }
 
 impl Default for HuginnPolicy {
@@ -50,6 +126,7 @@ impl Default for HuginnPolicy {
             allowed_domains: BTreeSet::new(),
             allowed_workspaces: BTreeSet::new(),
             allowed_commands: BTreeSet::new(),
+            domain_budgets: BTreeMap::new(),
         }
     }
 }
@@ -60,6 +137,11 @@ impl HuginnPolicy {
         self
     }
 
+    pub fn with_mode(mut self, mode: HuginnMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
     pub fn with_domains<I, S>(mut self, domains: I) -> Self
     where
         I: IntoIterator<Item = S>,
@@ -94,11 +176,20 @@ impl HuginnPolicy {
         self.allowed_commands.extend(
             commands
                 .into_iter()
-                .filter_map(|command| normalize_command_scope_entry(command.as_ref())),
+                .filter_map(|command| parse_command_rule(command.as_ref())),
         );
         self
     }
 
+    /// Budgets observes of hosts matching `domain` (same syntax as the domain allowlist). It
+    /// does not allowlist the domain.
+    pub fn with_domain_budget(mut self, domain: &str, budget: DomainBudget) -> Self {
+        if let Some(rule) = normalize_domain(domain) {
+            self.domain_budgets.insert(rule, budget);
+        }
+        self
+    }
+
     pub fn evaluate(&self, action: Action) -> PermissionDecision {
         match action {
             Action::Login => deny("action_login_disallowed"),
@@ -109,6 +200,81 @@ impl HuginnPolicy {
             Action::ObserveUrl(url) => self.evaluate_observe_url(&url),
             Action::ReadWorkspace(workspace) => self.evaluate_workspace(&workspace),
             Action::RunCommand(command) => self.evaluate_command(&command),
+            Action::FillForm(url) | Action::Click(url) => self.evaluate_interaction(&url),
+        }
+    }
+
+    /// `evaluate`, with allowed observes of `task` also held to the budgets of every domain
+    /// they match. Observes that fit are recorded in `ledger`.
+    pub fn evaluate_navigation(
+        &self,
+        action: Action,
+        task: &str,
+        ledger: &mut NavigationLedger,
+        now_unix: u64,
+    ) -> PermissionDecision {
+        let Action::ObserveUrl(url) = &action else {
+            return self.evaluate(action);
+        };
+        let page = url.trim().split('#').next().unwrap_or_default().to_string();
+        let decision = self.evaluate(action);
+        let (PermissionDecision::Allow { .. }, Some(host)) = (&decision, extract_host(&page))
+        else {
+            return decision;
+        };
+
+        let budgets: Vec<(String, &DomainBudget)> = self
+            .domain_budgets
+            .iter()
+            .filter(|(rule, _)| domain_matches(&host, rule))
+            .map(|(rule, budget)| (rule.key(), budget))
+            .collect();
+        for (domain, budget) in &budgets {
+            let observes = ledger.observes.entry(domain.clone()).or_default();
+            while observes
+                .front()
+                .is_some_and(|at| now_unix.saturating_sub(*at) >= 3_600)
+            {
+                observes.pop_front();
+            }
+            if budget
+                .max_observes_per_hour
+                .is_some_and(|max| observes.len() >= max as usize)
+            {
+                return deny("domain_observe_rate_exceeded");
+            }
+            let pages = ledger.pages.get(&(task.to_string(), domain.clone()));
+            if !pages.is_some_and(|pages| pages.contains(&page))
+                && budget
+                    .max_pages_per_task
+                    .is_some_and(|max| pages.map_or(0, BTreeSet::len) >= max as usize)
+            {
+                return deny("domain_page_budget_exceeded");
+            }
+        }
+        for (domain, _) in budgets {
+            ledger
+                .observes
+                .entry(domain.clone())
+                .or_default()
+                .push_back(now_unix);
+            ledger
+                .pages
+                .entry((task.to_string(), domain))
+                .or_default()
+                .insert(page.clone());
+        }
+        decision
+    }
+
+    fn evaluate_interaction(&self, url: &str) -> PermissionDecision {
+        if self.mode != HuginnMode::InteractWithApproval {
+            return deny("mode_not_supported");
+        }
+
+        match self.evaluate_observe_url(url) {
+            PermissionDecision::Allow { .. } => require_approval("interaction_requires_approval"),
+            decision => decision,
         }
     }
 
@@ -133,10 +299,6 @@ impl HuginnPolicy {
     }
 
     fn evaluate_workspace(&self, workspace: &str) -> PermissionDecision {
-        if !matches!(self.mode, HuginnMode::ReadObserve) {
-            return deny("mode_not_supported");
-        }
-
         let Some(workspace) = normalize_workspace(workspace) else {
             return deny("workspace_not_allowlisted");
         };
@@ -149,10 +311,6 @@ impl HuginnPolicy {
     }
 
     fn evaluate_command(&self, command: &str) -> PermissionDecision {
-        if !matches!(self.mode, HuginnMode::ReadObserve) {
-            return deny("mode_not_supported");
-        }
-
         if has_unsafe_shell_syntax(command) {
             return deny("command_unsafe_shell_syntax");
         }
@@ -161,44 +319,50 @@ impl HuginnPolicy {
             return deny("command_not_allowlisted");
         };
 
-        if !self.allowed_commands.contains(&command_name) {
+        let rules: Vec<&CommandRule> = self
+            .allowed_commands
+            .iter()
+            .filter(|rule| rule.name == command_name)
+            .collect();
+        let Some(first_rule) = rules.first() else {
             return deny("command_not_allowlisted");
-        }
-
-        if self.allowed_workspaces.is_empty() {
-            return deny("command_workspace_policy_missing");
-        }
-
-        if has_relative_parent_traversal(&args) {
-            return deny("command_relative_path_traversal");
-        }
-
-        if !self.allowed_workspaces.is_empty() && has_unscoped_relative_path(&args) {
-            return deny("command_relative_path_unscoped");
-        }
+        };
+        // A pinned subcommand is not a path argument; prefer the rule that pins one.
+        let Some(rule) = rules
+            .iter()
+            .filter(|rule| rule.refusal(&args).is_none())
+            .max_by_key(|rule| rule.subcommands.is_some())
+        else {
+            return deny(
+                first_rule
+                    .refusal(&args)
+                    .unwrap_or("command_not_allowlisted"),
+            );
+        };
+        let args = if rule.subcommands.is_some() {
+            &args[1..]
+        } else {
+            &args[..]
+        };
 
-        if first_absolute_path_outside_workspaces(&args, &self.allowed_workspaces).is_some() {
-            return deny("command_path_outside_allowlisted_workspace");
+        match validate_command_paths(args, &self.allowed_workspaces) {
+            Ok(()) => allow("command_allowlisted"),
+            Err(violation) => deny(violation.reason_code()),
         }
-
-        allow("command_allowlisted")
     }
 
     fn is_workspace_allowlisted(&self, workspace: &str) -> bool {
-        let Some(candidate) = normalize_boundary_path(Path::new(workspace)) else {
-            return false;
-        };
-
         self.allowed_workspaces
             .iter()
-            .filter_map(|allowed| normalize_boundary_path(Path::new(allowed)))
-            .any(|allowed| candidate == allowed || candidate.starts_with(&allowed))
+            .any(|allowed| is_within(Path::new(workspace), Path::new(allowed)))
     }
 }
 
 #[derive(Clone, Debug, Default)]
 pub struct PluginPermissionRegistry {
     envelopes: BTreeMap<String, PluginPermissionEnvelope>,
+    /// Envelopes keyed by (project level, plugin) that override the plugin-wide one.
+    project_envelopes: BTreeMap<(String, String), PluginPermissionEnvelope>,
 }
 
 impl PluginPermissionRegistry {
@@ -225,6 +389,64 @@ impl PluginPermissionRegistry {
         self.envelopes.get(plugin)
     }
 
+    /// Plugin-wide envelopes in plugin order.
+    pub fn envelopes(&self) -> impl Iterator<Item = &PluginPermissionEnvelope> {
+        self.envelopes.values()
+    }
+
+    /// Project-level envelopes with their project, in project then plugin order.
+    pub fn project_envelopes(&self) -> impl Iterator<Item = (&str, &PluginPermissionEnvelope)> {
+        self.project_envelopes
+            .iter()
+            .map(|((project, _), envelope)| (project.as_str(), envelope))
+    }
+
+    /// Sets the envelope for `project` (e.g. `acme/platform`) and every project below it.
+    pub fn insert_for_project(&mut self, project: &str, envelope: PluginPermissionEnvelope) {
+        self.project_envelopes
+            .insert((project.to_string(), envelope.plugin.clone()), envelope);
+    }
+
+    /// The envelope that applies to `plugin` in `project`: the nearest project level with an
+    /// envelope replaces those above it, falling back to the plugin-wide envelope.
+    pub fn effective(&self, plugin: &str, project: &str) -> Option<&PluginPermissionEnvelope> {
+        self.effective_level(plugin, project)
+            .map(|(_, envelope)| envelope)
+    }
+
+    /// `effective`, with the project level the envelope was set for; `None` for the plugin-wide
+    /// envelope.
+    pub fn effective_level(
+        &self,
+        plugin: &str,
+        project: &str,
+    ) -> Option<(Option<&str>, &PluginPermissionEnvelope)> {
+        project_lineage(project)
+            .into_iter()
+            .find_map(|level| {
+                self.project_envelopes
+                    .get_key_value(&(level, plugin.to_string()))
+            })
+            .map(|((level, _), envelope)| (Some(level.as_str()), envelope))
+            .or_else(|| self.get(plugin).map(|envelope| (None, envelope)))
+    }
+
+    /// Removes `capability` from every envelope of `plugin`, or only `scope` from its grants.
+    /// A grant left without scopes is dropped rather than widened. Returns the grants changed.
+    pub fn revoke(&mut self, plugin: &str, capability: &str, scope: Option<&str>) -> usize {
+        let project_envelopes = self
+            .project_envelopes
+            .iter_mut()
+            .filter(|((_, owner), _)| owner == plugin)
+            .map(|(_, envelope)| envelope);
+        self.envelopes
+            .get_mut(plugin)
+            .into_iter()
+            .chain(project_envelopes)
+            .map(|envelope| revoke_from_envelope(envelope, capability, scope))
+            .sum()
+    }
+
     pub fn huginn_policy(&self) -> HuginnPolicy {
         self.get("huginn")
             .map(huginn_policy_from_envelope)
@@ -288,18 +510,71 @@ fn apply_permission_scope(
                 permission
                     .scope
                     .iter()
-                    .filter_map(|command| normalize_command_scope_entry(command)),
+                    .filter_map(|command| parse_command_rule(command)),
             );
         }
-        "huginn.enabled" => {
-            if can_enable {
-                policy.enabled = true;
+        "huginn.budget" => {
+            for entry in &permission.scope {
+                apply_budget_entry(policy, entry);
             }
         }
+        "huginn.enabled" if can_enable => {
+            policy.enabled = true;
+        }
+        "huginn.interact" if can_enable => {
+            policy.mode = HuginnMode::InteractWithApproval;
+        }
         _ => {}
     }
 }
 
+/// Applies a `<domain>:observes_per_hour=<n>` or `<domain>:pages_per_task=<n>` budget entry.
+/// An entry with a readable domain but an unreadable limit zeroes that domain's budget, so a
+/// typo cannot lift it.
+fn apply_budget_entry(policy: &mut HuginnPolicy, entry: &str) {
+    let (domain, setting) = entry.trim().rsplit_once(':').unwrap_or((entry, ""));
+    let Some(rule) = normalize_domain(domain) else {
+        return;
+    };
+    let budget = policy.domain_budgets.entry(rule).or_default();
+    let (key, value) = setting.split_once('=').unwrap_or((setting, ""));
+    match (key.trim(), value.trim().parse::<u32>()) {
+        ("observes_per_hour", Ok(limit)) => budget.max_observes_per_hour = Some(limit),
+        ("pages_per_task", Ok(limit)) => budget.max_pages_per_task = Some(limit),
+        _ => {
+            *budget = DomainBudget {
+                max_observes_per_hour: Some(0),
+                max_pages_per_task: Some(0),
+            }
+        }
+    }
+}
+
+fn revoke_from_envelope(
+    envelope: &mut PluginPermissionEnvelope,
+    capability: &str,
+    scope: Option<&str>,
+) -> usize {
+    let mut changed = 0;
+    envelope.permissions.retain_mut(|permission| {
+        if permission.id != capability {
+            return true;
+        }
+        let Some(scope) = scope else {
+            changed += 1;
+            return false;
+        };
+        let before = permission.scope.len();
+        permission.scope.retain(|granted| granted != scope);
+        if permission.scope.len() == before {
+            return true;
+        }
+        changed += 1;
+        !permission.scope.is_empty()
+    });
+    changed
+}
+
 fn allow(reason_code: &str) -> PermissionDecision {
     PermissionDecision::Allow {
         reason_code: reason_code.to_string(),
@@ -312,6 +587,12 @@ fn deny(reason_code: &str) -> PermissionDecision {
     }
 }
 
+fn require_approval(reason_code: &str) -> PermissionDecision {
+    PermissionDecision::RequireApproval {
+        reason_code: reason_code.to_string(),
+    }
+}
+
 fn extract_host(url: &str) -> Option<String> {
     let trimmed = url.trim();
     let without_scheme = trimmed
@@ -368,23 +649,86 @@ fn normalize_workspace(workspace: &str) -> Option<String> {
 
     let path = Path::new(trimmed);
     if path.is_absolute() {
-        return normalize_boundary_path(path).map(pathbuf_to_string);
+        return normalize(path).map(pathbuf_to_string);
     }
 
     Some(trimmed.to_string())
 }
 
-fn normalize_command_scope_entry(command: &str) -> Option<String> {
-    let normalized = command.trim();
-    if normalized.is_empty() {
+/// Parses a command scope entry; anything malformed yields `None`, so the entry grants nothing.
+fn parse_command_rule(entry: &str) -> Option<CommandRule> {
+    let entry = entry.trim();
+    if entry.is_empty() || entry.chars().any(char::is_whitespace) {
+        return None;
+    }
+
+    let (head, denied_flags) = match entry.split_once('!') {
+        Some((head, flags)) => (head, parse_scope_set(flags)?),
+        None => (entry, BTreeSet::new()),
+    };
+    if denied_flags.iter().any(|flag| !flag.starts_with('-')) {
+        return None;
+    }
+    let (name, subcommands) = match head.split_once(':') {
+        Some((name, subcommands)) if subcommands.starts_with('{') => {
+            (name, Some(parse_scope_set(subcommands)?))
+        }
+        Some((name, subcommand)) => (name, Some(BTreeSet::from([subcommand.to_string()]))),
+        None => (head, None),
+    };
+    let reserved = |value: &str| value.is_empty() || value.contains(['{', '}', ',', ':', '!']);
+    if reserved(name) || subcommands.iter().flatten().any(|sub| reserved(sub)) {
         return None;
     }
 
-    if normalized.chars().any(char::is_whitespace) {
+    Some(CommandRule {
+        name: name.to_string(),
+        subcommands,
+        denied_flags,
+    })
+}
+
+/// A command line the command scope `entry` allows on its own: the name, plus the first pinned
+/// subcommand if any. `None` when the entry is malformed.
+pub fn command_scope_probe(entry: &str) -> Option<String> {
+    let rule = parse_command_rule(entry)?;
+    Some(
+        match rule.subcommands.and_then(|subs| subs.into_iter().next()) {
+            Some(subcommand) => format!("{} {subcommand}", rule.name),
+            None => rule.name,
+        },
+    )
+}
+
+/// `{a,b}` as a set of its non-empty items.
+fn parse_scope_set(value: &str) -> Option<BTreeSet<String>> {
+    let items = value.strip_prefix('{')?.strip_suffix('}')?;
+    let set: BTreeSet<String> = items.split(',').map(str::to_string).collect();
+    if set
+        .iter()
+        .any(|item| item.is_empty() || item.contains(['{', '}']))
+    {
         return None;
     }
+    Some(set)
+}
 
-    Some(normalized.to_string())
+/// Whether `arg` sets `flag`: exactly, as `--flag=value`, or bundled with other short flags
+/// (`-rf` sets `-f`).
+fn flag_matches(arg: &str, flag: &str) -> bool {
+    if arg == flag
+        || arg
+            .strip_prefix(flag)
+            .is_some_and(|rest| rest.starts_with('='))
+    {
+        return true;
+    }
+    match (flag.strip_prefix('-'), arg.strip_prefix('-')) {
+        (Some(short), Some(bundle)) if short.len() == 1 && !short.starts_with('-') => {
+            !bundle.starts_with('-') && bundle.contains(short)
+        }
+        _ => false,
+    }
 }
 
 fn normalize_command_name(command: &str) -> Option<String> {
@@ -396,49 +740,15 @@ fn normalize_command_name(command: &str) -> Option<String> {
     }
 }
 
-fn parse_command(command: &str) -> Option<(String, Vec<String>)> {
+/// Splits a command line into its name and arguments the same way `RunCommand` is
+/// evaluated, so executors run exactly what the policy checked.
+pub fn parse_command(command: &str) -> Option<(String, Vec<String>)> {
     let mut tokens = command.split_whitespace();
     let command_name = normalize_command_name(tokens.next()?)?;
     let args = tokens.map(strip_wrapping_quotes).collect::<Vec<_>>();
     Some((command_name, args))
 }
 
-fn first_absolute_path_outside_workspaces(
-    args: &[String],
-    allowed_workspaces: &BTreeSet<String>,
-) -> Option<String> {
-    for path in command_scoped_path_values(args)
-        .into_iter()
-        .filter(|value| Path::new(value).is_absolute())
-    {
-        let Some(path_obj) = canonicalize_existing_absolute_path(Path::new(&path)) else {
-            return Some(path);
-        };
-
-        let in_allowed_workspace = allowed_workspaces
-            .iter()
-            .filter_map(|workspace| normalize_boundary_path(Path::new(workspace)))
-            .any(|workspace| path_obj == workspace || path_obj.starts_with(&workspace));
-        if !in_allowed_workspace {
-            return Some(path);
-        }
-    }
-    None
-}
-
-fn strip_wrapping_quotes(value: &str) -> String {
-    let trimmed = value.trim();
-    if trimmed.len() >= 2 {
-        let starts_single = trimmed.starts_with('\'') && trimmed.ends_with('\'');
-        let starts_double = trimmed.starts_with('"') && trimmed.ends_with('"');
-        if starts_single || starts_double {
-            return trimmed[1..trimmed.len() - 1].to_string();
-        }
-    }
-
-    trimmed.to_string()
-}
-
 fn domain_matches(host: &str, allowed: &DomainRule) -> bool {
     if allowed.allow_subdomains {
         return host.ends_with(&format!(".{}", allowed.host));
@@ -455,153 +765,6 @@ fn has_unsafe_shell_syntax(command: &str) -> bool {
     })
 }
 
-fn has_relative_parent_traversal(args: &[String]) -> bool {
-    command_scoped_path_values(args)
-        .into_iter()
-        .any(|value| has_relative_parent_segment(&value))
-}
-
-fn has_relative_parent_segment(token: &str) -> bool {
-    let path = Path::new(token);
-    !path.is_absolute()
-        && path
-            .components()
-            .any(|component| matches!(component, Component::ParentDir))
-}
-
-fn has_unscoped_relative_path(args: &[String]) -> bool {
-    command_scoped_path_values(args)
-        .into_iter()
-        .any(|value| !Path::new(&value).is_absolute())
-}
-
-fn normalize_boundary_path(path: &Path) -> Option<PathBuf> {
-    if path.is_absolute() {
-        if let Ok(canonical) = fs::canonicalize(path) {
-            return Some(canonical);
-        }
-    }
-
-    normalize_lexical_path(path)
-}
-
-fn canonicalize_existing_absolute_path(path: &Path) -> Option<PathBuf> {
-    if !path.is_absolute() {
-        return None;
-    }
-
-    fs::canonicalize(path).ok()
-}
-
-fn command_scoped_path_values(args: &[String]) -> Vec<String> {
-    let mut values = Vec::new();
-
-    for arg in args {
-        let token = strip_wrapping_quotes(arg);
-        let trimmed = token.trim();
-        if trimmed.is_empty() {
-            continue;
-        }
-
-        if let Some(value) = extract_option_value(trimmed) {
-            values.push(value);
-            continue;
-        }
-
-        if trimmed.starts_with('-') {
-            continue;
-        }
-
-        values.push(trimmed.to_string());
-    }
-
-    values
-}
-
-fn extract_option_value(token: &str) -> Option<String> {
-    if token.starts_with("--") {
-        let (_, value) = token.split_once('=')?;
-        return normalize_option_value(value);
-    }
-
-    if token.starts_with('-') {
-        if token.len() <= 2 {
-            return None;
-        }
-
-        let attached = &token[2..];
-        let attached = attached.strip_prefix('=').unwrap_or(attached);
-        return normalize_option_value(attached);
-    }
-
-    None
-}
-
-fn normalize_option_value(value: &str) -> Option<String> {
-    let trimmed = strip_wrapping_quotes(value);
-    let trimmed = trimmed.trim();
-    if trimmed.is_empty() {
-        None
-    } else {
-        Some(trimmed.to_string())
-    }
-}
-
-fn normalize_lexical_path(path: &Path) -> Option<PathBuf> {
-    let mut prefix: Option<OsString> = None;
-    let mut has_root = false;
-    let mut parts: Vec<OsString> = Vec::new();
-
-    for component in path.components() {
-        match component {
-            Component::Prefix(value) => {
-                prefix = Some(value.as_os_str().to_os_string());
-            }
-            Component::RootDir => {
-                has_root = true;
-            }
-            Component::CurDir => {}
-            Component::ParentDir => {
-                if let Some(last) = parts.last() {
-                    if last != ".." {
-                        parts.pop();
-                    } else if has_root {
-                        return None;
-                    } else {
-                        parts.push(OsString::from(".."));
-                    }
-                } else if has_root {
-                    return None;
-                } else {
-                    parts.push(OsString::from(".."));
-                }
-            }
-            Component::Normal(value) => parts.push(value.to_os_string()),
-        }
-    }
-
-    let mut normalized = PathBuf::new();
-    if let Some(value) = prefix {
-        normalized.push(value);
-    }
-    if has_root {
-        normalized.push(std::path::MAIN_SEPARATOR.to_string());
-    }
-    for part in parts {
-        normalized.push(part);
-    }
-
-    if normalized.as_os_str().is_empty() {
-        if has_root {
-            normalized.push(std::path::MAIN_SEPARATOR.to_string());
-        } else {
-            normalized.push(".");
-        }
-    }
-
-    Some(normalized)
-}
-
 fn pathbuf_to_string(path: PathBuf) -> String {
     path.to_string_lossy().into_owned()
 }
@@ -609,8 +772,6 @@ fn pathbuf_to_string(path: PathBuf) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::fs;
-    use std::time::{SystemTime, UNIX_EPOCH};
 
     #[test]
     fn default_policy_denies_when_disabled() {
@@ -712,37 +873,84 @@ mod tests {
     }
 
     #[test]
-    fn boundary_path_uses_canonical_path_when_target_exists() {
-        let unique = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("clock")
-            .as_nanos();
-        let root = std::env::temp_dir().join(format!("odin-governance-path-existing-{unique}"));
-        let leaf = root.join("allowed");
-
-        fs::create_dir_all(&leaf).expect("create temp tree");
-
-        let input = root.join("allowed").join("..").join("allowed");
-        let expected = fs::canonicalize(&leaf).expect("canonical leaf");
-        let actual = normalize_boundary_path(&input).expect("normalize");
+    fn project_envelopes_are_inherited_by_child_projects() {
+        let envelope = |scope: &str| PluginPermissionEnvelope {
+            plugin: "example.safe-github".to_string(),
+            trust_level: TrustLevel::Trusted,
+            permissions: vec![DelegationCapability {
+                id: "repo.read".to_string(),
+                scope: vec![scope.to_string()],
+            }],
+        };
+        let mut registry = PluginPermissionRegistry::from_envelopes([envelope("global")]);
+        registry.insert_for_project("acme", envelope("acme"));
+        registry.insert_for_project("acme/legacy", envelope("legacy"));
+
+        let scope = |project: &str| {
+            registry
+                .effective("example.safe-github", project)
+                .map(|envelope| envelope.permissions[0].scope[0].clone())
+        };
+        assert_eq!(scope("acme/platform/api").as_deref(), Some("acme"));
+        assert_eq!(scope("acme/legacy/app").as_deref(), Some("legacy"));
+        assert_eq!(scope("other").as_deref(), Some("global"));
+        assert!(registry.effective("unknown", "acme").is_none());
+    }
 
-        assert_eq!(actual, expected);
+    #[test]
+    fn revoking_the_last_scope_drops_the_grant() {
+        let mut registry = PluginPermissionRegistry::from_envelopes([PluginPermissionEnvelope {
+            plugin: "example.git".to_string(),
+            trust_level: TrustLevel::Trusted,
+            permissions: vec![DelegationCapability {
+                id: "repo.push".to_string(),
+                scope: vec!["main".to_string()],
+            }],
+        }]);
+        registry.insert_for_project("acme", registry.get("example.git").unwrap().clone());
 
-        fs::remove_dir_all(&root).expect("cleanup");
+        assert_eq!(
+            registry.revoke("example.git", "repo.push", Some("release")),
+            0
+        );
+        assert_eq!(registry.revoke("example.git", "repo.push", Some("main")), 2);
+        assert!(registry.get("example.git").unwrap().permissions.is_empty());
+        assert!(registry
+            .effective("example.git", "acme")
+            .unwrap()
+            .permissions
+            .is_empty());
     }
 
     #[test]
-    fn boundary_path_falls_back_to_lexical_normalization_when_missing() {
-        let unique = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("clock")
-            .as_nanos();
-        let root = std::env::temp_dir().join(format!("odin-governance-path-missing-{unique}"));
-        let input = root.join("allowed").join("..").join("outside");
-
-        let actual = normalize_boundary_path(&input).expect("normalize");
-        let expected = root.join("outside");
-
-        assert_eq!(actual, expected);
+    fn malformed_budget_entries_close_the_domain() {
+        let mut policy = huginn_default_policy();
+        apply_budget_entry(&mut policy, "example.com:observes_per_hour=ten");
+        apply_budget_entry(&mut policy, "https://docs.rs:pages_per_task=3");
+        apply_budget_entry(&mut policy, ":pages_per_task=3");
+        let budgets: Vec<_> = policy
+            .domain_budgets
+            .iter()
+            .map(|(rule, budget)| (rule.key(), *budget))
+            .collect();
+        assert_eq!(
+            budgets,
+            [
+                (
+                    "docs.rs".to_string(),
+                    DomainBudget {
+                        max_observes_per_hour: None,
+                        max_pages_per_task: Some(3),
+                    }
+                ),
+                (
+                    "example.com".to_string(),
+                    DomainBudget {
+                        max_observes_per_hour: Some(0),
+                        max_pages_per_task: Some(0),
+                    }
+                ),
+            ]
+        );
     }
 }
@@ -0,0 +1,229 @@
+//! Trust-level changes for registered skills. Raising a skill's trust needs evidence: a signed
+//! ack, a clean risk scan, a minimum age and some recorded use. Lowering it is applied at once.
+
+use std::path::Path;
+use std::time::Duration;
+
+use odin_plugin_protocol::{SkillScope, TrustLevel};
+use thiserror::Error;
+
+use crate::risk_scan::RiskFinding;
+use crate::skills::{load_scoped_registry, update_skill, SkillRegistryWriteError};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PromotionPolicy {
+    pub require_signature: bool,
+    pub min_age: Duration,
+    pub min_uses: u64,
+}
+
+impl Default for PromotionPolicy {
+    fn default() -> Self {
+        Self {
+            require_signature: true,
+            min_age: Duration::from_secs(7 * 86_400),
+            min_uses: 1,
+        }
+    }
+}
+
+impl PromotionPolicy {
+    pub fn with_min_age(mut self, min_age: Duration) -> Self {
+        self.min_age = min_age;
+        self
+    }
+
+    pub fn with_min_uses(mut self, min_uses: u64) -> Self {
+        self.min_uses = min_uses;
+        self
+    }
+
+    /// Requirements `evidence` does not meet at `now_unix`; empty when promotion may proceed.
+    pub fn unmet(&self, evidence: &PromotionEvidence, now_unix: u64) -> Vec<PromotionRequirement> {
+        let mut unmet = Vec::new();
+        if self.require_signature && !evidence.signed {
+            unmet.push(PromotionRequirement::Signature);
+        }
+        if !evidence.findings.as_ref().is_some_and(Vec::is_empty) {
+            unmet.push(PromotionRequirement::CleanRiskScan);
+        }
+        let age_secs = evidence
+            .first_seen_unix
+            .map_or(0, |first| now_unix.saturating_sub(first));
+        if age_secs < self.min_age.as_secs() {
+            unmet.push(PromotionRequirement::MinimumAge {
+                required_secs: self.min_age.as_secs(),
+                actual_secs: age_secs,
+            });
+        }
+        if evidence.uses < self.min_uses {
+            unmet.push(PromotionRequirement::UsageHistory {
+                required: self.min_uses,
+                actual: evidence.uses,
+            });
+        }
+        unmet
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PromotionEvidence {
+    /// A signed ack for the skill verified against the ack trust store.
+    pub signed: bool,
+    /// Findings from scanning the skill; `None` when it was not scanned.
+    pub findings: Option<Vec<RiskFinding>>,
+    /// Earliest audited use of the skill.
+    pub first_seen_unix: Option<u64>,
+    pub uses: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PromotionRequirement {
+    Signature,
+    CleanRiskScan,
+    MinimumAge {
+        required_secs: u64,
+        actual_secs: u64,
+    },
+    UsageHistory {
+        required: u64,
+        actual: u64,
+    },
+}
+
+impl PromotionRequirement {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Signature => "signature_missing",
+            Self::CleanRiskScan => "risk_scan_not_clean",
+            Self::MinimumAge { .. } => "too_recent",
+            Self::UsageHistory { .. } => "insufficient_usage",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TrustChange {
+    pub skill: String,
+    pub from: TrustLevel,
+    pub to: TrustLevel,
+}
+
+impl TrustChange {
+    pub fn is_promotion(&self) -> bool {
+        trust_rank(&self.to) > trust_rank(&self.from)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum PromotionError {
+    #[error(transparent)]
+    Registry(#[from] SkillRegistryWriteError),
+    #[error("{skill} is already {}", trust_level_as_str(.level))]
+    Unchanged { skill: String, level: TrustLevel },
+    #[error("promoting {skill} is missing evidence ({} requirements unmet)", .unmet.len())]
+    Unmet {
+        skill: String,
+        unmet: Vec<PromotionRequirement>,
+    },
+}
+
+/// Sets the trust level of `name` in the `scope` registry at `path`. Raising it checks
+/// `evidence` against `policy` first; lowering it ignores both.
+pub fn change_trust(
+    path: &Path,
+    scope: SkillScope,
+    name: &str,
+    to: TrustLevel,
+    policy: &PromotionPolicy,
+    evidence: &PromotionEvidence,
+    now_unix: u64,
+) -> Result<TrustChange, PromotionError> {
+    let registry =
+        load_scoped_registry(path, scope.clone()).map_err(SkillRegistryWriteError::from)?;
+    let name = name.trim();
+    let mut record = registry
+        .skills
+        .into_iter()
+        .find(|skill| skill.name == name)
+        .ok_or_else(|| SkillRegistryWriteError::NotFound(name.to_string()))?;
+    if record.trust_level == to {
+        return Err(PromotionError::Unchanged {
+            skill: record.name,
+            level: to,
+        });
+    }
+
+    let change = TrustChange {
+        skill: record.name.clone(),
+        from: record.trust_level.clone(),
+        to: to.clone(),
+    };
+    if change.is_promotion() {
+        let unmet = policy.unmet(evidence, now_unix);
+        if !unmet.is_empty() {
+            return Err(PromotionError::Unmet {
+                skill: change.skill,
+                unmet,
+            });
+        }
+    }
+    record.trust_level = to;
+    update_skill(path, scope, record)?;
+    Ok(change)
+}
+
+/// Untrusted < caution < trusted.
+pub fn trust_rank(level: &TrustLevel) -> u8 {
+    match level {
+        TrustLevel::Untrusted => 0,
+        TrustLevel::Caution => 1,
+        TrustLevel::Trusted => 2,
+    }
+}
+
+fn trust_level_as_str(level: &TrustLevel) -> &'static str {
+    match level {
+        TrustLevel::Trusted => "trusted",
+        TrustLevel::Caution => "caution",
+        TrustLevel::Untrusted => "untrusted",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_every_unmet_requirement() {
+        let policy = PromotionPolicy::default().with_min_uses(3);
+        let evidence = PromotionEvidence {
+            signed: false,
+            findings: None,
+            first_seen_unix: Some(1_000),
+            uses: 1,
+        };
+        let codes: Vec<_> = policy
+            .unmet(&evidence, 2_000)
+            .iter()
+            .map(PromotionRequirement::code)
+            .collect();
+        assert_eq!(
+            codes,
+            [
+                "signature_missing",
+                "risk_scan_not_clean",
+                "too_recent",
+                "insufficient_usage"
+            ]
+        );
+
+        let evidence = PromotionEvidence {
+            signed: true,
+            findings: Some(Vec::new()),
+            first_seen_unix: Some(1_000),
+            uses: 3,
+        };
+        assert!(policy.unmet(&evidence, 1_000 + 7 * 86_400).is_empty());
+    }
+}
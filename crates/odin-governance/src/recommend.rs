@@ -0,0 +1,235 @@
+//! Least-privilege recommendations: the grants in permission envelopes compared with the
+//! capability uses seen in audit history. Each use counts for the envelope that applied in its
+//! project (`PluginPermissionRegistry::effective_level`). A grant no use reached should be
+//! revoked; scope entries of a used grant that no use requested should be dropped. Scopes
+//! compare exactly, and grants whose uses carried no scopes at all are not narrowed.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::Serialize;
+
+use crate::plugins::PluginPermissionRegistry;
+
+/// Uses of one capability by one plugin, as aggregated from the audit log.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ObservedUse {
+    pub plugin: String,
+    pub capability: String,
+    /// `None` for uses recorded without a project; they count for the plugin-wide envelope.
+    pub project: Option<String>,
+    pub uses: u64,
+    pub last_used_unix: u64,
+    pub scopes: BTreeSet<String>,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RecommendedAction {
+    /// The grant was never used.
+    RevokeGrant,
+    /// The grant was used, but never with `scopes`.
+    NarrowScope,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+pub struct Recommendation {
+    pub action: RecommendedAction,
+    pub plugin: String,
+    /// The project level of the envelope; absent for the plugin-wide one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    pub capability: String,
+    /// The scope entries to drop; all of the grant's for `revoke_grant`.
+    pub scopes: Vec<String>,
+    pub uses: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_used_unix: Option<u64>,
+}
+
+#[derive(Debug, Default)]
+struct Exercised {
+    uses: u64,
+    last_used_unix: u64,
+    scopes: BTreeSet<String>,
+}
+
+#[derive(Debug)]
+pub struct LeastPrivilegeAnalyzer<'a> {
+    registry: &'a PluginPermissionRegistry,
+    /// Keyed by (envelope project level, plugin, capability).
+    exercised: BTreeMap<(Option<String>, String, String), Exercised>,
+}
+
+impl<'a> LeastPrivilegeAnalyzer<'a> {
+    pub fn new(registry: &'a PluginPermissionRegistry) -> Self {
+        Self {
+            registry,
+            exercised: BTreeMap::new(),
+        }
+    }
+
+    /// Counts `observed` for the envelope that applied to it; uses no envelope covers are
+    /// ignored.
+    pub fn add(&mut self, observed: &ObservedUse) {
+        let envelope = match &observed.project {
+            Some(project) => self.registry.effective_level(&observed.plugin, project),
+            None => self
+                .registry
+                .get(&observed.plugin)
+                .map(|envelope| (None, envelope)),
+        };
+        let Some((level, _)) = envelope else {
+            return;
+        };
+        let exercised = self
+            .exercised
+            .entry((
+                level.map(str::to_string),
+                observed.plugin.clone(),
+                observed.capability.clone(),
+            ))
+            .or_default();
+        exercised.uses += observed.uses;
+        exercised.last_used_unix = exercised.last_used_unix.max(observed.last_used_unix);
+        exercised.scopes.extend(observed.scopes.iter().cloned());
+    }
+
+    /// Recommendations in envelope order: plugin-wide envelopes first, then project levels.
+    pub fn recommendations(&self) -> Vec<Recommendation> {
+        let envelopes = self
+            .registry
+            .envelopes()
+            .map(|envelope| (None, envelope))
+            .chain(
+                self.registry
+                    .project_envelopes()
+                    .map(|(project, envelope)| (Some(project), envelope)),
+            );
+
+        let mut recommendations = Vec::new();
+        for (project, envelope) in envelopes {
+            for permission in &envelope.permissions {
+                let key = (
+                    project.map(str::to_string),
+                    envelope.plugin.clone(),
+                    permission.id.clone(),
+                );
+                let recommendation = |action, scopes| Recommendation {
+                    action,
+                    plugin: envelope.plugin.clone(),
+                    project: project.map(str::to_string),
+                    capability: permission.id.clone(),
+                    scopes,
+                    uses: 0,
+                    last_used_unix: None,
+                };
+                let Some(exercised) = self.exercised.get(&key) else {
+                    recommendations.push(recommendation(
+                        RecommendedAction::RevokeGrant,
+                        permission.scope.clone(),
+                    ));
+                    continue;
+                };
+                if exercised.scopes.is_empty() {
+                    continue;
+                }
+                let unused: Vec<String> = permission
+                    .scope
+                    .iter()
+                    .filter(|scope| !exercised.scopes.contains(*scope))
+                    .cloned()
+                    .collect();
+                if !unused.is_empty() {
+                    recommendations.push(Recommendation {
+                        uses: exercised.uses,
+                        last_used_unix: Some(exercised.last_used_unix),
+                        ..recommendation(RecommendedAction::NarrowScope, unused)
+                    });
+                }
+            }
+        }
+        recommendations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use odin_plugin_protocol::{DelegationCapability, PluginPermissionEnvelope, TrustLevel};
+
+    use super::*;
+
+    fn envelope(plugin: &str, grants: &[(&str, &[&str])]) -> PluginPermissionEnvelope {
+        PluginPermissionEnvelope {
+            plugin: plugin.to_string(),
+            trust_level: TrustLevel::Caution,
+            permissions: grants
+                .iter()
+                .map(|(id, scope)| DelegationCapability {
+                    id: id.to_string(),
+                    scope: scope.iter().map(|entry| entry.to_string()).collect(),
+                })
+                .collect(),
+        }
+    }
+
+    fn used(project: &str, capability: &str, scopes: &[&str]) -> ObservedUse {
+        ObservedUse {
+            plugin: "example.git".to_string(),
+            capability: capability.to_string(),
+            project: Some(project.to_string()),
+            uses: 3,
+            last_used_unix: 100,
+            scopes: scopes.iter().map(|scope| scope.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn unused_grants_and_scopes_are_attributed_to_the_envelope_that_applied() {
+        let mut registry = PluginPermissionRegistry::from_envelopes([envelope(
+            "example.git",
+            &[
+                ("repo.read", &["main", "release"]),
+                ("repo.write", &["main"]),
+            ],
+        )]);
+        registry.insert_for_project(
+            "ops",
+            envelope("example.git", &[("repo.write", &["infra"])]),
+        );
+        let mut analyzer = LeastPrivilegeAnalyzer::new(&registry);
+        analyzer.add(&used("demo", "repo.read", &["main"]));
+        analyzer.add(&used("ops/prod", "repo.write", &[]));
+        analyzer.add(&used("demo", "repo.delete", &["main"]));
+
+        let recommendations = analyzer.recommendations();
+        let summary: Vec<_> = recommendations
+            .iter()
+            .map(|r| {
+                (
+                    r.action,
+                    r.project.as_deref(),
+                    r.capability.as_str(),
+                    r.scopes.clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    RecommendedAction::NarrowScope,
+                    None,
+                    "repo.read",
+                    vec!["release".to_string()]
+                ),
+                (
+                    RecommendedAction::RevokeGrant,
+                    None,
+                    "repo.write",
+                    vec!["main".to_string()]
+                ),
+            ]
+        );
+        assert_eq!(recommendations[0].uses, 3);
+    }
+}
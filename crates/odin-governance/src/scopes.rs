@@ -0,0 +1,262 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use odin_plugin_protocol::{DelegationCapability, PluginPermissionEnvelope};
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::plugins::PluginPermissionRegistry;
+
+/// Scope templates file read beside `plugin-permissions.yaml`.
+pub const SCOPE_TEMPLATES_FILE: &str = "scope-templates.yaml";
+
+/// Scope entries with this prefix are replaced by the named project template.
+pub const TEMPLATE_REF_PREFIX: &str = "template:";
+
+#[derive(Debug, Error)]
+pub enum ScopeTemplateError {
+    #[error("scope templates read failed: {0}")]
+    Io(String),
+    #[error("scope templates parse failed: {0}")]
+    Parse(String),
+    #[error("unknown scope template {template} for project {project}")]
+    UnknownTemplate { project: String, template: String },
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawScopeTemplates {
+    schema_version: u32,
+    #[serde(default)]
+    projects: BTreeMap<String, RawProjectScopes>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawProjectScopes {
+    #[serde(default)]
+    default_scopes: Vec<String>,
+    #[serde(default)]
+    templates: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProjectScopes {
+    pub default_scopes: Vec<String>,
+    pub templates: BTreeMap<String, Vec<String>>,
+}
+
+/// Per-project scope defaults and named templates. The `*` project applies to
+/// every project that does not define its own entry.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ScopeTemplates {
+    projects: BTreeMap<String, ProjectScopes>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScopeExpansion {
+    pub project: String,
+    pub capability: String,
+    /// `None` when the project default scopes were applied to an empty grant.
+    pub template: Option<String>,
+    pub scopes: Vec<String>,
+}
+
+impl ScopeTemplates {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.projects.is_empty()
+    }
+
+    pub fn insert_project(&mut self, project: impl Into<String>, scopes: ProjectScopes) {
+        self.projects.insert(project.into(), scopes);
+    }
+
+    pub fn project(&self, project: &str) -> Option<&ProjectScopes> {
+        self.projects
+            .get(project)
+            .or_else(|| self.projects.get("*"))
+    }
+
+    /// Expands template references and default scopes for `project`, returning the
+    /// expanded capabilities along with one record per applied expansion.
+    pub fn expand_capabilities(
+        &self,
+        project: &str,
+        capabilities: &[DelegationCapability],
+    ) -> Result<(Vec<DelegationCapability>, Vec<ScopeExpansion>), ScopeTemplateError> {
+        let project_scopes = self.project(project);
+        let mut expanded = Vec::with_capacity(capabilities.len());
+        let mut expansions = Vec::new();
+
+        for capability in capabilities {
+            if capability.scope.is_empty() {
+                let defaults = project_scopes
+                    .map(|scopes| scopes.default_scopes.clone())
+                    .unwrap_or_default();
+                if !defaults.is_empty() {
+                    expansions.push(ScopeExpansion {
+                        project: project.to_string(),
+                        capability: capability.id.clone(),
+                        template: None,
+                        scopes: defaults.clone(),
+                    });
+                }
+                expanded.push(DelegationCapability {
+                    id: capability.id.clone(),
+                    scope: defaults,
+                });
+                continue;
+            }
+
+            let mut scope = Vec::new();
+            for entry in &capability.scope {
+                let Some(name) = entry.trim().strip_prefix(TEMPLATE_REF_PREFIX) else {
+                    push_unique(&mut scope, entry.clone());
+                    continue;
+                };
+                let name = name.trim();
+                let values = project_scopes
+                    .and_then(|scopes| scopes.templates.get(name))
+                    .ok_or_else(|| ScopeTemplateError::UnknownTemplate {
+                        project: project.to_string(),
+                        template: name.to_string(),
+                    })?;
+                expansions.push(ScopeExpansion {
+                    project: project.to_string(),
+                    capability: capability.id.clone(),
+                    template: Some(name.to_string()),
+                    scopes: values.clone(),
+                });
+                for value in values {
+                    push_unique(&mut scope, value.clone());
+                }
+            }
+            expanded.push(DelegationCapability {
+                id: capability.id.clone(),
+                scope,
+            });
+        }
+
+        Ok((expanded, expansions))
+    }
+
+    pub fn expand_envelope(
+        &self,
+        project: &str,
+        envelope: &PluginPermissionEnvelope,
+    ) -> Result<(PluginPermissionEnvelope, Vec<ScopeExpansion>), ScopeTemplateError> {
+        let (permissions, expansions) = self.expand_capabilities(project, &envelope.permissions)?;
+        Ok((
+            PluginPermissionEnvelope {
+                plugin: envelope.plugin.clone(),
+                trust_level: envelope.trust_level.clone(),
+                permissions,
+            },
+            expansions,
+        ))
+    }
+
+    /// Expands every envelope in `registry` once, as governance loads it: project-level
+    /// envelopes with their project's templates, plugin-wide envelopes with the `*` project's.
+    pub fn expand_registry(
+        &self,
+        registry: &PluginPermissionRegistry,
+    ) -> Result<(PluginPermissionRegistry, Vec<ScopeExpansion>), ScopeTemplateError> {
+        let mut expanded = PluginPermissionRegistry::new();
+        let mut expansions = Vec::new();
+        for envelope in registry.envelopes() {
+            let (envelope, applied) = self.expand_envelope("*", envelope)?;
+            expanded.insert(envelope);
+            expansions.extend(applied);
+        }
+        for (project, envelope) in registry.project_envelopes() {
+            let (envelope, applied) = self.expand_envelope(project, envelope)?;
+            expanded.insert_for_project(project, envelope);
+            expansions.extend(applied);
+        }
+        Ok((expanded, expansions))
+    }
+}
+
+pub fn load_scope_templates(path: &Path) -> Result<ScopeTemplates, ScopeTemplateError> {
+    let raw = fs::read_to_string(path).map_err(|e| ScopeTemplateError::Io(e.to_string()))?;
+    parse_scope_templates(&raw)
+}
+
+pub fn parse_scope_templates(raw: &str) -> Result<ScopeTemplates, ScopeTemplateError> {
+    let raw_templates: RawScopeTemplates =
+        serde_yml::from_str(raw).map_err(|e| ScopeTemplateError::Parse(e.to_string()))?;
+    if raw_templates.schema_version != 1 {
+        return Err(ScopeTemplateError::Parse(format!(
+            "unsupported schema_version: {}",
+            raw_templates.schema_version
+        )));
+    }
+
+    let mut templates = ScopeTemplates::new();
+    for (project, raw_project) in raw_templates.projects {
+        let project = project.trim().to_string();
+        if project.is_empty() {
+            return Err(ScopeTemplateError::Parse(
+                "invalid project: empty".to_string(),
+            ));
+        }
+
+        let mut project_templates = BTreeMap::new();
+        for (name, values) in raw_project.templates {
+            let name = name.trim().to_string();
+            if name.is_empty() {
+                return Err(ScopeTemplateError::Parse(format!(
+                    "invalid template name in project {project}: empty"
+                )));
+            }
+            let values = normalize_scope_values(values);
+            if values
+                .iter()
+                .any(|value| value.starts_with(TEMPLATE_REF_PREFIX))
+            {
+                return Err(ScopeTemplateError::Parse(format!(
+                    "template {name} in project {project} must not reference other templates"
+                )));
+            }
+            if values.is_empty() {
+                return Err(ScopeTemplateError::Parse(format!(
+                    "template {name} in project {project} is empty"
+                )));
+            }
+            project_templates.insert(name, values);
+        }
+
+        templates.insert_project(
+            project,
+            ProjectScopes {
+                default_scopes: normalize_scope_values(raw_project.default_scopes),
+                templates: project_templates,
+            },
+        );
+    }
+
+    Ok(templates)
+}
+
+fn normalize_scope_values(values: Vec<String>) -> Vec<String> {
+    let mut normalized = Vec::new();
+    for value in values {
+        let trimmed = value.trim();
+        if !trimmed.is_empty() {
+            push_unique(&mut normalized, trimmed.to_string());
+        }
+    }
+    normalized
+}
+
+fn push_unique(values: &mut Vec<String>, value: String) {
+    if !values.contains(&value) {
+        values.push(value);
+    }
+}
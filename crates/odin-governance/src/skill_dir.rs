@@ -0,0 +1,262 @@
+//! Walks a skill directory for the install gate. Each file is classified by extension or
+//! shebang. The commands it runs and the URLs it mentions are extracted, and every line is
+//! checked against the risk patterns, so findings point at a file and line.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+use crate::risk_scan::{FindingLocation, RiskFinding, RiskScanner};
+
+/// Directories that hold tooling state rather than skill content.
+const SKIPPED_DIRS: &[&str] = &[".git", "node_modules", "__pycache__", ".venv"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FileKind {
+    Shell,
+    Python,
+    JavaScript,
+    Markdown,
+    Other,
+}
+
+impl FileKind {
+    /// Whether files of this kind run code when the skill is used.
+    pub fn is_script(self) -> bool {
+        matches!(self, Self::Shell | Self::Python | Self::JavaScript)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScannedFile {
+    /// Relative to the skill directory.
+    pub path: PathBuf,
+    pub kind: FileKind,
+    pub commands: Vec<String>,
+    pub urls: Vec<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SkillDirScan {
+    /// Text files in path order; binary files are skipped.
+    pub files: Vec<ScannedFile>,
+    /// The first match of each pattern in each file.
+    pub findings: Vec<RiskFinding>,
+}
+
+impl SkillDirScan {
+    pub fn has_scripts(&self) -> bool {
+        self.files.iter().any(|file| file.kind.is_script())
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum SkillScanError {
+    #[error("failed reading skill directory {path}: {message}")]
+    Io { path: PathBuf, message: String },
+}
+
+/// Scans with the built-in patterns only.
+pub fn scan_skill_dir(dir: &Path) -> Result<SkillDirScan, SkillScanError> {
+    scan_skill_dir_with(dir, &RiskScanner::default())
+}
+
+pub fn scan_skill_dir_with(
+    dir: &Path,
+    scanner: &RiskScanner,
+) -> Result<SkillDirScan, SkillScanError> {
+    let mut paths = Vec::new();
+    collect_files(dir, &mut paths)?;
+    paths.sort();
+
+    let mut scan = SkillDirScan::default();
+    for path in paths {
+        let raw = fs::read(&path).map_err(|e| io_error(&path, e))?;
+        let Ok(text) = String::from_utf8(raw) else {
+            continue;
+        };
+        let relative = path.strip_prefix(dir).unwrap_or(&path).to_path_buf();
+        let kind = classify(&relative, &text);
+        scan_file(scanner, &relative, &text, &mut scan.findings);
+        scan.files.push(ScannedFile {
+            commands: extract_commands(kind, &text),
+            urls: extract_urls(&text),
+            path: relative,
+            kind,
+        });
+    }
+    Ok(scan)
+}
+
+/// Regular files under `dir`, skipping tooling directories and symlinks.
+pub(crate) fn collect_files(dir: &Path, paths: &mut Vec<PathBuf>) -> Result<(), SkillScanError> {
+    for entry in fs::read_dir(dir).map_err(|e| io_error(dir, e))? {
+        let entry = entry.map_err(|e| io_error(dir, e))?;
+        let path = entry.path();
+        // Symlinks are not followed, so a skill cannot pull outside files into its scan.
+        let file_type = entry.file_type().map_err(|e| io_error(&path, e))?;
+        if file_type.is_dir() {
+            let skipped = entry
+                .file_name()
+                .to_str()
+                .is_some_and(|name| SKIPPED_DIRS.contains(&name));
+            if !skipped {
+                collect_files(&path, paths)?;
+            }
+        } else if file_type.is_file() {
+            paths.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn classify(path: &Path, text: &str) -> FileKind {
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_ascii_lowercase);
+    match extension.as_deref() {
+        Some("sh" | "bash" | "zsh") => return FileKind::Shell,
+        Some("py") => return FileKind::Python,
+        Some("js" | "mjs" | "cjs" | "ts") => return FileKind::JavaScript,
+        Some("md" | "markdown") => return FileKind::Markdown,
+        _ => {}
+    }
+    let Some(shebang) = text.lines().next().and_then(|line| line.strip_prefix("#!")) else {
+        return FileKind::Other;
+    };
+    if shebang.contains("python") {
+        FileKind::Python
+    } else if shebang.contains("node") {
+        FileKind::JavaScript
+    } else if ["sh", "bash", "zsh"].iter().any(|shell| {
+        shebang.ends_with(&format!("/{shell}")) || shebang.ends_with(&format!(" {shell}"))
+    }) {
+        FileKind::Shell
+    } else {
+        FileKind::Other
+    }
+}
+
+fn scan_file(scanner: &RiskScanner, path: &Path, text: &str, findings: &mut Vec<RiskFinding>) {
+    for (index, line) in text.lines().enumerate() {
+        for finding in scanner.matches(line) {
+            let seen = findings.iter().any(|seen| {
+                seen.category == finding.category
+                    && seen.pattern == finding.pattern
+                    && seen
+                        .location
+                        .as_ref()
+                        .is_some_and(|location| location.file == path)
+            });
+            if !seen {
+                findings.push(RiskFinding {
+                    location: Some(FindingLocation {
+                        file: path.to_path_buf(),
+                        line: index + 1,
+                    }),
+                    ..finding
+                });
+            }
+        }
+    }
+}
+
+/// Shell files run every line; markdown runs what is in shell code fences; Python and
+/// JavaScript run what they hand to a subprocess.
+fn extract_commands(kind: FileKind, text: &str) -> Vec<String> {
+    let lines = text.lines().map(str::trim);
+    let commands: Vec<&str> = match kind {
+        FileKind::Shell => lines
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .collect(),
+        FileKind::Markdown => {
+            let mut in_shell_fence = false;
+            let mut commands = Vec::new();
+            for line in lines {
+                if let Some(info) = line.strip_prefix("```") {
+                    in_shell_fence = !in_shell_fence
+                        && matches!(info.trim(), "sh" | "bash" | "shell" | "console" | "zsh");
+                    continue;
+                }
+                if in_shell_fence && !line.is_empty() && !line.starts_with('#') {
+                    commands.push(line.strip_prefix("$ ").unwrap_or(line));
+                }
+            }
+            commands
+        }
+        FileKind::Python => lines
+            .filter(|line| line.contains("subprocess.") || line.contains("os.system("))
+            .collect(),
+        FileKind::JavaScript => lines
+            .filter(|line| {
+                ["exec(", "execSync(", "spawn(", "spawnSync(", "execFile("]
+                    .iter()
+                    .any(|call| line.contains(call))
+            })
+            .collect(),
+        FileKind::Other => Vec::new(),
+    };
+    commands.into_iter().map(str::to_string).collect()
+}
+
+fn extract_urls(text: &str) -> Vec<String> {
+    let mut urls: Vec<String> = Vec::new();
+    for scheme in ["http://", "https://"] {
+        let mut rest = text;
+        while let Some(start) = rest.find(scheme) {
+            let candidate = &rest[start..];
+            let end = candidate
+                .find(|c: char| c.is_whitespace() || "\"'`<>()[]{}|".contains(c))
+                .unwrap_or(candidate.len());
+            let url = candidate[..end].trim_end_matches(['.', ',', ';', ':']);
+            if url.len() > scheme.len() && !urls.iter().any(|seen| seen == url) {
+                urls.push(url.to_string());
+            }
+            rest = &candidate[end..];
+        }
+    }
+    urls
+}
+
+fn io_error(path: &Path, err: std::io::Error) -> SkillScanError {
+    SkillScanError::Io {
+        path: path.to_path_buf(),
+        message: err.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_by_extension_then_shebang() {
+        assert_eq!(classify(Path::new("install.sh"), ""), FileKind::Shell);
+        assert_eq!(classify(Path::new("README.md"), ""), FileKind::Markdown);
+        assert_eq!(
+            classify(Path::new("bin/run"), "#!/usr/bin/env python3\n"),
+            FileKind::Python
+        );
+        assert_eq!(
+            classify(Path::new("bin/run"), "#!/usr/bin/env bash\n"),
+            FileKind::Shell
+        );
+        assert_eq!(classify(Path::new("notes.txt"), "hi\n"), FileKind::Other);
+    }
+
+    #[test]
+    fn extracts_fenced_commands_and_urls() {
+        let readme = "Install:\n\n```bash\n$ curl -fsSL https://example.com/i.sh | sh\n```\n\
+                      See (https://docs.example.com/guide).\n";
+        assert_eq!(
+            extract_commands(FileKind::Markdown, readme),
+            ["curl -fsSL https://example.com/i.sh | sh"]
+        );
+        assert_eq!(
+            extract_urls(readme),
+            ["https://example.com/i.sh", "https://docs.example.com/guide"]
+        );
+    }
+}
@@ -0,0 +1,236 @@
+//! Detached signatures over skill directories, checked with the plugin manager's minisign and
+//! sigstore verification. The signed message is the directory's `pins::content_sha256` as text
+//! (`sha256:<hex>`, no trailing newline); a signature file inside the directory is left out of
+//! the digest. To sign: `printf %s "$digest" > pack.digest && minisign -Sm pack.digest`.
+//!
+//! Signatures count only from signers in a `SkillSignerTrustStore`, so an importer cannot vouch
+//! for a pack with a key of their own:
+//!
+//! ```yaml
+//! schema_version: 1
+//! signers:
+//!   - id: release
+//!     method: minisign          # or sigstore
+//!     key: keys/release.pub     # key file relative to this file, or a minisign key line
+//! ```
+
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use odin_plugin_manager::verify_detached_signature;
+use odin_plugin_protocol::SkillSignatureRecord;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::pins::content_sha256_excluding;
+
+/// A detached signature by a trusted signer; built with `SkillSignerTrustStore::signature`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SkillSignature {
+    /// `minisign` or `sigstore` (`cosign` is accepted as an alias).
+    pub method: String,
+    pub signature: PathBuf,
+    /// A minisign public key or key file, or a cosign public key file.
+    pub key: String,
+    /// Where a relative key file is looked up: the trust store's directory.
+    key_dir: PathBuf,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct TrustedSigner {
+    pub id: String,
+    #[serde(default = "default_method")]
+    pub method: String,
+    pub key: String,
+}
+
+fn default_method() -> String {
+    "minisign".to_string()
+}
+
+/// The signers whose keys may verify a skill signature.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct SkillSignerTrustStore {
+    pub schema_version: u32,
+    #[serde(default)]
+    pub signers: Vec<TrustedSigner>,
+    #[serde(skip)]
+    key_dir: PathBuf,
+}
+
+impl SkillSignerTrustStore {
+    /// Parses a trust store whose relative key paths are resolved against `key_dir`.
+    pub fn from_yaml(raw: &str, key_dir: impl Into<PathBuf>) -> Result<Self, SkillSignatureError> {
+        let mut store: Self = serde_yml::from_str(raw).map_err(|e| {
+            SkillSignatureError::TrustStore(format!("invalid signer trust store: {e}"))
+        })?;
+        store.key_dir = key_dir.into();
+        Ok(store)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, SkillSignatureError> {
+        let raw = fs::read_to_string(path)
+            .map_err(|e| SkillSignatureError::TrustStore(format!("{}: {e}", path.display())))?;
+        let key_dir = path.parent().unwrap_or(Path::new("."));
+        Self::from_yaml(&raw, key_dir)
+    }
+
+    /// The signature file `signature`, to be checked against the key of the trusted `signer`.
+    pub fn signature(
+        &self,
+        signer: &str,
+        signature: impl Into<PathBuf>,
+    ) -> Result<SkillSignature, SkillSignatureError> {
+        let trusted = self
+            .signers
+            .iter()
+            .find(|entry| entry.id == signer)
+            .ok_or_else(|| SkillSignatureError::UnknownSigner(signer.to_string()))?;
+        Ok(SkillSignature {
+            method: trusted.method.clone(),
+            signature: signature.into(),
+            key: trusted.key.clone(),
+            key_dir: self.key_dir.clone(),
+        })
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum SkillSignatureError {
+    #[error("skill directory {path} cannot be digested: {message}")]
+    Digest { path: PathBuf, message: String },
+    #[error("skill signature rejected: {0}")]
+    Rejected(String),
+    #[error("{0}")]
+    TrustStore(String),
+    #[error("signer {0} is not in the skill signer trust store")]
+    UnknownSigner(String),
+}
+
+impl SkillSignature {
+    /// Checks the signature against the contents of `dir`, returning what the registry records.
+    pub fn verify(
+        &self,
+        dir: &Path,
+        now_unix: u64,
+    ) -> Result<SkillSignatureRecord, SkillSignatureError> {
+        let method = match self.method.trim().to_ascii_lowercase().as_str() {
+            "cosign" => "sigstore".to_string(),
+            method => method.to_string(),
+        };
+        if !self.signature.is_file() {
+            return Err(SkillSignatureError::Rejected(format!(
+                "signature file {} is missing",
+                self.signature.display()
+            )));
+        }
+        let content_sha256 = signed_digest(dir, Some(&self.signature))?;
+
+        let message_dir =
+            write_private_message(&content_sha256).map_err(|e| SkillSignatureError::Digest {
+                path: dir.to_path_buf(),
+                message: e.to_string(),
+            })?;
+        let verified = verify_detached_signature(
+            &method,
+            &message_dir.join(MESSAGE_FILE),
+            &self.signature,
+            self.key.trim(),
+            &self.key_dir,
+        );
+        let _ = fs::remove_dir_all(&message_dir);
+        verified.map_err(|e| SkillSignatureError::Rejected(e.to_string()))?;
+
+        Ok(SkillSignatureRecord {
+            method,
+            key: self.key.trim().to_string(),
+            content_sha256,
+            verified_at_unix: now_unix,
+        })
+    }
+}
+
+const MESSAGE_FILE: &str = "digest";
+
+/// Writes `message` for the verifier into a new directory only the owner can enter, creating
+/// both fresh so nothing planted at a predictable temp path is followed or reused. Returns the
+/// directory; the caller removes it.
+fn write_private_message(message: &str) -> io::Result<PathBuf> {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    let dir =
+        std::env::temp_dir().join(format!("odin-skill-digest-{}-{nanos}", std::process::id()));
+    let mut builder = fs::DirBuilder::new();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::DirBuilderExt;
+        builder.mode(0o700);
+    }
+    builder.create(&dir)?;
+
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let written = options
+        .open(dir.join(MESSAGE_FILE))
+        .and_then(|mut file| file.write_all(message.as_bytes()));
+    if let Err(e) = written {
+        let _ = fs::remove_dir_all(&dir);
+        return Err(e);
+    }
+    Ok(dir)
+}
+
+/// The digest a signature over `dir` covers, leaving out `signature` when it lies inside `dir`.
+pub fn signed_digest(dir: &Path, signature: Option<&Path>) -> Result<String, SkillSignatureError> {
+    content_sha256_excluding(dir, signature).map_err(|message| SkillSignatureError::Digest {
+        path: dir.to_path_buf(),
+        message,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_signed_message_is_written_privately() {
+        let dir = write_private_message("sha256:abc").expect("write message");
+        let path = dir.join(MESSAGE_FILE);
+        assert_eq!(fs::read_to_string(&path).expect("read"), "sha256:abc");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = |p: &Path| fs::metadata(p).expect("meta").permissions().mode() & 0o777;
+            assert_eq!(mode(&path), 0o600);
+            assert_eq!(mode(&dir), 0o700);
+        }
+        fs::remove_dir_all(&dir).expect("remove");
+    }
+
+    #[test]
+    fn only_trusted_signers_yield_a_signature_with_keys_relative_to_the_store() {
+        let store = SkillSignerTrustStore::from_yaml(
+            "schema_version: 1\nsigners:\n  - {id: release, key: keys/release.pub}\n",
+            "/etc/odin",
+        )
+        .expect("parse");
+        let signature = store.signature("release", "pack.minisig").expect("trusted");
+        assert_eq!(signature.method, "minisign");
+        assert_eq!(signature.key_dir, Path::new("/etc/odin"));
+        assert!(matches!(
+            store.signature("mallory", "pack.minisig"),
+            Err(SkillSignatureError::UnknownSigner(id)) if id == "mallory"
+        ));
+    }
+}
@@ -1,9 +1,10 @@
-use std::collections::HashSet;
-use std::fs;
-use std::path::Path;
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
 
 use odin_plugin_protocol::{
-    DelegationCapability, SkillRecord, SkillRegistry, SkillScope, TrustLevel,
+    DelegationCapability, SkillRecord, SkillRegistry, SkillScope, SkillSignatureRecord, TrustLevel,
 };
 use serde::Deserialize;
 use thiserror::Error;
@@ -16,6 +17,20 @@ pub enum SkillRegistryLoadError {
     Parse(String),
 }
 
+#[derive(Debug, Error)]
+pub enum SkillRegistryWriteError {
+    #[error(transparent)]
+    Load(#[from] SkillRegistryLoadError),
+    #[error("skill already registered: {0}")]
+    Duplicate(String),
+    #[error("skill not registered: {0}")]
+    NotFound(String),
+    #[error("invalid skill: {0}")]
+    Invalid(String),
+    #[error("registry write failed: {0}")]
+    Io(String),
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(deny_unknown_fields)]
 struct RawSkillRegistry {
@@ -35,6 +50,12 @@ struct RawSkillRecord {
     pinned_version: Option<String>,
     #[serde(default)]
     capabilities: Vec<RawDelegationCapability>,
+    #[serde(default)]
+    review_by_unix: Option<u64>,
+    #[serde(default)]
+    expires_at_unix: Option<u64>,
+    #[serde(default)]
+    signature: Option<SkillSignatureRecord>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -124,6 +145,208 @@ pub fn parse_scoped_registry(
     })
 }
 
+/// Registers `record` in the `scope` registry at `path`, creating the file when it does not
+/// exist yet. Fails when a skill with the same name is already registered.
+pub fn add_skill(
+    path: &Path,
+    scope: SkillScope,
+    record: SkillRecord,
+) -> Result<SkillRegistry, SkillRegistryWriteError> {
+    let raw = match fs::read_to_string(path) {
+        Ok(raw) => Some(raw),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => return Err(SkillRegistryLoadError::Io(e.to_string()).into()),
+    };
+    let mut registry = match &raw {
+        Some(raw) => parse_scoped_registry(raw, scope.clone())?,
+        None => SkillRegistry {
+            schema_version: 1,
+            scope: scope.clone(),
+            skills: Vec::new(),
+        },
+    };
+    let record = checked_record(record, &scope)?;
+    if registry
+        .skills
+        .iter()
+        .any(|skill| skill.name == record.name)
+    {
+        return Err(SkillRegistryWriteError::Duplicate(record.name));
+    }
+    registry.skills.push(record);
+    write_registry(path, &registry, raw.as_deref().unwrap_or_default())?;
+    Ok(registry)
+}
+
+/// Replaces the registered skill with the same name as `record`.
+pub fn update_skill(
+    path: &Path,
+    scope: SkillScope,
+    record: SkillRecord,
+) -> Result<SkillRegistry, SkillRegistryWriteError> {
+    let raw = fs::read_to_string(path).map_err(|e| SkillRegistryLoadError::Io(e.to_string()))?;
+    let mut registry = parse_scoped_registry(&raw, scope.clone())?;
+    let record = checked_record(record, &scope)?;
+    let slot = registry
+        .skills
+        .iter_mut()
+        .find(|skill| skill.name == record.name)
+        .ok_or_else(|| SkillRegistryWriteError::NotFound(record.name.clone()))?;
+    *slot = record;
+    write_registry(path, &registry, &raw)?;
+    Ok(registry)
+}
+
+pub fn remove_skill(
+    path: &Path,
+    scope: SkillScope,
+    name: &str,
+) -> Result<SkillRegistry, SkillRegistryWriteError> {
+    let raw = fs::read_to_string(path).map_err(|e| SkillRegistryLoadError::Io(e.to_string()))?;
+    let mut registry = parse_scoped_registry(&raw, scope)?;
+    let name = name.trim();
+    let before = registry.skills.len();
+    registry.skills.retain(|skill| skill.name != name);
+    if registry.skills.len() == before {
+        return Err(SkillRegistryWriteError::NotFound(name.to_string()));
+    }
+    write_registry(path, &registry, &raw)?;
+    Ok(registry)
+}
+
+/// Normalizes `record` like the loader does, and rejects sources scoped to another registry.
+fn checked_record(
+    record: SkillRecord,
+    scope: &SkillScope,
+) -> Result<SkillRecord, SkillRegistryWriteError> {
+    let trust_level = match record.trust_level {
+        TrustLevel::Trusted => "trusted",
+        TrustLevel::Caution => "caution",
+        TrustLevel::Untrusted => "untrusted",
+    };
+    let normalized = normalize_record(
+        RawSkillRecord {
+            name: record.name,
+            trust_level: trust_level.to_string(),
+            source: record.source,
+            pinned_version: record.pinned_version,
+            capabilities: record
+                .capabilities
+                .into_iter()
+                .map(|capability| RawDelegationCapability {
+                    id: capability.id,
+                    scope: capability.scope,
+                })
+                .collect(),
+            review_by_unix: record.review_by_unix,
+            expires_at_unix: record.expires_at_unix,
+            signature: record.signature,
+        },
+        scope.clone(),
+    )
+    .map_err(|e| SkillRegistryWriteError::Invalid(e.to_string()))?;
+    if let Some((prefix, _)) = normalized.source.split_once(':') {
+        if is_scope_prefix(prefix) && prefix != scope_prefix(scope.clone()) {
+            return Err(SkillRegistryWriteError::Invalid(format!(
+                "{} source {} does not belong in the {} registry",
+                normalized.name,
+                normalized.source,
+                scope_prefix(scope.clone())
+            )));
+        }
+    }
+    Ok(normalized)
+}
+
+/// Rewrites the registry through a temp file and a rename, so readers never see a partial
+/// file. Comments are carried over from `previous` where they can be placed: the header above
+/// the first key, and the comment lines directly above each `- name:` entry that survives.
+fn write_registry(
+    path: &Path,
+    registry: &SkillRegistry,
+    previous: &str,
+) -> Result<(), SkillRegistryWriteError> {
+    let (header, skill_comments) = registry_comments(previous);
+    let body =
+        serde_yml::to_string(registry).map_err(|e| SkillRegistryWriteError::Io(e.to_string()))?;
+    let mut out = header.join("\n");
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    for line in body.lines() {
+        if let Some(comments) = entry_name(line).and_then(|name| skill_comments.get(&name)) {
+            let indent = &line[..line.len() - line.trim_start().len()];
+            for comment in comments {
+                out.push_str(&format!("{indent}{}\n", comment.trim_start()));
+            }
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+    let io = |e: std::io::Error| SkillRegistryWriteError::Io(format!("{}: {e}", path.display()));
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent).map_err(io)?;
+    }
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(&tmp)
+        .map_err(io)?;
+    file.write_all(out.as_bytes())
+        .and_then(|()| file.sync_data())
+        .map_err(io)?;
+    fs::rename(&tmp, path).map_err(io)
+}
+
+/// Leading comment lines, and the comment lines directly above each skill entry by name.
+fn registry_comments(raw: &str) -> (Vec<&str>, HashMap<String, Vec<&str>>) {
+    let mut header = Vec::new();
+    let mut skill_comments = HashMap::new();
+    let mut pending = Vec::new();
+    let mut in_header = true;
+    for line in raw.lines() {
+        if line.trim_start().starts_with('#') {
+            pending.push(line);
+            continue;
+        }
+        if in_header {
+            if line.trim().is_empty() && !pending.is_empty() {
+                pending.push(line);
+                continue;
+            }
+            header.append(&mut pending);
+            in_header = false;
+        }
+        if let Some(name) = entry_name(line) {
+            if !pending.is_empty() {
+                skill_comments.insert(name, std::mem::take(&mut pending));
+            }
+        }
+        pending.clear();
+    }
+    (header, skill_comments)
+}
+
+/// The skill name on a `- name: <name>` list entry line.
+fn entry_name(line: &str) -> Option<String> {
+    let value = line
+        .trim_start()
+        .strip_prefix('-')?
+        .trim_start()
+        .strip_prefix("name:")?;
+    serde_yml::from_str::<String>(value.trim())
+        .ok()
+        .map(|name| name.trim().to_string())
+}
+
 fn find(
     name: &str,
     registry: Option<&SkillRegistry>,
@@ -173,6 +396,9 @@ fn normalize_record(
         .into_iter()
         .map(normalize_capability)
         .collect::<Result<Vec<_>, _>>()?;
+    normalized.review_by_unix = record.review_by_unix;
+    normalized.expires_at_unix = record.expires_at_unix;
+    normalized.signature = record.signature;
     Ok(normalized)
 }
 
@@ -0,0 +1,278 @@
+//! Workspace boundaries: whether a path stays inside an allowlisted directory, and whether the
+//! path arguments of a command do. Existing absolute paths are canonicalized, so symlinks cannot
+//! lead out of a workspace; missing ones are normalized lexically, and `..` above the root does
+//! not resolve at all.
+
+use std::ffi::OsString;
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BoundaryViolation {
+    /// No workspace is allowlisted, so no path argument can be judged.
+    NoWorkspaces,
+    /// A relative argument climbs with `..`.
+    RelativeTraversal,
+    /// A relative argument, whose meaning depends on the working directory.
+    UnscopedRelative,
+    /// An absolute argument outside every workspace, or one that does not exist.
+    OutsideWorkspace(String),
+}
+
+impl BoundaryViolation {
+    pub fn reason_code(&self) -> &'static str {
+        match self {
+            Self::NoWorkspaces => "command_workspace_policy_missing",
+            Self::RelativeTraversal => "command_relative_path_traversal",
+            Self::UnscopedRelative => "command_relative_path_unscoped",
+            Self::OutsideWorkspace(_) => "command_path_outside_allowlisted_workspace",
+        }
+    }
+}
+
+/// Whether `path` is `root` or below it, once both are normalized. Paths that do not normalize
+/// are never within.
+pub fn is_within(path: &Path, root: &Path) -> bool {
+    match (normalize(path), normalize(root)) {
+        (Some(path), Some(root)) => path.starts_with(root),
+        _ => false,
+    }
+}
+
+/// Checks the path-like arguments of a command: bare arguments and option values
+/// (`--out=/x`, `-o/x`). Every one must be an existing absolute path inside one of
+/// `workspaces`; flags without values are ignored.
+pub fn validate_command_paths<I, W>(args: &[String], workspaces: I) -> Result<(), BoundaryViolation>
+where
+    I: IntoIterator<Item = W>,
+    W: AsRef<Path>,
+{
+    let workspaces: Vec<PathBuf> = workspaces
+        .into_iter()
+        .filter_map(|workspace| normalize(workspace.as_ref()))
+        .collect();
+    if workspaces.is_empty() {
+        return Err(BoundaryViolation::NoWorkspaces);
+    }
+
+    let values = command_path_values(args);
+    if values
+        .iter()
+        .any(|value| has_relative_parent_segment(value))
+    {
+        return Err(BoundaryViolation::RelativeTraversal);
+    }
+    if values.iter().any(|value| !Path::new(value).is_absolute()) {
+        return Err(BoundaryViolation::UnscopedRelative);
+    }
+    for value in values {
+        let inside = fs::canonicalize(&value).is_ok_and(|path| {
+            workspaces
+                .iter()
+                .any(|workspace| path.starts_with(workspace))
+        });
+        if !inside {
+            return Err(BoundaryViolation::OutsideWorkspace(value));
+        }
+    }
+    Ok(())
+}
+
+/// The canonical form of an existing absolute `path`, otherwise its lexical normalization
+/// (see `normalize_lexical`).
+pub fn normalize(path: &Path) -> Option<PathBuf> {
+    if path.is_absolute() {
+        if let Ok(canonical) = fs::canonicalize(path) {
+            return Some(canonical);
+        }
+    }
+
+    normalize_lexical(path)
+}
+
+/// Resolves `.` and `..` without touching the filesystem. Leading `..` of a relative path are
+/// kept; `None` when `..` climbs above the root of an absolute one.
+pub fn normalize_lexical(path: &Path) -> Option<PathBuf> {
+    let mut prefix: Option<OsString> = None;
+    let mut has_root = false;
+    let mut parts: Vec<OsString> = Vec::new();
+
+    for component in path.components() {
+        match component {
+            Component::Prefix(value) => {
+                prefix = Some(value.as_os_str().to_os_string());
+            }
+            Component::RootDir => {
+                has_root = true;
+            }
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if let Some(last) = parts.last() {
+                    if last != ".." {
+                        parts.pop();
+                    } else if has_root {
+                        return None;
+                    } else {
+                        parts.push(OsString::from(".."));
+                    }
+                } else if has_root {
+                    return None;
+                } else {
+                    parts.push(OsString::from(".."));
+                }
+            }
+            Component::Normal(value) => parts.push(value.to_os_string()),
+        }
+    }
+
+    let mut normalized = PathBuf::new();
+    if let Some(value) = prefix {
+        normalized.push(value);
+    }
+    if has_root {
+        normalized.push(std::path::MAIN_SEPARATOR.to_string());
+    }
+    for part in parts {
+        normalized.push(part);
+    }
+
+    if normalized.as_os_str().is_empty() {
+        if has_root {
+            normalized.push(std::path::MAIN_SEPARATOR.to_string());
+        } else {
+            normalized.push(".");
+        }
+    }
+
+    Some(normalized)
+}
+
+/// `path` made absolute against the current directory, with its deepest existing ancestor
+/// canonicalized and the rest appended. For paths about to be created, such as output
+/// directories.
+pub fn resolve_allow_missing(path: &Path) -> io::Result<PathBuf> {
+    let absolute = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir()?.join(path)
+    };
+
+    let mut existing = absolute.as_path();
+    let mut missing_tail: Vec<OsString> = Vec::new();
+    while !existing.exists() {
+        let (Some(name), Some(parent)) = (existing.file_name(), existing.parent()) else {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no existing ancestor of {}", path.display()),
+            ));
+        };
+        missing_tail.push(name.to_os_string());
+        existing = parent;
+    }
+
+    let mut resolved = fs::canonicalize(existing)?;
+    for component in missing_tail.iter().rev() {
+        resolved.push(component);
+    }
+    Ok(resolved)
+}
+
+pub(crate) fn strip_wrapping_quotes(value: &str) -> String {
+    let trimmed = value.trim();
+    if trimmed.len() >= 2 {
+        let starts_single = trimmed.starts_with('\'') && trimmed.ends_with('\'');
+        let starts_double = trimmed.starts_with('"') && trimmed.ends_with('"');
+        if starts_single || starts_double {
+            return trimmed[1..trimmed.len() - 1].to_string();
+        }
+    }
+
+    trimmed.to_string()
+}
+
+fn has_relative_parent_segment(token: &str) -> bool {
+    let path = Path::new(token);
+    !path.is_absolute()
+        && path
+            .components()
+            .any(|component| matches!(component, Component::ParentDir))
+}
+
+fn command_path_values(args: &[String]) -> Vec<String> {
+    let mut values = Vec::new();
+
+    for arg in args {
+        let token = strip_wrapping_quotes(arg);
+        let trimmed = token.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        if let Some(value) = extract_option_value(trimmed) {
+            values.push(value);
+            continue;
+        }
+
+        if trimmed.starts_with('-') {
+            continue;
+        }
+
+        values.push(trimmed.to_string());
+    }
+
+    values
+}
+
+fn extract_option_value(token: &str) -> Option<String> {
+    if token.starts_with("--") {
+        let (_, value) = token.split_once('=')?;
+        return normalize_option_value(value);
+    }
+
+    if token.starts_with('-') {
+        if token.len() <= 2 {
+            return None;
+        }
+
+        let attached = &token[2..];
+        let attached = attached.strip_prefix('=').unwrap_or(attached);
+        return normalize_option_value(attached);
+    }
+
+    None
+}
+
+fn normalize_option_value(value: &str) -> Option<String> {
+    let trimmed = strip_wrapping_quotes(value);
+    let trimmed = trimmed.trim();
+    if trimmed.is_empty() {
+        None
+    } else {
+        Some(trimmed.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_uses_canonical_path_when_target_exists() {
+        let root = tempfile::tempdir().expect("tempdir");
+        let leaf = root.path().join("allowed");
+        fs::create_dir_all(&leaf).expect("create temp tree");
+
+        let input = root.path().join("allowed").join("..").join("allowed");
+        let expected = fs::canonicalize(&leaf).expect("canonical leaf");
+        assert_eq!(normalize(&input), Some(expected));
+    }
+
+    #[test]
+    fn normalize_falls_back_to_lexical_normalization_when_missing() {
+        let root = std::env::temp_dir().join("odin-governance-boundary-missing");
+        let input = root.join("allowed").join("..").join("outside");
+        assert_eq!(normalize(&input), Some(root.join("outside")));
+        assert_eq!(normalize_lexical(Path::new("/missing/../..")), None);
+    }
+}
@@ -1,6 +1,6 @@
 use odin_governance::plugins::{
-    huginn_default_policy, huginn_policy_from_envelope, huginn_with_domains, Action,
-    PermissionDecision,
+    huginn_default_policy, huginn_policy_from_envelope, huginn_with_domains, Action, DomainBudget,
+    HuginnMode, NavigationLedger, PermissionDecision,
 };
 use odin_plugin_protocol::{DelegationCapability, PluginPermissionEnvelope, TrustLevel};
 use std::fs;
@@ -19,6 +19,63 @@ fn huginn_denies_login_by_default() {
     );
 }
 
+#[test]
+fn huginn_interactions_require_interact_mode_and_approval() {
+    let policy = huginn_with_domains(["example.com"]);
+    let click = || Action::Click("https://example.com/form".to_string());
+    assert_eq!(
+        policy.evaluate(click()),
+        PermissionDecision::Deny {
+            reason_code: "mode_not_supported".to_string()
+        }
+    );
+
+    let policy = policy.with_mode(HuginnMode::InteractWithApproval);
+    assert_eq!(
+        policy.evaluate(click()),
+        PermissionDecision::RequireApproval {
+            reason_code: "interaction_requires_approval".to_string()
+        }
+    );
+    assert_eq!(
+        policy.evaluate(Action::FillForm("https://other.dev/form".to_string())),
+        PermissionDecision::Deny {
+            reason_code: "domain_not_allowlisted".to_string()
+        }
+    );
+    assert_eq!(
+        policy.evaluate(Action::Login),
+        PermissionDecision::Deny {
+            reason_code: "action_login_disallowed".to_string()
+        }
+    );
+}
+
+#[test]
+fn huginn_interact_permission_is_ignored_for_untrusted_envelopes() {
+    let envelope = |trust_level| PluginPermissionEnvelope {
+        plugin: "huginn".to_string(),
+        trust_level,
+        permissions: ["huginn.enabled", "huginn.interact", "browser.observe"]
+            .into_iter()
+            .map(|id| DelegationCapability {
+                id: id.to_string(),
+                scope: vec!["example.com".to_string()],
+            })
+            .collect(),
+    };
+    let click = || Action::Click("https://example.com/".to_string());
+
+    assert!(matches!(
+        huginn_policy_from_envelope(&envelope(TrustLevel::Caution)).evaluate(click()),
+        PermissionDecision::RequireApproval { .. }
+    ));
+    assert!(matches!(
+        huginn_policy_from_envelope(&envelope(TrustLevel::Untrusted)).evaluate(click()),
+        PermissionDecision::Deny { .. }
+    ));
+}
+
 #[test]
 fn huginn_denies_domain_outside_allowlist() {
     let policy = huginn_with_domains(["example.com"]);
@@ -290,6 +347,130 @@ fn huginn_untrusted_envelope_cannot_enable_plugin() {
     );
 }
 
+#[test]
+fn huginn_domain_budgets_limit_observes_per_hour_and_pages_per_task() {
+    let envelope = PluginPermissionEnvelope {
+        plugin: "huginn".to_string(),
+        trust_level: TrustLevel::Caution,
+        permissions: vec![
+            DelegationCapability {
+                id: "huginn.enabled".to_string(),
+                scope: vec![],
+            },
+            DelegationCapability {
+                id: "browser.observe".to_string(),
+                scope: vec!["*.example.com".to_string(), "docs.rs".to_string()],
+            },
+            DelegationCapability {
+                id: "huginn.budget".to_string(),
+                scope: vec![
+                    "*.example.com:pages_per_task=2".to_string(),
+                    "*.example.com:observes_per_hour=4".to_string(),
+                ],
+            },
+        ],
+    };
+    let policy = huginn_policy_from_envelope(&envelope);
+    let mut ledger = NavigationLedger::new();
+    let mut observe = |task: &str, url: &str, now: u64| match policy.evaluate_navigation(
+        Action::ObserveUrl(url.to_string()),
+        task,
+        &mut ledger,
+        now,
+    ) {
+        PermissionDecision::Allow { .. } => "allow".to_string(),
+        PermissionDecision::Deny { reason_code }
+        | PermissionDecision::RequireApproval { reason_code } => reason_code,
+    };
+
+    assert_eq!(observe("t1", "https://www.example.com/a", 0), "allow");
+    assert_eq!(observe("t1", "https://shop.example.com/b", 1), "allow");
+    assert_eq!(observe("t1", "https://www.example.com/a#top", 2), "allow");
+    assert_eq!(
+        observe("t1", "https://www.example.com/c", 3),
+        "domain_page_budget_exceeded"
+    );
+    assert_eq!(observe("t2", "https://www.example.com/c", 4), "allow");
+    assert_eq!(
+        observe("t3", "https://www.example.com/d", 5),
+        "domain_observe_rate_exceeded"
+    );
+    assert_eq!(observe("t3", "https://docs.rs/serde", 6), "allow");
+    assert_eq!(observe("t3", "https://www.example.com/d", 3_600), "allow");
+
+    let closed = huginn_with_domains(["example.com"]).with_domain_budget(
+        "example.com",
+        DomainBudget {
+            max_observes_per_hour: Some(0),
+            max_pages_per_task: None,
+        },
+    );
+    assert_eq!(
+        closed.evaluate_navigation(
+            Action::ObserveUrl("https://example.com".to_string()),
+            "t1",
+            &mut NavigationLedger::new(),
+            0
+        ),
+        PermissionDecision::Deny {
+            reason_code: "domain_observe_rate_exceeded".to_string()
+        }
+    );
+}
+
+#[test]
+fn huginn_command_scopes_pin_subcommands_and_deny_flags() {
+    let policy = huginn_default_policy()
+        .with_enabled(true)
+        .with_workspaces(["/tmp"])
+        .with_commands(["git:{status,log,diff}!{-f,--force}", "rm!{-r}"]);
+    let decide = |command: &str| match policy.evaluate(Action::RunCommand(command.to_string())) {
+        PermissionDecision::Allow { reason_code }
+        | PermissionDecision::Deny { reason_code }
+        | PermissionDecision::RequireApproval { reason_code } => reason_code,
+    };
+
+    assert_eq!(decide("git status"), "command_allowlisted");
+    assert_eq!(decide("git log --oneline /tmp"), "command_allowlisted");
+    assert_eq!(decide("git push"), "command_subcommand_not_allowlisted");
+    assert_eq!(decide("git"), "command_subcommand_not_allowlisted");
+    assert_eq!(
+        decide("git -C /tmp status"),
+        "command_subcommand_not_allowlisted"
+    );
+    assert_eq!(decide("git diff --force"), "command_flag_denied");
+    assert_eq!(decide("git diff --force=yes"), "command_flag_denied");
+    assert_eq!(
+        decide("rm /tmp/odin-scratch"),
+        "command_path_outside_allowlisted_workspace"
+    );
+    assert_eq!(decide("rm -rf /tmp"), "command_flag_denied");
+}
+
+#[test]
+fn huginn_ignores_malformed_command_scope_entries() {
+    for entry in [
+        "git:{status",
+        "git:{}",
+        "git:{status,}",
+        "git!{force}",
+        ":{status}",
+        "git:{status}!",
+    ] {
+        let policy = huginn_default_policy()
+            .with_enabled(true)
+            .with_workspaces(["/tmp"])
+            .with_commands([entry]);
+        assert_eq!(
+            policy.evaluate(Action::RunCommand("git status".to_string())),
+            PermissionDecision::Deny {
+                reason_code: "command_not_allowlisted".to_string()
+            },
+            "{entry} should grant nothing"
+        );
+    }
+}
+
 #[test]
 fn huginn_rejects_whitespace_command_scope_entry() {
     let policy = huginn_default_policy()
@@ -0,0 +1,58 @@
+use std::fs;
+
+use odin_plugin_protocol::{DelegationCapability, PluginPermissionEnvelope, TrustLevel};
+
+use odin_governance::envelopes::{load_permission_registry, save_permission_registry};
+use odin_governance::plugins::PluginPermissionRegistry;
+
+fn envelope(plugin: &str, id: &str, scope: &[&str]) -> PluginPermissionEnvelope {
+    PluginPermissionEnvelope {
+        plugin: plugin.to_string(),
+        trust_level: TrustLevel::Caution,
+        permissions: vec![DelegationCapability {
+            id: id.to_string(),
+            scope: scope.iter().map(|value| value.to_string()).collect(),
+        }],
+    }
+}
+
+#[test]
+fn saved_envelopes_reload_with_project_overrides_and_header() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("config").join("plugin-permissions.yaml");
+
+    let mut registry = PluginPermissionRegistry::from_envelopes([
+        envelope("huginn", "browser.observe", &["example.com"]),
+        envelope("example.git", "repo.read", &["project"]),
+    ]);
+    registry.insert_for_project(
+        "acme/platform",
+        envelope("huginn", "browser.observe", &["docs.acme.dev"]),
+    );
+    save_permission_registry(&path, &registry).expect("save");
+
+    let raw = fs::read_to_string(&path).expect("read");
+    fs::write(&path, format!("# Owned by the platform team.\n{raw}")).expect("annotate");
+    let reloaded = load_permission_registry(&path).expect("reload");
+    save_permission_registry(&path, &reloaded).expect("save again");
+
+    let reloaded = load_permission_registry(&path).expect("reload again");
+    assert_eq!(
+        reloaded
+            .envelopes()
+            .map(|envelope| envelope.plugin.as_str())
+            .collect::<Vec<_>>(),
+        ["example.git", "huginn"]
+    );
+    assert_eq!(
+        reloaded.effective("huginn", "acme/platform/api"),
+        Some(&envelope("huginn", "browser.observe", &["docs.acme.dev"]))
+    );
+    assert!(fs::read_to_string(&path)
+        .expect("read")
+        .starts_with("# Owned by the platform team.\nschema_version: 1\n"));
+    assert_eq!(
+        fs::read_dir(path.parent().unwrap()).expect("list").count(),
+        1
+    );
+}
@@ -0,0 +1,134 @@
+use odin_governance::envelopes::parse_permission_registry;
+use odin_governance::scopes::{parse_scope_templates, ScopeTemplateError};
+use odin_plugin_protocol::{DelegationCapability, PluginPermissionEnvelope, TrustLevel};
+
+const TEMPLATES: &str = r#"
+schema_version: 1
+projects:
+  demo:
+    default_scopes: [project]
+    templates:
+      prod-readonly: ["*.example.com", "/srv/prod"]
+  "*":
+    templates:
+      docs: [docs.example.com]
+"#;
+
+fn capability(id: &str, scope: &[&str]) -> DelegationCapability {
+    DelegationCapability {
+        id: id.to_string(),
+        scope: scope.iter().map(|value| value.to_string()).collect(),
+    }
+}
+
+#[test]
+fn template_reference_expands_and_is_recorded() {
+    let templates = parse_scope_templates(TEMPLATES).expect("parse");
+
+    let (expanded, expansions) = templates
+        .expand_capabilities(
+            "demo",
+            &[capability(
+                "browser.observe",
+                &["template:prod-readonly", "extra.dev"],
+            )],
+        )
+        .expect("expand");
+
+    assert_eq!(
+        expanded[0].scope,
+        vec!["*.example.com", "/srv/prod", "extra.dev"]
+    );
+    assert_eq!(expansions.len(), 1);
+    assert_eq!(expansions[0].template.as_deref(), Some("prod-readonly"));
+    assert_eq!(expansions[0].capability, "browser.observe");
+}
+
+#[test]
+fn empty_grant_scope_uses_project_defaults() {
+    let templates = parse_scope_templates(TEMPLATES).expect("parse");
+
+    let (expanded, expansions) = templates
+        .expand_capabilities("demo", &[capability("repo.read", &[])])
+        .expect("expand");
+
+    assert_eq!(expanded[0].scope, vec!["project"]);
+    assert_eq!(expansions[0].template, None);
+}
+
+#[test]
+fn wildcard_project_templates_apply_to_unlisted_projects() {
+    let templates = parse_scope_templates(TEMPLATES).expect("parse");
+    let envelope = PluginPermissionEnvelope {
+        plugin: "huginn".to_string(),
+        trust_level: TrustLevel::Caution,
+        permissions: vec![capability("browser.observe", &["template:docs"])],
+    };
+
+    let (expanded, _) = templates
+        .expand_envelope("other", &envelope)
+        .expect("expand");
+
+    assert_eq!(expanded.permissions[0].scope, vec!["docs.example.com"]);
+    assert_eq!(expanded.trust_level, TrustLevel::Caution);
+}
+
+#[test]
+fn registries_expand_each_envelope_with_its_project_templates() {
+    let templates = parse_scope_templates(TEMPLATES).expect("parse");
+    let registry = parse_permission_registry(
+        r#"
+envelopes:
+  - plugin: huginn
+    trust_level: caution
+    permissions:
+      - id: browser.observe
+        scope: ["template:docs"]
+projects:
+  - project: demo
+    envelopes:
+      - plugin: huginn
+        trust_level: caution
+        permissions:
+          - id: browser.observe
+            scope: ["template:prod-readonly"]
+          - id: repo.read
+"#,
+    )
+    .expect("registry");
+
+    let (expanded, expansions) = templates.expand_registry(&registry).expect("expand");
+
+    let wide = expanded.get("huginn").expect("plugin-wide envelope");
+    assert_eq!(wide.permissions[0].scope, vec!["docs.example.com"]);
+    let demo = expanded.effective("huginn", "demo").expect("demo envelope");
+    assert_eq!(
+        demo.permissions[0].scope,
+        vec!["*.example.com", "/srv/prod"]
+    );
+    assert_eq!(demo.permissions[1].scope, vec!["project"]);
+    assert_eq!(expansions.len(), 3);
+}
+
+#[test]
+fn unknown_template_fails_closed() {
+    let templates = parse_scope_templates(TEMPLATES).expect("parse");
+
+    let err = templates
+        .expand_capabilities("demo", &[capability("repo.read", &["template:missing"])])
+        .expect_err("unknown template");
+
+    assert!(matches!(err, ScopeTemplateError::UnknownTemplate { .. }));
+}
+
+#[test]
+fn nested_template_references_are_rejected() {
+    let err = parse_scope_templates(
+        "schema_version: 1\nprojects:\n  demo:\n    templates:\n      a: [\"template:b\"]\n",
+    )
+    .expect_err("nested reference");
+
+    assert!(err
+        .to_string()
+        .contains("must not reference other templates"));
+}
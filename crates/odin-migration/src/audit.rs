@@ -0,0 +1,140 @@
+//! Carries the audit log in a bundle under `events/audit/` so governance history travels with
+//! user data. `index.json` lists the chained log files oldest first, and validation recomputes
+//! the hash chain across them.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use odin_audit::chain::{verify_files, VerifyRange};
+
+use crate::model::AuditExportIndex;
+
+pub(crate) const AUDIT_DIR: &str = "events/audit";
+const INDEX_FILENAME: &str = "index.json";
+const ROLLUPS_SUFFIX: &str = ".rollups.json";
+
+/// Copies the log and its rotated files (plus prune rollups) into the bundle and returns the
+/// written paths relative to `out_dir`. A log whose chain is already broken is not exported.
+pub(crate) fn export_audit_log(audit_log: &Path, out_dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let files = odin_audit::file::log_files(audit_log)
+        .with_context(|| format!("failed to list audit log {}", audit_log.display()))?;
+    if files.is_empty() {
+        anyhow::bail!("export audit log does not exist: {}", audit_log.display());
+    }
+    let report = verify_files(&files, VerifyRange::default())
+        .with_context(|| format!("failed to verify audit log {}", audit_log.display()))?;
+    if let Some(divergence) = &report.divergence {
+        anyhow::bail!(
+            "refusing to export audit log with a broken chain at {} line {}: {}",
+            divergence.file.display(),
+            divergence.line,
+            divergence.reason
+        );
+    }
+
+    let out_audit_dir = out_dir.join(AUDIT_DIR);
+    if out_audit_dir.exists() {
+        anyhow::bail!(
+            "export audit log would overwrite exported events: {}",
+            out_audit_dir.display()
+        );
+    }
+    fs::create_dir_all(&out_audit_dir).with_context(|| {
+        format!(
+            "failed to create export audit directory {}",
+            out_audit_dir.display()
+        )
+    })?;
+
+    let mut rollups = audit_log.as_os_str().to_owned();
+    rollups.push(ROLLUPS_SUFFIX);
+    let rollups = PathBuf::from(rollups);
+    let mut names = Vec::with_capacity(files.len());
+    let mut written = Vec::with_capacity(files.len() + 2);
+    for file in files.iter().chain(Some(&rollups).filter(|r| r.is_file())) {
+        let name = file
+            .file_name()
+            .with_context(|| format!("audit log file has no name: {}", file.display()))?
+            .to_string_lossy()
+            .to_string();
+        let destination = out_audit_dir.join(&name);
+        fs::copy(file, &destination).with_context(|| {
+            format!(
+                "failed to copy audit log file {} -> {}",
+                file.display(),
+                destination.display()
+            )
+        })?;
+        written.push(Path::new(AUDIT_DIR).join(&name));
+        if file != &rollups {
+            names.push(name);
+        }
+    }
+
+    let index = AuditExportIndex {
+        schema_version: 1,
+        files: names,
+        head: report.head,
+    };
+    let index_path = out_audit_dir.join(INDEX_FILENAME);
+    let body = serde_json::to_string_pretty(&index).context("failed to format audit index")?;
+    fs::write(&index_path, body + "\n")
+        .with_context(|| format!("failed to write audit index {}", index_path.display()))?;
+    written.push(Path::new(AUDIT_DIR).join(INDEX_FILENAME));
+    Ok(written)
+}
+
+/// Checks chain continuity across the exported audit log; bundles without one pass.
+pub(crate) fn verify_audit_chain(bundle_dir: &Path) -> anyhow::Result<()> {
+    let audit_dir = bundle_dir.join(AUDIT_DIR);
+    let index_path = audit_dir.join(INDEX_FILENAME);
+    if !index_path.is_file() {
+        return Ok(());
+    }
+    let raw = fs::read_to_string(&index_path)
+        .with_context(|| format!("failed to read audit index {}", index_path.display()))?;
+    let index: AuditExportIndex = serde_json::from_str(&raw)
+        .with_context(|| format!("invalid audit index {}", index_path.display()))?;
+    if index.schema_version != 1 {
+        anyhow::bail!(
+            "unsupported audit index schema_version: expected 1, got {}",
+            index.schema_version
+        );
+    }
+
+    let mut files = Vec::with_capacity(index.files.len());
+    for name in &index.files {
+        if name.is_empty() || name.contains(['/', '\\']) || name == "." || name == ".." {
+            anyhow::bail!("audit index lists an invalid file name: {name:?}");
+        }
+        let path = audit_dir.join(name);
+        if !path.is_file() {
+            anyhow::bail!("audit index lists a missing file: {AUDIT_DIR}/{name}");
+        }
+        files.push(path);
+    }
+
+    let report = verify_files(&files, VerifyRange::default())
+        .with_context(|| format!("failed to verify audit chain in {}", audit_dir.display()))?;
+    if let Some(divergence) = report.divergence {
+        let name = divergence
+            .file
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        anyhow::bail!(
+            "audit chain broken at {AUDIT_DIR}/{name} line {}: {}",
+            divergence.line,
+            divergence.reason
+        );
+    }
+    if report.head != index.head {
+        anyhow::bail!(
+            "audit chain head {:?} does not match the exported head {:?}; records were removed from the end",
+            report.head,
+            index.head
+        );
+    }
+    Ok(())
+}
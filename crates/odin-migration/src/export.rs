@@ -2,8 +2,9 @@ use std::fs;
 use std::path::{Path, PathBuf};
 
 use anyhow::Context;
+use odin_governance::workspace_boundary::{is_within, resolve_allow_missing};
 
-use crate::checksum;
+use crate::{audit, checksum};
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub(crate) enum RootSelector {
@@ -52,7 +53,12 @@ pub(crate) const SECTION_MAPPINGS: [SectionMapping; 8] = [
     },
 ];
 
-pub fn write_bundle(source_root: &Path, odin_dir: &Path, out_dir: &Path) -> anyhow::Result<()> {
+pub fn write_bundle(
+    source_root: &Path,
+    odin_dir: &Path,
+    out_dir: &Path,
+    audit_log: Option<&Path>,
+) -> anyhow::Result<()> {
     validate_input_directory("source root", source_root)?;
     validate_input_directory("odin dir", odin_dir)?;
     reject_output_equal_input_roots(source_root, odin_dir, out_dir)?;
@@ -80,6 +86,10 @@ pub fn write_bundle(source_root: &Path, odin_dir: &Path, out_dir: &Path) -> anyh
         written_files.append(&mut section_files);
     }
 
+    if let Some(audit_log) = audit_log {
+        written_files.append(&mut audit::export_audit_log(audit_log, out_dir)?);
+    }
+
     if written_files.is_empty() {
         anyhow::bail!("export produced no mapped files from source roots");
     }
@@ -115,16 +125,16 @@ fn reject_output_inside_mapped_source_sections(
     odin_dir: &Path,
     out_dir: &Path,
 ) -> anyhow::Result<()> {
-    let out_abs = canonicalize_path_allow_missing(out_dir)?;
+    let out_abs = resolve_output_path(out_dir)?;
 
     for mapping in SECTION_MAPPINGS {
         let section_path = match mapping.source {
             RootSelector::SourceRoot => source_root.join(mapping.name),
             RootSelector::OdinDir => odin_dir.join(mapping.name),
         };
-        let section_abs = canonicalize_path_allow_missing(&section_path)?;
+        let section_abs = resolve_output_path(&section_path)?;
 
-        if out_abs.starts_with(&section_abs) {
+        if is_within(&out_abs, &section_abs) {
             anyhow::bail!(
                 "export output path cannot be inside mapped source section `{}`: {}",
                 mapping.name,
@@ -141,17 +151,15 @@ fn reject_output_equal_input_roots(
     odin_dir: &Path,
     out_dir: &Path,
 ) -> anyhow::Result<()> {
-    let out_abs = canonicalize_path_allow_missing(out_dir)?;
-    let source_abs = normalize_path(&fs::canonicalize(source_root).with_context(|| {
+    let out_abs = resolve_output_path(out_dir)?;
+    let source_abs = fs::canonicalize(source_root).with_context(|| {
         format!(
             "failed to canonicalize source root {}",
             source_root.display()
         )
-    })?);
-    let odin_abs = normalize_path(
-        &fs::canonicalize(odin_dir)
-            .with_context(|| format!("failed to canonicalize odin dir {}", odin_dir.display()))?,
-    );
+    })?;
+    let odin_abs = fs::canonicalize(odin_dir)
+        .with_context(|| format!("failed to canonicalize odin dir {}", odin_dir.display()))?;
 
     if out_abs == source_abs || out_abs == odin_abs {
         anyhow::bail!(
@@ -163,6 +171,11 @@ fn reject_output_equal_input_roots(
     Ok(())
 }
 
+fn resolve_output_path(path: &Path) -> anyhow::Result<PathBuf> {
+    resolve_allow_missing(path)
+        .with_context(|| format!("failed to resolve export path {}", path.display()))
+}
+
 fn prepare_clean_output_dir(out_dir: &Path) -> anyhow::Result<()> {
     if out_dir.exists() {
         if !out_dir.is_dir() {
@@ -229,7 +242,7 @@ fn copy_section_files(
     Ok(written_files)
 }
 
-fn collect_relative_files(root: &Path) -> anyhow::Result<Vec<PathBuf>> {
+pub(crate) fn collect_relative_files(root: &Path) -> anyhow::Result<Vec<PathBuf>> {
     let mut files = Vec::new();
     collect_relative_files_recursive(root, root, &mut files)?;
     files.sort_unstable();
@@ -270,58 +283,3 @@ fn collect_relative_files_recursive(
 fn manifest_json() -> &'static str {
     "{\n  \"schema_version\": 1,\n  \"user_data_model_version\": 1,\n  \"skills\": {},\n  \"learnings\": {},\n  \"runtime\": {},\n  \"checkpoints\": {},\n  \"events\": {},\n  \"opaque\": {},\n  \"quarantine\": {},\n  \"meta\": {}\n}\n"
 }
-
-fn canonicalize_path_allow_missing(path: &Path) -> anyhow::Result<PathBuf> {
-    let absolute = if path.is_absolute() {
-        path.to_path_buf()
-    } else {
-        std::env::current_dir()
-            .context("failed to read current working directory")?
-            .join(path)
-    };
-
-    let mut existing = absolute.as_path();
-    let mut missing_tail: Vec<std::ffi::OsString> = Vec::new();
-
-    while !existing.exists() {
-        let component = existing.file_name().ok_or_else(|| {
-            anyhow::anyhow!(
-                "failed to resolve output path ancestor for {}",
-                path.display()
-            )
-        })?;
-        missing_tail.push(component.to_os_string());
-        existing = existing.parent().ok_or_else(|| {
-            anyhow::anyhow!(
-                "failed to resolve output path ancestor for {}",
-                path.display()
-            )
-        })?;
-    }
-
-    let mut resolved = fs::canonicalize(existing).with_context(|| {
-        format!(
-            "failed to canonicalize path ancestor {}",
-            existing.display()
-        )
-    })?;
-    for component in missing_tail.iter().rev() {
-        resolved.push(component);
-    }
-
-    Ok(normalize_path(&resolved))
-}
-
-fn normalize_path(path: &Path) -> PathBuf {
-    let mut normalized = PathBuf::new();
-    for component in path.components() {
-        match component {
-            std::path::Component::CurDir => {}
-            std::path::Component::ParentDir => {
-                normalized.pop();
-            }
-            other => normalized.push(other.as_os_str()),
-        }
-    }
-    normalized
-}
@@ -0,0 +1,357 @@
+//! Applies a verified bundle back onto `source_root` and `odin_dir`. Each bundle section with
+//! files is built in a staging directory beside its target and swapped in by rename; if a swap
+//! fails, the swaps already made are undone. Sections without files are left alone.
+//!
+//! Quarantine: JSON files that do not parse are not applied but placed under
+//! `<source_root>/quarantine/<section>/`, and the bundle's `quarantine/` section is merged into
+//! the existing one rather than replacing it, so items awaiting review are never dropped.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::Context;
+use odin_governance::workspace_boundary::{is_within, resolve_allow_missing};
+
+use crate::export::{collect_relative_files, RootSelector, SECTION_MAPPINGS};
+use crate::verify;
+
+const QUARANTINE_SECTION: &str = "quarantine";
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ImportReport {
+    /// Sections replaced (or, for `quarantine`, extended), in mapping order.
+    pub sections: Vec<String>,
+    /// Bundle-relative paths applied to their targets.
+    pub applied: Vec<PathBuf>,
+    pub quarantined: Vec<QuarantinedFile>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QuarantinedFile {
+    /// Bundle-relative path of the file.
+    pub path: PathBuf,
+    pub reason: &'static str,
+}
+
+/// One section's files: (bundle file, path relative to the target section).
+type SectionPlan = Vec<(PathBuf, PathBuf)>;
+
+/// Verifies `bundle_dir` and applies it; with `dry_run`, only reports what would change.
+pub fn import_bundle(
+    bundle_dir: &Path,
+    source_root: &Path,
+    odin_dir: &Path,
+    dry_run: bool,
+) -> anyhow::Result<ImportReport> {
+    verify::verify_bundle(bundle_dir)?;
+    validate_target_directory("source root", source_root)?;
+    validate_target_directory("odin dir", odin_dir)?;
+    reject_bundle_inside_target_sections(bundle_dir, source_root, odin_dir)?;
+
+    let mut report = ImportReport::default();
+    let mut plans: BTreeMap<&'static str, SectionPlan> = BTreeMap::new();
+    for mapping in SECTION_MAPPINGS {
+        let section_dir = bundle_dir.join(mapping.name);
+        for relative in collect_relative_files(&section_dir)? {
+            let bundle_file = section_dir.join(&relative);
+            let bundle_path = Path::new(mapping.name).join(&relative);
+            if mapping.name == QUARANTINE_SECTION {
+                plans
+                    .entry(QUARANTINE_SECTION)
+                    .or_default()
+                    .push((bundle_file, relative));
+                report.applied.push(bundle_path);
+            } else if let Some(reason) = quarantine_reason(&bundle_file)? {
+                plans
+                    .entry(QUARANTINE_SECTION)
+                    .or_default()
+                    .push((bundle_file, bundle_path.clone()));
+                report.quarantined.push(QuarantinedFile {
+                    path: bundle_path,
+                    reason,
+                });
+            } else {
+                plans
+                    .entry(mapping.name)
+                    .or_default()
+                    .push((bundle_file, relative));
+                report.applied.push(bundle_path);
+            }
+        }
+    }
+    report.sections = SECTION_MAPPINGS
+        .iter()
+        .filter(|mapping| plans.contains_key(mapping.name))
+        .map(|mapping| mapping.name.to_string())
+        .collect();
+
+    if report.sections.is_empty() {
+        anyhow::bail!(
+            "import bundle has no files to apply: {}",
+            bundle_dir.display()
+        );
+    }
+    if dry_run {
+        return Ok(report);
+    }
+
+    let mut work = WorkDirs::default();
+    let swaps = match stage_sections(&plans, source_root, odin_dir, &mut work) {
+        Ok(swaps) => swaps,
+        Err(err) => {
+            work.remove();
+            return Err(err.context("import staging failed; targets were left unchanged"));
+        }
+    };
+    if let Err(failure) = swap_in(&swaps) {
+        if !failure.rolled_back {
+            return Err(failure.error.context(format!(
+                "import rollback incomplete; restore from the backups in {}",
+                work.describe()
+            )));
+        }
+        work.remove();
+        return Err(failure
+            .error
+            .context("import rolled back; targets were left unchanged"));
+    }
+    work.remove();
+    Ok(report)
+}
+
+fn validate_target_directory(label: &str, path: &Path) -> anyhow::Result<()> {
+    if !path.is_dir() {
+        anyhow::bail!("import {label} is not a directory: {}", path.display());
+    }
+    Ok(())
+}
+
+fn reject_bundle_inside_target_sections(
+    bundle_dir: &Path,
+    source_root: &Path,
+    odin_dir: &Path,
+) -> anyhow::Result<()> {
+    let bundle_abs = resolve_path(bundle_dir)?;
+    for mapping in SECTION_MAPPINGS {
+        let section = target_root(mapping.source, source_root, odin_dir).join(mapping.name);
+        if is_within(&bundle_abs, &resolve_path(&section)?) {
+            anyhow::bail!(
+                "import bundle cannot be inside target section `{}`: {}",
+                mapping.name,
+                bundle_dir.display()
+            );
+        }
+    }
+    Ok(())
+}
+
+fn resolve_path(path: &Path) -> anyhow::Result<PathBuf> {
+    resolve_allow_missing(path)
+        .with_context(|| format!("failed to resolve import path {}", path.display()))
+}
+
+fn target_root<'a>(selector: RootSelector, source_root: &'a Path, odin_dir: &'a Path) -> &'a Path {
+    match selector {
+        RootSelector::SourceRoot => source_root,
+        RootSelector::OdinDir => odin_dir,
+    }
+}
+
+/// Why a bundle file must not reach its live target, if it must not.
+fn quarantine_reason(path: &Path) -> anyhow::Result<Option<&'static str>> {
+    if path.extension().is_none_or(|extension| extension != "json") {
+        return Ok(None);
+    }
+    let bytes =
+        fs::read(path).with_context(|| format!("failed to read bundle file {}", path.display()))?;
+    Ok(serde_json::from_slice::<serde_json::Value>(&bytes)
+        .err()
+        .map(|_| "invalid_json"))
+}
+
+/// Per-root directories holding staged sections and the backups of the sections they replace.
+/// They sit inside the root so every swap is a rename within one filesystem.
+#[derive(Default)]
+struct WorkDirs {
+    dirs: BTreeMap<PathBuf, PathBuf>,
+}
+
+impl WorkDirs {
+    fn for_root(&mut self, root: &Path) -> anyhow::Result<PathBuf> {
+        if let Some(dir) = self.dirs.get(root) {
+            return Ok(dir.clone());
+        }
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        let dir = root.join(format!(".odin-import-{}-{nanos}", std::process::id()));
+        for sub in ["staged", "backup"] {
+            fs::create_dir_all(dir.join(sub)).with_context(|| {
+                format!(
+                    "failed to create import staging directory {}",
+                    dir.display()
+                )
+            })?;
+        }
+        self.dirs.insert(root.to_path_buf(), dir.clone());
+        Ok(dir)
+    }
+
+    fn describe(&self) -> String {
+        self.dirs
+            .values()
+            .map(|dir| dir.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn remove(&self) {
+        for dir in self.dirs.values() {
+            let _ = fs::remove_dir_all(dir);
+        }
+    }
+}
+
+struct Swap {
+    target: PathBuf,
+    staged: PathBuf,
+    backup: PathBuf,
+}
+
+fn stage_sections(
+    plans: &BTreeMap<&'static str, SectionPlan>,
+    source_root: &Path,
+    odin_dir: &Path,
+    work: &mut WorkDirs,
+) -> anyhow::Result<Vec<Swap>> {
+    let mut swaps = Vec::new();
+    for mapping in SECTION_MAPPINGS {
+        let Some(files) = plans.get(mapping.name) else {
+            continue;
+        };
+        let root = target_root(mapping.source, source_root, odin_dir);
+        let dir = work.for_root(root)?;
+        let swap = Swap {
+            target: root.join(mapping.name),
+            staged: dir.join("staged").join(mapping.name),
+            backup: dir.join("backup").join(mapping.name),
+        };
+
+        fs::create_dir_all(&swap.staged).with_context(|| {
+            format!("failed to create staged section {}", swap.staged.display())
+        })?;
+        if mapping.name == QUARANTINE_SECTION && swap.target.is_dir() {
+            for relative in collect_relative_files(&swap.target)? {
+                copy_file(&swap.target.join(&relative), &swap.staged.join(&relative))?;
+            }
+        }
+        for (bundle_file, relative) in files {
+            copy_file(bundle_file, &swap.staged.join(relative))?;
+        }
+        swaps.push(swap);
+    }
+    Ok(swaps)
+}
+
+fn copy_file(from: &Path, to: &Path) -> anyhow::Result<()> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create staged directory {}", parent.display()))?;
+    }
+    fs::copy(from, to)
+        .with_context(|| format!("failed to stage {} -> {}", from.display(), to.display()))?;
+    Ok(())
+}
+
+struct SwapFailure {
+    error: anyhow::Error,
+    /// Whether every section already swapped got its backup back.
+    rolled_back: bool,
+}
+
+/// Renames each staged section over its target, keeping the old one as a backup. On failure the
+/// sections already swapped get their backups back.
+fn swap_in(swaps: &[Swap]) -> Result<(), SwapFailure> {
+    let mut applied: Vec<(&Swap, bool)> = Vec::with_capacity(swaps.len());
+    for swap in swaps {
+        let backed_up = fs::symlink_metadata(&swap.target).is_ok();
+        let result = if backed_up {
+            fs::rename(&swap.target, &swap.backup)
+                .with_context(|| format!("failed to move aside {}", swap.target.display()))
+                .and_then(|()| {
+                    fs::rename(&swap.staged, &swap.target).map_err(|err| {
+                        let _ = fs::rename(&swap.backup, &swap.target);
+                        anyhow::Error::new(err)
+                    })
+                })
+        } else {
+            fs::rename(&swap.staged, &swap.target).map_err(anyhow::Error::new)
+        };
+        if let Err(err) = result {
+            // Undo every swap, even after one fails to undo.
+            let failed_undos = applied
+                .iter()
+                .rev()
+                .map(|(swap, backed_up)| undo_swap(swap, *backed_up))
+                .filter(Result::is_err)
+                .count();
+            return Err(SwapFailure {
+                error: err.context(format!("failed to swap in {}", swap.target.display())),
+                rolled_back: failed_undos == 0,
+            });
+        }
+        applied.push((swap, backed_up));
+    }
+    Ok(())
+}
+
+fn undo_swap(swap: &Swap, backed_up: bool) -> std::io::Result<()> {
+    fs::remove_dir_all(&swap.target)?;
+    if backed_up {
+        fs::rename(&swap.backup, &swap.target)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_failed_swap_restores_the_sections_already_swapped() {
+        let root = std::env::temp_dir().join(format!(
+            "odin-migration-swap-rollback-{}-{}",
+            std::process::id(),
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_nanos())
+                .unwrap_or(0)
+        ));
+        let write = |path: PathBuf, contents: &str| {
+            fs::create_dir_all(path.parent().expect("parent")).expect("create dir");
+            fs::write(path, contents).expect("write file");
+        };
+        write(root.join("runtime/state.json"), "old");
+        write(root.join("staged/runtime/state.json"), "new");
+        fs::create_dir_all(root.join("backup")).expect("create backup dir");
+        let swap = |name: &str| Swap {
+            target: root.join(name),
+            staged: root.join("staged").join(name),
+            backup: root.join("backup").join(name),
+        };
+
+        let result = swap_in(&[swap("runtime"), swap("events")]);
+
+        assert!(
+            result.is_err_and(|failure| failure.rolled_back),
+            "the missing staged section fails the swap and is rolled back"
+        );
+        let state = fs::read_to_string(root.join("runtime/state.json")).expect("read state");
+        assert_eq!(state, "old");
+        assert!(!root.join("events").exists());
+        let _ = fs::remove_dir_all(&root);
+    }
+}
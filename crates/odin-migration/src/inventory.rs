@@ -1,7 +1,8 @@
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::path::Path;
 
 use anyhow::Context;
+use odin_governance::workspace_boundary::{is_within, resolve_allow_missing};
 
 const COUNTED_SECTIONS: [&str; 4] = ["skills", "learnings", "checkpoints", "events"];
 
@@ -83,10 +84,15 @@ fn reject_output_inside_counted_sections(
             input_dir.display()
         )
     })?;
-    let output_abs = canonicalize_path_allow_missing(output_path)?;
+    let output_abs = resolve_allow_missing(output_path).with_context(|| {
+        format!(
+            "failed to resolve inventory output path {}",
+            output_path.display()
+        )
+    })?;
 
     for section in COUNTED_SECTIONS {
-        if output_abs.starts_with(input_root.join(section)) {
+        if is_within(&output_abs, &input_root.join(section)) {
             anyhow::bail!(
                 "inventory output path cannot be inside counted section `{section}` under input root: {}",
                 output_path.display()
@@ -97,61 +103,6 @@ fn reject_output_inside_counted_sections(
     Ok(())
 }
 
-fn canonicalize_path_allow_missing(path: &Path) -> anyhow::Result<PathBuf> {
-    let absolute = if path.is_absolute() {
-        path.to_path_buf()
-    } else {
-        std::env::current_dir()
-            .context("failed to read current working directory")?
-            .join(path)
-    };
-
-    let mut existing = absolute.as_path();
-    let mut missing_tail: Vec<std::ffi::OsString> = Vec::new();
-
-    while !existing.exists() {
-        let component = existing.file_name().ok_or_else(|| {
-            anyhow::anyhow!(
-                "failed to resolve output path ancestor for {}",
-                path.display()
-            )
-        })?;
-        missing_tail.push(component.to_os_string());
-        existing = existing.parent().ok_or_else(|| {
-            anyhow::anyhow!(
-                "failed to resolve output path ancestor for {}",
-                path.display()
-            )
-        })?;
-    }
-
-    let mut resolved = fs::canonicalize(existing).with_context(|| {
-        format!(
-            "failed to canonicalize output path ancestor {}",
-            existing.display()
-        )
-    })?;
-    for component in missing_tail.iter().rev() {
-        resolved.push(component);
-    }
-
-    Ok(normalize_path(&resolved))
-}
-
-fn normalize_path(path: &Path) -> PathBuf {
-    let mut normalized = PathBuf::new();
-    for component in path.components() {
-        match component {
-            std::path::Component::CurDir => {}
-            std::path::Component::ParentDir => {
-                normalized.pop();
-            }
-            other => normalized.push(other.as_os_str()),
-        }
-    }
-    normalized
-}
-
 fn count_section_files(input_dir: &Path, section_name: &str) -> anyhow::Result<usize> {
     let section_path = input_dir.join(section_name);
     count_regular_files_recursive(&section_path)
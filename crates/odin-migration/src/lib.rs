@@ -1,5 +1,7 @@
+pub mod audit;
 pub mod checksum;
 pub mod export;
+pub mod import;
 pub mod inventory;
 pub mod model;
 pub mod validate;
@@ -13,11 +15,19 @@ pub enum MigrationCommand {
         source_root: PathBuf,
         odin_dir: PathBuf,
         out_dir: PathBuf,
+        /// Audit log to carry under `events/audit/`.
+        audit_log: Option<PathBuf>,
     },
     Validate {
         bundle_dir: PathBuf,
     },
-    Import,
+    Import {
+        bundle_dir: PathBuf,
+        source_root: PathBuf,
+        odin_dir: PathBuf,
+        /// Verify and report without touching the targets.
+        dry_run: bool,
+    },
     Inventory {
         input_dir: PathBuf,
         output_path: PathBuf,
@@ -30,16 +40,36 @@ pub fn run(command: MigrationCommand) -> anyhow::Result<()> {
             source_root,
             odin_dir,
             out_dir,
+            audit_log,
         } => {
-            export::write_bundle(&source_root, &odin_dir, &out_dir)?;
+            export::write_bundle(&source_root, &odin_dir, &out_dir, audit_log.as_deref())?;
             println!("migrate export bundle written to {}", out_dir.display());
         }
         MigrationCommand::Validate { bundle_dir } => {
             verify::verify_bundle(&bundle_dir)?;
             println!("migrate validate bundle verified: {}", bundle_dir.display());
         }
-        MigrationCommand::Import => {
-            println!("migrate import is not implemented yet");
+        MigrationCommand::Import {
+            bundle_dir,
+            source_root,
+            odin_dir,
+            dry_run,
+        } => {
+            let report = import::import_bundle(&bundle_dir, &source_root, &odin_dir, dry_run)?;
+            for file in &report.quarantined {
+                println!(
+                    "migrate import quarantined {} ({})",
+                    file.path.display(),
+                    file.reason
+                );
+            }
+            println!(
+                "migrate import {} {} files ({}) from {}",
+                if dry_run { "would apply" } else { "applied" },
+                report.applied.len(),
+                report.sections.join(", "),
+                bundle_dir.display()
+            );
         }
         MigrationCommand::Inventory {
             input_dir,
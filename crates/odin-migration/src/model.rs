@@ -18,6 +18,16 @@ pub struct UserDataManifest {
     pub meta: Option<ManifestSectionRef>,
 }
 
+/// `events/audit/index.json`: the exported audit log files, oldest first, and the hash of the
+/// last chained record.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct AuditExportIndex {
+    pub schema_version: u32,
+    pub files: Vec<String>,
+    pub head: Option<String>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(deny_unknown_fields)]
 pub struct SkillPackMetadata {
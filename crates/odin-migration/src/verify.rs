@@ -6,6 +6,7 @@ use std::path::{Component, Path, PathBuf};
 use anyhow::Context;
 use sha2::{Digest, Sha256};
 
+use crate::audit;
 use crate::export::SECTION_MAPPINGS;
 
 const MANIFEST_FILENAME: &str = "manifest.json";
@@ -15,6 +16,7 @@ pub fn verify_bundle(bundle_dir: &Path) -> anyhow::Result<()> {
     ensure_bundle_root(bundle_dir)?;
     ensure_required_structure(bundle_dir)?;
     verify_checksums(bundle_dir)?;
+    audit::verify_audit_chain(bundle_dir)?;
     Ok(())
 }
 
@@ -48,6 +48,7 @@ fn export_bundle_creates_required_bundle_root_structure() {
         source_root,
         odin_dir,
         out_dir: out_dir.clone(),
+        audit_log: None,
     })
     .expect("export should succeed");
 
@@ -89,6 +90,7 @@ fn export_bundle_emits_manifest_json() {
         source_root,
         odin_dir,
         out_dir: out_dir.clone(),
+        audit_log: None,
     })
     .expect("export should succeed");
 
@@ -129,6 +131,7 @@ fn export_bundle_writes_checksums_file() {
         source_root,
         odin_dir,
         out_dir: out_dir.clone(),
+        audit_log: None,
     })
     .expect("export should succeed");
 
@@ -166,6 +169,7 @@ fn export_bundle_checksums_are_deterministically_ordered() {
         source_root: source_root.clone(),
         odin_dir: odin_dir.clone(),
         out_dir: out_a.clone(),
+        audit_log: None,
     })
     .expect("first export should succeed");
 
@@ -173,6 +177,7 @@ fn export_bundle_checksums_are_deterministically_ordered() {
         source_root,
         odin_dir,
         out_dir: out_b.clone(),
+        audit_log: None,
     })
     .expect("second export should succeed");
 
@@ -212,6 +217,7 @@ fn export_bundle_rejects_output_inside_mapped_source_sections() {
         source_root,
         odin_dir,
         out_dir: out_dir.clone(),
+        audit_log: None,
     });
 
     let err = result.expect_err("out dir inside mapped source section should fail");
@@ -240,6 +246,7 @@ fn export_bundle_removes_stale_files_when_output_directory_already_exists() {
         source_root: source_root.clone(),
         odin_dir: odin_dir.clone(),
         out_dir: out_dir.clone(),
+        audit_log: None,
     })
     .expect("first export should succeed");
 
@@ -250,6 +257,7 @@ fn export_bundle_removes_stale_files_when_output_directory_already_exists() {
         source_root,
         odin_dir,
         out_dir: out_dir.clone(),
+        audit_log: None,
     })
     .expect("second export should succeed");
 
@@ -279,6 +287,7 @@ fn export_bundle_checksums_file_uses_real_sha256_digest_format() {
         source_root,
         odin_dir,
         out_dir: out_dir.clone(),
+        audit_log: None,
     })
     .expect("export should succeed");
 
@@ -313,6 +322,7 @@ fn export_bundle_rejects_output_equal_to_source_or_odin_root() {
         source_root: source_root.clone(),
         odin_dir: odin_dir.clone(),
         out_dir: source_root.clone(),
+        audit_log: None,
     });
     let err_source = result_source.expect_err("out == source_root should fail");
     assert!(
@@ -330,6 +340,7 @@ fn export_bundle_rejects_output_equal_to_source_or_odin_root() {
         source_root,
         odin_dir: odin_dir.clone(),
         out_dir: odin_dir.clone(),
+        audit_log: None,
     });
     let err_odin = result_odin.expect_err("out == odin_dir should fail");
     assert!(
@@ -360,6 +371,7 @@ fn export_bundle_rejects_noop_when_no_mapped_files_are_copied() {
         source_root,
         odin_dir,
         out_dir: out_dir.clone(),
+        audit_log: None,
     });
 
     let err = result.expect_err("no-op export should fail");
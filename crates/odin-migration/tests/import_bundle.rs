@@ -0,0 +1,146 @@
+use odin_migration::{run, MigrationCommand};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    fn new(prefix: &str) -> Self {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system clock should be after unix epoch")
+            .as_nanos();
+        let path = std::env::temp_dir().join(format!("{prefix}-{}-{nanos}", std::process::id()));
+        fs::create_dir_all(&path).expect("create temp fixture dir");
+        Self { path }
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+fn create_file(path: &Path, contents: &str) {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).expect("create parent dir for fixture file");
+    }
+    fs::write(path, contents).expect("write fixture file");
+}
+
+fn read(path: &Path) -> String {
+    fs::read_to_string(path).expect("read imported file")
+}
+
+/// Exports a fixture with skills, runtime state, one unparseable JSON file and a quarantined
+/// item, returning the bundle directory.
+fn export_fixture(fixture: &TempDir) -> PathBuf {
+    let source_root = fixture.path.join("legacy");
+    let odin_dir = fixture.path.join("legacy-odin");
+    let bundle_dir = fixture.path.join("bundle");
+    create_file(&source_root.join("skills/notes/SKILL.md"), "# Notes\n");
+    create_file(&source_root.join("quarantine/broken.skill.xml"), "<skill");
+    create_file(&odin_dir.join("runtime/state.json"), "{\"ok\":true}");
+    create_file(&odin_dir.join("runtime/routing.json"), "{not json");
+
+    run(MigrationCommand::Export {
+        source_root,
+        odin_dir,
+        out_dir: bundle_dir.clone(),
+        audit_log: None,
+    })
+    .expect("export should succeed");
+    bundle_dir
+}
+
+#[test]
+fn import_applies_sections_and_quarantines_invalid_json() {
+    let fixture = TempDir::new("odin-migration-import-apply");
+    let bundle_dir = export_fixture(&fixture);
+    let source_root = fixture.path.join("core");
+    let odin_dir = fixture.path.join("core-odin");
+    create_file(&source_root.join("skills/stale/SKILL.md"), "# Stale\n");
+    create_file(&source_root.join("learnings/hot/keep.md"), "keep");
+    create_file(&source_root.join("quarantine/earlier.json"), "{");
+    fs::create_dir_all(&odin_dir).expect("create odin dir");
+
+    let import = || {
+        run(MigrationCommand::Import {
+            bundle_dir: bundle_dir.clone(),
+            source_root: source_root.clone(),
+            odin_dir: odin_dir.clone(),
+            dry_run: false,
+        })
+    };
+    import().expect("import should succeed");
+
+    assert_eq!(
+        read(&source_root.join("skills/notes/SKILL.md")),
+        "# Notes\n"
+    );
+    assert!(
+        !source_root.join("skills/stale").exists(),
+        "imported sections replace their targets"
+    );
+    assert_eq!(read(&source_root.join("learnings/hot/keep.md")), "keep");
+    assert_eq!(read(&odin_dir.join("runtime/state.json")), "{\"ok\":true}");
+    assert!(!odin_dir.join("runtime/routing.json").exists());
+    assert_eq!(
+        read(&source_root.join("quarantine/runtime/routing.json")),
+        "{not json"
+    );
+    assert_eq!(
+        read(&source_root.join("quarantine/broken.skill.xml")),
+        "<skill"
+    );
+    assert_eq!(read(&source_root.join("quarantine/earlier.json")), "{");
+
+    import().expect("importing the same bundle again should succeed");
+    assert_eq!(read(&odin_dir.join("runtime/state.json")), "{\"ok\":true}");
+    let leftovers: Vec<_> = fs::read_dir(&source_root)
+        .expect("read source root")
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            entry
+                .file_name()
+                .to_string_lossy()
+                .starts_with(".odin-import-")
+        })
+        .collect();
+    assert!(leftovers.is_empty(), "staging directories are removed");
+}
+
+#[test]
+fn import_dry_run_and_tampered_bundles_leave_targets_unchanged() {
+    let fixture = TempDir::new("odin-migration-import-unchanged");
+    let bundle_dir = export_fixture(&fixture);
+    let source_root = fixture.path.join("core");
+    let odin_dir = fixture.path.join("core-odin");
+    create_file(&source_root.join("skills/stale/SKILL.md"), "# Stale\n");
+    fs::create_dir_all(&odin_dir).expect("create odin dir");
+    let import = |dry_run: bool| {
+        run(MigrationCommand::Import {
+            bundle_dir: bundle_dir.clone(),
+            source_root: source_root.clone(),
+            odin_dir: odin_dir.clone(),
+            dry_run,
+        })
+    };
+
+    import(true).expect("dry run should succeed");
+    assert!(source_root.join("skills/stale/SKILL.md").is_file());
+    assert!(!odin_dir.join("runtime").exists());
+
+    create_file(&bundle_dir.join("runtime/state.json"), "{\"ok\":false}");
+    let err = import(false).expect_err("tampered bundle should be refused");
+    assert!(
+        format!("{err:#}").contains("checksum mismatch"),
+        "unexpected error: {err:#}"
+    );
+    assert!(source_root.join("skills/stale/SKILL.md").is_file());
+    assert!(!source_root.join("skills/notes").exists());
+}
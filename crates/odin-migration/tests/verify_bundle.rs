@@ -1,4 +1,7 @@
+use odin_audit::file::FileAuditSink;
+use odin_audit::{AuditRecord, AuditSink, Severity};
 use odin_migration::{run, MigrationCommand};
+use sha2::{Digest, Sha256};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -46,6 +49,7 @@ fn validate_bundle_rejects_checksum_tamper() {
         source_root,
         odin_dir,
         out_dir: bundle_dir.clone(),
+        audit_log: None,
     })
     .expect("export should succeed");
 
@@ -78,6 +82,7 @@ fn validate_bundle_accepts_fresh_export_bundle() {
         source_root,
         odin_dir,
         out_dir: bundle_dir.clone(),
+        audit_log: None,
     })
     .expect("export should succeed");
 
@@ -98,6 +103,7 @@ fn validate_bundle_rejects_missing_required_directory() {
         source_root,
         odin_dir,
         out_dir: bundle_dir.clone(),
+        audit_log: None,
     })
     .expect("export should succeed");
 
@@ -129,6 +135,7 @@ fn validate_bundle_rejects_missing_manifest_checksum_entry() {
         source_root,
         odin_dir,
         out_dir: bundle_dir.clone(),
+        audit_log: None,
     })
     .expect("export should succeed");
 
@@ -168,6 +175,7 @@ fn validate_bundle_rejects_symlinked_required_directory() {
         source_root,
         odin_dir,
         out_dir: bundle_dir.clone(),
+        audit_log: None,
     })
     .expect("export should succeed");
 
@@ -189,3 +197,65 @@ fn validate_bundle_rejects_symlinked_required_directory() {
         "unexpected error: {err:#}"
     );
 }
+
+#[test]
+fn validate_bundle_checks_exported_audit_chain() {
+    let fixture = TempDir::new("odin-migration-validate-audit-chain");
+    let source_root = fixture.path.join("source-root");
+    let odin_dir = fixture.path.join("odin-dir");
+    let bundle_dir = fixture.path.join("bundle");
+    let audit_log = fixture.path.join("audit/audit.jsonl");
+
+    create_file(&source_root.join("skills/skill-a.json"), "original");
+    fs::create_dir_all(&odin_dir).expect("create odin dir");
+    let sink = FileAuditSink::open(&audit_log).expect("open audit log");
+    for ts_unix in 1..=3 {
+        sink.record(AuditRecord {
+            ts_unix,
+            event_type: "policy.decision".to_string(),
+            severity: Severity::Info,
+            request_id: Some(format!("req-{ts_unix}")),
+            task_id: None,
+            project: None,
+            trace_id: None,
+            metadata: serde_json::Value::Null,
+        })
+        .expect("record");
+    }
+
+    run(MigrationCommand::Export {
+        source_root,
+        odin_dir,
+        out_dir: bundle_dir.clone(),
+        audit_log: Some(audit_log),
+    })
+    .expect("export should succeed");
+
+    let checksums = fs::read_to_string(bundle_dir.join("checksums.sha256")).expect("checksums");
+    assert!(checksums.contains("  events/audit/audit.jsonl\n"));
+    assert!(checksums.contains("  events/audit/index.json\n"));
+    run(MigrationCommand::Validate {
+        bundle_dir: bundle_dir.clone(),
+    })
+    .expect("fresh bundle should validate");
+
+    // Drop a record and re-seal the checksum, so only the chain can notice.
+    let exported = bundle_dir.join("events/audit/audit.jsonl");
+    let original = fs::read_to_string(&exported).expect("read exported log");
+    let mut lines: Vec<&str> = original.lines().collect();
+    lines.remove(1);
+    let edited = lines.join("\n") + "\n";
+    fs::write(&exported, &edited).expect("edit exported log");
+    let resealed = checksums.replace(
+        &format!("{:x}", Sha256::digest(original.as_bytes())),
+        &format!("{:x}", Sha256::digest(edited.as_bytes())),
+    );
+    fs::write(bundle_dir.join("checksums.sha256"), resealed).expect("reseal checksums");
+
+    let err = run(MigrationCommand::Validate { bundle_dir }).expect_err("broken chain");
+    assert!(
+        err.to_string()
+            .contains("audit chain broken at events/audit/audit.jsonl line 2"),
+        "unexpected error: {err:#}"
+    );
+}
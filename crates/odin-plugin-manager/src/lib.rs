@@ -7,7 +7,7 @@ use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::time::{SystemTime, UNIX_EPOCH};
 
-use odin_plugin_protocol::PluginManifest;
+use odin_plugin_protocol::{PluginClass, PluginManifest};
 use sha2::{Digest, Sha256};
 use thiserror::Error;
 
@@ -110,6 +110,22 @@ impl FilesystemPluginManager {
             ));
         }
 
+        if manifest.plugin.max_concurrent_dispatches == Some(0) {
+            return Err(PluginManagerError::InvalidManifest(
+                "max_concurrent_dispatches must be at least 1".to_string(),
+            ));
+        }
+
+        if manifest.plugin.class == PluginClass::ObserveOnly {
+            let mutating = manifest.mutating_capabilities();
+            if !mutating.is_empty() {
+                return Err(PluginManagerError::InvalidManifest(format!(
+                    "observe_only plugin declares mutating capabilities: {}",
+                    mutating.join(", ")
+                )));
+            }
+        }
+
         if let Some(expected) = &req.expected_checksum_sha256 {
             let actual = &manifest.distribution.integrity.checksum_sha256;
             if !expected.eq_ignore_ascii_case(actual) {
@@ -298,53 +314,20 @@ impl FilesystemPluginManager {
             return Err(PluginManagerError::SignatureMissing);
         }
 
+        let key = signing
+            .certificate
+            .as_deref()
+            .map(str::trim)
+            .filter(|s| !s.is_empty());
         match method.as_str() {
             "none" => Err(PluginManagerError::SignatureMissing),
-            "minisign" => {
-                let cert_value = signing
-                    .certificate
-                    .as_deref()
-                    .map(str::trim)
-                    .filter(|s| !s.is_empty())
-                    .ok_or(PluginManagerError::SignatureMissing)?;
-
-                let public_key = materialize_public_key(manifest_dir, cert_value)?;
-                run_command(
-                    Command::new("minisign")
-                        .arg("-Vm")
-                        .arg(manifest_path)
-                        .arg("-x")
-                        .arg(&signature_path)
-                        .arg("-P")
-                        .arg(public_key),
-                    "minisign verify",
-                )
-                .map_err(|e| PluginManagerError::SignatureVerificationFailed(e.to_string()))
-            }
-            "sigstore" => {
-                let cert_path = signing
-                    .certificate
-                    .as_deref()
-                    .map(str::trim)
-                    .filter(|s| !s.is_empty())
-                    .ok_or(PluginManagerError::SignatureMissing)?;
-                let cert_path = resolve_path(manifest_dir, cert_path);
-                if !cert_path.exists() {
-                    return Err(PluginManagerError::SignatureMissing);
-                }
-
-                run_command(
-                    Command::new("cosign")
-                        .arg("verify-blob")
-                        .arg("--key")
-                        .arg(&cert_path)
-                        .arg("--signature")
-                        .arg(&signature_path)
-                        .arg(manifest_path),
-                    "sigstore verify",
-                )
-                .map_err(|e| PluginManagerError::SignatureVerificationFailed(e.to_string()))
-            }
+            "minisign" | "sigstore" => verify_detached_signature(
+                &method,
+                manifest_path,
+                &signature_path,
+                key.ok_or(PluginManagerError::SignatureMissing)?,
+                manifest_dir,
+            ),
             other => Err(PluginManagerError::SignatureMethodUnsupported(
                 other.to_string(),
             )),
@@ -378,6 +361,55 @@ impl PluginManager for FilesystemPluginManager {
     }
 }
 
+/// Verifies a detached `signature` over the file `message` with the `minisign` or `sigstore`
+/// (`cosign verify-blob`) CLI. `key` is a minisign public key or key file, or a cosign public
+/// key file; relative key paths resolve under `base`.
+pub fn verify_detached_signature(
+    method: &str,
+    message: &Path,
+    signature: &Path,
+    key: &str,
+    base: &Path,
+) -> Result<(), PluginManagerError> {
+    match method {
+        "minisign" => {
+            let public_key = materialize_public_key(base, key)?;
+            run_command(
+                Command::new("minisign")
+                    .arg("-Vm")
+                    .arg(message)
+                    .arg("-x")
+                    .arg(signature)
+                    .arg("-P")
+                    .arg(public_key),
+                "minisign verify",
+            )
+            .map_err(|e| PluginManagerError::SignatureVerificationFailed(e.to_string()))
+        }
+        "sigstore" => {
+            let key_path = resolve_path(base, key);
+            if !key_path.exists() {
+                return Err(PluginManagerError::SignatureMissing);
+            }
+
+            run_command(
+                Command::new("cosign")
+                    .arg("verify-blob")
+                    .arg("--key")
+                    .arg(&key_path)
+                    .arg("--signature")
+                    .arg(signature)
+                    .arg(message),
+                "sigstore verify",
+            )
+            .map_err(|e| PluginManagerError::SignatureVerificationFailed(e.to_string()))
+        }
+        other => Err(PluginManagerError::SignatureMethodUnsupported(
+            other.to_string(),
+        )),
+    }
+}
+
 fn resolve_path(base: &Path, value: &str) -> PathBuf {
     let path = PathBuf::from(value);
     if path.is_absolute() {
@@ -543,6 +575,72 @@ mod tests {
         let _ = fs::remove_dir_all(root);
     }
 
+    #[test]
+    fn observe_only_install_rejects_mutating_capabilities() {
+        let root = temp_dir("observe-only");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).expect("mkdir");
+        let checksum = "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+        write_manifest(&root, checksum);
+        let manifest_path = root.join("odin.plugin.yaml");
+        let observe_only = fs::read_to_string(&manifest_path)
+            .expect("read manifest")
+            .replace(
+                "  runtime: external-process\n",
+                "  runtime: external-process\n  class: observe_only\n",
+            );
+        fs::write(&manifest_path, &observe_only).expect("write manifest");
+
+        let manager = FilesystemPluginManager::default();
+        let request = InstallRequest {
+            source: PluginSource::LocalPath(root.clone()),
+            expected_checksum_sha256: None,
+            require_signature: false,
+        };
+        assert!(manager.install(&request).is_ok());
+
+        fs::write(
+            &manifest_path,
+            observe_only.replace(
+                "      scope: [project]\n",
+                "      scope: [project]\n    - id: repo.write\n",
+            ),
+        )
+        .expect("write manifest");
+        let err = manager.install(&request).expect_err("mutating capability");
+        assert!(err.to_string().contains("repo.write"));
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn install_rejects_zero_max_concurrent_dispatches() {
+        let root = temp_dir("zero-concurrency");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).expect("mkdir");
+        let checksum = "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+        write_manifest(&root, checksum);
+        let manifest_path = root.join("odin.plugin.yaml");
+        let manifest = fs::read_to_string(&manifest_path).expect("read manifest");
+        fs::write(
+            &manifest_path,
+            manifest.replace(
+                "  runtime: external-process\n",
+                "  runtime: external-process\n  max_concurrent_dispatches: 0\n",
+            ),
+        )
+        .expect("write manifest");
+
+        let err = FilesystemPluginManager::default()
+            .install(&InstallRequest {
+                source: PluginSource::LocalPath(root.clone()),
+                expected_checksum_sha256: None,
+                require_signature: false,
+            })
+            .expect_err("zero limit");
+        assert!(err.to_string().contains("max_concurrent_dispatches"));
+        let _ = fs::remove_dir_all(root);
+    }
+
     #[test]
     fn local_install_rejects_mismatched_checksum() {
         let root = temp_dir("local-bad-checksum");
@@ -0,0 +1,255 @@
+//! JSON Schemas for well-known core event types, checked at runtime boundaries so a
+//! malformed envelope is caught before it reaches a plugin. Only the keywords the
+//! bundled schemas use are supported: `type`, `const`, `enum`, `required`,
+//! `properties`, `additionalProperties: false`, `minLength` and `items`.
+
+use std::sync::OnceLock;
+
+use serde_json::Value;
+
+use crate::EventEnvelope;
+
+const TASK_RECEIVED_SCHEMA: &str =
+    include_str!("../../../schemas/event.task-received.v1.schema.json");
+const ACTION_EXECUTED_SCHEMA: &str =
+    include_str!("../../../schemas/event.action-executed.v1.schema.json");
+const PLUGIN_PING_SCHEMA: &str = include_str!("../../../schemas/event.plugin-ping.v1.schema.json");
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SchemaViolation {
+    /// JSON pointer to the offending value (`""` for the envelope root).
+    pub path: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let path = if self.path.is_empty() {
+            "/"
+        } else {
+            &self.path
+        };
+        write!(f, "{path}: {}", self.message)
+    }
+}
+
+/// Returns the schema for `event_type`, or `None` if the type has no bundled schema.
+pub fn event_schema(event_type: &str) -> Option<&'static Value> {
+    static SCHEMAS: OnceLock<Vec<(&'static str, Value)>> = OnceLock::new();
+    let schemas = SCHEMAS.get_or_init(|| {
+        [
+            ("task.received", TASK_RECEIVED_SCHEMA),
+            ("action.executed", ACTION_EXECUTED_SCHEMA),
+            ("plugin.ping", PLUGIN_PING_SCHEMA),
+        ]
+        .into_iter()
+        .map(|(event_type, raw)| {
+            (
+                event_type,
+                serde_json::from_str(raw).expect("bundled event schema is valid JSON"),
+            )
+        })
+        .collect()
+    });
+    schemas
+        .iter()
+        .find(|(known, _)| *known == event_type)
+        .map(|(_, schema)| schema)
+}
+
+/// Validates `envelope` against its event type's schema. Types without a schema
+/// (e.g. plugin-emitted `plugin.*` events) always pass.
+pub fn validate_event(envelope: &EventEnvelope) -> Vec<SchemaViolation> {
+    let Some(schema) = event_schema(&envelope.event_type) else {
+        return Vec::new();
+    };
+    let value = serde_json::to_value(envelope).unwrap_or(Value::Null);
+    validate_value(schema, &value)
+}
+
+/// Validates an arbitrary document against `schema`, e.g. a policy file.
+pub fn validate_value(schema: &Value, value: &Value) -> Vec<SchemaViolation> {
+    let mut violations = Vec::new();
+    check(schema, value, "", &mut violations);
+    violations
+}
+
+fn check(schema: &Value, value: &Value, path: &str, violations: &mut Vec<SchemaViolation>) {
+    let mut fail = |message: String| {
+        violations.push(SchemaViolation {
+            path: path.to_string(),
+            message,
+        })
+    };
+
+    if let Some(expected) = schema.get("type") {
+        let allowed: Vec<&str> = match expected {
+            Value::String(name) => vec![name.as_str()],
+            Value::Array(names) => names.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !allowed.iter().any(|name| type_matches(name, value)) {
+            fail(format!("expected {}", allowed.join(" or ")));
+            return;
+        }
+    }
+    if let Some(expected) = schema.get("const") {
+        if value != expected {
+            fail(format!("expected {expected}"));
+        }
+    }
+    if let Some(Value::Array(options)) = schema.get("enum") {
+        if !options.contains(value) {
+            fail(format!(
+                "{value} is not one of {}",
+                Value::Array(options.clone())
+            ));
+        }
+    }
+    if let (Some(min), Value::String(text)) =
+        (schema.get("minLength").and_then(Value::as_u64), value)
+    {
+        if (text.chars().count() as u64) < min {
+            fail(format!("shorter than {min} characters"));
+        }
+    }
+
+    if let Value::Object(fields) = value {
+        if let Some(Value::Array(required)) = schema.get("required") {
+            for name in required.iter().filter_map(Value::as_str) {
+                if fields.get(name).is_none_or(Value::is_null) && !allows_null(schema, name) {
+                    fail(format!("missing required field {name}"));
+                }
+            }
+        }
+        let properties = schema.get("properties").and_then(Value::as_object);
+        // Null fields are absent optionals (or already reported as missing above).
+        for (name, field) in fields.iter().filter(|(_, field)| !field.is_null()) {
+            let field_path = format!("{path}/{name}");
+            match properties.and_then(|properties| properties.get(name)) {
+                Some(field_schema) => check(field_schema, field, &field_path, violations),
+                None if schema.get("additionalProperties") == Some(&Value::Bool(false)) => {
+                    violations.push(SchemaViolation {
+                        path: field_path,
+                        message: "unexpected field".to_string(),
+                    })
+                }
+                None => {}
+            }
+        }
+    }
+
+    if let (Some(items), Value::Array(elements)) = (schema.get("items"), value) {
+        for (idx, element) in elements.iter().enumerate() {
+            check(items, element, &format!("{path}/{idx}"), violations);
+        }
+    }
+}
+
+/// Serde writes `None` as `null`, so a required field only passes as null when its
+/// schema explicitly allows it.
+fn allows_null(schema: &Value, name: &str) -> bool {
+    let Some(field_type) = schema.pointer(&format!("/properties/{name}/type")) else {
+        return false;
+    };
+    match field_type {
+        Value::String(name) => name == "null",
+        Value::Array(names) => names.iter().any(|name| name == "null"),
+        _ => false,
+    }
+}
+
+fn type_matches(name: &str, value: &Value) -> bool {
+    match name {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "number" => value.is_number(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn task_received(payload: Value) -> EventEnvelope {
+        EventEnvelope {
+            event_id: "evt-1".to_string(),
+            event_type: "task.received".to_string(),
+            task_id: Some("task-1".to_string()),
+            request_id: None,
+            project: Some("demo".to_string()),
+            trace_id: None,
+            payload,
+        }
+    }
+
+    #[test]
+    fn well_formed_task_received_passes() {
+        let event = task_received(json!({
+            "task_type": "sentry_poll",
+            "source_key": null,
+            "trigger": "cron"
+        }));
+        assert!(validate_event(&event).is_empty());
+    }
+
+    #[test]
+    fn malformed_task_received_reports_paths() {
+        let mut event = task_received(json!({ "task_type": "", "trigger": 7 }));
+        event.project = None;
+
+        let violations = validate_event(&event);
+        let paths: Vec<&str> = violations.iter().map(|v| v.path.as_str()).collect();
+        assert_eq!(paths, vec!["", "/payload/task_type", "/payload/trigger"]);
+        assert_eq!(violations[0].message, "missing required field project");
+    }
+
+    #[test]
+    fn action_executed_status_must_be_known() {
+        let event = EventEnvelope {
+            event_id: "evt-2".to_string(),
+            event_type: "action.executed".to_string(),
+            task_id: None,
+            request_id: Some("req-1".to_string()),
+            project: Some("demo".to_string()),
+            trace_id: None,
+            payload: json!({
+                "plugin": "example.safe-github",
+                "capability": "repo.read",
+                "status": "exploded"
+            }),
+        };
+        let violations = validate_event(&event);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].path, "/payload/status");
+    }
+
+    #[test]
+    fn plugin_ping_requires_a_nonce_and_no_task() {
+        let mut event = task_received(json!({}));
+        event.event_type = "plugin.ping".to_string();
+
+        let violations = validate_event(&event);
+        let paths: Vec<&str> = violations.iter().map(|v| v.path.as_str()).collect();
+        assert_eq!(paths, vec!["/payload", "/project", "/task_id"]);
+
+        event.task_id = None;
+        event.project = None;
+        event.payload = json!({ "nonce": "n-1" });
+        assert!(validate_event(&event).is_empty());
+    }
+
+    #[test]
+    fn unknown_event_types_are_not_checked() {
+        let mut event = task_received(Value::Null);
+        event.event_type = "plugin.custom".to_string();
+        assert!(validate_event(&event).is_empty());
+    }
+}
@@ -1,5 +1,7 @@
 //! Shared protocol types for plugin manifests, policy requests, and runtime events.
 
+pub mod events;
+
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -35,6 +37,29 @@ pub struct SkillRecord {
     pub pinned_version: Option<String>,
     #[serde(default)]
     pub capabilities: Vec<DelegationCapability>,
+    /// Caution and untrusted records are flagged once this passes, until re-reviewed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub review_by_unix: Option<u64>,
+    /// Caution records are demoted to untrusted once this passes; untrusted ones are flagged
+    /// for removal.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at_unix: Option<u64>,
+    /// The detached signature verified when the skill was imported.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<SkillSignatureRecord>,
+}
+
+/// A verified detached signature over a skill directory's contents.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct SkillSignatureRecord {
+    /// `minisign` or `sigstore`.
+    pub method: String,
+    /// The minisign public key, or the cosign key file, the signature was checked against.
+    pub key: String,
+    /// The signed `sha256:<hex>` digest of the skill directory.
+    pub content_sha256: String,
+    pub verified_at_unix: u64,
 }
 
 impl SkillRecord {
@@ -45,6 +70,9 @@ impl SkillRecord {
             source: "local:unknown".to_string(),
             pinned_version: None,
             capabilities: Vec::new(),
+            review_by_unix: None,
+            expires_at_unix: None,
+            signature: None,
         }
     }
 }
@@ -103,8 +131,14 @@ pub struct ActionRequest {
     pub request_id: String,
     pub risk_tier: RiskTier,
     pub capability: CapabilityRequest,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_id: Option<String>,
     #[serde(default)]
     pub input: Value,
+    /// Identity that asked for the action, when a person did; an approver must differ from it
+    /// where separation of duties applies.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requested_by: Option<String>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -123,6 +157,40 @@ pub struct ActionOutcome {
     pub detail: String,
     #[serde(default)]
     pub output: Value,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<OutcomeWarning>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snapshot: Option<SnapshotRef>,
+}
+
+/// Workspace snapshot taken before a destructive action ran; the handle for restoring it.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SnapshotRef {
+    pub id: String,
+    pub snapshotter: String,
+    pub workspace: String,
+    pub location: String,
+    pub created_at_unix: u64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OutcomeWarningKind {
+    DeprecatedAlias,
+    Sunset,
+}
+
+/// Non-fatal signal attached to an outcome, e.g. a capability nearing removal.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OutcomeWarning {
+    pub kind: OutcomeWarningKind,
+    pub plugin: String,
+    pub capability: String,
+    #[serde(default)]
+    pub replacement: Option<String>,
+    #[serde(default)]
+    pub sunset: Option<String>,
+    pub message: String,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -132,6 +200,9 @@ pub struct EventEnvelope {
     pub task_id: Option<String>,
     pub request_id: Option<String>,
     pub project: Option<String>,
+    /// Correlates every event, request, follow-up task and audit record caused by one ingested task.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_id: Option<String>,
     #[serde(default)]
     pub payload: Value,
 }
@@ -149,12 +220,64 @@ pub struct PluginSpec {
     pub name: String,
     pub version: String,
     pub runtime: String,
+    /// `observe_only` plugins may only declare and use read capabilities.
+    #[serde(default)]
+    pub class: PluginClass,
     pub compatibility: CompatibilitySpec,
     pub entrypoint: EntrypointSpec,
     #[serde(default)]
     pub capabilities: Vec<CapabilitySpec>,
     #[serde(default)]
     pub hooks: Vec<HookSpec>,
+    /// Dispatches the runtime may have in flight at once; unset means unlimited.
+    #[serde(default)]
+    pub max_concurrent_dispatches: Option<u32>,
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PluginClass {
+    #[default]
+    Standard,
+    ObserveOnly,
+}
+
+/// Trailing capability segments that only observe state (`repo.read`, `browser.observe`).
+pub const OBSERVE_CAPABILITY_VERBS: &[&str] =
+    &["read", "observe", "list", "get", "query", "status", "watch"];
+
+/// Whether `capability` is read-only by naming convention.
+pub fn is_observe_capability(capability: &str) -> bool {
+    capability
+        .rsplit('.')
+        .next()
+        .is_some_and(|verb| OBSERVE_CAPABILITY_VERBS.contains(&verb))
+}
+
+/// A hierarchical project id (`org/team/project`) followed by its ancestors, nearest first.
+pub fn project_lineage(project: &str) -> Vec<String> {
+    let mut lineage = vec![project.to_string()];
+    let mut current = project;
+    while let Some((parent, _)) = current.rsplit_once('/') {
+        if parent.is_empty() {
+            break;
+        }
+        lineage.push(parent.to_string());
+        current = parent;
+    }
+    lineage
+}
+
+impl PluginManifest {
+    /// Declared capabilities that are not read-only.
+    pub fn mutating_capabilities(&self) -> Vec<&str> {
+        self.plugin
+            .capabilities
+            .iter()
+            .map(|capability| capability.id.as_str())
+            .filter(|id| !is_observe_capability(id))
+            .collect()
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -167,6 +290,31 @@ pub struct EntrypointSpec {
     pub command: String,
     #[serde(default)]
     pub args: Vec<String>,
+    /// Host environment variable names passed through to the plugin process.
+    #[serde(default)]
+    pub env: Vec<String>,
+    /// Secret handles exposed to the plugin process, as handle references or value files.
+    #[serde(default)]
+    pub secrets: Vec<SecretEnvSpec>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SecretEnvSpec {
+    pub env: String,
+    pub handle: String,
+    #[serde(default)]
+    pub delivery: SecretDelivery,
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SecretDelivery {
+    /// `env` holds the resolved handle reference, never the value.
+    #[default]
+    Handle,
+    /// The value is resolved at spawn time into a private file that lives as long as the process;
+    /// `env` holds the file's path.
+    File,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -246,6 +394,15 @@ mod tests {
         assert_eq!(decoded, decision);
     }
 
+    #[test]
+    fn project_lineage_walks_parents_nearest_first() {
+        assert_eq!(
+            project_lineage("acme/platform/api"),
+            vec!["acme/platform/api", "acme/platform", "acme"]
+        );
+        assert_eq!(project_lineage("demo"), vec!["demo"]);
+    }
+
     #[test]
     fn action_outcome_defaults_output() {
         let value = json!({
@@ -255,6 +412,11 @@ mod tests {
         });
         let decoded: ActionOutcome = serde_json::from_value(value).expect("decode");
         assert_eq!(decoded.output, json!(null));
+        assert!(decoded.warnings.is_empty());
+        assert!(serde_json::to_value(&decoded)
+            .expect("encode")
+            .get("warnings")
+            .is_none());
     }
 
     #[test]
@@ -373,6 +535,18 @@ mod tests {
         assert!(decoded.permissions.is_empty());
     }
 
+    #[test]
+    fn entrypoint_defaults_missing_env_and_secrets() {
+        let value = json!({
+            "command": "./bin/plugin",
+            "args": ["serve"]
+        });
+
+        let decoded: EntrypointSpec = serde_json::from_value(value).expect("decode");
+        assert!(decoded.env.is_empty());
+        assert!(decoded.secrets.is_empty());
+    }
+
     #[test]
     fn skill_registry_schema_allows_serde_defaulted_arrays() {
         let schema = load_schema("skill-registry.v1.schema.json");
@@ -0,0 +1,329 @@
+//! Break-glass overrides: an operator issues a short-lived token that turns a policy deny into
+//! an allow for one exact (plugin, project, capability). Only a digest of the token is stored,
+//! and every override expires on its own.
+
+use std::collections::hash_map::RandomState;
+use std::fs;
+use std::hash::{BuildHasher, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+
+use odin_plugin_protocol::ActionRequest;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{PolicyError, PolicyResult};
+
+/// Longest lifetime an override may be issued with.
+pub const MAX_BREAK_GLASS_TTL: Duration = Duration::from_secs(4 * 3_600);
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BreakGlass {
+    pub id: String,
+    /// Hex sha256 of the token handed to the operator.
+    pub token_sha256: String,
+    pub operator: String,
+    pub reason: String,
+    pub plugin: String,
+    pub project: String,
+    pub capability: String,
+    pub created_at_unix: u64,
+    pub expires_at_unix: u64,
+}
+
+impl BreakGlass {
+    pub fn is_expired(&self, now_unix: u64) -> bool {
+        now_unix >= self.expires_at_unix
+    }
+
+    pub fn covers(&self, request: &ActionRequest) -> bool {
+        let cap = &request.capability;
+        cap.plugin == self.plugin
+            && cap.project == self.project
+            && cap.capability == self.capability
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BreakGlassRequest {
+    /// Who is breaking the glass.
+    pub operator: String,
+    pub reason: String,
+    pub plugin: String,
+    pub project: String,
+    pub capability: String,
+    pub ttl: Duration,
+}
+
+fn token_digest(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+/// 128 bits from the standard library's randomly keyed hasher.
+fn new_token() -> String {
+    let half = || {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u128(
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_nanos())
+                .unwrap_or_default(),
+        );
+        hasher.finish()
+    };
+    format!("bg-{:016x}{:016x}", half(), half())
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StoreFile {
+    schema_version: u32,
+    #[serde(default)]
+    overrides: Vec<BreakGlass>,
+}
+
+/// Issued overrides, optionally persisted as JSON and re-read on every access like the
+/// elevation overlay.
+#[derive(Debug, Default)]
+pub struct BreakGlassStore {
+    path: Option<PathBuf>,
+    memory: Mutex<Vec<BreakGlass>>,
+}
+
+impl BreakGlassStore {
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    pub fn file(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: Some(path.into()),
+            memory: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Issues an override and returns it with its token. The token is not stored and cannot be
+    /// recovered later.
+    pub fn issue(
+        &self,
+        request: BreakGlassRequest,
+        now_unix: u64,
+    ) -> PolicyResult<(BreakGlass, String)> {
+        for (field, value) in [
+            ("operator", &request.operator),
+            ("reason", &request.reason),
+            ("plugin", &request.plugin),
+            ("project", &request.project),
+            ("capability", &request.capability),
+        ] {
+            if value.trim().is_empty() || value == "*" {
+                return Err(PolicyError::InvalidRequest(format!(
+                    "break-glass {field} must be given explicitly"
+                )));
+            }
+        }
+        if request.ttl.is_zero() || request.ttl > MAX_BREAK_GLASS_TTL {
+            return Err(PolicyError::InvalidRequest(format!(
+                "break-glass ttl must be between 1s and {}s",
+                MAX_BREAK_GLASS_TTL.as_secs()
+            )));
+        }
+
+        let token = new_token();
+        let entry = self.update(|overrides| {
+            let mut seq = overrides.len() + 1;
+            while overrides
+                .iter()
+                .any(|existing| existing.id == format!("bg-{now_unix}-{seq}"))
+            {
+                seq += 1;
+            }
+            let entry = BreakGlass {
+                id: format!("bg-{now_unix}-{seq}"),
+                token_sha256: token_digest(&token),
+                operator: request.operator,
+                reason: request.reason,
+                plugin: request.plugin,
+                project: request.project,
+                capability: request.capability,
+                created_at_unix: now_unix,
+                expires_at_unix: now_unix.saturating_add(request.ttl.as_secs()),
+            };
+            overrides.push(entry.clone());
+            entry
+        })?;
+        Ok((entry, token))
+    }
+
+    pub fn revoke(&self, id: &str) -> PolicyResult<Option<BreakGlass>> {
+        self.update(|overrides| {
+            let idx = overrides.iter().position(|entry| entry.id == id)?;
+            Some(overrides.remove(idx))
+        })
+    }
+
+    /// The unexpired override `token` was issued for, if it covers `request`.
+    pub fn find(
+        &self,
+        token: &str,
+        request: &ActionRequest,
+        now_unix: u64,
+    ) -> PolicyResult<Option<BreakGlass>> {
+        let digest = token_digest(token);
+        Ok(self
+            .active(now_unix)?
+            .into_iter()
+            .find(|entry| entry.token_sha256 == digest && entry.covers(request)))
+    }
+
+    pub fn active(&self, now_unix: u64) -> PolicyResult<Vec<BreakGlass>> {
+        let memory = self.lock()?;
+        Ok(self
+            .load(&memory)?
+            .into_iter()
+            .filter(|entry| !entry.is_expired(now_unix))
+            .collect())
+    }
+
+    /// Removes and returns expired overrides so callers can audit them.
+    pub fn take_expired(&self, now_unix: u64) -> PolicyResult<Vec<BreakGlass>> {
+        let mut memory = self.lock()?;
+        let (expired, kept): (Vec<_>, Vec<_>) = self
+            .load(&memory)?
+            .into_iter()
+            .partition(|entry| entry.is_expired(now_unix));
+        if !expired.is_empty() {
+            self.store(&mut memory, kept)?;
+        }
+        Ok(expired)
+    }
+
+    fn lock(&self) -> PolicyResult<MutexGuard<'_, Vec<BreakGlass>>> {
+        self.memory
+            .lock()
+            .map_err(|_| PolicyError::Evaluation("break-glass store lock poisoned".to_string()))
+    }
+
+    fn update<T>(&self, apply: impl FnOnce(&mut Vec<BreakGlass>) -> T) -> PolicyResult<T> {
+        let mut memory = self.lock()?;
+        let mut overrides = self.load(&memory)?;
+        let result = apply(&mut overrides);
+        self.store(&mut memory, overrides)?;
+        Ok(result)
+    }
+
+    fn load(&self, memory: &[BreakGlass]) -> PolicyResult<Vec<BreakGlass>> {
+        let Some(path) = &self.path else {
+            return Ok(memory.to_vec());
+        };
+        let raw = match fs::read(path) {
+            Ok(raw) => raw,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(store_io(path, e)),
+        };
+        let file: StoreFile = serde_json::from_slice(&raw).map_err(|e| {
+            PolicyError::Evaluation(format!(
+                "break-glass store {} is invalid: {e}",
+                path.display()
+            ))
+        })?;
+        if file.schema_version != 1 {
+            return Err(PolicyError::Evaluation(format!(
+                "unsupported break-glass store schema_version: {}",
+                file.schema_version
+            )));
+        }
+        Ok(file.overrides)
+    }
+
+    fn store(&self, memory: &mut Vec<BreakGlass>, overrides: Vec<BreakGlass>) -> PolicyResult<()> {
+        let Some(path) = &self.path else {
+            *memory = overrides;
+            return Ok(());
+        };
+        let body = serde_json::to_vec_pretty(&StoreFile {
+            schema_version: 1,
+            overrides,
+        })
+        .map_err(|e| PolicyError::Evaluation(format!("break-glass store encode failed: {e}")))?;
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(parent).map_err(|e| store_io(path, e))?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, body)
+            .and_then(|_| fs::rename(&tmp, path))
+            .map_err(|e| store_io(path, e))
+    }
+}
+
+fn store_io(path: &Path, err: std::io::Error) -> PolicyError {
+    PolicyError::Evaluation(format!("break-glass store {}: {err}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use odin_plugin_protocol::{CapabilityRequest, RiskTier};
+
+    use super::*;
+
+    fn issue_request(ttl_secs: u64) -> BreakGlassRequest {
+        BreakGlassRequest {
+            operator: "oncall@acme".to_string(),
+            reason: "incident 7".to_string(),
+            plugin: "example.safe-github".to_string(),
+            project: "demo".to_string(),
+            capability: "repo.write".to_string(),
+            ttl: Duration::from_secs(ttl_secs),
+        }
+    }
+
+    fn request(project: &str) -> ActionRequest {
+        ActionRequest {
+            request_id: "req-1".to_string(),
+            risk_tier: RiskTier::Safe,
+            capability: CapabilityRequest {
+                plugin: "example.safe-github".to_string(),
+                project: project.to_string(),
+                capability: "repo.write".to_string(),
+                scope: vec!["project".to_string()],
+                reason: "push fix".to_string(),
+            },
+            trace_id: None,
+            input: serde_json::Value::Null,
+            requested_by: None,
+        }
+    }
+
+    #[test]
+    fn tokens_cover_one_exact_target_until_they_expire() {
+        let store = BreakGlassStore::in_memory();
+        let (entry, token) = store.issue(issue_request(60), 100).expect("issue");
+        assert_ne!(entry.token_sha256, token);
+
+        assert!(store
+            .find(&token, &request("demo"), 120)
+            .expect("find")
+            .is_some());
+        assert!(store
+            .find(&token, &request("other"), 120)
+            .expect("find")
+            .is_none());
+        assert!(store
+            .find("bg-guess", &request("demo"), 120)
+            .expect("find")
+            .is_none());
+        assert!(store
+            .find(&token, &request("demo"), 160)
+            .expect("find")
+            .is_none());
+        assert_eq!(store.take_expired(160).expect("expired"), vec![entry]);
+
+        assert!(store.issue(issue_request(5 * 3_600), 100).is_err());
+        let mut wildcard = issue_request(60);
+        wildcard.project = "*".to_string();
+        assert!(store.issue(wildcard, 100).is_err());
+    }
+}
@@ -0,0 +1,365 @@
+//! Daily cost budgets layered over another engine, for metered capabilities such as LLM calls or
+//! paid API polling. Capabilities carry a cost weight and projects a daily budget; a request that
+//! would overrun its project's budget needs an approval. Cost is charged when the runtime reports
+//! an execution (`record_execution`), not when a request is decided. Spend can be persisted like
+//! quota counters.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+use odin_plugin_protocol::{project_lineage, ActionRequest, PolicyDecision};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    now_unix, ExpiredGrant, ExplainStep, PolicyEngine, PolicyError, PolicyExplanation, PolicyResult,
+};
+
+pub const BUDGET_EXCEEDED_REASON: &str = "budget_exceeded";
+
+const DAY_SECS: u64 = 86_400;
+
+/// Cost spent against one project's budget during the UTC day starting at `day_start_unix`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BudgetSpend {
+    pub project: String,
+    pub day_start_unix: u64,
+    pub spent: u64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SpendFile {
+    schema_version: u32,
+    #[serde(default)]
+    spend: Vec<BudgetSpend>,
+}
+
+/// Holds back requests the wrapped engine allows once they would overrun a budget. Capabilities
+/// without a weight are free, and projects without a budget (at their own level, an ancestor or `*`) are unmetered.
+pub struct BudgetPolicyEngine<P> {
+    inner: P,
+    weights: HashMap<String, u64>,
+    budgets: HashMap<String, u64>,
+    path: Option<PathBuf>,
+    memory: Mutex<Vec<BudgetSpend>>,
+}
+
+impl<P: PolicyEngine> BudgetPolicyEngine<P> {
+    pub fn new(inner: P) -> Self {
+        Self {
+            inner,
+            weights: HashMap::new(),
+            budgets: HashMap::new(),
+            path: None,
+            memory: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn with_cost(mut self, capability: impl Into<String>, weight: u64) -> Self {
+        self.weights.insert(capability.into(), weight);
+        self
+    }
+
+    /// Daily budget for `project`, shared with its child projects unless they have their own.
+    /// `*` sets the budget for every project without one.
+    pub fn with_daily_budget(mut self, project: impl Into<String>, budget: u64) -> Self {
+        self.budgets.insert(project.into(), budget);
+        self
+    }
+
+    /// Persists spend as JSON at `path`, re-read on every decision like the quota counters.
+    pub fn with_state_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// Spend recorded for the day containing `now_unix`.
+    pub fn spend(&self, now_unix: u64) -> PolicyResult<Vec<BudgetSpend>> {
+        let memory = self.lock()?;
+        let today = day_start(now_unix);
+        Ok(self
+            .load(&memory)?
+            .into_iter()
+            .filter(|spend| spend.day_start_unix == today)
+            .collect())
+    }
+
+    pub fn decide_at(
+        &self,
+        request: &ActionRequest,
+        now_unix: u64,
+    ) -> PolicyResult<PolicyDecision> {
+        let decision = self.inner.decide(request)?;
+        if !matches!(decision, PolicyDecision::Allow { .. }) {
+            return Ok(decision);
+        }
+        let Some((project, budget, cost)) = self.charge(request) else {
+            return Ok(decision);
+        };
+
+        let memory = self.lock()?;
+        let today = day_start(now_unix);
+        let mut spend = self.load(&memory)?;
+        spend.retain(|entry| entry.day_start_unix == today);
+        if spent(&spend, &project) + cost > budget {
+            return Ok(exceeded_decision(request));
+        }
+        Ok(decision)
+    }
+
+    /// Charges the cost of `request` to its project's budget for the day containing `now_unix`.
+    pub fn record_at(&self, request: &ActionRequest, now_unix: u64) -> PolicyResult<()> {
+        let Some((project, _, cost)) = self.charge(request) else {
+            return Ok(());
+        };
+
+        let mut memory = self.lock()?;
+        let today = day_start(now_unix);
+        let mut spend = self.load(&memory)?;
+        spend.retain(|entry| entry.day_start_unix == today);
+        match spend.iter_mut().find(|entry| entry.project == project) {
+            Some(entry) => entry.spent += cost,
+            None => spend.push(BudgetSpend {
+                project,
+                day_start_unix: today,
+                spent: cost,
+            }),
+        }
+        self.store(&mut memory, spend)
+    }
+
+    /// `explain` as of `now_unix`; reports the budget the request would spend against without
+    /// charging it.
+    pub fn explain_at(
+        &self,
+        request: &ActionRequest,
+        now_unix: u64,
+    ) -> PolicyResult<PolicyExplanation> {
+        let mut explanation = self.inner.explain(request)?;
+        if !matches!(explanation.decision, PolicyDecision::Allow { .. }) {
+            return Ok(explanation);
+        }
+        let Some((project, budget, cost)) = self.charge(request) else {
+            explanation.steps.push(ExplainStep::skipped(
+                "budget",
+                "capability is free or the project has no budget",
+            ));
+            return Ok(explanation);
+        };
+        let memory = self.lock()?;
+        let today = day_start(now_unix);
+        let spend: Vec<BudgetSpend> = self
+            .load(&memory)?
+            .into_iter()
+            .filter(|entry| entry.day_start_unix == today)
+            .collect();
+        let spent = spent(&spend, &project);
+        let name = format!("budget {project}");
+        let detail = format!("{spent} of {budget} spent today; this request costs {cost}");
+        if spent + cost > budget {
+            explanation.steps.push(ExplainStep::matched(name, detail));
+            explanation.decision = exceeded_decision(request);
+        } else {
+            explanation.steps.push(ExplainStep::skipped(name, detail));
+        }
+        Ok(explanation)
+    }
+
+    /// The project whose budget a request spends against, that budget, and the request's cost.
+    fn charge(&self, request: &ActionRequest) -> Option<(String, u64, u64)> {
+        let cost = *self
+            .weights
+            .get(&request.capability.capability)
+            .filter(|weight| **weight > 0)?;
+        let mut levels = project_lineage(&request.capability.project);
+        levels.push("*".to_string());
+        levels.into_iter().find_map(|project| {
+            self.budgets
+                .get(&project)
+                .map(|budget| (project, *budget, cost))
+        })
+    }
+
+    fn lock(&self) -> PolicyResult<MutexGuard<'_, Vec<BudgetSpend>>> {
+        self.memory
+            .lock()
+            .map_err(|_| PolicyError::Evaluation("budget spend lock poisoned".to_string()))
+    }
+
+    fn load(&self, memory: &[BudgetSpend]) -> PolicyResult<Vec<BudgetSpend>> {
+        let Some(path) = &self.path else {
+            return Ok(memory.to_vec());
+        };
+        match fs::read(path) {
+            Ok(raw) => serde_json::from_slice::<SpendFile>(&raw)
+                .map(|file| file.spend)
+                .map_err(|e| {
+                    PolicyError::Evaluation(format!("corrupt budget spend {}: {e}", path.display()))
+                }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(io_err("reading", path, e)),
+        }
+    }
+
+    fn store(&self, memory: &mut Vec<BudgetSpend>, spend: Vec<BudgetSpend>) -> PolicyResult<()> {
+        let Some(path) = &self.path else {
+            *memory = spend;
+            return Ok(());
+        };
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(parent).map_err(|e| io_err("creating dir for", parent, e))?;
+        }
+        let body = serde_json::to_vec_pretty(&SpendFile {
+            schema_version: 1,
+            spend,
+        })
+        .map_err(|e| PolicyError::Evaluation(format!("failed serializing budget spend: {e}")))?;
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, body)
+            .and_then(|_| fs::rename(&tmp, path))
+            .map_err(|e| io_err("writing", path, e))
+    }
+}
+
+fn day_start(now_unix: u64) -> u64 {
+    now_unix - now_unix % DAY_SECS
+}
+
+fn spent(spend: &[BudgetSpend], project: &str) -> u64 {
+    spend
+        .iter()
+        .find(|entry| entry.project == project)
+        .map_or(0, |entry| entry.spent)
+}
+
+fn exceeded_decision(request: &ActionRequest) -> PolicyDecision {
+    PolicyDecision::RequireApproval {
+        reason_code: BUDGET_EXCEEDED_REASON.to_string(),
+        tier: request.risk_tier.clone(),
+    }
+}
+
+fn io_err(action: &str, path: &Path, err: std::io::Error) -> PolicyError {
+    PolicyError::Evaluation(format!(
+        "failed {action} budget spend {}: {err}",
+        path.display()
+    ))
+}
+
+impl<P: PolicyEngine> PolicyEngine for BudgetPolicyEngine<P> {
+    fn decide(&self, request: &ActionRequest) -> PolicyResult<PolicyDecision> {
+        self.decide_at(request, now_unix())
+    }
+
+    fn version(&self) -> Option<String> {
+        self.inner.version()
+    }
+
+    fn explain(&self, request: &ActionRequest) -> PolicyResult<PolicyExplanation> {
+        self.explain_at(request, now_unix())
+    }
+
+    fn take_expired_grants(&self, now_unix: u64) -> Vec<ExpiredGrant> {
+        self.inner.take_expired_grants(now_unix)
+    }
+
+    fn record_execution(&self, request: &ActionRequest) -> PolicyResult<()> {
+        self.inner.record_execution(request)?;
+        self.record_at(request, now_unix())
+    }
+
+    fn reserve_execution(&self, request: &ActionRequest) -> PolicyResult<Option<PolicyDecision>> {
+        self.inner.reserve_execution(request)
+    }
+
+    fn release_execution(&self, request: &ActionRequest) -> PolicyResult<()> {
+        self.inner.release_execution(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use odin_plugin_protocol::{CapabilityRequest, RiskTier};
+
+    use super::*;
+    use crate::StaticPolicyEngine;
+
+    fn request(project: &str, capability: &str) -> ActionRequest {
+        ActionRequest {
+            request_id: "req-1".to_string(),
+            risk_tier: RiskTier::Safe,
+            capability: CapabilityRequest {
+                plugin: "example.llm".to_string(),
+                project: project.to_string(),
+                capability: capability.to_string(),
+                scope: vec!["project".to_string()],
+                reason: "test".to_string(),
+            },
+            trace_id: None,
+            input: serde_json::Value::Null,
+            requested_by: None,
+        }
+    }
+
+    fn engine() -> BudgetPolicyEngine<StaticPolicyEngine> {
+        let mut inner = StaticPolicyEngine::default();
+        inner.allow_capability("example.llm", "*", "llm.complete");
+        inner.allow_capability("example.llm", "*", "llm.models");
+        BudgetPolicyEngine::new(inner)
+            .with_cost("llm.complete", 4)
+            .with_daily_budget("acme", 10)
+    }
+
+    #[test]
+    fn over_budget_requests_need_approval_until_the_next_day() {
+        let engine = engine();
+        let now = 3 * DAY_SECS + 60;
+        for _ in 0..2 {
+            assert!(matches!(
+                engine
+                    .decide_at(&request("acme/api", "llm.complete"), now)
+                    .expect("decision"),
+                PolicyDecision::Allow { .. }
+            ));
+            engine
+                .record_at(&request("acme/api", "llm.complete"), now)
+                .expect("record");
+        }
+        // The child project spends against the parent's budget: 8 of 10 spent, 4 more overruns.
+        assert_eq!(
+            engine
+                .decide_at(&request("acme/web", "llm.complete"), now)
+                .expect("decision"),
+            PolicyDecision::RequireApproval {
+                reason_code: BUDGET_EXCEEDED_REASON.to_string(),
+                tier: RiskTier::Safe,
+            }
+        );
+        assert_eq!(engine.spend(now).expect("spend")[0].spent, 8);
+
+        // Free capabilities and unbudgeted projects are never held back.
+        assert!(matches!(
+            engine
+                .decide_at(&request("acme/api", "llm.models"), now)
+                .expect("decision"),
+            PolicyDecision::Allow { .. }
+        ));
+        assert!(matches!(
+            engine
+                .decide_at(&request("other", "llm.complete"), now)
+                .expect("decision"),
+            PolicyDecision::Allow { .. }
+        ));
+
+        assert!(matches!(
+            engine
+                .decide_at(&request("acme/web", "llm.complete"), now + DAY_SECS)
+                .expect("decision"),
+            PolicyDecision::Allow { .. }
+        ));
+    }
+}
@@ -0,0 +1,506 @@
+//! A CEL subset for grant conditions, e.g.
+//! `input.url.endsWith('.example.com') && request.risk_tier == 'safe'`.
+//!
+//! Supported: string, number, bool, null and list literals; `input` (the request input) and
+//! `request` (the whole `ActionRequest`) with field and index access; `!`, `&&`, `||`, `==`, `!=`,
+//! `<`, `<=`, `>`, `>=` and `in`; the `startsWith`, `endsWith` and `contains` methods and `size()`.
+//! Expressions are compiled when a policy is loaded, so syntax errors and unknown identifiers or
+//! functions are rejected up front.
+
+use odin_plugin_protocol::ActionRequest;
+use serde_json::Value;
+
+use crate::{PolicyError, PolicyResult};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CelExpression {
+    source: String,
+    expr: Expr,
+}
+
+impl CelExpression {
+    pub fn compile(source: &str) -> PolicyResult<Self> {
+        let invalid = |message: String| {
+            PolicyError::InvalidDocument(format!("condition `{source}`: {message}"))
+        };
+        let tokens = tokenize(source).map_err(invalid)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let expr = parser.expr().map_err(invalid)?;
+        if let Some(token) = parser.tokens.get(parser.pos) {
+            return Err(invalid(format!("unexpected {token:?}")));
+        }
+        check(&expr).map_err(invalid)?;
+        Ok(Self {
+            source: source.to_string(),
+            expr,
+        })
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Evaluates the condition for `request`. Missing fields and type mismatches are errors,
+    /// which callers treat as the condition not holding.
+    pub fn evaluate(&self, request: &ActionRequest) -> PolicyResult<bool> {
+        let env = Env {
+            request: serde_json::to_value(request)
+                .map_err(|e| PolicyError::InvalidRequest(format!("unserializable request: {e}")))?,
+        };
+        match eval(&self.expr, &env) {
+            Ok(Value::Bool(result)) => Ok(result),
+            Ok(other) => Err(format!("condition produced {other}, not a boolean")),
+            Err(err) => Err(err),
+        }
+        .map_err(|e| PolicyError::Evaluation(format!("condition `{}`: {e}", self.source)))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Num(f64),
+    Op(&'static str),
+}
+
+const OPERATORS: [&str; 16] = [
+    "==", "!=", "<=", ">=", "&&", "||", "<", ">", "!", "(", ")", "[", "]", ",", ".", "-",
+];
+
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut idx = 0;
+    while idx < chars.len() {
+        let c = chars[idx];
+        if c.is_whitespace() {
+            idx += 1;
+        } else if c == '\'' || c == '"' {
+            let mut text = String::new();
+            idx += 1;
+            loop {
+                match chars.get(idx) {
+                    None => return Err("unterminated string".to_string()),
+                    Some(&'\\') => {
+                        text.push(*chars.get(idx + 1).ok_or("unterminated string")?);
+                        idx += 2;
+                    }
+                    Some(&q) if q == c => {
+                        idx += 1;
+                        break;
+                    }
+                    Some(&other) => {
+                        text.push(other);
+                        idx += 1;
+                    }
+                }
+            }
+            tokens.push(Token::Str(text));
+        } else if c.is_ascii_digit() {
+            let start = idx;
+            while idx < chars.len()
+                && (chars[idx].is_ascii_digit()
+                    || (chars[idx] == '.' && chars.get(idx + 1).is_some_and(char::is_ascii_digit)))
+            {
+                idx += 1;
+            }
+            let text: String = chars[start..idx].iter().collect();
+            let number = text.parse().map_err(|_| format!("invalid number {text}"))?;
+            tokens.push(Token::Num(number));
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let start = idx;
+            while idx < chars.len() && (chars[idx].is_ascii_alphanumeric() || chars[idx] == '_') {
+                idx += 1;
+            }
+            tokens.push(Token::Ident(chars[start..idx].iter().collect()));
+        } else {
+            let rest: String = chars[idx..chars.len().min(idx + 2)].iter().collect();
+            let op = OPERATORS
+                .iter()
+                .find(|op| rest.starts_with(**op))
+                .ok_or_else(|| format!("unexpected character {c:?}"))?;
+            tokens.push(Token::Op(op));
+            idx += op.len();
+        }
+    }
+    Ok(tokens)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Expr {
+    Literal(Value),
+    Ident(String),
+    Member(Box<Expr>, String),
+    Index(Box<Expr>, Box<Expr>),
+    Call {
+        target: Option<Box<Expr>>,
+        name: String,
+        args: Vec<Expr>,
+    },
+    List(Vec<Expr>),
+    Not(Box<Expr>),
+    Binary(&'static str, Box<Expr>, Box<Expr>),
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek_op(&self, op: &str) -> bool {
+        matches!(self.tokens.get(self.pos), Some(Token::Op(found)) if *found == op)
+    }
+
+    fn peek_ident(&self, name: &str) -> bool {
+        matches!(self.tokens.get(self.pos), Some(Token::Ident(found)) if found == name)
+    }
+
+    fn expect_op(&mut self, op: &str) -> Result<(), String> {
+        if self.peek_op(op) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(format!("expected `{op}`"))
+        }
+    }
+
+    fn expr(&mut self) -> Result<Expr, String> {
+        let mut lhs = self.and()?;
+        while self.peek_op("||") {
+            self.pos += 1;
+            lhs = Expr::Binary("||", Box::new(lhs), Box::new(self.and()?));
+        }
+        Ok(lhs)
+    }
+
+    fn and(&mut self) -> Result<Expr, String> {
+        let mut lhs = self.relation()?;
+        while self.peek_op("&&") {
+            self.pos += 1;
+            lhs = Expr::Binary("&&", Box::new(lhs), Box::new(self.relation()?));
+        }
+        Ok(lhs)
+    }
+
+    fn relation(&mut self) -> Result<Expr, String> {
+        let lhs = self.unary()?;
+        for op in ["==", "!=", "<=", ">=", "<", ">"] {
+            if self.peek_op(op) {
+                self.pos += 1;
+                return Ok(Expr::Binary(op, Box::new(lhs), Box::new(self.unary()?)));
+            }
+        }
+        if self.peek_ident("in") {
+            self.pos += 1;
+            return Ok(Expr::Binary("in", Box::new(lhs), Box::new(self.unary()?)));
+        }
+        Ok(lhs)
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        if self.peek_op("!") {
+            self.pos += 1;
+            return Ok(Expr::Not(Box::new(self.unary()?)));
+        }
+        if self.peek_op("-") {
+            self.pos += 1;
+            return match self.tokens.get(self.pos) {
+                Some(Token::Num(number)) => {
+                    self.pos += 1;
+                    Ok(Expr::Literal(Value::from(-number)))
+                }
+                _ => Err("`-` is only supported before a number".to_string()),
+            };
+        }
+        self.postfix()
+    }
+
+    fn postfix(&mut self) -> Result<Expr, String> {
+        let mut expr = self.primary()?;
+        loop {
+            if self.peek_op(".") {
+                self.pos += 1;
+                let Some(Token::Ident(name)) = self.tokens.get(self.pos).cloned() else {
+                    return Err("expected a field or method name after `.`".to_string());
+                };
+                self.pos += 1;
+                expr = if self.peek_op("(") {
+                    Expr::Call {
+                        target: Some(Box::new(expr)),
+                        name,
+                        args: self.args()?,
+                    }
+                } else {
+                    Expr::Member(Box::new(expr), name)
+                };
+            } else if self.peek_op("[") {
+                self.pos += 1;
+                let index = self.expr()?;
+                self.expect_op("]")?;
+                expr = Expr::Index(Box::new(expr), Box::new(index));
+            } else {
+                return Ok(expr);
+            }
+        }
+    }
+
+    fn args(&mut self) -> Result<Vec<Expr>, String> {
+        self.expect_op("(")?;
+        self.list_items(")")
+    }
+
+    fn list_items(&mut self, close: &str) -> Result<Vec<Expr>, String> {
+        let mut items = Vec::new();
+        if self.peek_op(close) {
+            self.pos += 1;
+            return Ok(items);
+        }
+        loop {
+            items.push(self.expr()?);
+            if self.peek_op(",") {
+                self.pos += 1;
+            } else {
+                self.expect_op(close)?;
+                return Ok(items);
+            }
+        }
+    }
+
+    fn primary(&mut self) -> Result<Expr, String> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .cloned()
+            .ok_or("unexpected end of expression")?;
+        self.pos += 1;
+        match token {
+            Token::Str(text) => Ok(Expr::Literal(Value::String(text))),
+            Token::Num(number) => Ok(Expr::Literal(Value::from(number))),
+            Token::Ident(name) => match name.as_str() {
+                "true" => Ok(Expr::Literal(Value::Bool(true))),
+                "false" => Ok(Expr::Literal(Value::Bool(false))),
+                "null" => Ok(Expr::Literal(Value::Null)),
+                _ if self.peek_op("(") => Ok(Expr::Call {
+                    target: None,
+                    name,
+                    args: self.args()?,
+                }),
+                _ => Ok(Expr::Ident(name)),
+            },
+            Token::Op("(") => {
+                let inner = self.expr()?;
+                self.expect_op(")")?;
+                Ok(inner)
+            }
+            Token::Op("[") => Ok(Expr::List(self.list_items("]")?)),
+            Token::Op(op) => Err(format!("unexpected `{op}`")),
+        }
+    }
+}
+
+/// Rejects identifiers and functions that evaluation would not know.
+fn check(expr: &Expr) -> Result<(), String> {
+    match expr {
+        Expr::Literal(_) => Ok(()),
+        Expr::Ident(name) if name == "input" || name == "request" => Ok(()),
+        Expr::Ident(name) => Err(format!(
+            "unknown identifier `{name}` (expected input or request)"
+        )),
+        Expr::Member(target, _) | Expr::Not(target) => check(target),
+        Expr::Index(target, index) | Expr::Binary(_, target, index) => {
+            check(target)?;
+            check(index)
+        }
+        Expr::List(items) => items.iter().try_for_each(check),
+        Expr::Call { target, name, args } => {
+            let arity = match (name.as_str(), target.is_some()) {
+                ("startsWith" | "endsWith" | "contains", true) => 1,
+                ("size", true) => 0,
+                ("size", false) => 1,
+                _ => return Err(format!("unknown function `{name}`")),
+            };
+            if args.len() != arity {
+                return Err(format!("`{name}` takes {arity} argument(s)"));
+            }
+            if let Some(target) = target {
+                check(target)?;
+            }
+            args.iter().try_for_each(check)
+        }
+    }
+}
+
+struct Env {
+    request: Value,
+}
+
+fn eval(expr: &Expr, env: &Env) -> Result<Value, String> {
+    match expr {
+        Expr::Literal(value) => Ok(value.clone()),
+        Expr::Ident(name) if name == "input" => Ok(env.request["input"].clone()),
+        Expr::Ident(_) => Ok(env.request.clone()),
+        Expr::Member(target, field) => eval(target, env)?
+            .get(field)
+            .cloned()
+            .ok_or_else(|| format!("no field `{field}`")),
+        Expr::Index(target, index) => {
+            let target = eval(target, env)?;
+            let found = match eval(index, env)? {
+                Value::String(key) => target.get(&key).cloned(),
+                Value::Number(n) => n.as_f64().and_then(|n| target.get(n as usize).cloned()),
+                other => return Err(format!("cannot index with {other}")),
+            };
+            found.ok_or_else(|| "index out of range".to_string())
+        }
+        Expr::List(items) => Ok(Value::Array(
+            items
+                .iter()
+                .map(|item| eval(item, env))
+                .collect::<Result<_, _>>()?,
+        )),
+        Expr::Not(inner) => Ok(Value::Bool(!as_bool(&eval(inner, env)?)?)),
+        Expr::Binary("&&", lhs, rhs) => Ok(Value::Bool(
+            as_bool(&eval(lhs, env)?)? && as_bool(&eval(rhs, env)?)?,
+        )),
+        Expr::Binary("||", lhs, rhs) => Ok(Value::Bool(
+            as_bool(&eval(lhs, env)?)? || as_bool(&eval(rhs, env)?)?,
+        )),
+        Expr::Binary(op, lhs, rhs) => {
+            let (lhs, rhs) = (eval(lhs, env)?, eval(rhs, env)?);
+            let result = match *op {
+                "==" => equal(&lhs, &rhs),
+                "!=" => !equal(&lhs, &rhs),
+                "in" => match &rhs {
+                    Value::Array(items) => items.iter().any(|item| equal(item, &lhs)),
+                    Value::Object(fields) => lhs.as_str().is_some_and(|k| fields.contains_key(k)),
+                    other => return Err(format!("`in` needs a list or map, got {other}")),
+                },
+                _ => {
+                    let ordering = match (&lhs, &rhs) {
+                        (Value::Number(a), Value::Number(b)) => a
+                            .as_f64()
+                            .zip(b.as_f64())
+                            .and_then(|(a, b)| a.partial_cmp(&b)),
+                        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+                        _ => None,
+                    }
+                    .ok_or_else(|| format!("cannot compare {lhs} and {rhs}"))?;
+                    match *op {
+                        "<" => ordering.is_lt(),
+                        "<=" => ordering.is_le(),
+                        ">" => ordering.is_gt(),
+                        _ => ordering.is_ge(),
+                    }
+                }
+            };
+            Ok(Value::Bool(result))
+        }
+        Expr::Call { target, name, args } => {
+            let subject = match target {
+                Some(target) => eval(target, env)?,
+                None => eval(&args[0], env)?,
+            };
+            if name == "size" {
+                let size = match &subject {
+                    Value::String(text) => text.chars().count(),
+                    Value::Array(items) => items.len(),
+                    Value::Object(fields) => fields.len(),
+                    other => return Err(format!("size() of {other}")),
+                };
+                return Ok(Value::from(size));
+            }
+            let argument = eval(&args[0], env)?;
+            let result = match (&subject, &argument, name.as_str()) {
+                (Value::String(text), Value::String(arg), "startsWith") => text.starts_with(arg),
+                (Value::String(text), Value::String(arg), "endsWith") => text.ends_with(arg),
+                (Value::String(text), Value::String(arg), "contains") => {
+                    text.contains(arg.as_str())
+                }
+                (Value::Array(items), arg, "contains") => items.iter().any(|item| equal(item, arg)),
+                _ => return Err(format!("{name}() not defined for {subject} and {argument}")),
+            };
+            Ok(Value::Bool(result))
+        }
+    }
+}
+
+fn as_bool(value: &Value) -> Result<bool, String> {
+    value
+        .as_bool()
+        .ok_or_else(|| format!("expected a boolean, got {value}"))
+}
+
+/// JSON equality, except numbers compare by value (`1 == 1.0`).
+fn equal(lhs: &Value, rhs: &Value) -> bool {
+    match (lhs, rhs) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64() == b.as_f64(),
+        _ => lhs == rhs,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use odin_plugin_protocol::{CapabilityRequest, RiskTier};
+    use serde_json::json;
+
+    use super::*;
+
+    fn request(input: Value) -> ActionRequest {
+        ActionRequest {
+            request_id: "req-1".to_string(),
+            risk_tier: RiskTier::Safe,
+            capability: CapabilityRequest {
+                plugin: "example.http".to_string(),
+                project: "demo".to_string(),
+                capability: "http.get".to_string(),
+                scope: vec!["project".to_string()],
+                reason: "test".to_string(),
+            },
+            trace_id: None,
+            input,
+            requested_by: None,
+        }
+    }
+
+    #[test]
+    fn conditions_evaluate_against_input_and_request() {
+        let condition = CelExpression::compile(
+            "input.url.endsWith('.example.com') && request.risk_tier == 'safe'",
+        )
+        .expect("compile");
+        assert!(condition
+            .evaluate(&request(json!({"url": "https://api.example.com"})))
+            .expect("eval"));
+        assert!(!condition
+            .evaluate(&request(json!({"url": "https://api.other.io"})))
+            .expect("eval"));
+        assert!(condition.evaluate(&request(json!({}))).is_err());
+
+        let condition = CelExpression::compile(
+            "!(input.repo in ['infra', 'secrets']) && size(input.labels) <= 2 && input.labels[0] == \"bug\"",
+        )
+        .expect("compile");
+        assert!(condition
+            .evaluate(&request(json!({"repo": "app", "labels": ["bug"]})))
+            .expect("eval"));
+        assert!(!condition
+            .evaluate(&request(json!({"repo": "infra", "labels": ["bug"]})))
+            .expect("eval"));
+    }
+
+    #[test]
+    fn compile_rejects_syntax_errors_and_unknown_names() {
+        for source in [
+            "input.url.endsWith(",
+            "input.url == 'x' &&",
+            "env.HOME == 'x'",
+            "input.url.matches('.*')",
+            "input.url.startsWith()",
+        ] {
+            let err = CelExpression::compile(source).expect_err(source);
+            assert!(matches!(err, PolicyError::InvalidDocument(_)), "{source}");
+        }
+    }
+}
@@ -0,0 +1,299 @@
+//! Layers several policy engines, e.g. a global deny-list, org defaults and project overrides.
+//! An engine answering `capability_not_granted` has no grant for the request and abstains; the
+//! combinator only looks at the engines that took a position.
+
+use odin_plugin_protocol::{ActionRequest, PolicyDecision};
+
+use crate::denylist::DenyListPolicyEngine;
+use crate::{
+    describe_decision, ExpiredGrant, ExplainStep, PolicyEngine, PolicyExplanation, PolicyResult,
+};
+
+/// Reason code engines use when no grant covers a request.
+pub const NOT_GRANTED_REASON: &str = "capability_not_granted";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CompositeMode {
+    /// Engines are asked in order and the first that does not abstain decides.
+    #[default]
+    FirstMatch,
+    /// Every engine is asked; a deny beats an approval requirement, which beats an allow.
+    MostRestrictive,
+}
+
+fn abstains(decision: &PolicyDecision) -> bool {
+    matches!(decision, PolicyDecision::Deny { reason_code } if reason_code == NOT_GRANTED_REASON)
+}
+
+pub(crate) fn restrictiveness(decision: &PolicyDecision) -> u8 {
+    match decision {
+        PolicyDecision::Allow { .. } => 0,
+        PolicyDecision::RequireApproval { .. } => 1,
+        PolicyDecision::Deny { .. } => 2,
+    }
+}
+
+/// Evaluates its engines in the order they were added. Errors from any consulted engine fail the
+/// decision, and a request no engine grants is denied with `capability_not_granted`.
+#[derive(Default)]
+pub struct CompositePolicyEngine {
+    mode: CompositeMode,
+    deny_lists: Vec<DenyListPolicyEngine>,
+    engines: Vec<Box<dyn PolicyEngine>>,
+}
+
+impl CompositePolicyEngine {
+    pub fn new(mode: CompositeMode) -> Self {
+        Self {
+            mode,
+            deny_lists: Vec::new(),
+            engines: Vec::new(),
+        }
+    }
+
+    pub fn with_engine(mut self, engine: impl PolicyEngine + 'static) -> Self {
+        self.engines.push(Box::new(engine));
+        self
+    }
+
+    /// Checked before every engine, whatever the mode or order, so a match always wins.
+    pub fn with_deny_list(mut self, deny_list: DenyListPolicyEngine) -> Self {
+        self.deny_lists.push(deny_list);
+        self
+    }
+
+    pub fn mode(&self) -> CompositeMode {
+        self.mode
+    }
+
+    pub fn len(&self) -> usize {
+        self.engines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.engines.is_empty()
+    }
+}
+
+impl PolicyEngine for CompositePolicyEngine {
+    fn decide(&self, request: &ActionRequest) -> PolicyResult<PolicyDecision> {
+        if let Some(blocked) = self
+            .deny_lists
+            .iter()
+            .find_map(|deny_list| deny_list.blocked(request))
+        {
+            return Ok(blocked);
+        }
+        let mut decided: Option<PolicyDecision> = None;
+        for engine in &self.engines {
+            let decision = engine.decide(request)?;
+            if abstains(&decision) {
+                continue;
+            }
+            match self.mode {
+                CompositeMode::FirstMatch => return Ok(decision),
+                CompositeMode::MostRestrictive => {
+                    if decided
+                        .as_ref()
+                        .is_none_or(|current| restrictiveness(&decision) > restrictiveness(current))
+                    {
+                        decided = Some(decision);
+                    }
+                }
+            }
+        }
+        Ok(decided.unwrap_or_else(|| PolicyDecision::Deny {
+            reason_code: NOT_GRANTED_REASON.to_string(),
+        }))
+    }
+
+    /// Each consulted engine's steps, prefixed with its position and followed by whether it took
+    /// a position.
+    fn explain(&self, request: &ActionRequest) -> PolicyResult<PolicyExplanation> {
+        let decision = self.decide(request)?;
+        let mut steps = Vec::new();
+        for (index, deny_list) in self.deny_lists.iter().enumerate() {
+            let explanation = deny_list.explain(request)?;
+            steps.extend(explanation.steps.into_iter().map(|step| ExplainStep {
+                rule: format!("deny_lists[{index}] {}", step.rule),
+                ..step
+            }));
+            if deny_list.blocked(request).is_some() {
+                return Ok(PolicyExplanation { decision, steps });
+            }
+        }
+        for (index, engine) in self.engines.iter().enumerate() {
+            let explanation = engine.explain(request)?;
+            let prefix = format!("engines[{index}]");
+            steps.extend(explanation.steps.into_iter().map(|step| ExplainStep {
+                rule: format!("{prefix} {}", step.rule),
+                ..step
+            }));
+            if abstains(&explanation.decision) {
+                steps.push(ExplainStep::skipped(
+                    prefix,
+                    "abstained: no grant covers the request",
+                ));
+                continue;
+            }
+            steps.push(ExplainStep::matched(
+                prefix,
+                format!("decided {}", describe_decision(&explanation.decision)),
+            ));
+            if self.mode == CompositeMode::FirstMatch {
+                break;
+            }
+        }
+        Ok(PolicyExplanation { decision, steps })
+    }
+
+    fn take_expired_grants(&self, now_unix: u64) -> Vec<ExpiredGrant> {
+        self.engines
+            .iter()
+            .flat_map(|engine| engine.take_expired_grants(now_unix))
+            .collect()
+    }
+
+    fn record_execution(&self, request: &ActionRequest) -> PolicyResult<()> {
+        self.engines
+            .iter()
+            .try_for_each(|engine| engine.record_execution(request))
+    }
+
+    fn reserve_execution(&self, request: &ActionRequest) -> PolicyResult<Option<PolicyDecision>> {
+        for (index, engine) in self.engines.iter().enumerate() {
+            if let Some(decision) = engine.reserve_execution(request)? {
+                for held in &self.engines[..index] {
+                    held.release_execution(request)?;
+                }
+                return Ok(Some(decision));
+            }
+        }
+        Ok(None)
+    }
+
+    fn release_execution(&self, request: &ActionRequest) -> PolicyResult<()> {
+        self.engines
+            .iter()
+            .try_for_each(|engine| engine.release_execution(request))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use odin_plugin_protocol::{CapabilityRequest, RiskTier};
+
+    use super::*;
+    use crate::StaticPolicyEngine;
+
+    fn request(capability: &str) -> ActionRequest {
+        ActionRequest {
+            request_id: "req-1".to_string(),
+            risk_tier: RiskTier::Destructive,
+            capability: CapabilityRequest {
+                plugin: "example.safe-github".to_string(),
+                project: "acme/api".to_string(),
+                capability: capability.to_string(),
+                scope: vec!["project".to_string()],
+                reason: "test".to_string(),
+            },
+            trace_id: None,
+            input: serde_json::Value::Null,
+            requested_by: None,
+        }
+    }
+
+    fn layers(mode: CompositeMode) -> CompositePolicyEngine {
+        let mut deny_list = StaticPolicyEngine::default();
+        deny_list.deny_capability("example.safe-github", "*", "repo.delete");
+        let mut org = StaticPolicyEngine::default();
+        org.allow_capability("example.safe-github", "acme", "repo.read");
+        org.allow_capability("example.safe-github", "acme", "repo.delete");
+        let mut project = StaticPolicyEngine {
+            require_approval_for_destructive: true,
+            ..StaticPolicyEngine::default()
+        };
+        project.allow_capability("example.safe-github", "acme/api", "repo.read");
+        CompositePolicyEngine::new(mode)
+            .with_engine(deny_list)
+            .with_engine(org)
+            .with_engine(project)
+    }
+
+    #[test]
+    fn first_match_skips_abstaining_engines() {
+        let engine = layers(CompositeMode::FirstMatch);
+        assert_eq!(
+            engine.decide(&request("repo.delete")).expect("decision"),
+            PolicyDecision::Deny {
+                reason_code: "capability_denied".to_string()
+            }
+        );
+        // The org layer answers before the project layer's approval requirement.
+        assert!(matches!(
+            engine.decide(&request("repo.read")).expect("decision"),
+            PolicyDecision::Allow { .. }
+        ));
+        assert_eq!(
+            engine.decide(&request("repo.write")).expect("decision"),
+            PolicyDecision::Deny {
+                reason_code: NOT_GRANTED_REASON.to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn most_restrictive_takes_the_strictest_position() {
+        let engine = layers(CompositeMode::MostRestrictive);
+        assert!(matches!(
+            engine.decide(&request("repo.read")).expect("decision"),
+            PolicyDecision::RequireApproval { .. }
+        ));
+        assert!(matches!(
+            engine.decide(&request("repo.delete")).expect("decision"),
+            PolicyDecision::Deny { .. }
+        ));
+    }
+
+    #[test]
+    fn deny_lists_win_over_earlier_allows() {
+        use crate::conditions::InputMatcher;
+        use crate::denylist::{DenyListPolicyEngine, DenyRule};
+
+        let deny_list = DenyListPolicyEngine::new()
+            .with_rule(DenyRule::new("*", "repo.read").with_reason_code("incident_42"))
+            .with_rule(
+                DenyRule::new("example.*", "repo.write").with_input(InputMatcher {
+                    field: "/branch".to_string(),
+                    equals: Some(serde_json::json!("main")),
+                    ..InputMatcher::default()
+                }),
+            );
+        let mut org = StaticPolicyEngine::default();
+        org.allow_capability("example.safe-github", "acme", "repo.read");
+        org.allow_capability("example.safe-github", "acme", "repo.write");
+        let engine = CompositePolicyEngine::new(CompositeMode::FirstMatch)
+            .with_engine(org)
+            .with_deny_list(deny_list);
+
+        assert_eq!(
+            engine.decide(&request("repo.read")).expect("decision"),
+            PolicyDecision::Deny {
+                reason_code: "incident_42".to_string()
+            }
+        );
+        let mut write = request("repo.write");
+        write.input = serde_json::json!({"branch": "feature"});
+        assert!(matches!(
+            engine.decide(&write).expect("decision"),
+            PolicyDecision::Allow { .. }
+        ));
+        write.input = serde_json::json!({"branch": "main"});
+        assert_eq!(
+            engine.decide(&write).expect("decision"),
+            PolicyDecision::Deny {
+                reason_code: crate::denylist::DENY_LISTED_REASON.to_string()
+            }
+        );
+    }
+}
@@ -0,0 +1,213 @@
+//! Conditions that let a policy rule look past the (plugin, project, capability) triple: values in
+//! the request input, requested scopes and UTC time-of-day windows.
+
+use odin_plugin_protocol::ActionRequest;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{PolicyError, PolicyResult};
+
+const WEEKDAYS: [&str; 7] = ["mon", "tue", "wed", "thu", "fri", "sat", "sun"];
+
+/// All present conditions must hold for the rule to match.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct RuleConditions {
+    #[serde(default)]
+    pub input: Vec<InputMatcher>,
+    /// Matches when the request asks for any of these scopes.
+    #[serde(default)]
+    pub scope: Vec<String>,
+    #[serde(default)]
+    pub time: Option<TimeWindow>,
+}
+
+impl RuleConditions {
+    pub fn validate(&self) -> PolicyResult<()> {
+        self.time.as_ref().map_or(Ok(()), TimeWindow::validate)
+    }
+
+    pub fn matches(&self, request: &ActionRequest, now_unix: u64) -> bool {
+        self.input
+            .iter()
+            .all(|matcher| matcher.matches(&request.input))
+            && (self.scope.is_empty()
+                || request
+                    .capability
+                    .scope
+                    .iter()
+                    .any(|scope| self.scope.contains(scope)))
+            && self
+                .time
+                .as_ref()
+                .is_none_or(|window| window.contains(now_unix))
+    }
+}
+
+/// Tests one field of the request input, addressed by JSON pointer (e.g. `/repo/name`). A missing
+/// field never matches.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct InputMatcher {
+    pub field: String,
+    #[serde(default)]
+    pub equals: Option<Value>,
+    #[serde(default)]
+    pub one_of: Vec<Value>,
+    #[serde(default)]
+    pub prefix: Option<String>,
+    #[serde(default)]
+    pub suffix: Option<String>,
+    /// The field must be an http(s) URL whose host is listed; `*.example.com` also matches
+    /// subdomains.
+    #[serde(default)]
+    pub hosts: Vec<String>,
+}
+
+impl InputMatcher {
+    pub fn matches(&self, input: &Value) -> bool {
+        let Some(value) = input.pointer(&self.field) else {
+            return false;
+        };
+        let text = value.as_str();
+        self.equals
+            .as_ref()
+            .is_none_or(|expected| value == expected)
+            && (self.one_of.is_empty() || self.one_of.contains(value))
+            && self
+                .prefix
+                .as_ref()
+                .is_none_or(|prefix| text.is_some_and(|text| text.starts_with(prefix.as_str())))
+            && self
+                .suffix
+                .as_ref()
+                .is_none_or(|suffix| text.is_some_and(|text| text.ends_with(suffix.as_str())))
+            && (self.hosts.is_empty()
+                || text
+                    .and_then(url_host)
+                    .is_some_and(|host| self.hosts.iter().any(|rule| host_matches(&host, rule))))
+    }
+}
+
+fn url_host(url: &str) -> Option<String> {
+    let trimmed = url.trim();
+    let without_scheme = trimmed
+        .strip_prefix("https://")
+        .or_else(|| trimmed.strip_prefix("http://"))?;
+    let authority = without_scheme.split(['/', '?', '#']).next()?;
+    let host = authority.rsplit('@').next()?.split(':').next()?;
+    (!host.is_empty()).then(|| host.to_ascii_lowercase())
+}
+
+fn host_matches(host: &str, rule: &str) -> bool {
+    let rule = rule.to_ascii_lowercase();
+    match rule.strip_prefix("*.") {
+        Some(domain) => host == domain || host.ends_with(&format!(".{domain}")),
+        None => host == rule,
+    }
+}
+
+/// A UTC window from `start` to `end` (`HH:MM`, end exclusive). A window whose end is before its
+/// start runs past midnight; equal bounds cover the whole day. `days` (`mon`..`sun`) limits the
+/// days it applies on; empty means every day.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TimeWindow {
+    #[serde(default)]
+    pub days: Vec<String>,
+    pub start: String,
+    pub end: String,
+}
+
+fn parse_minutes(value: &str) -> Option<u64> {
+    let (hours, minutes) = value.split_once(':')?;
+    let (hours, minutes): (u64, u64) = (hours.parse().ok()?, minutes.parse().ok()?);
+    (hours < 24 && minutes < 60).then_some(hours * 60 + minutes)
+}
+
+impl TimeWindow {
+    pub fn validate(&self) -> PolicyResult<()> {
+        for value in [&self.start, &self.end] {
+            if parse_minutes(value).is_none() {
+                return Err(PolicyError::InvalidDocument(format!(
+                    "time window bound {value:?} is not HH:MM"
+                )));
+            }
+        }
+        if let Some(day) = self
+            .days
+            .iter()
+            .find(|day| !WEEKDAYS.contains(&day.as_str()))
+        {
+            return Err(PolicyError::InvalidDocument(format!(
+                "unknown weekday {day:?}"
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn contains(&self, now_unix: u64) -> bool {
+        let (Some(start), Some(end)) = (parse_minutes(&self.start), parse_minutes(&self.end))
+        else {
+            return false;
+        };
+        // 1970-01-01 was a Thursday.
+        let weekday = WEEKDAYS[((now_unix / 86_400 + 3) % 7) as usize];
+        if !self.days.is_empty() && !self.days.iter().any(|day| day == weekday) {
+            return false;
+        }
+        let minute = now_unix % 86_400 / 60;
+        if start == end {
+            true
+        } else if start < end {
+            (start..end).contains(&minute)
+        } else {
+            minute >= start || minute < end
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn input_matchers_check_hosts_and_values() {
+        let matcher = InputMatcher {
+            field: "/url".to_string(),
+            hosts: vec!["*.example.com".to_string()],
+            ..InputMatcher::default()
+        };
+        assert!(matcher.matches(&json!({"url": "https://api.example.com/v1"})));
+        assert!(!matcher.matches(&json!({"url": "https://example.com.evil.io/"})));
+        assert!(!matcher.matches(&json!({})));
+
+        let matcher = InputMatcher {
+            field: "/repo".to_string(),
+            one_of: vec![json!("odin-core"), json!("odin-docs")],
+            ..InputMatcher::default()
+        };
+        assert!(matcher.matches(&json!({"repo": "odin-docs"})));
+        assert!(!matcher.matches(&json!({"repo": "other"})));
+    }
+
+    #[test]
+    fn time_windows_wrap_midnight_and_respect_days() {
+        let window = TimeWindow {
+            days: vec!["thu".to_string()],
+            start: "22:00".to_string(),
+            end: "02:00".to_string(),
+        };
+        // 1970-01-01 (Thursday) 23:30 and 01:00 UTC.
+        assert!(window.contains(23 * 3_600 + 1_800));
+        assert!(window.contains(3_600));
+        assert!(!window.contains(12 * 3_600));
+        // Friday 01:00.
+        assert!(!window.contains(86_400 + 3_600));
+        assert!(TimeWindow {
+            start: "25:00".to_string(),
+            ..window
+        }
+        .validate()
+        .is_err());
+    }
+}
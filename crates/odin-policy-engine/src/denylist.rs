@@ -0,0 +1,125 @@
+//! Emergency deny-list: one entry blocks a capability across plugins, e.g. `command.run`
+//! everywhere. Added to a `CompositePolicyEngine` with `with_deny_list`, it is checked before the
+//! other engines so a match always wins over their allows.
+
+use odin_plugin_protocol::{ActionRequest, PolicyDecision};
+use serde::{Deserialize, Serialize};
+
+use crate::composite::NOT_GRANTED_REASON;
+use crate::conditions::InputMatcher;
+use crate::{glob_matches, ExplainStep, PolicyEngine, PolicyExplanation, PolicyResult};
+
+pub const DENY_LISTED_REASON: &str = "deny_listed";
+
+/// Blocks requests whose plugin and capability match the glob patterns (`*` any run of
+/// characters, `?` one) and whose input satisfies every matcher.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct DenyRule {
+    #[serde(default = "any")]
+    pub plugin: String,
+    pub capability: String,
+    #[serde(default)]
+    pub input: Vec<InputMatcher>,
+    /// Reported instead of `deny_listed`, e.g. to point at an incident.
+    #[serde(default)]
+    pub reason_code: Option<String>,
+}
+
+fn any() -> String {
+    "*".to_string()
+}
+
+impl DenyRule {
+    pub fn new(plugin: impl Into<String>, capability: impl Into<String>) -> Self {
+        Self {
+            plugin: plugin.into(),
+            capability: capability.into(),
+            input: Vec::new(),
+            reason_code: None,
+        }
+    }
+
+    pub fn with_input(mut self, matcher: InputMatcher) -> Self {
+        self.input.push(matcher);
+        self
+    }
+
+    pub fn with_reason_code(mut self, reason_code: impl Into<String>) -> Self {
+        self.reason_code = Some(reason_code.into());
+        self
+    }
+
+    pub fn matches(&self, request: &ActionRequest) -> bool {
+        let cap = &request.capability;
+        glob_matches(&self.plugin, &cap.plugin)
+            && glob_matches(&self.capability, &cap.capability)
+            && self
+                .input
+                .iter()
+                .all(|matcher| matcher.matches(&request.input))
+    }
+
+    fn decision(&self) -> PolicyDecision {
+        PolicyDecision::Deny {
+            reason_code: self
+                .reason_code
+                .clone()
+                .unwrap_or_else(|| DENY_LISTED_REASON.to_string()),
+        }
+    }
+}
+
+/// Denies requests matching any rule and abstains (`capability_not_granted`) on the rest, so on
+/// its own it grants nothing.
+#[derive(Clone, Debug, Default)]
+pub struct DenyListPolicyEngine {
+    rules: Vec<DenyRule>,
+}
+
+impl DenyListPolicyEngine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_rule(mut self, rule: DenyRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// The deny for the first matching rule, if any.
+    pub fn blocked(&self, request: &ActionRequest) -> Option<PolicyDecision> {
+        self.rules
+            .iter()
+            .find(|rule| rule.matches(request))
+            .map(DenyRule::decision)
+    }
+}
+
+impl PolicyEngine for DenyListPolicyEngine {
+    fn decide(&self, request: &ActionRequest) -> PolicyResult<PolicyDecision> {
+        Ok(self
+            .blocked(request)
+            .unwrap_or_else(|| PolicyDecision::Deny {
+                reason_code: NOT_GRANTED_REASON.to_string(),
+            }))
+    }
+
+    fn explain(&self, request: &ActionRequest) -> PolicyResult<PolicyExplanation> {
+        let mut steps = Vec::new();
+        for (index, rule) in self.rules.iter().enumerate() {
+            let name = format!("deny_list[{index}]");
+            if rule.matches(request) {
+                steps.push(ExplainStep::matched(
+                    name,
+                    format!("blocks {} {}", rule.plugin, rule.capability),
+                ));
+                break;
+            }
+            steps.push(ExplainStep::skipped(name, "request does not match"));
+        }
+        Ok(PolicyExplanation {
+            decision: self.decide(request)?,
+            steps,
+        })
+    }
+}
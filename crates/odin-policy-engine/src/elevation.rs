@@ -0,0 +1,309 @@
+//! Temporary capability elevations kept in an overlay separate from the base grants.
+//! Entries expire on their own, so a forgotten elevation cannot outlive its TTL.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+
+use odin_plugin_protocol::{project_lineage, ActionRequest};
+use serde::{Deserialize, Serialize};
+
+use crate::{PolicyError, PolicyResult};
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Elevation {
+    pub id: String,
+    pub plugin: String,
+    /// `*` elevates the capability in every project; a parent project also covers its children.
+    pub project: String,
+    pub capability: String,
+    /// Scopes the request must stay within; empty allows any scope.
+    #[serde(default)]
+    pub scope: Vec<String>,
+    pub reason: String,
+    pub created_at_unix: u64,
+    pub expires_at_unix: u64,
+}
+
+impl Elevation {
+    pub fn is_expired(&self, now_unix: u64) -> bool {
+        now_unix >= self.expires_at_unix
+    }
+
+    pub fn covers(&self, request: &ActionRequest) -> bool {
+        let cap = &request.capability;
+        cap.plugin == self.plugin
+            && cap.capability == self.capability
+            && (self.project == "*" || project_lineage(&cap.project).contains(&self.project))
+            && (self.scope.is_empty() || cap.scope.iter().all(|s| self.scope.contains(s)))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ElevationGrant {
+    pub plugin: String,
+    pub project: String,
+    pub capability: String,
+    pub scope: Vec<String>,
+    pub ttl: Duration,
+    pub reason: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct OverlayFile {
+    schema_version: u32,
+    #[serde(default)]
+    elevations: Vec<Elevation>,
+}
+
+/// Elevation overlay, optionally persisted as JSON. File-backed overlays re-read the file
+/// on every access so grants made by another process (e.g. the CLI) apply immediately.
+#[derive(Debug, Default)]
+pub struct ElevationOverlay {
+    path: Option<PathBuf>,
+    memory: Mutex<Vec<Elevation>>,
+}
+
+impl ElevationOverlay {
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    pub fn file(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: Some(path.into()),
+            memory: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn grant(&self, grant: ElevationGrant, now_unix: u64) -> PolicyResult<Elevation> {
+        for (field, value) in [
+            ("plugin", &grant.plugin),
+            ("project", &grant.project),
+            ("capability", &grant.capability),
+            ("reason", &grant.reason),
+        ] {
+            if value.trim().is_empty() {
+                return Err(PolicyError::InvalidRequest(format!(
+                    "elevation {field} is required"
+                )));
+            }
+        }
+        if grant.ttl.is_zero() {
+            return Err(PolicyError::InvalidRequest(
+                "elevation ttl must be positive".to_string(),
+            ));
+        }
+
+        self.update(|elevations| {
+            let mut seq = elevations.len() + 1;
+            while elevations
+                .iter()
+                .any(|existing| existing.id == format!("elev-{now_unix}-{seq}"))
+            {
+                seq += 1;
+            }
+            let elevation = Elevation {
+                id: format!("elev-{now_unix}-{seq}"),
+                plugin: grant.plugin,
+                project: grant.project,
+                capability: grant.capability,
+                scope: grant.scope,
+                reason: grant.reason,
+                created_at_unix: now_unix,
+                expires_at_unix: now_unix.saturating_add(grant.ttl.as_secs().max(1)),
+            };
+            elevations.push(elevation.clone());
+            elevation
+        })
+    }
+
+    pub fn revoke(&self, id: &str) -> PolicyResult<Option<Elevation>> {
+        self.update(|elevations| {
+            let idx = elevations.iter().position(|elevation| elevation.id == id)?;
+            Some(elevations.remove(idx))
+        })
+    }
+
+    pub fn active(&self, now_unix: u64) -> PolicyResult<Vec<Elevation>> {
+        let memory = self.lock()?;
+        Ok(self
+            .load(&memory)?
+            .into_iter()
+            .filter(|elevation| !elevation.is_expired(now_unix))
+            .collect())
+    }
+
+    /// First unexpired elevation covering `request`.
+    pub fn find_active(
+        &self,
+        request: &ActionRequest,
+        now_unix: u64,
+    ) -> PolicyResult<Option<Elevation>> {
+        Ok(self
+            .active(now_unix)?
+            .into_iter()
+            .find(|elevation| elevation.covers(request)))
+    }
+
+    /// Removes and returns expired elevations so callers can audit the revert.
+    pub fn take_expired(&self, now_unix: u64) -> PolicyResult<Vec<Elevation>> {
+        let mut memory = self.lock()?;
+        let (expired, kept): (Vec<_>, Vec<_>) = self
+            .load(&memory)?
+            .into_iter()
+            .partition(|elevation| elevation.is_expired(now_unix));
+        if !expired.is_empty() {
+            self.store(&mut memory, kept)?;
+        }
+        Ok(expired)
+    }
+
+    fn lock(&self) -> PolicyResult<MutexGuard<'_, Vec<Elevation>>> {
+        self.memory
+            .lock()
+            .map_err(|_| PolicyError::Evaluation("elevation overlay lock poisoned".to_string()))
+    }
+
+    fn update<T>(&self, apply: impl FnOnce(&mut Vec<Elevation>) -> T) -> PolicyResult<T> {
+        let mut memory = self.lock()?;
+        let mut elevations = self.load(&memory)?;
+        let result = apply(&mut elevations);
+        self.store(&mut memory, elevations)?;
+        Ok(result)
+    }
+
+    fn load(&self, memory: &[Elevation]) -> PolicyResult<Vec<Elevation>> {
+        let Some(path) = &self.path else {
+            return Ok(memory.to_vec());
+        };
+        let raw = match fs::read(path) {
+            Ok(raw) => raw,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(overlay_io(path, e)),
+        };
+        let file: OverlayFile = serde_json::from_slice(&raw).map_err(|e| {
+            PolicyError::Evaluation(format!(
+                "elevation overlay {} is invalid: {e}",
+                path.display()
+            ))
+        })?;
+        if file.schema_version != 1 {
+            return Err(PolicyError::Evaluation(format!(
+                "unsupported elevation overlay schema_version: {}",
+                file.schema_version
+            )));
+        }
+        Ok(file.elevations)
+    }
+
+    fn store(&self, memory: &mut Vec<Elevation>, elevations: Vec<Elevation>) -> PolicyResult<()> {
+        let Some(path) = &self.path else {
+            *memory = elevations;
+            return Ok(());
+        };
+        let body = serde_json::to_vec_pretty(&OverlayFile {
+            schema_version: 1,
+            elevations,
+        })
+        .map_err(|e| PolicyError::Evaluation(format!("elevation overlay encode failed: {e}")))?;
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(parent).map_err(|e| overlay_io(path, e))?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, body)
+            .and_then(|_| fs::rename(&tmp, path))
+            .map_err(|e| overlay_io(path, e))
+    }
+}
+
+fn overlay_io(path: &Path, err: std::io::Error) -> PolicyError {
+    PolicyError::Evaluation(format!("elevation overlay {}: {err}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use odin_plugin_protocol::{CapabilityRequest, RiskTier};
+
+    use super::*;
+
+    fn grant(ttl_secs: u64) -> ElevationGrant {
+        ElevationGrant {
+            plugin: "example.safe-github".to_string(),
+            project: "demo".to_string(),
+            capability: "repo.write".to_string(),
+            scope: vec!["project".to_string()],
+            ttl: Duration::from_secs(ttl_secs),
+            reason: "hotfix".to_string(),
+        }
+    }
+
+    fn request(scope: &str) -> ActionRequest {
+        ActionRequest {
+            request_id: "req-1".to_string(),
+            risk_tier: RiskTier::Safe,
+            capability: CapabilityRequest {
+                plugin: "example.safe-github".to_string(),
+                project: "demo".to_string(),
+                capability: "repo.write".to_string(),
+                scope: vec![scope.to_string()],
+                reason: "push fix".to_string(),
+            },
+            trace_id: None,
+            input: serde_json::Value::Null,
+            requested_by: None,
+        }
+    }
+
+    #[test]
+    fn elevation_covers_only_its_scope_until_expiry() {
+        let overlay = ElevationOverlay::in_memory();
+        let elevation = overlay.grant(grant(60), 1_000).expect("grant");
+
+        assert!(overlay
+            .find_active(&request("project"), 1_030)
+            .expect("lookup")
+            .is_some());
+        assert!(overlay
+            .find_active(&request("org"), 1_030)
+            .expect("lookup")
+            .is_none());
+        assert!(overlay
+            .find_active(&request("project"), 1_060)
+            .expect("lookup")
+            .is_none());
+
+        let expired = overlay.take_expired(1_060).expect("expire");
+        assert_eq!(expired, vec![elevation]);
+        assert!(overlay.take_expired(1_061).expect("expire").is_empty());
+    }
+
+    #[test]
+    fn parent_project_elevations_cover_child_projects() {
+        let overlay = ElevationOverlay::in_memory();
+        overlay.grant(grant(60), 1_000).expect("grant");
+        let in_project = |project: &str| {
+            let mut request = request("project");
+            request.capability.project = project.to_string();
+            overlay.find_active(&request, 1_030).expect("lookup")
+        };
+
+        assert!(in_project("demo/api").is_some());
+        assert!(in_project("demo/api/v2").is_some());
+        assert!(in_project("demo-legacy").is_none());
+        assert!(in_project("other/demo").is_none());
+    }
+
+    #[test]
+    fn grant_requires_reason_and_ttl() {
+        let overlay = ElevationOverlay::in_memory();
+        let mut missing_reason = grant(60);
+        missing_reason.reason = " ".to_string();
+        assert!(overlay.grant(missing_reason, 1).is_err());
+        assert!(overlay.grant(grant(0), 1).is_err());
+    }
+}
@@ -0,0 +1,927 @@
+//! Declarative policy loaded from a YAML or JSON document (see `schemas/policy.v1.schema.json`).
+//! The document is checked against the bundled schema before it is compiled, and the file is
+//! watched so edits apply without restarting the runtime.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{OnceLock, RwLock};
+use std::time::SystemTime;
+
+use odin_plugin_protocol::events::validate_value;
+use odin_plugin_protocol::{project_lineage, ActionRequest, PolicyDecision, RiskTier};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::bundle::{BundleManifest, BundleVerifier, SIGNATURE_FILE};
+use crate::cel::CelExpression;
+use crate::composite::NOT_GRANTED_REASON;
+use crate::conditions::RuleConditions;
+use crate::{
+    now_unix, scopes_permit, ExpiredGrant, ExplainStep, GrantResolution, PolicyEngine, PolicyError,
+    PolicyExplanation, PolicyResult, StaticPolicyEngine,
+};
+
+const POLICY_SCHEMA: &str = include_str!("../../../schemas/policy.v1.schema.json");
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PolicyFormat {
+    Yaml,
+    Json,
+}
+
+impl PolicyFormat {
+    /// `.json` files are JSON; anything else is read as YAML.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Self::Json,
+            _ => Self::Yaml,
+        }
+    }
+}
+
+/// Grants `capabilities` (or, under `denies`, overrides them) for `plugin` in `projects`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GrantRule {
+    pub plugin: String,
+    /// Empty applies the rule to every project (`*`).
+    #[serde(default)]
+    pub projects: Vec<String>,
+    pub capabilities: Vec<String>,
+    /// CEL expression (see `cel`) that must hold at decision time. Only grants take one; a
+    /// conditional grant fills in where no unconditional grant or deny decides.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub condition: Option<String>,
+    /// Makes the grant time-boxed: it lapses to a deny (`capability_grant_expired`) at this
+    /// time.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at_unix: Option<u64>,
+    /// Scope patterns the grant is limited to (see `scope_pattern_matches`); empty allows any
+    /// scope.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scopes: Vec<String>,
+}
+
+impl GrantRule {
+    fn project_levels(&self) -> Vec<String> {
+        if self.projects.is_empty() {
+            vec!["*".to_string()]
+        } else {
+            self.projects.clone()
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct ConditionalGrant {
+    /// Position under `grants`, for explanations.
+    index: usize,
+    rule: GrantRule,
+    condition: CelExpression,
+}
+
+impl ConditionalGrant {
+    fn expired(&self, now_unix: u64) -> bool {
+        self.rule
+            .expires_at_unix
+            .is_some_and(|deadline| now_unix >= deadline)
+    }
+
+    fn targets(&self, request: &ActionRequest) -> bool {
+        let cap = &request.capability;
+        let lineage = project_lineage(&cap.project);
+        self.rule.plugin == cap.plugin
+            && self.rule.capabilities.contains(&cap.capability)
+            && self
+                .rule
+                .project_levels()
+                .iter()
+                .any(|project| project == "*" || lineage.contains(project))
+    }
+
+    fn holds(&self, request: &ActionRequest, now_unix: u64) -> bool {
+        !self.expired(now_unix)
+            && self.targets(request)
+            && scopes_permit(&request.capability.scope, &self.rule.scopes)
+            && self.condition.evaluate(request).unwrap_or_else(|err| {
+                tracing::debug!(%err, "grant condition failed to evaluate");
+                false
+            })
+    }
+
+    fn explain(&self, request: &ActionRequest, now_unix: u64) -> ExplainStep {
+        let rule = format!("grants[{}]", self.index);
+        let source = self.condition.source();
+        if !self.targets(request) {
+            ExplainStep::skipped(rule, "targets another plugin, project or capability")
+        } else if self.expired(now_unix) {
+            ExplainStep::skipped(rule, "time-boxed grant has lapsed")
+        } else if !scopes_permit(&request.capability.scope, &self.rule.scopes) {
+            ExplainStep::skipped(rule, "requested scopes are outside the grant's scopes")
+        } else {
+            match self.condition.evaluate(request) {
+                Ok(true) => ExplainStep::matched(rule, format!("condition `{source}` holds")),
+                Ok(false) => {
+                    ExplainStep::skipped(rule, format!("condition `{source}` does not hold"))
+                }
+                Err(err) => ExplainStep::skipped(rule, format!("condition failed: {err}")),
+            }
+        }
+    }
+}
+
+/// Requires an explicit approval for requests the grants already allow. Omitted fields match
+/// anything; `project` also matches its child projects.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ApprovalRule {
+    #[serde(default)]
+    pub plugin: Option<String>,
+    #[serde(default)]
+    pub project: Option<String>,
+    #[serde(default)]
+    pub capability: Option<String>,
+    #[serde(default)]
+    pub risk_tiers: Vec<RiskTier>,
+    #[serde(default)]
+    pub reason_code: Option<String>,
+}
+
+impl ApprovalRule {
+    pub fn matches(&self, request: &ActionRequest) -> bool {
+        targets(&self.plugin, &self.project, &self.capability, request)
+            && (self.risk_tiers.is_empty() || self.risk_tiers.contains(&request.risk_tier))
+    }
+
+    fn explain(&self, index: usize, request: &ActionRequest) -> ExplainStep {
+        let rule = format!("approvals[{index}]");
+        if !targets(&self.plugin, &self.project, &self.capability, request) {
+            ExplainStep::skipped(rule, "targets another plugin, project or capability")
+        } else if !self.matches(request) {
+            ExplainStep::skipped(rule, "risk tier is not listed")
+        } else {
+            ExplainStep::matched(rule, "approval required")
+        }
+    }
+}
+
+fn targets(
+    plugin: &Option<String>,
+    project: &Option<String>,
+    capability: &Option<String>,
+    request: &ActionRequest,
+) -> bool {
+    let cap = &request.capability;
+    plugin.as_ref().is_none_or(|plugin| *plugin == cap.plugin)
+        && capability.as_ref().is_none_or(|c| *c == cap.capability)
+        && project
+            .as_ref()
+            .is_none_or(|project| project == "*" || project_lineage(&cap.project).contains(project))
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleEffect {
+    /// Grants the request even without a matching entry under `grants`.
+    Allow,
+    Deny,
+    /// Escalates a request the grants allow; it never grants on its own.
+    RequireApproval,
+}
+
+/// A rule that applies only when its `when` conditions hold. Rules are checked in order before
+/// the grants and the first match decides. Target fields match like `ApprovalRule`'s.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ConditionalRule {
+    #[serde(default)]
+    pub plugin: Option<String>,
+    #[serde(default)]
+    pub project: Option<String>,
+    #[serde(default)]
+    pub capability: Option<String>,
+    #[serde(default)]
+    pub when: RuleConditions,
+    pub effect: RuleEffect,
+    #[serde(default)]
+    pub reason_code: Option<String>,
+}
+
+impl ConditionalRule {
+    pub fn matches(&self, request: &ActionRequest, now_unix: u64) -> bool {
+        targets(&self.plugin, &self.project, &self.capability, request)
+            && self.when.matches(request, now_unix)
+    }
+
+    fn explain(&self, index: usize, request: &ActionRequest, now_unix: u64) -> ExplainStep {
+        let rule = format!("rules[{index}]");
+        if !targets(&self.plugin, &self.project, &self.capability, request) {
+            ExplainStep::skipped(rule, "targets another plugin, project or capability")
+        } else if !self.when.matches(request, now_unix) {
+            ExplainStep::skipped(rule, "`when` conditions do not hold")
+        } else {
+            ExplainStep::matched(rule, format!("effect {}", self.reason_code()))
+        }
+    }
+
+    fn reason_code(&self) -> String {
+        self.reason_code.clone().unwrap_or_else(|| {
+            match self.effect {
+                RuleEffect::Allow => "policy_rule_allowed",
+                RuleEffect::Deny => "policy_rule_denied",
+                RuleEffect::RequireApproval => "policy_rule_requires_approval",
+            }
+            .to_string()
+        })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct PolicyDocument {
+    pub schema_version: u32,
+    #[serde(default)]
+    pub require_approval_for_destructive: bool,
+    #[serde(default)]
+    pub grants: Vec<GrantRule>,
+    #[serde(default)]
+    pub denies: Vec<GrantRule>,
+    #[serde(default)]
+    pub approvals: Vec<ApprovalRule>,
+    #[serde(default)]
+    pub rules: Vec<ConditionalRule>,
+}
+
+fn policy_schema() -> &'static Value {
+    static SCHEMA: OnceLock<Value> = OnceLock::new();
+    SCHEMA.get_or_init(|| {
+        serde_json::from_str(POLICY_SCHEMA).expect("bundled policy schema is valid JSON")
+    })
+}
+
+impl PolicyDocument {
+    /// Parses and schema-checks a policy document.
+    pub fn parse(raw: &str, format: PolicyFormat) -> PolicyResult<Self> {
+        let value: Value = match format {
+            PolicyFormat::Yaml => serde_yml::from_str(raw)
+                .map_err(|e| PolicyError::InvalidDocument(format!("invalid YAML: {e}")))?,
+            PolicyFormat::Json => serde_json::from_str(raw)
+                .map_err(|e| PolicyError::InvalidDocument(format!("invalid JSON: {e}")))?,
+        };
+        let violations = validate_value(policy_schema(), &value);
+        if !violations.is_empty() {
+            let violations: Vec<String> = violations.iter().map(ToString::to_string).collect();
+            return Err(PolicyError::InvalidDocument(violations.join("; ")));
+        }
+        let document: Self = serde_json::from_value(value)
+            .map_err(|e| PolicyError::InvalidDocument(e.to_string()))?;
+        for rule in &document.rules {
+            rule.when.validate()?;
+        }
+        document.conditional_grants()?;
+        Ok(document)
+    }
+
+    pub fn load(path: &Path) -> PolicyResult<Self> {
+        let raw = fs::read_to_string(path).map_err(|e| {
+            PolicyError::InvalidDocument(format!("failed reading {}: {e}", path.display()))
+        })?;
+        Self::parse_for_path(&raw, path)
+    }
+
+    /// Parses `raw` in the format `path` implies, naming `path` in errors.
+    pub(crate) fn parse_for_path(raw: &str, path: &Path) -> PolicyResult<Self> {
+        Self::parse(raw, PolicyFormat::from_path(path)).map_err(|e| match e {
+            PolicyError::InvalidDocument(detail) => {
+                PolicyError::InvalidDocument(format!("{}: {detail}", path.display()))
+            }
+            other => other,
+        })
+    }
+
+    /// Compiles the unconditional grants and the denies into a `StaticPolicyEngine`, so project
+    /// inheritance and deny precedence behave the same as for in-code policy.
+    pub fn to_static_engine(&self) -> StaticPolicyEngine {
+        let mut engine = StaticPolicyEngine::default();
+        engine.set_require_approval_for_destructive(self.require_approval_for_destructive);
+        for (rules, deny) in [(&self.grants, false), (&self.denies, true)] {
+            for rule in rules.iter().filter(|rule| rule.condition.is_none()) {
+                for project in &rule.project_levels() {
+                    for capability in &rule.capabilities {
+                        if deny {
+                            engine.deny_capability(&rule.plugin, project, capability);
+                        } else if let Some(deadline) = rule.expires_at_unix {
+                            engine.allow_capability_until(
+                                &rule.plugin,
+                                project,
+                                capability,
+                                deadline,
+                            );
+                        } else {
+                            engine.allow_capability(&rule.plugin, project, capability);
+                        }
+                        if !deny {
+                            engine.restrict_scopes(&rule.plugin, project, capability, &rule.scopes);
+                        }
+                    }
+                }
+            }
+        }
+        engine
+    }
+
+    fn conditional_grants(&self) -> PolicyResult<Vec<ConditionalGrant>> {
+        self.grants
+            .iter()
+            .enumerate()
+            .filter_map(|(index, rule)| {
+                let source = rule.condition.as_ref()?;
+                Some(
+                    CelExpression::compile(source).map(|condition| ConditionalGrant {
+                        index,
+                        rule: rule.clone(),
+                        condition,
+                    }),
+                )
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug)]
+struct LoadedPolicy {
+    /// Modification time and length of the file the policy was last read from.
+    fingerprint: Option<(SystemTime, u64)>,
+    grants: StaticPolicyEngine,
+    conditional_grants: Vec<ConditionalGrant>,
+    approvals: Vec<ApprovalRule>,
+    rules: Vec<ConditionalRule>,
+    /// Manifest of the bundle the policy came from.
+    bundle: Option<BundleManifest>,
+}
+
+impl LoadedPolicy {
+    fn compile(
+        document: &PolicyDocument,
+        fingerprint: Option<(SystemTime, u64)>,
+    ) -> PolicyResult<Self> {
+        Ok(Self {
+            fingerprint,
+            grants: document.to_static_engine(),
+            conditional_grants: document.conditional_grants()?,
+            approvals: document.approvals.clone(),
+            rules: document.rules.clone(),
+            bundle: None,
+        })
+    }
+
+    /// A granted request, still subject to `require_approval_for_destructive`.
+    fn granted(&self, request: &ActionRequest, reason_code: String) -> PolicyDecision {
+        if matches!(request.risk_tier, RiskTier::Destructive)
+            && self.grants.require_approval_for_destructive
+        {
+            return PolicyDecision::RequireApproval {
+                reason_code: "destructive_requires_approval".to_string(),
+                tier: RiskTier::Destructive,
+            };
+        }
+        PolicyDecision::Allow { reason_code }
+    }
+
+    fn decide(&self, request: &ActionRequest, now_unix: u64) -> PolicyResult<PolicyDecision> {
+        let rule = self
+            .rules
+            .iter()
+            .find(|rule| rule.matches(request, now_unix));
+        let decision = match rule {
+            Some(rule) if rule.effect == RuleEffect::Deny => {
+                return Ok(PolicyDecision::Deny {
+                    reason_code: rule.reason_code(),
+                })
+            }
+            Some(rule) if rule.effect == RuleEffect::Allow => {
+                self.granted(request, rule.reason_code())
+            }
+            _ => match self.grants.decide(request)? {
+                PolicyDecision::Deny { reason_code }
+                    if reason_code == NOT_GRANTED_REASON
+                        && self
+                            .conditional_grants
+                            .iter()
+                            .any(|grant| grant.holds(request, now_unix)) =>
+                {
+                    self.granted(request, "capability_granted".to_string())
+                }
+                decision => decision,
+            },
+        };
+        if !matches!(decision, PolicyDecision::Allow { .. }) {
+            return Ok(decision);
+        }
+        if let Some(rule) = rule.filter(|rule| rule.effect == RuleEffect::RequireApproval) {
+            return Ok(PolicyDecision::RequireApproval {
+                reason_code: rule.reason_code(),
+                tier: request.risk_tier.clone(),
+            });
+        }
+        match self.approvals.iter().find(|rule| rule.matches(request)) {
+            Some(rule) => Ok(PolicyDecision::RequireApproval {
+                reason_code: rule
+                    .reason_code
+                    .clone()
+                    .unwrap_or_else(|| "policy_requires_approval".to_string()),
+                tier: request.risk_tier.clone(),
+            }),
+            None => Ok(decision),
+        }
+    }
+
+    /// Mirrors `decide`: the first matching rule, then the grants (conditional ones only when
+    /// nothing else grants), then the approval rules.
+    fn explain(&self, request: &ActionRequest, now_unix: u64) -> PolicyResult<PolicyExplanation> {
+        let decision = self.decide(request, now_unix)?;
+        let mut steps = Vec::new();
+        let mut effect = None;
+        for (index, rule) in self.rules.iter().enumerate() {
+            let step = rule.explain(index, request, now_unix);
+            let matched = step.matched;
+            steps.push(step);
+            if matched {
+                effect = Some(rule.effect);
+                break;
+            }
+        }
+        // What the grants alone decide, before approval rules escalate it.
+        let granted = match effect {
+            Some(RuleEffect::Deny) => return Ok(PolicyExplanation { decision, steps }),
+            Some(RuleEffect::Allow) => {
+                steps.push(self.grants.destructive_step(request));
+                self.granted(request, String::new())
+            }
+            _ => {
+                let grants = self.grants.explain_at(request, now_unix)?;
+                steps.extend(grants.steps);
+                let mut granted = grants.decision;
+                if matches!(&granted, PolicyDecision::Deny { reason_code } if reason_code == NOT_GRANTED_REASON)
+                {
+                    for grant in &self.conditional_grants {
+                        let step = grant.explain(request, now_unix);
+                        let matched = step.matched;
+                        steps.push(step);
+                        if matched {
+                            steps.push(self.grants.destructive_step(request));
+                            granted = self.granted(request, String::new());
+                            break;
+                        }
+                    }
+                }
+                granted
+            }
+        };
+        if !matches!(granted, PolicyDecision::Allow { .. })
+            || effect == Some(RuleEffect::RequireApproval)
+        {
+            return Ok(PolicyExplanation { decision, steps });
+        }
+        for (index, rule) in self.approvals.iter().enumerate() {
+            let step = rule.explain(index, request);
+            let matched = step.matched;
+            steps.push(step);
+            if matched {
+                break;
+            }
+        }
+        Ok(PolicyExplanation { decision, steps })
+    }
+}
+
+fn fingerprint(path: &Path) -> Option<(SystemTime, u64)> {
+    let meta = fs::metadata(path).ok()?;
+    Some((meta.modified().ok()?, meta.len()))
+}
+
+/// Reads the policy at `path`, a bundle directory when `bundle` is set, along with the
+/// fingerprint whose change triggers the next reload.
+fn read_policy(
+    path: &Path,
+    bundle: Option<&BundleVerifier>,
+) -> (Option<(SystemTime, u64)>, PolicyResult<LoadedPolicy>) {
+    let Some(verifier) = bundle else {
+        let stamp = fingerprint(path);
+        let policy =
+            PolicyDocument::load(path).and_then(|document| LoadedPolicy::compile(&document, stamp));
+        return (stamp, policy);
+    };
+    // Publishers write the signature last, so its change marks a complete bundle.
+    let stamp = fingerprint(&path.join(SIGNATURE_FILE));
+    let policy = verifier.verify(path).and_then(|verified| {
+        let mut policy = LoadedPolicy::compile(&verified.document, stamp)?;
+        verifier.record_activation(&verified.manifest)?;
+        policy.bundle = Some(verified.manifest);
+        Ok(policy)
+    });
+    (stamp, policy)
+}
+
+/// Policy engine backed by a policy file. The file is re-checked on every decision and
+/// reloaded when it changes; an edit that fails to parse or validate is logged and the last
+/// good policy stays in force.
+#[derive(Debug)]
+pub struct FilePolicyEngine {
+    path: PathBuf,
+    bundle: Option<BundleVerifier>,
+    loaded: RwLock<LoadedPolicy>,
+}
+
+impl FilePolicyEngine {
+    /// Loads `path`, failing if the initial document is missing or invalid.
+    pub fn load(path: impl Into<PathBuf>) -> PolicyResult<Self> {
+        Self::open(path.into(), None)
+    }
+
+    /// Loads the signed bundle in `dir` (see `bundle`). Reloads are verified the same way, so a
+    /// tampered, unsigned or rolled-back bundle never replaces the active policy.
+    pub fn load_bundle(dir: impl Into<PathBuf>, verifier: BundleVerifier) -> PolicyResult<Self> {
+        Self::open(dir.into(), Some(verifier))
+    }
+
+    fn open(path: PathBuf, bundle: Option<BundleVerifier>) -> PolicyResult<Self> {
+        let (_, policy) = read_policy(&path, bundle.as_ref());
+        Ok(Self {
+            loaded: RwLock::new(policy?),
+            path,
+            bundle,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Version of the active bundle; `None` for a plain policy file.
+    pub fn bundle_version(&self) -> Option<u64> {
+        self.loaded
+            .read()
+            .ok()?
+            .bundle
+            .as_ref()
+            .map(|manifest| manifest.version)
+    }
+
+    /// Re-reads the file now. On error the previous policy is kept.
+    pub fn reload(&self) -> PolicyResult<()> {
+        let (stamp, result) = read_policy(&self.path, self.bundle.as_ref());
+        let mut loaded = self
+            .loaded
+            .write()
+            .map_err(|_| PolicyError::Evaluation("policy lock poisoned".to_string()))?;
+        match result {
+            Ok(policy) => {
+                *loaded = policy;
+                tracing::info!(path = %self.path.display(), "policy reloaded");
+                Ok(())
+            }
+            Err(err) => {
+                // Remember the broken revision so it is not re-parsed on every decision.
+                loaded.fingerprint = stamp;
+                Err(err)
+            }
+        }
+    }
+
+    fn refresh(&self) {
+        let current = self
+            .loaded
+            .read()
+            .ok()
+            .and_then(|loaded| loaded.fingerprint);
+        let stamp = match self.bundle {
+            Some(_) => fingerprint(&self.path.join(SIGNATURE_FILE)),
+            None => fingerprint(&self.path),
+        };
+        if stamp.is_none() || stamp == current {
+            return;
+        }
+        if let Err(err) = self.reload() {
+            tracing::warn!(%err, "policy reload failed; keeping the previous policy");
+        }
+    }
+
+    /// Decides as of `now_unix`, which time-window conditions are checked against.
+    pub fn decide_at(
+        &self,
+        request: &ActionRequest,
+        now_unix: u64,
+    ) -> PolicyResult<PolicyDecision> {
+        let cap = &request.capability;
+        if cap.plugin.trim().is_empty() || cap.capability.trim().is_empty() {
+            return Err(PolicyError::InvalidRequest(
+                "plugin and capability are required".to_string(),
+            ));
+        }
+        self.refresh();
+        self.loaded
+            .read()
+            .map_err(|_| PolicyError::Evaluation("policy lock poisoned".to_string()))?
+            .decide(request, now_unix)
+    }
+
+    /// `explain` as of `now_unix`.
+    pub fn explain_at(
+        &self,
+        request: &ActionRequest,
+        now_unix: u64,
+    ) -> PolicyResult<PolicyExplanation> {
+        let cap = &request.capability;
+        if cap.plugin.trim().is_empty() || cap.capability.trim().is_empty() {
+            return Err(PolicyError::InvalidRequest(
+                "plugin and capability are required".to_string(),
+            ));
+        }
+        self.refresh();
+        self.loaded
+            .read()
+            .map_err(|_| PolicyError::Evaluation("policy lock poisoned".to_string()))?
+            .explain(request, now_unix)
+    }
+
+    /// See `StaticPolicyEngine::resolve_grant`.
+    pub fn resolve_grant(
+        &self,
+        plugin: &str,
+        project: &str,
+        capability: &str,
+    ) -> Option<GrantResolution> {
+        self.refresh();
+        self.loaded
+            .read()
+            .ok()?
+            .grants
+            .resolve_grant(plugin, project, capability)
+    }
+}
+
+impl PolicyEngine for FilePolicyEngine {
+    fn decide(&self, request: &ActionRequest) -> PolicyResult<PolicyDecision> {
+        self.decide_at(request, now_unix())
+    }
+
+    fn version(&self) -> Option<String> {
+        self.bundle_version().map(|version| version.to_string())
+    }
+
+    fn explain(&self, request: &ActionRequest) -> PolicyResult<PolicyExplanation> {
+        self.explain_at(request, now_unix())
+    }
+
+    fn take_expired_grants(&self, now_unix: u64) -> Vec<ExpiredGrant> {
+        self.refresh();
+        self.loaded
+            .read()
+            .map(|loaded| loaded.grants.take_expired_grants(now_unix))
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use odin_plugin_protocol::CapabilityRequest;
+    use serde_json::json;
+
+    use super::*;
+
+    fn request(project: &str, capability: &str, risk_tier: RiskTier) -> ActionRequest {
+        ActionRequest {
+            request_id: "req-1".to_string(),
+            risk_tier,
+            capability: CapabilityRequest {
+                plugin: "example.safe-github".to_string(),
+                project: project.to_string(),
+                capability: capability.to_string(),
+                scope: vec!["project".to_string()],
+                reason: "test".to_string(),
+            },
+            trace_id: None,
+            input: Value::Null,
+            requested_by: None,
+        }
+    }
+
+    const POLICY: &str = "\
+schema_version: 1
+grants:
+  - plugin: example.safe-github
+    projects: [acme]
+    capabilities: [repo.read, repo.write]
+denies:
+  - plugin: example.safe-github
+    projects: [acme/legacy]
+    capabilities: [repo.write]
+approvals:
+  - capability: repo.write
+    risk_tiers: [sensitive, destructive]
+    reason_code: write_needs_review
+";
+
+    #[test]
+    fn document_grants_denies_and_approvals_apply() {
+        let dir = std::env::temp_dir().join(format!("odin-policy-file-{}", std::process::id()));
+        fs::create_dir_all(&dir).expect("dir");
+        let path = dir.join("policy.yaml");
+        fs::write(&path, POLICY).expect("write");
+        let engine = FilePolicyEngine::load(&path).expect("load");
+
+        let decide = |project: &str, capability: &str, tier: RiskTier| {
+            engine
+                .decide(&request(project, capability, tier))
+                .expect("decision")
+        };
+        assert!(matches!(
+            decide("acme/api", "repo.read", RiskTier::Safe),
+            PolicyDecision::Allow { .. }
+        ));
+        assert_eq!(
+            decide("acme/api", "repo.write", RiskTier::Sensitive),
+            PolicyDecision::RequireApproval {
+                reason_code: "write_needs_review".to_string(),
+                tier: RiskTier::Sensitive,
+            }
+        );
+        assert_eq!(
+            decide("acme/legacy", "repo.write", RiskTier::Safe),
+            PolicyDecision::Deny {
+                reason_code: "capability_denied".to_string()
+            }
+        );
+
+        // A broken edit keeps the last good policy; a valid one replaces it.
+        fs::write(&path, "schema_version: 1\ngrants: nope\n").expect("write");
+        assert!(matches!(
+            decide("acme/api", "repo.read", RiskTier::Safe),
+            PolicyDecision::Allow { .. }
+        ));
+        fs::write(&path, "schema_version: 1\n").expect("write");
+        assert!(matches!(
+            decide("acme/api", "repo.read", RiskTier::Safe),
+            PolicyDecision::Deny { .. }
+        ));
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn conditional_rules_match_input_scope_and_time() {
+        let document = PolicyDocument::parse(
+            "\
+schema_version: 1
+grants:
+  - plugin: example.safe-github
+    capabilities: [repo.write]
+rules:
+  - capability: http.get
+    when:
+      input: [{field: /url, hosts: [\"*.example.com\"]}]
+    effect: allow
+  - capability: repo.write
+    when:
+      input: [{field: /repo, equals: infra}]
+    effect: deny
+    reason_code: infra_repo_frozen
+  - capability: repo.write
+    when:
+      time: {days: [sat, sun], start: \"00:00\", end: \"00:00\"}
+    effect: require_approval
+",
+            PolicyFormat::Yaml,
+        )
+        .expect("policy");
+        let policy = LoadedPolicy::compile(&document, None).expect("compile");
+        let decide = |capability: &str, input: Value, now_unix: u64| {
+            let mut request = request("acme", capability, RiskTier::Safe);
+            request.input = input;
+            policy.decide(&request, now_unix).expect("decision")
+        };
+        // 1970-01-01 was a Thursday; day 2 is a Saturday.
+        let (thursday, saturday) = (3_600, 2 * 86_400 + 3_600);
+
+        assert!(matches!(
+            decide(
+                "http.get",
+                json!({"url": "https://api.example.com"}),
+                thursday
+            ),
+            PolicyDecision::Allow { .. }
+        ));
+        assert_eq!(
+            decide("http.get", json!({"url": "https://other.io"}), thursday),
+            PolicyDecision::Deny {
+                reason_code: "capability_not_granted".to_string()
+            }
+        );
+        assert_eq!(
+            decide("repo.write", json!({"repo": "infra"}), saturday),
+            PolicyDecision::Deny {
+                reason_code: "infra_repo_frozen".to_string()
+            }
+        );
+        assert!(matches!(
+            decide("repo.write", json!({"repo": "app"}), thursday),
+            PolicyDecision::Allow { .. }
+        ));
+        assert!(matches!(
+            decide("repo.write", json!({"repo": "app"}), saturday),
+            PolicyDecision::RequireApproval { .. }
+        ));
+    }
+
+    #[test]
+    fn explain_lists_rules_that_did_not_match() {
+        let mut document = PolicyDocument::parse(POLICY, PolicyFormat::Yaml).expect("policy");
+        document.rules.push(ConditionalRule {
+            plugin: None,
+            project: None,
+            capability: Some("repo.write".to_string()),
+            when: RuleConditions {
+                scope: vec!["org".to_string()],
+                ..RuleConditions::default()
+            },
+            effect: RuleEffect::Deny,
+            reason_code: None,
+        });
+        let policy = LoadedPolicy::compile(&document, None).expect("compile");
+
+        let explanation = policy
+            .explain(&request("acme/api", "repo.write", RiskTier::Sensitive), 0)
+            .expect("explanation");
+        assert_eq!(
+            explanation.decision,
+            policy
+                .decide(&request("acme/api", "repo.write", RiskTier::Sensitive), 0)
+                .expect("decision")
+        );
+        let steps: Vec<(&str, bool)> = explanation
+            .steps
+            .iter()
+            .map(|step| (step.rule.as_str(), step.matched))
+            .collect();
+        assert_eq!(
+            steps,
+            vec![
+                ("rules[0]", false),
+                ("grant example.safe-github acme/api repo.write", false),
+                ("grant example.safe-github acme repo.write", true),
+                ("require_approval_for_destructive", false),
+                ("approvals[0]", true),
+            ]
+        );
+        assert_eq!(explanation.steps[0].detail, "`when` conditions do not hold");
+    }
+
+    #[test]
+    fn conditional_grants_apply_only_while_their_condition_holds() {
+        let document = PolicyDocument::parse(
+            "\
+schema_version: 1
+grants:
+  - plugin: example.safe-github
+    capabilities: [http.get]
+    condition: \"input.url.endsWith('.example.com') && request.risk_tier == 'safe'\"
+",
+            PolicyFormat::Yaml,
+        )
+        .expect("policy");
+        let policy = LoadedPolicy::compile(&document, None).expect("compile");
+        let decide = |url: &str, tier: RiskTier| {
+            let mut request = request("acme", "http.get", tier);
+            request.input = json!({ "url": url });
+            policy.decide(&request, 0).expect("decision")
+        };
+        assert!(matches!(
+            decide("https://api.example.com", RiskTier::Safe),
+            PolicyDecision::Allow { .. }
+        ));
+        assert_eq!(
+            decide("https://api.example.com", RiskTier::Sensitive),
+            PolicyDecision::Deny {
+                reason_code: NOT_GRANTED_REASON.to_string()
+            }
+        );
+
+        let err = PolicyDocument::parse(
+            "schema_version: 1\ngrants:\n  - plugin: p\n    capabilities: [c]\n    condition: \"env.HOME == 'x'\"\n",
+            PolicyFormat::Yaml,
+        )
+        .expect_err("unknown identifier");
+        assert!(
+            err.to_string().contains("unknown identifier `env`"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn schema_violations_are_reported_with_their_path() {
+        let err = PolicyDocument::parse(
+            r#"{"schema_version": 1, "grants": [{"plugin": "", "capabilities": []}], "extra": 1}"#,
+            PolicyFormat::Json,
+        )
+        .expect_err("invalid");
+        let message = err.to_string();
+        assert!(message.contains("/grants/0/plugin"), "{message}");
+        assert!(message.contains("/extra: unexpected field"), "{message}");
+    }
+}
@@ -1,8 +1,26 @@
 //! Policy engine contracts and baseline implementation.
 
-use std::collections::HashSet;
+pub mod breakglass;
+pub mod budget;
+pub mod bundle;
+pub mod cel;
+pub mod composite;
+pub mod conditions;
+pub mod denylist;
+pub mod elevation;
+pub mod file;
+pub mod quota;
+#[cfg(feature = "rego")]
+pub mod rego;
+pub mod simulate;
+pub mod trust;
 
-use odin_plugin_protocol::{ActionRequest, PolicyDecision, RiskTier};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use odin_plugin_protocol::{project_lineage, ActionRequest, PolicyDecision, RiskTier};
+use serde::Serialize;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -11,17 +29,269 @@ pub enum PolicyError {
     InvalidRequest(String),
     #[error("evaluation error: {0}")]
     Evaluation(String),
+    #[error("invalid policy document: {0}")]
+    InvalidDocument(String),
+    #[error("policy bundle rejected: {0}")]
+    Bundle(String),
 }
 
 pub type PolicyResult<T> = Result<T, PolicyError>;
 
 pub trait PolicyEngine: Send + Sync {
     fn decide(&self, request: &ActionRequest) -> PolicyResult<PolicyDecision>;
+
+    /// Time-boxed grants that lapsed by `now_unix` and have not been reported yet. Each is
+    /// returned once so the runtime can audit the expiry.
+    fn take_expired_grants(&self, _now_unix: u64) -> Vec<ExpiredGrant> {
+        Vec::new()
+    }
+
+    /// Version of the active policy, recorded with every audited decision. `None` when the
+    /// engine's policy is not versioned.
+    fn version(&self) -> Option<String> {
+        None
+    }
+
+    /// Called by the runtime once an allowed request has executed, so metering engines count
+    /// it. `decide` must not count: it also runs for scope introspection and when a resumed
+    /// approval is re-checked.
+    fn record_execution(&self, _request: &ActionRequest) -> PolicyResult<()> {
+        Ok(())
+    }
+
+    /// Called by the runtime right before an allowed request executes. Metering engines hold a
+    /// use for it, so concurrent requests cannot all pass the check before any is recorded.
+    /// Returns the decision replacing the allow when a limit is already spent; nothing is held
+    /// then. A held use ends in `record_execution` or `release_execution`.
+    fn reserve_execution(&self, _request: &ActionRequest) -> PolicyResult<Option<PolicyDecision>> {
+        Ok(None)
+    }
+
+    /// Returns the use `reserve_execution` held for a request that did not execute.
+    fn release_execution(&self, _request: &ActionRequest) -> PolicyResult<()> {
+        Ok(())
+    }
+
+    /// The decision together with the rules that were considered on the way to it. Engines that
+    /// cannot break their decision down report it as a single opaque step.
+    fn explain(&self, request: &ActionRequest) -> PolicyResult<PolicyExplanation> {
+        let decision = self.decide(request)?;
+        Ok(PolicyExplanation {
+            steps: vec![ExplainStep::matched(
+                "engine",
+                "this engine does not report the rules behind its decision",
+            )],
+            decision,
+        })
+    }
+}
+
+/// Lets callers pick an engine at runtime, e.g. file-backed or built-in policy.
+impl<P: PolicyEngine + ?Sized> PolicyEngine for Box<P> {
+    fn decide(&self, request: &ActionRequest) -> PolicyResult<PolicyDecision> {
+        (**self).decide(request)
+    }
+
+    fn take_expired_grants(&self, now_unix: u64) -> Vec<ExpiredGrant> {
+        (**self).take_expired_grants(now_unix)
+    }
+
+    fn version(&self) -> Option<String> {
+        (**self).version()
+    }
+
+    fn record_execution(&self, request: &ActionRequest) -> PolicyResult<()> {
+        (**self).record_execution(request)
+    }
+
+    fn reserve_execution(&self, request: &ActionRequest) -> PolicyResult<Option<PolicyDecision>> {
+        (**self).reserve_execution(request)
+    }
+
+    fn release_execution(&self, request: &ActionRequest) -> PolicyResult<()> {
+        (**self).release_execution(request)
+    }
+
+    fn explain(&self, request: &ActionRequest) -> PolicyResult<PolicyExplanation> {
+        (**self).explain(request)
+    }
+}
+
+/// Lets one engine be shared, e.g. by the runtime and a policy-gated secret store.
+impl<P: PolicyEngine + ?Sized> PolicyEngine for Arc<P> {
+    fn decide(&self, request: &ActionRequest) -> PolicyResult<PolicyDecision> {
+        (**self).decide(request)
+    }
+
+    fn take_expired_grants(&self, now_unix: u64) -> Vec<ExpiredGrant> {
+        (**self).take_expired_grants(now_unix)
+    }
+
+    fn version(&self) -> Option<String> {
+        (**self).version()
+    }
+
+    fn record_execution(&self, request: &ActionRequest) -> PolicyResult<()> {
+        (**self).record_execution(request)
+    }
+
+    fn reserve_execution(&self, request: &ActionRequest) -> PolicyResult<Option<PolicyDecision>> {
+        (**self).reserve_execution(request)
+    }
+
+    fn release_execution(&self, request: &ActionRequest) -> PolicyResult<()> {
+        (**self).release_execution(request)
+    }
+
+    fn explain(&self, request: &ActionRequest) -> PolicyResult<PolicyExplanation> {
+        (**self).explain(request)
+    }
+}
+
+pub(crate) fn now_unix() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default()
+}
+
+/// Whether `scope` matches a grant's scope pattern: exact, glob (`*` matches any run of
+/// characters, `?` one character), or domain-style `*.example.com`, which also matches
+/// `example.com` itself.
+pub fn scope_pattern_matches(pattern: &str, scope: &str) -> bool {
+    pattern.strip_prefix("*.") == Some(scope) || glob_matches(pattern, scope)
+}
+
+/// Glob match where `*` matches any run of characters and `?` exactly one.
+pub(crate) fn glob_matches(pattern: &str, text: &str) -> bool {
+    let (pattern, text): (Vec<char>, Vec<char>) =
+        (pattern.chars().collect(), text.chars().collect());
+    let (mut p, mut t) = (0, 0);
+    // Position of the last `*` and the text position it was tried against.
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = star {
+            p = star_p + 1;
+            t = star_t + 1;
+            star = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Requested scopes against a grant's patterns, with the manifest's rules: no patterns means the
+/// grant is unrestricted; otherwise the request must name scopes and each must match a pattern.
+pub(crate) fn scopes_permit(requested: &[String], patterns: &[String]) -> bool {
+    patterns.is_empty()
+        || (!requested.is_empty()
+            && requested.iter().all(|scope| {
+                patterns
+                    .iter()
+                    .any(|pattern| scope_pattern_matches(pattern, scope))
+            }))
+}
+
+/// Renders a decision as `allow (reason)`, `deny (reason)` or `require_approval (reason)`.
+pub fn describe_decision(decision: &PolicyDecision) -> String {
+    match decision {
+        PolicyDecision::Allow { reason_code } => format!("allow ({reason_code})"),
+        PolicyDecision::Deny { reason_code } => format!("deny ({reason_code})"),
+        PolicyDecision::RequireApproval { reason_code, .. } => {
+            format!("require_approval ({reason_code})")
+        }
+    }
+}
+
+/// One rule an engine considered, in evaluation order.
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+pub struct ExplainStep {
+    /// Where the rule lives, e.g. `rules[2]` or `grant acme repo.read`.
+    pub rule: String,
+    pub matched: bool,
+    /// Why the rule matched, or why it did not.
+    pub detail: String,
+}
+
+impl ExplainStep {
+    pub fn matched(rule: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self {
+            rule: rule.into(),
+            matched: true,
+            detail: detail.into(),
+        }
+    }
+
+    pub fn skipped(rule: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self {
+            rule: rule.into(),
+            matched: false,
+            detail: detail.into(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+pub struct PolicyExplanation {
+    pub decision: PolicyDecision,
+    pub steps: Vec<ExplainStep>,
+}
+
+impl PolicyExplanation {
+    /// The steps that contributed to the decision.
+    pub fn matched_rules(&self) -> impl Iterator<Item = &ExplainStep> {
+        self.steps.iter().filter(|step| step.matched)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+pub struct ExpiredGrant {
+    pub plugin: String,
+    pub project: String,
+    pub capability: String,
+    pub expired_at_unix: u64,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GrantEffect {
+    Allow,
+    Deny,
+    /// A time-boxed allow past its deadline; it decides like a deny.
+    Expired,
+}
+
+/// The grant that decided a (plugin, project, capability) triple and where it came from.
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+pub struct GrantResolution {
+    /// Project level holding the grant: the project itself, an ancestor, or `*`.
+    pub project: String,
+    pub effect: GrantEffect,
+    /// True when the grant was set on an ancestor or `*` rather than the project itself.
+    pub inherited: bool,
+    /// Deadline of a time-boxed grant.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at_unix: Option<u64>,
+    /// Scope patterns the grant is restricted to; empty means any scope.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub scopes: Vec<String>,
 }
 
 #[derive(Clone, Debug, Default)]
 pub struct StaticPolicyEngine {
     allowed: HashSet<(String, String, String)>,
+    denied: HashSet<(String, String, String)>,
+    deadlines: HashMap<(String, String, String), u64>,
+    scopes: HashMap<(String, String, String), Vec<String>>,
+    /// Lapsed grants already returned by `take_expired_grants`.
+    expiry_reported: Arc<Mutex<HashSet<(String, String, String)>>>,
     pub require_approval_for_destructive: bool,
 }
 
@@ -31,26 +301,223 @@ impl StaticPolicyEngine {
     }
 
     pub fn allow_capability(&mut self, plugin: &str, project: &str, capability: &str) {
-        self.allowed.insert((
+        let key = (
             plugin.to_string(),
             project.to_string(),
             capability.to_string(),
-        ));
+        );
+        self.deadlines.remove(&key);
+        self.allowed.insert(key);
     }
 
-    fn is_allowed(&self, plugin: &str, project: &str, capability: &str) -> bool {
-        self.allowed.contains(&(
+    /// Grants `capability` until `deadline_unix`, after which it lapses to a deny
+    /// (`capability_grant_expired`) and is reported once through `take_expired_grants`.
+    pub fn allow_capability_until(
+        &mut self,
+        plugin: &str,
+        project: &str,
+        capability: &str,
+        deadline_unix: u64,
+    ) {
+        let key = (
             plugin.to_string(),
             project.to_string(),
             capability.to_string(),
-        )) || self
-            .allowed
-            .contains(&(plugin.to_string(), "*".to_string(), capability.to_string()))
+        );
+        self.deadlines.insert(key.clone(), deadline_unix);
+        self.allowed.insert(key);
     }
-}
 
-impl PolicyEngine for StaticPolicyEngine {
-    fn decide(&self, request: &ActionRequest) -> PolicyResult<PolicyDecision> {
+    /// Restricts the grant for this triple to requests whose scopes all match one of
+    /// `patterns` (see `scope_pattern_matches`); others are denied with `scope_not_granted`.
+    pub fn restrict_scopes(
+        &mut self,
+        plugin: &str,
+        project: &str,
+        capability: &str,
+        patterns: &[String],
+    ) {
+        let key = (
+            plugin.to_string(),
+            project.to_string(),
+            capability.to_string(),
+        );
+        if patterns.is_empty() {
+            self.scopes.remove(&key);
+        } else {
+            self.scopes.insert(key, patterns.to_vec());
+        }
+    }
+
+    /// Overrides a grant inherited from a parent project (or `*`) for `project` and its
+    /// children.
+    pub fn deny_capability(&mut self, plugin: &str, project: &str, capability: &str) {
+        self.denied.insert((
+            plugin.to_string(),
+            project.to_string(),
+            capability.to_string(),
+        ));
+    }
+
+    /// Resolves the effective grant: the project itself, then each ancestor
+    /// (`org/team/project` -> `org/team` -> `org`), then `*`. A deny at any of those levels wins,
+    /// so a child grant cannot override a parent deny; otherwise the nearest level with a grant
+    /// wins.
+    pub fn resolve_grant(
+        &self,
+        plugin: &str,
+        project: &str,
+        capability: &str,
+    ) -> Option<GrantResolution> {
+        self.resolve_grant_at(plugin, project, capability, now_unix())
+    }
+
+    /// `resolve_grant` as of `now_unix`, which time-boxed grants are checked against.
+    pub fn resolve_grant_at(
+        &self,
+        plugin: &str,
+        project: &str,
+        capability: &str,
+        now_unix: u64,
+    ) -> Option<GrantResolution> {
+        let mut levels = project_lineage(project);
+        levels.push("*".to_string());
+        let key = |level: &str| {
+            (
+                plugin.to_string(),
+                level.to_string(),
+                capability.to_string(),
+            )
+        };
+        if let Some(level) = levels
+            .iter()
+            .find(|level| self.denied.contains(&key(level)))
+        {
+            return Some(GrantResolution {
+                inherited: level != project,
+                scopes: Vec::new(),
+                project: level.clone(),
+                effect: GrantEffect::Deny,
+                expires_at_unix: None,
+            });
+        }
+        levels.into_iter().find_map(|level| {
+            let key = key(&level);
+            if !self.allowed.contains(&key) {
+                return None;
+            }
+            let expires_at_unix = self.deadlines.get(&key).copied();
+            let effect = if expires_at_unix.is_some_and(|deadline| now_unix >= deadline) {
+                GrantEffect::Expired
+            } else {
+                GrantEffect::Allow
+            };
+            Some(GrantResolution {
+                inherited: level != project,
+                scopes: self.scopes.get(&key).cloned().unwrap_or_default(),
+                project: level,
+                effect,
+                expires_at_unix,
+            })
+        })
+    }
+
+    /// `explain` as of `now_unix`: one step per project level checked, then the destructive
+    /// approval check when the grant allows.
+    pub fn explain_at(
+        &self,
+        request: &ActionRequest,
+        now_unix: u64,
+    ) -> PolicyResult<PolicyExplanation> {
+        let decision = self.decide_at(request, now_unix)?;
+        let cap = &request.capability;
+        let mut levels = project_lineage(&cap.project);
+        levels.push("*".to_string());
+        let key = |level: &str| {
+            (
+                cap.plugin.clone(),
+                level.to_string(),
+                cap.capability.clone(),
+            )
+        };
+        let denied_in_lineage = levels.iter().any(|level| self.denied.contains(&key(level)));
+        let mut steps = Vec::new();
+        let mut granted = None;
+        for level in levels {
+            let key = key(&level);
+            let rule = format!("grant {} {level} {}", cap.plugin, cap.capability);
+            if self.denied.contains(&key) {
+                steps.push(ExplainStep::matched(
+                    rule.replacen("grant", "deny", 1),
+                    "denied at this level, which beats any grant in the lineage",
+                ));
+                break;
+            }
+            if denied_in_lineage && self.allowed.contains(&key) {
+                steps.push(ExplainStep::skipped(
+                    rule,
+                    "overridden by a deny further up",
+                ));
+                continue;
+            }
+            if !self.allowed.contains(&key) {
+                steps.push(ExplainStep::skipped(rule, "no grant or deny at this level"));
+                continue;
+            }
+            match self.deadlines.get(&key) {
+                Some(deadline) if now_unix >= *deadline => {
+                    steps.push(ExplainStep::matched(
+                        rule,
+                        format!("time-boxed grant lapsed at {deadline}"),
+                    ));
+                }
+                Some(deadline) => {
+                    granted = Some(key);
+                    steps.push(ExplainStep::matched(
+                        rule,
+                        format!("granted until {deadline}"),
+                    ));
+                }
+                None => {
+                    granted = Some(key);
+                    steps.push(ExplainStep::matched(rule, "granted"));
+                }
+            }
+            break;
+        }
+        if let Some(key) = granted {
+            let patterns = self.scopes.get(&key).map(Vec::as_slice).unwrap_or_default();
+            if !patterns.is_empty() {
+                let detail = format!("requested {:?} against patterns {:?}", cap.scope, patterns);
+                if !scopes_permit(&cap.scope, patterns) {
+                    steps.push(ExplainStep::matched(
+                        "scopes",
+                        format!("{detail}: not granted"),
+                    ));
+                    return Ok(PolicyExplanation { decision, steps });
+                }
+                steps.push(ExplainStep::skipped(
+                    "scopes",
+                    format!("{detail}: within the grant"),
+                ));
+            }
+            steps.push(self.destructive_step(request));
+        }
+        Ok(PolicyExplanation { decision, steps })
+    }
+
+    /// How `require_approval_for_destructive` applied to a granted request.
+    pub(crate) fn destructive_step(&self, request: &ActionRequest) -> ExplainStep {
+        let rule = "require_approval_for_destructive";
+        let destructive = matches!(request.risk_tier, RiskTier::Destructive);
+        match (destructive, self.require_approval_for_destructive) {
+            (true, true) => ExplainStep::matched(rule, "destructive requests need approval"),
+            (false, true) => ExplainStep::skipped(rule, "request is not destructive"),
+            (_, false) => ExplainStep::skipped(rule, "disabled"),
+        }
+    }
+
+    fn decide_at(&self, request: &ActionRequest, now_unix: u64) -> PolicyResult<PolicyDecision> {
         let cap = &request.capability;
         if cap.plugin.trim().is_empty() || cap.capability.trim().is_empty() {
             return Err(PolicyError::InvalidRequest(
@@ -58,10 +525,29 @@ impl PolicyEngine for StaticPolicyEngine {
             ));
         }
 
-        if !self.is_allowed(&cap.plugin, &cap.project, &cap.capability) {
-            return Ok(PolicyDecision::Deny {
-                reason_code: "capability_not_granted".to_string(),
-            });
+        match self.resolve_grant_at(&cap.plugin, &cap.project, &cap.capability, now_unix) {
+            Some(resolution) if resolution.effect == GrantEffect::Allow => {
+                if !scopes_permit(&cap.scope, &resolution.scopes) {
+                    return Ok(PolicyDecision::Deny {
+                        reason_code: "scope_not_granted".to_string(),
+                    });
+                }
+            }
+            Some(resolution) if resolution.effect == GrantEffect::Expired => {
+                return Ok(PolicyDecision::Deny {
+                    reason_code: "capability_grant_expired".to_string(),
+                })
+            }
+            Some(_) => {
+                return Ok(PolicyDecision::Deny {
+                    reason_code: "capability_denied".to_string(),
+                })
+            }
+            None => {
+                return Ok(PolicyDecision::Deny {
+                    reason_code: "capability_not_granted".to_string(),
+                })
+            }
         }
 
         if matches!(request.risk_tier, RiskTier::Destructive)
@@ -79,11 +565,40 @@ impl PolicyEngine for StaticPolicyEngine {
     }
 }
 
+impl PolicyEngine for StaticPolicyEngine {
+    fn decide(&self, request: &ActionRequest) -> PolicyResult<PolicyDecision> {
+        self.decide_at(request, now_unix())
+    }
+
+    fn explain(&self, request: &ActionRequest) -> PolicyResult<PolicyExplanation> {
+        self.explain_at(request, now_unix())
+    }
+
+    fn take_expired_grants(&self, now_unix: u64) -> Vec<ExpiredGrant> {
+        let Ok(mut reported) = self.expiry_reported.lock() else {
+            return Vec::new();
+        };
+        let mut expired: Vec<ExpiredGrant> = self
+            .deadlines
+            .iter()
+            .filter(|(key, deadline)| now_unix >= **deadline && reported.insert((*key).clone()))
+            .map(|((plugin, project, capability), deadline)| ExpiredGrant {
+                plugin: plugin.clone(),
+                project: project.clone(),
+                capability: capability.clone(),
+                expired_at_unix: *deadline,
+            })
+            .collect();
+        expired.sort_by_key(|grant| grant.expired_at_unix);
+        expired
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use odin_plugin_protocol::{ActionRequest, CapabilityRequest, RiskTier};
 
-    use super::{PolicyEngine, StaticPolicyEngine};
+    use super::{scope_pattern_matches, GrantEffect, PolicyEngine, StaticPolicyEngine};
 
     fn make_request(risk_tier: RiskTier) -> ActionRequest {
         ActionRequest {
@@ -96,7 +611,9 @@ mod tests {
                 scope: vec!["project".to_string()],
                 reason: "read repository metadata".to_string(),
             },
+            trace_id: None,
             input: serde_json::Value::Null,
+            requested_by: None,
         }
     }
 
@@ -142,4 +659,177 @@ mod tests {
             odin_plugin_protocol::PolicyDecision::RequireApproval { .. }
         ));
     }
+
+    #[test]
+    fn time_boxed_grants_lapse_to_deny_and_report_once() {
+        let mut engine = StaticPolicyEngine::default();
+        engine.allow_capability_until("example.safe-github", "demo", "repo.read", 100);
+
+        let resolution = engine
+            .resolve_grant_at("example.safe-github", "demo", "repo.read", 99)
+            .expect("resolved");
+        assert_eq!(resolution.effect, GrantEffect::Allow);
+        assert!(engine.take_expired_grants(99).is_empty());
+
+        // The deadline is in the past, so `decide` (which uses the current time) denies.
+        assert_eq!(
+            engine
+                .decide(&make_request(RiskTier::Safe))
+                .expect("decision"),
+            odin_plugin_protocol::PolicyDecision::Deny {
+                reason_code: "capability_grant_expired".to_string()
+            }
+        );
+        assert_eq!(engine.take_expired_grants(100).len(), 1);
+        assert!(engine.take_expired_grants(200).is_empty());
+    }
+
+    #[test]
+    fn explain_walks_the_project_lineage() {
+        let mut engine = StaticPolicyEngine {
+            require_approval_for_destructive: true,
+            ..StaticPolicyEngine::default()
+        };
+        engine.allow_capability("example.safe-github", "*", "repo.read");
+        let mut request = make_request(RiskTier::Destructive);
+        request.capability.project = "acme/api".to_string();
+
+        let explanation = engine.explain(&request).expect("explanation");
+        assert!(matches!(
+            explanation.decision,
+            odin_plugin_protocol::PolicyDecision::RequireApproval { .. }
+        ));
+        let steps: Vec<(&str, bool)> = explanation
+            .steps
+            .iter()
+            .map(|step| (step.rule.as_str(), step.matched))
+            .collect();
+        assert_eq!(
+            steps,
+            vec![
+                ("grant example.safe-github acme/api repo.read", false),
+                ("grant example.safe-github acme repo.read", false),
+                ("grant example.safe-github * repo.read", true),
+                ("require_approval_for_destructive", true),
+            ]
+        );
+    }
+
+    #[test]
+    fn nested_projects_inherit_parent_grants_unless_overridden() {
+        let mut engine = StaticPolicyEngine::default();
+        engine.allow_capability("example.safe-github", "acme", "repo.read");
+        engine.deny_capability("example.safe-github", "acme/legacy", "repo.read");
+
+        let decide = |project: &str| {
+            let mut request = make_request(RiskTier::Safe);
+            request.capability.project = project.to_string();
+            engine.decide(&request).expect("decision")
+        };
+        assert!(matches!(
+            decide("acme/platform/api"),
+            odin_plugin_protocol::PolicyDecision::Allow { .. }
+        ));
+        assert_eq!(
+            decide("acme/legacy/app"),
+            odin_plugin_protocol::PolicyDecision::Deny {
+                reason_code: "capability_denied".to_string()
+            }
+        );
+        assert_eq!(
+            decide("other/api"),
+            odin_plugin_protocol::PolicyDecision::Deny {
+                reason_code: "capability_not_granted".to_string()
+            }
+        );
+
+        let resolution = engine
+            .resolve_grant("example.safe-github", "acme/platform/api", "repo.read")
+            .expect("resolved");
+        assert_eq!(resolution.project, "acme");
+        assert_eq!(resolution.effect, GrantEffect::Allow);
+        assert!(resolution.inherited);
+    }
+
+    #[test]
+    fn a_parent_deny_overrides_a_child_grant() {
+        let mut engine = StaticPolicyEngine::default();
+        engine.deny_capability("example.safe-github", "acme", "repo.read");
+        engine.allow_capability("example.safe-github", "acme/api", "repo.read");
+        let mut request = make_request(RiskTier::Safe);
+        request.capability.project = "acme/api".to_string();
+
+        assert_eq!(
+            engine.decide(&request).expect("decision"),
+            odin_plugin_protocol::PolicyDecision::Deny {
+                reason_code: "capability_denied".to_string()
+            }
+        );
+        let resolution = engine
+            .resolve_grant("example.safe-github", "acme/api", "repo.read")
+            .expect("resolved");
+        assert_eq!(resolution.project, "acme");
+        assert_eq!(resolution.effect, GrantEffect::Deny);
+        assert!(resolution.inherited);
+
+        let steps: Vec<(String, bool)> = engine
+            .explain(&request)
+            .expect("explanation")
+            .steps
+            .into_iter()
+            .map(|step| (step.rule, step.matched))
+            .collect();
+        assert_eq!(
+            steps,
+            vec![
+                (
+                    "grant example.safe-github acme/api repo.read".to_string(),
+                    false
+                ),
+                ("deny example.safe-github acme repo.read".to_string(), true),
+            ]
+        );
+    }
+
+    #[test]
+    fn scope_patterns_match_exact_glob_and_domain_forms() {
+        assert!(scope_pattern_matches("project", "project"));
+        assert!(scope_pattern_matches("repo:acme/*", "repo:acme/api"));
+        assert!(scope_pattern_matches("env-?", "env-1"));
+        assert!(!scope_pattern_matches("env-?", "env-10"));
+        assert!(scope_pattern_matches("*.example.com", "api.example.com"));
+        assert!(scope_pattern_matches("*.example.com", "example.com"));
+        assert!(!scope_pattern_matches("*.example.com", "example.org"));
+    }
+
+    #[test]
+    fn restricted_grants_deny_scopes_outside_their_patterns() {
+        let mut engine = StaticPolicyEngine::default();
+        engine.allow_capability("example.safe-github", "demo", "repo.read");
+        engine.restrict_scopes(
+            "example.safe-github",
+            "demo",
+            "repo.read",
+            &["*.example.com".to_string()],
+        );
+
+        let mut request = make_request(RiskTier::Safe);
+        request.capability.scope = vec!["api.example.com".to_string()];
+        assert!(matches!(
+            engine.decide(&request).expect("decision"),
+            odin_plugin_protocol::PolicyDecision::Allow { .. }
+        ));
+        request.capability.scope = vec!["api.example.com".to_string(), "evil.test".to_string()];
+        assert_eq!(
+            engine.decide(&request).expect("decision"),
+            odin_plugin_protocol::PolicyDecision::Deny {
+                reason_code: "scope_not_granted".to_string()
+            }
+        );
+        request.capability.scope.clear();
+        assert!(matches!(
+            engine.decide(&request).expect("decision"),
+            odin_plugin_protocol::PolicyDecision::Deny { .. }
+        ));
+    }
 }
@@ -0,0 +1,555 @@
+//! Invocation quotas layered over another engine: once a plugin has used a capability `limit`
+//! times in the current hour or day, further requests are denied or escalated to an approval.
+//! Uses are counted when the runtime reports an execution (`record_execution`), not when a
+//! request is decided; in between, `reserve_execution` holds the use in memory so parallel
+//! requests cannot overshoot a quota. Counters can be persisted so a restart does not reset them.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+use odin_plugin_protocol::{ActionRequest, PolicyDecision};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    now_unix, ExpiredGrant, ExplainStep, PolicyEngine, PolicyError, PolicyExplanation, PolicyResult,
+};
+
+pub const QUOTA_EXCEEDED_REASON: &str = "quota_exceeded";
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum QuotaWindow {
+    Hour,
+    Day,
+}
+
+impl QuotaWindow {
+    pub fn secs(self) -> u64 {
+        match self {
+            Self::Hour => 3_600,
+            Self::Day => 86_400,
+        }
+    }
+
+    fn start_of(self, now_unix: u64) -> u64 {
+        now_unix - now_unix % self.secs()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum QuotaAction {
+    #[default]
+    Deny,
+    RequireApproval,
+}
+
+/// Caps how often `plugin` may use `capability` per window; `*` matches any. Each plugin and
+/// capability pair is counted separately, across projects.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct QuotaRule {
+    pub plugin: String,
+    pub capability: String,
+    pub limit: u64,
+    pub window: QuotaWindow,
+    #[serde(default)]
+    pub on_exceeded: QuotaAction,
+}
+
+impl QuotaRule {
+    fn covers(&self, request: &ActionRequest) -> bool {
+        let cap = &request.capability;
+        (self.plugin == "*" || self.plugin == cap.plugin)
+            && (self.capability == "*" || self.capability == cap.capability)
+    }
+
+    fn used(&self, counters: &[QuotaCounter], request: &ActionRequest, now_unix: u64) -> u64 {
+        counters
+            .iter()
+            .find(|counter| counter.is_slot(request, self.window, now_unix))
+            .map_or(0, |counter| counter.count)
+    }
+}
+
+/// The strictest action among the exceeded quotas: a deny beats an approval requirement.
+fn strictest(current: Option<QuotaAction>, next: QuotaAction) -> Option<QuotaAction> {
+    match (current, next) {
+        (Some(QuotaAction::Deny), _) | (_, QuotaAction::Deny) => Some(QuotaAction::Deny),
+        _ => Some(QuotaAction::RequireApproval),
+    }
+}
+
+fn exceeded_decision(action: QuotaAction, request: &ActionRequest) -> PolicyDecision {
+    match action {
+        QuotaAction::Deny => PolicyDecision::Deny {
+            reason_code: QUOTA_EXCEEDED_REASON.to_string(),
+        },
+        QuotaAction::RequireApproval => PolicyDecision::RequireApproval {
+            reason_code: QUOTA_EXCEEDED_REASON.to_string(),
+            tier: request.risk_tier.clone(),
+        },
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct QuotaCounter {
+    pub plugin: String,
+    pub capability: String,
+    pub window: QuotaWindow,
+    /// Start of the fixed window the count belongs to.
+    pub window_start_unix: u64,
+    pub count: u64,
+}
+
+impl QuotaCounter {
+    fn slot(request: &ActionRequest, window: QuotaWindow, now_unix: u64) -> Self {
+        Self {
+            plugin: request.capability.plugin.clone(),
+            capability: request.capability.capability.clone(),
+            window,
+            window_start_unix: window.start_of(now_unix),
+            count: 1,
+        }
+    }
+
+    fn is_slot(&self, request: &ActionRequest, window: QuotaWindow, now_unix: u64) -> bool {
+        self.plugin == request.capability.plugin
+            && self.capability == request.capability.capability
+            && self.window == window
+            && self.window_start_unix == window.start_of(now_unix)
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CounterFile {
+    schema_version: u32,
+    #[serde(default)]
+    counters: Vec<QuotaCounter>,
+}
+
+/// Holds back requests the wrapped engine allows once a quota is spent. Requests it denies or
+/// sends for approval are passed through untouched.
+pub struct QuotaPolicyEngine<P> {
+    inner: P,
+    rules: Vec<QuotaRule>,
+    path: Option<PathBuf>,
+    memory: Mutex<Vec<QuotaCounter>>,
+    /// Uses held by `reserve_execution`, by request id; never persisted.
+    reserved: Mutex<Vec<(String, QuotaCounter)>>,
+}
+
+impl<P: PolicyEngine> QuotaPolicyEngine<P> {
+    pub fn new(inner: P) -> Self {
+        Self {
+            inner,
+            rules: Vec::new(),
+            path: None,
+            memory: Mutex::new(Vec::new()),
+            reserved: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn with_quota(mut self, rule: QuotaRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Persists counters as JSON at `path`, re-read on every decision like the elevation
+    /// overlay.
+    pub fn with_state_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// Counters for windows still open at `now_unix`.
+    pub fn counters(&self, now_unix: u64) -> PolicyResult<Vec<QuotaCounter>> {
+        let memory = self.lock()?;
+        Ok(self
+            .load(&memory)?
+            .into_iter()
+            .filter(|counter| counter.window_start_unix + counter.window.secs() > now_unix)
+            .collect())
+    }
+
+    pub fn decide_at(
+        &self,
+        request: &ActionRequest,
+        now_unix: u64,
+    ) -> PolicyResult<PolicyDecision> {
+        let decision = self.inner.decide(request)?;
+        if !matches!(decision, PolicyDecision::Allow { .. }) {
+            return Ok(decision);
+        }
+        let rules = self.covering(request);
+        if rules.is_empty() {
+            return Ok(decision);
+        }
+
+        let memory = self.lock()?;
+        let counters = self.load(&memory)?;
+        let reserved = self.lock_reserved()?;
+        Ok(self
+            .exceeded(&rules, &counters, &reserved, request, now_unix)
+            .map_or(decision, |action| exceeded_decision(action, request)))
+    }
+
+    /// Holds one use of `request` against every quota covering it until `record_at` or
+    /// `release`, unless a quota is already spent; then returns the decision that replaces the
+    /// allow.
+    pub fn reserve_at(
+        &self,
+        request: &ActionRequest,
+        now_unix: u64,
+    ) -> PolicyResult<Option<PolicyDecision>> {
+        let rules = self.covering(request);
+        if rules.is_empty() {
+            return Ok(None);
+        }
+        let memory = self.lock()?;
+        let counters = self.load(&memory)?;
+        let mut reserved = self.lock_reserved()?;
+        if let Some(action) = self.exceeded(&rules, &counters, &reserved, request, now_unix) {
+            return Ok(Some(exceeded_decision(action, request)));
+        }
+        for window in windows(&rules) {
+            reserved.push((
+                request.request_id.clone(),
+                QuotaCounter::slot(request, window, now_unix),
+            ));
+        }
+        Ok(None)
+    }
+
+    /// Drops the uses `reserve_at` held for `request`.
+    pub fn release(&self, request: &ActionRequest) -> PolicyResult<()> {
+        self.lock_reserved()?.retain(|(request_id, counter)| {
+            request_id != &request.request_id
+                || counter.plugin != request.capability.plugin
+                || counter.capability != request.capability.capability
+        });
+        Ok(())
+    }
+
+    /// Counts one use of `request` against every quota covering it, as of `now_unix`, in place
+    /// of any use `reserve_at` held for it.
+    pub fn record_at(&self, request: &ActionRequest, now_unix: u64) -> PolicyResult<()> {
+        let rules = self.covering(request);
+        if rules.is_empty() {
+            return Ok(());
+        }
+
+        let mut memory = self.lock()?;
+        let mut counters = self.load(&memory)?;
+        counters.retain(|counter| counter.window_start_unix + counter.window.secs() > now_unix);
+        for window in windows(&rules) {
+            match counters
+                .iter_mut()
+                .find(|counter| counter.is_slot(request, window, now_unix))
+            {
+                Some(counter) => counter.count += 1,
+                None => counters.push(QuotaCounter::slot(request, window, now_unix)),
+            }
+        }
+        self.store(&mut memory, counters)?;
+        self.release(request)
+    }
+
+    fn covering(&self, request: &ActionRequest) -> Vec<&QuotaRule> {
+        self.rules
+            .iter()
+            .filter(|rule| rule.covers(request))
+            .collect()
+    }
+
+    /// The strictest action among `rules` whose recorded plus reserved uses reach the limit.
+    fn exceeded(
+        &self,
+        rules: &[&QuotaRule],
+        counters: &[QuotaCounter],
+        reserved: &[(String, QuotaCounter)],
+        request: &ActionRequest,
+        now_unix: u64,
+    ) -> Option<QuotaAction> {
+        let mut exceeded: Option<QuotaAction> = None;
+        for rule in rules {
+            let held = reserved
+                .iter()
+                .filter(|(_, counter)| counter.is_slot(request, rule.window, now_unix))
+                .count() as u64;
+            if rule.used(counters, request, now_unix) + held >= rule.limit {
+                exceeded = strictest(exceeded, rule.on_exceeded);
+            }
+        }
+        exceeded
+    }
+
+    /// `explain` as of `now_unix`; reports usage against each quota without counting the request.
+    pub fn explain_at(
+        &self,
+        request: &ActionRequest,
+        now_unix: u64,
+    ) -> PolicyResult<PolicyExplanation> {
+        let mut explanation = self.inner.explain(request)?;
+        if !matches!(explanation.decision, PolicyDecision::Allow { .. }) {
+            return Ok(explanation);
+        }
+        let memory = self.lock()?;
+        let counters = self.load(&memory)?;
+        let mut exceeded: Option<QuotaAction> = None;
+        for (index, rule) in self.rules.iter().enumerate() {
+            let name = format!("quotas[{index}]");
+            if !rule.covers(request) {
+                explanation.steps.push(ExplainStep::skipped(
+                    name,
+                    "covers another plugin or capability",
+                ));
+                continue;
+            }
+            let used = rule.used(&counters, request, now_unix);
+            let detail = format!("{used} of {} uses spent in the current window", rule.limit);
+            if used >= rule.limit {
+                exceeded = strictest(exceeded, rule.on_exceeded);
+                explanation.steps.push(ExplainStep::matched(name, detail));
+            } else {
+                explanation.steps.push(ExplainStep::skipped(name, detail));
+            }
+        }
+        if let Some(action) = exceeded {
+            explanation.decision = exceeded_decision(action, request);
+        }
+        Ok(explanation)
+    }
+
+    fn lock(&self) -> PolicyResult<MutexGuard<'_, Vec<QuotaCounter>>> {
+        self.memory
+            .lock()
+            .map_err(|_| PolicyError::Evaluation("quota counters lock poisoned".to_string()))
+    }
+
+    /// Taken after `lock` when both are needed.
+    fn lock_reserved(&self) -> PolicyResult<MutexGuard<'_, Vec<(String, QuotaCounter)>>> {
+        self.reserved
+            .lock()
+            .map_err(|_| PolicyError::Evaluation("quota reservations lock poisoned".to_string()))
+    }
+
+    fn load(&self, memory: &[QuotaCounter]) -> PolicyResult<Vec<QuotaCounter>> {
+        let Some(path) = &self.path else {
+            return Ok(memory.to_vec());
+        };
+        match fs::read(path) {
+            Ok(raw) => serde_json::from_slice::<CounterFile>(&raw)
+                .map(|file| file.counters)
+                .map_err(|e| {
+                    PolicyError::Evaluation(format!(
+                        "corrupt quota counters {}: {e}",
+                        path.display()
+                    ))
+                }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(io_err("reading", path, e)),
+        }
+    }
+
+    fn store(
+        &self,
+        memory: &mut Vec<QuotaCounter>,
+        counters: Vec<QuotaCounter>,
+    ) -> PolicyResult<()> {
+        let Some(path) = &self.path else {
+            *memory = counters;
+            return Ok(());
+        };
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(parent).map_err(|e| io_err("creating dir for", parent, e))?;
+        }
+        let body = serde_json::to_vec_pretty(&CounterFile {
+            schema_version: 1,
+            counters,
+        })
+        .map_err(|e| PolicyError::Evaluation(format!("failed serializing quota counters: {e}")))?;
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, body)
+            .and_then(|_| fs::rename(&tmp, path))
+            .map_err(|e| io_err("writing", path, e))
+    }
+}
+
+fn windows(rules: &[&QuotaRule]) -> Vec<QuotaWindow> {
+    let mut windows: Vec<QuotaWindow> = rules.iter().map(|rule| rule.window).collect();
+    windows.sort();
+    windows.dedup();
+    windows
+}
+
+fn io_err(action: &str, path: &Path, err: std::io::Error) -> PolicyError {
+    PolicyError::Evaluation(format!(
+        "failed {action} quota counters {}: {err}",
+        path.display()
+    ))
+}
+
+impl<P: PolicyEngine> PolicyEngine for QuotaPolicyEngine<P> {
+    fn decide(&self, request: &ActionRequest) -> PolicyResult<PolicyDecision> {
+        self.decide_at(request, now_unix())
+    }
+
+    fn version(&self) -> Option<String> {
+        self.inner.version()
+    }
+
+    fn explain(&self, request: &ActionRequest) -> PolicyResult<PolicyExplanation> {
+        self.explain_at(request, now_unix())
+    }
+
+    fn take_expired_grants(&self, now_unix: u64) -> Vec<ExpiredGrant> {
+        self.inner.take_expired_grants(now_unix)
+    }
+
+    fn record_execution(&self, request: &ActionRequest) -> PolicyResult<()> {
+        self.inner.record_execution(request)?;
+        self.record_at(request, now_unix())
+    }
+
+    fn reserve_execution(&self, request: &ActionRequest) -> PolicyResult<Option<PolicyDecision>> {
+        if let Some(decision) = self.inner.reserve_execution(request)? {
+            return Ok(Some(decision));
+        }
+        let held = self.reserve_at(request, now_unix());
+        if !matches!(held, Ok(None)) {
+            self.inner.release_execution(request)?;
+        }
+        held
+    }
+
+    fn release_execution(&self, request: &ActionRequest) -> PolicyResult<()> {
+        self.inner.release_execution(request)?;
+        self.release(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use odin_plugin_protocol::{CapabilityRequest, RiskTier};
+
+    use super::*;
+    use crate::StaticPolicyEngine;
+
+    fn request() -> ActionRequest {
+        ActionRequest {
+            request_id: "req-1".to_string(),
+            risk_tier: RiskTier::Safe,
+            capability: CapabilityRequest {
+                plugin: "example.safe-github".to_string(),
+                project: "demo".to_string(),
+                capability: "repo.read".to_string(),
+                scope: vec!["project".to_string()],
+                reason: "test".to_string(),
+            },
+            trace_id: None,
+            input: serde_json::Value::Null,
+            requested_by: None,
+        }
+    }
+
+    fn engine(path: &Path, on_exceeded: QuotaAction) -> QuotaPolicyEngine<StaticPolicyEngine> {
+        let mut inner = StaticPolicyEngine::default();
+        inner.allow_capability("example.safe-github", "*", "repo.read");
+        QuotaPolicyEngine::new(inner)
+            .with_quota(QuotaRule {
+                plugin: "*".to_string(),
+                capability: "repo.read".to_string(),
+                limit: 2,
+                window: QuotaWindow::Hour,
+                on_exceeded,
+            })
+            .with_state_file(path)
+    }
+
+    #[test]
+    fn quota_counters_survive_restarts_and_reset_each_window() {
+        let dir = std::env::temp_dir().join(format!("odin-quota-{}", std::process::id()));
+        let path = dir.join("quota.json");
+        let now = 10 * 3_600 + 60;
+
+        let first = engine(&path, QuotaAction::Deny);
+        for _ in 0..2 {
+            // Deciding alone spends nothing; only recorded executions count.
+            for _ in 0..3 {
+                assert!(matches!(
+                    first.decide_at(&request(), now).expect("decision"),
+                    PolicyDecision::Allow { .. }
+                ));
+            }
+            first.record_at(&request(), now).expect("record");
+        }
+
+        // A fresh engine over the same file keeps counting.
+        let restarted = engine(&path, QuotaAction::RequireApproval);
+        assert_eq!(
+            restarted.decide_at(&request(), now + 60).expect("decision"),
+            PolicyDecision::RequireApproval {
+                reason_code: QUOTA_EXCEEDED_REASON.to_string(),
+                tier: RiskTier::Safe,
+            }
+        );
+        assert_eq!(restarted.counters(now).expect("counters")[0].count, 2);
+
+        assert!(matches!(
+            restarted
+                .decide_at(&request(), now + 3_600)
+                .expect("decision"),
+            PolicyDecision::Allow { .. }
+        ));
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn reservations_hold_quota_until_recorded_or_released() {
+        let dir = std::env::temp_dir().join(format!("odin-quota-hold-{}", std::process::id()));
+        let engine = engine(&dir.join("quota.json"), QuotaAction::Deny);
+        let now = 10 * 3_600;
+        let numbered = |id: &str| ActionRequest {
+            request_id: id.to_string(),
+            ..request()
+        };
+        let denied = Some(PolicyDecision::Deny {
+            reason_code: QUOTA_EXCEEDED_REASON.to_string(),
+        });
+
+        assert_eq!(
+            engine.reserve_at(&numbered("a"), now).expect("reserve"),
+            None
+        );
+        assert_eq!(
+            engine.reserve_at(&numbered("b"), now).expect("reserve"),
+            None
+        );
+        // Both uses are in flight: a third request is refused before either is recorded.
+        assert_eq!(
+            engine.reserve_at(&numbered("c"), now).expect("reserve"),
+            denied
+        );
+        assert!(matches!(
+            engine.decide_at(&numbered("c"), now).expect("decision"),
+            PolicyDecision::Deny { .. }
+        ));
+
+        engine.release(&numbered("b")).expect("release");
+        engine.record_at(&numbered("a"), now).expect("record");
+        assert_eq!(engine.counters(now).expect("counters")[0].count, 1);
+        assert_eq!(
+            engine.reserve_at(&numbered("c"), now).expect("reserve"),
+            None
+        );
+        assert_eq!(
+            engine.reserve_at(&numbered("d"), now).expect("reserve"),
+            denied
+        );
+        let _ = fs::remove_dir_all(dir);
+    }
+}
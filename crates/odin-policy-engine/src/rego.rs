@@ -0,0 +1,342 @@
+//! Rego policy backend. The full `ActionRequest` is the Rego `input`, and the queried rule must
+//! produce either a boolean or a document like
+//! `{"decision": "allow" | "deny" | "require_approval", "reason_code": "...", "tier": "..."}`.
+//!
+//! Evaluation goes through `RegoEvaluator`; `OpaEvalEvaluator` shells out to the `opa` binary so
+//! existing OPA bundles work unchanged. Evaluation fails closed: a missing `opa` binary, a
+//! non-zero exit, unreadable output or an evaluation outliving its timeout (the process is
+//! killed) denies with `rego_evaluation_failed`.
+
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use odin_plugin_protocol::{ActionRequest, PolicyDecision, RiskTier};
+use serde_json::Value;
+
+use crate::{PolicyEngine, PolicyError, PolicyResult};
+
+pub const REGO_EVALUATION_FAILED_REASON: &str = "rego_evaluation_failed";
+
+/// How long one `opa eval` may run before it is killed.
+pub const DEFAULT_OPA_EVAL_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub trait RegoEvaluator: Send + Sync {
+    /// Evaluates the configured query against `input`. An undefined result is `Value::Null`.
+    fn evaluate(&self, input: &Value) -> PolicyResult<Value>;
+}
+
+/// Runs `opa eval --stdin-input` with the configured policy and data files.
+#[derive(Clone, Debug)]
+pub struct OpaEvalEvaluator {
+    binary: PathBuf,
+    bundles: Vec<PathBuf>,
+    query: String,
+    timeout: Duration,
+}
+
+impl OpaEvalEvaluator {
+    pub fn new(binary: impl Into<PathBuf>, query: impl Into<String>) -> Self {
+        Self {
+            binary: binary.into(),
+            bundles: Vec::new(),
+            query: query.into(),
+            timeout: DEFAULT_OPA_EVAL_TIMEOUT,
+        }
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Adds a `.rego` file, data file or bundle directory passed to `opa eval --data`.
+    pub fn with_bundle(mut self, path: impl Into<PathBuf>) -> Self {
+        self.bundles.push(path.into());
+        self
+    }
+}
+
+impl RegoEvaluator for OpaEvalEvaluator {
+    fn evaluate(&self, input: &Value) -> PolicyResult<Value> {
+        let mut command = Command::new(&self.binary);
+        command.args(["eval", "--format", "json", "--stdin-input"]);
+        for bundle in &self.bundles {
+            command.arg("--data").arg(bundle);
+        }
+        let mut child = command
+            .arg(&self.query)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| {
+                PolicyError::Evaluation(format!("failed to start {}: {e}", self.binary.display()))
+            })?;
+        // Input, stdout and stderr go through threads so a stalled `opa` cannot block past the
+        // deadline; killing it closes the pipes and ends them.
+        let stdin = child.stdin.take().map(|mut stdin| {
+            let input = input.to_string();
+            thread::spawn(move || stdin.write_all(input.as_bytes()))
+        });
+        let drain = |pipe: Option<Box<dyn Read + Send>>| {
+            thread::spawn(move || {
+                let mut bytes = Vec::new();
+                if let Some(mut pipe) = pipe {
+                    let _ = pipe.read_to_end(&mut bytes);
+                }
+                bytes
+            })
+        };
+        let stdout = drain(
+            child
+                .stdout
+                .take()
+                .map(|p| Box::new(p) as Box<dyn Read + Send>),
+        );
+        let stderr = drain(
+            child
+                .stderr
+                .take()
+                .map(|p| Box::new(p) as Box<dyn Read + Send>),
+        );
+
+        let deadline = Instant::now() + self.timeout;
+        let status = loop {
+            match child.try_wait() {
+                Ok(Some(status)) => break status,
+                Ok(None) if Instant::now() >= deadline => {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Err(PolicyError::Evaluation(format!(
+                        "opa eval timed out after {:?}",
+                        self.timeout
+                    )));
+                }
+                Ok(None) => thread::sleep(Duration::from_millis(5)),
+                Err(e) => {
+                    let _ = child.kill();
+                    return Err(PolicyError::Evaluation(format!("opa eval failed: {e}")));
+                }
+            }
+        };
+        if let Some(Ok(Err(e))) = stdin.map(thread::JoinHandle::join) {
+            return Err(PolicyError::Evaluation(format!(
+                "failed writing rego input: {e}"
+            )));
+        }
+        let stdout = stdout.join().unwrap_or_default();
+        let stderr = stderr.join().unwrap_or_default();
+        if !status.success() {
+            return Err(PolicyError::Evaluation(format!(
+                "opa eval exited with {}: {}",
+                status,
+                String::from_utf8_lossy(&stderr).trim()
+            )));
+        }
+        let body: Value = serde_json::from_slice(&stdout)
+            .map_err(|e| PolicyError::Evaluation(format!("invalid opa eval output: {e}")))?;
+        Ok(body
+            .pointer("/result/0/expressions/0/value")
+            .cloned()
+            .unwrap_or(Value::Null))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RegoPolicyEngine<E> {
+    evaluator: E,
+}
+
+impl<E: RegoEvaluator> RegoPolicyEngine<E> {
+    pub fn new(evaluator: E) -> Self {
+        Self { evaluator }
+    }
+}
+
+/// Maps a Rego result document onto a decision. An undefined result denies; a malformed one is an
+/// evaluation error, which the runtime also treats as a block.
+pub fn decision_from_result(
+    result: &Value,
+    request: &ActionRequest,
+) -> PolicyResult<PolicyDecision> {
+    let reason = |default: &str| {
+        result
+            .get("reason_code")
+            .and_then(Value::as_str)
+            .unwrap_or(default)
+            .to_string()
+    };
+    match result {
+        Value::Null => Ok(PolicyDecision::Deny {
+            reason_code: "rego_undefined".to_string(),
+        }),
+        Value::Bool(true) => Ok(PolicyDecision::Allow {
+            reason_code: "rego_allowed".to_string(),
+        }),
+        Value::Bool(false) => Ok(PolicyDecision::Deny {
+            reason_code: "rego_denied".to_string(),
+        }),
+        Value::Object(_) => match result.get("decision").and_then(Value::as_str) {
+            Some("allow") => Ok(PolicyDecision::Allow {
+                reason_code: reason("rego_allowed"),
+            }),
+            Some("deny") => Ok(PolicyDecision::Deny {
+                reason_code: reason("rego_denied"),
+            }),
+            Some("require_approval") => {
+                let tier = match result.get("tier") {
+                    Some(tier) => {
+                        serde_json::from_value::<RiskTier>(tier.clone()).map_err(|e| {
+                            PolicyError::Evaluation(format!("invalid tier in rego result: {e}"))
+                        })?
+                    }
+                    None => request.risk_tier.clone(),
+                };
+                Ok(PolicyDecision::RequireApproval {
+                    reason_code: reason("rego_requires_approval"),
+                    tier,
+                })
+            }
+            other => Err(PolicyError::Evaluation(format!(
+                "rego result has unknown decision {other:?}"
+            ))),
+        },
+        other => Err(PolicyError::Evaluation(format!(
+            "rego result must be a boolean or object, got {other}"
+        ))),
+    }
+}
+
+impl<E: RegoEvaluator> PolicyEngine for RegoPolicyEngine<E> {
+    fn decide(&self, request: &ActionRequest) -> PolicyResult<PolicyDecision> {
+        let input = serde_json::to_value(request)
+            .map_err(|e| PolicyError::InvalidRequest(format!("unserializable request: {e}")))?;
+        let result = match self.evaluator.evaluate(&input) {
+            Ok(result) => result,
+            Err(err) => {
+                tracing::warn!(%err, "rego evaluation failed; denying");
+                return Ok(PolicyDecision::Deny {
+                    reason_code: REGO_EVALUATION_FAILED_REASON.to_string(),
+                });
+            }
+        };
+        decision_from_result(&result, request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use odin_plugin_protocol::CapabilityRequest;
+    use serde_json::json;
+
+    use super::*;
+
+    /// Stands in for a compiled policy: protects `main` and escalates writes to other branches.
+    struct BranchGuard;
+
+    impl RegoEvaluator for BranchGuard {
+        fn evaluate(&self, input: &Value) -> PolicyResult<Value> {
+            match input.pointer("/input/branch").and_then(Value::as_str) {
+                Some("main") => Ok(json!({"decision": "deny", "reason_code": "main_protected"})),
+                Some(_) => Ok(json!({"decision": "require_approval", "tier": "sensitive"})),
+                None => Ok(Value::Null),
+            }
+        }
+    }
+
+    fn request(input: Value) -> ActionRequest {
+        ActionRequest {
+            request_id: "req-1".to_string(),
+            risk_tier: RiskTier::Safe,
+            capability: CapabilityRequest {
+                plugin: "example.safe-github".to_string(),
+                project: "demo".to_string(),
+                capability: "repo.write".to_string(),
+                scope: vec!["project".to_string()],
+                reason: "test".to_string(),
+            },
+            trace_id: None,
+            input,
+            requested_by: None,
+        }
+    }
+
+    #[test]
+    fn result_documents_map_to_decisions() {
+        let engine = RegoPolicyEngine::new(BranchGuard);
+        assert_eq!(
+            engine
+                .decide(&request(json!({"branch": "main"})))
+                .expect("decision"),
+            PolicyDecision::Deny {
+                reason_code: "main_protected".to_string()
+            }
+        );
+        assert_eq!(
+            engine
+                .decide(&request(json!({"branch": "feature"})))
+                .expect("decision"),
+            PolicyDecision::RequireApproval {
+                reason_code: "rego_requires_approval".to_string(),
+                tier: RiskTier::Sensitive,
+            }
+        );
+        assert_eq!(
+            engine.decide(&request(Value::Null)).expect("decision"),
+            PolicyDecision::Deny {
+                reason_code: "rego_undefined".to_string()
+            }
+        );
+        assert!(decision_from_result(&json!("yes"), &request(Value::Null)).is_err());
+    }
+
+    #[test]
+    fn opa_failures_deny() {
+        let failed = PolicyDecision::Deny {
+            reason_code: REGO_EVALUATION_FAILED_REASON.to_string(),
+        };
+        let decide = |binary: &str| {
+            RegoPolicyEngine::new(OpaEvalEvaluator::new(binary, "data.odin.allow"))
+                .decide(&request(Value::Null))
+                .expect("decision")
+        };
+
+        assert_eq!(decide("/nonexistent/odin-test/opa"), failed);
+        #[cfg(unix)]
+        {
+            // `false` exits non-zero; `true` exits cleanly with no output to parse.
+            assert_eq!(decide("false"), failed);
+            assert_eq!(decide("true"), failed);
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn opa_evaluations_past_the_timeout_are_killed_and_deny() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("odin-opa-hang-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("create dir");
+        let opa = dir.join("opa");
+        std::fs::write(&opa, "#!/bin/sh\nexec sleep 30\n").expect("write opa");
+        std::fs::set_permissions(&opa, std::fs::Permissions::from_mode(0o755)).expect("chmod");
+
+        let started = Instant::now();
+        let decision = RegoPolicyEngine::new(
+            OpaEvalEvaluator::new(&opa, "data.odin.allow").with_timeout(Duration::from_millis(200)),
+        )
+        .decide(&request(Value::Null))
+        .expect("decision");
+        assert_eq!(
+            decision,
+            PolicyDecision::Deny {
+                reason_code: REGO_EVALUATION_FAILED_REASON.to_string()
+            }
+        );
+        assert!(started.elapsed() < Duration::from_secs(10));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
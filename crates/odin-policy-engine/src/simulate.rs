@@ -0,0 +1,156 @@
+//! What-if evaluation: replays hypothetical requests against a candidate policy and the active
+//! one, so a policy change can be reviewed by its effect before it is deployed.
+
+use odin_plugin_protocol::{ActionRequest, PolicyDecision};
+use serde::Serialize;
+
+use crate::composite::restrictiveness;
+use crate::{PolicyEngine, PolicyResult};
+
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DecisionChange {
+    Unchanged,
+    /// Same kind of decision for a different reason.
+    ReasonChanged,
+    /// The candidate is more permissive, e.g. deny -> allow.
+    Loosened,
+    /// The candidate is stricter, e.g. allow -> require_approval.
+    Tightened,
+}
+
+impl DecisionChange {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Unchanged => "unchanged",
+            Self::ReasonChanged => "reason_changed",
+            Self::Loosened => "loosened",
+            Self::Tightened => "tightened",
+        }
+    }
+
+    fn between(active: &PolicyDecision, candidate: &PolicyDecision) -> Self {
+        match restrictiveness(candidate).cmp(&restrictiveness(active)) {
+            std::cmp::Ordering::Less => Self::Loosened,
+            std::cmp::Ordering::Greater => Self::Tightened,
+            std::cmp::Ordering::Equal if active == candidate => Self::Unchanged,
+            std::cmp::Ordering::Equal => Self::ReasonChanged,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+pub struct SimulatedRequest {
+    pub request_id: String,
+    pub plugin: String,
+    pub project: String,
+    pub capability: String,
+    pub active: PolicyDecision,
+    pub candidate: PolicyDecision,
+    pub change: DecisionChange,
+}
+
+#[derive(Clone, Debug, Default, Serialize, PartialEq, Eq)]
+pub struct SimulationReport {
+    pub requests: Vec<SimulatedRequest>,
+}
+
+impl SimulationReport {
+    /// Requests whose decision differs between the two policies.
+    pub fn changes(&self) -> impl Iterator<Item = &SimulatedRequest> {
+        self.requests
+            .iter()
+            .filter(|request| request.change != DecisionChange::Unchanged)
+    }
+
+    pub fn has_changes(&self) -> bool {
+        self.changes().next().is_some()
+    }
+}
+
+/// Decides every request with both engines. Neither engine's side effects are suppressed, so pass
+/// undecorated engines (no quota counters) when simulating. The first evaluation error fails the
+/// run.
+pub fn simulate(
+    active: &dyn PolicyEngine,
+    candidate: &dyn PolicyEngine,
+    requests: &[ActionRequest],
+) -> PolicyResult<SimulationReport> {
+    let mut report = SimulationReport::default();
+    for request in requests {
+        let active = active.decide(request)?;
+        let candidate = candidate.decide(request)?;
+        report.requests.push(SimulatedRequest {
+            request_id: request.request_id.clone(),
+            plugin: request.capability.plugin.clone(),
+            project: request.capability.project.clone(),
+            capability: request.capability.capability.clone(),
+            change: DecisionChange::between(&active, &candidate),
+            active,
+            candidate,
+        });
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use odin_plugin_protocol::{CapabilityRequest, RiskTier};
+
+    use super::*;
+    use crate::StaticPolicyEngine;
+
+    fn request(request_id: &str, capability: &str) -> ActionRequest {
+        ActionRequest {
+            request_id: request_id.to_string(),
+            risk_tier: RiskTier::Destructive,
+            capability: CapabilityRequest {
+                plugin: "example.safe-github".to_string(),
+                project: "acme/api".to_string(),
+                capability: capability.to_string(),
+                scope: vec!["project".to_string()],
+                reason: "test".to_string(),
+            },
+            trace_id: None,
+            input: serde_json::Value::Null,
+            requested_by: None,
+        }
+    }
+
+    #[test]
+    fn reports_loosened_and_tightened_decisions() {
+        let mut active = StaticPolicyEngine::default();
+        active.allow_capability("example.safe-github", "acme", "repo.read");
+        let mut candidate = StaticPolicyEngine {
+            require_approval_for_destructive: true,
+            ..StaticPolicyEngine::default()
+        };
+        candidate.allow_capability("example.safe-github", "acme", "repo.read");
+        candidate.allow_capability("example.safe-github", "acme", "repo.write");
+
+        let report = simulate(
+            &active,
+            &candidate,
+            &[
+                request("read", "repo.read"),
+                request("write", "repo.write"),
+                request("delete", "repo.delete"),
+            ],
+        )
+        .expect("report");
+        let changes: Vec<(&str, DecisionChange)> = report
+            .requests
+            .iter()
+            .map(|request| (request.request_id.as_str(), request.change))
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                ("read", DecisionChange::Tightened),
+                ("write", DecisionChange::Loosened),
+                ("delete", DecisionChange::Unchanged),
+            ]
+        );
+        assert_eq!(report.changes().count(), 2);
+    }
+}
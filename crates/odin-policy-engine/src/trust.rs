@@ -0,0 +1,238 @@
+//! Trust levels from plugin permission envelopes layered over another engine. Untrusted plugins
+//! may only use allow-listed capabilities at the `safe` tier, `caution` plugins need an approval
+//! for anything `sensitive` or riskier, and trusted plugins get the inner engine's decision.
+
+use std::collections::{HashMap, HashSet};
+
+use odin_plugin_protocol::{
+    ActionRequest, PluginPermissionEnvelope, PolicyDecision, RiskTier, TrustLevel,
+};
+
+use crate::{ExpiredGrant, ExplainStep, PolicyEngine, PolicyExplanation, PolicyResult};
+
+pub const UNTRUSTED_REASON: &str = "untrusted_plugin_capability";
+pub const CAUTION_REASON: &str = "caution_plugin_requires_approval";
+
+/// Plugins without an envelope are treated as untrusted, like unregistered skills.
+pub struct TrustPolicyEngine<P> {
+    inner: P,
+    trust: HashMap<String, TrustLevel>,
+    untrusted_allowlist: HashSet<String>,
+}
+
+impl<P: PolicyEngine> TrustPolicyEngine<P> {
+    pub fn new(inner: P) -> Self {
+        Self {
+            inner,
+            trust: HashMap::new(),
+            untrusted_allowlist: HashSet::new(),
+        }
+    }
+
+    pub fn with_envelope(mut self, envelope: &PluginPermissionEnvelope) -> Self {
+        self.trust
+            .insert(envelope.plugin.clone(), envelope.trust_level.clone());
+        self
+    }
+
+    /// Lets untrusted plugins use `capability` for `safe` requests the inner engine grants.
+    pub fn with_untrusted_capability(mut self, capability: impl Into<String>) -> Self {
+        self.untrusted_allowlist.insert(capability.into());
+        self
+    }
+
+    pub fn trust_level(&self, plugin: &str) -> TrustLevel {
+        self.trust
+            .get(plugin)
+            .cloned()
+            .unwrap_or(TrustLevel::Untrusted)
+    }
+
+    /// The trust-level override for a request, and why it does or does not apply.
+    fn restrict(&self, request: &ActionRequest) -> (Option<PolicyDecision>, String) {
+        let cap = &request.capability;
+        match self.trust_level(&cap.plugin) {
+            TrustLevel::Trusted => (None, "trusted plugin follows the grants".to_string()),
+            TrustLevel::Caution if request.risk_tier == RiskTier::Safe => {
+                (None, "caution plugin making a safe request".to_string())
+            }
+            TrustLevel::Caution => (
+                Some(PolicyDecision::RequireApproval {
+                    reason_code: CAUTION_REASON.to_string(),
+                    tier: request.risk_tier.clone(),
+                }),
+                "caution plugin needs approval above the safe tier".to_string(),
+            ),
+            TrustLevel::Untrusted
+                if request.risk_tier == RiskTier::Safe
+                    && self.untrusted_allowlist.contains(&cap.capability) =>
+            {
+                (
+                    None,
+                    format!("{} is allow-listed for untrusted plugins", cap.capability),
+                )
+            }
+            TrustLevel::Untrusted => (
+                Some(PolicyDecision::Deny {
+                    reason_code: UNTRUSTED_REASON.to_string(),
+                }),
+                "untrusted plugins only get allow-listed safe capabilities".to_string(),
+            ),
+        }
+    }
+}
+
+/// Only tightens: a deny or approval requirement from the inner engine is returned as-is.
+fn apply(decision: PolicyDecision, restriction: Option<PolicyDecision>) -> PolicyDecision {
+    match (decision, restriction) {
+        (PolicyDecision::Allow { .. }, Some(restricted)) => restricted,
+        (
+            PolicyDecision::RequireApproval { .. },
+            Some(restricted @ PolicyDecision::Deny { .. }),
+        ) => restricted,
+        (decision, _) => decision,
+    }
+}
+
+impl<P: PolicyEngine> PolicyEngine for TrustPolicyEngine<P> {
+    fn decide(&self, request: &ActionRequest) -> PolicyResult<PolicyDecision> {
+        let decision = self.inner.decide(request)?;
+        Ok(apply(decision, self.restrict(request).0))
+    }
+
+    fn version(&self) -> Option<String> {
+        self.inner.version()
+    }
+
+    fn explain(&self, request: &ActionRequest) -> PolicyResult<PolicyExplanation> {
+        let mut explanation = self.inner.explain(request)?;
+        let (restriction, detail) = self.restrict(request);
+        let rule = format!("trust {}", request.capability.plugin);
+        let decision = apply(explanation.decision.clone(), restriction);
+        if decision == explanation.decision {
+            explanation.steps.push(ExplainStep::skipped(rule, detail));
+        } else {
+            explanation.steps.push(ExplainStep::matched(rule, detail));
+            explanation.decision = decision;
+        }
+        Ok(explanation)
+    }
+
+    fn take_expired_grants(&self, now_unix: u64) -> Vec<ExpiredGrant> {
+        self.inner.take_expired_grants(now_unix)
+    }
+
+    fn record_execution(&self, request: &ActionRequest) -> PolicyResult<()> {
+        self.inner.record_execution(request)
+    }
+
+    fn reserve_execution(&self, request: &ActionRequest) -> PolicyResult<Option<PolicyDecision>> {
+        self.inner.reserve_execution(request)
+    }
+
+    fn release_execution(&self, request: &ActionRequest) -> PolicyResult<()> {
+        self.inner.release_execution(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use odin_plugin_protocol::CapabilityRequest;
+
+    use super::*;
+    use crate::StaticPolicyEngine;
+
+    fn request(plugin: &str, capability: &str, risk_tier: RiskTier) -> ActionRequest {
+        ActionRequest {
+            request_id: "req-1".to_string(),
+            risk_tier,
+            capability: CapabilityRequest {
+                plugin: plugin.to_string(),
+                project: "demo".to_string(),
+                capability: capability.to_string(),
+                scope: vec!["project".to_string()],
+                reason: "test".to_string(),
+            },
+            trace_id: None,
+            input: serde_json::Value::Null,
+            requested_by: None,
+        }
+    }
+
+    fn envelope(plugin: &str, trust_level: TrustLevel) -> PluginPermissionEnvelope {
+        PluginPermissionEnvelope {
+            plugin: plugin.to_string(),
+            trust_level,
+            permissions: Vec::new(),
+        }
+    }
+
+    fn engine() -> TrustPolicyEngine<StaticPolicyEngine> {
+        let mut inner = StaticPolicyEngine::default();
+        for plugin in ["trusted", "caution", "untrusted"] {
+            inner.allow_capability(plugin, "*", "repo.read");
+            inner.allow_capability(plugin, "*", "repo.write");
+        }
+        TrustPolicyEngine::new(inner)
+            .with_envelope(&envelope("trusted", TrustLevel::Trusted))
+            .with_envelope(&envelope("caution", TrustLevel::Caution))
+            .with_envelope(&envelope("untrusted", TrustLevel::Untrusted))
+            .with_untrusted_capability("repo.read")
+    }
+
+    #[test]
+    fn trust_levels_tighten_granted_requests() {
+        let engine = engine();
+        let decide = |plugin: &str, capability: &str, tier: RiskTier| {
+            engine
+                .decide(&request(plugin, capability, tier))
+                .expect("decision")
+        };
+
+        assert!(matches!(
+            decide("trusted", "repo.write", RiskTier::Destructive),
+            PolicyDecision::Allow { .. }
+        ));
+        assert!(matches!(
+            decide("caution", "repo.write", RiskTier::Safe),
+            PolicyDecision::Allow { .. }
+        ));
+        assert_eq!(
+            decide("caution", "repo.write", RiskTier::Sensitive),
+            PolicyDecision::RequireApproval {
+                reason_code: CAUTION_REASON.to_string(),
+                tier: RiskTier::Sensitive,
+            }
+        );
+        assert!(matches!(
+            decide("untrusted", "repo.read", RiskTier::Safe),
+            PolicyDecision::Allow { .. }
+        ));
+        for (capability, tier) in [
+            ("repo.write", RiskTier::Safe),
+            ("repo.read", RiskTier::Sensitive),
+        ] {
+            assert_eq!(
+                decide("untrusted", capability, tier),
+                PolicyDecision::Deny {
+                    reason_code: UNTRUSTED_REASON.to_string()
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn plugins_without_an_envelope_are_untrusted_and_grants_still_apply() {
+        let engine = engine();
+        assert_eq!(engine.trust_level("unknown"), TrustLevel::Untrusted);
+        // The allow-list never widens what the inner engine grants.
+        assert_eq!(
+            engine
+                .decide(&request("unknown", "repo.read", RiskTier::Safe))
+                .expect("decision"),
+            PolicyDecision::Deny {
+                reason_code: "capability_not_granted".to_string()
+            }
+        );
+    }
+}
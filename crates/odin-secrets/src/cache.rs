@@ -0,0 +1,193 @@
+//! Short-lived in-memory cache of revealed secret values, for bursts of plugin spawns that would
+//! otherwise hit Vault or the keychain once each. Values are held as `Zeroizing` buffers, so
+//! they are wiped when they expire, are invalidated, or the cache is dropped. Authorization
+//! happens above the cache: wrap the backend with it, then put the policy gate in front.
+//!
+//! ```yaml
+//! secret_cache:
+//!   ttl_secs: 30
+//! ```
+
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+use zeroize::Zeroizing;
+
+use crate::rotation::RotationInfo;
+use crate::{AccessContext, SecretError, SecretHandle, SecretRef, SecretStore};
+
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(30);
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct SecretCacheConfig {
+    pub ttl_secs: u64,
+}
+
+struct CachedValue {
+    value: Zeroizing<Vec<u8>>,
+    cached_at: Instant,
+}
+
+pub struct CachedSecretStore<S> {
+    inner: S,
+    ttl: Duration,
+    entries: Mutex<HashMap<SecretHandle, CachedValue>>,
+}
+
+impl<S: SecretStore> CachedSecretStore<S> {
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            ttl: DEFAULT_CACHE_TTL,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Drops every cached value.
+    pub fn clear(&self) -> Result<(), SecretError> {
+        self.lock()?.clear();
+        Ok(())
+    }
+
+    /// Drops values older than the TTL; reads do this for the handle they touch.
+    pub fn purge_expired(&self) -> Result<usize, SecretError> {
+        let mut entries = self.lock()?;
+        let before = entries.len();
+        entries.retain(|_, cached| cached.cached_at.elapsed() < self.ttl);
+        Ok(before - entries.len())
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, HashMap<SecretHandle, CachedValue>>, SecretError> {
+        self.entries
+            .lock()
+            .map_err(|_| SecretError::Backend("secret cache lock poisoned".to_string()))
+    }
+}
+
+impl<S: SecretStore> SecretStore for CachedSecretStore<S> {
+    fn resolve_secret_handle(
+        &self,
+        handle: &SecretHandle,
+        ctx: &AccessContext,
+    ) -> Result<SecretRef, SecretError> {
+        self.inner.resolve_secret_handle(handle, ctx)
+    }
+
+    fn reveal_secret(
+        &self,
+        handle: &SecretHandle,
+        ctx: &AccessContext,
+    ) -> Result<Zeroizing<Vec<u8>>, SecretError> {
+        {
+            let mut entries = self.lock()?;
+            match entries.get(handle) {
+                Some(cached) if cached.cached_at.elapsed() < self.ttl => {
+                    return Ok(cached.value.clone())
+                }
+                Some(_) => {
+                    entries.remove(handle);
+                }
+                None => {}
+            }
+        }
+        let value = self.inner.reveal_secret(handle, ctx)?;
+        if !self.ttl.is_zero() {
+            self.lock()?.insert(
+                handle.clone(),
+                CachedValue {
+                    value: value.clone(),
+                    cached_at: Instant::now(),
+                },
+            );
+        }
+        Ok(value)
+    }
+
+    /// Invalidates the cached value before rotating, so the old one is never served again.
+    fn rotate(&self, handle: &SecretHandle) -> Result<u64, SecretError> {
+        self.lock()?.remove(handle);
+        self.inner.rotate(handle)
+    }
+
+    fn rotation_info(&self) -> Result<Vec<RotationInfo>, SecretError> {
+        self.inner.rotation_info()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    use super::*;
+
+    /// Returns `<handle>#<n>`, where `n` counts backend round-trips.
+    #[derive(Default)]
+    struct CountingStore {
+        reveals: AtomicU64,
+    }
+
+    impl SecretStore for &CountingStore {
+        fn resolve_secret_handle(
+            &self,
+            handle: &SecretHandle,
+            _ctx: &AccessContext,
+        ) -> Result<SecretRef, SecretError> {
+            Ok(SecretRef {
+                handle: handle.clone(),
+            })
+        }
+
+        fn reveal_secret(
+            &self,
+            handle: &SecretHandle,
+            _ctx: &AccessContext,
+        ) -> Result<Zeroizing<Vec<u8>>, SecretError> {
+            let n = self.reveals.fetch_add(1, Ordering::SeqCst) + 1;
+            Ok(Zeroizing::new(format!("{}#{n}", handle.0).into_bytes()))
+        }
+
+        fn rotate(&self, _handle: &SecretHandle) -> Result<u64, SecretError> {
+            Ok(2)
+        }
+    }
+
+    fn ctx() -> AccessContext {
+        AccessContext {
+            plugin: "example.git".to_string(),
+            project: "demo".to_string(),
+            capability: "repo.push".to_string(),
+            reason: "unit".to_string(),
+        }
+    }
+
+    #[test]
+    fn serves_repeat_reveals_from_memory_until_invalidated() {
+        let backend = CountingStore::default();
+        let cache = CachedSecretStore::new(&backend);
+        let token = SecretHandle("secret://demo/example.git/token".to_string());
+        let reveal = || cache.reveal_secret(&token, &ctx()).expect("reveal");
+
+        assert_eq!(reveal().as_slice(), b"secret://demo/example.git/token#1");
+        assert_eq!(reveal().as_slice(), b"secret://demo/example.git/token#1");
+        assert_eq!(backend.reveals.load(Ordering::SeqCst), 1);
+
+        cache.rotate(&token).expect("rotate");
+        assert_eq!(reveal().as_slice(), b"secret://demo/example.git/token#2");
+        cache.clear().expect("clear");
+        assert_eq!(reveal().as_slice(), b"secret://demo/example.git/token#3");
+
+        let uncached = CachedSecretStore::new(&backend).with_ttl(Duration::ZERO);
+        uncached.reveal_secret(&token, &ctx()).expect("reveal");
+        uncached.reveal_secret(&token, &ctx()).expect("reveal");
+        assert_eq!(backend.reveals.load(Ordering::SeqCst), 5);
+        assert_eq!(uncached.purge_expired().expect("purge"), 0);
+    }
+}
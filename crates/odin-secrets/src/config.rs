@@ -0,0 +1,181 @@
+//! Picks the `SecretStore` backend from the `secrets:` section of the orchestrator config:
+//!
+//! ```yaml
+//! secrets:
+//!   backend: encrypted_file   # handle_only (default) | encrypted_file | keychain | vault
+//!   path: /var/odin/secrets.json
+//!   keyfile: /etc/odin/secrets.key
+//! ```
+
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+use crate::file::{EncryptedFileSecretStore, KeySource};
+use crate::rotation::RotationPolicy;
+use crate::{HandleOnlyStore, SecretError, SecretStore};
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(tag = "backend", rename_all = "snake_case", deny_unknown_fields)]
+pub enum SecretBackendConfig {
+    /// Handles pass through unresolved; nothing is stored.
+    #[default]
+    HandleOnly,
+    /// Exactly one of `keyfile` or `passphrase_env` (the variable holding the passphrase).
+    EncryptedFile {
+        path: PathBuf,
+        #[serde(default)]
+        keyfile: Option<PathBuf>,
+        #[serde(default)]
+        passphrase_env: Option<String>,
+    },
+    /// Requires the `keychain` feature.
+    Keychain {
+        #[serde(default = "default_service")]
+        service: String,
+    },
+    /// Requires the `vault` feature. Authenticates with the token in `token_env`, or with AppRole
+    /// using `role_id` and the secret id in `secret_id_env`.
+    Vault {
+        addr: String,
+        #[serde(default = "default_kv_mount")]
+        mount: String,
+        #[serde(default)]
+        namespace: Option<String>,
+        #[serde(default)]
+        token_env: Option<String>,
+        #[serde(default)]
+        role_id: Option<String>,
+        #[serde(default)]
+        secret_id_env: Option<String>,
+    },
+}
+
+fn default_service() -> String {
+    "odin-orchestrator".to_string()
+}
+
+fn default_kv_mount() -> String {
+    "secret".to_string()
+}
+
+fn env_secret(var: &str) -> Result<String, SecretError> {
+    std::env::var(var)
+        .map_err(|_| SecretError::Backend(format!("secret backend variable {var} is unset")))
+}
+
+impl SecretBackendConfig {
+    pub fn build(&self) -> Result<Box<dyn SecretStore>, SecretError> {
+        self.build_with_rotation(&RotationPolicy::default())
+    }
+
+    /// Like `build`, attaching `rotation`'s hook to backends that rotate in place (the encrypted
+    /// file store).
+    pub fn build_with_rotation(
+        &self,
+        rotation: &RotationPolicy,
+    ) -> Result<Box<dyn SecretStore>, SecretError> {
+        match self {
+            Self::HandleOnly => Ok(Box::new(HandleOnlyStore)),
+            Self::EncryptedFile {
+                path,
+                keyfile,
+                passphrase_env,
+            } => {
+                let key =
+                    match (keyfile, passphrase_env) {
+                        (Some(keyfile), None) => KeySource::Keyfile(keyfile.clone()),
+                        (None, Some(var)) => KeySource::Passphrase(env_secret(var)?),
+                        _ => return Err(SecretError::Backend(
+                            "encrypted_file secrets need exactly one of keyfile or passphrase_env"
+                                .to_string(),
+                        )),
+                    };
+                let store = EncryptedFileSecretStore::open(path, key)?;
+                Ok(match rotation.hook() {
+                    Some(hook) => Box::new(store.with_rotation_hook(hook)),
+                    None => Box::new(store),
+                })
+            }
+            #[cfg(feature = "keychain")]
+            Self::Keychain { service } => {
+                Ok(Box::new(crate::keychain::KeychainSecretStore::new(service)))
+            }
+            #[cfg(not(feature = "keychain"))]
+            Self::Keychain { .. } => Err(SecretError::Backend(
+                "the keychain secret backend needs odin built with the `keychain` feature"
+                    .to_string(),
+            )),
+            #[cfg(feature = "vault")]
+            Self::Vault {
+                addr,
+                mount,
+                namespace,
+                token_env,
+                role_id,
+                secret_id_env,
+            } => {
+                use crate::vault::{HttpVaultTransport, VaultAuth, VaultSecretStore};
+                let auth = match (token_env, role_id, secret_id_env) {
+                    (Some(var), None, None) => VaultAuth::Token(env_secret(var)?),
+                    (None, Some(role_id), Some(var)) => VaultAuth::AppRole {
+                        role_id: role_id.clone(),
+                        secret_id: env_secret(var)?,
+                        mount: "approle".to_string(),
+                    },
+                    _ => {
+                        return Err(SecretError::Backend(
+                            "vault secrets need either token_env or role_id with secret_id_env"
+                                .to_string(),
+                        ))
+                    }
+                };
+                let mut transport = HttpVaultTransport::new(addr);
+                if let Some(namespace) = namespace {
+                    transport = transport.with_namespace(namespace);
+                }
+                Ok(Box::new(
+                    VaultSecretStore::new(transport, auth).with_mount(mount),
+                ))
+            }
+            #[cfg(not(feature = "vault"))]
+            Self::Vault { .. } => Err(SecretError::Backend(
+                "the vault secret backend needs odin built with the `vault` feature".to_string(),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_backends_and_rejects_ambiguous_keys() {
+        let config: SecretBackendConfig = serde_json::from_value(serde_json::json!({
+            "backend": "keychain"
+        }))
+        .expect("keychain");
+        assert_eq!(
+            config,
+            SecretBackendConfig::Keychain {
+                service: "odin-orchestrator".to_string()
+            }
+        );
+        assert!(
+            serde_json::from_value::<SecretBackendConfig>(serde_json::json!({
+                "backend": "keychain",
+                "path": "/tmp/x"
+            }))
+            .is_err()
+        );
+
+        let config = SecretBackendConfig::EncryptedFile {
+            path: PathBuf::from("/nonexistent/secrets.json"),
+            keyfile: Some(PathBuf::from("/nonexistent/key")),
+            passphrase_env: Some("ODIN_SECRETS_PASSPHRASE".to_string()),
+        };
+        let err = config.build().err().expect("ambiguous");
+        assert!(err.to_string().contains("exactly one of"));
+    }
+}
@@ -0,0 +1,527 @@
+//! Secrets kept in one JSON file, each value sealed with ChaCha20-Poly1305. The key comes from
+//! a 32-byte keyfile or from a passphrase through Argon2id, with the salt stored in the file.
+//! Each ciphertext is bound to its handle, so a value copied under another handle fails to
+//! open.
+
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+use argon2::Argon2;
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use crate::rotation::{RotationHook, RotationInfo};
+use crate::{now_unix, AccessContext, SecretError, SecretHandle, SecretRef, SecretStore};
+
+const KEY_CHECK_AAD: &[u8] = b"odin-secrets:key-check";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+#[derive(Clone, Debug)]
+pub enum KeySource {
+    /// 32 raw bytes or 64 hex digits; see `generate_keyfile`.
+    Keyfile(PathBuf),
+    Passphrase(String),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum Kdf {
+    Keyfile,
+    Argon2id { salt: String },
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct SealedSecret {
+    version: u32,
+    nonce: String,
+    ciphertext: String,
+    created_unix: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rotated_unix: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct StoreFile {
+    schema_version: u32,
+    kdf: Kdf,
+    /// Seals a fixed value so opening with the wrong key fails before anything is written.
+    key_check: SealedSecret,
+    #[serde(default)]
+    secrets: BTreeMap<String, SealedSecret>,
+}
+
+pub struct EncryptedFileSecretStore {
+    path: PathBuf,
+    cipher: ChaCha20Poly1305,
+    state: Mutex<StoreFile>,
+    rotation_hook: Option<Box<dyn RotationHook>>,
+}
+
+impl EncryptedFileSecretStore {
+    /// Opens the store at `path`, creating an empty one when it does not exist yet.
+    pub fn open(path: impl Into<PathBuf>, key: KeySource) -> Result<Self, SecretError> {
+        let path = path.into();
+        let existing = match fs::read(&path) {
+            Ok(raw) => Some(serde_json::from_slice::<StoreFile>(&raw).map_err(|e| {
+                SecretError::Backend(format!("corrupt secret store {}: {e}", path.display()))
+            })?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => return Err(io_err("reading", &path, e)),
+        };
+
+        let Some(file) = existing else {
+            let (kdf, cipher) = match &key {
+                KeySource::Keyfile(keyfile) => (Kdf::Keyfile, cipher(&read_keyfile(keyfile)?)),
+                KeySource::Passphrase(passphrase) => {
+                    let salt = random_bytes(SALT_LEN)?;
+                    let key = derive_key(passphrase, &salt)?;
+                    (
+                        Kdf::Argon2id {
+                            salt: to_hex(&salt),
+                        },
+                        cipher(&key),
+                    )
+                }
+            };
+            let now = now_unix();
+            let file = StoreFile {
+                schema_version: 1,
+                kdf,
+                key_check: seal(&cipher, KEY_CHECK_AAD, KEY_CHECK_AAD, 1, now)?,
+                secrets: BTreeMap::new(),
+            };
+            write_store(&path, &file)?;
+            return Ok(Self {
+                path,
+                cipher,
+                state: Mutex::new(file),
+                rotation_hook: None,
+            });
+        };
+
+        let key = match (&file.kdf, &key) {
+            (Kdf::Keyfile, KeySource::Keyfile(keyfile)) => read_keyfile(keyfile)?,
+            (Kdf::Argon2id { salt }, KeySource::Passphrase(passphrase)) => {
+                derive_key(passphrase, &from_hex(salt)?)?
+            }
+            _ => {
+                return Err(SecretError::Unauthorized(format!(
+                    "secret store {} uses a different kind of key",
+                    path.display()
+                )))
+            }
+        };
+        let cipher = cipher(&key);
+        open_sealed(&cipher, KEY_CHECK_AAD, &file.key_check).map_err(|_| {
+            SecretError::Unauthorized(format!("key does not open secret store {}", path.display()))
+        })?;
+        Ok(Self {
+            path,
+            cipher,
+            state: Mutex::new(file),
+            rotation_hook: None,
+        })
+    }
+
+    /// Source of new values for `SecretStore::rotate`.
+    pub fn with_rotation_hook(mut self, hook: impl RotationHook + 'static) -> Self {
+        self.rotation_hook = Some(Box::new(hook));
+        self
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Stores a new secret; fails when the handle already exists.
+    pub fn create(&self, handle: &SecretHandle, value: &[u8]) -> Result<(), SecretError> {
+        let mut state = self.lock()?;
+        if state.secrets.contains_key(&handle.0) {
+            return Err(SecretError::AlreadyExists(handle.0.clone()));
+        }
+        let sealed = seal(&self.cipher, handle.0.as_bytes(), value, 1, now_unix())?;
+        state.secrets.insert(handle.0.clone(), sealed);
+        self.persist(&mut state, |state| {
+            state.secrets.remove(&handle.0);
+        })
+    }
+
+    /// Replaces an existing secret's value and returns its new version.
+    pub fn rotate_to(&self, handle: &SecretHandle, value: &[u8]) -> Result<u32, SecretError> {
+        let mut state = self.lock()?;
+        let Some(previous) = state.secrets.get(&handle.0).cloned() else {
+            return Err(SecretError::NotFound(handle.0.clone()));
+        };
+        let now = now_unix();
+        let mut sealed = seal(
+            &self.cipher,
+            handle.0.as_bytes(),
+            value,
+            previous.version + 1,
+            previous.created_unix,
+        )?;
+        sealed.rotated_unix = Some(now);
+        let version = sealed.version;
+        state.secrets.insert(handle.0.clone(), sealed);
+        self.persist(&mut state, |state| {
+            state.secrets.insert(handle.0.clone(), previous);
+        })?;
+        Ok(version)
+    }
+
+    pub fn delete(&self, handle: &SecretHandle) -> Result<(), SecretError> {
+        let mut state = self.lock()?;
+        let Some(previous) = state.secrets.remove(&handle.0) else {
+            return Err(SecretError::NotFound(handle.0.clone()));
+        };
+        self.persist(&mut state, |state| {
+            state.secrets.insert(handle.0.clone(), previous);
+        })
+    }
+
+    /// Decrypts a secret's value. Callers hand it to the plugin and drop it; it is zeroed on drop.
+    pub fn reveal(&self, handle: &SecretHandle) -> Result<Zeroizing<Vec<u8>>, SecretError> {
+        let state = self.lock()?;
+        let sealed = state
+            .secrets
+            .get(&handle.0)
+            .ok_or_else(|| SecretError::NotFound(handle.0.clone()))?;
+        open_sealed(&self.cipher, handle.0.as_bytes(), sealed)
+    }
+
+    /// The stored handle and version of each secret, in handle order.
+    pub fn handles(&self) -> Result<Vec<(SecretHandle, u32)>, SecretError> {
+        Ok(self
+            .lock()?
+            .secrets
+            .iter()
+            .map(|(handle, sealed)| (SecretHandle(handle.clone()), sealed.version))
+            .collect())
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, StoreFile>, SecretError> {
+        self.state
+            .lock()
+            .map_err(|_| SecretError::Backend("secret store lock poisoned".to_string()))
+    }
+
+    /// Writes the store, running `undo` on the in-memory state when the write fails.
+    fn persist(
+        &self,
+        state: &mut StoreFile,
+        undo: impl FnOnce(&mut StoreFile),
+    ) -> Result<(), SecretError> {
+        write_store(&self.path, state).inspect_err(|_| undo(state))
+    }
+}
+
+impl SecretStore for EncryptedFileSecretStore {
+    fn resolve_secret_handle(
+        &self,
+        handle: &SecretHandle,
+        _ctx: &AccessContext,
+    ) -> Result<SecretRef, SecretError> {
+        if !self.lock()?.secrets.contains_key(&handle.0) {
+            return Err(SecretError::NotFound(handle.0.clone()));
+        }
+        Ok(SecretRef {
+            handle: handle.clone(),
+        })
+    }
+
+    fn reveal_secret(
+        &self,
+        handle: &SecretHandle,
+        _ctx: &AccessContext,
+    ) -> Result<Zeroizing<Vec<u8>>, SecretError> {
+        self.reveal(handle)
+    }
+
+    fn rotate(&self, handle: &SecretHandle) -> Result<u64, SecretError> {
+        let hook = self.rotation_hook.as_ref().ok_or_else(|| {
+            SecretError::Backend(format!(
+                "no rotation hook configured to rotate {}",
+                handle.0
+            ))
+        })?;
+        if !self.lock()?.secrets.contains_key(&handle.0) {
+            return Err(SecretError::NotFound(handle.0.clone()));
+        }
+        let value = hook.next_value(handle)?;
+        self.rotate_to(handle, &value).map(u64::from)
+    }
+
+    fn rotation_info(&self) -> Result<Vec<RotationInfo>, SecretError> {
+        Ok(self
+            .lock()?
+            .secrets
+            .iter()
+            .map(|(handle, sealed)| RotationInfo {
+                handle: SecretHandle(handle.clone()),
+                version: u64::from(sealed.version),
+                rotated_at_unix: sealed.rotated_unix.unwrap_or(sealed.created_unix),
+            })
+            .collect())
+    }
+}
+
+/// Writes a new random key as 64 hex digits, readable only by the owner.
+pub fn generate_keyfile(path: &Path) -> Result<(), SecretError> {
+    let key = Zeroizing::new(to_hex(&random_bytes(32)?));
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options
+        .open(path)
+        .map_err(|e| io_err("creating", path, e))?;
+    file.write_all(format!("{}\n", key.as_str()).as_bytes())
+        .map_err(|e| io_err("writing", path, e))
+}
+
+fn read_keyfile(path: &Path) -> Result<Zeroizing<[u8; 32]>, SecretError> {
+    let raw = Zeroizing::new(fs::read(path).map_err(|e| io_err("reading keyfile", path, e))?);
+    let bytes = match raw.len() {
+        32 => Zeroizing::new(raw.to_vec()),
+        _ => Zeroizing::new(from_hex(String::from_utf8_lossy(&raw).trim())?),
+    };
+    let mut key = Zeroizing::new([0u8; 32]);
+    if bytes.len() != key.len() {
+        return Err(SecretError::Backend(format!(
+            "keyfile {} must hold 32 bytes or 64 hex digits",
+            path.display()
+        )));
+    }
+    key.copy_from_slice(&bytes);
+    Ok(key)
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<Zeroizing<[u8; 32]>, SecretError> {
+    let mut key = Zeroizing::new([0u8; 32]);
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, key.as_mut())
+        .map_err(|e| SecretError::Backend(format!("passphrase key derivation failed: {e}")))?;
+    Ok(key)
+}
+
+fn cipher(key: &Zeroizing<[u8; 32]>) -> ChaCha20Poly1305 {
+    ChaCha20Poly1305::new(Key::from_slice(key.as_slice()))
+}
+
+fn seal(
+    cipher: &ChaCha20Poly1305,
+    aad: &[u8],
+    value: &[u8],
+    version: u32,
+    created_unix: u64,
+) -> Result<SealedSecret, SecretError> {
+    let nonce = random_bytes(NONCE_LEN)?;
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), Payload { msg: value, aad })
+        .map_err(|_| SecretError::Backend("secret encryption failed".to_string()))?;
+    Ok(SealedSecret {
+        version,
+        nonce: to_hex(&nonce),
+        ciphertext: to_hex(&ciphertext),
+        created_unix,
+        rotated_unix: None,
+    })
+}
+
+fn open_sealed(
+    cipher: &ChaCha20Poly1305,
+    aad: &[u8],
+    sealed: &SealedSecret,
+) -> Result<Zeroizing<Vec<u8>>, SecretError> {
+    let nonce = from_hex(&sealed.nonce)?;
+    if nonce.len() != NONCE_LEN {
+        return Err(SecretError::Backend(
+            "sealed secret has a malformed nonce".to_string(),
+        ));
+    }
+    let ciphertext = from_hex(&sealed.ciphertext)?;
+    cipher
+        .decrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: &ciphertext,
+                aad,
+            },
+        )
+        .map(Zeroizing::new)
+        .map_err(|_| SecretError::Backend("sealed secret failed authentication".to_string()))
+}
+
+/// Atomically replaces the store, readable only by the owner.
+fn write_store(path: &Path, file: &StoreFile) -> Result<(), SecretError> {
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent).map_err(|e| io_err("creating dir for", parent, e))?;
+    }
+    let body = serde_json::to_vec_pretty(file)
+        .map_err(|e| SecretError::Backend(format!("failed serializing secret store: {e}")))?;
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut out = options.open(&tmp).map_err(|e| io_err("writing", &tmp, e))?;
+    out.write_all(&body)
+        .and_then(|_| out.sync_data())
+        .map_err(|e| io_err("writing", &tmp, e))?;
+    fs::rename(&tmp, path).map_err(|e| io_err("replacing", path, e))
+}
+
+fn random_bytes(len: usize) -> Result<Vec<u8>, SecretError> {
+    let mut bytes = vec![0u8; len];
+    getrandom::getrandom(&mut bytes)
+        .map_err(|e| SecretError::Backend(format!("no system randomness: {e}")))?;
+    Ok(bytes)
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn from_hex(text: &str) -> Result<Vec<u8>, SecretError> {
+    if !text.len().is_multiple_of(2) || !text.is_ascii() {
+        return Err(SecretError::Backend(
+            "malformed hex in secret store".to_string(),
+        ));
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&text[i..i + 2], 16)
+                .map_err(|_| SecretError::Backend("malformed hex in secret store".to_string()))
+        })
+        .collect()
+}
+
+fn io_err(action: &str, path: &Path, err: std::io::Error) -> SecretError {
+    SecretError::Backend(format!("failed {action} {}: {err}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("odin-secrets-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).expect("create dir");
+        dir
+    }
+
+    fn handle(name: &str) -> SecretHandle {
+        SecretHandle(format!("secret://demo/{name}"))
+    }
+
+    #[test]
+    fn create_rotate_delete_survive_reopening() {
+        let dir = temp_dir("keyfile");
+        let keyfile = dir.join("store.key");
+        generate_keyfile(&keyfile).expect("keyfile");
+        let path = dir.join("secrets.json");
+        let store = EncryptedFileSecretStore::open(&path, KeySource::Keyfile(keyfile.clone()))
+            .expect("open");
+
+        store
+            .create(&handle("github"), b"ghp_first")
+            .expect("create");
+        assert!(matches!(
+            store.create(&handle("github"), b"again"),
+            Err(SecretError::AlreadyExists(_))
+        ));
+        assert_eq!(
+            store
+                .rotate_to(&handle("github"), b"ghp_second")
+                .expect("rotate"),
+            2
+        );
+        store.create(&handle("slack"), b"xoxb").expect("create");
+        store.delete(&handle("slack")).expect("delete");
+        assert!(!fs::read_to_string(&path)
+            .expect("read")
+            .contains("ghp_second"));
+
+        let store = EncryptedFileSecretStore::open(&path, KeySource::Keyfile(keyfile.clone()))
+            .expect("reopen");
+        assert_eq!(
+            store.reveal(&handle("github")).expect("reveal").as_slice(),
+            b"ghp_second"
+        );
+        assert!(SecretStore::rotate(&store, &handle("github")).is_err());
+        let info = store.rotation_info().expect("info");
+        assert_eq!(
+            (info[0].version, info[0].handle.clone()),
+            (2, handle("github"))
+        );
+        assert_eq!(
+            store.handles().expect("handles"),
+            vec![(handle("github"), 2)]
+        );
+        assert!(matches!(
+            store.delete(&handle("slack")),
+            Err(SecretError::NotFound(_))
+        ));
+
+        struct Counter;
+        impl RotationHook for Counter {
+            fn next_value(&self, handle: &SecretHandle) -> Result<Zeroizing<Vec<u8>>, SecretError> {
+                Ok(Zeroizing::new(format!("{}#3", handle.0).into_bytes()))
+            }
+        }
+        let store = EncryptedFileSecretStore::open(&path, KeySource::Keyfile(keyfile))
+            .expect("reopen")
+            .with_rotation_hook(Counter);
+        assert_eq!(
+            SecretStore::rotate(&store, &handle("github")).expect("rotate"),
+            3
+        );
+        assert_eq!(
+            store.reveal(&handle("github")).expect("reveal").as_slice(),
+            b"secret://demo/github#3"
+        );
+        assert!(matches!(
+            SecretStore::rotate(&store, &handle("slack")),
+            Err(SecretError::NotFound(_))
+        ));
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn wrong_passphrase_and_swapped_ciphertexts_are_rejected() {
+        let dir = temp_dir("passphrase");
+        let path = dir.join("secrets.json");
+        let passphrase = || KeySource::Passphrase("correct horse".to_string());
+        let store = EncryptedFileSecretStore::open(&path, passphrase()).expect("open");
+        store.create(&handle("a"), b"alpha").expect("create");
+        store.create(&handle("b"), b"bravo").expect("create");
+        drop(store);
+
+        assert!(matches!(
+            EncryptedFileSecretStore::open(&path, KeySource::Passphrase("wrong".to_string())),
+            Err(SecretError::Unauthorized(_))
+        ));
+
+        let mut file: StoreFile =
+            serde_json::from_slice(&fs::read(&path).expect("read")).expect("parse");
+        let a = file.secrets[&handle("a").0].clone();
+        file.secrets.insert(handle("b").0, a);
+        write_store(&path, &file).expect("write");
+        let store = EncryptedFileSecretStore::open(&path, passphrase()).expect("reopen");
+        assert!(store.reveal(&handle("b")).is_err());
+        let _ = fs::remove_dir_all(dir);
+    }
+}
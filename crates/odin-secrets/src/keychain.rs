@@ -0,0 +1,123 @@
+//! Secrets kept in the platform keychain: Secret Service on Linux, the macOS Keychain and the
+//! Windows Credential Manager. Each handle is one credential under the store's service name, so
+//! operator machines keep orchestrator credentials out of plaintext files.
+
+use keyring::Entry;
+use zeroize::Zeroizing;
+
+use crate::{AccessContext, SecretError, SecretHandle, SecretRef, SecretStore};
+
+#[derive(Clone, Debug)]
+pub struct KeychainSecretStore {
+    service: String,
+}
+
+impl KeychainSecretStore {
+    /// `service` namespaces the credentials, e.g. `odin-orchestrator`.
+    pub fn new(service: impl Into<String>) -> Self {
+        Self {
+            service: service.into(),
+        }
+    }
+
+    pub fn service(&self) -> &str {
+        &self.service
+    }
+
+    /// Stores a new secret; fails when the handle already exists.
+    pub fn create(&self, handle: &SecretHandle, value: &[u8]) -> Result<(), SecretError> {
+        let entry = self.entry(handle)?;
+        match entry.get_secret() {
+            Ok(_) => Err(SecretError::AlreadyExists(handle.0.clone())),
+            Err(keyring::Error::NoEntry) => {
+                entry.set_secret(value).map_err(|e| backend_err(handle, e))
+            }
+            Err(e) => Err(backend_err(handle, e)),
+        }
+    }
+
+    /// Replaces an existing secret's value.
+    pub fn rotate_to(&self, handle: &SecretHandle, value: &[u8]) -> Result<(), SecretError> {
+        let entry = self.entry(handle)?;
+        entry.get_secret().map_err(|e| keyring_err(handle, e))?;
+        entry.set_secret(value).map_err(|e| backend_err(handle, e))
+    }
+
+    pub fn delete(&self, handle: &SecretHandle) -> Result<(), SecretError> {
+        self.entry(handle)?
+            .delete_credential()
+            .map_err(|e| keyring_err(handle, e))
+    }
+
+    /// Reads a secret's value; it is zeroed on drop.
+    pub fn reveal(&self, handle: &SecretHandle) -> Result<Zeroizing<Vec<u8>>, SecretError> {
+        self.entry(handle)?
+            .get_secret()
+            .map(Zeroizing::new)
+            .map_err(|e| keyring_err(handle, e))
+    }
+
+    fn entry(&self, handle: &SecretHandle) -> Result<Entry, SecretError> {
+        Entry::new(&self.service, &handle.0).map_err(|e| backend_err(handle, e))
+    }
+}
+
+impl SecretStore for KeychainSecretStore {
+    fn resolve_secret_handle(
+        &self,
+        handle: &SecretHandle,
+        _ctx: &AccessContext,
+    ) -> Result<SecretRef, SecretError> {
+        self.reveal(handle)?;
+        Ok(SecretRef {
+            handle: handle.clone(),
+        })
+    }
+
+    fn reveal_secret(
+        &self,
+        handle: &SecretHandle,
+        _ctx: &AccessContext,
+    ) -> Result<Zeroizing<Vec<u8>>, SecretError> {
+        self.reveal(handle)
+    }
+}
+
+fn keyring_err(handle: &SecretHandle, err: keyring::Error) -> SecretError {
+    match err {
+        keyring::Error::NoEntry => SecretError::NotFound(handle.0.clone()),
+        other => backend_err(handle, other),
+    }
+}
+
+fn backend_err(handle: &SecretHandle, err: keyring::Error) -> SecretError {
+    SecretError::Backend(format!("keychain access for {} failed: {err}", handle.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_credentials_are_not_found() {
+        keyring::set_default_credential_builder(keyring::mock::default_credential_builder());
+        let store = KeychainSecretStore::new("odin-test");
+        let handle = SecretHandle("secret://demo/github".to_string());
+        let ctx = AccessContext {
+            plugin: "p".to_string(),
+            project: "demo".to_string(),
+            capability: "secret.read".to_string(),
+            reason: "unit".to_string(),
+        };
+
+        assert!(matches!(
+            store.resolve_secret_handle(&handle, &ctx),
+            Err(SecretError::NotFound(_))
+        ));
+        assert!(matches!(
+            store.delete(&handle),
+            Err(SecretError::NotFound(_))
+        ));
+        store.create(&handle, b"ghp_value").expect("create");
+    }
+}
@@ -0,0 +1,220 @@
+//! Enforces `SessionLease` expiry. `LeaseTracker` remembers every lease its vault issues and
+//! checks it each time the session is used. Expired or revoked leases are rejected. A lease
+//! inside the renewal window comes back with `reauth_required` set, so callers renew it before
+//! it lapses.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+
+use crate::{now_unix, AccessContext, SecretError, SessionHandle, SessionLease, SessionVault};
+
+/// Leases with less than this left are flagged `reauth_required`.
+pub const DEFAULT_RENEW_WINDOW: Duration = Duration::from_secs(300);
+
+#[derive(Clone, Debug)]
+struct TrackedLease {
+    lease: SessionLease,
+    /// Context the lease was issued for; renewals reuse it.
+    ctx: AccessContext,
+}
+
+pub struct LeaseTracker<V> {
+    vault: V,
+    renew_window: Duration,
+    leases: Mutex<HashMap<SessionHandle, TrackedLease>>,
+}
+
+impl<V: SessionVault> LeaseTracker<V> {
+    pub fn new(vault: V) -> Self {
+        Self {
+            vault,
+            renew_window: DEFAULT_RENEW_WINDOW,
+            leases: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn with_renew_window(mut self, renew_window: Duration) -> Self {
+        self.renew_window = renew_window;
+        self
+    }
+
+    /// The active lease for `handle`, checked at the time of use. Fails with `NotFound` when none
+    /// was issued or it was revoked, and with `Expired` once it has lapsed. An expired lease is
+    /// forgotten, so the session must be issued again.
+    pub fn check(&self, handle: &SessionHandle) -> Result<SessionLease, SecretError> {
+        self.check_at(handle, now_unix())
+    }
+
+    /// `check` as of `now_unix`.
+    pub fn check_at(
+        &self,
+        handle: &SessionHandle,
+        now_unix: u64,
+    ) -> Result<SessionLease, SecretError> {
+        let mut leases = self.lock()?;
+        let tracked = leases
+            .get(handle)
+            .ok_or_else(|| SecretError::NotFound(format!("no active lease for {}", handle.0)))?;
+        if now_unix >= tracked.lease.expires_at_unix {
+            let expired_at = tracked.lease.expires_at_unix;
+            leases.remove(handle);
+            return Err(SecretError::Expired(format!(
+                "{} expired at {expired_at}",
+                handle.0
+            )));
+        }
+        Ok(self.signal(&tracked.lease, now_unix))
+    }
+
+    /// Re-issues an active lease from the vault with the context it was first issued for.
+    pub fn renew_lease(&self, handle: &SessionHandle) -> Result<SessionLease, SecretError> {
+        let now = now_unix();
+        self.check_at(handle, now)?;
+        let ctx = self
+            .lock()?
+            .get(handle)
+            .map(|tracked| tracked.ctx.clone())
+            .ok_or_else(|| SecretError::NotFound(format!("no active lease for {}", handle.0)))?;
+        let lease = self.vault.issue_session_lease(handle, &ctx)?;
+        tracing::debug!(
+            handle = %handle.0,
+            expires_at_unix = lease.expires_at_unix,
+            "lease renewed"
+        );
+        self.track(lease, ctx, now)
+    }
+
+    /// Ends a lease early; later checks and renewals fail with `NotFound`.
+    pub fn revoke_lease(&self, handle: &SessionHandle) -> Result<SessionLease, SecretError> {
+        self.lock()?
+            .remove(handle)
+            .map(|tracked| tracked.lease)
+            .ok_or_else(|| SecretError::NotFound(format!("no active lease for {}", handle.0)))
+    }
+
+    fn track(
+        &self,
+        lease: SessionLease,
+        ctx: AccessContext,
+        now_unix: u64,
+    ) -> Result<SessionLease, SecretError> {
+        let signalled = self.signal(&lease, now_unix);
+        self.lock()?
+            .insert(lease.handle.clone(), TrackedLease { lease, ctx });
+        Ok(signalled)
+    }
+
+    /// `lease` with `reauth_required` also set when it ends within the renewal window.
+    fn signal(&self, lease: &SessionLease, now_unix: u64) -> SessionLease {
+        let remaining = lease.expires_at_unix.saturating_sub(now_unix);
+        SessionLease {
+            reauth_required: lease.reauth_required || remaining <= self.renew_window.as_secs(),
+            ..lease.clone()
+        }
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, HashMap<SessionHandle, TrackedLease>>, SecretError> {
+        self.leases
+            .lock()
+            .map_err(|_| SecretError::Backend("lease tracker lock poisoned".to_string()))
+    }
+}
+
+impl<V: SessionVault> SessionVault for LeaseTracker<V> {
+    /// Issues through the wrapped vault and starts tracking the lease.
+    fn issue_session_lease(
+        &self,
+        handle: &SessionHandle,
+        ctx: &AccessContext,
+    ) -> Result<SessionLease, SecretError> {
+        let lease = self.vault.issue_session_lease(handle, ctx)?;
+        self.track(lease, ctx.clone(), now_unix())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    use super::*;
+
+    /// Issues leases that last `ttl` seconds from now.
+    struct TtlVault {
+        ttl: AtomicU64,
+    }
+
+    impl SessionVault for TtlVault {
+        fn issue_session_lease(
+            &self,
+            handle: &SessionHandle,
+            _ctx: &AccessContext,
+        ) -> Result<SessionLease, SecretError> {
+            Ok(SessionLease {
+                handle: handle.clone(),
+                expires_at_unix: now_unix() + self.ttl.load(Ordering::SeqCst),
+                reauth_required: false,
+            })
+        }
+    }
+
+    fn ctx() -> AccessContext {
+        AccessContext {
+            plugin: "p".to_string(),
+            project: "demo".to_string(),
+            capability: "session.use".to_string(),
+            reason: "unit".to_string(),
+        }
+    }
+
+    #[test]
+    fn enforces_expiry_and_signals_reauth_near_it() {
+        let tracker = LeaseTracker::new(TtlVault {
+            ttl: AtomicU64::new(120),
+        });
+        let handle = SessionHandle("session://demo/gmail".to_string());
+
+        // Two minutes left is inside the default five-minute window.
+        let lease = tracker.issue_session_lease(&handle, &ctx()).expect("issue");
+        assert!(lease.reauth_required);
+
+        tracker.vault.ttl.store(3600, Ordering::SeqCst);
+        let renewed = tracker.renew_lease(&handle).expect("renew");
+        assert!(!renewed.reauth_required);
+        assert!(renewed.expires_at_unix > lease.expires_at_unix);
+        assert!(tracker.check(&handle).is_ok());
+        assert!(
+            tracker
+                .check_at(&handle, renewed.expires_at_unix - 60)
+                .expect("near expiry")
+                .reauth_required
+        );
+
+        assert!(matches!(
+            tracker.check_at(&handle, renewed.expires_at_unix),
+            Err(SecretError::Expired(_))
+        ));
+        assert!(matches!(
+            tracker.renew_lease(&handle),
+            Err(SecretError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn revoked_leases_cannot_be_used_or_renewed() {
+        let tracker = LeaseTracker::new(TtlVault {
+            ttl: AtomicU64::new(3600),
+        })
+        .with_renew_window(Duration::from_secs(10));
+        let handle = SessionHandle("session://demo/gmail".to_string());
+        tracker.issue_session_lease(&handle, &ctx()).expect("issue");
+
+        tracker.revoke_lease(&handle).expect("revoke");
+        assert!(matches!(
+            tracker.check(&handle),
+            Err(SecretError::NotFound(_))
+        ));
+        assert!(tracker.renew_lease(&handle).is_err());
+        assert!(tracker.revoke_lease(&handle).is_err());
+    }
+}
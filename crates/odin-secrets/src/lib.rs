@@ -1,7 +1,24 @@
 //! Secrets and session interfaces using opaque handles.
 
+pub mod cache;
+pub mod config;
+pub mod file;
+#[cfg(feature = "keychain")]
+pub mod keychain;
+pub mod lease;
+pub mod namespace;
+pub mod rotation;
+pub mod session;
+#[cfg(feature = "vault")]
+pub mod vault;
+
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rotation::RotationInfo;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
+use zeroize::Zeroizing;
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct SecretHandle(pub String);
@@ -35,6 +52,10 @@ pub enum SecretError {
     Unauthorized(String),
     #[error("not found: {0}")]
     NotFound(String),
+    #[error("already exists: {0}")]
+    AlreadyExists(String),
+    #[error("lease expired: {0}")]
+    Expired(String),
     #[error("backend failure: {0}")]
     Backend(String),
 }
@@ -45,6 +66,33 @@ pub trait SecretStore: Send + Sync {
         handle: &SecretHandle,
         ctx: &AccessContext,
     ) -> Result<SecretRef, SecretError>;
+
+    /// The secret's value, for delivery into a plugin process at spawn time. Stores that only
+    /// issue handles have no values to give.
+    fn reveal_secret(
+        &self,
+        handle: &SecretHandle,
+        _ctx: &AccessContext,
+    ) -> Result<Zeroizing<Vec<u8>>, SecretError> {
+        Err(SecretError::Backend(format!(
+            "this secret backend holds no value for {}",
+            handle.0
+        )))
+    }
+
+    /// Replaces the value with one from the store's rotation hook and returns the new version.
+    fn rotate(&self, handle: &SecretHandle) -> Result<u64, SecretError> {
+        Err(SecretError::Backend(format!(
+            "this secret backend cannot rotate {}",
+            handle.0
+        )))
+    }
+
+    /// When each stored secret last changed, for rotation status. Stores that cannot list their
+    /// secrets report none.
+    fn rotation_info(&self) -> Result<Vec<RotationInfo>, SecretError> {
+        Ok(Vec::new())
+    }
 }
 
 pub trait SessionVault: Send + Sync {
@@ -55,6 +103,65 @@ pub trait SessionVault: Send + Sync {
     ) -> Result<SessionLease, SecretError>;
 }
 
+impl<S: SecretStore + ?Sized> SecretStore for Box<S> {
+    fn resolve_secret_handle(
+        &self,
+        handle: &SecretHandle,
+        ctx: &AccessContext,
+    ) -> Result<SecretRef, SecretError> {
+        (**self).resolve_secret_handle(handle, ctx)
+    }
+
+    fn reveal_secret(
+        &self,
+        handle: &SecretHandle,
+        ctx: &AccessContext,
+    ) -> Result<Zeroizing<Vec<u8>>, SecretError> {
+        (**self).reveal_secret(handle, ctx)
+    }
+
+    fn rotate(&self, handle: &SecretHandle) -> Result<u64, SecretError> {
+        (**self).rotate(handle)
+    }
+
+    fn rotation_info(&self) -> Result<Vec<RotationInfo>, SecretError> {
+        (**self).rotation_info()
+    }
+}
+
+impl<S: SecretStore + ?Sized> SecretStore for Arc<S> {
+    fn resolve_secret_handle(
+        &self,
+        handle: &SecretHandle,
+        ctx: &AccessContext,
+    ) -> Result<SecretRef, SecretError> {
+        (**self).resolve_secret_handle(handle, ctx)
+    }
+
+    fn reveal_secret(
+        &self,
+        handle: &SecretHandle,
+        ctx: &AccessContext,
+    ) -> Result<Zeroizing<Vec<u8>>, SecretError> {
+        (**self).reveal_secret(handle, ctx)
+    }
+
+    fn rotate(&self, handle: &SecretHandle) -> Result<u64, SecretError> {
+        (**self).rotate(handle)
+    }
+
+    fn rotation_info(&self) -> Result<Vec<RotationInfo>, SecretError> {
+        (**self).rotation_info()
+    }
+}
+
+pub(crate) fn now_unix() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct HandleOnlyStore;
 
@@ -0,0 +1,96 @@
+//! Structured secret handles: `secret://<project>/<plugin>/<name>`. The last segment is the
+//! name and the one before it the owning plugin; everything before that is the project, which
+//! may itself be hierarchical (`secret://acme/api/example.git/token`).
+
+use crate::{SecretError, SecretHandle};
+
+pub const SECRET_SCHEME: &str = "secret://";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NamespacedHandle {
+    pub project: String,
+    pub plugin: String,
+    pub name: String,
+}
+
+impl NamespacedHandle {
+    pub fn parse(handle: &SecretHandle) -> Result<Self, SecretError> {
+        let invalid = |why: &str| {
+            SecretError::Unauthorized(format!(
+                "{} is not a namespaced handle ({SECRET_SCHEME}<project>/<plugin>/<name>): {why}",
+                handle.0
+            ))
+        };
+        let path = handle
+            .0
+            .strip_prefix(SECRET_SCHEME)
+            .ok_or_else(|| invalid("missing scheme"))?;
+        let segments: Vec<&str> = path.split('/').collect();
+        if segments.len() < 3 {
+            return Err(invalid("too few segments"));
+        }
+        if segments
+            .iter()
+            .any(|segment| segment.is_empty() || *segment == "." || *segment == "..")
+        {
+            return Err(invalid("empty or relative segment"));
+        }
+        let (project, rest) = segments.split_at(segments.len() - 2);
+        Ok(Self {
+            project: project.join("/"),
+            plugin: rest[0].to_string(),
+            name: rest[1].to_string(),
+        })
+    }
+
+    /// `secret://<project>/<plugin>`.
+    pub fn namespace(&self) -> String {
+        format!("{SECRET_SCHEME}{}/{}", self.project, self.plugin)
+    }
+
+    /// Whether `plugin` in `project` owns this handle: the plugins match and the handle's
+    /// project is `project` or one of its ancestors.
+    pub fn owned_by(&self, plugin: &str, project: &str) -> bool {
+        self.plugin == plugin
+            && (self.project == project
+                || project
+                    .strip_prefix(&self.project)
+                    .is_some_and(|rest| rest.starts_with('/')))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(handle: &str) -> Result<NamespacedHandle, SecretError> {
+        NamespacedHandle::parse(&SecretHandle(handle.to_string()))
+    }
+
+    #[test]
+    fn parses_project_plugin_and_name_from_the_right() {
+        let handle = parse("secret://acme/api/example.git/token").expect("parse");
+        assert_eq!(handle.project, "acme/api");
+        assert_eq!(handle.plugin, "example.git");
+        assert_eq!(handle.name, "token");
+        assert_eq!(handle.namespace(), "secret://acme/api/example.git");
+
+        assert!(handle.owned_by("example.git", "acme/api"));
+        assert!(handle.owned_by("example.git", "acme/api/web"));
+        assert!(!handle.owned_by("example.git", "acme/apiary"));
+        assert!(!handle.owned_by("example.git", "acme"));
+        assert!(!handle.owned_by("other", "acme/api"));
+
+        for bad in [
+            "secret://demo/github",
+            "vault://demo/p/token",
+            "secret://demo//token",
+            "secret://demo/../token",
+        ] {
+            assert!(
+                matches!(parse(bad), Err(SecretError::Unauthorized(_))),
+                "{bad}"
+            );
+        }
+    }
+}
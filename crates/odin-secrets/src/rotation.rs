@@ -0,0 +1,253 @@
+//! Secret rotation: hooks that mint replacement values, and a max-age policy that reports
+//! handles past their rotation deadline. The policy comes from the `secret_rotation:` config
+//! section:
+//!
+//! ```yaml
+//! secret_rotation:
+//!   max_age_days: 90
+//!   rules:
+//!     - handle: "secret://demo/example.git/*"
+//!       max_age_days: 30
+//!   hook: [/usr/local/bin/mint-token, --quiet]
+//! ```
+
+use std::process::Command;
+
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use crate::{SecretError, SecretHandle};
+
+const DAY_SECS: u64 = 86_400;
+
+pub trait RotationHook: Send + Sync {
+    /// Produces the replacement value for `handle`, e.g. by minting a new token upstream.
+    fn next_value(&self, handle: &SecretHandle) -> Result<Zeroizing<Vec<u8>>, SecretError>;
+}
+
+/// Runs `program args... <handle>` and takes its stdout, minus one trailing newline, as the
+/// new value.
+#[derive(Clone, Debug)]
+pub struct CommandRotationHook {
+    program: String,
+    args: Vec<String>,
+}
+
+impl CommandRotationHook {
+    pub fn new(program: impl Into<String>) -> Self {
+        Self {
+            program: program.into(),
+            args: Vec::new(),
+        }
+    }
+
+    pub fn with_args(mut self, args: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.args = args.into_iter().map(Into::into).collect();
+        self
+    }
+}
+
+impl RotationHook for CommandRotationHook {
+    fn next_value(&self, handle: &SecretHandle) -> Result<Zeroizing<Vec<u8>>, SecretError> {
+        let output = Command::new(&self.program)
+            .args(&self.args)
+            .arg(&handle.0)
+            .output()
+            .map_err(|e| {
+                SecretError::Backend(format!("failed to run rotation hook {}: {e}", self.program))
+            })?;
+        let mut value = Zeroizing::new(output.stdout);
+        if !output.status.success() {
+            return Err(SecretError::Backend(format!(
+                "rotation hook {} failed for {} ({}): {}",
+                self.program,
+                handle.0,
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        if value.ends_with(b"\n") {
+            value.pop();
+        }
+        if value.is_empty() {
+            return Err(SecretError::Backend(format!(
+                "rotation hook {} printed no value for {}",
+                self.program, handle.0
+            )));
+        }
+        Ok(value)
+    }
+}
+
+/// When a stored secret last changed; see `SecretStore::rotation_info`.
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+pub struct RotationInfo {
+    pub handle: SecretHandle,
+    pub version: u64,
+    /// Last rotation, or creation when it was never rotated.
+    pub rotated_at_unix: u64,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct MaxAgeRule {
+    /// `*` matches any run of characters.
+    pub handle: String,
+    pub max_age_days: u64,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct RotationPolicy {
+    /// Applies to handles no rule matches; unset means they never go stale.
+    #[serde(default)]
+    pub max_age_days: Option<u64>,
+    /// First match wins.
+    #[serde(default)]
+    pub rules: Vec<MaxAgeRule>,
+    /// Program and arguments of a `CommandRotationHook`.
+    #[serde(default)]
+    pub hook: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+pub struct RotationStatus {
+    pub handle: SecretHandle,
+    pub version: u64,
+    pub rotated_at_unix: u64,
+    pub max_age_days: Option<u64>,
+    pub deadline_unix: Option<u64>,
+    pub overdue: bool,
+}
+
+impl RotationPolicy {
+    pub fn max_age_days(&self, handle: &SecretHandle) -> Option<u64> {
+        self.rules
+            .iter()
+            .find(|rule| wildcard_matches(&rule.handle, &handle.0))
+            .map(|rule| rule.max_age_days)
+            .or(self.max_age_days)
+    }
+
+    /// The configured hook, if any.
+    pub fn hook(&self) -> Option<CommandRotationHook> {
+        let (program, args) = self.hook.split_first()?;
+        Some(CommandRotationHook::new(program.clone()).with_args(args.iter().cloned()))
+    }
+
+    /// Deadline and overdue flag for each secret, overdue ones first, then by handle.
+    pub fn status(&self, secrets: &[RotationInfo], now_unix: u64) -> Vec<RotationStatus> {
+        let mut report: Vec<RotationStatus> = secrets
+            .iter()
+            .map(|info| {
+                let max_age_days = self.max_age_days(&info.handle);
+                let deadline_unix = max_age_days.map(|days| {
+                    info.rotated_at_unix
+                        .saturating_add(days.saturating_mul(DAY_SECS))
+                });
+                RotationStatus {
+                    handle: info.handle.clone(),
+                    version: info.version,
+                    rotated_at_unix: info.rotated_at_unix,
+                    max_age_days,
+                    deadline_unix,
+                    overdue: deadline_unix.is_some_and(|deadline| now_unix >= deadline),
+                }
+            })
+            .collect();
+        report.sort_by(|a, b| b.overdue.cmp(&a.overdue).then(a.handle.0.cmp(&b.handle.0)));
+        report
+    }
+}
+
+fn wildcard_matches(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(at) => rest = &rest[at + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(handle: &str, rotated_at_unix: u64) -> RotationInfo {
+        RotationInfo {
+            handle: SecretHandle(handle.to_string()),
+            version: 1,
+            rotated_at_unix,
+        }
+    }
+
+    #[test]
+    fn reports_handles_past_their_max_age() {
+        let policy: RotationPolicy = serde_json::from_value(serde_json::json!({
+            "max_age_days": 90,
+            "rules": [{ "handle": "secret://demo/example.git/*", "max_age_days": 30 }]
+        }))
+        .expect("policy");
+        let now = 100 * DAY_SECS;
+        let report = policy.status(
+            &[
+                info("secret://demo/example.git/token", 60 * DAY_SECS),
+                info("secret://demo/other/token", 60 * DAY_SECS),
+                info("secret://demo/example.git/fresh", 95 * DAY_SECS),
+            ],
+            now,
+        );
+
+        assert_eq!(report[0].handle.0, "secret://demo/example.git/token");
+        assert!(report[0].overdue);
+        assert_eq!(report[0].deadline_unix, Some(90 * DAY_SECS));
+        assert_eq!(report.iter().filter(|s| s.overdue).count(), 1);
+        let other = report
+            .iter()
+            .find(|s| s.handle.0 == "secret://demo/other/token")
+            .expect("other");
+        assert_eq!(other.max_age_days, Some(90));
+
+        assert!(RotationPolicy::default()
+            .status(&[info("secret://a/b/c", 0)], now)
+            .iter()
+            .all(|s| !s.overdue && s.deadline_unix.is_none()));
+    }
+
+    #[test]
+    fn wildcards_match_any_run_of_characters() {
+        assert!(wildcard_matches("secret://demo/*", "secret://demo/p/t"));
+        assert!(wildcard_matches("*/token", "secret://demo/p/token"));
+        assert!(wildcard_matches("secret://*/p/*", "secret://demo/p/t"));
+        assert!(!wildcard_matches("secret://ops/*", "secret://demo/p/t"));
+        assert!(!wildcard_matches("a*a", "a"));
+        assert!(wildcard_matches("exact", "exact"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn command_hook_takes_stdout_as_the_new_value() {
+        let handle = SecretHandle("secret://demo/p/token".to_string());
+        let hook = CommandRotationHook::new("sh").with_args(["-c", "echo \"new-$0\""]);
+        assert_eq!(
+            hook.next_value(&handle).expect("value").as_slice(),
+            b"new-secret://demo/p/token"
+        );
+        let failing = CommandRotationHook::new("sh").with_args(["-c", "echo nope >&2; exit 3"]);
+        assert!(failing
+            .next_value(&handle)
+            .expect_err("fails")
+            .to_string()
+            .contains("nope"));
+    }
+}
@@ -0,0 +1,155 @@
+//! Browser-style sessions (cookies, storage state) kept encrypted at rest. Each blob is sealed
+//! together with its expiry in an `EncryptedFileSecretStore` of its own, so neither can be read
+//! or altered without the key. A lease for a lapsed or missing session comes back with
+//! `reauth_required` set; a human then logs in again and the fresh blob is saved over it.
+
+use std::path::PathBuf;
+
+use zeroize::Zeroizing;
+
+use crate::file::{EncryptedFileSecretStore, KeySource};
+use crate::{
+    now_unix, AccessContext, SecretError, SecretHandle, SessionHandle, SessionLease, SessionVault,
+};
+
+const EXPIRY_LEN: usize = 8;
+
+pub struct EncryptedSessionVault {
+    store: EncryptedFileSecretStore,
+}
+
+impl EncryptedSessionVault {
+    /// Opens the vault at `path`, creating an empty one when it does not exist yet.
+    pub fn open(path: impl Into<PathBuf>, key: KeySource) -> Result<Self, SecretError> {
+        Ok(Self {
+            store: EncryptedFileSecretStore::open(path, key)?,
+        })
+    }
+
+    /// Stores `blob` as the session for `handle`, replacing any earlier one.
+    pub fn save_session(
+        &self,
+        handle: &SessionHandle,
+        blob: &[u8],
+        expires_at_unix: u64,
+    ) -> Result<(), SecretError> {
+        let mut sealed = Zeroizing::new(Vec::with_capacity(EXPIRY_LEN + blob.len()));
+        sealed.extend_from_slice(&expires_at_unix.to_be_bytes());
+        sealed.extend_from_slice(blob);
+        let key = secret_handle(handle);
+        match self.store.create(&key, &sealed) {
+            Err(SecretError::AlreadyExists(_)) => self.store.rotate_to(&key, &sealed).map(|_| ()),
+            other => other,
+        }
+    }
+
+    /// The session blob, or `Expired` once it has lapsed.
+    pub fn load_session(&self, handle: &SessionHandle) -> Result<Zeroizing<Vec<u8>>, SecretError> {
+        let (expires_at_unix, blob) = self.read(handle)?;
+        if now_unix() >= expires_at_unix {
+            return Err(SecretError::Expired(format!(
+                "session {} expired at {expires_at_unix}",
+                handle.0
+            )));
+        }
+        Ok(blob)
+    }
+
+    pub fn remove_session(&self, handle: &SessionHandle) -> Result<(), SecretError> {
+        self.store.delete(&secret_handle(handle))
+    }
+
+    fn read(&self, handle: &SessionHandle) -> Result<(u64, Zeroizing<Vec<u8>>), SecretError> {
+        let sealed = self.store.reveal(&secret_handle(handle))?;
+        let expiry: [u8; EXPIRY_LEN] = sealed
+            .get(..EXPIRY_LEN)
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| SecretError::Backend(format!("corrupt session {}", handle.0)))?;
+        Ok((
+            u64::from_be_bytes(expiry),
+            Zeroizing::new(sealed[EXPIRY_LEN..].to_vec()),
+        ))
+    }
+}
+
+impl SessionVault for EncryptedSessionVault {
+    /// Leases the stored session until it expires. A missing session leases as already expired,
+    /// so both cases ask for reauthentication instead of failing.
+    fn issue_session_lease(
+        &self,
+        handle: &SessionHandle,
+        _ctx: &AccessContext,
+    ) -> Result<SessionLease, SecretError> {
+        let expires_at_unix = match self.read(handle) {
+            Ok((expires_at_unix, _)) => expires_at_unix,
+            Err(SecretError::NotFound(_)) => 0,
+            Err(e) => return Err(e),
+        };
+        Ok(SessionLease {
+            handle: handle.clone(),
+            expires_at_unix,
+            reauth_required: now_unix() >= expires_at_unix,
+        })
+    }
+}
+
+fn secret_handle(handle: &SessionHandle) -> SecretHandle {
+    SecretHandle(handle.0.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn lapsed_or_missing_sessions_require_reauth() {
+        let dir = std::env::temp_dir().join(format!("odin-sessions-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let path = dir.join("sessions.json");
+        let key = KeySource::Passphrase("correct horse".to_string());
+        let ctx = AccessContext {
+            plugin: "stagehand".to_string(),
+            project: "demo".to_string(),
+            capability: "browser.session".to_string(),
+            reason: "unit".to_string(),
+        };
+        let portal = SessionHandle("session://demo/stagehand/portal".to_string());
+
+        let vault = EncryptedSessionVault::open(&path, key.clone()).expect("open");
+        let lease = vault.issue_session_lease(&portal, &ctx).expect("lease");
+        assert!(lease.reauth_required);
+        assert_eq!(lease.expires_at_unix, 0);
+
+        vault
+            .save_session(&portal, b"cookies-v1", now_unix() + 3_600)
+            .expect("save");
+        let lease = vault.issue_session_lease(&portal, &ctx).expect("lease");
+        assert!(!lease.reauth_required);
+        assert_eq!(
+            vault.load_session(&portal).expect("load").as_slice(),
+            b"cookies-v1"
+        );
+        assert!(!fs::read_to_string(&path).expect("read").contains("cookies"));
+
+        vault.save_session(&portal, b"cookies-v2", 1).expect("save");
+        let vault = EncryptedSessionVault::open(&path, key).expect("reopen");
+        assert!(
+            vault
+                .issue_session_lease(&portal, &ctx)
+                .expect("lease")
+                .reauth_required
+        );
+        assert!(matches!(
+            vault.load_session(&portal),
+            Err(SecretError::Expired(_))
+        ));
+        vault.remove_session(&portal).expect("remove");
+        assert!(matches!(
+            vault.load_session(&portal),
+            Err(SecretError::NotFound(_))
+        ));
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,558 @@
+//! HashiCorp Vault backend: secrets live in a KV v2 mount, so they never touch the orchestrator
+//! host's disk. `secret://<path>` handles map to `<mount>/data/<path>`, and values are stored
+//! under the `value` key. The client logs in with a static token or AppRole. Its token lease is
+//! what `issue_session_lease` reports; it is renewed shortly before expiry and re-obtained
+//! through AppRole when renewal is no longer possible.
+
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use zeroize::Zeroizing;
+
+use crate::{
+    now_unix, AccessContext, SecretError, SecretHandle, SecretRef, SecretStore, SessionHandle,
+    SessionLease, SessionVault,
+};
+
+/// Tokens are renewed once less than this much of their TTL is left.
+const RENEW_MARGIN_SECS: u64 = 60;
+
+pub trait VaultTransport: Send + Sync {
+    /// Sends one request to `/v1/<path>` and returns the status code and JSON body (`Null` when
+    /// empty). Only connection failures are errors.
+    fn send(
+        &self,
+        method: &str,
+        path: &str,
+        token: Option<&str>,
+        body: Option<&Value>,
+    ) -> Result<(u16, Value), SecretError>;
+}
+
+#[derive(Clone, Debug)]
+pub struct HttpVaultTransport {
+    addr: String,
+    namespace: Option<String>,
+    timeout: Duration,
+}
+
+impl HttpVaultTransport {
+    /// `addr` is the server root, e.g. `https://vault.internal:8200`.
+    pub fn new(addr: impl Into<String>) -> Self {
+        Self {
+            addr: addr.into().trim_end_matches('/').to_string(),
+            namespace: None,
+            timeout: Duration::from_secs(10),
+        }
+    }
+
+    /// Vault Enterprise namespace sent as `X-Vault-Namespace`.
+    pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+impl VaultTransport for HttpVaultTransport {
+    fn send(
+        &self,
+        method: &str,
+        path: &str,
+        token: Option<&str>,
+        body: Option<&Value>,
+    ) -> Result<(u16, Value), SecretError> {
+        let agent = ureq::AgentBuilder::new().timeout(self.timeout).build();
+        let mut request = agent.request(method, &format!("{}/v1/{path}", self.addr));
+        if let Some(token) = token {
+            request = request.set("X-Vault-Token", token);
+        }
+        if let Some(namespace) = &self.namespace {
+            request = request.set("X-Vault-Namespace", namespace);
+        }
+        let response = match body {
+            Some(body) => request.send_json(body),
+            None => request.call(),
+        };
+        let response = match response {
+            Ok(response) => response,
+            Err(ureq::Error::Status(_, response)) => response,
+            Err(e) => {
+                return Err(SecretError::Backend(format!(
+                    "vault request {method} {path} failed: {e}"
+                )))
+            }
+        };
+        let status = response.status();
+        let text = response.into_string().unwrap_or_default();
+        let body = serde_json::from_str(&text).unwrap_or(Value::Null);
+        Ok((status, body))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum VaultAuth {
+    Token(String),
+    AppRole {
+        role_id: String,
+        secret_id: String,
+        /// Auth mount path; usually `approle`.
+        mount: String,
+    },
+}
+
+#[derive(Clone, Debug)]
+struct VaultToken {
+    token: Zeroizing<String>,
+    expires_at_unix: u64,
+    renewable: bool,
+}
+
+pub struct VaultSecretStore<T> {
+    transport: T,
+    auth: VaultAuth,
+    mount: String,
+    token: Mutex<Option<VaultToken>>,
+}
+
+impl<T: VaultTransport> VaultSecretStore<T> {
+    pub fn new(transport: T, auth: VaultAuth) -> Self {
+        Self {
+            transport,
+            auth,
+            mount: "secret".to_string(),
+            token: Mutex::new(None),
+        }
+    }
+
+    /// KV v2 mount path; defaults to `secret`.
+    pub fn with_mount(mut self, mount: impl Into<String>) -> Self {
+        self.mount = mount.into().trim_matches('/').to_string();
+        self
+    }
+
+    /// Stores a new secret; fails when the handle already exists.
+    pub fn create(&self, handle: &SecretHandle, value: &[u8]) -> Result<(), SecretError> {
+        self.write(handle, value, 0).map(|_| ())
+    }
+
+    /// Writes a new version of an existing secret and returns its version number.
+    pub fn rotate_to(&self, handle: &SecretHandle, value: &[u8]) -> Result<u64, SecretError> {
+        let metadata = self.metadata(handle)?;
+        let current = metadata
+            .pointer("/data/current_version")
+            .and_then(Value::as_u64)
+            .unwrap_or(0);
+        self.write(handle, value, current)
+    }
+
+    /// Deletes every version and the metadata of a secret.
+    pub fn delete(&self, handle: &SecretHandle) -> Result<(), SecretError> {
+        self.metadata(handle)?;
+        let path = self.kv_path("metadata", handle)?;
+        self.call("DELETE", &path, None, handle).map(|_| ())
+    }
+
+    /// Reads the latest version's value; it is zeroed on drop.
+    pub fn reveal(&self, handle: &SecretHandle) -> Result<Zeroizing<Vec<u8>>, SecretError> {
+        let path = self.kv_path("data", handle)?;
+        let body = self.call("GET", &path, None, handle)?;
+        let value = body
+            .pointer("/data/data/value")
+            .and_then(Value::as_str)
+            .ok_or_else(|| {
+                SecretError::Backend(format!("vault secret {} has no `value` field", handle.0))
+            })?;
+        Ok(Zeroizing::new(value.as_bytes().to_vec()))
+    }
+
+    fn write(&self, handle: &SecretHandle, value: &[u8], cas: u64) -> Result<u64, SecretError> {
+        let value = std::str::from_utf8(value).map_err(|_| {
+            SecretError::Backend("vault secret values must be UTF-8 text".to_string())
+        })?;
+        let path = self.kv_path("data", handle)?;
+        let body = json!({ "options": { "cas": cas }, "data": { "value": value } });
+        match self.call("POST", &path, Some(&body), handle) {
+            Ok(body) => Ok(body
+                .pointer("/data/version")
+                .and_then(Value::as_u64)
+                .unwrap_or(cas + 1)),
+            // A check-and-set mismatch: the secret exists (create) or changed meanwhile (rotate).
+            Err(SecretError::Backend(detail)) if detail.contains("check-and-set") => {
+                Err(if cas == 0 {
+                    SecretError::AlreadyExists(handle.0.clone())
+                } else {
+                    SecretError::Backend(format!("{} changed during rotation", handle.0))
+                })
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    fn metadata(&self, handle: &SecretHandle) -> Result<Value, SecretError> {
+        let path = self.kv_path("metadata", handle)?;
+        self.call("GET", &path, None, handle)
+    }
+
+    fn kv_path(&self, kind: &str, handle: &SecretHandle) -> Result<String, SecretError> {
+        let path = handle.0.strip_prefix("secret://").unwrap_or(&handle.0);
+        if path.is_empty()
+            || path
+                .split('/')
+                .any(|segment| segment.is_empty() || segment == "." || segment == "..")
+        {
+            return Err(SecretError::Backend(format!(
+                "secret handle {} is not a valid vault path",
+                handle.0
+            )));
+        }
+        Ok(format!("{}/{kind}/{path}", self.mount))
+    }
+
+    /// Sends an authenticated request and maps Vault's status codes onto `SecretError`.
+    fn call(
+        &self,
+        method: &str,
+        path: &str,
+        body: Option<&Value>,
+        handle: &SecretHandle,
+    ) -> Result<Value, SecretError> {
+        let token = self.valid_token()?.token;
+        let (status, response) = self.transport.send(method, path, Some(&token), body)?;
+        match status {
+            200..=299 => Ok(response),
+            404 => Err(SecretError::NotFound(handle.0.clone())),
+            401 | 403 => Err(SecretError::Unauthorized(format!(
+                "vault denied {method} {path}"
+            ))),
+            _ => Err(SecretError::Backend(format!(
+                "vault {method} {path} returned {status}: {}",
+                vault_errors(&response)
+            ))),
+        }
+    }
+
+    /// The cached token, renewed or re-obtained when it is close to expiring.
+    fn valid_token(&self) -> Result<VaultToken, SecretError> {
+        let mut cached = self.lock()?;
+        let now = now_unix();
+        if let Some(token) = cached.as_ref() {
+            if token.expires_at_unix > now.saturating_add(RENEW_MARGIN_SECS) {
+                return Ok(token.clone());
+            }
+            if token.renewable {
+                if let Some(renewed) = self.renew(token)? {
+                    *cached = Some(renewed.clone());
+                    return Ok(renewed);
+                }
+            }
+        }
+        let token = self.login()?;
+        *cached = Some(token.clone());
+        Ok(token)
+    }
+
+    fn renew(&self, token: &VaultToken) -> Result<Option<VaultToken>, SecretError> {
+        let (status, body) = self.transport.send(
+            "POST",
+            "auth/token/renew-self",
+            Some(&token.token),
+            Some(&json!({})),
+        )?;
+        if !(200..=299).contains(&status) {
+            tracing::warn!(status, "vault token renewal failed; logging in again");
+            return Ok(None);
+        }
+        Ok(Some(auth_token(&body, token.token.as_str())?))
+    }
+
+    fn login(&self) -> Result<VaultToken, SecretError> {
+        match &self.auth {
+            VaultAuth::Token(token) => {
+                let (status, body) =
+                    self.transport
+                        .send("GET", "auth/token/lookup-self", Some(token), None)?;
+                if !(200..=299).contains(&status) {
+                    return Err(SecretError::Unauthorized(format!(
+                        "vault token lookup returned {status}: {}",
+                        vault_errors(&body)
+                    )));
+                }
+                let ttl = body
+                    .pointer("/data/ttl")
+                    .and_then(Value::as_u64)
+                    .unwrap_or(0);
+                Ok(VaultToken {
+                    token: Zeroizing::new(token.clone()),
+                    expires_at_unix: expiry(ttl),
+                    renewable: body
+                        .pointer("/data/renewable")
+                        .and_then(Value::as_bool)
+                        .unwrap_or(false),
+                })
+            }
+            VaultAuth::AppRole {
+                role_id,
+                secret_id,
+                mount,
+            } => {
+                let (status, body) = self.transport.send(
+                    "POST",
+                    &format!("auth/{mount}/login"),
+                    None,
+                    Some(&json!({ "role_id": role_id, "secret_id": secret_id })),
+                )?;
+                if !(200..=299).contains(&status) {
+                    return Err(SecretError::Unauthorized(format!(
+                        "vault approle login returned {status}: {}",
+                        vault_errors(&body)
+                    )));
+                }
+                auth_token(&body, "")
+            }
+        }
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, Option<VaultToken>>, SecretError> {
+        self.token
+            .lock()
+            .map_err(|_| SecretError::Backend("vault token lock poisoned".to_string()))
+    }
+}
+
+impl<T: VaultTransport> SecretStore for VaultSecretStore<T> {
+    fn resolve_secret_handle(
+        &self,
+        handle: &SecretHandle,
+        _ctx: &AccessContext,
+    ) -> Result<SecretRef, SecretError> {
+        self.metadata(handle)?;
+        Ok(SecretRef {
+            handle: handle.clone(),
+        })
+    }
+
+    fn reveal_secret(
+        &self,
+        handle: &SecretHandle,
+        _ctx: &AccessContext,
+    ) -> Result<Zeroizing<Vec<u8>>, SecretError> {
+        self.reveal(handle)
+    }
+}
+
+impl<T: VaultTransport> SessionVault for VaultSecretStore<T> {
+    /// The session lasts as long as the Vault token; a non-renewable token needs re-auth at expiry.
+    fn issue_session_lease(
+        &self,
+        handle: &SessionHandle,
+        _ctx: &AccessContext,
+    ) -> Result<SessionLease, SecretError> {
+        let token = self.valid_token()?;
+        Ok(SessionLease {
+            handle: handle.clone(),
+            expires_at_unix: token.expires_at_unix,
+            reauth_required: !token.renewable && matches!(self.auth, VaultAuth::Token(_)),
+        })
+    }
+}
+
+/// Reads `auth.client_token` (falling back to `current` for renewals that omit it).
+fn auth_token(body: &Value, current: &str) -> Result<VaultToken, SecretError> {
+    let token = body
+        .pointer("/auth/client_token")
+        .and_then(Value::as_str)
+        .unwrap_or(current);
+    if token.is_empty() {
+        return Err(SecretError::Backend(
+            "vault auth response has no client_token".to_string(),
+        ));
+    }
+    let ttl = body
+        .pointer("/auth/lease_duration")
+        .and_then(Value::as_u64)
+        .unwrap_or(0);
+    Ok(VaultToken {
+        token: Zeroizing::new(token.to_string()),
+        expires_at_unix: expiry(ttl),
+        renewable: body
+            .pointer("/auth/renewable")
+            .and_then(Value::as_bool)
+            .unwrap_or(false),
+    })
+}
+
+/// A TTL of zero is a token that never expires.
+fn expiry(ttl: u64) -> u64 {
+    if ttl == 0 {
+        u64::MAX
+    } else {
+        now_unix().saturating_add(ttl)
+    }
+}
+
+fn vault_errors(body: &Value) -> String {
+    body.get("errors")
+        .and_then(Value::as_array)
+        .map(|errors| {
+            errors
+                .iter()
+                .filter_map(Value::as_str)
+                .collect::<Vec<_>>()
+                .join("; ")
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    /// An in-memory KV v2 mount behind AppRole login with short, renewable tokens.
+    #[derive(Default)]
+    struct FakeVault {
+        secrets: Mutex<BTreeMap<String, Vec<String>>>,
+        calls: Mutex<Vec<String>>,
+    }
+
+    impl VaultTransport for &FakeVault {
+        fn send(
+            &self,
+            method: &str,
+            path: &str,
+            token: Option<&str>,
+            body: Option<&Value>,
+        ) -> Result<(u16, Value), SecretError> {
+            self.calls.lock().unwrap().push(format!("{method} {path}"));
+            if path == "auth/approle/login" {
+                let ok = body.and_then(|b| b.get("secret_id")) == Some(&json!("s3cret"));
+                return Ok(if ok {
+                    (
+                        200,
+                        json!({ "auth": { "client_token": "hvs.1", "lease_duration": 30, "renewable": true } }),
+                    )
+                } else {
+                    (400, json!({ "errors": ["invalid secret id"] }))
+                });
+            }
+            if path == "auth/token/renew-self" {
+                return Ok((
+                    200,
+                    json!({ "auth": { "client_token": "hvs.1", "lease_duration": 3600, "renewable": true } }),
+                ));
+            }
+            if token != Some("hvs.1") {
+                return Ok((403, json!({ "errors": ["permission denied"] })));
+            }
+            let mut secrets = self.secrets.lock().unwrap();
+            let (kind, key) = path
+                .strip_prefix("secret/")
+                .and_then(|rest| rest.split_once('/'))
+                .expect("kv path");
+            let versions = secrets.get(key).cloned();
+            Ok(match (method, kind, versions) {
+                ("GET", "metadata", Some(v)) => {
+                    (200, json!({ "data": { "current_version": v.len() } }))
+                }
+                ("GET", "data", Some(v)) => {
+                    (200, json!({ "data": { "data": { "value": v.last() } } }))
+                }
+                ("DELETE", "metadata", Some(_)) => {
+                    secrets.remove(key);
+                    (204, Value::Null)
+                }
+                ("POST", "data", existing) => {
+                    let body = body.expect("body");
+                    let cas = body.pointer("/options/cas").and_then(Value::as_u64);
+                    let current = existing.as_ref().map_or(0, Vec::len) as u64;
+                    if cas != Some(current) {
+                        return Ok((
+                            400,
+                            json!({ "errors": ["check-and-set parameter did not match the current version"] }),
+                        ));
+                    }
+                    let value = body.pointer("/data/value").and_then(Value::as_str).unwrap();
+                    let entry = secrets.entry(key.to_string()).or_default();
+                    entry.push(value.to_string());
+                    (200, json!({ "data": { "version": entry.len() } }))
+                }
+                _ => (404, json!({ "errors": [] })),
+            })
+        }
+    }
+
+    fn approle(secret_id: &str) -> VaultAuth {
+        VaultAuth::AppRole {
+            role_id: "odin".to_string(),
+            secret_id: secret_id.to_string(),
+            mount: "approle".to_string(),
+        }
+    }
+
+    #[test]
+    fn kv_v2_lifecycle_through_approle() {
+        let vault = FakeVault::default();
+        let store = VaultSecretStore::new(&vault, approle("s3cret"));
+        let handle = SecretHandle("secret://demo/github".to_string());
+
+        store.create(&handle, b"ghp_first").expect("create");
+        assert!(matches!(
+            store.create(&handle, b"again"),
+            Err(SecretError::AlreadyExists(_))
+        ));
+        assert_eq!(store.rotate_to(&handle, b"ghp_second").expect("rotate"), 2);
+        assert_eq!(
+            store.reveal(&handle).expect("reveal").as_slice(),
+            b"ghp_second"
+        );
+        store.delete(&handle).expect("delete");
+        assert!(matches!(
+            store.reveal(&handle),
+            Err(SecretError::NotFound(_))
+        ));
+        assert!(store
+            .reveal(&SecretHandle("secret://demo/../admin".to_string()))
+            .is_err());
+
+        // The 30s login lease is inside the renewal margin, so the next call renews it.
+        let lease = store
+            .issue_session_lease(
+                &SessionHandle("session://demo".to_string()),
+                &AccessContext {
+                    plugin: "p".to_string(),
+                    project: "demo".to_string(),
+                    capability: "secret.read".to_string(),
+                    reason: "unit".to_string(),
+                },
+            )
+            .expect("lease");
+        assert!(lease.expires_at_unix > now_unix() + 3000);
+        assert!(!lease.reauth_required);
+        let calls = vault.calls.lock().unwrap();
+        assert_eq!(
+            calls.first().map(String::as_str),
+            Some("POST auth/approle/login")
+        );
+        assert!(calls
+            .iter()
+            .any(|call| call == "POST auth/token/renew-self"));
+    }
+
+    #[test]
+    fn failed_login_is_unauthorized() {
+        let vault = FakeVault::default();
+        let store = VaultSecretStore::new(&vault, approle("wrong"));
+        let err = store
+            .reveal(&SecretHandle("secret://demo/github".to_string()))
+            .expect_err("login fails");
+        assert!(matches!(err, SecretError::Unauthorized(_)));
+        assert!(err.to_string().contains("invalid secret id"));
+    }
+}